@@ -33,10 +33,22 @@ export interface Position {
   core_responsibilities: string;
   tools_systems_skills: string[];
   achievements: string[];
+  industry?: string | null;
   created_at: Date;
   updated_at: Date;
 }
 
+export interface PositionSummary {
+  id?: number;
+  employer_name: string;
+  job_title: string;
+  employment_type: string;
+  location: string;
+  start_date: Date;
+  end_date?: Date;
+  seniority_level: string;
+}
+
 export interface CompensationRecord {
   id?: number;
   position_id: number;
@@ -89,7 +101,7 @@ export interface EarningsAnalysis {
     effective_hourly_rate: number;
   }>;
   hours_vs_earnings: Array<{
-    year: number;
+    label: string;
     total_hours_worked: number;
     total_earnings: number;
     overtime_percentage: number;
@@ -414,6 +426,11 @@ const sampleCompensation: CompensationRecord[] = [
   },
 ];
 
+function toPositionSummary(position: Position): PositionSummary {
+  const { id, employer_name, job_title, employment_type, location, start_date, end_date, seniority_level } = position;
+  return { id, employer_name, job_title, employment_type, location, start_date, end_date, seniority_level };
+}
+
 // Mock Tauri invoke function
 export async function invoke<T>(command: string, args?: any): Promise<T> {
   console.log(`Mock Tauri invoke: ${command}`, args);
@@ -437,6 +454,15 @@ export async function invoke<T>(command: string, args?: any): Promise<T> {
     case 'get_positions':
       return getUserData().positions as T;
 
+    case 'get_position_summaries':
+      return getUserData().positions.map(toPositionSummary) as T;
+
+    case 'get_position': {
+      const position = getUserData().positions.find((p: Position) => p.id === args?.id);
+      if (!position) throw new Error(`Position ${args?.id} not found`);
+      return position as T;
+    }
+
     case 'save_position':
       const currentData = getUserData();
       const posArgs = args as Position;
@@ -467,9 +493,12 @@ export async function invoke<T>(command: string, args?: any): Promise<T> {
     case 'get_all_compensation_records':
       return getUserData().compensation as T;
 
+    case 'check_compensation_record':
+      return [] as T;
+
     case 'save_compensation_record':
       const compCurrent = getUserData();
-      const compArgs = args as CompensationRecord;
+      const compArgs = (args.record ?? args) as CompensationRecord;
       if (compArgs.id) {
         // Update
         setUserData({
@@ -492,6 +521,26 @@ export async function invoke<T>(command: string, args?: any): Promise<T> {
       });
       return undefined as T;
 
+    case 'get_data_quality':
+      // Only the missing-records deduction; the app scores the rest
+      const qualityData = getUserData();
+      const qualityPositions = qualityData.positions.map(p => {
+        const hasRecords = qualityData.compensation.some(c => c.position_id === p.id);
+        const deductions = hasRecords ? [] : [{ reason: 'No compensation records', points: 40 }];
+        return {
+          position_id: p.id,
+          employer_name: p.employer_name,
+          job_title: p.job_title,
+          quality: { score: hasRecords ? 100 : 60, deductions },
+        };
+      }).sort((a, b) => a.quality.score - b.quality.score);
+      return {
+        overall_score: qualityPositions.length
+          ? qualityPositions.reduce((sum, p) => sum + p.quality.score, 0) / qualityPositions.length
+          : 0,
+        positions: qualityPositions,
+      } as T;
+
     // Weekly Entry operations
     case 'get_weekly_entries':
       return getUserData().weeklyEntries as T;
@@ -590,10 +639,13 @@ export async function invoke<T>(command: string, args?: any): Promise<T> {
       });
 
       // Calculate dynamic hours vs earnings
-      const hoursVsEarningsMap = new Map<number, { total_hours: number, total_earnings: number, overtime_hours: number }>();
+      const hoursVsEarningsMap = new Map<string, { total_hours: number, total_earnings: number, overtime_hours: number }>();
 
       userCompData.compensation.forEach((c: any) => {
-        const year = new Date(c.effective_date).getFullYear();
+        // Financial year label, e.g. "2024-25"
+        const effective = new Date(c.effective_date);
+        const fyStart = effective.getMonth() >= 6 ? effective.getFullYear() : effective.getFullYear() - 1;
+        const year = `${fyStart}-${String(fyStart + 1).slice(-2)}`;
         const existing = hoursVsEarningsMap.get(year) || { total_hours: 0, total_earnings: 0, overtime_hours: 0 };
 
         const weeksActive = 52;
@@ -613,12 +665,12 @@ export async function invoke<T>(command: string, args?: any): Promise<T> {
         hoursVsEarningsMap.set(year, existing);
       });
 
-      const hoursVsEarnings = Array.from(hoursVsEarningsMap.entries()).map(([year, data]) => ({
-        year,
+      const hoursVsEarnings = Array.from(hoursVsEarningsMap.entries()).map(([label, data]) => ({
+        label,
         total_hours_worked: Math.round(data.total_hours),
         total_earnings: Math.round(data.total_earnings),
         overtime_percentage: data.total_hours > 0 ? Math.round((data.overtime_hours / data.total_hours) * 100) : 0
-      })).sort((a, b) => a.year - b.year);
+      })).sort((a, b) => a.label.localeCompare(b.label));
 
       // Calculate super trajectory
       const superMap = new Map<string, { employer: number, personal: number, balance: number }>();
@@ -681,7 +733,8 @@ export async function invoke<T>(command: string, args?: any): Promise<T> {
         hours_vs_earnings: hoursVsEarnings,
         super_trajectory: superTrajectory,
         super_summary: superTrajectory[superTrajectory.length - 1] || { financial_year: 'N/A', employer_contributions: 0, personal_contributions: 0, total_super_balance: 0 },
-        insights: insightsArray
+        insights: insightsArray,
+        current_positions: userCompData.positions.filter((p: Position) => !p.end_date).map(toPositionSummary)
       } as T;
 
     case 'calculate_loyalty_tax':
@@ -730,12 +783,12 @@ export async function invoke<T>(command: string, args?: any): Promise<T> {
     case 'export_all_data':
       const exportDataAll = getUserData();
       return {
+        metadata: { export_date: new Date().toISOString() },
         user_profile: exportDataAll.profile,
         positions: exportDataAll.positions,
         compensation_records: exportDataAll.compensation,
         weekly_entries: exportDataAll.weeklyEntries,
         yearly_entries: exportDataAll.yearlyEntries,
-        export_date: new Date().toISOString(),
         version: '1.0.0'
       } as T;
 
@@ -754,7 +807,9 @@ export async function invoke<T>(command: string, args?: any): Promise<T> {
         positions_count: importArgs.positions?.length || 0,
         compensation_count: importArgs.compensation_records?.length || 0,
         weekly_count: importArgs.weekly_entries?.length || 0,
-        yearly_count: importArgs.yearly_entries?.length || 0
+        yearly_count: importArgs.yearly_entries?.length || 0,
+        exchange_rate_count: 0,
+        warnings: []
       } as T;
 
     case 'load_sample_data':
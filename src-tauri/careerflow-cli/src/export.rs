@@ -0,0 +1,48 @@
+use careerflow_core::tables::Table;
+use std::path::{Path, PathBuf};
+
+/// Writes one `<table>.csv` per table into the directory, returning the files written.
+pub fn write_csv(tables: &[Table], dir: &Path) -> Result<Vec<PathBuf>, String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+    let mut written = Vec::new();
+    for table in tables {
+        let path = dir.join(format!("{}.csv", table.name));
+        let mut writer = csv::Writer::from_path(&path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        writer.write_record(&table.headers).map_err(|e| e.to_string())?;
+        for row in &table.rows {
+            writer.write_record(row).map_err(|e| e.to_string())?;
+        }
+        writer.flush().map_err(|e| e.to_string())?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Writes a workbook with one sheet per table.
+pub fn write_xlsx(tables: &[Table], path: &Path) -> Result<(), String> {
+    let mut workbook = rust_xlsxwriter::Workbook::new();
+
+    for table in tables {
+        let sheet = workbook.add_worksheet();
+        sheet.set_name(&table.name).map_err(|e| e.to_string())?;
+
+        for (col, header) in table.headers.iter().enumerate() {
+            sheet.write_string(0, col as u16, header).map_err(|e| e.to_string())?;
+        }
+        for (row, cells) in table.rows.iter().enumerate() {
+            for (col, cell) in cells.iter().enumerate() {
+                // Keep numbers numeric so the sheet can be summed without conversion
+                match cell.parse::<f64>() {
+                    Ok(number) => sheet.write_number(row as u32 + 1, col as u16, number),
+                    Err(_) => sheet.write_string(row as u32 + 1, col as u16, cell),
+                }
+                .map_err(|e| e.to_string())?;
+            }
+        }
+    }
+
+    workbook.save(path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
@@ -0,0 +1,401 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use careerflow_core::{backup, calculations, instance_lock, paths, tables, validation};
+use careerflow_core::dates::FinancialYear;
+use careerflow_core::database::Database;
+use careerflow_core::models::*;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde_json::json;
+
+mod export;
+
+// Exit codes: clap uses 2 for usage errors
+const EXIT_ERROR: u8 = 1;
+const EXIT_INVALID_DATA: u8 = 3;
+
+/// Backup, export and analysis for a CareerFlow database.
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    /// Database file. Defaults to the desktop app's database.
+    #[arg(long, global = true)]
+    db: Option<PathBuf>,
+
+    /// Print machine-readable JSON instead of text.
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Export all data.
+    Export {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+        /// JSON or XLSX file, or a directory for CSV. JSON goes to stdout when omitted.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Import a JSON export.
+    Import {
+        file: PathBuf,
+        /// Validate and count the records without writing anything.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Copy the database to a timestamped file next to it, or to --output.
+    Backup {
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Run an analysis.
+    Analyze {
+        #[command(subcommand)]
+        analysis: Analysis,
+    },
+    /// Check every stored record; exits with 3 when any fail.
+    Validate,
+    /// Record counts and coverage.
+    Stats,
+}
+
+#[derive(Subcommand)]
+enum Analysis {
+    /// Current compensation, timeline and insights.
+    Earnings,
+    /// Earnings forgone by staying with each employer.
+    LoyaltyTax,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Json,
+    Csv,
+    Xlsx,
+}
+
+/// What a command produced: the JSON form for --json and the text form otherwise.
+struct Output {
+    value: serde_json::Value,
+    text: String,
+    exit_code: u8,
+}
+
+impl Output {
+    fn new(value: serde_json::Value, text: impl Into<String>) -> Self {
+        Self { value, text: text.into(), exit_code: 0 }
+    }
+}
+
+// Same location Tauri resolves for the desktop app: <platform data dir>/<bundle identifier>
+struct PlatformAppDataDir;
+
+impl paths::AppDataDir for PlatformAppDataDir {
+    fn app_data_dir(&self) -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join(paths::APP_IDENTIFIER))
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(&cli) {
+        Ok(output) => {
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&output.value).unwrap_or_default());
+            } else if !output.text.is_empty() {
+                println!("{}", output.text);
+            }
+            ExitCode::from(output.exit_code)
+        }
+        Err(e) => {
+            if cli.json {
+                println!("{}", json!({ "error": e }));
+            }
+            eprintln!("error: {}", e);
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+fn run(cli: &Cli) -> Result<Output, String> {
+    let db_path = match &cli.db {
+        Some(path) => path.clone(),
+        None => paths::database_path(&PlatformAppDataDir)?,
+    };
+
+    // Import --dry-run only reads the file
+    if let Command::Import { file, dry_run: true } = &cli.command {
+        return import_dry_run(file);
+    }
+
+    let writes = matches!(cli.command, Command::Import { .. });
+    if !db_path.exists() && !writes {
+        return Err(format!("No database at {}", db_path.display()));
+    }
+    // Held until the command finishes, so the app opens read-only rather than alongside it
+    let lock = instance_lock::acquire(&db_path)?;
+    let db = open(&db_path, writes, lock.is_some())?;
+
+    match &cli.command {
+        Command::Export { format, output } => export(&db, *format, output.as_deref()),
+        Command::Import { file, .. } => import(&db, file),
+        Command::Backup { output } => backup_database(&db, &db_path, output.as_deref()),
+        Command::Analyze { analysis: Analysis::Earnings } => analyze_earnings(&db),
+        Command::Analyze { analysis: Analysis::LoyaltyTax } => analyze_loyalty_tax(&db),
+        Command::Validate => validate(&db),
+        Command::Stats => stats(&db, &db_path),
+    }
+}
+
+// Only an import writes. Everything else reads, and only migrates the schema when it's out of
+// date and no one else has the database open.
+fn open(db_path: &Path, writes: bool, locked: bool) -> Result<Database, String> {
+    let failed = |e: String| format!("Failed to open {}: {}", db_path.display(), e);
+    if writes {
+        if !locked {
+            return Err(format!("{} is open in CareerFlow; close the app before importing", db_path.display()));
+        }
+        return Database::new(db_path.to_path_buf()).map_err(|e| failed(e.to_string()));
+    }
+
+    let db = Database::open_read_only(db_path.to_path_buf()).map_err(|e| failed(e.to_string()))?;
+    let latest = careerflow_core::database::MIGRATIONS.last().map_or(0, |m| m.version);
+    if locked && db.status().schema_version < latest {
+        drop(db);
+        return Database::new(db_path.to_path_buf()).map_err(|e| failed(e.to_string()));
+    }
+    Ok(db)
+}
+
+fn export(db: &Database, format: ExportFormat, output: Option<&Path>) -> Result<Output, String> {
+    let data = backup::export_all_data(db)?;
+
+    let files = match format {
+        ExportFormat::Json => {
+            let text = serde_json::to_string_pretty(&data).map_err(|e| e.to_string())?;
+            match output {
+                Some(path) => {
+                    std::fs::write(path, text)
+                        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+                    vec![path.to_path_buf()]
+                }
+                // The export itself is the machine-readable output
+                None => return Ok(Output::new(serde_json::to_value(&data).map_err(|e| e.to_string())?, text)),
+            }
+        }
+        ExportFormat::Csv => {
+            let dir = output.unwrap_or(Path::new("."));
+            export::write_csv(&tables::export_tables(&data)?, dir)?
+        }
+        ExportFormat::Xlsx => {
+            let path = output.unwrap_or(Path::new("careerflow-export.xlsx"));
+            export::write_xlsx(&tables::export_tables(&data)?, path)?;
+            vec![path.to_path_buf()]
+        }
+    };
+
+    let text = files.iter().map(|f| format!("Wrote {}", f.display())).collect::<Vec<_>>().join("\n");
+    Ok(Output::new(json!({ "files": files }), text))
+}
+
+fn read_export(file: &Path) -> Result<DataExport, String> {
+    let text = std::fs::read_to_string(file)
+        .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+    serde_json::from_str(&text).map_err(|e| format!("{} is not a CareerFlow export: {}", file.display(), e))
+}
+
+fn import_dry_run(file: &Path) -> Result<Output, String> {
+    let data = read_export(file)?;
+    let issues = validation::validate_all(&data, chrono::Local::now().date_naive());
+
+    let counts = json!({
+        "profile": data.user_profile.is_some(),
+        "positions": data.positions.len(),
+        "compensation_records": data.compensation_records.len(),
+        "weekly_entries": data.weekly_entries.len(),
+        "yearly_entries": data.yearly_entries.len(),
+        "exchange_rates": data.exchange_rates.len(),
+    });
+    let mut text = format!(
+        "Would import {} positions, {} compensation records, {} weekly entries, {} yearly entries, {} exchange rates",
+        data.positions.len(), data.compensation_records.len(), data.weekly_entries.len(),
+        data.yearly_entries.len(), data.exchange_rates.len()
+    );
+    text.push_str(&issues_text(&issues));
+
+    let mut output = Output::new(json!({ "dry_run": true, "counts": counts, "issues": issues }), text);
+    if has_errors(&issues) {
+        output.exit_code = EXIT_INVALID_DATA;
+    }
+    Ok(output)
+}
+
+fn import(db: &Database, file: &Path) -> Result<Output, String> {
+    let data = read_export(file)?;
+    let result = backup::import_all_data(db, data, chrono::Local::now().date_naive())?;
+
+    let mut text = format!(
+        "Imported {} positions, {} compensation records, {} weekly entries, {} yearly entries, {} exchange rates",
+        result.positions_count, result.compensation_count, result.weekly_count,
+        result.yearly_count, result.exchange_rate_count
+    );
+    for warning in &result.warnings {
+        text.push_str(&format!("\nWarning: {}", warning));
+    }
+    Ok(Output::new(serde_json::to_value(&result).map_err(|e| e.to_string())?, text))
+}
+
+fn backup_database(db: &Database, db_path: &Path, output: Option<&Path>) -> Result<Output, String> {
+    let path = match output {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+            db_path.with_file_name(format!("careerflow-backup-{}.db", stamp))
+        }
+    };
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()));
+    }
+
+    db.backup_to(&path).map_err(|e| format!("Backup failed: {}", e))?;
+    Ok(Output::new(json!({ "backup": path }), format!("Backed up to {}", path.display())))
+}
+
+fn analyze_earnings(db: &Database) -> Result<Output, String> {
+    // Archived positions are left out, as in the app
+    let calculations::AnalysisData { positions, records, .. } = calculations::analysis_data(
+        &db.get_positions()?,
+        db.get_all_compensation_records()?,
+        db.get_weekly_entries()?,
+        &[],
+        &[],
+        false,
+    );
+    let profile = db.get_user_profile()?;
+    let exchange_rates = db.get_exchange_rates()?;
+    let today = chrono::Local::now().date_naive();
+    let mut analysis = calculations::calculate_earnings_analysis(&positions, &records, &profile, &exchange_rates, today);
+    let industry = profile.as_ref().map(|p| p.industry.as_str()).unwrap_or("Unknown");
+    let wage_index = db.get_setting::<WageIndex>(calculations::WAGE_INDEX_SETTINGS_KEY)?;
+    analysis.percentile_over_time =
+        calculations::percentile_over_time(&analysis.earnings_over_time, industry, &wage_index, today)?;
+    analysis.insights.extend(calculations::percentile_decline_insight(&analysis.percentile_over_time));
+
+    let mut text = format!(
+        "Current total compensation: ${:.0}\nEffective hourly rate: ${:.2}/hr\nIncome percentile: {:.0}",
+        analysis.current_total_compensation, analysis.current_effective_hourly_rate, analysis.income_percentile
+    );
+    for insight in &analysis.insights {
+        text.push_str(&format!("\n- {}: {}", insight.title, insight.description));
+    }
+
+    Ok(Output::new(serde_json::to_value(&analysis).map_err(|e| e.to_string())?, text))
+}
+
+fn analyze_loyalty_tax(db: &Database) -> Result<Output, String> {
+    let positions = calculations::analysed_positions(&db.get_positions()?, false);
+    let analysis = calculations::calculate_loyalty_tax(&positions, chrono::Local::now().date_naive());
+
+    let mut text = format!("Cumulative loyalty tax: ${:.0}", analysis.cumulative_loyalty_tax);
+    for block in &analysis.tenure_blocks {
+        text.push_str(&format!(
+            "\n- {}: {:.1} years, ${:.0}",
+            block.employer_name, block.years_of_service, block.loyalty_tax_impact
+        ));
+    }
+
+    Ok(Output::new(serde_json::to_value(&analysis).map_err(|e| e.to_string())?, text))
+}
+
+fn validate(db: &Database) -> Result<Output, String> {
+    let data = backup::export_all_data(db)?;
+    let issues = validation::validate_all(&data, chrono::Local::now().date_naive());
+
+    let text = if issues.is_empty() {
+        "All records are valid".to_string()
+    } else {
+        issues_text(&issues).trim_start().to_string()
+    };
+
+    let mut output = Output::new(json!({ "valid": !has_errors(&issues), "issues": issues }), text);
+    if has_errors(&issues) {
+        output.exit_code = EXIT_INVALID_DATA;
+    }
+    Ok(output)
+}
+
+fn stats(db: &Database, db_path: &Path) -> Result<Output, String> {
+    let data = backup::export_all_data(db)?;
+
+    let mut financial_years: Vec<String> = data.weekly_entries.iter()
+        .map(|e| FinancialYear::containing(e.week_ending))
+        .chain(data.yearly_entries.iter().filter_map(|e| FinancialYear::parse(&e.financial_year).ok()))
+        .map(|fy| fy.label())
+        .collect();
+    financial_years.sort();
+    financial_years.dedup();
+
+    let career_start = data.positions.iter().map(|p| p.start_date).min();
+    let quality = calculations::calculate_data_quality_report(
+        &data.positions,
+        &data.compensation_records,
+        &data.weekly_entries,
+        chrono::Local::now().date_naive(),
+    );
+    let size_bytes = std::fs::metadata(db_path).map(|m| m.len()).unwrap_or(0);
+
+    let value = json!({
+        "database": db_path,
+        "size_bytes": size_bytes,
+        "profile": data.user_profile.is_some(),
+        "positions": data.positions.len(),
+        "compensation_records": data.compensation_records.len(),
+        "weekly_entries": data.weekly_entries.len(),
+        "yearly_entries": data.yearly_entries.len(),
+        "exchange_rates": data.exchange_rates.len(),
+        "career_start": career_start,
+        "financial_years": financial_years,
+        "data_quality": quality.overall_score.round(),
+    });
+
+    let text = format!(
+        "Database: {} ({} KB)\nPositions: {}\nCompensation records: {}\nWeekly entries: {}\nYearly entries: {}\nExchange rates: {}\nCareer start: {}\nFinancial years: {}\nData quality: {:.0}/100",
+        db_path.display(),
+        size_bytes / 1024,
+        data.positions.len(),
+        data.compensation_records.len(),
+        data.weekly_entries.len(),
+        data.yearly_entries.len(),
+        data.exchange_rates.len(),
+        career_start.map(|d| d.to_string()).unwrap_or_else(|| "-".to_string()),
+        if financial_years.is_empty() { "-".to_string() } else { financial_years.join(", ") },
+        quality.overall_score,
+    );
+
+    Ok(Output::new(value, text))
+}
+
+fn issues_text(issues: &[ValidationIssue]) -> String {
+    issues.iter()
+        .map(|issue| {
+            let prefix = match issue.severity {
+                IssueSeverity::Error => "",
+                IssueSeverity::Warning => "warning: ",
+            };
+            match issue.id {
+                Some(id) => format!("\n{}{} {}: {}", prefix, issue.record_type, id, issue.message),
+                None => format!("\n{}{}: {}", prefix, issue.record_type, issue.message),
+            }
+        })
+        .collect()
+}
+
+// Warnings are reported but don't make the data invalid
+fn has_errors(issues: &[ValidationIssue]) -> bool {
+    issues.iter().any(|issue| issue.severity == IssueSeverity::Error)
+}
@@ -0,0 +1,180 @@
+use std::path::Path;
+use std::process::{Command, Output};
+
+use careerflow_core::models::*;
+use careerflow_core::Database;
+use chrono::{NaiveDate, Utc};
+use serde_json::{json, Value};
+
+fn careerflow(db: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_careerflow-cli"))
+        .arg("--db")
+        .arg(db)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout_json(output: &Output) -> Value {
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+/// A database with one position, its salary record and a payslip.
+fn seeded_db(dir: &Path) -> std::path::PathBuf {
+    let path = dir.join("careerflow.db");
+    let db = Database::new(path.clone()).unwrap();
+
+    let position: Position = serde_json::from_value(json!({
+        "id": null, "employer_name": "Acme", "job_title": "Planner", "employment_type": "Permanent",
+        "location": "Perth WA", "start_date": "2021-02-01", "end_date": null, "seniority_level": "Mid",
+        "core_responsibilities": "Planning", "tools_systems_skills": ["SAP"], "achievements": [],
+        "created_at": Utc::now(), "updated_at": Utc::now(),
+    })).unwrap();
+    let position_id = db.save_position(position).unwrap();
+
+    db.save_compensation_record(compensation_record(position_id, "AUD")).unwrap();
+
+    let entry: WeeklyCompensationEntry = serde_json::from_value(json!({
+        "id": null, "position_id": position_id, "financial_year": "FY2024-25", "week_ending": "2024-07-07",
+        "gross_pay": 2000.0, "tax_withheld": 500.0, "net_pay": 1500.0, "hours_ordinary": 38.0,
+        "hours_overtime": 0.0, "overtime_rate_multiplier": 1.5, "allowances": [], "super_contributed": 230.0,
+        "notes": null, "created_at": Utc::now(),
+    })).unwrap();
+    db.save_weekly_entry(entry).unwrap();
+
+    path
+}
+
+fn compensation_record(position_id: i64, currency: &str) -> CompensationRecord {
+    serde_json::from_value(json!({
+        "id": null, "position_id": position_id, "entry_type": "Exact", "pay_type": "Salary", "base_rate": 104000.0,
+        "standard_weekly_hours": 38.0,
+        "overtime": { "frequency": "None", "rate_multiplier": 1.5, "average_hours_per_week": 0.0, "annual_hours": null },
+        "allowances": [], "bonuses": [],
+        "super_contributions": { "contribution_rate": 11.5, "additional_contributions": 0.0, "salary_sacrifice": 0.0 },
+        "tax_withheld": null, "payslip_frequency": "Fortnightly",
+        "effective_date": NaiveDate::from_ymd_opt(2024, 7, 1).unwrap(), "confidence_score": 100.0,
+        "currency": currency, "notes": null, "created_at": Utc::now(),
+    })).unwrap()
+}
+
+#[test]
+fn stats_reports_record_counts() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = seeded_db(dir.path());
+
+    let output = careerflow(&db, &["stats", "--json"]);
+
+    assert!(output.status.success());
+    let stats = stdout_json(&output);
+    assert_eq!(stats["positions"], 1);
+    assert_eq!(stats["compensation_records"], 1);
+    assert_eq!(stats["weekly_entries"], 1);
+    assert_eq!(stats["financial_years"], json!(["FY2024-25"]));
+}
+
+#[test]
+fn exported_json_passes_import_dry_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = seeded_db(dir.path());
+    let file = dir.path().join("export.json");
+
+    let export = careerflow(&db, &["export", "--output", file.to_str().unwrap()]);
+    assert!(export.status.success());
+
+    let dry_run = careerflow(&db, &["import", file.to_str().unwrap(), "--dry-run", "--json"]);
+    assert!(dry_run.status.success());
+    let result = stdout_json(&dry_run);
+    assert_eq!(result["counts"]["positions"], 1);
+    assert_eq!(result["issues"], json!([]));
+}
+
+#[test]
+fn csv_and_xlsx_exports_write_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = seeded_db(dir.path());
+    let csv_dir = dir.path().join("csv");
+    let xlsx = dir.path().join("export.xlsx");
+
+    assert!(careerflow(&db, &["export", "--format", "csv", "-o", csv_dir.to_str().unwrap()]).status.success());
+    assert!(careerflow(&db, &["export", "--format", "xlsx", "-o", xlsx.to_str().unwrap()]).status.success());
+
+    let positions = std::fs::read_to_string(csv_dir.join("positions.csv")).unwrap();
+    assert!(positions.lines().next().unwrap().contains("employer_name"));
+    assert!(positions.contains("Acme"));
+    assert!(std::fs::metadata(xlsx).unwrap().len() > 0);
+}
+
+#[test]
+fn validate_exits_with_invalid_data_code() {
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = seeded_db(dir.path());
+    {
+        let db = Database::new(db_path.clone()).unwrap();
+        let position_id = db.get_positions().unwrap()[0].id.unwrap();
+        db.save_compensation_record(compensation_record(position_id, "XYZ")).unwrap();
+    }
+
+    let output = careerflow(&db_path, &["validate", "--json"]);
+
+    assert_eq!(output.status.code(), Some(3));
+    let result = stdout_json(&output);
+    assert_eq!(result["valid"], false);
+    assert_eq!(result["issues"][0]["record_type"], "compensation_record");
+}
+
+#[test]
+fn backup_copies_the_database() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = seeded_db(dir.path());
+    let copy = dir.path().join("copy.db");
+
+    assert!(careerflow(&db, &["backup", "-o", copy.to_str().unwrap()]).status.success());
+
+    let restored = Database::new(copy).unwrap();
+    assert_eq!(restored.get_positions().unwrap().len(), 1);
+}
+
+#[test]
+fn analyze_earnings_outputs_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = seeded_db(dir.path());
+
+    let output = careerflow(&db, &["analyze", "earnings", "--json"]);
+
+    assert!(output.status.success());
+    assert!(stdout_json(&output)["earnings_over_time"].is_array());
+}
+
+#[test]
+fn missing_database_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = careerflow(&dir.path().join("missing.db"), &["stats"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No database"));
+}
+
+#[test]
+fn while_the_app_holds_the_database_only_reads_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = seeded_db(dir.path());
+    let file = dir.path().join("export.json");
+    assert!(careerflow(&db, &["export", "-o", file.to_str().unwrap()]).status.success());
+
+    // The test process stands in for the running app
+    let lock = careerflow_core::instance_lock::acquire(&db).unwrap().unwrap();
+    let stats = careerflow(&db, &["stats", "--json"]);
+    assert!(stats.status.success());
+    assert_eq!(stdout_json(&stats)["positions"], 1);
+
+    let import = careerflow(&db, &["import", file.to_str().unwrap()]);
+    assert_eq!(import.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&import.stderr).contains("close the app"));
+    drop(lock);
+
+    // The CLI gives the lock back when it's done
+    assert!(careerflow(&db, &["import", file.to_str().unwrap()]).status.success());
+    assert!(!careerflow_core::instance_lock::lock_path(&db).exists());
+}
@@ -0,0 +1,148 @@
+use crate::dates;
+use crate::models::*;
+use chrono::{NaiveDate, Utc};
+use std::collections::HashMap;
+
+// Upper bound (exclusive) of each age band
+const AGE_BANDS: &[(i32, &str)] = &[
+    (25, "Under 25"),
+    (35, "25-34"),
+    (45, "35-44"),
+    (55, "45-54"),
+    (65, "55-64"),
+];
+const OLDEST_AGE_BAND: &str = "65+";
+
+// Place names that identify a state when the location has no abbreviation. Cities come
+// before state names so "Victoria Park, Perth" is WA.
+const STATE_PLACES: &[(&str, AustralianState)] = &[
+    ("sydney", AustralianState::NSW),
+    ("newcastle", AustralianState::NSW),
+    ("melbourne", AustralianState::VIC),
+    ("brisbane", AustralianState::QLD),
+    ("gold coast", AustralianState::QLD),
+    ("perth", AustralianState::WA),
+    ("pilbara", AustralianState::WA),
+    ("adelaide", AustralianState::SA),
+    ("hobart", AustralianState::TAS),
+    ("canberra", AustralianState::ACT),
+    ("darwin", AustralianState::NT),
+    ("new south wales", AustralianState::NSW),
+    ("victoria", AustralianState::VIC),
+    ("queensland", AustralianState::QLD),
+    ("western australia", AustralianState::WA),
+    ("south australia", AustralianState::SA),
+    ("tasmania", AustralianState::TAS),
+    ("australian capital territory", AustralianState::ACT),
+    ("northern territory", AustralianState::NT),
+];
+
+/// Strips identity from an export: names and date of birth go (the age is kept as a band),
+/// employers become industry tokens, locations are coarsened to the state, and free-text
+/// fields are dropped. Pay amounts, dates and structure are kept; a partner's income is not.
+///
+/// Refuses unless the profile exists and the user has acknowledged the privacy notice.
+pub fn anonymize(data: &DataExport, today: NaiveDate) -> Result<AnonymizedExport, String> {
+    let profile = data.user_profile.as_ref()
+        .ok_or_else(|| "Create a profile before sharing data".to_string())?;
+    if !profile.career_preferences.privacy_acknowledged {
+        return Err("Acknowledge the privacy notice before sharing data".to_string());
+    }
+
+    let industry = if profile.industry.trim().is_empty() { "Unspecified" } else { profile.industry.trim() };
+
+    // Numbered by first appearance in the career, so tokens don't follow the alphabet
+    let mut positions: Vec<&Position> = data.positions.iter().collect();
+    positions.sort_by_key(|p| p.start_date);
+    let mut employer_tokens: HashMap<String, String> = HashMap::new();
+    let positions = positions.into_iter()
+        .map(|position| {
+            let count = employer_tokens.len();
+            let employer = employer_tokens
+                .entry(position.employer_name.trim().to_lowercase())
+                .or_insert_with(|| format!("{} employer {}", industry, count + 1))
+                .clone();
+
+            AnonymizedPosition {
+                id: position.id,
+                employer,
+                job_title: position.job_title.clone(),
+                employment_type: position.employment_type.clone(),
+                state: state_from_location(&position.location),
+                start_date: position.start_date,
+                end_date: position.end_date,
+                seniority_level: position.seniority_level.clone(),
+                tools_systems_skills: position.tools_systems_skills.clone(),
+                archived: position.archived,
+            }
+        })
+        .collect();
+
+    Ok(AnonymizedExport {
+        profile: AnonymizedProfile {
+            age_band: age_band(dates::age_on(profile.date_of_birth, today)).to_string(),
+            state: profile.state,
+            industry: industry.to_string(),
+            highest_qualification: profile.highest_qualification.clone(),
+            standard_weekly_hours: profile.standard_weekly_hours,
+        },
+        positions,
+        compensation_records: data.compensation_records.iter()
+            .map(|r| CompensationRecord { notes: None, ..r.clone() })
+            .collect(),
+        weekly_entries: data.weekly_entries.iter()
+            .map(|e| WeeklyCompensationEntry { notes: None, ..e.clone() })
+            .collect(),
+        yearly_entries: data.yearly_entries.iter()
+            .map(|e| YearlyIncomeEntry { notes: None, ..e.clone() })
+            .collect(),
+        export_date: data.metadata.as_ref().map_or_else(Utc::now, |m| m.export_date),
+        version: data.version.clone(),
+    })
+}
+
+pub fn age_band(age: i32) -> &'static str {
+    AGE_BANDS.iter()
+        .find(|(upper, _)| age < *upper)
+        .map(|(_, band)| *band)
+        .unwrap_or(OLDEST_AGE_BAND)
+}
+
+/// An age inside the band, for rebuilding a profile from an anonymised export.
+pub fn representative_age(band: &str) -> i32 {
+    let mut lower = 18;
+    for (upper, name) in AGE_BANDS {
+        if *name == band {
+            return lower;
+        }
+        lower = *upper;
+    }
+    lower
+}
+
+/// The state named in a free-text location, by abbreviation ("Perth WA") or place name.
+pub fn state_from_location(location: &str) -> Option<AustralianState> {
+    let words: Vec<String> = location
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_uppercase)
+        .collect();
+    let abbreviations = [
+        ("NSW", AustralianState::NSW),
+        ("VIC", AustralianState::VIC),
+        ("QLD", AustralianState::QLD),
+        ("WA", AustralianState::WA),
+        ("SA", AustralianState::SA),
+        ("TAS", AustralianState::TAS),
+        ("ACT", AustralianState::ACT),
+        ("NT", AustralianState::NT),
+    ];
+    if let Some((_, state)) = abbreviations.into_iter().find(|(code, _)| words.iter().any(|w| w == code)) {
+        return Some(state);
+    }
+
+    let lower = location.to_lowercase();
+    STATE_PLACES.iter()
+        .find(|(place, _)| lower.contains(place))
+        .map(|(_, state)| *state)
+}
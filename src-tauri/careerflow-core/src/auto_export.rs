@@ -0,0 +1,112 @@
+use crate::backup;
+use crate::database::Database;
+use crate::models::*;
+use chrono::{DateTime, Duration, Local, Utc};
+use std::path::{Path, PathBuf};
+
+pub const SETTINGS_KEY: &str = "auto_export";
+pub const STATUS_KEY: &str = "auto_export_status";
+
+const FILE_PREFIX: &str = "careerflow-export-";
+const FILE_EXTENSION: &str = ".json";
+
+/// Whether an export should run now. Failed attempts are retried on the next check rather
+/// than waiting for the following period.
+pub fn is_due(settings: &AutoExportSettings, status: &AutoExportStatus, now: DateTime<Utc>) -> bool {
+    if !settings.enabled || settings.path.as_deref().unwrap_or("").trim().is_empty() {
+        return false;
+    }
+
+    match status.last_success {
+        None => true,
+        Some(last) => match settings.frequency {
+            // Once per local calendar day
+            AutoExportFrequency::Daily => last.with_timezone(&Local).date_naive() < now.with_timezone(&Local).date_naive(),
+            AutoExportFrequency::Weekly => now - last >= Duration::days(7),
+        },
+    }
+}
+
+/// Writes `careerflow-export-YYYYMMDD.json` into the folder and prunes older exports
+/// beyond the retention count. The file is written under a temporary name and renamed so
+/// a sync client never picks up a half-written export.
+pub fn run_export(db: &Database, folder: &Path, retention_count: u32, now: DateTime<Utc>) -> Result<PathBuf, String> {
+    if !folder.is_dir() {
+        return Err(format!("Export folder {} does not exist", folder.display()));
+    }
+
+    let data = backup::export_all_data(db)?;
+    let json = serde_json::to_string_pretty(&data).map_err(|e| e.to_string())?;
+
+    let file_name = format!("{}{}{}", FILE_PREFIX, now.with_timezone(&Local).format("%Y%m%d"), FILE_EXTENSION);
+    let path = folder.join(&file_name);
+    let temp_path = folder.join(format!(".{}.tmp", file_name));
+
+    if let Err(e) = std::fs::write(&temp_path, json) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(format!("Failed to write {}: {}", temp_path.display(), e));
+    }
+    if let Err(e) = std::fs::rename(&temp_path, &path) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(format!("Failed to move export into place at {}: {}", path.display(), e));
+    }
+
+    prune_exports(folder, retention_count)?;
+    Ok(path)
+}
+
+/// Deletes the oldest auto-exports so at most `retention_count` remain. Other files in the
+/// folder are never touched.
+pub fn prune_exports(folder: &Path, retention_count: u32) -> Result<(), String> {
+    if retention_count == 0 {
+        return Ok(());
+    }
+
+    let entries = std::fs::read_dir(folder)
+        .map_err(|e| format!("Failed to read {}: {}", folder.display(), e))?;
+
+    // The date stamp sorts chronologically, so names sort oldest first
+    let mut exports: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.starts_with(FILE_PREFIX) && name.ends_with(FILE_EXTENSION))
+                .unwrap_or(false)
+        })
+        .collect();
+    exports.sort();
+
+    let excess = exports.len().saturating_sub(retention_count as usize);
+    for path in exports.iter().take(excess) {
+        std::fs::remove_file(path)
+            .map_err(|e| format!("Failed to remove old export {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Runs the export if it's due and records the outcome in the status setting.
+///
+/// Returns the updated status when an export was attempted, None when nothing was due.
+pub fn run_if_due(db: &Database, now: DateTime<Utc>) -> Result<Option<AutoExportStatus>, String> {
+    let settings: AutoExportSettings = db.get_setting(SETTINGS_KEY)?;
+    let mut status: AutoExportStatus = db.get_setting(STATUS_KEY)?;
+
+    if !is_due(&settings, &status, now) {
+        return Ok(None);
+    }
+
+    let folder = PathBuf::from(settings.path.unwrap_or_default());
+    status.last_attempt = Some(now);
+    match run_export(db, &folder, settings.retention_count, now) {
+        Ok(path) => {
+            status.last_success = Some(now);
+            status.last_file = Some(path.to_string_lossy().into_owned());
+            status.last_error = None;
+        }
+        Err(e) => status.last_error = Some(e),
+    }
+
+    db.save_setting(STATUS_KEY, &status).map_err(|e| e.to_string())?;
+    Ok(Some(status))
+}
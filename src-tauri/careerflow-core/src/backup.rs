@@ -0,0 +1,310 @@
+use crate::database::Database;
+use crate::models::*;
+use crate::validation;
+use chrono::NaiveDate;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Everything in the database as a single document, for backup.
+///
+/// Two exports of the same data serialize identically apart from `metadata`, so exports kept
+/// in version control diff cleanly: collections are in a fixed order (positions by start date,
+/// records by position then effective date, entries by date, rates by currency then year, ties
+/// by id), money is rounded to cents, and the checksum covers everything but the metadata.
+pub fn export_all_data(db: &Database) -> Result<DataExport, String> {
+    let user_profile = db.get_user_profile()?;
+    // A backup that quietly left out unreadable rows would lose them for good
+    let mut positions = complete(db.list_positions()?)?;
+    let mut compensation_records = complete(db.list_compensation_records()?)?;
+    let mut weekly_entries = complete(db.list_weekly_entries()?)?;
+    let mut yearly_entries = complete(db.list_yearly_entries()?)?;
+    let mut exchange_rates = db.get_exchange_rates()?;
+    let mut award_rates = db.get_award_rates()?;
+    let mut scenarios = db.get_scenarios()?;
+    let mut templates = db.get_templates()?;
+    let mut insurance_policies = db.get_insurance_policies()?;
+    let mut partner_income_entries = db.get_partner_income_entries()?;
+    let mut tags = db.get_tags()?;
+    let mut entity_tags = db.get_entity_tags()?;
+
+    positions.sort_by_key(|p| (p.start_date, p.id));
+    compensation_records.sort_by_key(|r| (r.position_id, r.effective_date, r.id));
+    weekly_entries.sort_by_key(|e| (e.week_ending, e.id));
+    yearly_entries.sort_by(|a, b| a.financial_year.cmp(&b.financial_year).then(a.id.cmp(&b.id)));
+    exchange_rates.sort_by(|a, b| (&a.currency, &a.financial_year).cmp(&(&b.currency, &b.financial_year)));
+    award_rates.sort_by(|a, b| {
+        (&a.award_name, &a.classification, a.effective_date, a.id)
+            .cmp(&(&b.award_name, &b.classification, b.effective_date, b.id))
+    });
+    scenarios.sort_by_key(|s| (s.created_at, s.id));
+    templates.sort_by_key(|t| (t.created_at, t.id));
+    insurance_policies.sort_by_key(|p| (p.start_date, p.id));
+    partner_income_entries.sort_by(|a, b| a.financial_year.cmp(&b.financial_year).then(a.id.cmp(&b.id)));
+    tags.sort_by_key(|t| t.id);
+    entity_tags.sort_by_key(|t| (t.tag_id, t.entity_type as u8, t.entity_id));
+
+    for record in &mut compensation_records {
+        record.base_rate = cents(record.base_rate);
+        record.tax_withheld = record.tax_withheld.map(cents);
+        record.super_contributions.additional_contributions = cents(record.super_contributions.additional_contributions);
+        record.super_contributions.salary_sacrifice = cents(record.super_contributions.salary_sacrifice);
+        round_allowances(&mut record.allowances);
+        for bonus in &mut record.bonuses {
+            bonus.amount = cents(bonus.amount);
+        }
+    }
+    for entry in &mut weekly_entries {
+        entry.gross_pay = cents(entry.gross_pay);
+        entry.tax_withheld = cents(entry.tax_withheld);
+        entry.net_pay = cents(entry.net_pay);
+        entry.super_contributed = cents(entry.super_contributed);
+        round_allowances(&mut entry.allowances);
+    }
+    for entry in &mut yearly_entries {
+        entry.gross_income = cents(entry.gross_income);
+        entry.tax_withheld = cents(entry.tax_withheld);
+        entry.reportable_super = cents(entry.reportable_super);
+        entry.reportable_fringe_benefits = entry.reportable_fringe_benefits.map(cents);
+        round_allowances(&mut entry.allowances);
+    }
+    for rate in &mut award_rates {
+        rate.hourly_minimum = cents(rate.hourly_minimum);
+    }
+    for policy in &mut insurance_policies {
+        policy.annual_premium = cents(policy.annual_premium);
+    }
+    for entry in &mut partner_income_entries {
+        entry.gross_income = cents(entry.gross_income);
+    }
+
+    let mut export = DataExport {
+        metadata: Some(ExportMetadata { export_date: chrono::Utc::now() }),
+        user_profile,
+        positions,
+        compensation_records,
+        weekly_entries,
+        yearly_entries,
+        exchange_rates,
+        award_rates,
+        scenarios,
+        templates,
+        insurance_policies,
+        partner_income_entries,
+        tags,
+        entity_tags,
+        version: "1.0.0".to_string(),
+        checksum: None,
+    };
+    export.checksum = Some(checksum(&export)?);
+    Ok(export)
+}
+
+/// SHA-256, as lowercase hex, of the export serialized without its metadata or checksum.
+pub fn checksum(data: &DataExport) -> Result<String, String> {
+    let payload = DataExport { metadata: None, checksum: None, ..data.clone() };
+    let bytes = serde_json::to_vec(&payload).map_err(|e| e.to_string())?;
+    Ok(Sha256::digest(&bytes).iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+// Every row of a list, or an error naming the ones that couldn't be read
+fn complete<T>(list: ListResult<T>) -> Result<Vec<T>, String> {
+    match list.errors.as_slice() {
+        [] => Ok(list.rows),
+        errors => Err(format!(
+            "Some rows can't be read and would be left out of the export: {}",
+            errors.iter()
+                .map(|e| format!("{} row {} ({})", e.table_name, e.row_id, e.message))
+                .collect::<Vec<_>>()
+                .join("; ")
+        )),
+    }
+}
+
+// Rounded to the cent, without a negative zero
+fn cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0 + 0.0
+}
+
+fn round_allowances(allowances: &mut [Allowance]) {
+    for allowance in allowances {
+        allowance.amount = cents(allowance.amount);
+    }
+}
+
+// Saves an exported row under its own id, which rows in other tables may refer to it by.
+// Saving with an id only updates, so a row this database doesn't have yet (restoring into a
+// fresh install) is inserted and then given its id back.
+fn restore<T>(
+    db: &Database,
+    table: &str,
+    mut row: T,
+    id: fn(&mut T) -> &mut Option<i64>,
+    save: fn(&Database, T) -> rusqlite::Result<i64>,
+) -> Result<(), String> {
+    let exported_id = *id(&mut row);
+    let missing = match exported_id {
+        Some(exported) => !db.row_exists(table, exported)?,
+        None => false,
+    };
+    if missing {
+        *id(&mut row) = None;
+    }
+    let saved = save(db, row).map_err(|e| e.to_string())?;
+    if let (true, Some(exported)) = (missing, exported_id) {
+        db.renumber_row(table, saved, exported).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Saves every record in an export, validating each before it's written. `today` is the
+/// date the profile's age is checked on. It's all or nothing: a record that fails leaves the
+/// database as it was.
+///
+/// An export whose checksum doesn't match its contents is still imported, with a warning;
+/// exports from before checksums were added have none to check.
+pub fn import_all_data(db: &Database, data: DataExport, today: NaiveDate) -> Result<ImportResult, String> {
+    let mut warnings = Vec::new();
+    if let Some(expected) = &data.checksum {
+        if checksum(&data)? != *expected {
+            warnings.push("The export's checksum doesn't match its contents, so it has been edited or damaged since it was exported".to_string());
+        }
+    }
+
+    let mut result = db.in_transaction(|db| import_records(db, data, today))?;
+    result.warnings = warnings;
+    Ok(result)
+}
+
+fn import_records(db: &Database, data: DataExport, today: NaiveDate) -> Result<ImportResult, String> {
+    let mut profile_imported = false;
+    let mut positions_count = 0;
+    let mut compensation_count = 0;
+    let mut weekly_count = 0;
+    let mut yearly_count = 0;
+    let mut exchange_rate_count = 0;
+    let mut award_rate_count = 0;
+    let mut scenario_count = 0;
+    let mut template_count = 0;
+    let mut insurance_policy_count = 0;
+    let mut partner_income_count = 0;
+    let mut tag_count = 0;
+    
+    // Import profile
+    if let Some(mut profile) = data.user_profile {
+        validation::validate_user_profile(&profile, today)?;
+        // There's only ever one profile, so an unknown id replaces whichever is here
+        if let Some(id) = profile.id {
+            if !db.row_exists("user_profile", id)? {
+                profile.id = db.get_user_profile()?.and_then(|p| p.id);
+            }
+        }
+        db.save_user_profile(profile).map_err(|e| e.to_string())?;
+        profile_imported = true;
+    }
+    
+    // Import positions
+    for position in data.positions {
+        restore(db, "positions", position, |p| &mut p.id, Database::save_position)?;
+        positions_count += 1;
+    }
+    
+    // Import award rates, which compensation records link to
+    for rate in data.award_rates {
+        validation::validate_award_rate(&rate)?;
+        restore(db, "award_rates", rate, |r| &mut r.id, Database::save_award_rate)?;
+        award_rate_count += 1;
+    }
+    
+    // Import compensation records
+    for record in data.compensation_records {
+        validation::validate_compensation_record(&record)?;
+        restore(db, "compensation_records", record, |r| &mut r.id, Database::save_compensation_record)?;
+        compensation_count += 1;
+    }
+    
+    // Import weekly entries
+    for entry in data.weekly_entries {
+        validation::validate_weekly_entry(&entry)?;
+        restore(db, "weekly_entries", entry, |e| &mut e.id, Database::save_weekly_entry)?;
+        weekly_count += 1;
+    }
+    
+    // Import yearly entries
+    for entry in data.yearly_entries {
+        validation::validate_yearly_entry(&entry)?;
+        restore(db, "yearly_income_entries", entry, |e| &mut e.id, Database::save_yearly_entry)?;
+        yearly_count += 1;
+    }
+    
+    // Import exchange rates
+    for rate in data.exchange_rates {
+        validation::validate_exchange_rate(&rate)?;
+        db.save_exchange_rate(rate).map_err(|e| e.to_string())?;
+        exchange_rate_count += 1;
+    }
+    
+    // Import scenarios, which keep the pay they were compared against
+    for scenario in data.scenarios {
+        validation::validate_compensation_record(&scenario.baseline)?;
+        validation::validate_compensation_record(&scenario.offer)?;
+        restore(db, "scenarios", scenario, |s| &mut s.id, Database::save_scenario)?;
+        scenario_count += 1;
+    }
+    
+    // Import position templates
+    for template in data.templates {
+        restore(db, "position_templates", template, |t| &mut t.id, Database::save_template)?;
+        template_count += 1;
+    }
+    
+    // Import insurance policies
+    for policy in data.insurance_policies {
+        validation::validate_insurance_policy(&policy)?;
+        restore(db, "insurance_policies", policy, |p| &mut p.id, Database::save_insurance_policy)?;
+        insurance_policy_count += 1;
+    }
+    
+    // Import partner income
+    for entry in data.partner_income_entries {
+        validation::validate_partner_income_entry(&entry)?;
+        restore(db, "partner_income_entries", entry, |e| &mut e.id, Database::save_partner_income_entry)?;
+        partner_income_count += 1;
+    }
+    
+    // Import tags; one named the same as a different tag already here is merged into it
+    let mut merged_tags = HashMap::new();
+    for tag in data.tags {
+        let existing = db.get_tags()?;
+        let same_name = existing.iter()
+            .find(|t| t.id != tag.id && t.name.to_lowercase() == tag.name.trim().to_lowercase())
+            .and_then(|t| t.id);
+        if let (Some(exported), Some(merged)) = (tag.id, same_name) {
+            merged_tags.insert(exported, merged);
+        } else {
+            validation::validate_tag(&tag, &existing)?;
+            restore(db, "tags", tag, |t| &mut t.id, Database::save_tag)?;
+        }
+        tag_count += 1;
+    }
+    for entity_tag in data.entity_tags {
+        let tag_id = merged_tags.get(&entity_tag.tag_id).copied().unwrap_or(entity_tag.tag_id);
+        db.assign_tag(&EntityTag { tag_id, ..entity_tag }).map_err(|e| e.to_string())?;
+    }
+    
+    Ok(ImportResult {
+        success: true,
+        profile_imported,
+        positions_count,
+        compensation_count,
+        weekly_count,
+        yearly_count,
+        exchange_rate_count,
+        award_rate_count,
+        scenario_count,
+        template_count,
+        insurance_policy_count,
+        partner_income_count,
+        tag_count,
+        warnings: Vec::new(),
+    })
+}
@@ -0,0 +1,231 @@
+use crate::database::Database;
+use crate::dates;
+use crate::models::*;
+use crate::validation;
+use chrono::{Duration, NaiveDate, Weekday};
+
+/// Moves pay entries from one position (or unlinked entries, when `from_position_id` is None)
+/// to another, optionally only those ending within an inclusive date range. Entries that end
+/// outside the target position's dates are left where they are. The moves are made as one
+/// transaction, with a result per matching entry.
+pub fn reassign_weekly_entries(
+    db: &Database,
+    from_position_id: Option<i64>,
+    to_position_id: i64,
+    date_range: Option<(NaiveDate, NaiveDate)>,
+) -> Result<Vec<BulkEditResult>, String> {
+    check_range(date_range)?;
+    let target = target_position(db, to_position_id)?;
+    let mut entries: Vec<WeeklyCompensationEntry> = db.get_weekly_entries()?
+        .into_iter()
+        .filter(|e| e.position_id == from_position_id && in_range(e.week_ending, date_range))
+        .collect();
+    entries.sort_by_key(|e| e.week_ending);
+
+    db.in_transaction(|db| {
+        let mut results = Vec::new();
+        for mut entry in entries {
+            let id = entry.id.unwrap_or_default();
+            if let Some(error) = outside_position(&target, entry.week_ending) {
+                results.push(skipped(id, error));
+                continue;
+            }
+            entry.position_id = Some(to_position_id);
+            db.save_weekly_entry(entry).map_err(|e| e.to_string())?;
+            results.push(updated(id));
+        }
+        Ok(results)
+    })
+}
+
+/// Moves compensation records from one position to another, optionally only those effective
+/// within an inclusive date range. Records effective outside the target position's dates, or
+/// on a date the target already has a record for, are left where they are.
+pub fn reassign_compensation_records(
+    db: &Database,
+    from_position_id: i64,
+    to_position_id: i64,
+    date_range: Option<(NaiveDate, NaiveDate)>,
+) -> Result<Vec<BulkEditResult>, String> {
+    check_range(date_range)?;
+    if from_position_id == to_position_id {
+        return Err("Records are already on that position".to_string());
+    }
+    let target = target_position(db, to_position_id)?;
+    let mut taken: Vec<NaiveDate> = db.get_compensation_records(to_position_id)?
+        .iter()
+        .map(|r| r.effective_date)
+        .collect();
+    let mut records: Vec<CompensationRecord> = db.get_compensation_records(from_position_id)?
+        .into_iter()
+        .filter(|r| in_range(r.effective_date, date_range))
+        .collect();
+    records.sort_by_key(|r| r.effective_date);
+
+    db.in_transaction(|db| {
+        let mut results = Vec::new();
+        for record in records {
+            let id = record.id.unwrap_or_default();
+            if let Some(error) = outside_position(&target, record.effective_date) {
+                results.push(skipped(id, error));
+                continue;
+            }
+            if taken.contains(&record.effective_date) {
+                results.push(skipped(id, format!("The position already has a record effective {}", record.effective_date)));
+                continue;
+            }
+            taken.push(record.effective_date);
+            db.move_compensation_record(id, to_position_id).map_err(|e| e.to_string())?;
+            results.push(updated(id));
+        }
+        Ok(results)
+    })
+}
+
+/// Applies the patch to each of the given pay entries as one transaction. Ids that don't
+/// exist, or entries the patch would make invalid, are reported and left unchanged.
+pub fn update_weekly_entries(
+    db: &Database,
+    ids: &[i64],
+    patch: &WeeklyEntryPatch,
+) -> Result<Vec<BulkEditResult>, String> {
+    if let Some(multiplier) = patch.overtime_rate_multiplier {
+        if !(1.0..=5.0).contains(&multiplier) {
+            return Err(format!("An overtime multiplier of {} is outside 1x to 5x", multiplier));
+        }
+    }
+    if let Some(rate) = patch.super_rate {
+        if !(0.0..=100.0).contains(&rate) {
+            return Err(format!("A super rate of {}% is outside 0% to 100%", rate));
+        }
+    }
+    let entries = db.get_weekly_entries()?;
+
+    db.in_transaction(|db| {
+        let mut results = Vec::new();
+        for &id in ids {
+            let Some(entry) = entries.iter().find(|e| e.id == Some(id)) else {
+                results.push(skipped(id, format!("Pay entry {} not found", id)));
+                continue;
+            };
+            let entry = apply_patch(entry.clone(), patch);
+            if let Err(error) = validation::validate_weekly_entry(&entry) {
+                results.push(skipped(id, error));
+                continue;
+            }
+            db.save_weekly_entry(entry).map_err(|e| e.to_string())?;
+            results.push(updated(id));
+        }
+        Ok(results)
+    })
+}
+
+/// Moves each of the position's weekly and fortnightly pay entries onto the nearest
+/// `target_weekday`, at most three days either way, shifting the period start with it so the
+/// amounts and period length are unchanged. Unless it's a dry run, the shifts are applied as
+/// one transaction and the day is saved as the position's week-ending day. Errors, changing
+/// nothing, when two entries would end on the same day.
+pub fn normalize_week_endings(
+    db: &Database,
+    position_id: i64,
+    target_weekday: Weekday,
+    dry_run: bool,
+) -> Result<WeekEndingNormalization, String> {
+    let mut position = target_position(db, position_id)?;
+    let mut entries: Vec<WeeklyCompensationEntry> = db.get_weekly_entries()?
+        .into_iter()
+        .filter(|e| e.position_id == Some(position_id) && !matches!(e.period_type, PayslipFrequency::Monthly))
+        .collect();
+    entries.sort_by_key(|e| e.week_ending);
+
+    let mut shifted: Vec<(Option<i64>, NaiveDate)> = Vec::new();
+    let mut shifts = Vec::new();
+    for entry in &entries {
+        let to = dates::nearest_weekday(entry.week_ending, target_weekday);
+        if let Some((other_id, _)) = shifted.iter().find(|(_, date)| *date == to) {
+            return Err(format!(
+                "Pay entries {} and {} would both end on {}; remove the duplicate first",
+                other_id.unwrap_or_default(), entry.id.unwrap_or_default(), to
+            ));
+        }
+        shifted.push((entry.id, to));
+        if to != entry.week_ending {
+            shifts.push(WeekEndingShift {
+                entry_id: entry.id.unwrap_or_default(),
+                from: entry.week_ending,
+                to,
+                days: (to - entry.week_ending).num_days(),
+            });
+        }
+    }
+
+    if !dry_run {
+        db.in_transaction(|db| {
+            for shift in &shifts {
+                let Some(entry) = entries.iter().find(|e| e.id == Some(shift.entry_id)) else {
+                    continue;
+                };
+                let mut entry = entry.clone();
+                let period_start = entry.period_start
+                    .unwrap_or_else(|| dates::period_start_for(entry.week_ending, &entry.period_type));
+                entry.period_start = Some(period_start + Duration::days(shift.days));
+                entry.week_ending = shift.to;
+                db.save_weekly_entry(entry).map_err(|e| e.to_string())?;
+            }
+            position.week_ending_day = Some(target_weekday);
+            db.save_position(position).map_err(|e| e.to_string())?;
+            Ok(())
+        })?;
+    }
+
+    Ok(WeekEndingNormalization { position_id, target_weekday, shifts, applied: !dry_run })
+}
+
+fn apply_patch(mut entry: WeeklyCompensationEntry, patch: &WeeklyEntryPatch) -> WeeklyCompensationEntry {
+    if let Some(multiplier) = patch.overtime_rate_multiplier {
+        entry.overtime_rate_multiplier = multiplier;
+    }
+    if let Some(rate) = patch.super_rate {
+        entry.super_contributed = ordinary_pay(&entry) * rate / 100.0;
+    }
+    entry
+}
+
+// Gross pay less the overtime share of it, with overtime hours weighted by their multiplier
+fn ordinary_pay(entry: &WeeklyCompensationEntry) -> f64 {
+    let weighted_hours = entry.hours_ordinary + entry.hours_overtime * entry.overtime_rate_multiplier;
+    if weighted_hours <= 0.0 {
+        return entry.gross_pay;
+    }
+    entry.gross_pay * entry.hours_ordinary / weighted_hours
+}
+
+fn check_range(date_range: Option<(NaiveDate, NaiveDate)>) -> Result<(), String> {
+    match date_range {
+        Some((from, to)) if from > to => Err(format!("Date range starts on {} after it ends on {}", from, to)),
+        _ => Ok(()),
+    }
+}
+
+fn in_range(date: NaiveDate, date_range: Option<(NaiveDate, NaiveDate)>) -> bool {
+    date_range.is_none_or(|(from, to)| from <= date && date <= to)
+}
+
+fn target_position(db: &Database, id: i64) -> Result<Position, String> {
+    db.get_position(id)?.ok_or_else(|| format!("Position {} not found", id))
+}
+
+fn outside_position(position: &Position, date: NaiveDate) -> Option<String> {
+    let after_end = position.end_date.is_some_and(|end| date > end);
+    (date < position.start_date || after_end).then(|| {
+        format!("{} is outside {} at {}", date, position.job_title, position.employer_name)
+    })
+}
+
+fn updated(id: i64) -> BulkEditResult {
+    BulkEditResult { id, updated: true, error: None }
+}
+
+fn skipped(id: i64, error: String) -> BulkEditResult {
+    BulkEditResult { id, updated: false, error: Some(error) }
+}
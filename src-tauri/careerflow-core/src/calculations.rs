@@ -0,0 +1,5151 @@
+use crate::anonymize;
+use crate::cost_of_living;
+use crate::currency;
+use crate::dates::{self, FinancialYear, YearBasis};
+use crate::error::AppError;
+use crate::models::*;
+use crate::tax;
+use chrono::{DateTime, Days, Months, NaiveDate, Datelike, Utc};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+// Superannuation guarantee rates by financial year start
+const SUPER_RATES: &[(i32, f64)] = &[
+    (2014, 9.5),
+    (2021, 10.0),
+    (2022, 10.5),
+    (2023, 11.0),
+    (2024, 11.5),
+    (2025, 12.0),
+];
+
+// Australian market growth assumptions by industry and role level
+const MARKET_GROWTH_RATES: &[(SeniorityLevel, f64)] = &[
+    (SeniorityLevel::Entry, 0.04),    // 4% annual growth
+    (SeniorityLevel::Junior, 0.05),   // 5% annual growth
+    (SeniorityLevel::Mid, 0.06),      // 6% annual growth
+    (SeniorityLevel::Senior, 0.07),   // 7% annual growth
+    (SeniorityLevel::Lead, 0.08),     // 8% annual growth
+    (SeniorityLevel::Manager, 0.08),  // 8% annual growth
+    (SeniorityLevel::Director, 0.09), // 9% annual growth
+    (SeniorityLevel::Executive, 0.10),// 10% annual growth
+];
+
+// Government super co-contribution thresholds by financial year start:
+// (lower income threshold, higher income threshold)
+const CO_CONTRIBUTION_THRESHOLDS: &[(i32, f64, f64)] = &[
+    (2020, 39837.0, 54837.0),
+    (2021, 41112.0, 56112.0),
+    (2022, 42016.0, 57016.0),
+    (2023, 43445.0, 58445.0),
+    (2024, 45400.0, 60400.0),
+    (2025, 47488.0, 62488.0),
+];
+// Australian CPI, annual change to the June quarter (ABS), by financial year start
+const CPI_ANNUAL_CHANGE: &[(i32, f64)] = &[
+    (2010, 3.5),
+    (2011, 1.2),
+    (2012, 2.4),
+    (2013, 3.0),
+    (2014, 1.5),
+    (2015, 1.0),
+    (2016, 1.9),
+    (2017, 2.1),
+    (2018, 1.6),
+    (2019, -0.3),
+    (2020, 3.8),
+    (2021, 6.1),
+    (2022, 6.0),
+    (2023, 3.8),
+    (2024, 2.1),
+];
+
+// Growth this far below a scorecard benchmark, in percentage points a year, is amber not red
+const AMBER_GRADE_POINTS: f64 = 1.0;
+
+// Typical years spent at a seniority level before moving up, by industry
+const TYPICAL_YEARS_PER_LEVEL: &[(&str, f64)] = &[
+    ("mining", 3.0),
+    ("technology", 2.5),
+    ("it", 2.5),
+    ("engineering", 3.0),
+    ("construction", 3.5),
+    ("healthcare", 4.0),
+    ("education", 4.5),
+    ("finance", 2.5),
+];
+const DEFAULT_YEARS_PER_LEVEL: f64 = 3.0;
+// Time at one level beyond this multiple of the typical pace counts as a stall
+const PROGRESSION_STALL_MULTIPLE: f64 = 2.0;
+
+// Concessional (before-tax) super contributions cap by financial year start
+const CONTRIBUTIONS_TAX_RATE: f64 = 0.15;
+
+// Data quality deductions, in points off 100. Tune them here.
+const NO_RECORDS_PENALTY: f64 = 40.0;
+const LOW_CONFIDENCE_BELOW: f64 = 50.0; // Confidence score, 0-100
+const LOW_CONFIDENCE_PENALTY: f64 = 10.0; // Per record
+const LOW_CONFIDENCE_MAX_PENALTY: f64 = 30.0;
+const MISSING_PAYSLIPS_MAX_PENALTY: f64 = 30.0; // Scaled by the share of time not covered
+const NO_RESPONSIBILITIES_PENALTY: f64 = 5.0;
+const LOW_QUALITY_SCORE: f64 = 70.0; // Below this an insight points at the positions
+
+// Withholding within this share of the expected amount, or this many dollars, is as expected
+const WITHHOLDING_TOLERANCE_SHARE: f64 = 0.10;
+const WITHHOLDING_TOLERANCE_DOLLARS: f64 = 500.0;
+
+// A quarter's super short by no more than this is put down to rounding
+const SUPER_SHORTFALL_TOLERANCE_DOLLARS: f64 = 5.0;
+
+// A payslip short of the award minimum by no more than this is put down to rounding
+const AWARD_SHORTFALL_TOLERANCE_DOLLARS: f64 = 1.0;
+
+// How close a predicted bonus has to be to suggest waiting, unless the caller says otherwise
+const DEFAULT_BONUS_WAIT_WINDOW_DAYS: u32 = 90;
+const AVERAGE_DAYS_PER_MONTH: f64 = 30.44;
+
+// Long service leave thresholds this close are worth an insight
+const LSL_THRESHOLD_WARNING_DAYS: i64 = 365;
+const DAYS_PER_YEAR: f64 = 365.25;
+
+// National Employment Standards annual leave, and notice by years of service when the
+// contract doesn't say
+const ANNUAL_LEAVE_WEEKS_PER_YEAR: f64 = 4.0;
+// A 38-hour week over five days
+const ORDINARY_HOURS_PER_DAY: f64 = 7.6;
+
+// Total weekly hours past which an overtime target is flagged, unless the caller sets one
+pub const DEFAULT_WEEKLY_HOURS_CEILING: f64 = 60.0;
+// Smaller moves in the marginal rate come from offsets phasing in and out, not a new bracket
+const BRACKET_CHANGE_POINTS: f64 = 0.5;
+
+pub const PROJECTION_SETTINGS_KEY: &str = "projection_assumptions";
+const SUPER_PROJECTION_AGE: i32 = 67;
+// Middle of the RBA's 2-3% target, for years without a CPI figure
+const ASSUMED_INFLATION_PERCENT: f64 = 2.5;
+const DEFAULT_SALARY_PROJECTION_YEARS: u32 = 10;
+
+// Income risk points, out of 100 between them. Tune them here.
+const CONCENTRATION_RISK_POINTS: f64 = 35.0; // All income from one employer
+const OVERTIME_RISK_POINTS: f64 = 25.0;
+const VARIABLE_PAY_RISK_POINTS: f64 = 20.0; // Bonuses and commission
+const EMPLOYMENT_TYPE_RISK_POINTS: f64 = 20.0; // All income casual
+const CONTRACT_RISK_SHARE: f64 = 0.6; // Contract income counts for this much of casual's
+const FULL_RISK_INCOME_SHARE: f64 = 0.5; // Overtime or variable pay share that scores in full
+const MODERATE_INCOME_RISK_SCORE: f64 = 35.0;
+const HIGH_INCOME_RISK_SCORE: f64 = 60.0;
+
+// How far a net pay's withholding can be from the annual approximation, either way
+const REVERSE_BASE_NET_TOLERANCE: f64 = 0.03;
+const MINIMUM_NOTICE_WEEKS: &[(f64, f64)] = &[(1.0, 1.0), (3.0, 2.0), (5.0, 3.0)];
+const MINIMUM_NOTICE_WEEKS_AFTER: f64 = 4.0;
+
+// Four days of a five-day week
+const FOUR_DAY_WEEK_FTE: f64 = 0.8;
+
+// Novated lease assumptions: finance at a typical novated lease rate, running costs per km for
+// fuel, tyres and servicing (GST included), and registration and insurance a year
+const NOVATED_LEASE_INTEREST_RATE: f64 = 8.0;
+const RUNNING_COST_PER_KM: f64 = 0.20;
+const FIXED_RUNNING_COSTS: f64 = 3000.0;
+const GST_RATE: f64 = 0.10;
+
+const FIFO_TERMS: &[&str] = &["fifo", "fly-in", "fly in fly out", "dido", "drive-in", "drive in drive out"];
+
+pub const DEMOGRAPHIC_SETTINGS_KEY: &str = "demographic_settings";
+// ABS Average Weekly Earnings, November 2024: full-time adult ordinary time earnings for women
+// and for men against all full-time employees, a gap of about 11.5%. It's the national gap,
+// applied to every industry's median.
+const WOMEN_MEDIAN_RATIO: f64 = 0.926;
+const MEN_MEDIAN_RATIO: f64 = 1.046;
+
+pub const INSURANCE_SETTINGS_KEY: &str = "insurance_settings";
+
+pub const HOUSEHOLD_SETTINGS_KEY: &str = "household";
+
+pub const WAGE_INDEX_SETTINGS_KEY: &str = "wage_index";
+// A fall in percentile this large over this many years, with pay no lower, is flagged
+const PERCENTILE_DECLINE_POINTS: f64 = 10.0;
+const PERCENTILE_DECLINE_YEARS: i32 = 3;
+
+const CO_CONTRIBUTION_MAX: f64 = 500.0;
+const CO_CONTRIBUTION_MATCH_RATE: f64 = 0.5; // 50c per dollar of personal contribution
+
+/// Current compensation, the earnings timeline and insights across all positions.
+///
+/// Amounts are in AUD; New Zealand roles are converted with the exchange-rate table.
+pub fn calculate_earnings_analysis(
+    positions: &[Position],
+    records: &[CompensationRecord],
+    profile: &Option<UserProfile>,
+    exchange_rates: &[ExchangeRate],
+    today: NaiveDate,
+) -> EarningsAnalysis {
+    let mut earnings_over_time = Vec::new();
+    let hours_vs_earnings = Vec::new();
+    let super_trajectory = Vec::new();
+    let mut insights = Vec::new();
+    let mut warnings = Vec::new();
+    let upcoming = upcoming_positions(positions, today);
+    let positions = &started_positions(positions, today);
+
+    // Calculate current compensation
+    let current_position = current_position(positions, today);
+    let (current_total, current_hourly) = if let Some(pos) = current_position {
+        // Get latest compensation for current position
+        calculate_position_earnings(pos, profile)
+    } else {
+        (0.0, 0.0)
+    };
+
+    // Calculate earnings history
+    let mut _total_career_earnings = 0.0;
+    let mut _years_experience = 0.0;
+
+    for position in positions {
+        // Calculate tenure
+        let end_date = position.end_date.unwrap_or_else(|| NaiveDate::from_ymd_opt(2024, 12, 31).unwrap());
+        let tenure_days = (end_date - position.start_date).num_days();
+        let tenure_years = tenure_days as f64 / 365.25;
+        _years_experience += tenure_years;
+
+        // New Zealand roles are valued from their own records, converted to AUD
+        if let Some(record) = latest_record_for(position, records)
+            .filter(|r| r.jurisdiction == Jurisdiction::NZ)
+        {
+            let fy = FinancialYear::containing(record.effective_date);
+            if currency::exchange_rate_for(exchange_rates, &record.currency, &fy).is_none() {
+                warnings.push(AnalysisWarning {
+                    code: AnalysisWarningCode::MissingExchangeRate,
+                    message: format!(
+                        "There's no {} exchange rate for {}, so pay at {} counts as nothing",
+                        record.currency, fy.label(), position.employer_name
+                    ),
+                    related_entity: position.id.map(RelatedEntity::Position),
+                });
+            }
+            let snapshot = nz_earnings_snapshot(position, record, exchange_rates);
+            _total_career_earnings += snapshot.actual_annual;
+            earnings_over_time.push(snapshot);
+            continue;
+        }
+
+        let (annual_earnings, hourly_rate) = calculate_position_earnings(position, profile);
+        _total_career_earnings += annual_earnings;
+        warnings.push(AnalysisWarning {
+            code: AnalysisWarningCode::PayEstimatedFromLevel,
+            message: format!(
+                "Pay at {} is estimated for a {:?} level role rather than taken from its compensation records",
+                position.employer_name, position.seniority_level
+            ),
+            related_entity: position.id.map(RelatedEntity::Position),
+        });
+
+        // Add to earnings timeline
+        earnings_over_time.push(EarningsSnapshot {
+            date: position.start_date,
+            base_annual: position.base_salary_estimate(),
+            actual_annual: annual_earnings,
+            // Super is on the base, not on the overtime in the estimate
+            total_with_super: annual_earnings
+                + position.base_salary_estimate() * super_guarantee_rate(&FinancialYear::containing(position.start_date)) / 100.0,
+            effective_hourly_rate: hourly_rate,
+            jurisdiction: Jurisdiction::AU,
+            col_adjusted_annual: cost_of_living::adjust(annual_earnings, &position.location),
+        });
+    }
+
+    // Generate insights
+    if let Some(profile) = profile {
+        // Overtime analysis
+        if has_overtime_heavy_earnings(positions) {
+            insights.push(EarningsInsight {
+                category: InsightCategory::OvertimeHeavy,
+                title: "Overtime-Heavy Compensation Detected".to_string(),
+                description: "Your earnings are significantly boosted by overtime. Your base rate may appear below market, but actual earnings place you higher.".to_string(),
+                confidence_level: 0.85,
+                position_ids: Vec::new(),
+                data_points: {
+                    let mut points = vec![
+                        format!("Effective hourly rate: ${:.2}/hr", current_hourly),
+                        "Consider roles with better base rates if overtime burnout is a concern".to_string(),
+                    ];
+                    if let Some(record) = current_position.and_then(|pos| latest_record_for(pos, records)) {
+                        let effect = calculate_overtime_rate_effect(record);
+                        if effect.direction != OvertimeEffectDirection::Neutral {
+                            points.push(format!(
+                                "Overtime moves your hourly rate from ${:.2}/hr to ${:.2}/hr",
+                                effect.hourly_rate_without_overtime, effect.hourly_rate_with_overtime
+                            ));
+                        }
+                    }
+                    points
+                },
+            });
+        }
+
+        // Market comparison
+        let percentile = calculate_income_percentile(current_total, &profile.industry, &profile.state);
+        if percentile < 25.0 {
+            insights.push(EarningsInsight {
+                category: InsightCategory::Underpaid,
+                title: "Earnings Below Market Median".to_string(),
+                description: format!("You're in the {:.0}th percentile for your industry and location. Consider negotiating or exploring market opportunities.", percentile),
+                confidence_level: 0.75,
+                position_ids: Vec::new(),
+                data_points: vec![
+                    format!("Current total: ${:.0}", current_total),
+                    format!("Industry median: ${:.0}", calculate_industry_median(&profile.industry)),
+                ],
+            });
+        } else if percentile > 75.0 {
+            insights.push(EarningsInsight {
+                category: InsightCategory::Overpaid,
+                title: "Earnings Above Market".to_string(),
+                description: format!("You're in the {:.0}th percentile for your industry and location.", percentile),
+                confidence_level: 0.75,
+                position_ids: Vec::new(),
+                data_points: vec![
+                    format!("Current total: ${:.0}", current_total),
+                    "You're well compensated compared to peers".to_string(),
+                ],
+            });
+        }
+    }
+
+    // Super co-contribution opportunity on the current role's latest record
+    if let Some(record) = current_position.and_then(|pos| latest_record_for(pos, records)) {
+        let income = record.annual_base() + record.annual_overtime_pay()
+            + record.annual_allowances() + record.annual_bonuses()
+            + record.super_contributions.salary_sacrifice;
+        let fy = FinancialYear::containing(record.effective_date);
+        let personal = record.super_contributions.additional_contributions;
+        let current_entitlement = calculate_co_contribution(income, personal, &fy);
+        let potential_entitlement = calculate_co_contribution(income, f64::MAX, &fy);
+
+        if potential_entitlement > current_entitlement + 1.0 {
+            insights.push(EarningsInsight {
+                category: InsightCategory::SuperOpportunity,
+                title: "Unclaimed Government Co-Contribution".to_string(),
+                description: format!(
+                    "At your income the government matches personal after-tax super contributions. You could receive up to ${:.0} in {} but your current contributions attract ${:.0}.",
+                    potential_entitlement, fy.label(), current_entitlement
+                ),
+                confidence_level: 0.7,
+                position_ids: Vec::new(),
+                data_points: vec![
+                    format!("Personal after-tax contributions: ${:.0}", personal),
+                    format!("Contribution needed for the full amount: ${:.0}", potential_entitlement / CO_CONTRIBUTION_MATCH_RATE),
+                ],
+            });
+        }
+    }
+
+    // Division 293 on the current role's super, which can make salary sacrifice barely worthwhile
+    if let Some(record) = current_position.and_then(|pos| latest_record_for(pos, records))
+        .filter(|r| r.jurisdiction == Jurisdiction::AU)
+    {
+        let sacrifice = record.super_contributions.salary_sacrifice;
+        let income = record.annual_base() + record.annual_overtime_pay()
+            + record.annual_allowances() + record.annual_bonuses() - sacrifice;
+        let employer = record.annual_employer_super();
+        let fy = FinancialYear::containing(record.effective_date);
+        let div293 = tax::div293_liability(income, employer + sacrifice, &fy);
+
+        if div293 > 0.0 {
+            let mut data_points = vec![
+                format!("Income for Division 293: ${:.0}", income + employer + sacrifice),
+                format!("Concessional contributions: ${:.0}", employer + sacrifice),
+            ];
+            if sacrifice > 0.0 {
+                data_points.push(format!(
+                    "Salary sacrifice is taxed at {:.0}% going into super rather than 15%",
+                    (CONTRIBUTIONS_TAX_RATE + tax::DIV293_RATE) * 100.0
+                ));
+            }
+            insights.push(EarningsInsight {
+                category: InsightCategory::SuperOpportunity,
+                title: "Division 293 Tax Applies".to_string(),
+                description: format!(
+                    "Your income and concessional super together are over ${:.0}, so about ${:.0} a year of extra tax is charged on your super contributions.",
+                    tax::div293_threshold(&fy), div293
+                ),
+                confidence_level: 0.7,
+                position_ids: Vec::new(),
+                data_points,
+            });
+        }
+    }
+
+    if profile.is_none() {
+        warnings.push(AnalysisWarning {
+            code: AnalysisWarningCode::NoProfile,
+            message: "Without a profile, the percentile compares against all industries in NSW".to_string(),
+            related_entity: None,
+        });
+    }
+
+    // Time stuck at one level, separate from what it cost in pay
+    insights.extend(calculate_promotion_velocity(positions, profile, today).insights);
+
+    EarningsAnalysis {
+        current_total_compensation: current_total,
+        current_effective_hourly_rate: current_hourly,
+        income_percentile: calculate_income_percentile(current_total, 
+            profile.as_ref().map(|p| &p.industry).unwrap_or(&"Unknown".to_string()),
+            profile.as_ref().map(|p| &p.state).unwrap_or(&AustralianState::NSW)),
+        loyalty_tax_annual: 0.0, // Calculated separately
+        loyalty_tax_cumulative: 0.0, // Calculated separately
+        earnings_over_time,
+        percentile_over_time: Vec::new(), // Needs the wage index; see percentile_over_time
+        gender_benchmark: None, // Only when asked for; see apply_gender_benchmark
+        hours_vs_earnings,
+        super_trajectory,
+        insights,
+        current_positions: current_positions(positions, today).into_iter().map(PositionSummary::from).collect(),
+        upcoming_positions: upcoming,
+        warnings,
+    }
+}
+
+/// The industry percentile for every financial year from the first earnings snapshot to
+/// the one containing `today`, each year's pay being the latest snapshot from before it ended.
+///
+/// The benchmark medians are for `index.benchmark_year`; other years' medians are carried
+/// back (or forward) by the wage growth in the index, so flat pay shows as falling behind.
+pub fn percentile_over_time(
+    snapshots: &[EarningsSnapshot],
+    industry: &str,
+    index: &WageIndex,
+    today: NaiveDate,
+) -> Result<Vec<PercentilePoint>, String> {
+    let Some(first) = snapshots.iter().map(|s| s.date).min() else {
+        return Ok(Vec::new());
+    };
+    let benchmark_year = FinancialYear::parse(&index.benchmark_year)?;
+    let mut growth = HashMap::new();
+    for year in &index.growth {
+        growth.insert(FinancialYear::parse(&year.financial_year)?.start_year, year.growth_percent);
+    }
+    let growth_factor = |start_year: i32| {
+        1.0 + growth.get(&start_year).copied().unwrap_or(index.default_growth_percent) / 100.0
+    };
+    let benchmark_median = calculate_industry_median(industry);
+
+    let mut points = Vec::new();
+    for start_year in FinancialYear::containing(first).start_year..=FinancialYear::containing(today).start_year {
+        let fy = FinancialYear { start_year };
+        let Some(snapshot) = snapshots.iter()
+            .filter(|s| s.date <= fy.end())
+            .max_by_key(|s| s.date)
+        else {
+            continue;
+        };
+
+        // Each year's growth is what took the median from the year before to that one
+        let median = if start_year <= benchmark_year.start_year {
+            (start_year + 1..=benchmark_year.start_year).fold(benchmark_median, |median, year| median / growth_factor(year))
+        } else {
+            (benchmark_year.start_year + 1..=start_year).fold(benchmark_median, |median, year| median * growth_factor(year))
+        };
+
+        points.push(PercentilePoint {
+            financial_year: fy.label(),
+            annual_earnings: snapshot.actual_annual,
+            benchmark_median: median,
+            percentile: percentile_against_median(snapshot.actual_annual, median),
+        });
+    }
+    Ok(points)
+}
+
+/// An insight when the latest year's percentile is more than 10 points below three years
+/// earlier even though pay hasn't gone down: raises that haven't kept up with the market.
+pub fn percentile_decline_insight(points: &[PercentilePoint]) -> Option<EarningsInsight> {
+    let latest = points.last()?;
+    let latest_year = FinancialYear::parse(&latest.financial_year).ok()?;
+    let earlier = points.iter().find(|p| {
+        FinancialYear::parse(&p.financial_year).ok()
+            .is_some_and(|fy| fy.start_year == latest_year.start_year - PERCENTILE_DECLINE_YEARS)
+    })?;
+
+    let decline = earlier.percentile - latest.percentile;
+    if decline <= PERCENTILE_DECLINE_POINTS || latest.annual_earnings < earlier.annual_earnings {
+        return None;
+    }
+
+    Some(EarningsInsight {
+        category: InsightCategory::Underpaid,
+        title: "Falling Behind the Market".to_string(),
+        description: format!(
+            "Your pay hasn't gone down, but you've slipped from the {:.0}th to the {:.0}th percentile since {} because market wages grew faster.",
+            earlier.percentile, latest.percentile, earlier.financial_year
+        ),
+        confidence_level: 0.6,
+        position_ids: Vec::new(),
+        data_points: vec![
+            format!("{}: ${:.0} against a median of ${:.0}", earlier.financial_year, earlier.annual_earnings, earlier.benchmark_median),
+            format!("{}: ${:.0} against a median of ${:.0}", latest.financial_year, latest.annual_earnings, latest.benchmark_median),
+        ],
+    })
+}
+
+/// Adds where current pay sits against the median for the profile's gender, to the analysis
+/// and as a data point on its market comparison insights.
+///
+/// Does nothing unless demographic comparisons are on and the profile gives a gender there's
+/// a median for; without one the analysis is exactly as it was.
+pub fn apply_gender_benchmark(analysis: &mut EarningsAnalysis, profile: &UserProfile, settings: &DemographicSettings) {
+    if !settings.comparisons_enabled {
+        return;
+    }
+    let (ratio, group) = match profile.gender {
+        Gender::Woman => (WOMEN_MEDIAN_RATIO, "women"),
+        Gender::Man => (MEN_MEDIAN_RATIO, "men"),
+        Gender::NonBinary | Gender::PreferNotToSay => return,
+    };
+    let median = calculate_industry_median(&profile.industry) * ratio;
+    let percentile = percentile_against_median(analysis.current_total_compensation, median);
+
+    for insight in analysis.insights.iter_mut()
+        .filter(|i| matches!(i.category, InsightCategory::Underpaid | InsightCategory::Overpaid))
+    {
+        insight.data_points.push(format!(
+            "Against an estimated median of ${:.0} for {} in your industry you'd be around the {:.0}th percentile. This reflects pay gaps across the workforce, not what you should be paid.",
+            median, group, percentile
+        ));
+    }
+    analysis.gender_benchmark = Some(GenderBenchmark { gender: profile.gender, median, percentile });
+}
+
+/// The positions carrying any of the tags in `tag_filter`, or all of them when it's empty. For
+/// narrowing the inputs to an analysis, such as loyalty tax over FIFO roles only.
+pub fn filter_positions_by_tags(positions: &[Position], entity_tags: &[EntityTag], tag_filter: &[i64]) -> Vec<Position> {
+    if tag_filter.is_empty() {
+        return positions.to_vec();
+    }
+    positions.iter()
+        .filter(|p| entity_tags.iter().any(|t| {
+            t.entity_type == TagEntityType::Position && Some(t.entity_id) == p.id && tag_filter.contains(&t.tag_id)
+        }))
+        .cloned()
+        .collect()
+}
+
+/// The positions analyses cover: all of them with `include_archived`, otherwise those not
+/// archived. Archived positions stay in listings and exports.
+pub fn analysed_positions(positions: &[Position], include_archived: bool) -> Vec<Position> {
+    positions.iter()
+        .filter(|p| include_archived || !p.archived)
+        .cloned()
+        .collect()
+}
+
+/// The positions, records and pay entries an analysis works from, as [`analysis_data`] picks them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalysisData {
+    pub positions: Vec<Position>,
+    pub records: Vec<CompensationRecord>,
+    pub weekly_entries: Vec<WeeklyCompensationEntry>,
+}
+
+/// The [`analysed_positions`] carrying any of the tags in `tag_filter` (all of them when it's
+/// empty), with only their records and pay entries. Pay entries not tied to a position still
+/// count unless a tag filter is set.
+pub fn analysis_data(
+    positions: &[Position],
+    mut records: Vec<CompensationRecord>,
+    mut weekly_entries: Vec<WeeklyCompensationEntry>,
+    entity_tags: &[EntityTag],
+    tag_filter: &[i64],
+    include_archived: bool,
+) -> AnalysisData {
+    let positions = filter_positions_by_tags(&analysed_positions(positions, include_archived), entity_tags, tag_filter);
+    let kept = |id: i64| positions.iter().any(|p| p.id == Some(id));
+    records.retain(|r| kept(r.position_id));
+    weekly_entries.retain(|e| e.position_id.map_or(tag_filter.is_empty(), kept));
+
+    AnalysisData { positions, records, weekly_entries }
+}
+
+/// Positions started by `as_of` with no end date, the one treated as current first: latest
+/// start date, then permanent before contract before casual, then the most recently saved
+/// (highest id). A position that hasn't started yet is upcoming, not current.
+pub fn current_positions(positions: &[Position], as_of: NaiveDate) -> Vec<&Position> {
+    let mut current: Vec<&Position> = positions.iter()
+        .filter(|p| p.end_date.is_none() && p.start_date <= as_of)
+        .collect();
+    current.sort_by(|a, b| {
+        b.start_date.cmp(&a.start_date)
+            .then_with(|| employment_type_rank(&a.employment_type).cmp(&employment_type_rank(&b.employment_type)))
+            .then_with(|| b.id.cmp(&a.id))
+    });
+    current
+}
+
+/// The position the analysis and resume treat as current: the first of [`current_positions`],
+/// or the most recently started position when every position has ended.
+pub fn current_position(positions: &[Position], as_of: NaiveDate) -> Option<&Position> {
+    current_positions(positions, as_of).into_iter().next()
+        .or_else(|| positions.iter().filter(|p| p.start_date <= as_of).max_by_key(|p| (p.start_date, p.id)))
+}
+
+/// Positions starting after `as_of`, soonest first, with the days until each starts.
+pub fn upcoming_positions(positions: &[Position], as_of: NaiveDate) -> Vec<UpcomingPosition> {
+    let mut upcoming: Vec<UpcomingPosition> = positions.iter()
+        .filter(|p| p.status_on(as_of) == PositionStatus::Future)
+        .map(|p| UpcomingPosition {
+            position: PositionSummary::from(p),
+            starts_in_days: (p.start_date - as_of).num_days(),
+        })
+        .collect();
+    upcoming.sort_by_key(|u| (u.starts_in_days, u.position.id));
+    upcoming
+}
+
+// Positions that have started by `as_of`; future ones have no tenure or earnings yet
+fn started_positions(positions: &[Position], as_of: NaiveDate) -> Vec<Position> {
+    positions.iter().filter(|p| p.start_date <= as_of).cloned().collect()
+}
+
+/// Estimated earnings forgone by staying with an employer instead of moving at market rates.
+pub fn calculate_loyalty_tax(positions: &[Position], today: NaiveDate) -> LoyaltyTaxAnalysis {
+    let positions = &started_positions(positions, today);
+    let mut tenure_blocks = Vec::new();
+    let annual_loyalty_tax = Vec::new();
+    let mut cumulative_tax = 0.0;
+    let mut warnings = Vec::new();
+
+    // Group positions by employer
+    let mut employer_groups: HashMap<String, Vec<&Position>> = HashMap::new();
+    for position in positions {
+        employer_groups.entry(position.employer_name.clone())
+            .or_default()
+            .push(position);
+    }
+
+    for (employer, pos_list) in employer_groups {
+        // Sort by date
+        let mut sorted_positions = pos_list.clone();
+        sorted_positions.sort_by_key(|p| p.start_date);
+
+        let start_date = sorted_positions.first().unwrap().start_date;
+        let end_date = sorted_positions.last().unwrap().end_date
+            .unwrap_or_else(|| NaiveDate::from_ymd_opt(2024, 12, 31).unwrap());
+        if sorted_positions.last().unwrap().end_date.is_none() {
+            warnings.push(AnalysisWarning {
+                code: AnalysisWarningCode::OpenEndedTenure,
+                message: format!("{} has no end date, so its tenure is counted to {}", employer, end_date.format("%-d %b %Y")),
+                related_entity: Some(RelatedEntity::Employer(employer.clone())),
+            });
+        }
+        
+        let tenure_years = (end_date - start_date).num_days() as f64 / 365.25;
+
+        if tenure_years <= 2.0 {
+            warnings.push(AnalysisWarning {
+                code: AnalysisWarningCode::ShortTenureSkipped,
+                message: format!("{} lasted {:.1} years, too short to compare against the market", employer, tenure_years),
+                related_entity: Some(RelatedEntity::Employer(employer.clone())),
+            });
+        }
+
+        if tenure_years > 2.0 { // Only calculate for tenures > 2 years
+            // Calculate actual progression
+            let first_salary = sorted_positions.first().unwrap().base_salary_estimate();
+            let last_salary = sorted_positions.last().unwrap().base_salary_estimate();
+            let actual_progression = if first_salary > 0.0 {
+                ((last_salary - first_salary) / first_salary) / tenure_years
+            } else {
+                0.0
+            };
+
+            // Expected market progression
+            let seniority = sorted_positions.last().unwrap().seniority_level.clone();
+            let market_expected = market_growth_rate(&seniority);
+
+            // Calculate loyalty tax impact
+            let loyalty_tax_rate = market_expected - actual_progression;
+            let loyalty_tax_impact = if loyalty_tax_rate > 0.0 {
+                last_salary * loyalty_tax_rate * tenure_years
+            } else {
+                0.0
+            };
+
+            tenure_blocks.push(TenureBlock {
+                employer_name: employer.clone(),
+                start_date,
+                end_date: Some(end_date),
+                years_of_service: tenure_years,
+                actual_progression: actual_progression * 100.0,
+                market_expected_progression: market_expected * 100.0,
+                loyalty_tax_impact,
+            });
+
+            cumulative_tax += loyalty_tax_impact;
+        }
+    }
+
+    let confidence_level = if tenure_blocks.is_empty() { 0.0 } else { 0.75 };
+
+    LoyaltyTaxAnalysis {
+        tenure_blocks,
+        market_comparison: MarketComparison {
+            industry_average_growth: 0.06,
+            role_level_growth: 0.07,
+            cpi_adjusted_growth: 0.03,
+        },
+        annual_loyalty_tax,
+        cumulative_loyalty_tax: cumulative_tax,
+        confidence_level,
+        warnings,
+    }
+}
+
+/// Base pay earned each calendar year against what it would have been had pay at each employer
+/// grown at the market rate for the role's level from the day the user joined.
+///
+/// Employers are tenure blocks as in `calculate_loyalty_tax`; each move restarts the comparison
+/// at the new employer's actual starting pay. Amounts are in AUD, up to `today` for current roles.
+pub fn counterfactual_earnings(
+    positions: &[Position],
+    records: &[CompensationRecord],
+    exchange_rates: &[ExchangeRate],
+    today: NaiveDate,
+) -> CounterfactualEarnings {
+    let mut employer_groups: HashMap<&str, Vec<Position>> = HashMap::new();
+    for position in positions {
+        employer_groups.entry(position.employer_name.as_str()).or_default().push(position.clone());
+    }
+
+    let mut by_year: BTreeMap<i32, (f64, f64)> = BTreeMap::new();
+    for block in employer_groups.values_mut() {
+        block.sort_by_key(|p| p.start_date);
+        let start = block[0].start_date;
+        let end = if block.iter().any(|p| p.end_date.is_none()) {
+            today
+        } else {
+            block.iter().filter_map(|p| p.end_date).max().unwrap_or(today).min(today)
+        };
+        let Some(first) = records.iter()
+            .filter(|r| block.iter().any(|p| p.id == Some(r.position_id)))
+            .min_by_key(|r| r.effective_date)
+        else {
+            continue;
+        };
+        let Some(rate) = currency::exchange_rate_for(exchange_rates, &first.currency, &FinancialYear::containing(first.effective_date)) else {
+            continue;
+        };
+        let starting_pay = first.annual_base() * rate;
+        let growth_from = first.effective_date.max(start);
+        let growth = market_growth_rate(&block[block.len() - 1].seniority_level);
+
+        for year in start.year()..=end.year() {
+            let from = start.max(NaiveDate::from_ymd_opt(year, 1, 1).unwrap_or(start));
+            let until = end.min(NaiveDate::from_ymd_opt(year, 12, 31).unwrap_or(end));
+            let days = (until - from).num_days() as f64 + 1.0;
+            if days <= 0.0 {
+                continue;
+            }
+            let midpoint = from + Days::new(((until - from).num_days() / 2) as u64);
+            let share = days / DAYS_PER_YEAR;
+            let actual = annual_base_pay_on(midpoint, &FinancialYear::containing(midpoint), block, records, exchange_rates)
+                .unwrap_or(0.0);
+            let years_in = (midpoint - growth_from).num_days().max(0) as f64 / DAYS_PER_YEAR;
+            let counterfactual = starting_pay * (1.0 + growth).powf(years_in);
+
+            let totals = by_year.entry(year).or_insert((0.0, 0.0));
+            totals.0 += actual * share;
+            totals.1 += counterfactual * share;
+        }
+    }
+
+    let mut cumulative_actual = 0.0;
+    let mut cumulative_counterfactual = 0.0;
+    let years: Vec<CounterfactualYear> = by_year.into_iter()
+        .map(|(year, (actual, counterfactual))| {
+            cumulative_actual += actual;
+            cumulative_counterfactual += counterfactual;
+            CounterfactualYear { year, actual, counterfactual, cumulative_actual, cumulative_counterfactual }
+        })
+        .collect();
+
+    CounterfactualEarnings {
+        years,
+        actual_total: cumulative_actual,
+        counterfactual_total: cumulative_counterfactual,
+        foregone_total: cumulative_counterfactual - cumulative_actual,
+    }
+}
+
+/// One role's base pay growth, graded against CPI, rises in the super guarantee and the
+/// market growth rate for its seniority level.
+///
+/// Growth runs from the role's first record to its end (or `today`), so a raise that stopped
+/// coming counts against it. The super guarantee benchmark compares the package (base plus
+/// guarantee) instead, since a package that only grew with the guarantee means the base paid
+/// for it. CPI is left out when no year of the period has a figure. Errors without a year of
+/// history.
+pub fn progression_scorecard(
+    position: &Position,
+    records: &[CompensationRecord],
+    today: NaiveDate,
+) -> Result<ProgressionScorecard, String> {
+    let position_id = position.id.ok_or_else(|| "Save the position before grading its pay".to_string())?;
+    let mut held: Vec<&CompensationRecord> = records.iter().filter(|r| r.position_id == position_id).collect();
+    held.sort_by_key(|r| r.effective_date);
+    let (Some(first), Some(latest)) = (held.first(), held.last()) else {
+        return Err("No compensation records found for this position".to_string());
+    };
+
+    let from = first.effective_date;
+    let to = position.end_date.unwrap_or(today);
+    let years = (to - from).num_days() as f64 / DAYS_PER_YEAR;
+    if years < 1.0 {
+        return Err("Grading pay growth needs at least a year of pay history for the role".to_string());
+    }
+    if first.annual_base() <= 0.0 {
+        return Err("The role's first record has no base pay to grow from".to_string());
+    }
+
+    let annualise = |ratio: f64| (ratio.powf(1.0 / years) - 1.0) * 100.0;
+    let grade = |benchmark: ProgressionBenchmark, benchmark_growth_percent: f64, actual_growth_percent: f64| BenchmarkGrade {
+        benchmark,
+        benchmark_growth_percent,
+        actual_growth_percent,
+        grade: if actual_growth_percent >= benchmark_growth_percent {
+            ScorecardGrade::Green
+        } else if actual_growth_percent >= benchmark_growth_percent - AMBER_GRADE_POINTS {
+            ScorecardGrade::Amber
+        } else {
+            ScorecardGrade::Red
+        },
+    };
+
+    let base_growth = annualise(latest.annual_base() / first.annual_base());
+    let mut grades = Vec::new();
+
+    let (first_fy, last_fy) = (FinancialYear::containing(from), FinancialYear::containing(to));
+    let cpi: Vec<f64> = CPI_ANNUAL_CHANGE.iter()
+        .filter(|(start_year, _)| (first_fy.start_year..=last_fy.start_year).contains(start_year))
+        .map(|(_, change)| *change)
+        .collect();
+    if !cpi.is_empty() {
+        let compound: f64 = cpi.iter().map(|change| 1.0 + change / 100.0).product();
+        grades.push(grade(ProgressionBenchmark::Cpi, (compound.powf(1.0 / cpi.len() as f64) - 1.0) * 100.0, base_growth));
+    }
+
+    let guarantee_start = 1.0 + super_guarantee_rate(&first_fy) / 100.0;
+    let guarantee_end = 1.0 + super_guarantee_rate(&last_fy) / 100.0;
+    let package_growth = annualise(latest.annual_base() * guarantee_end / (first.annual_base() * guarantee_start));
+    grades.push(grade(ProgressionBenchmark::SuperGuarantee, annualise(guarantee_end / guarantee_start), package_growth));
+
+    grades.push(grade(ProgressionBenchmark::Market, market_growth_rate(&position.seniority_level) * 100.0, base_growth));
+
+    Ok(ProgressionScorecard {
+        position_id,
+        from,
+        to,
+        years,
+        annual_base_growth_percent: base_growth,
+        grades,
+    })
+}
+
+/// Structured career summary for feeding into an AI resume writer.
+pub fn generate_resume_export(
+    positions: &[Position],
+    profile: &Option<UserProfile>,
+    today: NaiveDate,
+) -> ResumeExport {
+    let (upcoming, started): (Vec<Position>, Vec<Position>) = positions.iter().cloned()
+        .partition(|p| p.status_on(today) == PositionStatus::Future);
+    let positions = &started;
+
+    let profile_summary = if let Some(p) = profile {
+        let age = dates::age_on(p.date_of_birth, today);
+        
+        let experience_years = calculate_total_experience(positions);
+        let current_seniority = current_position(positions, today)
+            .map(|p| p.seniority_level.clone())
+            .unwrap_or(SeniorityLevel::Entry);
+
+        ProfileSummary {
+            name: format!("{} {}", p.first_name, p.last_name),
+            age,
+            location: format!("{:?}", p.state),
+            industry: p.industry.clone(),
+            experience_years,
+            seniority_level: current_seniority,
+        }
+    } else {
+        ProfileSummary {
+            name: "Unknown".to_string(),
+            age: 0,
+            location: "Unknown".to_string(),
+            industry: "Unknown".to_string(),
+            experience_years: 0.0,
+            seniority_level: SeniorityLevel::Entry,
+        }
+    };
+
+    let resume_position = |pos: &Position| {
+        let duration = if pos.start_date > today {
+            format!("Starting {}", pos.start_date.format("%b %Y"))
+        } else if let Some(end) = pos.end_date {
+            format_duration(pos.start_date, end)
+        } else {
+            format!("{} - Present", pos.start_date.format("%b %Y"))
+        };
+
+        ResumePosition {
+            employer: pos.employer_name.clone(),
+            title: pos.job_title.clone(),
+            duration,
+            responsibilities: pos.core_responsibilities.lines()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            achievements: pos.achievements.clone(),
+            skills_used: pos.tools_systems_skills.clone(),
+        }
+    };
+    let career_timeline: Vec<ResumePosition> = positions.iter().map(resume_position).collect();
+    let mut upcoming = upcoming;
+    upcoming.sort_by_key(|p| (p.start_date, p.id));
+    let upcoming_positions: Vec<ResumePosition> = upcoming.iter().map(resume_position).collect();
+
+    let all_achievements: Vec<String> = positions.iter()
+        .flat_map(|p| p.achievements.clone())
+        .collect();
+
+    // Most recently used first, the way a recruiter reads a skills section
+    let all_skills: Vec<String> = skill_recency_ranking(positions, today)
+        .into_iter()
+        .map(|s| s.skill)
+        .collect();
+
+    let compensation_summary = calculate_compensation_summary(positions, today);
+
+    ResumeExport {
+        profile_summary,
+        career_timeline,
+        upcoming_positions,
+        achievements: all_achievements,
+        skills_and_tools: all_skills,
+        compensation_summary,
+        target_preferences: profile.as_ref()
+            .map(|p| p.career_preferences.clone())
+            .unwrap_or_else(|| CareerPreferences {
+                employment_type_preference: EmploymentType::Permanent,
+                fifo_tolerance: FIFOTolerance::None,
+                travel_tolerance: TravelTolerance::None,
+                overtime_appetite: OvertimeAppetite::Moderate,
+                privacy_acknowledged: false,
+                disclaimer_acknowledged: false,
+            }),
+    }
+}
+
+// A skill last used this many years ago counts half as much as one used today
+const SKILL_RECENCY_HALF_LIFE_YEARS: f64 = 5.0;
+
+// A short stint still counts for something
+const MIN_SKILL_YEARS: f64 = 0.25;
+
+/// Every distinct skill across the positions, ranked by years of use weighted toward recent
+/// ones: each position's years count in full while it's current and halve every
+/// `SKILL_RECENCY_HALF_LIFE_YEARS` after it ended. Skills match case-insensitively and keep the
+/// spelling from the most recent position.
+pub fn skill_recency_ranking(positions: &[Position], today: NaiveDate) -> Vec<SkillRecency> {
+    let mut by_recency: Vec<&Position> = positions.iter().filter(|p| p.start_date <= today).collect();
+    by_recency.sort_by_key(|p| std::cmp::Reverse(p.end_date.unwrap_or(today)));
+
+    let mut skills: Vec<SkillRecency> = Vec::new();
+    for position in by_recency {
+        let last_used = position.end_date.unwrap_or(today).min(today);
+        let years = ((last_used - position.start_date).num_days() as f64 / 365.25).max(MIN_SKILL_YEARS);
+        let years_since = (today - last_used).num_days().max(0) as f64 / 365.25;
+        let weight = years * 0.5_f64.powf(years_since / SKILL_RECENCY_HALF_LIFE_YEARS);
+
+        let mut seen = Vec::new();
+        for name in &position.tools_systems_skills {
+            let key = name.trim().to_lowercase();
+            if key.is_empty() || seen.contains(&key) {
+                continue;
+            }
+            match skills.iter_mut().find(|s| s.skill.to_lowercase() == key) {
+                Some(skill) => {
+                    skill.positions_used += 1;
+                    skill.years_used += years;
+                    skill.score += weight;
+                }
+                None => skills.push(SkillRecency {
+                    skill: name.trim().to_string(),
+                    last_used_year: last_used.year(),
+                    current: position.end_date.is_none(),
+                    positions_used: 1,
+                    years_used: years,
+                    score: weight,
+                }),
+            }
+            seen.push(key);
+        }
+    }
+
+    skills.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.skill.cmp(&b.skill)));
+    skills
+}
+
+/// Annualised breakdown of a compensation record into base, overtime, allowances, bonuses and
+/// employer super, converted to AUD. Non-cash benefits are kept out of the breakdown and added
+/// to it for the total package value, so packages with a car and with more cash compare.
+///
+/// Without an exchange rate for the record's currency and year, the AUD amounts are nothing
+/// and a `MissingExchangeRate` warning says so; the original amounts are still given.
+pub fn calculate_income_composition(
+    record: &CompensationRecord,
+    exchange_rates: &[ExchangeRate],
+) -> IncomeComposition {
+    let employer_super = record.annual_employer_super();
+    let fy = FinancialYear::containing(record.effective_date);
+    let exchange_rate = currency::exchange_rate_for(exchange_rates, &record.currency, &fy);
+
+    let amounts = vec![
+        (IncomeComponentKind::BaseSalary, record.annual_base()),
+        (IncomeComponentKind::Overtime, record.annual_overtime_pay()),
+        (IncomeComponentKind::Allowances, record.annual_allowances()),
+        (IncomeComponentKind::Bonuses, record.annual_bonuses()),
+        (IncomeComponentKind::EmployerSuper, employer_super),
+    ];
+
+    let original_total: f64 = amounts.iter().map(|(_, amount)| amount).sum();
+    let non_cash_benefits = record.annual_non_cash_benefits();
+
+    let components = amounts.into_iter()
+        .map(|(kind, original_annual_amount)| IncomeComponent {
+            kind,
+            annual_amount: original_annual_amount * exchange_rate.unwrap_or(0.0),
+            original_annual_amount,
+            percentage: if original_total > 0.0 { original_annual_amount / original_total * 100.0 } else { 0.0 },
+        })
+        .collect();
+
+    let warnings = if exchange_rate.is_none() {
+        vec![AnalysisWarning {
+            code: AnalysisWarningCode::MissingExchangeRate,
+            message: format!(
+                "There's no {} exchange rate for {}, so this package isn't counted in AUD",
+                record.currency, fy.label()
+            ),
+            related_entity: Some(RelatedEntity::Position(record.position_id)),
+        }]
+    } else {
+        Vec::new()
+    };
+
+    IncomeComposition {
+        position_id: record.position_id,
+        effective_date: record.effective_date,
+        currency: record.currency.clone(),
+        exchange_rate_to_aud: exchange_rate,
+        total_compensation: original_total * exchange_rate.unwrap_or(0.0),
+        original_total_compensation: original_total,
+        components,
+        non_cash_benefits: non_cash_benefits * exchange_rate.unwrap_or(0.0),
+        total_package_value: (original_total + non_cash_benefits) * exchange_rate.unwrap_or(0.0),
+        original_total_package_value: original_total + non_cash_benefits,
+        warnings,
+    }
+}
+
+/// An offer's package against a current one: composition of each, and take-home for a year
+/// at the tax rates of the year each record takes effect.
+pub fn compare_offer(
+    current: &CompensationRecord,
+    offer: &CompensationRecord,
+    exchange_rates: &[ExchangeRate],
+    has_help_debt: bool,
+) -> OfferComparison {
+    let current_composition = calculate_income_composition(current, exchange_rates);
+    let offer_composition = calculate_income_composition(offer, exchange_rates);
+    let current_take_home = annual_take_home(current, &FinancialYear::containing(current.effective_date), has_help_debt);
+    let offer_take_home = annual_take_home(offer, &FinancialYear::containing(offer.effective_date), has_help_debt);
+
+    OfferComparison {
+        total_compensation_difference: offer_composition.total_compensation - current_composition.total_compensation,
+        total_package_value_difference: offer_composition.total_package_value - current_composition.total_package_value,
+        take_home_difference: offer_take_home - current_take_home,
+        current: current_composition,
+        offer: offer_composition,
+        current_take_home,
+        offer_take_home,
+        mls_tier_change: None,
+    }
+}
+
+/// A named offer compared against the latest record for a position, keeping that record so
+/// the scenario can still be rerun once the position is gone.
+pub fn offer_scenario(
+    name: &str,
+    position_id: i64,
+    records: &[CompensationRecord],
+    offer: CompensationRecord,
+    exchange_rates: &[ExchangeRate],
+    has_help_debt: bool,
+) -> Result<Scenario, String> {
+    let baseline = records.iter()
+        .filter(|r| r.position_id == position_id)
+        .max_by_key(|r| r.effective_date)
+        .ok_or("The position has no compensation records to compare the offer against")?
+        .clone();
+    let result = compare_offer(&baseline, &offer, exchange_rates, has_help_debt);
+
+    Ok(Scenario {
+        id: None,
+        name: name.trim().to_string(),
+        position_id: Some(position_id),
+        baseline,
+        offer,
+        result,
+        created_at: chrono::Utc::now(),
+    })
+}
+
+/// Reruns a saved scenario against the position's latest record as it stands now, next to
+/// the result it had when saved. When the position has been deleted or has no records left,
+/// the record saved with the scenario is used instead, with a warning.
+pub fn rerun_scenario(
+    scenario: &Scenario,
+    positions: &[Position],
+    records: &[CompensationRecord],
+    exchange_rates: &[ExchangeRate],
+    has_help_debt: bool,
+) -> ScenarioRerun {
+    let mut warnings = Vec::new();
+    let position = positions.iter().find(|p| p.id.is_some() && p.id == scenario.position_id);
+    let latest = position.and_then(|p| latest_record_for(p, records));
+
+    let baseline = match (position, latest) {
+        (Some(_), Some(record)) => record,
+        (Some(position), None) => {
+            warnings.push(format!(
+                "{} has no compensation records left, so the pay saved with the scenario was used",
+                position.employer_name
+            ));
+            &scenario.baseline
+        }
+        (None, _) => {
+            warnings.push(
+                "The position this scenario was compared against has been deleted, so the pay saved with the scenario was used".to_string()
+            );
+            &scenario.baseline
+        }
+    };
+
+    ScenarioRerun {
+        scenario: scenario.clone(),
+        original: scenario.result.clone(),
+        current: compare_offer(baseline, &scenario.offer, exchange_rates, has_help_debt),
+        warnings,
+    }
+}
+
+/// Per financial year totals from pay-period entries, reconciled against yearly summaries.
+///
+/// New Zealand yearly summaries get their own rows on the April-March tax year.
+pub fn calculate_financial_year_summaries(
+    weekly_entries: &[WeeklyCompensationEntry],
+    yearly_entries: &[YearlyIncomeEntry],
+    exchange_rates: &[ExchangeRate],
+) -> Vec<FinancialYearSummary> {
+    // Payslips are always Australian; yearly summaries follow their own jurisdiction's tax year
+    let mut weekly_by_fy: BTreeMap<(FinancialYear, Jurisdiction), Vec<&WeeklyCompensationEntry>> = BTreeMap::new();
+    for entry in weekly_entries {
+        weekly_by_fy.entry((FinancialYear::containing(entry.week_ending), Jurisdiction::AU))
+            .or_default()
+            .push(entry);
+    }
+
+    let mut yearly_by_fy: BTreeMap<(FinancialYear, Jurisdiction), Vec<&YearlyIncomeEntry>> = BTreeMap::new();
+    // Placeholders from the FY rollover would otherwise read as a lodged income of nothing
+    for entry in yearly_entries.iter().filter(|e| !e.is_placeholder()) {
+        if let Ok(fy) = FinancialYear::parse(&entry.financial_year) {
+            yearly_by_fy.entry((fy, entry.jurisdiction))
+                .or_default()
+                .push(entry);
+        }
+    }
+
+    let mut years: Vec<(FinancialYear, Jurisdiction)> = weekly_by_fy.keys().chain(yearly_by_fy.keys()).copied().collect();
+    years.sort();
+    years.dedup();
+
+    years.into_iter().map(|(fy, jurisdiction)| {
+        let entries = weekly_by_fy.get(&(fy, jurisdiction)).cloned().unwrap_or_default();
+        let yearly = yearly_by_fy.get(&(fy, jurisdiction)).cloned().unwrap_or_default();
+
+        // Convert every amount to AUD; entries without a known rate contribute nothing
+        let rates: Vec<f64> = entries.iter()
+            .map(|e| currency::exchange_rate_for(exchange_rates, &e.currency, &fy).unwrap_or(0.0))
+            .collect();
+        let sum_aud = |amount: fn(&WeeklyCompensationEntry) -> f64| -> f64 {
+            entries.iter().zip(&rates).map(|(e, rate)| amount(e) * rate).sum()
+        };
+
+        let mut foreign_by_currency: BTreeMap<String, f64> = BTreeMap::new();
+        for entry in entries.iter().filter(|e| !currency::is_base_currency(&e.currency)) {
+            *foreign_by_currency.entry(entry.currency.clone()).or_insert(0.0) += entry.gross_pay;
+        }
+        for entry in yearly.iter().filter(|e| !currency::is_base_currency(&e.currency)) {
+            *foreign_by_currency.entry(entry.currency.clone()).or_insert(0.0) += entry.gross_income;
+        }
+        let foreign_income: Vec<ForeignIncome> = foreign_by_currency.into_iter()
+            .map(|(code, original_gross)| {
+                let rate = currency::exchange_rate_for(exchange_rates, &code, &fy);
+                ForeignIncome {
+                    currency: code,
+                    original_gross,
+                    exchange_rate_to_aud: rate,
+                    aud_gross: rate.map(|r| original_gross * r),
+                }
+            })
+            .collect();
+
+        let gross_income = sum_aud(|e| e.gross_pay);
+        let hours_ordinary: f64 = entries.iter().map(|e| e.hours_ordinary).sum();
+        let hours_overtime: f64 = entries.iter().map(|e| e.hours_overtime).sum();
+
+        // Normalise to per-week figures using the days each pay period actually covers
+        let weeks_covered: f64 = entries.iter()
+            .map(|e| {
+                let start = e.period_start
+                    .unwrap_or_else(|| dates::period_start_for(e.week_ending, &e.period_type));
+                dates::period_days(start, e.week_ending) as f64 / 7.0
+            })
+            .sum();
+
+        // Each position is paid on its own cadence, anchored on its latest period end
+        let mut latest_by_position: HashMap<Option<i64>, &WeeklyCompensationEntry> = HashMap::new();
+        for entry in &entries {
+            let latest = latest_by_position.entry(entry.position_id).or_insert(entry);
+            if entry.week_ending > latest.week_ending {
+                *latest = entry;
+            }
+        }
+        let expected_periods: u32 = latest_by_position.values()
+            .map(|e| dates::pay_periods_in_fy(&fy, &e.period_type, e.week_ending))
+            .sum();
+
+        let periods_recorded = entries.len() as u32;
+        let projected_gross_income = if periods_recorded > 0 && periods_recorded < expected_periods {
+            gross_income / periods_recorded as f64 * expected_periods as f64
+        } else {
+            gross_income
+        };
+
+        let ato_gross_income = if yearly.is_empty() {
+            None
+        } else {
+            Some(yearly.iter()
+                .map(|e| e.gross_income * currency::exchange_rate_for(exchange_rates, &e.currency, &fy).unwrap_or(0.0))
+                .sum())
+        };
+
+        let mut warnings = Vec::new();
+        let related_entity = Some(RelatedEntity::FinancialYear(fy.label()));
+        if projected_gross_income != gross_income {
+            warnings.push(AnalysisWarning {
+                code: AnalysisWarningCode::PartialYearProjected,
+                message: format!(
+                    "{} of {} pay periods are recorded, so the year's gross is projected from them",
+                    periods_recorded, expected_periods
+                ),
+                related_entity: related_entity.clone(),
+            });
+        }
+        for foreign in foreign_income.iter().filter(|f| f.exchange_rate_to_aud.is_none()) {
+            warnings.push(AnalysisWarning {
+                code: AnalysisWarningCode::MissingExchangeRate,
+                message: format!("There's no {} exchange rate for {}, so that income counts as nothing", foreign.currency, fy.label()),
+                related_entity: related_entity.clone(),
+            });
+        }
+
+        let estimated_income_tax = match jurisdiction {
+            Jurisdiction::AU => {
+                let taxable = ato_gross_income.unwrap_or(projected_gross_income);
+                Some(tax::income_tax(&jurisdiction, taxable, &fy))
+            }
+            // NZ tax is assessed in NZD, so only estimate when every summary is in NZD
+            Jurisdiction::NZ => {
+                let nzd_rate = currency::exchange_rate_for(exchange_rates, "NZD", &fy);
+                if yearly.iter().all(|e| e.currency == "NZD") {
+                    let taxable: f64 = yearly.iter().map(|e| e.gross_income).sum();
+                    nzd_rate.map(|rate| tax::income_tax(&jurisdiction, taxable, &fy) * rate)
+                } else {
+                    None
+                }
+            }
+        };
+
+        if estimated_income_tax.is_none() {
+            warnings.push(AnalysisWarning {
+                code: AnalysisWarningCode::TaxNotEstimated,
+                message: "New Zealand tax is only estimated when every income summary for the year is in NZD, with an NZD exchange rate".to_string(),
+                related_entity,
+            });
+        }
+
+        FinancialYearSummary {
+            financial_year: fy.label(),
+            jurisdiction,
+            year_start: fy.tax_year_start(&jurisdiction),
+            year_end: fy.tax_year_end(&jurisdiction),
+            gross_income,
+            tax_withheld: sum_aud(|e| e.tax_withheld),
+            net_income: sum_aud(|e| e.net_pay),
+            super_contributed: sum_aud(|e| e.super_contributed),
+            hours_ordinary,
+            hours_overtime,
+            average_weekly_gross: if weeks_covered > 0.0 { gross_income / weeks_covered } else { 0.0 },
+            average_weekly_hours: if weeks_covered > 0.0 { (hours_ordinary + hours_overtime) / weeks_covered } else { 0.0 },
+            periods_recorded,
+            expected_periods,
+            projected_gross_income,
+            ato_gross_income,
+            reconciliation_difference: ato_gross_income
+                .filter(|_| periods_recorded > 0)
+                .map(|ato| ato - projected_gross_income),
+            estimated_income_tax,
+            foreign_income,
+            vested_equity: 0.0,
+            unvested_equity: 0.0,
+            total_compensation: gross_income,
+            warnings,
+        }
+    }).collect()
+}
+
+/// Equity vesting in each financial year from the first grant to the last tranche, and what
+/// is still to vest after each year, in AUD at grant value.
+///
+/// A grant copied onto later records of the same position (same date, value and currency)
+/// counts once. Amounts are converted at the rate for the year they vest in; what's still to
+/// vest, at the rate for the year it's counted in.
+pub fn calculate_equity_by_fy(records: &[CompensationRecord], exchange_rates: &[ExchangeRate]) -> Vec<EquityYear> {
+    let mut grants: Vec<(&CompensationRecord, &EquityGrant)> = Vec::new();
+    for record in records {
+        let Some(grant) = &record.equity else { continue };
+        let seen = grants.iter().any(|(r, g)| {
+            r.position_id == record.position_id && r.currency == record.currency
+                && g.grant_date == grant.grant_date && g.grant_value == grant.grant_value
+        });
+        if !seen {
+            grants.push((record, grant));
+        }
+    }
+
+    let first = grants.iter().map(|(_, grant)| FinancialYear::containing(grant.grant_date)).min();
+    let last = grants.iter()
+        .flat_map(|(_, grant)| grant.vesting_events())
+        .map(|(date, _)| FinancialYear::containing(date))
+        .max();
+    let (Some(first), Some(last)) = (first, last) else {
+        return Vec::new();
+    };
+
+    (first.start_year..=last.start_year)
+        .map(|start_year| {
+            let fy = FinancialYear { start_year };
+            let (mut vested, mut unvested) = (0.0, 0.0);
+            for (record, grant) in &grants {
+                let rate = currency::exchange_rate_for(exchange_rates, &record.currency, &fy).unwrap_or(0.0);
+                for (date, value) in grant.vesting_events() {
+                    if FinancialYear::containing(date) == fy {
+                        vested += value * rate;
+                    } else if date > fy.end() && grant.grant_date <= fy.end() {
+                        unvested += value * rate;
+                    }
+                }
+            }
+            EquityYear { financial_year: fy.label(), vested, unvested }
+        })
+        .collect()
+}
+
+/// Adds vested and unvested equity to the Australian financial year summaries, and vested
+/// equity to their total compensation. Years with equity but no summary are left out.
+pub fn add_equity_to_summaries(summaries: &mut [FinancialYearSummary], equity: &[EquityYear]) {
+    for summary in summaries.iter_mut().filter(|s| s.jurisdiction == Jurisdiction::AU) {
+        if let Some(year) = equity.iter().find(|e| e.financial_year == summary.financial_year) {
+            summary.vested_equity = year.vested;
+            summary.unvested_equity = year.unvested;
+            summary.total_compensation = summary.gross_income + year.vested;
+        }
+    }
+}
+
+/// Where the financial year's earnings rank among all the years on record, as the percentage
+/// of the other years it beat (ties count half): 100 is the best year, 0 the worst.
+///
+/// A year's earnings are its income statement gross, else its projected gross from payslips,
+/// plus vested equity, summed across jurisdictions.
+pub fn personal_earnings_percentile(summaries: &[FinancialYearSummary], fy: &FinancialYear) -> Result<f64, String> {
+    let mut by_year: BTreeMap<String, f64> = BTreeMap::new();
+    for summary in summaries {
+        *by_year.entry(summary.financial_year.clone()).or_default() +=
+            summary.ato_gross_income.unwrap_or(summary.projected_gross_income) + summary.vested_equity;
+    }
+
+    let label = fy.label();
+    let earnings = *by_year.get(&label).ok_or_else(|| format!("No earnings on record for {}", label))?;
+    let others: Vec<f64> = by_year.iter().filter(|(year, _)| **year != label).map(|(_, e)| *e).collect();
+    if others.is_empty() {
+        return Err("Earnings for at least two financial years are needed to rank one".to_string());
+    }
+
+    let beaten: f64 = others.iter()
+        .map(|other| if earnings > *other { 1.0 } else if earnings == *other { 0.5 } else { 0.0 })
+        .sum();
+    Ok(beaten / others.len() as f64 * 100.0)
+}
+
+/// Hours worked against gross pay from pay period entries, one point per year in label order.
+///
+/// Each entry counts in the year its period ends; gross is converted to AUD at the rate for
+/// the financial year of the period end, and entries without a known rate add hours only.
+pub fn calculate_hours_vs_earnings(
+    weekly_entries: &[WeeklyCompensationEntry],
+    exchange_rates: &[ExchangeRate],
+    basis: YearBasis,
+) -> Vec<HoursEarningsPoint> {
+    let mut by_year: BTreeMap<String, (f64, f64, f64)> = BTreeMap::new();
+    for entry in weekly_entries {
+        let fy = FinancialYear::containing(entry.week_ending);
+        let rate = currency::exchange_rate_for(exchange_rates, &entry.currency, &fy).unwrap_or(0.0);
+        let (hours, overtime, earnings) = by_year.entry(basis.label(entry.week_ending)).or_default();
+        *hours += entry.hours_ordinary + entry.hours_overtime;
+        *overtime += entry.hours_overtime;
+        *earnings += entry.gross_pay * rate;
+    }
+
+    by_year.into_iter()
+        .map(|(label, (hours, overtime, earnings))| HoursEarningsPoint {
+            label,
+            total_hours_worked: hours,
+            total_earnings: earnings,
+            overtime_percentage: if hours > 0.0 { overtime / hours * 100.0 } else { 0.0 },
+        })
+        .collect()
+}
+
+/// Take-home pay per hour worked for a compensation record: the annual package after income
+/// tax, and for Australian income the Medicare levy and any HELP repayment, divided by
+/// ordinary plus overtime hours.
+///
+/// Salary sacrifice and pre-tax packaging come out before tax and are not take-home pay, nor
+/// is post-tax packaging; non-taxable allowances and bonuses are. Packaging that attracts FBT
+/// counts towards HELP repayment income as reportable fringe benefits. Offsets and the Medicare levy surcharge are not modelled, and New Zealand
+/// income is taxed on the PAYE brackets alone. In the record's currency.
+pub fn calculate_net_hourly_rate(record: &CompensationRecord, tax_year: &FinancialYear, has_help_debt: bool) -> f64 {
+    let annual_hours = record.standard_weekly_hours * 52.0 + record.annual_overtime_hours();
+    if annual_hours <= 0.0 {
+        return 0.0;
+    }
+
+    annual_take_home(record, tax_year, has_help_debt) / annual_hours
+}
+
+/// Gross pay per door-to-door hour: the record's annual package over ordinary and overtime
+/// hours plus the position's unpaid commute, so a well-paid job a long way off can be weighed
+/// against a closer one. The commute is counted on each day the ordinary hours take (7.6
+/// hours a day) outside annual leave. The plain effective rate when no commute is set. In the
+/// record's currency.
+pub fn commute_adjusted_hourly_rate(position: &Position, record: &CompensationRecord) -> f64 {
+    let days_per_week = (record.standard_weekly_hours / ORDINARY_HOURS_PER_DAY).round().clamp(1.0, 7.0);
+    let commute_hours = position.commute_minutes_per_day.unwrap_or(0) as f64 / 60.0
+        * days_per_week * (52.0 - ANNUAL_LEAVE_WEEKS_PER_YEAR);
+    let annual_hours = record.standard_weekly_hours * 52.0 + record.annual_overtime_hours() + commute_hours;
+    if annual_hours <= 0.0 {
+        return 0.0;
+    }
+
+    annual_gross_and_taxable(record).0 / annual_hours
+}
+
+// Pay after salary sacrifice, packaging, income tax, Medicare and HELP, for a year
+fn annual_take_home(record: &CompensationRecord, tax_year: &FinancialYear, has_help_debt: bool) -> f64 {
+    let (gross, _) = annual_gross_and_taxable(record);
+    gross - annual_sacrificed(record) - annual_tax_deductions(record, tax_year, has_help_debt)
+}
+
+// Super salary sacrifice and pre- and post-tax packaging, none of which reaches the pay packet
+fn annual_sacrificed(record: &CompensationRecord) -> f64 {
+    record.super_contributions.salary_sacrifice
+        + record.annual_pre_tax_packaging() + record.annual_post_tax_packaging()
+}
+
+// Income tax, plus the Medicare levy and any HELP repayment on Australian income
+fn annual_tax_deductions(record: &CompensationRecord, tax_year: &FinancialYear, has_help_debt: bool) -> f64 {
+    let (_, taxable_income) = annual_gross_and_taxable(record);
+    let salary_sacrifice = record.super_contributions.salary_sacrifice;
+
+    let mut deductions = tax::income_tax(&record.jurisdiction, taxable_income, tax_year);
+    if record.jurisdiction == Jurisdiction::AU {
+        deductions += tax::medicare_levy(taxable_income, tax_year);
+        if has_help_debt {
+            // Repayment income adds back reportable super contributions and fringe benefits
+            let fringe_benefits = tax::reportable_fringe_benefits(record.annual_fringe_benefits());
+            deductions += tax::help_repayment(taxable_income + salary_sacrifice + fringe_benefits, tax_year);
+        }
+    }
+    deductions
+}
+
+/// The record's annual gross, tax, sacrifice, net and employer super spread over pay periods
+/// of the given frequency.
+///
+/// A year is 52.18 weeks or 26.09 fortnights rather than a whole number, so per-period figures
+/// divide by the average and a 53rd week or 27th fortnight turns up every few years; a salary
+/// paid over that many periods comes out slightly lower each time.
+pub fn pay_cycle_breakdown(
+    record: &CompensationRecord,
+    frequency: PayslipFrequency,
+    tax_year: &FinancialYear,
+    has_help_debt: bool,
+) -> PayCycleBreakdown {
+    let annual = annual_pay_amounts(record, tax_year, has_help_debt);
+    let periods_per_year = frequency.average_periods_per_year();
+    let per_period = PayCycleAmounts {
+        gross: annual.gross / periods_per_year,
+        tax: annual.tax / periods_per_year,
+        salary_sacrifice: annual.salary_sacrifice / periods_per_year,
+        net: annual.net / periods_per_year,
+        employer_super: annual.employer_super / periods_per_year,
+    };
+    let extra_periods = periods_per_year - frequency.periods_per_year();
+
+    PayCycleBreakdown {
+        position_id: record.position_id,
+        currency: record.currency.clone(),
+        frequency,
+        periods_per_year,
+        extra_period_every_years: (extra_periods > 0.0).then(|| 1.0 / extra_periods),
+        annual,
+        per_period,
+    }
+}
+
+// A year's gross, tax, sacrifice, net and employer super on the record
+fn annual_pay_amounts(record: &CompensationRecord, tax_year: &FinancialYear, has_help_debt: bool) -> PayCycleAmounts {
+    let (gross, _) = annual_gross_and_taxable(record);
+    let salary_sacrifice = annual_sacrificed(record);
+    let tax = annual_tax_deductions(record, tax_year, has_help_debt);
+    PayCycleAmounts {
+        gross,
+        tax,
+        salary_sacrifice,
+        net: gross - salary_sacrifice - tax,
+        employer_super: record.annual_employer_super(),
+    }
+}
+
+/// The record's pay at 0.8 of its ordinary hours against as it is: a day a week less for a
+/// smaller cut in take-home than in gross, as less of the pay falls in the higher brackets.
+///
+/// Ordinary hours, a salary and allowances are scaled; overtime, bonuses, salary sacrifice and
+/// packaging stay as they are.
+pub fn model_four_day_week(record: &CompensationRecord, tax_year: &FinancialYear, has_help_debt: bool) -> FourDayWeekImpact {
+    let mut reduced = record.clone();
+    reduced.standard_weekly_hours *= FOUR_DAY_WEEK_FTE;
+    if matches!(reduced.pay_type, PayType::Salary) {
+        reduced.base_rate *= FOUR_DAY_WEEK_FTE;
+    }
+    for allowance in &mut reduced.allowances {
+        allowance.amount *= FOUR_DAY_WEEK_FTE;
+    }
+
+    let current = annual_pay_amounts(record, tax_year, has_help_debt);
+    let four_day_week = annual_pay_amounts(&reduced, tax_year, has_help_debt);
+    let reduction_percent = |before: f64, after: f64| if before > 0.0 { (before - after) / before * 100.0 } else { 0.0 };
+
+    FourDayWeekImpact {
+        position_id: record.position_id,
+        currency: record.currency.clone(),
+        fte: FOUR_DAY_WEEK_FTE,
+        weekly_hours_gained: record.standard_weekly_hours - reduced.standard_weekly_hours,
+        time_gained_percent: (1.0 - FOUR_DAY_WEEK_FTE) * 100.0,
+        gross_reduction_percent: reduction_percent(current.gross, four_day_week.gross),
+        net_reduction_percent: reduction_percent(current.net, four_day_week.net),
+        current,
+        four_day_week,
+    }
+}
+
+/// The tax withheld on a compensation record against what its employer is expected to
+/// withhold, given whether the job claims the tax-free threshold. A second job that doesn't
+/// claim it withholds more, and that isn't over-withholding.
+///
+/// Australian income only. Errors when the record has no tax withheld to check.
+pub fn calculate_withholding_check(record: &CompensationRecord) -> Result<WithholdingCheck, String> {
+    if record.jurisdiction != Jurisdiction::AU {
+        return Err("Withholding is only checked for Australian income".to_string());
+    }
+    let tax_withheld = record.tax_withheld
+        .ok_or("The record has no tax withheld to check")?;
+    let fy = FinancialYear::containing(record.effective_date);
+    let (_, taxable_income) = annual_gross_and_taxable(record);
+    let expected = tax::expected_payg_withholding(taxable_income, record.claims_tax_free_threshold, &fy);
+
+    let difference = tax_withheld - expected;
+    let tolerance = (expected * WITHHOLDING_TOLERANCE_SHARE).max(WITHHOLDING_TOLERANCE_DOLLARS);
+    let status = if difference > tolerance {
+        WithholdingStatus::OverWithheld
+    } else if difference < -tolerance {
+        WithholdingStatus::UnderWithheld
+    } else {
+        WithholdingStatus::AsExpected
+    };
+
+    Ok(WithholdingCheck {
+        position_id: record.position_id,
+        effective_date: record.effective_date,
+        financial_year: fy.label(),
+        claims_tax_free_threshold: record.claims_tax_free_threshold,
+        taxable_income,
+        expected_withholding: expected,
+        tax_withheld,
+        difference,
+        status,
+    })
+}
+
+/// The reportable fringe benefits amount a position's salary packaging and non-cash benefits
+/// should put on its income statement for the financial year, in AUD: the packaging that
+/// attracts FBT and the benefits flagged as fringe benefits on the record in force, pro-rated
+/// to the part of the year worked and grossed up. The FBT year
+/// runs April to March, which is not modelled.
+///
+/// `None` when the position has no record for the year or its currency has no rate.
+pub fn expected_reportable_fringe_benefits(
+    position: &Position,
+    records: &[CompensationRecord],
+    exchange_rates: &[ExchangeRate],
+    fy: &FinancialYear,
+) -> Option<f64> {
+    let (record, share_of_year) = record_for_fy(position, records, fy)?;
+    let rate = currency::exchange_rate_for(exchange_rates, &record.currency, fy)?;
+    Some(tax::reportable_fringe_benefits(record.annual_fringe_benefits() * share_of_year * rate))
+}
+
+/// Employer super on a compensation record. Super is paid on ordinary time earnings, so
+/// overtime, allowances that aren't ordinary time and bonuses don't attract it, however much
+/// overtime is worked.
+pub fn calculate_employer_super(record: &CompensationRecord) -> EmployerSuperBreakdown {
+    let ordinary_time_earnings = record.annual_ordinary_time_earnings();
+    let total = record.annual_base() + record.annual_overtime_pay()
+        + record.annual_allowances() + record.annual_bonuses();
+
+    EmployerSuperBreakdown {
+        position_id: record.position_id,
+        effective_date: record.effective_date,
+        currency: record.currency.clone(),
+        ordinary_time_earnings,
+        excluded_earnings: total - ordinary_time_earnings,
+        contribution_rate: record.super_contributions.contribution_rate,
+        employer_super: record.annual_employer_super(),
+    }
+}
+
+/// Which way overtime moves the effective hourly rate for a compensation record.
+///
+/// Allowances and bonuses are earned regardless of overtime, so they're spread across all
+/// hours worked: overtime paid below the resulting ordinary-hours rate pulls the figure down.
+pub fn calculate_overtime_rate_effect(record: &CompensationRecord) -> OvertimeRateEffect {
+    let fixed_pay = record.annual_base() + record.annual_allowances() + record.annual_bonuses();
+    let ordinary_hours = record.standard_weekly_hours * 52.0;
+    let overtime_hours = record.annual_overtime_hours();
+
+    let without = if ordinary_hours > 0.0 { fixed_pay / ordinary_hours } else { 0.0 };
+    let total_hours = ordinary_hours + overtime_hours;
+    let with = if total_hours > 0.0 {
+        (fixed_pay + record.annual_overtime_pay()) / total_hours
+    } else {
+        0.0
+    };
+
+    let difference = with - without;
+    let direction = if overtime_hours <= 0.0 || difference.abs() < 0.005 {
+        OvertimeEffectDirection::Neutral
+    } else if difference > 0.0 {
+        OvertimeEffectDirection::NetPositive
+    } else {
+        OvertimeEffectDirection::NetNegative
+    };
+
+    OvertimeRateEffect {
+        position_id: record.position_id,
+        effective_date: record.effective_date,
+        currency: record.currency.clone(),
+        overtime_hours_per_week: overtime_hours / 52.0,
+        rate_multiplier: record.overtime.rate_multiplier,
+        hourly_rate_without_overtime: without,
+        hourly_rate_with_overtime: with,
+        difference,
+        direction,
+    }
+}
+
+/// Overtime hours a week for the record's gross pay to reach `target_annual`, at its base
+/// hourly rate and overtime multiplier. Allowances and bonuses count towards the target.
+///
+/// Flags a target that takes more than `weekly_hours_ceiling` hours a week in total, and one
+/// whose overtime lifts the marginal tax rate (HELP included when `has_help_debt`), so each
+/// extra hour keeps less. Errors unless the target is positive and the record pays overtime.
+pub fn overtime_hours_for_target(
+    record: &CompensationRecord,
+    target_annual: f64,
+    weekly_hours_ceiling: f64,
+    fy: &FinancialYear,
+    has_help_debt: bool,
+) -> Result<OvertimeTargetResult, String> {
+    if !(target_annual.is_finite() && target_annual > 0.0) {
+        return Err("Target income must be a positive amount".to_string());
+    }
+    let overtime_hourly_rate = record.base_hourly_rate() * record.overtime.rate_multiplier;
+    if overtime_hourly_rate <= 0.0 {
+        return Err("The record needs a base rate and an overtime multiplier to plan overtime".to_string());
+    }
+
+    let income_without_overtime = record.annual_base() + record.annual_allowances() + record.annual_bonuses();
+    let overtime_hours_per_week = ((target_annual - income_without_overtime) / overtime_hourly_rate / 52.0).max(0.0);
+    let total_weekly_hours = record.standard_weekly_hours + overtime_hours_per_week;
+
+    let deductions_on = |gross: f64| {
+        tax::total_tax_payable(gross, fy) + if has_help_debt { tax::help_repayment(gross, fy) } else { 0.0 }
+    };
+    let marginal_rate_on = |gross: f64| (deductions_on(gross + 1.0) - deductions_on(gross)) * 100.0;
+    let income_at_target = target_annual.max(income_without_overtime);
+    let marginal_tax_rate_without_overtime = marginal_rate_on(income_without_overtime);
+    let marginal_tax_rate_at_target = marginal_rate_on(income_at_target);
+
+    Ok(OvertimeTargetResult {
+        position_id: record.position_id,
+        target_annual,
+        income_without_overtime,
+        overtime_hourly_rate,
+        overtime_hours_per_week,
+        total_weekly_hours,
+        weekly_hours_ceiling,
+        unsustainable: total_weekly_hours > weekly_hours_ceiling,
+        marginal_tax_rate_without_overtime,
+        marginal_tax_rate_at_target,
+        crosses_tax_bracket: marginal_tax_rate_at_target - marginal_tax_rate_without_overtime > BRACKET_CHANGE_POINTS,
+        net_per_overtime_hour_at_target: overtime_hourly_rate * (1.0 - marginal_tax_rate_at_target / 100.0),
+    })
+}
+
+/// Employer super for a financial year, summed from payslips when any were entered.
+///
+/// Without payslips it's estimated as the SG rate on the base salary of each position active
+/// in the year, pro-rated for the part of the year the position covers. `positions` should
+/// be the positions active in the year.
+pub fn calculate_super_contributions_for_fy(
+    fy: &FinancialYear,
+    weekly_entries: &[WeeklyCompensationEntry],
+    positions: &[Position],
+    records: &[CompensationRecord],
+    exchange_rates: &[ExchangeRate],
+) -> SuperContributionSummary {
+    let entries: Vec<&WeeklyCompensationEntry> = weekly_entries.iter()
+        .filter(|e| FinancialYear::containing(e.week_ending) == *fy)
+        .collect();
+
+    if !entries.is_empty() {
+        let total: f64 = entries.iter()
+            .map(|e| e.super_contributed * currency::exchange_rate_for(exchange_rates, &e.currency, fy).unwrap_or(0.0))
+            .sum();
+        return SuperContributionSummary {
+            financial_year: fy.label(),
+            employer_contributions: total,
+            method: SuperContributionMethod::WeeklyEntries,
+            periods_recorded: entries.len() as u32,
+            sg_rate: None,
+        };
+    }
+
+    let sg_rate = super_guarantee_rate(fy);
+    let estimate: f64 = positions.iter()
+        .filter_map(|position| {
+            let (record, share_of_year) = record_for_fy(position, records, fy)?;
+            let rate = currency::exchange_rate_for(exchange_rates, &record.currency, fy).unwrap_or(0.0);
+
+            Some(record.annual_ordinary_time_earnings() * rate * share_of_year * sg_rate / 100.0)
+        })
+        .sum();
+
+    SuperContributionSummary {
+        financial_year: fy.label(),
+        employer_contributions: estimate,
+        method: SuperContributionMethod::Estimated,
+        periods_recorded: 0,
+        sg_rate: Some(sg_rate),
+    }
+}
+
+/// Super paid on a position's payslips in the financial year against the contracted rate on
+/// their ordinary time earnings, by quarter, since the guarantee is owed quarterly.
+///
+/// The rate is the one on the compensation record in force at each pay period's end, or the
+/// super guarantee rate when that's higher or there's no record. Ordinary time earnings are
+/// gross pay less overtime, worked out from the hours and rate multiplier, and less allowances
+/// that aren't ordinary time. Pay periods are put in the quarter they end in. Errors when the
+/// position has no payslips in the year.
+pub fn calculate_super_compliance(
+    position: &Position,
+    records: &[CompensationRecord],
+    weekly_entries: &[WeeklyCompensationEntry],
+    fy: &FinancialYear,
+) -> Result<SuperComplianceReport, String> {
+    let position_id = position.id.ok_or("The position hasn't been saved")?;
+    let entries: Vec<&WeeklyCompensationEntry> = weekly_entries.iter()
+        .filter(|e| e.position_id == Some(position_id) && FinancialYear::containing(e.week_ending) == *fy)
+        .collect();
+    if entries.is_empty() {
+        return Err(format!("No payslips for {} in {} to check super against", position.employer_name, fy.label()));
+    }
+
+    let position_records: Vec<&CompensationRecord> = records.iter()
+        .filter(|r| r.position_id == position_id)
+        .collect();
+    let sg_rate = super_guarantee_rate(fy);
+
+    let mut quarters: Vec<SuperQuarterCompliance> = (1..=4)
+        .map(|quarter| {
+            let start = fy.start() + Months::new(3 * (quarter - 1));
+            SuperQuarterCompliance {
+                quarter,
+                start,
+                end: start + Months::new(3) - Days::new(1),
+                ordinary_time_earnings: 0.0,
+                expected_super: 0.0,
+                super_paid: 0.0,
+                shortfall: 0.0,
+                underpaid: false,
+            }
+        })
+        .collect();
+
+    for entry in &entries {
+        let contracted = position_records.iter()
+            .filter(|r| r.effective_date <= entry.week_ending)
+            .max_by_key(|r| r.effective_date)
+            .or_else(|| position_records.iter().min_by_key(|r| r.effective_date))
+            .map(|r| r.super_contributions.contribution_rate)
+            .unwrap_or(0.0);
+        let ote = entry_ordinary_time_earnings(entry);
+
+        let Some(quarter) = quarters.iter_mut().find(|q| q.start <= entry.week_ending && entry.week_ending <= q.end) else {
+            continue;
+        };
+        quarter.ordinary_time_earnings += ote;
+        quarter.expected_super += ote * contracted.max(sg_rate) / 100.0;
+        quarter.super_paid += entry.super_contributed;
+    }
+
+    quarters.retain(|q| q.ordinary_time_earnings > 0.0 || q.super_paid > 0.0);
+    for quarter in &mut quarters {
+        quarter.shortfall = quarter.expected_super - quarter.super_paid;
+        quarter.underpaid = quarter.shortfall > SUPER_SHORTFALL_TOLERANCE_DOLLARS;
+    }
+
+    Ok(SuperComplianceReport {
+        position_id,
+        financial_year: fy.label(),
+        periods_checked: entries.len() as u32,
+        ordinary_time_earnings: quarters.iter().map(|q| q.ordinary_time_earnings).sum(),
+        expected_super: quarters.iter().map(|q| q.expected_super).sum(),
+        super_paid: quarters.iter().map(|q| q.super_paid).sum(),
+        underpayment: quarters.iter().filter(|q| q.underpaid).map(|q| q.shortfall).sum(),
+        quarters,
+    })
+}
+
+// Gross pay less overtime and allowances that aren't ordinary time. Allowances on a payslip
+// are taken as paid in that period, and overtime as the multiplier's share of the rest.
+fn entry_ordinary_time_earnings(entry: &WeeklyCompensationEntry) -> f64 {
+    let allowances: f64 = entry.allowances.iter().map(|a| a.amount).sum();
+    let other_allowances: f64 = entry.allowances.iter()
+        .filter(|a| !a.is_ordinary_time())
+        .map(|a| a.amount)
+        .sum();
+    let overtime_weight = entry.hours_overtime * entry.overtime_rate_multiplier;
+    let paid_hours = entry.hours_ordinary + overtime_weight;
+    let overtime_pay = if paid_hours > 0.0 {
+        (entry.gross_pay - allowances).max(0.0) * overtime_weight / paid_hours
+    } else {
+        0.0
+    };
+    (entry.gross_pay - overtime_pay - other_allowances).max(0.0)
+}
+
+/// Each of a position's compensation records linked to an award classification, checked
+/// against the award rate in force on its effective date, with back pay estimated from the
+/// payslips in the period it was in force.
+///
+/// The minimum includes the casual loading for a casual position. A payslip is short when its
+/// gross pay less allowances is under the minimum for its ordinary hours plus its overtime
+/// hours at the award's overtime rate, each priced at the award rate in force when it ended.
+/// Records, and payslips, before the first rate for the classification aren't checked.
+/// Errors when a record is linked to a rate that doesn't exist.
+pub fn calculate_award_compliance(
+    position: &Position,
+    records: &[CompensationRecord],
+    award_rates: &[AwardRate],
+    weekly_entries: &[WeeklyCompensationEntry],
+) -> Result<AwardComplianceReport, String> {
+    let position_id = position.id.ok_or("The position hasn't been saved")?;
+    let casual_loading = |rate: &AwardRate| {
+        if matches!(position.employment_type, EmploymentType::Casual) { rate.casual_loading } else { 0.0 }
+    };
+
+    let mut position_records: Vec<&CompensationRecord> = records.iter()
+        .filter(|r| r.position_id == position_id)
+        .collect();
+    position_records.sort_by_key(|r| r.effective_date);
+
+    let mut findings = Vec::new();
+    let mut records_checked = 0;
+    for (i, record) in position_records.iter().enumerate() {
+        let Some(linked_id) = record.award_classification_id else {
+            continue;
+        };
+        let linked = award_rates.iter()
+            .find(|rate| rate.id == Some(linked_id))
+            .ok_or_else(|| format!("A compensation record is linked to award rate {}, which doesn't exist", linked_id))?;
+        let rate_on = |date: NaiveDate| {
+            award_rates.iter()
+                .filter(|rate| rate.award_name == linked.award_name && rate.classification == linked.classification)
+                .filter(|rate| rate.effective_date <= date)
+                .max_by_key(|rate| rate.effective_date)
+        };
+        let Some(award) = rate_on(record.effective_date) else {
+            continue;
+        };
+        records_checked += 1;
+
+        let minimum_hourly_rate = award.hourly_minimum * (1.0 + casual_loading(award) / 100.0);
+        let base_hourly_rate = record.base_hourly_rate();
+        let has_overtime = !matches!(record.overtime.frequency, OvertimeFrequency::None);
+        let base_rate_below_minimum = base_hourly_rate < minimum_hourly_rate - 0.005;
+        let overtime_below_minimum = has_overtime && record.overtime.rate_multiplier < award.overtime_multiplier;
+
+        let in_force_until = position_records.get(i + 1).map(|next| next.effective_date);
+        let mut periods_underpaid = 0;
+        let mut estimated_underpayment = 0.0;
+        for entry in weekly_entries.iter().filter(|e| e.position_id == Some(position_id)) {
+            if entry.week_ending < record.effective_date || in_force_until.is_some_and(|until| entry.week_ending >= until) {
+                continue;
+            }
+            let Some(award) = rate_on(entry.week_ending) else {
+                continue;
+            };
+            let minimum_hourly = award.hourly_minimum * (1.0 + casual_loading(award) / 100.0);
+            let minimum_pay = minimum_hourly * (entry.hours_ordinary + entry.hours_overtime * award.overtime_multiplier);
+            let allowances: f64 = entry.allowances.iter().map(|a| a.amount).sum();
+            let shortfall = minimum_pay - (entry.gross_pay - allowances);
+            if shortfall > AWARD_SHORTFALL_TOLERANCE_DOLLARS {
+                periods_underpaid += 1;
+                estimated_underpayment += shortfall;
+            }
+        }
+
+        if base_rate_below_minimum || overtime_below_minimum || periods_underpaid > 0 {
+            findings.push(AwardFinding {
+                compensation_record_id: record.id,
+                effective_date: record.effective_date,
+                award_name: award.award_name.clone(),
+                classification: award.classification.clone(),
+                minimum_hourly_rate,
+                base_hourly_rate,
+                minimum_overtime_multiplier: award.overtime_multiplier,
+                overtime_multiplier: record.overtime.rate_multiplier,
+                base_rate_below_minimum,
+                overtime_below_minimum,
+                periods_underpaid,
+                estimated_underpayment,
+            });
+        }
+    }
+
+    Ok(AwardComplianceReport {
+        position_id,
+        records_checked,
+        estimated_back_pay: findings.iter().map(|f| f.estimated_underpayment).sum(),
+        findings,
+    })
+}
+
+/// Further salary sacrifice that fits under the concessional cap for the year, after employer
+/// super and the sacrifice already on each record, and the tax it would save.
+///
+/// The saving is income tax at marginal rates on the sacrificed amount, less the 15% tax on
+/// concessional contributions and any Division 293 the headroom adds; it can be negative on a
+/// low income. Only Australian income is counted. Taxable income is estimated from the package
+/// on each record in force less pre-tax packaging, pro-rated like the super estimate, and
+/// stands in for the income Division 293 is tested on. Carry-forward of unused cap amounts is not modelled.
+/// `positions` should be the positions active in the year.
+pub fn calculate_sacrifice_headroom(
+    fy: &FinancialYear,
+    weekly_entries: &[WeeklyCompensationEntry],
+    positions: &[Position],
+    records: &[CompensationRecord],
+    exchange_rates: &[ExchangeRate],
+) -> SacrificeHeadroom {
+    let concessional_cap = tax::concessional_cap(fy);
+    let au_records: Vec<CompensationRecord> = records.iter()
+        .filter(|r| r.jurisdiction == Jurisdiction::AU)
+        .cloned()
+        .collect();
+    let employer = calculate_super_contributions_for_fy(fy, weekly_entries, positions, &au_records, exchange_rates);
+
+    let (existing_salary_sacrifice, package) = positions.iter()
+        .filter_map(|position| {
+            let (record, share_of_year) = record_for_fy(position, &au_records, fy)?;
+            let rate = currency::exchange_rate_for(exchange_rates, &record.currency, fy).unwrap_or(0.0);
+            let package = record.annual_base() + record.annual_overtime_pay()
+                + record.annual_allowances() + record.annual_bonuses() - record.annual_pre_tax_packaging();
+            Some((
+                record.super_contributions.salary_sacrifice * rate * share_of_year,
+                package * rate * share_of_year,
+            ))
+        })
+        .fold((0.0, 0.0), |(sacrifice, total), (s, p)| (sacrifice + s, total + p));
+
+    let taxable_income = (package - existing_salary_sacrifice).max(0.0);
+    let headroom = (concessional_cap - employer.employer_contributions - existing_salary_sacrifice)
+        .max(0.0)
+        .min(taxable_income);
+    let tax_before = tax::income_tax(&Jurisdiction::AU, taxable_income, fy);
+    let tax_after = tax::income_tax(&Jurisdiction::AU, taxable_income - headroom, fy);
+    let concessional = employer.employer_contributions + existing_salary_sacrifice;
+    let div293_on_headroom = tax::div293_liability(taxable_income - headroom, concessional + headroom, fy)
+        - tax::div293_liability(taxable_income, concessional, fy);
+
+    SacrificeHeadroom {
+        financial_year: fy.label(),
+        concessional_cap,
+        employer_contributions: employer.employer_contributions,
+        employer_contributions_method: employer.method,
+        existing_salary_sacrifice,
+        taxable_income,
+        headroom,
+        div293_on_headroom,
+        estimated_tax_saving: tax_before - tax_after - headroom * CONTRIBUTIONS_TAX_RATE - div293_on_headroom,
+    }
+}
+
+/// Concessional super contributions for each financial year from the first one with any
+/// data through `through`, in AUD.
+///
+/// Employer contributions are summed from payslips, else estimated like
+/// [`calculate_super_contributions_for_fy`]. Salary sacrifice is the reportable super on the
+/// year's income statements when there is any, else the sacrifice on each record in force,
+/// pro-rated for the part of the year the position was held, as are additional contributions.
+/// Only Australian records and income statements are counted.
+pub fn concessional_contributions_by_fy(
+    positions: &[Position],
+    records: &[CompensationRecord],
+    weekly_entries: &[WeeklyCompensationEntry],
+    yearly_entries: &[YearlyIncomeEntry],
+    exchange_rates: &[ExchangeRate],
+    through: &FinancialYear,
+) -> Vec<ConcessionalContributions> {
+    let au_records: Vec<CompensationRecord> = records.iter()
+        .filter(|r| r.jurisdiction == Jurisdiction::AU)
+        .cloned()
+        .collect();
+    let au_yearly: Vec<&YearlyIncomeEntry> = yearly_entries.iter()
+        .filter(|e| e.jurisdiction == Jurisdiction::AU)
+        .collect();
+
+    let first = positions.iter().map(|p| FinancialYear::containing(p.start_date))
+        .chain(weekly_entries.iter().map(|e| FinancialYear::containing(e.week_ending)))
+        .chain(au_yearly.iter().filter_map(|e| FinancialYear::parse(&e.financial_year).ok()))
+        .min();
+    let Some(first) = first else {
+        return Vec::new();
+    };
+
+    (first.start_year..=through.start_year)
+        .map(|start_year| {
+            let fy = FinancialYear { start_year };
+            let employer = calculate_super_contributions_for_fy(&fy, weekly_entries, positions, &au_records, exchange_rates);
+
+            let (recorded_sacrifice, additional_contributions) = positions.iter()
+                .filter_map(|position| {
+                    let (record, share_of_year) = record_for_fy(position, &au_records, &fy)?;
+                    let rate = currency::exchange_rate_for(exchange_rates, &record.currency, &fy).unwrap_or(0.0);
+                    Some((
+                        record.super_contributions.salary_sacrifice * rate * share_of_year,
+                        record.super_contributions.additional_contributions * rate * share_of_year,
+                    ))
+                })
+                .fold((0.0, 0.0), |(sacrifice, additional), (s, a)| (sacrifice + s, additional + a));
+            let reported: f64 = au_yearly.iter()
+                .filter(|e| FinancialYear::parse(&e.financial_year).ok() == Some(fy))
+                .map(|e| e.reportable_super * currency::exchange_rate_for(exchange_rates, &e.currency, &fy).unwrap_or(0.0))
+                .sum();
+            let salary_sacrifice = if reported > 0.0 { reported } else { recorded_sacrifice };
+
+            ConcessionalContributions {
+                financial_year: fy.label(),
+                employer_contributions: employer.employer_contributions,
+                employer_contributions_method: employer.method,
+                salary_sacrifice,
+                additional_contributions,
+                total: employer.employer_contributions + salary_sacrifice + additional_contributions,
+            }
+        })
+        .collect()
+}
+
+/// Each year's concessional contributions against the cap, in the order given.
+///
+/// Unused cap from 2018-19 on carries forward for five years and is used oldest first. It's
+/// only available when the profile records a total super balance under $500,000; that one
+/// balance is applied to every year. Years before the first one given are taken to have no
+/// unused cap, since nothing is known about them.
+pub fn calculate_super_cap_status(
+    contributions: &[ConcessionalContributions],
+    total_super_balance: Option<f64>,
+) -> Vec<SuperCapStatus> {
+    let can_carry_forward = total_super_balance.is_some_and(|balance| balance < tax::CARRY_FORWARD_BALANCE_LIMIT);
+    let mut unused: Vec<(i32, f64)> = Vec::new(); // (start year, amount left), oldest first
+
+    contributions.iter()
+        .filter_map(|year| Some((FinancialYear::parse(&year.financial_year).ok()?, year)))
+        .map(|(fy, year)| {
+            unused.retain(|(start_year, _)| fy.start_year - start_year <= tax::CARRY_FORWARD_YEARS);
+            let concessional_cap = tax::concessional_cap(&fy);
+            let carry_forward_available = if can_carry_forward {
+                unused.iter().map(|(_, amount)| amount).sum()
+            } else {
+                0.0
+            };
+
+            let over_cap = (year.total - concessional_cap).max(0.0);
+            let carry_forward_used = over_cap.min(carry_forward_available);
+            let mut to_use = carry_forward_used;
+            for (_, amount) in unused.iter_mut() {
+                let taken = to_use.min(*amount);
+                *amount -= taken;
+                to_use -= taken;
+            }
+            unused.retain(|(_, amount)| *amount > 0.0);
+            if fy.start_year >= tax::CARRY_FORWARD_FROM && year.total < concessional_cap {
+                unused.push((fy.start_year, concessional_cap - year.total));
+            }
+
+            SuperCapStatus {
+                contributions: year.clone(),
+                concessional_cap,
+                carry_forward_available,
+                carry_forward_used,
+                headroom: (concessional_cap - year.total).max(0.0) + carry_forward_available - carry_forward_used,
+                excess: over_cap - carry_forward_used,
+            }
+        })
+        .collect()
+}
+
+/// An insight listing the years concessional contributions went over the cap, or None when
+/// none did.
+pub fn super_cap_insight(statuses: &[SuperCapStatus]) -> Option<EarningsInsight> {
+    let over: Vec<&SuperCapStatus> = statuses.iter().filter(|s| s.excess > 0.0).collect();
+    if over.is_empty() {
+        return None;
+    }
+
+    Some(EarningsInsight {
+        category: InsightCategory::SuperOpportunity,
+        title: "Super Contributions Over the Cap".to_string(),
+        description: "Concessional contributions over the cap are added to your taxable income and taxed at \
+            your marginal rate, less a 15% offset. Check the figures with your fund; you may be able to \
+            release the excess."
+            .to_string(),
+        confidence_level: 0.7,
+        data_points: over.iter()
+            .map(|s| format!(
+                "{}: ${:.0} contributed against a ${:.0} cap, ${:.0} over",
+                s.contributions.financial_year, s.contributions.total,
+                s.concessional_cap + s.carry_forward_used, s.excess
+            ))
+            .collect(),
+        position_ids: Vec::new(),
+    })
+}
+
+/// Tax on a financial year's Australian income from every source together, against what was
+/// withheld, which each employer does as if its job were the only one.
+///
+/// Each position's income comes from its yearly entry for the year, else its payslips, else
+/// an estimate from its compensation record pro-rated to the part of the year held. Yearly
+/// entries and payslips not linked to a position are a source of their own. Taxable income
+/// is the gross for entries and the record's taxable income for estimates. Amounts are in
+/// AUD; income without a rate counts for nothing and is named in the warnings. Deductions are
+/// added by [`apply_insurance_deductions`].
+pub fn calculate_combined_tax_position(
+    fy: &FinancialYear,
+    positions: &[Position],
+    records: &[CompensationRecord],
+    weekly_entries: &[WeeklyCompensationEntry],
+    yearly_entries: &[YearlyIncomeEntry],
+    exchange_rates: &[ExchangeRate],
+    has_help_debt: bool,
+) -> CombinedTaxPosition {
+    let unconverted = RefCell::new(BTreeSet::new());
+    let to_aud = |amount: f64, code: &str| to_aud_or_note(amount, code, exchange_rates, fy, &unconverted);
+    // A rollover placeholder would otherwise win over the year's payslips with nothing
+    let yearly: Vec<&YearlyIncomeEntry> = yearly_entries.iter()
+        .filter(|e| !e.is_placeholder())
+        .filter(|e| e.jurisdiction == Jurisdiction::AU && FinancialYear::parse(&e.financial_year).ok() == Some(*fy))
+        .collect();
+    let weekly: Vec<&WeeklyCompensationEntry> = weekly_entries.iter()
+        .filter(|e| FinancialYear::containing(e.week_ending) == *fy)
+        .collect();
+
+    let source_for = |position_id: Option<i64>, employer_name: String| -> Option<TaxedIncomeSource> {
+        let position_yearly: Vec<&&YearlyIncomeEntry> = yearly.iter().filter(|e| e.position_id == position_id).collect();
+        if !position_yearly.is_empty() {
+            let gross = position_yearly.iter().map(|e| to_aud(e.gross_income, &e.currency)).sum();
+            return Some(TaxedIncomeSource {
+                position_id,
+                employer_name,
+                method: TaxedIncomeMethod::YearlyEntry,
+                gross_income: gross,
+                taxable_income: gross,
+                tax_withheld: position_yearly.iter().map(|e| to_aud(e.tax_withheld, &e.currency)).sum(),
+            });
+        }
+        let payslips: Vec<&&WeeklyCompensationEntry> = weekly.iter().filter(|e| e.position_id == position_id).collect();
+        if !payslips.is_empty() {
+            let gross = payslips.iter().map(|e| to_aud(e.gross_pay, &e.currency)).sum();
+            return Some(TaxedIncomeSource {
+                position_id,
+                employer_name,
+                method: TaxedIncomeMethod::Payslips,
+                gross_income: gross,
+                taxable_income: gross,
+                tax_withheld: payslips.iter().map(|e| to_aud(e.tax_withheld, &e.currency)).sum(),
+            });
+        }
+        None
+    };
+
+    let mut sources: Vec<TaxedIncomeSource> = positions.iter()
+        .filter_map(|position| {
+            if let Some(source) = source_for(position.id, position.employer_name.clone()) {
+                return Some(source);
+            }
+            let (record, share_of_year) = record_for_fy(position, records, fy)?;
+            if record.jurisdiction != Jurisdiction::AU {
+                return None;
+            }
+            let (gross, taxable) = annual_gross_and_taxable(record);
+            let (gross, taxable) = (to_aud(gross, &record.currency) * share_of_year, to_aud(taxable, &record.currency) * share_of_year);
+            Some(TaxedIncomeSource {
+                position_id: position.id,
+                employer_name: position.employer_name.clone(),
+                method: TaxedIncomeMethod::Estimated,
+                gross_income: gross,
+                taxable_income: taxable,
+                tax_withheld: tax::expected_payg_withholding(taxable, record.claims_tax_free_threshold, fy),
+            })
+        })
+        .collect();
+    sources.extend(source_for(None, "Other income".to_string()));
+
+    let total_taxable_income: f64 = sources.iter().map(|s| s.taxable_income).sum();
+    let tax_on = |taxable: f64| {
+        let help = if has_help_debt { tax::help_repayment(taxable, fy) } else { 0.0 };
+        tax::total_tax_payable(taxable, fy) + help
+    };
+    let tax_payable = tax_on(total_taxable_income);
+    let total_withheld: f64 = sources.iter().map(|s| s.tax_withheld).sum();
+
+    CombinedTaxPosition {
+        financial_year: fy.label(),
+        total_gross_income: sources.iter().map(|s| s.gross_income).sum(),
+        total_taxable_income,
+        deductions: 0.0,
+        tax_payable,
+        medicare_levy_surcharge: 0.0,
+        family_income: None,
+        tax_payable_if_separate: sources.iter().map(|s| tax_on(s.taxable_income)).sum(),
+        total_withheld,
+        net_position: total_withheld - tax_payable,
+        sources,
+        warnings: missing_rate_warnings(unconverted.into_inner(), fy),
+    }
+}
+
+// The amount in AUD, or nothing with the currency noted when there's no rate for it
+fn to_aud_or_note(
+    amount: f64,
+    code: &str,
+    exchange_rates: &[ExchangeRate],
+    fy: &FinancialYear,
+    unconverted: &RefCell<BTreeSet<String>>,
+) -> f64 {
+    match currency::exchange_rate_for(exchange_rates, code, fy) {
+        Some(rate) => amount * rate,
+        None => {
+            unconverted.borrow_mut().insert(code.to_string());
+            0.0
+        }
+    }
+}
+
+fn missing_rate_warnings(currencies: BTreeSet<String>, fy: &FinancialYear) -> Vec<AnalysisWarning> {
+    currencies.into_iter()
+        .map(|code| AnalysisWarning {
+            code: AnalysisWarningCode::MissingExchangeRate,
+            message: format!("There's no {} exchange rate for {}, so that income counts as nothing", code, fy.label()),
+            related_entity: Some(RelatedEntity::FinancialYear(fy.label())),
+        })
+        .collect()
+}
+
+/// Takes income protection premiums paid outside super, for the part of the year the cover
+/// was held, off the combined taxable income and works out the tax payable again.
+pub fn apply_insurance_deductions(
+    position: &mut CombinedTaxPosition,
+    insurance_policies: &[InsurancePolicy],
+    fy: &FinancialYear,
+    has_help_debt: bool,
+) {
+    let deductions = deductible_premiums(insurance_policies, fy);
+    let taxable = (position.total_taxable_income + position.deductions - deductions).max(0.0);
+    let help = if has_help_debt { tax::help_repayment(taxable, fy) } else { 0.0 };
+
+    position.deductions = deductions;
+    position.total_taxable_income = taxable;
+    position.tax_payable = tax::total_tax_payable(taxable, fy) + help + position.medicare_levy_surcharge;
+    position.net_position = position.total_withheld - position.tax_payable;
+}
+
+/// The partner's income for the financial year, else for the latest year before it, as
+/// incomes carry on from year to year. None without a partner's income on record.
+pub fn partner_income_for(entries: &[PartnerIncomeEntry], fy: &FinancialYear) -> Option<f64> {
+    let year_of = |e: &PartnerIncomeEntry| FinancialYear::parse(&e.financial_year).ok();
+    let latest = entries.iter()
+        .filter_map(year_of)
+        .filter(|year| year <= fy)
+        .max()?;
+    Some(entries.iter().filter(|e| year_of(e) == Some(latest)).map(|e| e.gross_income).sum())
+}
+
+/// Where the income falls for the Medicare levy surcharge and the private health insurance
+/// rebate. With a partner's income the family thresholds apply to the combined income.
+pub fn health_cover_position(
+    fy: &FinancialYear,
+    income_for_mls: f64,
+    partner_income: Option<f64>,
+    oldest_age_on_policy: i32,
+) -> HealthCoverPosition {
+    let family_income = partner_income.map(|partner| income_for_mls + partner);
+    let family = family_income.is_some();
+    let tier = tax::health_income_tier(family_income.unwrap_or(income_for_mls), family, fy);
+    let thresholds = tax::health_tier_thresholds(family, fy);
+
+    HealthCoverPosition {
+        financial_year: fy.label(),
+        income_for_mls,
+        partner_income,
+        family,
+        tier,
+        next_tier_threshold: match tier {
+            HealthIncomeTier::Base => Some(thresholds[0]),
+            HealthIncomeTier::Tier1 => Some(thresholds[1]),
+            HealthIncomeTier::Tier2 => Some(thresholds[2]),
+            HealthIncomeTier::Tier3 => None,
+        },
+        medicare_levy_surcharge: tax::medicare_levy_surcharge(income_for_mls, family_income, fy),
+        rebate_percent: tax::private_health_rebate_percent(tier, oldest_age_on_policy),
+    }
+}
+
+/// Adds the Medicare levy surcharge to the tax payable when the household settings say there's
+/// no private hospital cover, on the family's tier when a partner's income is recorded.
+pub fn apply_medicare_levy_surcharge(
+    position: &mut CombinedTaxPosition,
+    partner_income: Option<f64>,
+    settings: &HouseholdSettings,
+    fy: &FinancialYear,
+) {
+    let family_income = partner_income.map(|partner| position.total_taxable_income + partner);
+    let surcharge = if settings.private_hospital_cover == Some(false) {
+        tax::medicare_levy_surcharge(position.total_taxable_income, family_income, fy)
+    } else {
+        0.0
+    };
+
+    position.tax_payable += surcharge - position.medicare_levy_surcharge;
+    position.medicare_levy_surcharge = surcharge;
+    position.family_income = family_income;
+    position.net_position = position.total_withheld - position.tax_payable;
+}
+
+/// The surcharge tier change when an offer replaces the current pay, on the family's combined
+/// income when a partner's income is recorded. None when the tier stays the same, or either
+/// record is not Australian income.
+pub fn mls_tier_change(
+    current: &CompensationRecord,
+    offer: &CompensationRecord,
+    partner_income: Option<f64>,
+) -> Option<MlsTierChange> {
+    if current.jurisdiction != Jurisdiction::AU || offer.jurisdiction != Jurisdiction::AU
+        || !currency::is_base_currency(&current.currency) || !currency::is_base_currency(&offer.currency) {
+        return None;
+    }
+    let fy = FinancialYear::containing(offer.effective_date);
+    let family = partner_income.is_some();
+    let tier_and_surcharge = |record: &CompensationRecord| {
+        let (_, taxable) = annual_gross_and_taxable(record);
+        let family_income = partner_income.map(|partner| taxable + partner);
+        (
+            tax::health_income_tier(family_income.unwrap_or(taxable), family, &fy),
+            tax::medicare_levy_surcharge(taxable, family_income, &fy),
+        )
+    };
+    let (from, current_surcharge) = tier_and_surcharge(current);
+    let (to, offer_surcharge) = tier_and_surcharge(offer);
+
+    (from != to).then_some(MlsTierChange {
+        family,
+        from,
+        to,
+        surcharge_difference: offer_surcharge - current_surcharge,
+    })
+}
+
+/// A financial year's income, withholding, super, fringe benefits and taxable allowances from
+/// each job, in AUD, for the tax return.
+///
+/// Each job's figures come from its income statement when entered, else its payslips, and
+/// jobs with neither are left out. What those don't hold is filled in from the record in force:
+/// employer super when there are no payslips, salary sacrifice when the income statement has no
+/// reportable super, and reportable fringe benefits from salary packaging. Income statements
+/// and payslips not linked to a position are summed as other income. Income in a currency
+/// without a rate counts for nothing and is named in the warnings.
+pub fn tax_time_summary(
+    fy: &FinancialYear,
+    positions: &[Position],
+    records: &[CompensationRecord],
+    weekly_entries: &[WeeklyCompensationEntry],
+    yearly_entries: &[YearlyIncomeEntry],
+    exchange_rates: &[ExchangeRate],
+) -> TaxTimeSummary {
+    let unconverted = RefCell::new(BTreeSet::new());
+    let to_aud = |amount: f64, code: &str| to_aud_or_note(amount, code, exchange_rates, fy, &unconverted);
+    // A rollover placeholder would otherwise win over the year's payslips with nothing
+    let yearly: Vec<&YearlyIncomeEntry> = yearly_entries.iter()
+        .filter(|e| !e.is_placeholder())
+        .filter(|e| e.jurisdiction == Jurisdiction::AU && FinancialYear::parse(&e.financial_year).ok() == Some(*fy))
+        .collect();
+    let weekly: Vec<&WeeklyCompensationEntry> = weekly_entries.iter()
+        .filter(|e| FinancialYear::containing(e.week_ending) == *fy)
+        .collect();
+    let taxable_allowances = |allowances: &[Allowance], code: &str| -> f64 {
+        allowances.iter().filter(|a| a.taxable).map(|a| to_aud(a.amount, code)).sum()
+    };
+
+    let source_for = |position: Option<&Position>, employer_name: String| -> Option<TaxTimeIncome> {
+        let position_id = position.and_then(|p| p.id);
+        let statements: Vec<&&YearlyIncomeEntry> = yearly.iter().filter(|e| e.position_id == position_id).collect();
+        let payslips: Vec<&&WeeklyCompensationEntry> = weekly.iter().filter(|e| e.position_id == position_id).collect();
+        if statements.is_empty() && payslips.is_empty() {
+            return None;
+        }
+        let record = position
+            .and_then(|p| record_for_fy(p, records, fy))
+            .filter(|(record, _)| record.jurisdiction == Jurisdiction::AU);
+        let from_record = |annual: fn(&CompensationRecord) -> f64| {
+            record.map_or(0.0, |(record, share_of_year)| to_aud(annual(record), &record.currency) * share_of_year)
+        };
+
+        let employer_super = if payslips.is_empty() {
+            from_record(|r| r.annual_employer_super())
+        } else {
+            payslips.iter().map(|e| to_aud(e.super_contributed, &e.currency)).sum()
+        };
+        let reported_super: f64 = statements.iter().map(|e| to_aud(e.reportable_super, &e.currency)).sum();
+        let salary_sacrifice = if reported_super > 0.0 {
+            reported_super
+        } else {
+            from_record(|r| r.super_contributions.salary_sacrifice)
+        };
+        let reported_fringe_benefits: Option<f64> = statements.iter()
+            .filter_map(|e| e.reportable_fringe_benefits.map(|amount| to_aud(amount, &e.currency)))
+            .reduce(|a, b| a + b);
+        let reportable_fringe_benefits = reported_fringe_benefits.unwrap_or_else(|| {
+            position
+                .and_then(|p| expected_reportable_fringe_benefits(p, records, exchange_rates, fy))
+                .unwrap_or(0.0)
+        });
+
+        let (method, gross_income, tax_withheld, allowances) = if statements.is_empty() {
+            (
+                TaxedIncomeMethod::Payslips,
+                payslips.iter().map(|e| to_aud(e.gross_pay, &e.currency)).sum(),
+                payslips.iter().map(|e| to_aud(e.tax_withheld, &e.currency)).sum(),
+                payslips.iter().map(|e| taxable_allowances(&e.allowances, &e.currency)).sum(),
+            )
+        } else {
+            (
+                TaxedIncomeMethod::YearlyEntry,
+                statements.iter().map(|e| to_aud(e.gross_income, &e.currency)).sum(),
+                statements.iter().map(|e| to_aud(e.tax_withheld, &e.currency)).sum(),
+                statements.iter().map(|e| taxable_allowances(&e.allowances, &e.currency)).sum(),
+            )
+        };
+
+        Some(TaxTimeIncome {
+            position_id,
+            employer_name,
+            method,
+            gross_income,
+            tax_withheld,
+            employer_super,
+            salary_sacrifice,
+            reportable_fringe_benefits,
+            taxable_allowances: allowances,
+        })
+    };
+
+    let mut sources: Vec<TaxTimeIncome> = positions.iter()
+        .filter(|p| p.id.is_some())
+        .filter_map(|p| source_for(Some(p), p.employer_name.clone()))
+        .collect();
+    sources.extend(source_for(None, "Other income".to_string()));
+
+    let employer_super: f64 = sources.iter().map(|s| s.employer_super).sum();
+    let salary_sacrifice: f64 = sources.iter().map(|s| s.salary_sacrifice).sum();
+    TaxTimeSummary {
+        financial_year: fy.label(),
+        total_gross_income: sources.iter().map(|s| s.gross_income).sum(),
+        total_tax_withheld: sources.iter().map(|s| s.tax_withheld).sum(),
+        employer_super,
+        salary_sacrifice,
+        reportable_super: employer_super + salary_sacrifice,
+        reportable_fringe_benefits: sources.iter().map(|s| s.reportable_fringe_benefits).sum(),
+        taxable_allowances: sources.iter().map(|s| s.taxable_allowances).sum(),
+        sources,
+        warnings: missing_rate_warnings(unconverted.into_inner(), fy),
+    }
+}
+
+/// Points out that no income protection is recorded while current pay is above the settings'
+/// threshold. Off when the settings turn it off; it states what's recorded and stops there.
+pub fn income_protection_insight(
+    policies: &[InsurancePolicy],
+    positions: &[Position],
+    records: &[CompensationRecord],
+    settings: &InsuranceSettings,
+    today: NaiveDate,
+) -> Option<EarningsInsight> {
+    if !settings.income_protection_insight {
+        return None;
+    }
+    if policies.iter().any(|p| p.policy_type == InsuranceType::IncomeProtection && p.is_held_on(today)) {
+        return None;
+    }
+    let current = current_positions(positions, today);
+    let annual_pay: f64 = current.iter()
+        .filter_map(|p| latest_record_for(p, records))
+        .filter(|r| currency::is_base_currency(&r.currency))
+        .map(|r| r.annual_base() + r.annual_overtime_pay() + r.annual_allowances())
+        .sum();
+    if annual_pay <= settings.income_protection_threshold {
+        return None;
+    }
+
+    Some(EarningsInsight {
+        category: InsightCategory::Insurance,
+        title: "No Income Protection Recorded".to_string(),
+        description: format!(
+            "Your current pay is ${:.0} a year and no income protection policy is recorded. Premiums held outside super are tax deductible.",
+            annual_pay
+        ),
+        confidence_level: 0.6,
+        data_points: vec![
+            format!("Recorded policies: {}", policies.iter().filter(|p| p.is_held_on(today)).count()),
+            "Cover held through super may not be recorded here".to_string(),
+        ],
+        position_ids: current.into_iter().filter_map(|p| p.id).collect(),
+    })
+}
+
+// Fewer pay periods than this say too little about how often overtime is worked
+const MIN_OVERTIME_PERIODS: u32 = 8;
+
+// Share of pay periods with overtime at or above which each frequency is implied
+const OCCASIONAL_OVERTIME_SHARE: f64 = 0.1;
+const FREQUENT_OVERTIME_SHARE: f64 = 0.5;
+
+// Overtime averaging this much a week across all periods reads as three or more days of it
+const EXTREME_OVERTIME_HOURS_PER_WEEK: f64 = 10.0;
+
+// How far the observed share can sit outside the declared frequency's range before it's flagged
+const OVERTIME_SHARE_DIVERGENCE: f64 = 0.25;
+
+fn overtime_frequency_rank(frequency: &OvertimeFrequency) -> i32 {
+    match frequency {
+        OvertimeFrequency::None => 0,
+        OvertimeFrequency::Occasional => 1,
+        OvertimeFrequency::Frequent => 2,
+        OvertimeFrequency::Extreme => 3,
+    }
+}
+
+// The share of pay periods with overtime each declared frequency would produce
+fn overtime_share_range(frequency: &OvertimeFrequency) -> (f64, f64) {
+    match frequency {
+        OvertimeFrequency::None => (0.0, OCCASIONAL_OVERTIME_SHARE),
+        OvertimeFrequency::Occasional => (OCCASIONAL_OVERTIME_SHARE, FREQUENT_OVERTIME_SHARE),
+        OvertimeFrequency::Frequent | OvertimeFrequency::Extreme => (FREQUENT_OVERTIME_SHARE, 1.0),
+    }
+}
+
+/// How often overtime actually shows up in a position's pay entries, against what the declared
+/// record says. Only periods ending on or after the record's effective date count, since earlier
+/// ones were worked under different terms. When the two diverge badly, the suggested correction
+/// is the record's overtime with the observed frequency and hours, ready to save back.
+pub fn infer_overtime_profile(
+    position_id: i64,
+    weekly_entries: &[WeeklyCompensationEntry],
+    declared: Option<&CompensationRecord>,
+) -> OvertimeProfile {
+    let entries: Vec<&WeeklyCompensationEntry> = weekly_entries.iter()
+        .filter(|e| e.position_id == Some(position_id))
+        .filter(|e| declared.is_none_or(|r| e.week_ending >= r.effective_date))
+        .collect();
+    let periods_observed = entries.len() as u32;
+    let periods_with_overtime = entries.iter().filter(|e| e.hours_overtime > 0.0).count() as u32;
+    let weeks: f64 = entries.iter().map(|e| 52.0 / e.period_type.periods_per_year()).sum();
+    let overtime_hours: f64 = entries.iter().map(|e| e.hours_overtime).sum();
+
+    let observed_share = if periods_observed > 0 { periods_with_overtime as f64 / periods_observed as f64 } else { 0.0 };
+    let average_hours_per_week = if weeks > 0.0 { overtime_hours / weeks } else { 0.0 };
+    let implied_frequency = if observed_share < OCCASIONAL_OVERTIME_SHARE {
+        OvertimeFrequency::None
+    } else if observed_share < FREQUENT_OVERTIME_SHARE {
+        OvertimeFrequency::Occasional
+    } else if average_hours_per_week >= EXTREME_OVERTIME_HOURS_PER_WEEK {
+        OvertimeFrequency::Extreme
+    } else {
+        OvertimeFrequency::Frequent
+    };
+
+    let declared_overtime = declared.map(|r| &r.overtime);
+    let diverges = periods_observed >= MIN_OVERTIME_PERIODS
+        && declared_overtime.is_some_and(|o| {
+            let (low, high) = overtime_share_range(&o.frequency);
+            observed_share < low - OVERTIME_SHARE_DIVERGENCE || observed_share > high + OVERTIME_SHARE_DIVERGENCE
+        });
+    let suggested_correction = declared.filter(|_| diverges).and_then(|record| {
+        Some(OvertimeCorrection {
+            record_id: record.id?,
+            overtime: OvertimeDetails {
+                frequency: implied_frequency.clone(),
+                rate_multiplier: record.overtime.rate_multiplier,
+                average_hours_per_week,
+                annual_hours: record.overtime.annual_hours.map(|_| average_hours_per_week * 52.0),
+            },
+        })
+    });
+
+    OvertimeProfile {
+        position_id,
+        periods_observed,
+        periods_with_overtime,
+        observed_percent: observed_share * 100.0,
+        average_hours_per_week,
+        frequency_steps: declared_overtime
+            .map(|o| overtime_frequency_rank(&implied_frequency) - overtime_frequency_rank(&o.frequency)),
+        hours_delta: declared_overtime.map(|o| average_hours_per_week - o.average_hours_per_week),
+        declared_frequency: declared_overtime.map(|o| o.frequency.clone()),
+        declared_hours_per_week: declared_overtime.map(|o| o.average_hours_per_week),
+        implied_frequency,
+        diverges,
+        suggested_correction,
+    }
+}
+
+/// One insight listing the current positions whose pay entries badly contradict the overtime
+/// declared on their latest record, or None when they all roughly agree.
+pub fn overtime_profile_insight(
+    positions: &[Position],
+    records: &[CompensationRecord],
+    weekly_entries: &[WeeklyCompensationEntry],
+    today: NaiveDate,
+) -> Option<EarningsInsight> {
+    let diverging: Vec<(&Position, OvertimeProfile)> = current_positions(positions, today).into_iter()
+        .filter_map(|p| {
+            let profile = infer_overtime_profile(p.id?, weekly_entries, latest_record_for(p, records));
+            profile.diverges.then_some((p, profile))
+        })
+        .collect();
+    if diverging.is_empty() {
+        return None;
+    }
+
+    Some(EarningsInsight {
+        category: InsightCategory::DataQuality,
+        title: "Overtime Doesn't Match Your Pay Entries".to_string(),
+        description: "How often you record working overtime differs from what your pay entries show. Updating the record makes overtime earnings and projections more accurate.".to_string(),
+        confidence_level: 0.8,
+        data_points: diverging.iter()
+            .map(|(p, profile)| format!(
+                "{}: recorded as {:?}, {} of {} pay periods had overtime",
+                p.employer_name,
+                profile.declared_frequency.as_ref().unwrap_or(&OvertimeFrequency::None),
+                profile.periods_with_overtime,
+                profile.periods_observed,
+            ))
+            .collect(),
+        position_ids: diverging.iter().filter_map(|(p, _)| p.id).collect(),
+    })
+}
+
+// Less history than this can't separate a seasonal pattern from a one-off
+pub const SEASONALITY_MIN_MONTHS: u32 = 18;
+
+// The best and worst months must be this many index points apart to be worth pointing out
+const SEASONAL_SPREAD_POINTS: f64 = 15.0;
+
+/// Gross pay and hours by calendar month, averaged over the years each month was worked, with
+/// an index where 100 is the average month. `years` limits it to the most recent calendar years.
+///
+/// Each pay period is spread evenly over its days. Only months wholly inside the span of the
+/// entries count, so a history starting in October doesn't make October look light, and months
+/// with no pay entries are left out rather than counted as unpaid.
+pub fn seasonality(weekly_entries: &[WeeklyCompensationEntry], years: Option<u32>) -> Result<EarningsSeasonality, AppError> {
+    let mut entries: Vec<&WeeklyCompensationEntry> = weekly_entries.iter()
+        .filter(|e| currency::is_base_currency(&e.currency))
+        .collect();
+    if let (Some(years), Some(latest)) = (years, entries.iter().map(|e| e.week_ending.year()).max()) {
+        entries.retain(|e| e.week_ending.year() > latest - years as i32);
+    }
+    let period_start = |e: &WeeklyCompensationEntry| e.period_start.unwrap_or_else(|| dates::period_start_for(e.week_ending, &e.period_type));
+    let not_enough = |have_months| AppError::NotEnoughData { needed_months: SEASONALITY_MIN_MONTHS, have_months };
+    let (Some(span_start), Some(span_end)) = (
+        entries.iter().map(|e| period_start(e)).min(),
+        entries.iter().map(|e| e.week_ending).max(),
+    ) else {
+        return Err(not_enough(0));
+    };
+
+    // (year, month) -> (gross, hours)
+    let mut totals: BTreeMap<(i32, u32), (f64, f64)> = BTreeMap::new();
+    for entry in &entries {
+        let start = period_start(entry);
+        let days = (entry.week_ending - start).num_days().max(0) + 1;
+        for day in start.iter_days().take(days as usize) {
+            let total = totals.entry((day.year(), day.month())).or_insert((0.0, 0.0));
+            total.0 += entry.gross_pay / days as f64;
+            total.1 += (entry.hours_ordinary + entry.hours_overtime) / days as f64;
+        }
+    }
+    totals.retain(|&(year, month), _| {
+        let first = NaiveDate::from_ymd_opt(year, month, 1);
+        let last = first.and_then(|d| d.checked_add_months(Months::new(1))).and_then(|d| d.pred_opt());
+        matches!((first, last), (Some(first), Some(last)) if first >= span_start && last <= span_end)
+    });
+    let months_of_data = totals.len() as u32;
+    if months_of_data < SEASONALITY_MIN_MONTHS {
+        return Err(not_enough(months_of_data));
+    }
+
+    let mut year_totals: BTreeMap<i32, (f64, u32)> = BTreeMap::new();
+    for (&(year, _), &(gross, _)) in &totals {
+        let year_total = year_totals.entry(year).or_insert((0.0, 0));
+        year_total.0 += gross;
+        year_total.1 += 1;
+    }
+    let year_mean = |year: i32| year_totals.get(&year).map(|&(gross, months)| gross / months as f64).unwrap_or(0.0);
+
+    let mut months: Vec<SeasonalMonth> = (1..=12)
+        .filter_map(|month| {
+            let observed: Vec<(i32, f64, f64)> = totals.iter()
+                .filter(|(&(_, m), _)| m == month)
+                .map(|(&(year, _), &(gross, hours))| (year, gross, hours))
+                .collect();
+            if observed.is_empty() {
+                return None;
+            }
+            let count = observed.len() as f64;
+            let above = observed.iter().filter(|&&(year, gross, _)| gross > year_mean(year)).count() as f64;
+            Some(SeasonalMonth {
+                month,
+                years_observed: observed.len() as u32,
+                average_gross: observed.iter().map(|o| o.1).sum::<f64>() / count,
+                average_hours: observed.iter().map(|o| o.2).sum::<f64>() / count,
+                index: 0.0,
+                hours_index: 0.0,
+                above_average_percent: above / count * 100.0,
+            })
+        })
+        .collect();
+
+    let mean_gross = months.iter().map(|m| m.average_gross).sum::<f64>() / months.len() as f64;
+    let mean_hours = months.iter().map(|m| m.average_hours).sum::<f64>() / months.len() as f64;
+    for month in &mut months {
+        month.index = if mean_gross > 0.0 { month.average_gross / mean_gross * 100.0 } else { 0.0 };
+        month.hours_index = if mean_hours > 0.0 { month.average_hours / mean_hours * 100.0 } else { 0.0 };
+    }
+
+    // Months seen in more than one year say more than a single observation
+    let repeated: Vec<&SeasonalMonth> = months.iter().filter(|m| m.years_observed > 1).collect();
+    let candidates: Vec<&SeasonalMonth> = if repeated.is_empty() { months.iter().collect() } else { repeated };
+    let strongest_month = candidates.iter().max_by(|a, b| a.index.total_cmp(&b.index)).map(|m| m.month);
+    let weakest_month = candidates.iter().min_by(|a, b| a.index.total_cmp(&b.index)).map(|m| m.month);
+
+    Ok(EarningsSeasonality {
+        months_of_data,
+        years: year_totals.keys().copied().collect(),
+        months,
+        strongest_month,
+        weakest_month,
+    })
+}
+
+fn month_name(month: u32) -> &'static str {
+    u8::try_from(month).ok()
+        .and_then(|m| chrono::Month::try_from(m).ok())
+        .map(|m| m.name())
+        .unwrap_or("Unknown")
+}
+
+/// Names the months that have paid the most and least when they sit far enough apart, as
+/// context for timing unpaid leave or a pay conversation.
+pub fn seasonality_insight(seasonality: &EarningsSeasonality) -> Option<EarningsInsight> {
+    let find = |month: Option<u32>| seasonality.months.iter().find(|m| Some(m.month) == month);
+    let (strongest, weakest) = (find(seasonality.strongest_month)?, find(seasonality.weakest_month)?);
+    if strongest.index - weakest.index < SEASONAL_SPREAD_POINTS {
+        return None;
+    }
+
+    Some(EarningsInsight {
+        category: InsightCategory::IncomeRisk,
+        title: "Your Earnings Follow the Seasons".to_string(),
+        description: format!(
+            "{} has paid {:.0}% above your average month and {} {:.0}% below. Unpaid leave in {} gives up less of your usual income, and a pay conversation around {} comes with your strongest recent earnings.",
+            month_name(strongest.month),
+            strongest.index - 100.0,
+            month_name(weakest.month),
+            100.0 - weakest.index,
+            month_name(weakest.month),
+            month_name(strongest.month),
+        ),
+        confidence_level: (seasonality.months_of_data as f64 / 36.0).min(0.9),
+        data_points: vec![
+            format!("{} months of pay entries across {} calendar years", seasonality.months_of_data, seasonality.years.len()),
+            format!("{} was above that year's average in {:.0}% of years", month_name(strongest.month), strongest.above_average_percent),
+            format!("{} was above that year's average in {:.0}% of years", month_name(weakest.month), weakest.above_average_percent),
+        ],
+        position_ids: Vec::new(),
+    })
+}
+
+/// The gross weekly pay and annual base salary that leave `net_weekly` after PAYG
+/// withholding in the financial year, found by inverting the annual approximation of the
+/// withholding schedules. Super is added at the guarantee rate, on top of the base.
+///
+/// The band is the base for a net pay 3% either side, covering the weekly tables' rounding and
+/// offsets that are only settled at tax time. Errors unless `net_weekly` is positive.
+pub fn reverse_engineer_base(
+    net_weekly: f64,
+    fy: &FinancialYear,
+    claims_tax_free_threshold: bool,
+) -> Result<ReverseBaseEstimate, String> {
+    if !(net_weekly.is_finite() && net_weekly > 0.0) {
+        return Err("Net weekly pay must be a positive amount".to_string());
+    }
+    let annual_base_for = |net_weekly: f64| gross_for_net(net_weekly * 52.0, claims_tax_free_threshold, fy);
+    let annual_base = annual_base_for(net_weekly);
+    let employer_super = annual_base * super_guarantee_rate(fy) / 100.0;
+
+    Ok(ReverseBaseEstimate {
+        financial_year: fy.label(),
+        net_weekly,
+        claims_tax_free_threshold,
+        gross_weekly: annual_base / 52.0,
+        tax_withheld_weekly: annual_base / 52.0 - net_weekly,
+        annual_base,
+        employer_super,
+        total_package: annual_base + employer_super,
+        annual_base_low: annual_base_for(net_weekly * (1.0 - REVERSE_BASE_NET_TOLERANCE)),
+        annual_base_high: annual_base_for(net_weekly * (1.0 + REVERSE_BASE_NET_TOLERANCE)),
+    })
+}
+
+/// What salary packaging a car through a novated lease saves against buying it on the same
+/// finance out of take-home pay, a year over the lease.
+///
+/// The lease finances the car less the GST credit the employer claims, to the ATO minimum
+/// residual for the term, and running costs are packaged without GST on the per-km costs.
+/// FBT is the statutory formula, cancelled under the employee contribution method by paying
+/// its taxable value post-tax; the rest comes out of gross pay and saves tax at
+/// `marginal_rate`, a percentage including the Medicare levy. The residual, due at the end,
+/// is spread over the term on both sides.
+///
+/// The saving is returned as a non-cash benefit to add to the compensation record: the car
+/// itself is paid for from the salary already counted, so only the saving adds to the package.
+pub fn novated_lease_analysis(
+    vehicle_cost: f64,
+    lease_term_months: u32,
+    annual_km: f64,
+    marginal_rate: f64,
+) -> Result<NovatedLeaseAnalysis, String> {
+    if !(vehicle_cost.is_finite() && vehicle_cost > 0.0) {
+        return Err("Vehicle cost must be a positive amount".to_string());
+    }
+    if !(12..=60).contains(&lease_term_months) {
+        return Err("Novated leases run for one to five years (12 to 60 months)".to_string());
+    }
+    if !(annual_km.is_finite() && annual_km >= 0.0) {
+        return Err("Annual kilometres can't be negative".to_string());
+    }
+    if !(0.0..=100.0).contains(&marginal_rate) {
+        return Err("Marginal tax rate must be a percentage between 0 and 100".to_string());
+    }
+
+    let years = lease_term_months as f64 / 12.0;
+    let residual_share = tax::minimum_lease_residual(lease_term_months);
+    let annual_cost_financing = |amount: f64| {
+        lease_payment(amount, amount * residual_share, lease_term_months) * 12.0 + amount * residual_share / years
+    };
+
+    let gst_credit = vehicle_cost.min(tax::CAR_LIMIT) * GST_RATE / (1.0 + GST_RATE);
+    let amount_financed = vehicle_cost - gst_credit;
+    let annual_lease_cost = annual_cost_financing(amount_financed);
+    let per_km_costs = annual_km * RUNNING_COST_PER_KM;
+    let annual_running_costs = per_km_costs / (1.0 + GST_RATE) + FIXED_RUNNING_COSTS;
+
+    // Averaged over the term, so a five-year lease picks up the reduced base value in its last year
+    let fbt_taxable_value = (0..lease_term_months.div_ceil(12))
+        .map(|year| {
+            let months_in_year = (lease_term_months - year * 12).min(12) as f64;
+            tax::car_statutory_taxable_value(vehicle_cost, year) * months_in_year / 12.0
+        })
+        .sum::<f64>() / years;
+
+    let annual_package_cost = annual_lease_cost + annual_running_costs;
+    let post_tax_contribution = fbt_taxable_value.min(annual_package_cost);
+    let pre_tax_contribution = annual_package_cost - post_tax_contribution;
+    let income_tax_saved = pre_tax_contribution * marginal_rate / 100.0;
+    let annual_cost_with_lease = annual_package_cost - income_tax_saved;
+    let annual_cost_without_lease = annual_cost_financing(vehicle_cost) + per_km_costs + FIXED_RUNNING_COSTS;
+    let annual_net_benefit = annual_cost_without_lease - annual_cost_with_lease;
+
+    Ok(NovatedLeaseAnalysis {
+        vehicle_cost,
+        lease_term_months,
+        annual_km,
+        marginal_rate,
+        gst_credit,
+        amount_financed,
+        residual_value: amount_financed * residual_share,
+        annual_lease_cost,
+        annual_running_costs,
+        fbt_taxable_value,
+        pre_tax_contribution,
+        post_tax_contribution,
+        income_tax_saved,
+        annual_cost_with_lease,
+        annual_cost_without_lease,
+        annual_net_benefit,
+        non_cash_benefit: NonCashBenefit {
+            name: "Novated lease saving".to_string(),
+            annual_value: annual_net_benefit.max(0.0),
+            fringe_benefit: false,
+        },
+    })
+}
+
+// Monthly repayment that pays a loan down to `residual` over the term at the novated lease rate
+fn lease_payment(principal: f64, residual: f64, months: u32) -> f64 {
+    let monthly_rate = NOVATED_LEASE_INTEREST_RATE / 100.0 / 12.0;
+    let discount = (1.0 + monthly_rate).powi(-(months as i32));
+    (principal - residual * discount) * monthly_rate / (1.0 - discount)
+}
+
+/// The gross salary that leaves `target_net` a year once the year's tax is assessed: income
+/// tax less the low income tax offset, the Medicare levy, and HELP repayments when the
+/// profile has a HELP debt. Super is paid on top of the salary at the guarantee rate, so it's
+/// added for the total package rather than taken out of the net.
+///
+/// Rates are percentages; the marginal rate is on the next dollar of salary. Errors unless
+/// `target_net` is positive.
+pub fn gross_for_target_net(
+    target_net: f64,
+    fy: &FinancialYear,
+    profile: &Option<UserProfile>,
+) -> Result<TargetNetEstimate, String> {
+    if !(target_net.is_finite() && target_net > 0.0) {
+        return Err("Target take-home pay must be a positive amount".to_string());
+    }
+    let has_help_debt = profile.as_ref().is_some_and(|p| p.has_help_debt);
+    let help_on = |gross: f64| if has_help_debt { tax::help_repayment(gross, fy) } else { 0.0 };
+    let deductions_on = |gross: f64| tax::total_tax_payable(gross, fy) + help_on(gross);
+
+    let gross_salary = invert_net(target_net, |gross| gross - deductions_on(gross));
+    let deductions = deductions_on(gross_salary);
+    let employer_super = gross_salary * super_guarantee_rate(fy) / 100.0;
+
+    Ok(TargetNetEstimate {
+        financial_year: fy.label(),
+        target_net,
+        gross_salary,
+        employer_super,
+        total_package: gross_salary + employer_super,
+        tax_payable: deductions - help_on(gross_salary),
+        help_repayment: help_on(gross_salary),
+        average_tax_rate: if gross_salary > 0.0 { deductions / gross_salary * 100.0 } else { 0.0 },
+        marginal_tax_rate: (deductions_on(gross_salary + 1.0) - deductions) * 100.0,
+    })
+}
+
+// Gross income whose expected withholding leaves `annual_net`, by bisection; net pay only
+// grows with gross, so there's one answer
+fn gross_for_net(annual_net: f64, claims_tax_free_threshold: bool, fy: &FinancialYear) -> f64 {
+    invert_net(annual_net, |gross| gross - tax::expected_payg_withholding(gross, claims_tax_free_threshold, fy))
+}
+
+fn invert_net(annual_net: f64, net_of: impl Fn(f64) -> f64) -> f64 {
+    let (mut low, mut high) = (annual_net, annual_net * 2.0 + 1.0);
+    while net_of(high) < annual_net {
+        high *= 2.0;
+    }
+    for _ in 0..100 {
+        let mid = (low + high) / 2.0;
+        if net_of(mid) < annual_net {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    (low + high) / 2.0
+}
+
+/// The profile's super balance projected each year to age 67: a year's return on the balance,
+/// then the year's contributions less contributions tax, less premiums for cover held through
+/// super. Contributions are the employer super,
+/// salary sacrifice and additional contributions on the current Australian positions' latest
+/// records, growing at the current position's market salary growth. The low and high series
+/// take the spreads off and onto both the return and the growth.
+pub fn project_super(
+    positions: &[Position],
+    records: &[CompensationRecord],
+    profile: &UserProfile,
+    assumptions: &ProjectionAssumptions,
+    insurance_policies: &[InsurancePolicy],
+    today: NaiveDate,
+) -> SuperProjection {
+    let starting_age = dates::age_on(profile.date_of_birth, today);
+    let years = (SUPER_PROJECTION_AGE - starting_age).max(0) as u32;
+    let starting_balance = profile.total_super_balance.unwrap_or(0.0);
+    let annual_contributions: f64 = current_positions(positions, today).into_iter()
+        .filter_map(|p| latest_record_for(p, records))
+        .filter(|r| r.jurisdiction == Jurisdiction::AU)
+        .map(|r| {
+            r.annual_employer_super()
+                + r.super_contributions.salary_sacrifice
+                + r.super_contributions.additional_contributions
+        })
+        .sum();
+    let insurance_premiums: f64 = insurance_policies.iter()
+        .filter(|p| p.through_super && p.is_held_on(today))
+        .map(|p| p.annual_premium)
+        .sum();
+    let salary_growth = current_position(positions, today).map_or(0.05, |p| market_growth_rate(&p.seniority_level)) * 100.0;
+
+    let points = projection_points(years, |direction| {
+        let investment_return = (assumptions.investment_return + direction * assumptions.return_spread) / 100.0;
+        let growth = (salary_growth + direction * assumptions.salary_growth_spread) / 100.0;
+        let (mut balance, mut contributions) = (starting_balance, annual_contributions);
+        let mut series = vec![balance];
+        for _ in 0..years {
+            balance = (balance * (1.0 + investment_return) + contributions * (1.0 - CONTRIBUTIONS_TAX_RATE)
+                - insurance_premiums).max(0.0);
+            contributions *= 1.0 + growth;
+            series.push(balance);
+        }
+        series
+    });
+
+    SuperProjection {
+        starting_age,
+        starting_balance,
+        annual_contributions,
+        insurance_premiums,
+        salary_growth,
+        investment_return: assumptions.investment_return,
+        points,
+    }
+}
+
+/// A position's annual gross on its latest record, grown each year for `years` (10 by default)
+/// at the market salary growth for its seniority, with the low and high series the salary
+/// growth spread either side. Errors when the position has no compensation record.
+pub fn project_salary(
+    position: &Position,
+    records: &[CompensationRecord],
+    assumptions: &ProjectionAssumptions,
+    years: Option<u32>,
+) -> Result<SalaryProjection, String> {
+    let position_id = position.id.ok_or("The position hasn't been saved")?;
+    let record = latest_record_for(position, records).ok_or("The position has no compensation record")?;
+    let (current_salary, _) = annual_gross_and_taxable(record);
+    let years = years.unwrap_or(DEFAULT_SALARY_PROJECTION_YEARS);
+    let salary_growth = market_growth_rate(&position.seniority_level) * 100.0;
+
+    let points = projection_points(years, |direction| {
+        let growth = 1.0 + (salary_growth + direction * assumptions.salary_growth_spread) / 100.0;
+        (0..=years).map(|year| current_salary * growth.powi(year as i32)).collect()
+    });
+
+    Ok(SalaryProjection {
+        position_id,
+        current_salary,
+        salary_growth,
+        points,
+    })
+}
+
+// A point per year from the series `series(direction)` builds for each direction: -1 for
+// low, 0 for expected and 1 for high. Each series starts at year 0.
+fn projection_points(years: u32, series: impl Fn(f64) -> Vec<f64>) -> Vec<ProjectionPoint> {
+    let (low, expected, high) = (series(-1.0), series(0.0), series(1.0));
+    (0..=years)
+        .map(|year| {
+            let i = year as usize;
+            ProjectionPoint { years_from_now: year, low: low[i], expected: expected[i], high: high[i] }
+        })
+        .collect()
+}
+
+fn market_growth_rate(seniority: &SeniorityLevel) -> f64 {
+    MARKET_GROWTH_RATES
+        .iter()
+        .find(|(level, _)| std::mem::discriminant(level) == std::mem::discriminant(seniority))
+        .map(|(_, rate)| *rate)
+        .unwrap_or(0.05)
+}
+
+// A qualification that hasn't paid for itself by then isn't reported as paying back
+const MAX_PAYBACK_YEARS: u32 = 40;
+
+const QUALIFICATION_BENEFIT_YEARS: u32 = 10;
+
+// The level a qualification typically lets you step up to; the top of the ladder stays put
+fn next_seniority_level(level: &SeniorityLevel) -> SeniorityLevel {
+    match level {
+        SeniorityLevel::Entry => SeniorityLevel::Junior,
+        SeniorityLevel::Junior => SeniorityLevel::Mid,
+        SeniorityLevel::Mid => SeniorityLevel::Senior,
+        SeniorityLevel::Senior => SeniorityLevel::Lead,
+        SeniorityLevel::Lead | SeniorityLevel::Manager => SeniorityLevel::Director,
+        SeniorityLevel::Director | SeniorityLevel::Executive => SeniorityLevel::Executive,
+    }
+}
+
+/// What further study costs and returns, before tax: the course cost and the pay given up while
+/// studying full time, against a yearly uplift once qualified that grows at the market rate for
+/// the current level. Without an expected uplift, it's the gap between the base estimates for
+/// the current level and the next.
+///
+/// Payback counts from the start of study and is None when it takes more than
+/// `MAX_PAYBACK_YEARS`.
+pub fn qualification_roi(
+    cost: f64,
+    expected_salary_uplift: Option<f64>,
+    study_years: f64,
+    profile: &UserProfile,
+    positions: &[Position],
+    records: &[CompensationRecord],
+    today: NaiveDate,
+) -> QualificationRoi {
+    let current = current_position(positions, today);
+    let seniority = current.map(|p| p.seniority_level.clone()).unwrap_or(SeniorityLevel::Entry);
+    let current_pay: f64 = current_positions(positions, today).iter()
+        .filter_map(|p| latest_record_for(p, records))
+        .filter(|r| currency::is_base_currency(&r.currency))
+        .map(|r| r.annual_base() + r.annual_overtime_pay() + r.annual_allowances())
+        .sum();
+    let current_annual_pay = match (current_pay > 0.0, current) {
+        (false, Some(position)) => position.base_salary_estimate(),
+        _ => current_pay,
+    };
+    let default_uplift = current.map(|position| {
+        let stepped_up = Position { seniority_level: next_seniority_level(&seniority), ..position.clone() };
+        stepped_up.base_salary_estimate() - position.base_salary_estimate()
+    });
+    let uplift_is_estimate = expected_salary_uplift.is_none() && default_uplift.is_some();
+    let expected_salary_uplift = expected_salary_uplift.or(default_uplift).unwrap_or(0.0);
+    let growth = market_growth_rate(&seniority);
+
+    // Month by month from the start of study
+    let study_months = (study_years.max(0.0) * 12.0).round() as u32;
+    let mut cumulative = -cost;
+    let mut payback_years = (cumulative >= 0.0).then_some(0.0);
+    let mut ten_year_net_benefit = cumulative;
+    for month in 1..=MAX_PAYBACK_YEARS * 12 {
+        if month <= study_months {
+            cumulative -= current_annual_pay / 12.0;
+        } else {
+            let years_qualified = (month - study_months - 1) / 12;
+            cumulative += expected_salary_uplift * (1.0 + growth).powi(years_qualified as i32) / 12.0;
+        }
+        if payback_years.is_none() && cumulative >= 0.0 {
+            payback_years = Some(month as f64 / 12.0);
+        }
+        if month == QUALIFICATION_BENEFIT_YEARS * 12 {
+            ten_year_net_benefit = cumulative;
+        }
+    }
+
+    let foregone_earnings = current_annual_pay * study_months as f64 / 12.0;
+    QualificationRoi {
+        current_qualification: profile.highest_qualification.clone(),
+        cost,
+        study_years,
+        current_annual_pay,
+        foregone_earnings,
+        total_investment: cost + foregone_earnings,
+        expected_salary_uplift,
+        uplift_is_estimate,
+        market_growth_percent: growth * 100.0,
+        payback_years,
+        ten_year_net_benefit,
+    }
+}
+
+/// Earnings over a whole working life to age 67: what's been earned so far plus current pay
+/// projected to retirement, in nominal and today's dollars.
+///
+/// Past earnings are each started position's pay over the time it was held, from the record in
+/// force at the time (or its estimate for the level without one), converted to AUD. They're
+/// brought to today's dollars with CPI for the financial years since, or the assumed inflation
+/// where there's no figure. Future pay starts from the current positions' latest records and
+/// grows each year at `assumed_growth` (percent), else the market rate for the current level,
+/// stepping up a level at the industry's typical pace until Lead. Further steps aren't assumed.
+pub fn project_lifetime_earnings(
+    profile: &UserProfile,
+    positions: &[Position],
+    records: &[CompensationRecord],
+    exchange_rates: &[ExchangeRate],
+    assumed_growth: Option<f64>,
+    today: NaiveDate,
+) -> LifetimeEarningsProjection {
+    let current_fy = FinancialYear::containing(today);
+    let inflation = |start_year: i32| CPI_ANNUAL_CHANGE.iter()
+        .find(|(year, _)| *year == start_year)
+        .map_or(ASSUMED_INFLATION_PERCENT, |(_, change)| *change) / 100.0;
+    let gross_aud = |record: &CompensationRecord, fy: &FinancialYear| {
+        annual_gross_and_taxable(record).0
+            * currency::exchange_rate_for(exchange_rates, &record.currency, fy).unwrap_or(0.0)
+    };
+
+    let (mut historical_nominal, mut historical_real) = (0.0, 0.0);
+    for position in positions.iter().filter(|p| p.start_date <= today) {
+        let held_to = position.end_date.unwrap_or(today).min(today);
+        let position_records: Vec<&CompensationRecord> = records.iter()
+            .filter(|r| Some(r.position_id) == position.id)
+            .collect();
+        let mut fy = FinancialYear::containing(position.start_date);
+        while fy.start() <= held_to {
+            let (from, to) = (position.start_date.max(fy.start()), held_to.min(fy.end()));
+            let record = position_records.iter()
+                .filter(|r| r.effective_date <= to)
+                .max_by_key(|r| r.effective_date)
+                .or_else(|| position_records.iter().min_by_key(|r| r.effective_date));
+            let annual = record.map_or_else(|| position.base_salary_estimate(), |r| gross_aud(r, &fy));
+            let earned = annual * dates::period_days(from, to) as f64 / dates::period_days(fy.start(), fy.end()) as f64;
+            let to_today: f64 = ((fy.start_year + 1)..=current_fy.start_year)
+                .map(|year| 1.0 + inflation(year))
+                .product();
+            historical_nominal += earned;
+            historical_real += earned * to_today;
+            fy = FinancialYear { start_year: fy.start_year + 1 };
+        }
+    }
+
+    let current = current_position(positions, today);
+    let mut seniority = current.map(|p| p.seniority_level.clone()).unwrap_or(SeniorityLevel::Entry);
+    let current_pay: f64 = current_positions(positions, today).iter()
+        .filter_map(|p| latest_record_for(p, records))
+        .map(|r| gross_aud(r, &current_fy))
+        .sum();
+    let current_annual_earnings = match (current_pay > 0.0, current) {
+        (false, Some(position)) if position.end_date.is_none() => position.base_salary_estimate(),
+        _ => current_pay,
+    };
+    let salary_growth = assumed_growth.unwrap_or_else(|| market_growth_rate(&seniority) * 100.0);
+    let years_per_level = typical_years_per_level(&profile.industry).max(1.0);
+
+    let current_age = dates::age_on(profile.date_of_birth, today);
+    let years_to_retirement = (SUPER_PROJECTION_AGE - current_age).max(0) as u32;
+    let (mut pay, mut years_at_level) = (current_annual_earnings, 0.0);
+    let (mut projected_nominal, mut projected_real) = (0.0, 0.0);
+    let mut points = Vec::new();
+    for year in 1..=years_to_retirement {
+        pay *= 1.0 + salary_growth / 100.0;
+        years_at_level += 1.0;
+        let next = next_seniority_level(&seniority);
+        if years_at_level >= years_per_level && seniority_rank(&next) <= seniority_rank(&SeniorityLevel::Lead) && next != seniority {
+            if let Some(position) = current {
+                let estimate = |level: &SeniorityLevel| Position { seniority_level: level.clone(), ..position.clone() }.base_salary_estimate();
+                pay *= estimate(&next) / estimate(&seniority);
+            }
+            seniority = next;
+            years_at_level = 0.0;
+        }
+        let real = pay / (1.0 + ASSUMED_INFLATION_PERCENT / 100.0).powi(year as i32);
+        projected_nominal += pay;
+        projected_real += real;
+        points.push(LifetimeEarningsPoint {
+            age: current_age + year as i32,
+            nominal: pay,
+            real,
+            seniority_level: seniority.clone(),
+        });
+    }
+
+    LifetimeEarningsProjection {
+        current_age,
+        retirement_age: SUPER_PROJECTION_AGE,
+        current_annual_earnings,
+        salary_growth,
+        inflation: ASSUMED_INFLATION_PERCENT,
+        historical_nominal,
+        historical_real,
+        projected_nominal,
+        projected_real,
+        lifetime_nominal: historical_nominal + projected_nominal,
+        lifetime_real: historical_real + projected_real,
+        points,
+    }
+}
+
+/// Back pay owed when a pay rise is backdated: each pay period of the position ending between
+/// `from_date` and `to_date` recomputed at the new record's hourly rate, with overtime hours
+/// at the payslip's multiplier. Weeks in the range that no payslip covers are estimated at the
+/// new record's standard hours and flagged.
+///
+/// Withholding on the lump sum follows the ATO's method for back payments: spread over the
+/// year's pay periods, it's the extra tax on a year's pay at the new rate with the lump sum
+/// added. Errors when a record belongs to another position or the dates are the wrong way round.
+pub fn back_pay(
+    position_id: i64,
+    old_record: &CompensationRecord,
+    new_record: &CompensationRecord,
+    from_date: NaiveDate,
+    to_date: NaiveDate,
+    weekly_entries: &[WeeklyCompensationEntry],
+) -> Result<BackPayCalculation, String> {
+    if old_record.position_id != position_id || new_record.position_id != position_id {
+        return Err("Both compensation records must belong to the position".to_string());
+    }
+    if from_date > to_date {
+        return Err("The back pay period must start before it ends".to_string());
+    }
+    let old_hourly_rate = old_record.base_hourly_rate();
+    let new_hourly_rate = new_record.base_hourly_rate();
+
+    let entries: Vec<&WeeklyCompensationEntry> = weekly_entries.iter()
+        .filter(|e| e.position_id == Some(position_id) && e.week_ending >= from_date && e.week_ending <= to_date)
+        .collect();
+    let period_start = |e: &WeeklyCompensationEntry| e.period_start
+        .unwrap_or_else(|| dates::period_start_for(e.week_ending, &e.period_type));
+
+    let mut periods: Vec<BackPayPeriod> = entries.iter()
+        .map(|e| {
+            let paid_hours = e.hours_ordinary + e.hours_overtime * e.overtime_rate_multiplier;
+            let difference = (new_hourly_rate - old_hourly_rate) * paid_hours;
+            BackPayPeriod {
+                period_start: period_start(e),
+                period_end: e.week_ending,
+                hours_ordinary: e.hours_ordinary,
+                hours_overtime: e.hours_overtime,
+                old_gross: e.gross_pay,
+                new_gross: e.gross_pay + difference,
+                difference,
+                estimated: false,
+            }
+        })
+        .collect();
+
+    // Weeks ending on the payslips' day, or the last day of the range, that no payslip covers
+    let week_end_day = entries.first().map(|e| e.week_ending).unwrap_or(to_date).weekday();
+    let mut week_end = from_date;
+    while week_end.weekday() != week_end_day {
+        week_end = week_end.succ_opt().ok_or("The back pay period is out of range")?;
+    }
+    while week_end <= to_date {
+        let week_start = week_end - Days::new(6);
+        let covered = entries.iter().any(|e| period_start(e) <= week_end && e.week_ending >= week_start);
+        if !covered {
+            let hours = new_record.standard_weekly_hours;
+            periods.push(BackPayPeriod {
+                period_start: week_start,
+                period_end: week_end,
+                hours_ordinary: hours,
+                hours_overtime: 0.0,
+                old_gross: old_hourly_rate * hours,
+                new_gross: new_hourly_rate * hours,
+                difference: (new_hourly_rate - old_hourly_rate) * hours,
+                estimated: true,
+            });
+        }
+        week_end = week_end + Days::new(7);
+    }
+    periods.sort_by_key(|p| p.period_end);
+
+    let total_back_pay: f64 = periods.iter().map(|p| p.difference).sum();
+    let fy = FinancialYear::containing(to_date);
+    let (_, annual_taxable) = annual_gross_and_taxable(new_record);
+    let estimated_withholding = if new_record.jurisdiction == Jurisdiction::AU && total_back_pay > 0.0 {
+        let threshold = new_record.claims_tax_free_threshold;
+        tax::expected_payg_withholding(annual_taxable + total_back_pay, threshold, &fy)
+            - tax::expected_payg_withholding(annual_taxable, threshold, &fy)
+    } else {
+        0.0
+    };
+
+    Ok(BackPayCalculation {
+        position_id,
+        from_date,
+        to_date,
+        old_hourly_rate,
+        new_hourly_rate,
+        estimated_periods: periods.iter().filter(|p| p.estimated).count() as u32,
+        periods,
+        total_back_pay,
+        estimated_withholding,
+        net_back_pay: total_back_pay - estimated_withholding,
+    })
+}
+
+/// When a position's next bonus is likely to be paid, and whether resigning now would forfeit
+/// it. Bonuses copied onto later records are counted once. The next date is the last award
+/// date plus the average gap between award dates, rounded to whole months, or a year when
+/// there's only one; it's rolled forward by that gap until it's after `today`.
+///
+/// `window_days` defaults to 90. The bonus at risk is what was paid on the last award date.
+pub fn calculate_resignation_timing(
+    position: &Position,
+    records: &[CompensationRecord],
+    today: NaiveDate,
+    window_days: Option<u32>,
+) -> Result<ResignationTimingAdvice, String> {
+    let position_id = position.id.ok_or("The position hasn't been saved")?;
+    let window_days = window_days.unwrap_or(DEFAULT_BONUS_WAIT_WINDOW_DAYS);
+
+    // Distinct bonuses by date, name and amount, then summed per award date
+    let mut by_date: BTreeMap<NaiveDate, f64> = BTreeMap::new();
+    let mut seen = Vec::new();
+    for bonus in records.iter().filter(|r| r.position_id == position_id).flat_map(|r| &r.bonuses) {
+        let key = (bonus.date_awarded, bonus.name.as_str(), bonus.amount.to_bits());
+        if !seen.contains(&key) {
+            seen.push(key);
+            *by_date.entry(bonus.date_awarded).or_insert(0.0) += bonus.amount;
+        }
+    }
+
+    let Some((&last_date, &bonus_at_risk)) = by_date.iter().next_back() else {
+        return Ok(ResignationTimingAdvice {
+            position_id,
+            bonuses_on_record: 0,
+            typical_interval_months: None,
+            predicted_bonus_date: None,
+            days_until_bonus: None,
+            bonus_at_risk: 0.0,
+            window_days,
+            wait_for_bonus: false,
+            advice: "No bonuses are on record for this role, so there's nothing to time a resignation around".to_string(),
+        });
+    };
+
+    let dates: Vec<NaiveDate> = by_date.keys().copied().collect();
+    let interval_months = if dates.len() > 1 {
+        let average_days = (last_date - dates[0]).num_days() as f64 / (dates.len() - 1) as f64;
+        ((average_days / AVERAGE_DAYS_PER_MONTH).round() as u32).max(1)
+    } else {
+        12
+    };
+
+    let mut predicted = last_date;
+    while predicted <= today {
+        predicted = predicted.checked_add_months(Months::new(interval_months))
+            .ok_or("The predicted bonus date is out of range")?;
+    }
+    let days_until_bonus = (predicted - today).num_days();
+    let wait_for_bonus = days_until_bonus <= window_days as i64;
+
+    let advice = if wait_for_bonus {
+        format!(
+            "The next bonus is likely around {}, {} days away. Resigning before then could forfeit about ${:.0}",
+            predicted, days_until_bonus, bonus_at_risk
+        )
+    } else {
+        format!(
+            "The next bonus isn't likely until around {}, more than {} days away, so there's no bonus to wait for",
+            predicted, window_days
+        )
+    };
+
+    Ok(ResignationTimingAdvice {
+        position_id,
+        bonuses_on_record: dates.len() as u32,
+        typical_interval_months: (dates.len() > 1).then_some(interval_months),
+        predicted_bonus_date: Some(predicted),
+        days_until_bonus: Some(days_until_bonus),
+        bonus_at_risk,
+        window_days,
+        wait_for_bonus,
+        advice,
+    })
+}
+
+/// What resigning from a position with notice given `today` and a last day of
+/// `proposed_last_day` would pay out: annual leave with any leave loading, long service leave
+/// the employee is entitled to by then, from `lsl` worked out to the last day, and the tax
+/// withheld on them.
+///
+/// Notice is the position's contractual notice period, or the National Employment Standards
+/// minimum for the years of service. Annual leave is `annual_leave_hours` when given, else
+/// four weeks a year accrued since the last work anniversary, taking earlier years' leave as
+/// used; casuals have none. Withholding is the extra tax on a year's pay with the leave added.
+/// Errors when the last day doesn't give the notice required, or for a position that has
+/// already ended, has no compensation record or isn't Australian.
+pub fn calculate_resignation_plan(
+    position: &Position,
+    records: &[CompensationRecord],
+    lsl: &LslStatus,
+    annual_leave_hours: Option<f64>,
+    today: NaiveDate,
+    proposed_last_day: NaiveDate,
+) -> Result<ResignationPlan, String> {
+    let position_id = position.id.ok_or("The position hasn't been saved")?;
+    if let Some(end_date) = position.end_date.filter(|end| *end < today) {
+        return Err(format!("{} at {} already ended on {}", position.job_title, position.employer_name, end_date));
+    }
+    let record = records.iter()
+        .filter(|r| r.position_id == position_id && r.effective_date <= proposed_last_day)
+        .max_by_key(|r| r.effective_date)
+        .or_else(|| latest_record_for(position, records))
+        .ok_or("The position has no compensation record")?;
+    if record.jurisdiction != Jurisdiction::AU {
+        return Err("Resignation planning only covers Australian positions".to_string());
+    }
+
+    let years_at_notice = dates::period_days(position.start_date, today) as f64 / DAYS_PER_YEAR;
+    let notice_period_weeks = position.notice_period_weeks.unwrap_or_else(|| {
+        MINIMUM_NOTICE_WEEKS.iter()
+            .find(|(under_years, _)| years_at_notice < *under_years)
+            .map_or(MINIMUM_NOTICE_WEEKS_AFTER, |(_, weeks)| *weeks)
+    });
+    let earliest_last_day = today + Days::new((notice_period_weeks * 7.0).round() as u64);
+    if proposed_last_day < earliest_last_day {
+        return Err(format!(
+            "A last day of {} is too early: {} weeks' notice given on {} runs until {}",
+            proposed_last_day, notice_period_weeks, today, earliest_last_day
+        ));
+    }
+
+    let (annual_leave_hours, annual_leave_estimated) = match (annual_leave_hours, &position.employment_type) {
+        (Some(hours), _) => (hours.max(0.0), false),
+        (None, EmploymentType::Casual) => (0.0, false),
+        (None, _) => {
+            let years = dates::period_days(position.start_date, proposed_last_day) as f64 / DAYS_PER_YEAR;
+            (years.fract() * ANNUAL_LEAVE_WEEKS_PER_YEAR * record.standard_weekly_hours, true)
+        }
+    };
+    let annual_leave_value = annual_leave_hours * record.base_hourly_rate();
+    let leave_loading = annual_leave_value * position.leave_loading_percent.unwrap_or(0.0) / 100.0;
+
+    let long_service_leave = lsl.tenures.iter()
+        .find(|t| t.position_ids.contains(&position_id))
+        .cloned();
+    let long_service_leave_value = long_service_leave.as_ref()
+        .filter(|t| !t.portable)
+        .map_or(0.0, |t| t.payable_on_leaving);
+
+    let final_pay_gross = annual_leave_value + leave_loading + long_service_leave_value;
+    let fy = FinancialYear::containing(proposed_last_day);
+    let (_, annual_taxable) = annual_gross_and_taxable(record);
+    let threshold = record.claims_tax_free_threshold;
+    let estimated_withholding = tax::expected_payg_withholding(annual_taxable + final_pay_gross, threshold, &fy)
+        - tax::expected_payg_withholding(annual_taxable, threshold, &fy);
+
+    let mut tax_treatment = "Unused annual leave, leave loading and long service leave paid on resigning are taxed \
+        at your marginal rate, like ordinary pay. Withholding on the lump sum is worked out as if it were \
+        spread over the year's pay, so it may be more or less than the tax finally assessed.".to_string();
+    if position.start_date < NaiveDate::from_ymd_opt(1993, 8, 18).unwrap_or(NaiveDate::MIN) {
+        tax_treatment.push_str(" Leave accrued before 18 August 1993 is taxed at no more than 32%.");
+    }
+
+    Ok(ResignationPlan {
+        position_id,
+        notice_given: today,
+        notice_period_weeks,
+        notice_from_contract: position.notice_period_weeks.is_some(),
+        earliest_last_day,
+        proposed_last_day,
+        annual_leave_hours,
+        annual_leave_estimated,
+        annual_leave_value,
+        leave_loading,
+        long_service_leave,
+        long_service_leave_value,
+        final_pay_gross,
+        estimated_withholding,
+        final_pay_net: final_pay_gross - estimated_withholding,
+        tax_treatment,
+    })
+}
+
+/// Taking a bonus as cash, taxed at the marginal rate, against sacrificing it into super,
+/// where it's taxed at 15%. Only as much as fits in `cap_headroom` is sacrificed; the rest is
+/// taken as cash either way, so sacrificing past the cap is never suggested.
+pub fn calculate_bonus_sacrifice(
+    bonus_amount: f64,
+    marginal_rate: f64,
+    cap_headroom: f64,
+    fy: &FinancialYear,
+) -> Result<BonusSacrificeAnalysis, String> {
+    if bonus_amount < 0.0 {
+        return Err("Bonus amount can't be negative".to_string());
+    }
+    if !(0.0..=100.0).contains(&marginal_rate) {
+        return Err("Marginal rate must be a percentage between 0 and 100".to_string());
+    }
+
+    let rate = marginal_rate / 100.0;
+    let amount_sacrificed = bonus_amount.min(cap_headroom.max(0.0));
+    let cash_after_tax = bonus_amount * (1.0 - rate);
+    let sacrifice_after_tax = amount_sacrificed * (1.0 - CONTRIBUTIONS_TAX_RATE)
+        + (bonus_amount - amount_sacrificed) * (1.0 - rate);
+
+    Ok(BonusSacrificeAnalysis {
+        financial_year: fy.label(),
+        bonus_amount,
+        marginal_rate,
+        cap_headroom,
+        has_cap_room: cap_headroom > 0.0,
+        amount_sacrificed,
+        cash_after_tax,
+        sacrifice_after_tax,
+        net_benefit: sacrifice_after_tax - cash_after_tax,
+    })
+}
+
+/// Government co-contribution for personal after-tax super contributions.
+///
+/// Matches 50c per dollar up to $500 for incomes at or below the lower threshold; the
+/// maximum phases out linearly to nil at the higher threshold. Eligibility tests other
+/// than income (age, 10% employment income, total super balance) are not modelled.
+pub fn calculate_co_contribution(income: f64, personal_after_tax_contribution: f64, financial_year: &FinancialYear) -> f64 {
+    let (lower, higher) = CO_CONTRIBUTION_THRESHOLDS
+        .iter()
+        .min_by_key(|(year, _, _)| (year - financial_year.start_year).abs())
+        .map(|(_, lower, higher)| (*lower, *higher))
+        .unwrap_or((45400.0, 60400.0));
+
+    if income >= higher || personal_after_tax_contribution <= 0.0 {
+        return 0.0;
+    }
+
+    let max_entitlement = if income <= lower {
+        CO_CONTRIBUTION_MAX
+    } else {
+        CO_CONTRIBUTION_MAX * (higher - income) / (higher - lower)
+    };
+
+    (personal_after_tax_contribution * CO_CONTRIBUTION_MATCH_RATE).min(max_entitlement)
+}
+
+/// What sacrificing `sacrifice` into super is worth at each income from `from` to `to`, every
+/// `step` dollars, with `other_concessional` already going in.
+///
+/// Income is before the sacrifice. The benefit is income tax avoided less the 15%
+/// contributions tax and any Division 293 the sacrifice adds, so it flattens once income
+/// plus contributions is far enough over the threshold for the sacrifice to be caught.
+pub fn calculate_sacrifice_benefit_curve(
+    sacrifice: f64,
+    other_concessional: f64,
+    from: f64,
+    to: f64,
+    step: f64,
+    fy: &FinancialYear,
+) -> Result<Vec<SacrificeBenefitPoint>, String> {
+    if sacrifice <= 0.0 {
+        return Err("The amount sacrificed must be more than zero".to_string());
+    }
+
+    Ok(tax::curve_incomes(from, to, step)?
+        .into_iter()
+        .map(|income| {
+            let sacrificed = sacrifice.min(income);
+            let income_tax_saving = tax::income_tax(&Jurisdiction::AU, income, fy)
+                - tax::income_tax(&Jurisdiction::AU, income - sacrificed, fy);
+            let contributions_tax = sacrificed * CONTRIBUTIONS_TAX_RATE;
+            let div293 = tax::div293_liability(income - sacrificed, other_concessional + sacrificed, fy)
+                - tax::div293_liability(income, other_concessional, fy);
+            let net_benefit = income_tax_saving - contributions_tax - div293;
+
+            SacrificeBenefitPoint {
+                income,
+                income_tax_saving,
+                contributions_tax,
+                div293,
+                net_benefit,
+                benefit_per_dollar: if sacrificed > 0.0 { net_benefit / sacrificed } else { 0.0 },
+            }
+        })
+        .collect())
+}
+
+/// Seniority changes across positions in start-date order: the years taken per level gained
+/// and any stint at one level well past the industry's typical pace.
+///
+/// Consecutive positions at the same level form one stint, so moving employer without moving
+/// up doesn't reset the clock. Lead and Manager are treated as the same rung. The current
+/// stint runs to `today`; there is nowhere to stall from Executive.
+pub fn calculate_promotion_velocity(
+    positions: &[Position],
+    profile: &Option<UserProfile>,
+    today: NaiveDate,
+) -> PromotionVelocity {
+    let industry = profile.as_ref().map(|p| p.industry.clone()).unwrap_or_default();
+    let typical_years_per_level = typical_years_per_level(&industry);
+
+    let mut sorted: Vec<&Position> = positions.iter().collect();
+    sorted.sort_by_key(|p| p.start_date);
+
+    let mut stints: Vec<LevelStint> = Vec::new();
+    let mut levels_gained = 0;
+    let mut last_promotion = None;
+    for position in &sorted {
+        if let Some(stint) = stints.last_mut() {
+            let (from, to) = (seniority_rank(&stint.seniority_level), seniority_rank(&position.seniority_level));
+            if from == to {
+                stint.end_date = position.end_date;
+                continue;
+            }
+            stint.end_date = Some(position.start_date);
+            if to > from {
+                levels_gained += to - from;
+                last_promotion = Some(position.start_date);
+            }
+        }
+        stints.push(LevelStint {
+            seniority_level: position.seniority_level.clone(),
+            start_date: position.start_date,
+            end_date: position.end_date,
+            years: 0.0,
+            stalled: false,
+        });
+    }
+
+    for stint in &mut stints {
+        let end = stint.end_date.unwrap_or(today).min(today);
+        stint.years = (end - stint.start_date).num_days().max(0) as f64 / 365.25;
+        stint.stalled = !matches!(stint.seniority_level, SeniorityLevel::Executive)
+            && stint.years > typical_years_per_level * PROGRESSION_STALL_MULTIPLE;
+    }
+
+    let average_years_per_level = match (sorted.first(), last_promotion) {
+        (Some(first), Some(promoted)) if levels_gained > 0 => {
+            Some((promoted - first.start_date).num_days() as f64 / 365.25 / levels_gained as f64)
+        }
+        _ => None,
+    };
+
+    let insights = stints.iter()
+        .filter(|stint| stint.stalled)
+        .map(|stint| EarningsInsight {
+            category: InsightCategory::ProgressionStall,
+            title: format!("Progression Stall at {:?} Level", stint.seniority_level),
+            description: format!(
+                "You spent {:.1} years at {:?} level{}, where {:.1} years is typical before moving up. This is about your level rather than your pay.",
+                stint.years,
+                stint.seniority_level,
+                if stint.end_date.is_none() { " so far" } else { "" },
+                typical_years_per_level
+            ),
+            confidence_level: 0.6,
+            position_ids: Vec::new(),
+            data_points: vec![
+                format!("At {:?} since {}", stint.seniority_level, stint.start_date.format("%b %Y")),
+                format!("Typical pace: {:.1} years per level", typical_years_per_level),
+            ],
+        })
+        .collect();
+
+    PromotionVelocity {
+        industry,
+        typical_years_per_level,
+        levels_gained,
+        average_years_per_level,
+        stints,
+        insights,
+    }
+}
+
+/// Years worked and AUD earned in each industry, largest share of years first.
+///
+/// A position's own industry wins over the profile's, and industries differing only in case
+/// are counted together. Earnings run each compensation record from its effective date until
+/// the next record, the end of the position or `today`; concurrent positions both count, and
+/// records in a currency without an exchange rate earn nothing here.
+pub fn calculate_industry_exposure(
+    positions: &[Position],
+    records: &[CompensationRecord],
+    profile: &Option<UserProfile>,
+    exchange_rates: &[ExchangeRate],
+    today: NaiveDate,
+) -> Vec<IndustryExposure> {
+    let profile_industry = profile.as_ref().map(|p| p.industry.trim()).filter(|i| !i.is_empty());
+
+    let mut exposures: Vec<IndustryExposure> = Vec::new();
+    for position in positions {
+        let industry = position.industry.as_deref().map(str::trim).filter(|i| !i.is_empty())
+            .or(profile_industry)
+            .unwrap_or("Unknown");
+        let end = position.end_date.unwrap_or(today).min(today);
+        let years = (end - position.start_date).num_days().max(0) as f64 / 365.25;
+        let earnings = earnings_between(position, records, exchange_rates, end);
+
+        match exposures.iter_mut().find(|e| e.industry.eq_ignore_ascii_case(industry)) {
+            Some(exposure) => {
+                exposure.position_count += 1;
+                exposure.years += years;
+                exposure.total_earnings += earnings;
+            }
+            None => exposures.push(IndustryExposure {
+                industry: industry.to_string(),
+                position_count: 1,
+                years,
+                share_of_years: 0.0,
+                total_earnings: earnings,
+                share_of_earnings: 0.0,
+            }),
+        }
+    }
+
+    let total_years: f64 = exposures.iter().map(|e| e.years).sum();
+    let total_earnings: f64 = exposures.iter().map(|e| e.total_earnings).sum();
+    for exposure in &mut exposures {
+        exposure.share_of_years = if total_years > 0.0 { exposure.years / total_years * 100.0 } else { 0.0 };
+        exposure.share_of_earnings = if total_earnings > 0.0 { exposure.total_earnings / total_earnings * 100.0 } else { 0.0 };
+    }
+    exposures.sort_by(|a, b| b.years.total_cmp(&a.years).then_with(|| a.industry.cmp(&b.industry)));
+    exposures
+}
+
+// AUD earned in a position up to `end`, each record valued at the rate for the year it took effect
+fn earnings_between(
+    position: &Position,
+    records: &[CompensationRecord],
+    exchange_rates: &[ExchangeRate],
+    end: NaiveDate,
+) -> f64 {
+    let mut held: Vec<&CompensationRecord> = records.iter()
+        .filter(|r| Some(r.position_id) == position.id)
+        .collect();
+    held.sort_by_key(|r| r.effective_date);
+
+    held.iter().enumerate()
+        .map(|(i, record)| {
+            let from = record.effective_date.max(position.start_date);
+            let until = held.get(i + 1).map(|next| next.effective_date.min(end)).unwrap_or(end);
+            let days = (until - from).num_days().max(0) as f64;
+            let annual = record.annual_base() + record.annual_overtime_pay()
+                + record.annual_allowances() + record.annual_bonuses();
+            let rate = currency::exchange_rate_for(exchange_rates, &record.currency, &FinancialYear::containing(from))
+                .unwrap_or(0.0);
+            annual * days / 365.25 * rate
+        })
+        .sum()
+}
+
+/// A position's compensation records oldest first, each with the change from the one before.
+///
+/// Records for other positions are ignored, so the full record list can be passed.
+pub fn calculate_record_lineage(position_id: i64, records: &[CompensationRecord]) -> Vec<RecordLineageEntry> {
+    let mut sorted: Vec<&CompensationRecord> = records.iter()
+        .filter(|r| r.position_id == position_id)
+        .collect();
+    sorted.sort_by_key(|r| r.effective_date);
+
+    let mut lineage: Vec<RecordLineageEntry> = Vec::new();
+    for record in sorted {
+        let annual_base = record.annual_base();
+        let annual_package = annual_base + record.annual_overtime_pay()
+            + record.annual_allowances() + record.annual_bonuses();
+        let change_from_previous = lineage.last().map(|previous| RecordDelta {
+            days_since_previous: (record.effective_date - previous.record.effective_date).num_days(),
+            base_rate_change: record.base_rate - previous.record.base_rate,
+            annual_base_change: annual_base - previous.annual_base,
+            annual_base_change_percent: if previous.annual_base > 0.0 {
+                (annual_base - previous.annual_base) / previous.annual_base * 100.0
+            } else {
+                0.0
+            },
+            annual_package_change: annual_package - previous.annual_package,
+            super_rate_change: record.super_contributions.contribution_rate
+                - previous.record.super_contributions.contribution_rate,
+        });
+        lineage.push(RecordLineageEntry {
+            record: record.clone(),
+            annual_base,
+            annual_package,
+            change_from_previous,
+        });
+    }
+
+    lineage
+}
+
+/// Fuzzy records, the ones most of the career's earnings rest on first, to know which are
+/// worth checking against a contract or payslip. A record's weight is its total compensation
+/// in AUD over the years it was in effect: until the position's next record, the position's
+/// end, or `today`.
+pub fn fuzzy_records_by_weight(
+    positions: &[Position],
+    records: &[CompensationRecord],
+    exchange_rates: &[ExchangeRate],
+    today: NaiveDate,
+) -> Vec<FuzzyRecordWeight> {
+    let mut weighted = Vec::new();
+    let mut total_weight = 0.0;
+
+    for position in positions {
+        let mut held: Vec<&CompensationRecord> = records.iter()
+            .filter(|r| Some(r.position_id) == position.id)
+            .collect();
+        held.sort_by_key(|r| r.effective_date);
+        let position_end = position.end_date.unwrap_or(today).min(today);
+
+        for (i, record) in held.iter().enumerate() {
+            let until = held.get(i + 1).map(|next| next.effective_date).unwrap_or(position_end);
+            let years_in_effect = ((until - record.effective_date).num_days() as f64 / DAYS_PER_YEAR).max(0.0);
+            let annual_total_compensation = calculate_income_composition(record, exchange_rates).total_compensation;
+            let earnings_weight = annual_total_compensation * years_in_effect;
+            total_weight += earnings_weight;
+
+            if matches!(record.entry_type, CompensationEntryType::Fuzzy) {
+                weighted.push(FuzzyRecordWeight {
+                    record: (*record).clone(),
+                    employer_name: position.employer_name.clone(),
+                    annual_total_compensation,
+                    years_in_effect,
+                    earnings_weight,
+                    share_of_earnings: 0.0,
+                });
+            }
+        }
+    }
+
+    for entry in &mut weighted {
+        entry.share_of_earnings = if total_weight > 0.0 { entry.earnings_weight / total_weight * 100.0 } else { 0.0 };
+    }
+    weighted.sort_by(|a, b| b.earnings_weight.total_cmp(&a.earnings_weight));
+    weighted
+}
+
+/// Annual amount of each allowance on the position's compensation record in force at the
+/// end of the financial year (or when the position ended, if earlier), with taxable and
+/// non-taxable totals kept apart. Amounts are in the record's currency.
+pub fn calculate_allowance_summary(
+    position: &Position,
+    fy: &FinancialYear,
+    records: &[CompensationRecord],
+) -> Result<AllowanceSummary, String> {
+    let position_id = position.id.ok_or("Position has not been saved")?;
+    if position.start_date > fy.end() || position.end_date.map(|end| end < fy.start()).unwrap_or(false) {
+        return Err(format!("Position was not held in {}", fy.label()));
+    }
+
+    let as_at = position.end_date.map(|end| end.min(fy.end())).unwrap_or(fy.end());
+    let record = records.iter()
+        .filter(|r| r.position_id == position_id && r.effective_date <= as_at)
+        .max_by_key(|r| r.effective_date)
+        .ok_or_else(|| format!("No compensation record for the position in {}", fy.label()))?;
+
+    let allowances: Vec<AllowanceBreakdown> = record.allowances.iter()
+        .map(|allowance| AllowanceBreakdown {
+            name: allowance.name.clone(),
+            amount: allowance.amount,
+            frequency: allowance.frequency.clone(),
+            taxable: allowance.taxable,
+            annual_amount: annualize_allowance(allowance),
+        })
+        .collect();
+    let total = |taxable: bool| allowances.iter()
+        .filter(|a| a.taxable == taxable)
+        .map(|a| a.annual_amount)
+        .sum();
+
+    Ok(AllowanceSummary {
+        position_id,
+        financial_year: fy.label(),
+        effective_date: record.effective_date,
+        currency: record.currency.clone(),
+        taxable_total: total(true),
+        non_taxable_total: total(false),
+        allowances,
+    })
+}
+
+/// How complete a position's data is: 100, less itemised deductions for no compensation
+/// records, low-confidence records, gaps in payslips and no responsibilities. Payslip gaps are
+/// only counted in financial years the position has payslips in, up to `today`, so a
+/// position recorded without payslips isn't marked down for it.
+pub fn data_quality(
+    position: &Position,
+    records: &[CompensationRecord],
+    weekly_entries: &[WeeklyCompensationEntry],
+    today: NaiveDate,
+) -> DataQuality {
+    let mut deductions = Vec::new();
+    let records: Vec<&CompensationRecord> = records.iter()
+        .filter(|r| Some(r.position_id) == position.id)
+        .collect();
+
+    if records.is_empty() {
+        deductions.push(QualityDeduction {
+            reason: "No compensation records".to_string(),
+            points: NO_RECORDS_PENALTY,
+        });
+    }
+
+    let low_confidence = records.iter().filter(|r| r.confidence_score < LOW_CONFIDENCE_BELOW).count();
+    if low_confidence > 0 {
+        deductions.push(QualityDeduction {
+            reason: format!("{} record(s) with confidence below {:.0}%", low_confidence, LOW_CONFIDENCE_BELOW),
+            points: (low_confidence as f64 * LOW_CONFIDENCE_PENALTY).min(LOW_CONFIDENCE_MAX_PENALTY),
+        });
+    }
+
+    let missing_share = missing_payslip_share(position, weekly_entries, today);
+    if missing_share > 0.0 {
+        deductions.push(QualityDeduction {
+            reason: format!("Payslips cover {:.0}% of the time in the years they were entered", (1.0 - missing_share) * 100.0),
+            points: (missing_share * MISSING_PAYSLIPS_MAX_PENALTY).round(),
+        });
+    }
+
+    if position.core_responsibilities.trim().is_empty() {
+        deductions.push(QualityDeduction {
+            reason: "No responsibilities described".to_string(),
+            points: NO_RESPONSIBILITIES_PENALTY,
+        });
+    }
+
+    let score = (100.0 - deductions.iter().map(|d| d.points).sum::<f64>()).max(0.0);
+    DataQuality { score, deductions }
+}
+
+/// [`data_quality`] for every position, lowest score first, with the average as the overall
+/// score. The overall score is 0 with no positions.
+pub fn calculate_data_quality_report(
+    positions: &[Position],
+    records: &[CompensationRecord],
+    weekly_entries: &[WeeklyCompensationEntry],
+    today: NaiveDate,
+) -> DataQualityReport {
+    let mut scored: Vec<PositionDataQuality> = positions.iter()
+        .filter_map(|position| Some(PositionDataQuality {
+            position_id: position.id?,
+            employer_name: position.employer_name.clone(),
+            job_title: position.job_title.clone(),
+            quality: data_quality(position, records, weekly_entries, today),
+        }))
+        .collect();
+    scored.sort_by(|a, b| a.quality.score.total_cmp(&b.quality.score).then(a.position_id.cmp(&b.position_id)));
+
+    let overall_score = if scored.is_empty() {
+        0.0
+    } else {
+        scored.iter().map(|p| p.quality.score).sum::<f64>() / scored.len() as f64
+    };
+    DataQualityReport { overall_score, positions: scored }
+}
+
+/// An insight naming the positions that pull the overall data quality below
+/// `LOW_QUALITY_SCORE`, or None when it's good enough.
+pub fn data_quality_insight(report: &DataQualityReport) -> Option<EarningsInsight> {
+    if report.positions.is_empty() || report.overall_score >= LOW_QUALITY_SCORE {
+        return None;
+    }
+    let weak: Vec<&PositionDataQuality> = report.positions.iter()
+        .filter(|p| p.quality.score < LOW_QUALITY_SCORE)
+        .collect();
+
+    Some(EarningsInsight {
+        category: InsightCategory::DataQuality,
+        title: "Some Figures Rest on Thin Data".to_string(),
+        description: format!(
+            "Your data scores {:.0}/100. Filling in the positions below will make the analysis more reliable.",
+            report.overall_score
+        ),
+        confidence_level: 1.0,
+        data_points: weak.iter()
+            .map(|p| {
+                let worst = p.quality.deductions.iter()
+                    .max_by(|a, b| a.points.total_cmp(&b.points))
+                    .map(|d| d.reason.to_lowercase())
+                    .unwrap_or_default();
+                format!("{} at {}: {:.0}/100, {}", p.job_title, p.employer_name, p.quality.score, worst)
+            })
+            .collect(),
+        position_ids: weak.iter().map(|p| p.position_id).collect(),
+    })
+}
+
+/// How risky the income from current positions is, from 0 to 100: how much of it comes from
+/// one employer, how much is overtime or bonuses and commission, which can stop without a pay
+/// cut, and how much is casual or contract work. Each factor's points are in
+/// `factors`. Uses each current position's latest record; with none the score is 0.
+pub fn income_risk_score(positions: &[Position], records: &[CompensationRecord], today: NaiveDate) -> IncomeRiskAssessment {
+    let mut by_employer: BTreeMap<String, f64> = BTreeMap::new();
+    let (mut income, mut overtime, mut variable, mut casual, mut contract) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for position in current_positions(positions, today) {
+        let Some(record) = latest_record_for(position, records) else {
+            continue;
+        };
+        let (gross, _) = annual_gross_and_taxable(record);
+        income += gross;
+        overtime += record.annual_overtime_pay();
+        variable += record.annual_bonuses();
+        match position.employment_type {
+            EmploymentType::Casual => casual += gross,
+            EmploymentType::Contract => contract += gross,
+            EmploymentType::Permanent => {}
+        }
+        *by_employer.entry(position.employer_name.trim().to_lowercase()).or_insert(0.0) += gross;
+    }
+    if income <= 0.0 {
+        return IncomeRiskAssessment { score: 0.0, level: IncomeRiskLevel::Low, annual_income: 0.0, factors: Vec::new() };
+    }
+
+    let largest_share = by_employer.values().fold(0.0, |max: f64, &amount| max.max(amount)) / income;
+    let overtime_share = overtime / income;
+    let variable_share = variable / income;
+    let insecure_share = (casual + contract * CONTRACT_RISK_SHARE) / income;
+    let scaled = |share: f64| (share / FULL_RISK_INCOME_SHARE).min(1.0);
+    let factors = vec![
+        IncomeRiskFactor {
+            factor: "Employer concentration".to_string(),
+            points: CONCENTRATION_RISK_POINTS * largest_share,
+            max_points: CONCENTRATION_RISK_POINTS,
+            detail: format!("{:.0}% of income from one employer across {} employer(s)", largest_share * 100.0, by_employer.len()),
+        },
+        IncomeRiskFactor {
+            factor: "Overtime dependence".to_string(),
+            points: OVERTIME_RISK_POINTS * scaled(overtime_share),
+            max_points: OVERTIME_RISK_POINTS,
+            detail: format!("{:.0}% of income is overtime", overtime_share * 100.0),
+        },
+        IncomeRiskFactor {
+            factor: "Bonus and commission dependence".to_string(),
+            points: VARIABLE_PAY_RISK_POINTS * scaled(variable_share),
+            max_points: VARIABLE_PAY_RISK_POINTS,
+            detail: format!("{:.0}% of income is bonuses or commission", variable_share * 100.0),
+        },
+        IncomeRiskFactor {
+            factor: "Employment security".to_string(),
+            points: EMPLOYMENT_TYPE_RISK_POINTS * insecure_share.min(1.0),
+            max_points: EMPLOYMENT_TYPE_RISK_POINTS,
+            detail: format!("{:.0}% casual and {:.0}% contract income", casual / income * 100.0, contract / income * 100.0),
+        },
+    ];
+
+    let score = factors.iter().map(|f| f.points).sum::<f64>().min(100.0);
+    let level = if score >= HIGH_INCOME_RISK_SCORE {
+        IncomeRiskLevel::High
+    } else if score >= MODERATE_INCOME_RISK_SCORE {
+        IncomeRiskLevel::Moderate
+    } else {
+        IncomeRiskLevel::Low
+    };
+    IncomeRiskAssessment { score, level, annual_income: income, factors }
+}
+
+/// An insight when income is high risk, naming the factors that add the most.
+pub fn income_risk_insight(assessment: &IncomeRiskAssessment, positions: &[Position], today: NaiveDate) -> Option<EarningsInsight> {
+    if assessment.level != IncomeRiskLevel::High {
+        return None;
+    }
+    let mut factors: Vec<&IncomeRiskFactor> = assessment.factors.iter().filter(|f| f.points > 0.0).collect();
+    factors.sort_by(|a, b| b.points.total_cmp(&a.points));
+
+    Some(EarningsInsight {
+        category: InsightCategory::IncomeRisk,
+        title: "Your Income Is Exposed".to_string(),
+        description: format!(
+            "Your income scores {:.0}/100 for risk. Losing one job, or the overtime and bonuses drying up, would cut it hard.",
+            assessment.score
+        ),
+        confidence_level: 0.7,
+        data_points: factors.iter()
+            .map(|f| format!("{}: {:.0} of {:.0} points, {}", f.factor, f.points, f.max_points, f.detail))
+            .collect(),
+        position_ids: current_positions(positions, today).into_iter().filter_map(|p| p.id).collect(),
+    })
+}
+
+/// Current positions at odds with the profile's stated preferences: FIFO work when FIFO
+/// isn't tolerated at all, or more overtime on the latest record than the stated appetite.
+///
+/// A position counts as FIFO when its title, location, responsibilities or skills mention
+/// FIFO or DIDO work.
+pub fn preference_mismatch_check(
+    profile: &UserProfile,
+    positions: &[Position],
+    records: &[CompensationRecord],
+    today: NaiveDate,
+) -> Vec<PreferenceMismatch> {
+    let preferences = &profile.career_preferences;
+    let mut mismatches = Vec::new();
+
+    for position in current_positions(positions, today) {
+        let Some(position_id) = position.id else { continue };
+
+        if matches!(preferences.fifo_tolerance, FIFOTolerance::None) && is_fifo(position) {
+            mismatches.push(PreferenceMismatch {
+                position_id,
+                preference: MismatchedPreference::FifoTolerance,
+                description: format!(
+                    "Your profile says you won't do FIFO work, but {} at {} is a FIFO role.",
+                    position.job_title, position.employer_name
+                ),
+            });
+        }
+
+        let weekly_overtime = latest_record_for(position, records)
+            .map(|r| r.annual_overtime_hours() / 52.0)
+            .unwrap_or(0.0);
+        if let Some(limit) = overtime_appetite_limit(&preferences.overtime_appetite) {
+            if weekly_overtime > limit {
+                mismatches.push(PreferenceMismatch {
+                    position_id,
+                    preference: MismatchedPreference::OvertimeAppetite,
+                    description: format!(
+                        "Your profile says your overtime appetite is {:?}, but {} at {} averages {:.1} hours of overtime a week.",
+                        preferences.overtime_appetite, position.job_title, position.employer_name, weekly_overtime
+                    ),
+                });
+            }
+        }
+    }
+    mismatches
+}
+
+fn is_fifo(position: &Position) -> bool {
+    let text = format!(
+        "{} {} {} {}",
+        position.job_title, position.location, position.core_responsibilities, position.tools_systems_skills.join(" ")
+    )
+    .to_lowercase();
+    FIFO_TERMS.iter().any(|term| text.contains(term))
+}
+
+// Most overtime a week each appetite is comfortable with; an extreme appetite takes any amount
+fn overtime_appetite_limit(appetite: &OvertimeAppetite) -> Option<f64> {
+    match appetite {
+        OvertimeAppetite::None => Some(1.0),
+        OvertimeAppetite::Minimal => Some(4.0),
+        OvertimeAppetite::Moderate => Some(10.0),
+        OvertimeAppetite::High => Some(20.0),
+        OvertimeAppetite::Extreme => None,
+    }
+}
+
+/// Long service leave accrued with each employer, or across a portable industry, under the
+/// rules for the state each position is in.
+///
+/// The state comes from the position's location, else the profile's. Service is the days the
+/// employer's positions were held, overlaps counted once and gaps left out, and it's valued
+/// at the ordinary weekly pay on the latest record. Positions with New Zealand records, or no
+/// state, aren't counted. Amounts are in the records' currency.
+pub fn lsl_accrual(
+    positions: &[Position],
+    records: &[CompensationRecord],
+    rules: &[LslRule],
+    profile: &Option<UserProfile>,
+    today: NaiveDate,
+) -> LslStatus {
+    // Positions grouped by state, portability and employer or industry
+    let mut groups: BTreeMap<(String, bool, String), (&LslRule, Vec<&Position>)> = BTreeMap::new();
+    for position in positions.iter().filter(|p| p.id.is_some() && p.start_date <= today) {
+        if latest_record_for(position, records).is_some_and(|r| r.jurisdiction != Jurisdiction::AU) {
+            continue;
+        }
+        let Some(state) = anonymize::state_from_location(&position.location).or(profile.as_ref().map(|p| p.state)) else {
+            continue;
+        };
+        let Some(rule) = rules.iter().find(|r| r.state == state) else {
+            continue;
+        };
+        let industry = position.industry.clone()
+            .or_else(|| profile.as_ref().map(|p| p.industry.clone()))
+            .unwrap_or_default();
+        let portable = rule.portable_industries.iter().any(|i| i.trim().eq_ignore_ascii_case(industry.trim()));
+        let name = if portable { industry } else { position.employer_name.trim().to_string() };
+        groups.entry((format!("{:?}", state), portable, name.to_lowercase()))
+            .or_insert_with(|| (rule, Vec::new()))
+            .1
+            .push(position);
+    }
+
+    let mut tenures: Vec<LslTenure> = groups.into_iter()
+        .map(|((_, portable, _), (rule, group))| {
+            let mut spans: Vec<(NaiveDate, NaiveDate)> = group.iter()
+                .map(|p| (p.start_date, p.end_date.unwrap_or(today).min(today)))
+                .collect();
+            spans.sort();
+            let mut days = 0;
+            let mut covered_to: Option<NaiveDate> = None;
+            for (start, end) in spans {
+                let start = covered_to.map_or(start, |c| start.max(c + Days::new(1)));
+                if end >= start {
+                    days += dates::period_days(start, end);
+                }
+                covered_to = Some(covered_to.map_or(end, |c| c.max(end)));
+            }
+            let years_of_service = days as f64 / DAYS_PER_YEAR;
+
+            let weekly_rate = group.iter()
+                .filter_map(|p| latest_record_for(p, records))
+                .max_by_key(|r| r.effective_date)
+                .map(|r| r.annual_base() / 52.0)
+                .unwrap_or(0.0);
+            let accrued_weeks = years_of_service * rule.weeks_per_year;
+            let entitlement = if years_of_service >= rule.full_entitlement_years {
+                LslEntitlement::Full
+            } else if years_of_service >= rule.pro_rata_years {
+                LslEntitlement::ProRata
+            } else {
+                LslEntitlement::None
+            };
+            let current = group.iter().any(|p| p.end_date.is_none_or(|end| end >= today));
+            let next = if years_of_service < rule.pro_rata_years && rule.pro_rata_years < rule.full_entitlement_years {
+                Some((LslEntitlement::ProRata, rule.pro_rata_years))
+            } else if years_of_service < rule.full_entitlement_years {
+                Some((LslEntitlement::Full, rule.full_entitlement_years))
+            } else {
+                None
+            }.filter(|_| current);
+            let accrued_value = accrued_weeks * weekly_rate;
+
+            LslTenure {
+                position_ids: group.iter().filter_map(|p| p.id).collect(),
+                employer_name: if portable {
+                    group[0].industry.clone().unwrap_or_else(|| group[0].employer_name.clone())
+                } else {
+                    group[0].employer_name.clone()
+                },
+                state: rule.state,
+                portable,
+                current,
+                years_of_service,
+                accrued_weeks,
+                weekly_rate,
+                accrued_value,
+                entitlement,
+                payable_on_leaving: if entitlement == LslEntitlement::None { 0.0 } else { accrued_value },
+                next_threshold: next.map(|(kind, _)| kind),
+                next_threshold_date: next.map(|(_, years)| {
+                    today + Days::new(((years - years_of_service) * DAYS_PER_YEAR).ceil() as u64)
+                }),
+            }
+        })
+        .collect();
+    tenures.sort_by(|a, b| b.years_of_service.total_cmp(&a.years_of_service));
+
+    LslStatus {
+        total_accrued_weeks: tenures.iter().map(|t| t.accrued_weeks).sum(),
+        total_accrued_value: tenures.iter().map(|t| t.accrued_value).sum(),
+        total_payable_on_leaving: tenures.iter().map(|t| t.payable_on_leaving).sum(),
+        tenures,
+    }
+}
+
+/// An insight for each current tenure within a year of reaching pro-rata or full long
+/// service leave, since leaving just before can forfeit all of it.
+pub fn lsl_insight(status: &LslStatus, rules: &[LslRule], today: NaiveDate) -> Option<EarningsInsight> {
+    let close: Vec<(&LslTenure, NaiveDate, f64)> = status.tenures.iter()
+        .filter_map(|t| {
+            let date = t.next_threshold_date?;
+            let rule = rules.iter().find(|r| r.state == t.state)?;
+            let years = match t.next_threshold? {
+                LslEntitlement::ProRata => rule.pro_rata_years,
+                _ => rule.full_entitlement_years,
+            };
+            ((date - today).num_days() <= LSL_THRESHOLD_WARNING_DAYS)
+                .then_some((t, date, years * rule.weeks_per_year * t.weekly_rate))
+        })
+        .collect();
+    let (first, first_date, first_value) = close.first()?;
+
+    Some(EarningsInsight {
+        category: InsightCategory::LongServiceLeave,
+        title: "Long Service Leave Within Reach".to_string(),
+        description: format!(
+            "You'll reach long service leave at {} on {}, worth about ${:.0}. Leaving before then could forfeit it.",
+            first.employer_name, first_date, first_value
+        ),
+        confidence_level: 0.7,
+        data_points: close.iter()
+            .map(|(t, date, value)| format!(
+                "{}: {} entitlement on {} after {:.1} years, about ${:.0}",
+                t.employer_name,
+                if t.next_threshold == Some(LslEntitlement::Full) { "full" } else { "pro-rata" },
+                date, t.years_of_service, value
+            ))
+            .collect(),
+        position_ids: close.iter().flat_map(|(t, _, _)| t.position_ids.clone()).collect(),
+    })
+}
+
+// Share of the days the position was held, in financial years it has payslips in, that no
+// payslip covers
+fn missing_payslip_share(position: &Position, weekly_entries: &[WeeklyCompensationEntry], today: NaiveDate) -> f64 {
+    let held_to = position.end_date.unwrap_or(today).min(today);
+    let mut covered_by_fy: BTreeMap<i32, i64> = BTreeMap::new();
+    for entry in weekly_entries.iter().filter(|e| e.position_id.is_some() && e.position_id == position.id) {
+        let start = entry.period_start
+            .unwrap_or_else(|| dates::period_start_for(entry.week_ending, &entry.period_type))
+            .max(position.start_date);
+        let end = entry.week_ending.min(held_to);
+        if start <= end {
+            *covered_by_fy.entry(FinancialYear::containing(entry.week_ending).start_year).or_default()
+                += dates::period_days(start, end);
+        }
+    }
+
+    let (mut held, mut covered) = (0, 0);
+    for (start_year, days) in covered_by_fy {
+        let fy = FinancialYear { start_year };
+        let from = fy.start().max(position.start_date);
+        let to = fy.end().min(held_to);
+        if from <= to {
+            let held_in_fy = dates::period_days(from, to);
+            held += held_in_fy;
+            covered += days.min(held_in_fy);
+        }
+    }
+    if held == 0 {
+        return 0.0;
+    }
+    1.0 - covered as f64 / held as f64
+}
+
+/// Week-ending dates in a financial year with no pay entry for the position.
+///
+/// Weeks are expected on the position's week-ending day when it's set, otherwise on the
+/// weekday of its most recent entry (or the week starting on its start date when it has
+/// none), for every week overlapping the time the position was held, up to `today`. A
+/// fortnightly or monthly payslip covers each week ending inside its period. Entries without
+/// a position count when they fall within the position's dates.
+pub fn detect_missing_weeks(
+    position: &Position,
+    fy: &FinancialYear,
+    weekly_entries: &[WeeklyCompensationEntry],
+    today: NaiveDate,
+) -> Vec<NaiveDate> {
+    let held_from = position.start_date;
+    let held_to = position.end_date.unwrap_or(today).min(today);
+
+    let entries: Vec<&WeeklyCompensationEntry> = weekly_entries.iter()
+        .filter(|e| match e.position_id {
+            Some(id) => Some(id) == position.id,
+            None => e.week_ending >= held_from && e.week_ending <= held_to + chrono::Duration::days(6),
+        })
+        .collect();
+
+    let anchor_day = position.week_ending_day.unwrap_or_else(|| {
+        entries.iter()
+            .map(|e| e.week_ending)
+            .max()
+            .unwrap_or(held_from + chrono::Duration::days(6))
+            .weekday()
+    });
+
+    // First week ending on the anchor day whose week overlaps both the FY and the position
+    let first_allowed = fy.start().max(held_from);
+    let offset = (anchor_day.num_days_from_monday() as i64
+        - first_allowed.weekday().num_days_from_monday() as i64).rem_euclid(7);
+    let mut week_ending = first_allowed + chrono::Duration::days(offset);
+    let last_allowed = fy.end().min(held_to + chrono::Duration::days(6)).min(today);
+
+    let mut missing = Vec::new();
+    while week_ending <= last_allowed {
+        let covered = entries.iter().any(|e| {
+            let period_start = e.period_start.unwrap_or_else(|| dates::period_start_for(e.week_ending, &e.period_type));
+            period_start <= week_ending && week_ending <= e.week_ending
+        });
+        if !covered {
+            missing.push(week_ending);
+        }
+        week_ending += chrono::Duration::days(7);
+    }
+
+    missing
+}
+
+/// Base pay at the end of the financial year against the end of the previous one, net of
+/// CPI for the year. A pay freeze through 6% inflation is a 6% real cut.
+///
+/// Pay is the annual base of every position held on the day, in AUD, so a change of job
+/// counts as a pay change. Errors when either year has no pay on record or CPI for the year
+/// isn't known.
+pub fn calculate_real_pay_cut(
+    fy: &FinancialYear,
+    positions: &[Position],
+    records: &[CompensationRecord],
+    exchange_rates: &[ExchangeRate],
+) -> Result<RealPayCut, String> {
+    let cpi_percent = CPI_ANNUAL_CHANGE.iter()
+        .find(|(start_year, _)| *start_year == fy.start_year)
+        .map(|(_, change)| *change)
+        .ok_or_else(|| format!("No CPI figure for {}", fy.label()))?;
+
+    let previous_fy = FinancialYear { start_year: fy.start_year - 1 };
+    let previous_annual_pay = annual_base_pay_on(previous_fy.end(), &previous_fy, positions, records, exchange_rates)
+        .ok_or_else(|| format!("No pay recorded at the end of {}", previous_fy.label()))?;
+    let current_annual_pay = annual_base_pay_on(fy.end(), fy, positions, records, exchange_rates)
+        .ok_or_else(|| format!("No pay recorded at the end of {}", fy.label()))?;
+
+    let nominal_change_percent = if previous_annual_pay > 0.0 {
+        (current_annual_pay - previous_annual_pay) / previous_annual_pay * 100.0
+    } else {
+        0.0
+    };
+    let keep_pace_pay = previous_annual_pay * (1.0 + cpi_percent / 100.0);
+
+    Ok(RealPayCut {
+        financial_year: fy.label(),
+        previous_annual_pay,
+        current_annual_pay,
+        nominal_change_percent,
+        cpi_percent,
+        real_change_percent: nominal_change_percent - cpi_percent,
+        purchasing_power_lost: keep_pace_pay - current_annual_pay,
+    })
+}
+
+/// Splits each year's change in average income tax rate into bracket creep and real income growth.
+///
+/// Creep is the rate change the previous year's income would have seen had it grown only by CPI,
+/// taxed on this year's brackets; whatever remains came from real growth. Stage 3 and other
+/// threshold rises show up as negative creep. Only consecutive Australian years with a CPI
+/// figure are compared, and income is the ATO gross where lodged, otherwise the projected gross.
+pub fn bracket_creep_analysis(summaries: &[FinancialYearSummary]) -> BracketCreepReport {
+    let mut incomes: Vec<(FinancialYear, f64)> = summaries.iter()
+        .filter(|summary| summary.jurisdiction == Jurisdiction::AU)
+        .filter_map(|summary| {
+            let fy = FinancialYear::parse(&summary.financial_year).ok()?;
+            Some((fy, summary.ato_gross_income.unwrap_or(summary.projected_gross_income)))
+        })
+        .filter(|(_, income)| *income > 0.0)
+        .collect();
+    incomes.sort_by_key(|(fy, _)| fy.start_year);
+
+    let average_rate = |income: f64, fy: &FinancialYear| {
+        tax::income_tax(&Jurisdiction::AU, income, fy) / income * 100.0
+    };
+
+    let years: Vec<BracketCreepYear> = incomes.windows(2)
+        .filter(|pair| pair[1].0.start_year == pair[0].0.start_year + 1)
+        .filter_map(|pair| {
+            let (previous_fy, previous_income) = &pair[0];
+            let (fy, income) = &pair[1];
+            let cpi_percent = CPI_ANNUAL_CHANGE.iter()
+                .find(|(start_year, _)| *start_year == fy.start_year)
+                .map(|(_, change)| *change)?;
+
+            let previous_average_tax_rate = average_rate(*previous_income, previous_fy);
+            let average_tax_rate = average_rate(*income, fy);
+            let indexed_income = previous_income * (1.0 + cpi_percent / 100.0);
+            let indexed_rate = average_rate(indexed_income, fy);
+
+            Some(BracketCreepYear {
+                financial_year: fy.label(),
+                income: *income,
+                average_tax_rate,
+                previous_average_tax_rate,
+                cpi_percent,
+                creep_points: indexed_rate - previous_average_tax_rate,
+                real_growth_points: average_tax_rate - indexed_rate,
+                creep_tax: (indexed_rate - previous_average_tax_rate) / 100.0 * indexed_income,
+            })
+        })
+        .collect();
+
+    BracketCreepReport {
+        total_creep_points: years.iter().map(|year| year.creep_points).sum(),
+        total_real_growth_points: years.iter().map(|year| year.real_growth_points).sum(),
+        total_creep_tax: years.iter().map(|year| year.creep_tax).sum(),
+        years,
+    }
+}
+
+/// The headline figures of an analysis, with the employer super for the financial year as the projection.
+pub fn analysis_snapshot(
+    analysis: &EarningsAnalysis,
+    super_summary: &SuperContributionSummary,
+    taken_at: DateTime<Utc>,
+) -> AnalysisSnapshot {
+    AnalysisSnapshot {
+        id: None,
+        taken_at,
+        total_compensation: analysis.current_total_compensation,
+        income_percentile: analysis.income_percentile,
+        loyalty_tax_annual: analysis.loyalty_tax_annual,
+        loyalty_tax_cumulative: analysis.loyalty_tax_cumulative,
+        projected_super: super_summary.employer_contributions,
+    }
+}
+
+/// How the figures moved between two snapshots, whichever order they're given in.
+pub fn compare_snapshots(a: &AnalysisSnapshot, b: &AnalysisSnapshot) -> SnapshotComparison {
+    let (earlier, later) = if b.taken_at < a.taken_at { (b, a) } else { (a, b) };
+
+    SnapshotComparison {
+        days_between: (later.taken_at - earlier.taken_at).num_days(),
+        total_compensation_change: later.total_compensation - earlier.total_compensation,
+        income_percentile_change: later.income_percentile - earlier.income_percentile,
+        loyalty_tax_annual_change: later.loyalty_tax_annual - earlier.loyalty_tax_annual,
+        loyalty_tax_cumulative_change: later.loyalty_tax_cumulative - earlier.loyalty_tax_cumulative,
+        projected_super_change: later.projected_super - earlier.projected_super,
+        earlier: earlier.clone(),
+        later: later.clone(),
+    }
+}
+
+// Helper functions
+
+/// The super guarantee rate (percent) for the financial year.
+pub fn super_guarantee_rate(fy: &FinancialYear) -> f64 {
+    SUPER_RATES.iter()
+        .rev()
+        .find(|(start_year, _)| *start_year <= fy.start_year)
+        .or_else(|| SUPER_RATES.first())
+        .map(|(_, rate)| *rate)
+        .unwrap_or(9.5)
+}
+
+// Income protection premiums paid outside super for the part of the financial year the cover was held
+fn deductible_premiums(policies: &[InsurancePolicy], fy: &FinancialYear) -> f64 {
+    let year_days = (fy.end() - fy.start()).num_days() as f64 + 1.0;
+    policies.iter()
+        .filter(|p| p.policy_type == InsuranceType::IncomeProtection && !p.through_super)
+        .map(|p| {
+            let from = p.start_date.max(fy.start());
+            let to = p.end_date.map_or(fy.end(), |end| end.min(fy.end()));
+            let days_held = ((to - from).num_days() as f64 + 1.0).max(0.0);
+            p.annual_premium * days_held / year_days
+        })
+        .sum()
+}
+
+// The position's record in force at the end of the year (else the earliest one after it) and
+// the share of the year the position was held
+fn record_for_fy<'a>(
+    position: &Position,
+    records: &'a [CompensationRecord],
+    fy: &FinancialYear,
+) -> Option<(&'a CompensationRecord, f64)> {
+    let position_records: Vec<&CompensationRecord> = records.iter()
+        .filter(|r| Some(r.position_id) == position.id)
+        .collect();
+    let record = position_records.iter()
+        .filter(|r| r.effective_date <= fy.end())
+        .max_by_key(|r| r.effective_date)
+        .or_else(|| position_records.iter().min_by_key(|r| r.effective_date))?;
+
+    let start = position.start_date.max(fy.start());
+    let end = position.end_date.unwrap_or(fy.end()).min(fy.end());
+    if start > end {
+        return None;
+    }
+    let fy_days = dates::period_days(fy.start(), fy.end()) as f64;
+    Some((record, dates::period_days(start, end) as f64 / fy_days))
+}
+
+fn latest_record_for<'a>(position: &Position, records: &'a [CompensationRecord]) -> Option<&'a CompensationRecord> {
+    records.iter()
+        .filter(|r| Some(r.position_id) == position.id)
+        .max_by_key(|r| r.effective_date)
+}
+
+fn nz_earnings_snapshot(
+    position: &Position,
+    record: &CompensationRecord,
+    exchange_rates: &[ExchangeRate],
+) -> EarningsSnapshot {
+    let rate = currency::exchange_rate_for(
+        exchange_rates,
+        &record.currency,
+        &FinancialYear::containing(record.effective_date),
+    ).unwrap_or(0.0);
+    let tax_year = FinancialYear::containing_for(record.effective_date, &Jurisdiction::NZ);
+
+    let actual_annual = (record.annual_base() + record.annual_overtime_pay()
+        + record.annual_allowances() + record.annual_bonuses()) * rate;
+    let annual_hours = record.standard_weekly_hours * 52.0 + record.annual_overtime_hours();
+
+    EarningsSnapshot {
+        date: position.start_date,
+        base_annual: record.annual_base() * rate,
+        actual_annual,
+        total_with_super: actual_annual * (1.0 + tax::kiwisaver_employer_rate(&tax_year) / 100.0),
+        effective_hourly_rate: if annual_hours > 0.0 { actual_annual / annual_hours } else { 0.0 },
+        jurisdiction: Jurisdiction::NZ,
+        // The index only covers Australian locations
+        col_adjusted_annual: None,
+    }
+}
+
+/// The record's annual pay in AUD (base, overtime, allowances and bonuses) expressed at
+/// national-average prices for the position's location, so pay in places that cost more or
+/// less to live in can be compared.
+pub fn col_adjusted_earnings(
+    position: &Position,
+    record: &CompensationRecord,
+    exchange_rates: &[ExchangeRate],
+) -> Result<f64, String> {
+    let fy = FinancialYear::containing(record.effective_date);
+    let rate = currency::exchange_rate_for(exchange_rates, &record.currency, &fy)
+        .ok_or_else(|| format!("There's no {} exchange rate for {}", record.currency, fy.label()))?;
+    let (gross, _) = annual_gross_and_taxable(record);
+    cost_of_living::adjust(gross * rate, &position.location).ok_or_else(|| format!(
+        "There's no cost-of-living figure for '{}'; give the location with its state, such as 'Newcastle NSW'",
+        position.location
+    ))
+}
+
+/// The record's headline figures, with its annual package in the record's own currency.
+pub fn compensation_record_summary(record: &CompensationRecord) -> CompensationRecordSummary {
+    CompensationRecordSummary {
+        id: record.id,
+        effective_date: record.effective_date,
+        pay_type: record.pay_type.clone(),
+        base_rate: record.base_rate,
+        annual_gross: annual_gross_and_taxable(record).0,
+        currency: record.currency.clone(),
+    }
+}
+
+// Annual package, and the part of it that's taxed: less non-taxable allowances and bonuses,
+// and salary sacrifice
+fn annual_gross_and_taxable(record: &CompensationRecord) -> (f64, f64) {
+    let non_taxable = record.allowances.iter().filter(|a| !a.taxable).map(annualize_allowance).sum::<f64>()
+        + record.bonuses.iter().filter(|b| !b.taxable).map(|b| b.amount).sum::<f64>();
+    let gross = record.annual_base() + record.annual_overtime_pay()
+        + record.annual_allowances() + record.annual_bonuses();
+    let taxable = (gross - non_taxable - record.super_contributions.salary_sacrifice
+        - record.annual_pre_tax_packaging()).max(0.0);
+    (gross, taxable)
+}
+
+fn annualize_allowance(allowance: &Allowance) -> f64 {
+    let periods_per_year = match allowance.frequency {
+        AllowanceFrequency::Weekly => 52.0,
+        AllowanceFrequency::Fortnightly => 26.0,
+        AllowanceFrequency::Monthly => 12.0,
+        AllowanceFrequency::Annually => 1.0,
+    };
+    allowance.amount * periods_per_year
+}
+
+fn calculate_position_earnings(
+    position: &Position,
+    profile: &Option<UserProfile>,
+) -> (f64, f64) {
+    // This would normally fetch actual compensation records
+    // For now, estimate based on position data
+    let base_annual = position.base_salary_estimate();
+    
+    // Estimate overtime impact based on role and industry
+    let overtime_multiplier = estimate_overtime_multiplier(position, profile);
+    let actual_annual = base_annual * overtime_multiplier;
+    
+    // Calculate effective hourly rate (assuming 2080 hours/year for full-time)
+    let annual_hours = estimate_annual_hours(position);
+    let effective_hourly = if annual_hours > 0.0 {
+        actual_annual / annual_hours
+    } else {
+        0.0
+    };
+
+    (actual_annual, effective_hourly)
+}
+
+fn estimate_overtime_multiplier(
+    position: &Position,
+    profile: &Option<UserProfile>,
+) -> f64 {
+    // Base multiplier varies by role and industry
+    let base_multiplier = match position.seniority_level {
+        SeniorityLevel::Entry => 1.05,
+        SeniorityLevel::Junior => 1.10,
+        SeniorityLevel::Mid => 1.15,
+        SeniorityLevel::Senior => 1.20,
+        SeniorityLevel::Lead => 1.25,
+        SeniorityLevel::Manager => 1.10,
+        SeniorityLevel::Director => 1.05,
+        SeniorityLevel::Executive => 1.05,
+    };
+
+    // Adjust for industry (overtime-heavy industries)
+    let industry_adjustment = if let Some(profile) = profile {
+        match profile.industry.to_lowercase().as_str() {
+            s if s.contains("mining") => 1.30,
+            s if s.contains("construction") => 1.25,
+            s if s.contains("engineering") => 1.20,
+            s if s.contains("it") || s.contains("technology") => 1.15,
+            _ => 1.0,
+        }
+    } else {
+        1.0
+    };
+
+    // Adjust for personal overtime appetite
+    let personal_adjustment = if let Some(profile) = profile {
+        match profile.career_preferences.overtime_appetite {
+            OvertimeAppetite::None => 0.95,
+            OvertimeAppetite::Minimal => 1.0,
+            OvertimeAppetite::Moderate => 1.1,
+            OvertimeAppetite::High => 1.25,
+            OvertimeAppetite::Extreme => 1.4,
+        }
+    } else {
+        1.0
+    };
+
+    base_multiplier * industry_adjustment * personal_adjustment
+}
+
+fn estimate_annual_hours(position: &Position) -> f64 {
+    // Standard full-time hours in Australia: 38 hours/week
+    let standard_weekly = 38.0;
+    let weeks_per_year = 52.0;
+    
+    // Adjust for employment type
+    let hours_multiplier = match position.employment_type {
+        EmploymentType::Permanent => 1.0,
+        EmploymentType::Contract => 1.1, // Often work more hours
+        EmploymentType::Casual => 0.8,   // Variable hours
+    };
+
+    standard_weekly * weeks_per_year * hours_multiplier
+}
+
+fn has_overtime_heavy_earnings(positions: &[Position]) -> bool {
+    // Check if actual earnings significantly exceed base estimates
+    for position in positions {
+        let _base = position.base_salary_estimate();
+        let overtime_mult = estimate_overtime_multiplier(position, &None);
+        if overtime_mult > 1.2 {
+            return true;
+        }
+    }
+    false
+}
+
+fn calculate_income_percentile(
+    income: f64,
+    industry: &str,
+    _state: &AustralianState,
+) -> f64 {
+    percentile_against_median(income, calculate_industry_median(industry))
+}
+
+// Simplified percentile calculation
+// In production, this would use actual ABS data
+fn percentile_against_median(income: f64, industry_median: f64) -> f64 {
+    if income <= industry_median * 0.75 {
+        25.0
+    } else if income <= industry_median {
+        50.0
+    } else if income <= industry_median * 1.25 {
+        75.0
+    } else {
+        90.0
+    }
+}
+
+fn calculate_industry_median(industry: &str) -> f64 {
+    // Simplified industry median salaries (Australia, 2024)
+    match industry.to_lowercase().as_str() {
+        s if s.contains("mining") => 125000.0,
+        s if s.contains("it") || s.contains("technology") => 110000.0,
+        s if s.contains("engineering") => 105000.0,
+        s if s.contains("construction") => 95000.0,
+        s if s.contains("healthcare") => 85000.0,
+        s if s.contains("education") => 80000.0,
+        s if s.contains("finance") => 100000.0,
+        _ => 90000.0,
+    }
+}
+
+fn typical_years_per_level(industry: &str) -> f64 {
+    let industry = industry.to_lowercase();
+    TYPICAL_YEARS_PER_LEVEL.iter()
+        .find(|(name, _)| industry.contains(name))
+        .map(|(_, years)| *years)
+        .unwrap_or(DEFAULT_YEARS_PER_LEVEL)
+}
+
+// Rung on the ladder; Lead and Manager are parallel tracks at the same height
+fn seniority_rank(level: &SeniorityLevel) -> u32 {
+    match level {
+        SeniorityLevel::Entry => 0,
+        SeniorityLevel::Junior => 1,
+        SeniorityLevel::Mid => 2,
+        SeniorityLevel::Senior => 3,
+        SeniorityLevel::Lead | SeniorityLevel::Manager => 4,
+        SeniorityLevel::Director => 5,
+        SeniorityLevel::Executive => 6,
+    }
+}
+
+fn employment_type_rank(employment_type: &EmploymentType) -> u8 {
+    match employment_type {
+        EmploymentType::Permanent => 0,
+        EmploymentType::Contract => 1,
+        EmploymentType::Casual => 2,
+    }
+}
+
+fn calculate_total_experience(positions: &[Position]) -> f64 {
+    let mut total_days = 0;
+    for position in positions {
+        let end_date = position.end_date.unwrap_or_else(|| NaiveDate::from_ymd_opt(2024, 12, 31).unwrap());
+        total_days += (end_date - position.start_date).num_days();
+    }
+    total_days as f64 / 365.25
+}
+
+fn calculate_compensation_summary(positions: &[Position], today: NaiveDate) -> CompensationSummary {
+    if positions.is_empty() {
+        return CompensationSummary {
+            current_base: 0.0,
+            current_total: 0.0,
+            career_earnings_total: 0.0,
+            average_annual_increase: 0.0,
+        };
+    }
+
+    let current = current_position(positions, today);
+    let current_base = current.map(|p| p.base_salary_estimate()).unwrap_or(0.0);
+    let current_total = current
+        .map(|p| current_base * estimate_overtime_multiplier(p, &None))
+        .unwrap_or(0.0);
+    
+    let career_total: f64 = positions.iter()
+        .map(|p| p.base_salary_estimate() * estimate_overtime_multiplier(p, &None))
+        .sum();
+
+    // Calculate average annual increase
+    let avg_increase = if positions.len() > 1 {
+        let first_salary = positions.last().unwrap().base_salary_estimate();
+        let last_salary = positions.first().unwrap().base_salary_estimate();
+        let years = calculate_total_experience(positions);
+        if years > 0.0 && first_salary > 0.0 {
+            ((last_salary - first_salary) / first_salary) / years * 100.0
+        } else {
+            0.0
+        }
+    } else {
+        0.0
+    };
+
+    CompensationSummary {
+        current_base,
+        current_total,
+        career_earnings_total: career_total,
+        average_annual_increase: avg_increase,
+    }
+}
+
+fn format_duration(start: NaiveDate, end: NaiveDate) -> String {
+    let months = (end.year() - start.year()) * 12 + (end.month0() as i32 - start.month0() as i32);
+    let years = months / 12;
+    let remaining_months = months % 12;
+    
+    if years > 0 {
+        if remaining_months > 0 {
+            format!("{}y {}m", years, remaining_months)
+        } else {
+            format!("{}y", years)
+        }
+    } else {
+        format!("{}m", remaining_months)
+    }
+}
+
+// Extension trait for Position
+trait PositionExt {
+    fn base_salary_estimate(&self) -> f64;
+}
+
+impl PositionExt for Position {
+    fn base_salary_estimate(&self) -> f64 {
+        // Estimate base salary based on role, industry, and seniority
+        let base = match self.seniority_level {
+            SeniorityLevel::Entry => 60000.0,
+            SeniorityLevel::Junior => 75000.0,
+            SeniorityLevel::Mid => 95000.0,
+            SeniorityLevel::Senior => 120000.0,
+            SeniorityLevel::Lead => 140000.0,
+            SeniorityLevel::Manager => 130000.0,
+            SeniorityLevel::Director => 180000.0,
+            SeniorityLevel::Executive => 250000.0,
+        };
+
+        // Adjust for employment type
+        let employment_adjustment = match self.employment_type {
+            EmploymentType::Permanent => 1.0,
+            EmploymentType::Contract => 1.2, // 20% premium for contract
+            EmploymentType::Casual => 1.25, // 25% loading for casual
+        };
+
+        base * employment_adjustment
+    }
+}
+
+// AUD base pay across the positions held on the date, from the record in force for each
+fn annual_base_pay_on(
+    date: NaiveDate,
+    fy: &FinancialYear,
+    positions: &[Position],
+    records: &[CompensationRecord],
+    exchange_rates: &[ExchangeRate],
+) -> Option<f64> {
+    let pays: Vec<f64> = positions.iter()
+        .filter(|p| p.start_date <= date && p.end_date.map(|end| end >= date).unwrap_or(true))
+        .filter_map(|position| {
+            let record = records.iter()
+                .filter(|r| Some(r.position_id) == position.id && r.effective_date <= date)
+                .max_by_key(|r| r.effective_date)?;
+            let rate = currency::exchange_rate_for(exchange_rates, &record.currency, fy)?;
+            Some(record.annual_base() * rate)
+        })
+        .collect();
+
+    if pays.is_empty() { None } else { Some(pays.iter().sum()) }
+}
+
+// Extension trait for CompensationRecord
+trait CompensationRecordExt {
+    fn annual_base(&self) -> f64;
+    fn base_hourly_rate(&self) -> f64;
+    fn annual_overtime_hours(&self) -> f64;
+    fn annual_overtime_pay(&self) -> f64;
+    fn annual_allowances(&self) -> f64;
+    fn annual_bonuses(&self) -> f64;
+    fn annual_ordinary_time_earnings(&self) -> f64;
+    fn annual_employer_super(&self) -> f64;
+    fn pay_periods_per_year(&self) -> f64;
+    fn annual_pre_tax_packaging(&self) -> f64;
+    fn annual_post_tax_packaging(&self) -> f64;
+    fn annual_fbt_packaging(&self) -> f64;
+    fn annual_non_cash_benefits(&self) -> f64;
+    fn annual_fringe_benefits(&self) -> f64;
+}
+
+impl CompensationRecordExt for CompensationRecord {
+    fn annual_base(&self) -> f64 {
+        match self.pay_type {
+            PayType::Salary => self.base_rate,
+            PayType::Hourly => self.base_rate * self.standard_weekly_hours * 52.0,
+        }
+    }
+
+    fn base_hourly_rate(&self) -> f64 {
+        match self.pay_type {
+            PayType::Hourly => self.base_rate,
+            PayType::Salary => {
+                let ordinary_hours = self.standard_weekly_hours * 52.0;
+                if ordinary_hours > 0.0 { self.base_rate / ordinary_hours } else { 0.0 }
+            }
+        }
+    }
+
+    fn annual_overtime_hours(&self) -> f64 {
+        self.overtime.annual_hours
+            .unwrap_or(self.overtime.average_hours_per_week * 52.0)
+    }
+
+    fn annual_overtime_pay(&self) -> f64 {
+        self.base_hourly_rate() * self.overtime.rate_multiplier * self.annual_overtime_hours()
+    }
+
+    fn annual_allowances(&self) -> f64 {
+        self.allowances.iter().map(annualize_allowance).sum()
+    }
+
+    fn annual_bonuses(&self) -> f64 {
+        self.bonuses.iter().map(|b| b.amount).sum()
+    }
+
+    // Base pay for the standard hours and ordinary-time allowances; never overtime
+    fn annual_ordinary_time_earnings(&self) -> f64 {
+        self.annual_base()
+            + self.allowances.iter().filter(|a| a.is_ordinary_time()).map(annualize_allowance).sum::<f64>()
+    }
+
+    fn annual_employer_super(&self) -> f64 {
+        self.annual_ordinary_time_earnings() * self.super_contributions.contribution_rate / 100.0
+    }
+
+    // Packaging amounts are per payslip; without a frequency they're taken as weekly
+    fn pay_periods_per_year(&self) -> f64 {
+        self.payslip_frequency.clone().unwrap_or_default().periods_per_year()
+    }
+
+    fn annual_pre_tax_packaging(&self) -> f64 {
+        self.packaging.iter().map(|p| p.pre_tax_amount_per_period).sum::<f64>() * self.pay_periods_per_year()
+    }
+
+    fn annual_post_tax_packaging(&self) -> f64 {
+        self.packaging.iter().map(|p| p.post_tax_amount_per_period).sum::<f64>() * self.pay_periods_per_year()
+    }
+
+    fn annual_fbt_packaging(&self) -> f64 {
+        self.packaging.iter()
+            .filter(|p| p.attracts_fbt)
+            .map(|p| p.pre_tax_amount_per_period)
+            .sum::<f64>() * self.pay_periods_per_year()
+    }
+
+    fn annual_non_cash_benefits(&self) -> f64 {
+        self.non_cash_benefits.iter().map(|b| b.annual_value).sum()
+    }
+
+    // Taxable value of everything that's a fringe benefit, before the gross-up
+    fn annual_fringe_benefits(&self) -> f64 {
+        self.annual_fbt_packaging()
+            + self.non_cash_benefits.iter().filter(|b| b.fringe_benefit).map(|b| b.annual_value).sum::<f64>()
+    }
+}
@@ -0,0 +1,226 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// How a text cell from an imported file is turned into a field value.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Coercion {
+    Text,
+    Integer,
+    Number,
+    Money,   // "$95,000", "95k", "(1,200.50)"
+    Hours,   // "38", "38 hrs", "37.5 hours per week"
+    Percent, // "11.5%" or "11.5", stored as 11.5
+    Date,
+    Boolean,
+    List,    // Separated by ';', '|' or ','
+}
+
+// Day-first formats only: a file from an Australian tool never means 07/08 as 8 July
+const DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d",
+    "%d/%m/%Y",
+    "%d-%m-%Y",
+    "%d.%m.%Y",
+    "%Y/%m/%d",
+    "%d %b %Y",
+    "%d %B %Y",
+    "%d-%b-%Y",
+    "%b %d, %Y",
+    "%B %d, %Y",
+];
+const SHORT_YEAR_FORMATS: &[&str] = &["%d/%m/%y", "%d-%m-%y", "%d.%m.%y", "%d %b %y", "%d-%b-%y"];
+const DATETIME_FORMATS: &[&str] = &["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S%.f"];
+const MONTH_FORMATS: &[&str] = &["%b %Y", "%B %Y", "%m/%Y", "%Y-%m"];
+const SHORT_YEAR_MONTH_FORMATS: &[&str] = &["%b %y", "%B %y", "%m/%y"];
+
+const HOURS_SUFFIXES: &[&str] = &["hours", "hour", "hrs", "hr", "h"];
+const PER_PERIOD_SUFFIXES: &[&str] = &["per week", "/week", "/wk", "pw", "p/w"];
+const CURRENCY_PREFIXES: &[&str] = &["AUD", "NZD", "USD", "A$", "NZ$", "US$", "$"];
+
+/// Converts a non-empty cell to the JSON value stored in the record.
+pub fn coerce(raw: &str, coercion: Coercion) -> Result<Value, String> {
+    let value = raw.trim();
+    match coercion {
+        Coercion::Text => Ok(Value::String(value.to_string())),
+        Coercion::Integer => parse_integer(value).map(Value::from),
+        Coercion::Number => parse_number(value).map(Value::from),
+        Coercion::Money => parse_money(value).map(Value::from),
+        Coercion::Hours => parse_hours(value).map(Value::from),
+        Coercion::Percent => parse_percent(value).map(Value::from),
+        Coercion::Date => parse_date(value).map(|d| Value::String(d.to_string())),
+        Coercion::Boolean => parse_bool(value).map(Value::Bool),
+        Coercion::List => Ok(Value::from(parse_list(value))),
+    }
+}
+
+/// A plain number, allowing thousands separators and surrounding spaces.
+pub fn parse_number(value: &str) -> Result<f64, String> {
+    let cleaned: String = value.trim().chars().filter(|c| *c != ',' && *c != '_' && !c.is_whitespace()).collect();
+    match cleaned.parse::<f64>() {
+        Ok(number) if number.is_finite() => Ok(number),
+        _ => Err(format!("'{}' is not a number", value.trim())),
+    }
+}
+
+pub fn parse_integer(value: &str) -> Result<i64, String> {
+    let number = parse_number(value)?;
+    if number.fract() != 0.0 {
+        return Err(format!("'{}' is not a whole number", value.trim()));
+    }
+    Ok(number as i64)
+}
+
+/// An amount of money: currency symbols or codes, thousands separators, a `k`/`m` suffix
+/// and accounting-style negatives in parentheses are all accepted.
+pub fn parse_money(value: &str) -> Result<f64, String> {
+    let original = value.trim();
+    let mut text = original.to_string();
+
+    let negative = (text.starts_with('(') && text.ends_with(')')) || text.starts_with('-');
+    text = text.trim_start_matches('-').trim_start_matches('(').trim_end_matches(')').trim().to_string();
+
+    for prefix in CURRENCY_PREFIXES {
+        if let Some(rest) = strip_prefix_ignore_case(&text, prefix) {
+            text = rest.trim_start().to_string();
+            break;
+        }
+    }
+    for code in ["AUD", "NZD", "USD"] {
+        if let Some(rest) = strip_suffix_ignore_case(&text, code) {
+            text = rest.trim_end().to_string();
+        }
+    }
+    // A sign written after the symbol, e.g. "$-250"
+    let negative = negative || text.starts_with('-');
+    text = text.trim_start_matches('-').to_string();
+
+    let multiplier = match text.chars().last() {
+        Some('k') | Some('K') => 1_000.0,
+        Some('m') | Some('M') => 1_000_000.0,
+        _ => 1.0,
+    };
+    if multiplier > 1.0 {
+        text.pop();
+    }
+
+    let amount = parse_number(&text).map_err(|_| format!("'{}' is not an amount", original))? * multiplier;
+    Ok(if negative { -amount } else { amount })
+}
+
+/// Hours such as "38", "38 hrs", "37.5 hours per week".
+pub fn parse_hours(value: &str) -> Result<f64, String> {
+    let original = value.trim();
+    let mut text = original.to_lowercase();
+
+    for suffix in PER_PERIOD_SUFFIXES {
+        if let Some(rest) = text.strip_suffix(suffix) {
+            text = rest.trim_end().to_string();
+            break;
+        }
+    }
+    for suffix in HOURS_SUFFIXES {
+        if let Some(rest) = text.strip_suffix(suffix) {
+            text = rest.trim_end().to_string();
+            break;
+        }
+    }
+
+    let hours = parse_number(&text).map_err(|_| format!("'{}' is not a number of hours", original))?;
+    if hours < 0.0 {
+        return Err(format!("'{}' is negative", original));
+    }
+    Ok(hours)
+}
+
+/// A percentage stored as the number before the sign: "11.5%" and "11.5" both give 11.5.
+pub fn parse_percent(value: &str) -> Result<f64, String> {
+    let original = value.trim();
+    let text = original.strip_suffix('%').unwrap_or(original);
+    parse_number(text).map_err(|_| format!("'{}' is not a percentage", original))
+}
+
+/// A calendar date, day first when ambiguous. Dates with a time keep only the date, and a
+/// month on its own ("Jul 2024") means the first of the month.
+pub fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    let text = value.trim();
+
+    // chrono reads "24" as the year 24 under %Y, so two-digit years need their own formats
+    let (formats, month_formats) = if has_two_digit_year(text) {
+        (SHORT_YEAR_FORMATS, SHORT_YEAR_MONTH_FORMATS)
+    } else {
+        (DATE_FORMATS, MONTH_FORMATS)
+    };
+    for format in formats {
+        if let Ok(date) = NaiveDate::parse_from_str(text, format) {
+            return Ok(date);
+        }
+    }
+    for format in DATETIME_FORMATS {
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(text, format) {
+            return Ok(datetime.date());
+        }
+    }
+    if let Ok(datetime) = chrono::DateTime::parse_from_rfc3339(text) {
+        return Ok(datetime.date_naive());
+    }
+    for format in month_formats {
+        if let Ok(date) = NaiveDate::parse_from_str(&format!("1 {}", text), &format!("%d {}", format)) {
+            return Ok(date);
+        }
+    }
+
+    Err(format!("'{}' is not a date", text))
+}
+
+pub fn parse_bool(value: &str) -> Result<bool, String> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "yes" | "y" | "1" | "x" => Ok(true),
+        "false" | "no" | "n" | "0" => Ok(false),
+        _ => Err(format!("'{}' is not yes or no", value.trim())),
+    }
+}
+
+/// Splits on the first separator present out of ';', '|' and ','.
+pub fn parse_list(value: &str) -> Vec<String> {
+    let separator = [';', '|', ','].into_iter().find(|s| value.contains(*s));
+    let items: Vec<&str> = match separator {
+        Some(separator) => value.split(separator).collect(),
+        None => vec![value],
+    };
+    items.into_iter()
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Matches a cell against the allowed names of an enum, ignoring case, spaces, hyphens and
+/// underscores: "full-time" would match "FullTime".
+pub fn coerce_choice(value: &str, choices: &[&str]) -> Result<String, String> {
+    let normalise = |s: &str| s.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase();
+    let wanted = normalise(value);
+    choices.iter()
+        .find(|choice| normalise(choice) == wanted)
+        .map(|choice| choice.to_string())
+        .ok_or_else(|| format!("'{}' must be one of {}", value.trim(), choices.join(", ")))
+}
+
+fn has_two_digit_year(text: &str) -> bool {
+    let parts: Vec<&str> = text.split(['/', '-', '.', ' ']).collect();
+    let is_digits = |part: &&str| part.chars().all(|c| c.is_ascii_digit());
+    let four_digit_year = parts.iter().any(|part| part.len() == 4 && is_digits(part));
+    let last = parts.last().filter(|last| last.len() == 2 && is_digits(last));
+    !four_digit_year && last.is_some()
+}
+
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = text.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix).then(|| &text[prefix.len()..])
+}
+
+fn strip_suffix_ignore_case<'a>(text: &'a str, suffix: &str) -> Option<&'a str> {
+    let split = text.len().checked_sub(suffix.len())?;
+    let tail = text.get(split..)?;
+    tail.eq_ignore_ascii_case(suffix).then(|| &text[..split])
+}
@@ -0,0 +1,155 @@
+use crate::database::Database;
+use crate::models::*;
+use crate::validation;
+use chrono::{NaiveDate, Utc};
+
+const EXACT_CONFIDENCE: f64 = 100.0;
+
+/// Copies a compensation record to a new effective date with the overrides applied, for a
+/// raise that leaves most of the package unchanged. The copy is validated and saved, and
+/// returned with its new id.
+pub fn clone_record(
+    db: &Database,
+    id: i64,
+    new_effective_date: NaiveDate,
+    overrides: CompensationRecordOverrides,
+) -> Result<CompensationRecord, String> {
+    let source = db.get_compensation_record(id)?
+        .ok_or_else(|| format!("Compensation record {} not found", id))?;
+
+    let taken = db.get_compensation_records(source.position_id)?
+        .iter()
+        .any(|r| r.effective_date == new_effective_date);
+    if taken {
+        return Err(format!("The position already has a record effective {}", new_effective_date));
+    }
+
+    let mut record = apply_overrides(source, overrides);
+    record.id = None;
+    record.effective_date = new_effective_date;
+    record.created_at = Utc::now();
+
+    validation::validate_compensation_record(&record)?;
+    record.id = Some(db.save_compensation_record(record.clone()).map_err(|e| e.to_string())?);
+    Ok(record)
+}
+
+/// Folds the secondary record into the primary, for a pay situation entered as several partial
+/// records (a base record and an overtime-only one, say). The primary keeps its effective date
+/// and gains the secondary's allowances and bonuses, its overtime if the primary has none, and
+/// the higher confidence. The secondary is deleted. Returns the primary's id.
+pub fn merge_records(db: &Database, primary_id: i64, secondary_id: i64) -> Result<i64, String> {
+    if primary_id == secondary_id {
+        return Err("Can't merge a compensation record with itself".to_string());
+    }
+    let mut primary = db.get_compensation_record(primary_id)?
+        .ok_or_else(|| format!("Compensation record {} not found", primary_id))?;
+    let secondary = db.get_compensation_record(secondary_id)?
+        .ok_or_else(|| format!("Compensation record {} not found", secondary_id))?;
+
+    if primary.position_id != secondary.position_id {
+        return Err("Can't merge compensation records from different positions".to_string());
+    }
+    if primary.currency != secondary.currency {
+        return Err(format!(
+            "Can't merge a record in {} with one in {}",
+            primary.currency, secondary.currency
+        ));
+    }
+
+    primary.allowances.extend(secondary.allowances);
+    primary.bonuses.extend(secondary.bonuses);
+    if matches!(primary.overtime.frequency, OvertimeFrequency::None) {
+        primary.overtime = secondary.overtime;
+    }
+    primary.tax_withheld = primary.tax_withheld.or(secondary.tax_withheld);
+    primary.payslip_frequency = primary.payslip_frequency.or(secondary.payslip_frequency);
+    primary.equity = primary.equity.or(secondary.equity);
+    if primary.packaging.is_empty() {
+        primary.packaging = secondary.packaging;
+    }
+    if primary.non_cash_benefits.is_empty() {
+        primary.non_cash_benefits = secondary.non_cash_benefits;
+    }
+    primary.confidence_score = primary.confidence_score.max(secondary.confidence_score);
+    primary.notes = match (primary.notes.take(), secondary.notes) {
+        (Some(a), Some(b)) if a != b => Some(format!("{}\n{}", a, b)),
+        (a, b) => a.or(b),
+    };
+
+    validation::validate_compensation_record(&primary)?;
+    db.in_transaction(|db| {
+        db.save_compensation_record(primary).map_err(|e| e.to_string())?;
+        db.delete_compensation_record(secondary_id).map_err(|e| e.to_string())
+    })?;
+    Ok(primary_id)
+}
+
+/// Firms up a fuzzy record once the real figures are known: applies the corrections, marks it
+/// exact with full confidence, and keeps the record as it was in the change log. Returns the
+/// upgraded record.
+pub fn upgrade_record_to_exact(
+    db: &Database,
+    id: i64,
+    corrections: CompensationRecordOverrides,
+) -> Result<CompensationRecord, String> {
+    let before = db.get_compensation_record(id)?
+        .ok_or_else(|| format!("Compensation record {} not found", id))?;
+    if matches!(before.entry_type, CompensationEntryType::Exact) {
+        return Err(format!("Compensation record {} is already exact", id));
+    }
+
+    let mut record = apply_overrides(before.clone(), corrections);
+    record.entry_type = CompensationEntryType::Exact;
+    record.confidence_score = EXACT_CONFIDENCE;
+
+    validation::validate_compensation_record(&record)?;
+    db.in_transaction(|db| {
+        db.log_record_change(id, "Upgraded to exact", &before).map_err(|e| e.to_string())?;
+        db.save_compensation_record(record.clone()).map_err(|e| e.to_string())
+    })?;
+    Ok(record)
+}
+
+fn apply_overrides(mut record: CompensationRecord, overrides: CompensationRecordOverrides) -> CompensationRecord {
+    if let Some(entry_type) = overrides.entry_type {
+        record.entry_type = entry_type;
+    }
+    if let Some(pay_type) = overrides.pay_type {
+        record.pay_type = pay_type;
+    }
+    if let Some(base_rate) = overrides.base_rate {
+        record.base_rate = base_rate;
+    }
+    if let Some(hours) = overrides.standard_weekly_hours {
+        record.standard_weekly_hours = hours;
+    }
+    if let Some(overtime) = overrides.overtime {
+        record.overtime = overtime;
+    }
+    if let Some(allowances) = overrides.allowances {
+        record.allowances = allowances;
+    }
+    if let Some(bonuses) = overrides.bonuses {
+        record.bonuses = bonuses;
+    }
+    if let Some(rate) = overrides.super_contribution_rate {
+        record.super_contributions.contribution_rate = rate;
+    }
+    if let Some(additional) = overrides.super_additional_contributions {
+        record.super_contributions.additional_contributions = additional;
+    }
+    if let Some(sacrifice) = overrides.super_salary_sacrifice {
+        record.super_contributions.salary_sacrifice = sacrifice;
+    }
+    if let Some(tax_withheld) = overrides.tax_withheld {
+        record.tax_withheld = Some(tax_withheld);
+    }
+    if let Some(confidence) = overrides.confidence_score {
+        record.confidence_score = confidence;
+    }
+    if let Some(notes) = overrides.notes {
+        record.notes = Some(notes);
+    }
+    record
+}
@@ -0,0 +1,41 @@
+use crate::anonymize;
+use crate::models::*;
+
+// Relative cost of living against the national average of 100: (state, capital city, rest of
+// the state). Weighted from capital city price levels and median rents, so a guide to how far
+// pay goes rather than a quote. Remote WA and the NT run above their capitals.
+const STATE_INDEX: &[(AustralianState, f64, f64)] = &[
+    (AustralianState::NSW, 116.0, 97.0),
+    (AustralianState::VIC, 104.0, 94.0),
+    (AustralianState::QLD, 101.0, 96.0),
+    (AustralianState::WA, 100.0, 104.0),
+    (AustralianState::SA, 95.0, 90.0),
+    (AustralianState::TAS, 97.0, 91.0),
+    (AustralianState::ACT, 106.0, 106.0),
+    (AustralianState::NT, 104.0, 110.0),
+];
+
+// Capital cities and the suburbs often given in place of them
+const CAPITAL_PLACES: &[&str] = &[
+    "sydney", "parramatta", "melbourne", "brisbane", "perth", "fremantle",
+    "adelaide", "hobart", "canberra", "darwin",
+];
+
+pub const BASELINE_INDEX: f64 = 100.0;
+
+/// The cost-of-living index for a free-text location, or None when its state can't be told
+/// (overseas, or too vague). Anywhere not named as a capital counts as the rest of its state.
+pub fn index_for_location(location: &str) -> Option<f64> {
+    let state = anonymize::state_from_location(location)?;
+    let lower = location.to_lowercase();
+    let capital = CAPITAL_PLACES.iter().any(|place| lower.contains(place));
+    STATE_INDEX.iter()
+        .find(|(s, _, _)| *s == state)
+        .map(|&(_, capital_index, regional_index)| if capital { capital_index } else { regional_index })
+}
+
+/// `amount` earned at `location` expressed at national-average prices, or None when the
+/// location has no index.
+pub fn adjust(amount: f64, location: &str) -> Option<f64> {
+    index_for_location(location).map(|index| amount * BASELINE_INDEX / index)
+}
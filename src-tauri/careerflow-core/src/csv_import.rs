@@ -0,0 +1,114 @@
+use crate::coercion;
+use crate::dates::FinancialYear;
+use crate::models::*;
+use crate::validation;
+use chrono::{NaiveDate, Utc};
+use std::collections::HashMap;
+use std::io::Read;
+
+// Columns a payslip CSV must have; everything else is optional
+const REQUIRED_COLUMNS: &[&str] = &["week_ending", "gross_pay"];
+
+/// Parses a payslip CSV into weekly entries without saving anything.
+///
+/// Column names match the weekly entry fields (the same headers the CSV export writes) and
+/// are case-insensitive. Dates and amounts are read as in [`coercion`], so `07/07/2024` and
+/// `$2,150.00` are fine. Rows that fail to parse or validate are reported by line.
+pub fn parse_weekly_entries_csv(reader: impl Read) -> Result<CsvImportPreview, String> {
+    let mut csv_reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(reader);
+
+    let headers: Vec<String> = csv_reader.headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?
+        .iter()
+        .map(|h| h.to_lowercase())
+        .collect();
+
+    let missing: Vec<&str> = REQUIRED_COLUMNS.iter()
+        .filter(|column| !headers.iter().any(|h| h == *column))
+        .copied()
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("CSV is missing required columns: {}", missing.join(", ")));
+    }
+
+    let mut preview = CsvImportPreview { entries: Vec::new(), errors: Vec::new() };
+    for (index, record) in csv_reader.records().enumerate() {
+        // Line 1 is the header
+        let line = index + 2;
+        let result = record
+            .map_err(|e| e.to_string())
+            .and_then(|record| {
+                let row: HashMap<&str, &str> = headers.iter()
+                    .map(String::as_str)
+                    .zip(record.iter())
+                    .filter(|(_, value)| !value.is_empty())
+                    .collect();
+                weekly_entry_from_row(&row)
+            });
+
+        match result {
+            Ok(entry) => preview.entries.push(entry),
+            Err(message) => preview.errors.push(CsvRowError { line, message }),
+        }
+    }
+
+    Ok(preview)
+}
+
+fn weekly_entry_from_row(row: &HashMap<&str, &str>) -> Result<WeeklyCompensationEntry, String> {
+    let week_ending = date_column(row, "week_ending")?
+        .ok_or_else(|| "week_ending is empty".to_string())?;
+    let gross_pay = number_column(row, "gross_pay")?
+        .ok_or_else(|| "gross_pay is empty".to_string())?;
+    let tax_withheld = number_column(row, "tax_withheld")?.unwrap_or(0.0);
+
+    let entry = WeeklyCompensationEntry {
+        id: None,
+        position_id: number_column(row, "position_id")?.map(|id| id as i64),
+        financial_year: FinancialYear::containing(week_ending).label(),
+        week_ending,
+        period_start: date_column(row, "period_start")?,
+        period_type: match row.get("period_type") {
+            Some(value) => parse_frequency(value)?,
+            None => PayslipFrequency::Weekly,
+        },
+        gross_pay,
+        tax_withheld,
+        net_pay: number_column(row, "net_pay")?.unwrap_or(gross_pay - tax_withheld),
+        hours_ordinary: number_column(row, "hours_ordinary")?.unwrap_or(0.0),
+        hours_overtime: number_column(row, "hours_overtime")?.unwrap_or(0.0),
+        overtime_rate_multiplier: number_column(row, "overtime_rate_multiplier")?.unwrap_or(1.5),
+        allowances: Vec::new(),
+        super_contributed: number_column(row, "super_contributed")?.unwrap_or(0.0),
+        currency: row.get("currency").map(|c| c.to_uppercase()).unwrap_or_else(default_currency),
+        notes: row.get("notes").map(|n| n.to_string()),
+        created_at: Utc::now(),
+    };
+
+    validation::validate_weekly_entry(&entry)?;
+    Ok(entry)
+}
+
+fn date_column(row: &HashMap<&str, &str>, column: &str) -> Result<Option<NaiveDate>, String> {
+    row.get(column)
+        .map(|value| coercion::parse_date(value).map_err(|e| format!("{}: {}", column, e)))
+        .transpose()
+}
+
+fn number_column(row: &HashMap<&str, &str>, column: &str) -> Result<Option<f64>, String> {
+    row.get(column)
+        .map(|value| coercion::parse_money(value).map_err(|e| format!("{}: {}", column, e)))
+        .transpose()
+}
+
+fn parse_frequency(value: &str) -> Result<PayslipFrequency, String> {
+    match value.to_lowercase().as_str() {
+        "weekly" => Ok(PayslipFrequency::Weekly),
+        "fortnightly" => Ok(PayslipFrequency::Fortnightly),
+        "monthly" => Ok(PayslipFrequency::Monthly),
+        _ => Err(format!("period_type '{}' must be Weekly, Fortnightly or Monthly", value)),
+    }
+}
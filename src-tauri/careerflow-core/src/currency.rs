@@ -0,0 +1,36 @@
+use crate::dates::FinancialYear;
+use crate::models::ExchangeRate;
+
+/// All analysis is reported in Australian dollars.
+pub const BASE_CURRENCY: &str = "AUD";
+
+// ISO 4217 codes accepted on compensation, weekly and yearly entries
+const KNOWN_CURRENCIES: &[&str] = &[
+    "AUD", "NZD", "USD", "GBP", "EUR", "CAD", "CHF", "SGD", "HKD", "JPY",
+    "CNY", "INR", "IDR", "MYR", "PHP", "THB", "VND", "KRW", "AED", "SAR",
+    "QAR", "ZAR", "SEK", "NOK", "DKK", "PGK", "FJD",
+];
+
+pub fn is_known_currency(code: &str) -> bool {
+    KNOWN_CURRENCIES.contains(&code)
+}
+
+pub fn is_base_currency(code: &str) -> bool {
+    code == BASE_CURRENCY
+}
+
+/// AUD value of one unit of `currency` for the financial year.
+///
+/// Falls back to the nearest financial year with a recorded rate for that currency,
+/// and returns None when the user has never entered a rate for it.
+pub fn exchange_rate_for(rates: &[ExchangeRate], currency: &str, fy: &FinancialYear) -> Option<f64> {
+    if is_base_currency(currency) {
+        return Some(1.0);
+    }
+
+    rates.iter()
+        .filter(|r| r.currency == currency)
+        .filter_map(|r| FinancialYear::parse(&r.financial_year).ok().map(|rate_fy| (rate_fy, r.rate_to_aud)))
+        .min_by_key(|(rate_fy, _)| (rate_fy.start_year - fy.start_year).abs())
+        .map(|(_, rate)| rate)
+}
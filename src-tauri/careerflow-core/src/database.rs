@@ -0,0 +1,2711 @@
+use crate::dates::{self, FinancialYear};
+use crate::models::*;
+use crate::error::AppError;
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension, Result as SqlResult};
+use chrono::{DateTime, Utc, NaiveDate, Weekday};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Safe JSON serialization helper - converts serde_json errors to rusqlite errors
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, rusqlite::Error> {
+    serde_json::to_string(value)
+        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+}
+
+const POSITION_COLUMNS: &str =
+    "id, employer_name, job_title, employment_type, location,
+     start_date, end_date, seniority_level, core_responsibilities,
+     tools_systems_skills, achievements, created_at, updated_at, industry, week_ending_day,
+     notice_period_weeks, leave_loading_percent, probation_months, commute_minutes_per_day, archived";
+
+const POSITION_SUMMARY_COLUMNS: &str =
+    "id, employer_name, job_title, employment_type, location, start_date, end_date, seniority_level";
+
+fn position_summary_from_row(row: &rusqlite::Row) -> SqlResult<PositionSummary> {
+    Ok(PositionSummary {
+        id: Some(row.get(0)?),
+        employer_name: row.get(1)?,
+        job_title: row.get(2)?,
+        employment_type: serde_json::from_str(&row.get::<_, String>(3)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e)))?,
+        location: row.get(4)?,
+        start_date: NaiveDate::parse_from_str(&row.get::<_, String>(5)?, "%Y-%m-%d")
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(5, rusqlite::types::Type::Text, Box::new(e)))?,
+        end_date: {
+            match row.get::<_, Option<String>>(6)? {
+                Some(s) => Some(NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(6, rusqlite::types::Type::Text, Box::new(e)))?),
+                None => None,
+            }
+        },
+        seniority_level: serde_json::from_str(&row.get::<_, String>(7)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(7, rusqlite::types::Type::Text, Box::new(e)))?,
+    })
+}
+
+struct JsonArrayColumn {
+    table: &'static str,
+    column: &'static str,
+    parses: fn(&str) -> bool,
+    string_list: bool, // Plain text can be split on commas into the list
+}
+
+fn parses_as<T: serde::de::DeserializeOwned>(json: &str) -> bool {
+    serde_json::from_str::<T>(json).is_ok()
+}
+
+const JSON_ARRAY_COLUMNS: &[JsonArrayColumn] = &[
+    JsonArrayColumn { table: "positions", column: "tools_systems_skills", parses: parses_as::<Vec<String>>, string_list: true },
+    JsonArrayColumn { table: "positions", column: "achievements", parses: parses_as::<Vec<String>>, string_list: true },
+    JsonArrayColumn { table: "compensation_records", column: "allowances", parses: parses_as::<Vec<Allowance>>, string_list: false },
+    JsonArrayColumn { table: "compensation_records", column: "bonuses", parses: parses_as::<Vec<Bonus>>, string_list: false },
+    JsonArrayColumn { table: "weekly_entries", column: "allowances", parses: parses_as::<Vec<Allowance>>, string_list: false },
+    JsonArrayColumn { table: "yearly_income_entries", column: "allowances", parses: parses_as::<Vec<Allowance>>, string_list: false },
+];
+
+// A single item saved without its array, or a skills list typed as "Excel, SAP"
+fn lenient_json_array(original: &str, column: &JsonArrayColumn) -> Option<String> {
+    let trimmed = original.trim();
+    let wrapped = format!("[{}]", trimmed);
+    if !trimmed.is_empty() && (column.parses)(&wrapped) {
+        return Some(wrapped);
+    }
+    if !column.string_list || trimmed.starts_with('[') || trimmed.starts_with('{') {
+        return None;
+    }
+    let items: Vec<&str> = trimmed
+        .split([',', '\n'])
+        .map(|item| item.trim().trim_matches('"').trim())
+        .filter(|item| !item.is_empty())
+        .collect();
+    if items.is_empty() {
+        return None;
+    }
+    serde_json::to_string(&items).ok()
+}
+
+fn position_from_row(row: &rusqlite::Row) -> SqlResult<Position> {
+    let tools_json: String = row.get(9)?;
+    let achievements_json: String = row.get(10)?;
+
+    Ok(Position {
+        id: Some(row.get(0)?),
+        employer_name: row.get(1)?,
+        job_title: row.get(2)?,
+        employment_type: serde_json::from_str(&row.get::<_, String>(3)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e)))?,
+        location: row.get(4)?,
+        start_date: NaiveDate::parse_from_str(&row.get::<_, String>(5)?, "%Y-%m-%d")
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(5, rusqlite::types::Type::Text, Box::new(e)))?,
+        end_date: {
+            match row.get::<_, Option<String>>(6)? {
+                Some(s) => Some(NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(6, rusqlite::types::Type::Text, Box::new(e)))?),
+                None => None,
+            }
+        },
+        seniority_level: serde_json::from_str(&row.get::<_, String>(7)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(7, rusqlite::types::Type::Text, Box::new(e)))?,
+        core_responsibilities: row.get(8)?,
+        tools_systems_skills: serde_json::from_str(&tools_json)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(9, rusqlite::types::Type::Text, Box::new(e)))?,
+        achievements: serde_json::from_str(&achievements_json)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(10, rusqlite::types::Type::Text, Box::new(e)))?,
+        created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(11)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(11, rusqlite::types::Type::Text, Box::new(e)))?
+            .with_timezone(&Utc),
+        updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(12)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(12, rusqlite::types::Type::Text, Box::new(e)))?
+            .with_timezone(&Utc),
+        industry: row.get(13)?,
+        week_ending_day: match row.get::<_, Option<String>>(14)? {
+            Some(s) => Some(s.parse::<Weekday>()
+                .map_err(|e| rusqlite::Error::FromSqlConversionFailure(14, rusqlite::types::Type::Text, Box::new(e)))?),
+            None => None,
+        },
+        notice_period_weeks: row.get(15)?,
+        leave_loading_percent: row.get(16)?,
+        probation_months: row.get(17)?,
+        commute_minutes_per_day: row.get(18)?,
+        archived: row.get(19)?,
+    })
+}
+
+fn row_to_analysis_snapshot(row: &rusqlite::Row) -> SqlResult<AnalysisSnapshot> {
+    Ok(AnalysisSnapshot {
+        id: Some(row.get(0)?),
+        taken_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(1)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Text, Box::new(e)))?
+            .with_timezone(&Utc),
+        total_compensation: row.get(2)?,
+        income_percentile: row.get(3)?,
+        loyalty_tax_annual: row.get(4)?,
+        loyalty_tax_cumulative: row.get(5)?,
+        projected_super: row.get(6)?,
+    })
+}
+
+fn row_to_award_rate(row: &rusqlite::Row) -> SqlResult<AwardRate> {
+    Ok(AwardRate {
+        id: Some(row.get(0)?),
+        award_name: row.get(1)?,
+        classification: row.get(2)?,
+        effective_date: NaiveDate::parse_from_str(&row.get::<_, String>(3)?, "%Y-%m-%d")
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e)))?,
+        hourly_minimum: row.get(4)?,
+        overtime_multiplier: row.get(5)?,
+        casual_loading: row.get(6)?,
+    })
+}
+
+fn row_to_insurance_policy(row: &rusqlite::Row) -> SqlResult<InsurancePolicy> {
+    Ok(InsurancePolicy {
+        id: Some(row.get(0)?),
+        policy_type: serde_json::from_str(&row.get::<_, String>(1)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Text, Box::new(e)))?,
+        provider: row.get(2)?,
+        annual_premium: row.get(3)?,
+        through_super: row.get(4)?,
+        start_date: NaiveDate::parse_from_str(&row.get::<_, String>(5)?, "%Y-%m-%d")
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(5, rusqlite::types::Type::Text, Box::new(e)))?,
+        end_date: row.get::<_, Option<String>>(6)?
+            .map(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d"))
+            .transpose()
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(6, rusqlite::types::Type::Text, Box::new(e)))?,
+    })
+}
+
+fn row_to_partner_income(row: &rusqlite::Row) -> SqlResult<PartnerIncomeEntry> {
+    Ok(PartnerIncomeEntry {
+        id: Some(row.get(0)?),
+        financial_year: row.get(1)?,
+        gross_income: row.get(2)?,
+        is_estimate: row.get(3)?,
+    })
+}
+
+fn row_to_entity_tag(row: &rusqlite::Row) -> SqlResult<EntityTag> {
+    Ok(EntityTag {
+        tag_id: row.get(0)?,
+        entity_type: serde_json::from_str(&row.get::<_, String>(1)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Text, Box::new(e)))?,
+        entity_id: row.get(2)?,
+    })
+}
+
+fn row_to_record_change(row: &rusqlite::Row) -> SqlResult<RecordChange> {
+    Ok(RecordChange {
+        id: Some(row.get(0)?),
+        record_id: row.get(1)?,
+        changed_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(2)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Text, Box::new(e)))?
+            .with_timezone(&Utc),
+        change: row.get(3)?,
+        before: serde_json::from_str(&row.get::<_, String>(4)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(4, rusqlite::types::Type::Text, Box::new(e)))?,
+    })
+}
+
+fn row_to_scenario(row: &rusqlite::Row) -> SqlResult<Scenario> {
+    Ok(Scenario {
+        id: Some(row.get(0)?),
+        name: row.get(1)?,
+        position_id: row.get(2)?,
+        baseline: serde_json::from_str(&row.get::<_, String>(3)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e)))?,
+        offer: serde_json::from_str(&row.get::<_, String>(4)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(4, rusqlite::types::Type::Text, Box::new(e)))?,
+        result: serde_json::from_str(&row.get::<_, String>(5)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(5, rusqlite::types::Type::Text, Box::new(e)))?,
+        created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(6)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(6, rusqlite::types::Type::Text, Box::new(e)))?
+            .with_timezone(&Utc),
+    })
+}
+
+fn row_to_template(row: &rusqlite::Row) -> SqlResult<PositionTemplate> {
+    Ok(PositionTemplate {
+        id: Some(row.get(0)?),
+        name: row.get(1)?,
+        fields: serde_json::from_str(&row.get::<_, String>(2)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Text, Box::new(e)))?,
+        created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(3)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e)))?
+            .with_timezone(&Utc),
+    })
+}
+
+const COMPENSATION_RECORD_COLUMNS: &str =
+    "id, position_id, entry_type, pay_type, base_rate,
+     standard_weekly_hours, overtime_frequency, overtime_rate_multiplier,
+     overtime_average_hours_per_week, overtime_annual_hours, allowances,
+     bonuses, super_contribution_rate, super_additional_contributions,
+     super_salary_sacrifice, payslip_frequency, tax_withheld, effective_date,
+     confidence_score, notes, created_at, currency, jurisdiction, claims_tax_free_threshold, equity, packaging,
+     award_classification_id, non_cash_benefits";
+
+fn compensation_record_from_row(row: &rusqlite::Row) -> SqlResult<CompensationRecord> {
+    let allowances_json: String = row.get(10)?;
+    let bonuses_json: String = row.get(11)?;
+
+    Ok(CompensationRecord {
+        id: Some(row.get(0)?),
+        position_id: row.get(1)?,
+        entry_type: serde_json::from_str(&row.get::<_, String>(2)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Text, Box::new(e)))?,
+        pay_type: serde_json::from_str(&row.get::<_, String>(3)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e)))?,
+        base_rate: row.get(4)?,
+        standard_weekly_hours: row.get(5)?,
+        overtime: OvertimeDetails {
+            frequency: serde_json::from_str(&row.get::<_, String>(6)?)
+                .map_err(|e| rusqlite::Error::FromSqlConversionFailure(6, rusqlite::types::Type::Text, Box::new(e)))?,
+            rate_multiplier: row.get(7)?,
+            average_hours_per_week: row.get(8)?,
+            annual_hours: row.get(9)?,
+        },
+        allowances: serde_json::from_str(&allowances_json)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(10, rusqlite::types::Type::Text, Box::new(e)))?,
+        bonuses: serde_json::from_str(&bonuses_json)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(11, rusqlite::types::Type::Text, Box::new(e)))?,
+        super_contributions: SuperDetails {
+            contribution_rate: row.get(12)?,
+            additional_contributions: row.get(13)?,
+            salary_sacrifice: row.get(14)?,
+        },
+        payslip_frequency: {
+            match row.get::<_, Option<String>>(15)? {
+                Some(s) => Some(serde_json::from_str(&s)
+                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(15, rusqlite::types::Type::Text, Box::new(e)))?),
+                None => None,
+            }
+        },
+        tax_withheld: row.get(16)?,
+        effective_date: NaiveDate::parse_from_str(&row.get::<_, String>(17)?, "%Y-%m-%d")
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(17, rusqlite::types::Type::Text, Box::new(e)))?,
+        confidence_score: row.get(18)?,
+        notes: row.get(19)?,
+        currency: row.get(21)?,
+        jurisdiction: serde_json::from_str(&row.get::<_, String>(22)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(22, rusqlite::types::Type::Text, Box::new(e)))?,
+        claims_tax_free_threshold: row.get(23)?,
+        equity: match row.get::<_, Option<String>>(24)? {
+            Some(s) => Some(serde_json::from_str(&s)
+                .map_err(|e| rusqlite::Error::FromSqlConversionFailure(24, rusqlite::types::Type::Text, Box::new(e)))?),
+            None => None,
+        },
+        packaging: serde_json::from_str(&row.get::<_, String>(25)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(25, rusqlite::types::Type::Text, Box::new(e)))?,
+        award_classification_id: row.get(26)?,
+        non_cash_benefits: serde_json::from_str(&row.get::<_, String>(27)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(27, rusqlite::types::Type::Text, Box::new(e)))?,
+        created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(20)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(20, rusqlite::types::Type::Text, Box::new(e)))?
+            .with_timezone(&Utc),
+    })
+}
+
+fn weekly_entry_from_row(row: &rusqlite::Row) -> SqlResult<WeeklyCompensationEntry> {
+    let allowances_json: String = row.get(10)?;
+    let week_ending = NaiveDate::parse_from_str(&row.get::<_, String>(3)?, "%Y-%m-%d")
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e)))?;
+    let period_type: PayslipFrequency = serde_json::from_str(&row.get::<_, String>(15)?)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(15, rusqlite::types::Type::Text, Box::new(e)))?;
+    let period_start = match row.get::<_, Option<String>>(14)? {
+        Some(s) => NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(14, rusqlite::types::Type::Text, Box::new(e)))?,
+        None => dates::period_start_for(week_ending, &period_type),
+    };
+
+    Ok(WeeklyCompensationEntry {
+        id: Some(row.get(0)?),
+        position_id: row.get(1)?,
+        financial_year: row.get(2)?,
+        week_ending,
+        period_start: Some(period_start),
+        period_type,
+        gross_pay: row.get(4)?,
+        tax_withheld: row.get(5)?,
+        net_pay: row.get(6)?,
+        hours_ordinary: row.get(7)?,
+        hours_overtime: row.get(8)?,
+        overtime_rate_multiplier: row.get(9)?,
+        allowances: serde_json::from_str(&allowances_json)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(10, rusqlite::types::Type::Text, Box::new(e)))?,
+        super_contributed: row.get(11)?,
+        currency: row.get(16)?,
+        notes: row.get(12)?,
+        created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(13)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(13, rusqlite::types::Type::Text, Box::new(e)))?
+            .with_timezone(&Utc),
+    })
+}
+
+fn yearly_entry_from_row(row: &rusqlite::Row) -> SqlResult<YearlyIncomeEntry> {
+    Ok(YearlyIncomeEntry {
+        id: Some(row.get(0)?),
+        position_id: row.get(1)?,
+        financial_year: row.get(2)?,
+        gross_income: row.get(3)?,
+        tax_withheld: row.get(4)?,
+        reportable_super: row.get(5)?,
+        reportable_fringe_benefits: row.get(6)?,
+        allowances: serde_json::from_str(&row.get::<_, String>(7)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(7, rusqlite::types::Type::Text, Box::new(e)))?,
+        source: serde_json::from_str(&row.get::<_, String>(8)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(8, rusqlite::types::Type::Text, Box::new(e)))?,
+        currency: row.get(11)?,
+        jurisdiction: serde_json::from_str(&row.get::<_, String>(12)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(12, rusqlite::types::Type::Text, Box::new(e)))?,
+        notes: row.get(9)?,
+        created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(10)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(10, rusqlite::types::Type::Text, Box::new(e)))?
+            .with_timezone(&Utc),
+    })
+}
+
+// A row that fails to convert is set aside with its id instead of failing the whole list
+fn collect_list<T>(
+    table_name: &str,
+    rows: impl Iterator<Item = SqlResult<(i64, SqlResult<T>)>>,
+) -> Result<ListResult<T>, String> {
+    let mut list = ListResult { rows: Vec::new(), errors: Vec::new() };
+    for row_result in rows {
+        match row_result.map_err(|e| e.to_string())? {
+            (_, Ok(row)) => list.rows.push(row),
+            (row_id, Err(e)) => list.errors.push(RowError {
+                table_name: table_name.to_string(),
+                row_id,
+                message: e.to_string(),
+            }),
+        }
+    }
+    Ok(list)
+}
+
+// Every row of a list, or an error naming the ones that couldn't be read, for the callers that
+// would otherwise work from part of the data without knowing it
+fn every_row<T>(list: ListResult<T>) -> Result<Vec<T>, String> {
+    match list.errors.as_slice() {
+        [] => Ok(list.rows),
+        errors => Err(format!(
+            "Some rows can't be read, repair them first: {}",
+            errors.iter()
+                .map(|e| format!("{} row {} ({})", e.table_name, e.row_id, e.message))
+                .collect::<Vec<_>>()
+                .join("; ")
+        )),
+    }
+}
+
+fn backfill_message(column: &str, default_value: &str) -> String {
+    match column {
+        "standard_weekly_hours" => format!("Standard weekly hours were assumed to be {} when this field was added. Confirm or correct your usual hours.", default_value.trim_end_matches(".0")),
+        "period_type" => "This entry was assumed to cover a weekly pay period. Confirm or correct the pay frequency.".to_string(),
+        "currency" => format!("Amounts were assumed to be in {}. Confirm or correct the currency.", default_value),
+        "has_help_debt" => "You were assumed to have no HELP debt when this field was added. Confirm or tell us if you're repaying one.".to_string(),
+        "jurisdiction" => "This income was assumed to be Australian. Confirm or mark it as New Zealand income.".to_string(),
+        "claims_tax_free_threshold" => "This job was assumed to claim the tax-free threshold. If it's a second job, tell us it doesn't.".to_string(),
+        _ => format!("{} was set to {} by a data migration. Confirm or correct it.", column, default_value),
+    }
+}
+
+fn migrate_initial_schema(conn: &Connection) -> SqlResult<()> {
+    // Columns already there before reviews were tracked still hold defaults nobody entered
+    let tracked_before: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'backfill_reviews')",
+        [],
+        |row| row.get(0),
+    )?;
+
+    // User Profile table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS user_profile (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            first_name TEXT NOT NULL,
+            last_name TEXT NOT NULL,
+            date_of_birth TEXT NOT NULL,
+            state TEXT NOT NULL,
+            industry TEXT NOT NULL,
+            highest_qualification TEXT NOT NULL,
+            employment_type_preference TEXT NOT NULL,
+            fifo_tolerance TEXT NOT NULL,
+            travel_tolerance TEXT NOT NULL,
+            overtime_appetite TEXT NOT NULL,
+            privacy_acknowledged BOOLEAN NOT NULL DEFAULT FALSE,
+            disclaimer_acknowledged BOOLEAN NOT NULL DEFAULT FALSE,
+            standard_weekly_hours REAL NOT NULL DEFAULT 38.0,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    // Positions table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS positions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            employer_name TEXT NOT NULL,
+            job_title TEXT NOT NULL,
+            employment_type TEXT NOT NULL,
+            location TEXT NOT NULL,
+            start_date TEXT NOT NULL,
+            end_date TEXT,
+            seniority_level TEXT NOT NULL,
+            core_responsibilities TEXT NOT NULL,
+            tools_systems_skills TEXT NOT NULL, -- JSON array
+            achievements TEXT NOT NULL, -- JSON array
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    // Compensation Records table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS compensation_records (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            position_id INTEGER NOT NULL,
+            entry_type TEXT NOT NULL,
+            pay_type TEXT NOT NULL,
+            base_rate REAL NOT NULL,
+            standard_weekly_hours REAL NOT NULL,
+            overtime_frequency TEXT NOT NULL,
+            overtime_rate_multiplier REAL NOT NULL,
+            overtime_average_hours_per_week REAL NOT NULL,
+            overtime_annual_hours REAL,
+            allowances TEXT NOT NULL, -- JSON array
+            bonuses TEXT NOT NULL, -- JSON array
+            super_contribution_rate REAL NOT NULL,
+            super_additional_contributions REAL NOT NULL,
+            super_salary_sacrifice REAL NOT NULL,
+            payslip_frequency TEXT,
+            tax_withheld REAL,
+            effective_date TEXT NOT NULL,
+            confidence_score REAL NOT NULL,
+            currency TEXT NOT NULL DEFAULT 'AUD',
+            jurisdiction TEXT NOT NULL DEFAULT '\"AU\"',
+            notes TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (position_id) REFERENCES positions(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // One-off Weekly Entries table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS weekly_entries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            position_id INTEGER, -- Optional link to a position
+            financial_year TEXT NOT NULL,
+            week_ending TEXT NOT NULL, -- End of the pay period
+            period_start TEXT,
+            period_type TEXT NOT NULL DEFAULT '\"Weekly\"',
+            gross_pay REAL NOT NULL,
+            tax_withheld REAL NOT NULL,
+            net_pay REAL NOT NULL,
+            hours_ordinary REAL NOT NULL,
+            hours_overtime REAL NOT NULL,
+            overtime_rate_multiplier REAL NOT NULL,
+            allowances TEXT NOT NULL, -- JSON array
+            super_contributed REAL NOT NULL,
+            currency TEXT NOT NULL DEFAULT 'AUD',
+            notes TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (position_id) REFERENCES positions(id) ON DELETE SET NULL
+        )",
+        [],
+    )?;
+
+    // Yearly Income Entries table (ATO summaries)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS yearly_income_entries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            position_id INTEGER,
+            financial_year TEXT NOT NULL,
+            gross_income REAL NOT NULL,
+            tax_withheld REAL NOT NULL,
+            reportable_super REAL NOT NULL,
+            reportable_fringe_benefits REAL,
+            allowances TEXT NOT NULL DEFAULT '[]', -- JSON array
+            source TEXT NOT NULL,
+            currency TEXT NOT NULL DEFAULT 'AUD',
+            jurisdiction TEXT NOT NULL DEFAULT '\"AU\"',
+            notes TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (position_id) REFERENCES positions(id) ON DELETE SET NULL
+        )",
+        [],
+    )?;
+
+    // User-maintained exchange rates for converting overseas income to AUD
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS exchange_rates (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            currency TEXT NOT NULL,
+            financial_year TEXT NOT NULL,
+            rate_to_aud REAL NOT NULL,
+            UNIQUE (currency, financial_year)
+        )",
+        [],
+    )?;
+
+    // App preferences as JSON values, kept out of exports and clear_all_data
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    // Rows whose values were filled in by a column migration rather than entered by the user
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS backfill_reviews (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            table_name TEXT NOT NULL,
+            row_id INTEGER NOT NULL,
+            column_name TEXT NOT NULL,
+            default_value TEXT NOT NULL,
+            needs_review BOOLEAN NOT NULL DEFAULT TRUE,
+            UNIQUE (table_name, row_id, column_name)
+        )",
+        [],
+    )?;
+
+    // Migration: Add allowances column if it doesn't exist (for existing databases)
+    // We attempt to add it and ignore the error if it already exists (duplicate column name)
+    let _ = conn.execute(
+        "ALTER TABLE yearly_income_entries ADD COLUMN allowances TEXT NOT NULL DEFAULT '[]'",
+        [],
+    );
+
+    // Create indexes for performance
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_positions_dates ON positions(start_date, end_date)",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_compensation_position_date ON compensation_records(position_id, effective_date)",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_weekly_date ON weekly_entries(week_ending)",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_yearly_fy ON yearly_income_entries(financial_year)",
+        [],
+    )?;
+
+    // Migration: Add standard_weekly_hours column if it doesn't exist (for existing databases)
+    add_backfilled_column(conn, "user_profile", "standard_weekly_hours", "REAL NOT NULL DEFAULT 38.0")?;
+    if !tracked_before {
+        flag_rows_at_default(conn, "user_profile", "standard_weekly_hours", "38.0")?;
+    }
+
+    // Migration: Generalise weekly entries into pay-period entries. Existing rows are
+    // weekly, so their period starts six days before the recorded week ending.
+    add_backfilled_column(conn, "weekly_entries", "period_type", "TEXT NOT NULL DEFAULT '\"Weekly\"'")?;
+    let _ = conn.execute(
+        "ALTER TABLE weekly_entries ADD COLUMN period_start TEXT",
+        [],
+    );
+    conn.execute(
+        "UPDATE weekly_entries SET period_start = date(week_ending, '-6 days') WHERE period_start IS NULL",
+        [],
+    )?;
+
+    // Migration: Currency on income records, existing rows were entered in AUD
+    for table in ["compensation_records", "weekly_entries", "yearly_income_entries"] {
+        add_backfilled_column(conn, table, "currency", "TEXT NOT NULL DEFAULT 'AUD'")?;
+    }
+
+    // Migration: Tax jurisdiction, everything before NZ support was Australian
+    for table in ["compensation_records", "yearly_income_entries"] {
+        add_backfilled_column(conn, table, "jurisdiction", "TEXT NOT NULL DEFAULT '\"AU\"'")?;
+    }
+
+    Ok(())
+}
+
+/// Adds a column with a default and flags every existing row for review, since their
+/// value is an assumption rather than something the user entered. Does nothing when the
+/// column already exists.
+fn add_backfilled_column(conn: &Connection, table: &str, column: &str, definition: &str) -> SqlResult<()> {
+    let added = conn.execute(
+        &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+        [],
+    );
+    if added.is_err() {
+        return Ok(());
+    }
+
+    conn.execute(
+        &format!(
+            "INSERT OR IGNORE INTO backfill_reviews (table_name, row_id, column_name, default_value)
+             SELECT ?1, id, ?2, CAST({} AS TEXT) FROM {}",
+            column, table
+        ),
+        params![table, column],
+    )?;
+    Ok(())
+}
+
+/// Flags the rows still holding a column's default for review, for a column that was added
+/// before added columns were flagged.
+fn flag_rows_at_default(conn: &Connection, table: &str, column: &str, default_value: &str) -> SqlResult<()> {
+    conn.execute(
+        &format!(
+            "INSERT OR IGNORE INTO backfill_reviews (table_name, row_id, column_name, default_value)
+             SELECT ?1, id, ?2, ?3 FROM {} WHERE {} = {}",
+            table, column, default_value
+        ),
+        params![table, column, default_value],
+    )?;
+    Ok(())
+}
+
+/// A schema change applied once, in version order. `PRAGMA user_version` records the last
+/// one applied.
+#[derive(Clone, Copy)]
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub apply: fn(&Connection) -> SqlResult<()>,
+}
+
+/// Every migration, oldest first. Version 1 is the schema as it stood before migrations were
+/// versioned; it only adds what's missing, so it's safe on a database from any earlier release.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration { version: 1, name: "initial schema", apply: migrate_initial_schema },
+    Migration { version: 2, name: "write tracking", apply: migrate_write_tracking },
+    Migration { version: 3, name: "help debt", apply: migrate_help_debt },
+    Migration { version: 4, name: "position industry", apply: migrate_position_industry },
+    Migration { version: 5, name: "tax-free threshold", apply: migrate_tax_free_threshold },
+    Migration { version: 6, name: "position week ending day", apply: migrate_week_ending_day },
+    Migration { version: 7, name: "analysis snapshots", apply: migrate_analysis_snapshots },
+    Migration { version: 8, name: "profile super balance", apply: migrate_profile_super_balance },
+    Migration { version: 9, name: "compensation equity", apply: migrate_compensation_equity },
+    Migration { version: 10, name: "compensation packaging", apply: migrate_compensation_packaging },
+    Migration { version: 11, name: "award rates", apply: migrate_award_rates },
+    Migration { version: 12, name: "position notice and leave loading", apply: migrate_notice_and_leave_loading },
+    Migration { version: 13, name: "compensation non-cash benefits", apply: migrate_non_cash_benefits },
+    Migration { version: 14, name: "probation and reminder dismissals", apply: migrate_reminders },
+    Migration { version: 15, name: "saved scenarios", apply: migrate_scenarios },
+    Migration { version: 16, name: "profile gender", apply: migrate_profile_gender },
+    Migration { version: 17, name: "record change log", apply: migrate_record_changes },
+    Migration { version: 18, name: "position templates", apply: migrate_position_templates },
+    Migration { version: 19, name: "insurance policies", apply: migrate_insurance_policies },
+    Migration { version: 20, name: "partner income", apply: migrate_partner_income },
+    Migration { version: 21, name: "tags", apply: migrate_tags },
+    Migration { version: 22, name: "commute", apply: migrate_commute },
+    Migration { version: 23, name: "archived positions", apply: migrate_archived_positions },
+    Migration { version: 24, name: "weekly position index", apply: migrate_weekly_position_index },
+    Migration { version: 25, name: "write tracking for later tables", apply: migrate_later_write_tracking },
+];
+
+/// Tables whose writes are counted in `table_writes`, to notice a sync tool putting back an
+/// older copy of the database.
+pub const TRACKED_TABLES: &[&str] = &[
+    "user_profile",
+    "positions",
+    "compensation_records",
+    "weekly_entries",
+    "yearly_income_entries",
+    "exchange_rates",
+    "settings",
+    "backfill_reviews",
+    "award_rates",
+    "scenarios",
+    "position_templates",
+    "insurance_policies",
+    "partner_income_entries",
+    "tags",
+    "entity_tags",
+];
+
+// Triggers count every row written, so no save path can miss an update
+fn migrate_write_tracking(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS table_writes (
+            table_name TEXT PRIMARY KEY,
+            write_count INTEGER NOT NULL,
+            last_modified TEXT NOT NULL
+        )",
+        [],
+    )?;
+    create_write_triggers(conn)
+}
+
+// Counts writes to the tables added since version 2, which are exported user data too
+fn migrate_later_write_tracking(conn: &Connection) -> SqlResult<()> {
+    create_write_triggers(conn)
+}
+
+// Triggers for every tracked table the schema has so far; later migrations catch up the rest
+fn create_write_triggers(conn: &Connection) -> SqlResult<()> {
+    for table in TRACKED_TABLES {
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
+            [table],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            continue;
+        }
+        for event in ["INSERT", "UPDATE", "DELETE"] {
+            conn.execute_batch(&format!(
+                "CREATE TRIGGER IF NOT EXISTS track_{table}_{name} AFTER {event} ON {table}
+                 BEGIN
+                     INSERT INTO table_writes (table_name, write_count, last_modified)
+                     VALUES ('{table}', 1, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+                     ON CONFLICT (table_name) DO UPDATE SET
+                         write_count = write_count + 1,
+                         last_modified = excluded.last_modified;
+                 END;",
+                table = table,
+                name = event.to_lowercase(),
+                event = event,
+            ))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn migrate_help_debt(conn: &Connection) -> SqlResult<()> {
+    add_backfilled_column(conn, "user_profile", "has_help_debt", "BOOLEAN NOT NULL DEFAULT FALSE")
+}
+
+// Nullable, and NULL means the profile's industry, so existing rows need no review
+fn migrate_position_industry(conn: &Connection) -> SqlResult<()> {
+    conn.execute("ALTER TABLE positions ADD COLUMN industry TEXT", [])?;
+    Ok(())
+}
+
+fn migrate_tax_free_threshold(conn: &Connection) -> SqlResult<()> {
+    add_backfilled_column(conn, "compensation_records", "claims_tax_free_threshold", "BOOLEAN NOT NULL DEFAULT TRUE")
+}
+
+// Nullable, and NULL means the day is worked out from the entries
+fn migrate_week_ending_day(conn: &Connection) -> SqlResult<()> {
+    conn.execute("ALTER TABLE positions ADD COLUMN week_ending_day TEXT", [])?;
+    Ok(())
+}
+
+// Figures worked out from the rest of the data, so kept out of exports
+fn migrate_analysis_snapshots(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS analysis_snapshots (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            taken_at TEXT NOT NULL,
+            total_compensation REAL NOT NULL,
+            income_percentile REAL NOT NULL,
+            loyalty_tax_annual REAL NOT NULL,
+            loyalty_tax_cumulative REAL NOT NULL,
+            projected_super REAL NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+// Nullable, and NULL means the balance isn't known, so existing rows need no review
+fn migrate_profile_super_balance(conn: &Connection) -> SqlResult<()> {
+    conn.execute("ALTER TABLE user_profile ADD COLUMN total_super_balance REAL", [])?;
+    Ok(())
+}
+
+// JSON, and NULL means no equity, so existing rows need no review
+fn migrate_compensation_equity(conn: &Connection) -> SqlResult<()> {
+    conn.execute("ALTER TABLE compensation_records ADD COLUMN equity TEXT", [])?;
+    Ok(())
+}
+
+// JSON list, and an empty one means nothing is packaged, so existing rows need no review
+fn migrate_compensation_packaging(conn: &Connection) -> SqlResult<()> {
+    conn.execute("ALTER TABLE compensation_records ADD COLUMN packaging TEXT NOT NULL DEFAULT '[]'", [])?;
+    Ok(())
+}
+
+// Records start unlinked, so existing rows need no review
+fn migrate_award_rates(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS award_rates (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            award_name TEXT NOT NULL,
+            classification TEXT NOT NULL,
+            effective_date TEXT NOT NULL,
+            hourly_minimum REAL NOT NULL,
+            overtime_multiplier REAL NOT NULL,
+            casual_loading REAL NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute("ALTER TABLE compensation_records ADD COLUMN award_classification_id INTEGER", [])?;
+    Ok(())
+}
+
+fn migrate_notice_and_leave_loading(conn: &Connection) -> SqlResult<()> {
+    conn.execute("ALTER TABLE positions ADD COLUMN notice_period_weeks REAL", [])?;
+    conn.execute("ALTER TABLE positions ADD COLUMN leave_loading_percent REAL", [])?;
+    Ok(())
+}
+
+fn migrate_non_cash_benefits(conn: &Connection) -> SqlResult<()> {
+    conn.execute("ALTER TABLE compensation_records ADD COLUMN non_cash_benefits TEXT NOT NULL DEFAULT '[]'", [])?;
+    Ok(())
+}
+
+fn migrate_reminders(conn: &Connection) -> SqlResult<()> {
+    conn.execute("ALTER TABLE positions ADD COLUMN probation_months INTEGER", [])?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS dismissed_reminders (
+            reminder_id TEXT PRIMARY KEY,
+            dismissed_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+// No foreign key on position_id: a scenario outlives its position and reruns from its snapshot
+fn migrate_scenarios(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS scenarios (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            position_id INTEGER,
+            baseline TEXT NOT NULL,
+            offer TEXT NOT NULL,
+            result TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn migrate_profile_gender(conn: &Connection) -> SqlResult<()> {
+    conn.execute("ALTER TABLE user_profile ADD COLUMN gender TEXT NOT NULL DEFAULT '\"PreferNotToSay\"'", [])?;
+    Ok(())
+}
+
+fn migrate_record_changes(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS record_changes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            record_id INTEGER NOT NULL,
+            changed_at TEXT NOT NULL,
+            change TEXT NOT NULL,
+            before TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn migrate_position_templates(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS position_templates (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            fields TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn migrate_insurance_policies(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS insurance_policies (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            policy_type TEXT NOT NULL,
+            provider TEXT NOT NULL,
+            annual_premium REAL NOT NULL,
+            through_super INTEGER NOT NULL,
+            start_date TEXT NOT NULL,
+            end_date TEXT
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn migrate_partner_income(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS partner_income_entries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            financial_year TEXT NOT NULL,
+            gross_income REAL NOT NULL,
+            is_estimate INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+// Tags can go on rows of several tables, so entity_tags can't have a foreign key to them;
+// triggers drop a row's tags when it's deleted instead
+fn migrate_tags(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS tags (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE COLLATE NOCASE
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS entity_tags (
+            tag_id INTEGER NOT NULL,
+            entity_type TEXT NOT NULL,
+            entity_id INTEGER NOT NULL,
+            PRIMARY KEY (tag_id, entity_type, entity_id),
+            FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE ON UPDATE CASCADE
+        )",
+        [],
+    )?;
+    for (table, entity_type) in TAGGED_TABLES {
+        let entity_type = to_json(entity_type)?;
+        conn.execute(
+            &format!(
+                "CREATE TRIGGER IF NOT EXISTS untag_{table} AFTER DELETE ON {table}
+                 BEGIN
+                     DELETE FROM entity_tags WHERE entity_type = '{entity_type}' AND entity_id = OLD.id;
+                 END"
+            ),
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+fn migrate_commute(conn: &Connection) -> SqlResult<()> {
+    conn.execute("ALTER TABLE positions ADD COLUMN commute_minutes_per_day INTEGER", [])?;
+    Ok(())
+}
+
+fn migrate_archived_positions(conn: &Connection) -> SqlResult<()> {
+    conn.execute("ALTER TABLE positions ADD COLUMN archived BOOLEAN NOT NULL DEFAULT FALSE", [])?;
+    Ok(())
+}
+
+// Backs the per-position statistics, which read one position's entries in date order
+fn migrate_weekly_position_index(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_weekly_position_date ON weekly_entries(position_id, week_ending)",
+        [],
+    )?;
+    Ok(())
+}
+
+// The table each kind of tagged record is kept in
+const TAGGED_TABLES: &[(&str, TagEntityType)] = &[
+    ("positions", TagEntityType::Position),
+    ("compensation_records", TagEntityType::CompensationRecord),
+    ("weekly_entries", TagEntityType::WeeklyEntry),
+    ("yearly_income_entries", TagEntityType::YearlyEntry),
+];
+
+/// Where the copy taken before migrating is kept: `careerflow.db.pre-migrate` next to the database.
+pub fn pre_migrate_path(db_path: &Path) -> PathBuf {
+    let mut path = db_path.as_os_str().to_owned();
+    path.push(".pre-migrate");
+    PathBuf::from(path)
+}
+
+/// Where the write counts at the last clean shutdown are kept: `careerflow.db.state.json`.
+pub fn shutdown_state_path(db_path: &Path) -> PathBuf {
+    let mut path = db_path.as_os_str().to_owned();
+    path.push(".state.json");
+    PathBuf::from(path)
+}
+
+fn io_error(e: std::io::Error) -> rusqlite::Error {
+    rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_IOERR), Some(e.to_string()))
+}
+
+fn open_connection(db_path: &Path) -> SqlResult<Connection> {
+    let conn = Connection::open(db_path)?;
+
+    // Enable foreign key constraints
+    conn.pragma_update(None, "foreign_keys", "ON")?;
+
+    // Set WAL mode for better performance
+    conn.pragma_update(None, "journal_mode", "WAL")?;
+
+    Ok(conn)
+}
+
+fn schema_version(conn: &Connection) -> SqlResult<u32> {
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+}
+
+// Puts the pre-migrate copy back, dropping the WAL files of the half-migrated database
+fn restore_pre_migrate_copy(copy: &Path, db_path: &Path) -> SqlResult<()> {
+    std::fs::copy(copy, db_path).map_err(io_error)?;
+    for suffix in ["-wal", "-shm"] {
+        let mut sidecar = db_path.as_os_str().to_owned();
+        sidecar.push(suffix);
+        match std::fs::remove_file(PathBuf::from(sidecar)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(io_error(e)),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Connection to the CareerFlow SQLite database.
+pub struct Database {
+    conn: Connection,
+    status: DatabaseStatus,
+    // Kept until the migrated database has taken a write, then deleted
+    pre_migrate_copy: RefCell<Option<PathBuf>>,
+    read_only: bool,
+    path: PathBuf,
+}
+
+impl Database {
+    /// Opens (or creates) the database at the path and brings its schema up to date.
+    ///
+    /// A failed migration doesn't stop the database opening: the copy taken beforehand is
+    /// restored and [`Database::status`] reports the failure.
+    pub fn new(db_path: PathBuf) -> SqlResult<Self> {
+        Self::open_with_migrations(db_path, MIGRATIONS)
+    }
+
+    /// [`Database::new`] with a given list of migrations, for exercising the migration path.
+    pub fn open_with_migrations(db_path: PathBuf, migrations: &[Migration]) -> SqlResult<Self> {
+        let conn = open_connection(&db_path)?;
+        let current_version = schema_version(&conn)?;
+
+        let pending: Vec<&Migration> = migrations.iter().filter(|m| m.version > current_version).collect();
+        if pending.is_empty() {
+            return Ok(Self::ready(conn, db_path, current_version, None));
+        }
+
+        // A new, empty database has nothing worth copying
+        let has_tables: bool = conn.query_row("SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table')", [], |row| row.get(0))?;
+        let copy = if has_tables {
+            let copy = pre_migrate_path(&db_path);
+            if copy.exists() {
+                std::fs::remove_file(&copy).map_err(io_error)?;
+            }
+            conn.execute("VACUUM INTO ?1", [copy.to_string_lossy()])?;
+            Some(copy)
+        } else {
+            None
+        };
+
+        let mut version = current_version;
+        for migration in pending {
+            let applied = (migration.apply)(&conn)
+                .and_then(|_| conn.pragma_update(None, "user_version", migration.version));
+            if let Err(e) = applied {
+                let Some(copy) = &copy else {
+                    return Err(e);
+                };
+
+                drop(conn);
+                restore_pre_migrate_copy(copy, &db_path)?;
+                let conn = open_connection(&db_path)?;
+                let schema_version = schema_version(&conn)?;
+                let mut db = Self::ready(conn, db_path, schema_version, None);
+                db.status.state = DatabaseState::MigrationFailed;
+                db.status.error = Some(format!("Migration {} ({}) failed: {}", migration.version, migration.name, e));
+                return Ok(db);
+            }
+            version = migration.version;
+        }
+
+        Ok(Self::ready(conn, db_path, version, copy))
+    }
+
+    /// Opens an existing database without migrating it or ever writing to it, for when another
+    /// instance of the app holds the database. Every save fails with [`AppError::ReadOnly`].
+    pub fn open_read_only(db_path: PathBuf) -> SqlResult<Self> {
+        let conn = Connection::open_with_flags(
+            &db_path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX | OpenFlags::SQLITE_OPEN_URI,
+        )?;
+        let schema_version = schema_version(&conn)?;
+        let mut db = Self::ready(conn, db_path, schema_version, None);
+        db.read_only = true;
+        Ok(db)
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    fn ready(conn: Connection, path: PathBuf, schema_version: u32, pre_migrate_copy: Option<PathBuf>) -> Self {
+        let mut db = Self {
+            conn,
+            status: DatabaseStatus {
+                state: DatabaseState::Ready,
+                schema_version,
+                error: None,
+                possible_data_rollback: None,
+            },
+            pre_migrate_copy: RefCell::new(pre_migrate_copy),
+            read_only: false,
+            path,
+        };
+        db.status.possible_data_rollback = db.detect_rollback();
+        db
+    }
+
+    /// Whether the schema is current, or the last migration failed and was rolled back, and
+    /// whether the database looks older than it was at the last clean shutdown.
+    pub fn status(&self) -> DatabaseStatus {
+        self.status.clone()
+    }
+
+    /// Writes so far and when each tracked table was last written.
+    pub fn get_last_modified_summary(&self) -> Result<Vec<TableModified>, String> {
+        let mut writes: HashMap<String, (i64, String)> = HashMap::new();
+        let tracked: bool = self.conn
+            .query_row("SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'table_writes')", [], |row| row.get(0))
+            .map_err(|e| e.to_string())?;
+        if tracked {
+            let mut stmt = self.conn
+                .prepare("SELECT table_name, write_count, last_modified FROM table_writes")
+                .map_err(|e| e.to_string())?;
+            let rows = stmt
+                .query_map([], |row| Ok((row.get::<_, String>(0)?, (row.get(1)?, row.get(2)?))))
+                .map_err(|e| e.to_string())?;
+            for row in rows {
+                let (table, counts) = row.map_err(|e| e.to_string())?;
+                writes.insert(table, counts);
+            }
+        }
+
+        TRACKED_TABLES.iter()
+            .map(|table| {
+                let (write_count, last_modified) = match writes.remove(*table) {
+                    Some((count, modified)) => {
+                        let modified = DateTime::parse_from_rfc3339(&modified)
+                            .map_err(|e| format!("Invalid last modified time for {}: {}", table, e))?
+                            .with_timezone(&Utc);
+                        (count, Some(modified))
+                    }
+                    None => (0, None),
+                };
+                Ok(TableModified { table_name: table.to_string(), write_count, last_modified })
+            })
+            .collect()
+    }
+
+    /// Saves the current write counts next to the database, for the next launch to compare
+    /// against. Called when the app closes normally; a read-only instance leaves it alone.
+    pub fn record_clean_shutdown(&self) -> Result<(), String> {
+        if self.read_only {
+            return Ok(());
+        }
+        let state = ShutdownState {
+            recorded_at: Utc::now(),
+            tables: self.get_last_modified_summary()?,
+        };
+        let json = serde_json::to_string_pretty(&state).map_err(|e| e.to_string())?;
+        let path = shutdown_state_path(&self.path);
+        std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    // A table with fewer writes than at the last clean shutdown has gone back in time, which
+    // happens when a sync tool replaces the file with an older copy. A missing or unreadable
+    // state file (first launch, or after a crash) just means there's nothing to compare.
+    fn detect_rollback(&self) -> Option<DataRollback> {
+        let json = std::fs::read_to_string(shutdown_state_path(&self.path)).ok()?;
+        let expected: ShutdownState = serde_json::from_str(&json).ok()?;
+        let current = self.get_last_modified_summary().ok()?;
+
+        let tables: Vec<TableRegression> = expected.tables.iter()
+            .filter_map(|before| {
+                let now = current.iter().find(|t| t.table_name == before.table_name)?;
+                (now.write_count < before.write_count).then(|| TableRegression {
+                    table_name: before.table_name.clone(),
+                    expected_write_count: before.write_count,
+                    actual_write_count: now.write_count,
+                    writes_missing: before.write_count - now.write_count,
+                    expected_last_modified: before.last_modified,
+                    actual_last_modified: now.last_modified,
+                })
+            })
+            .collect();
+
+        if tables.is_empty() {
+            None
+        } else {
+            Some(DataRollback { recorded_at: expected.recorded_at, tables })
+        }
+    }
+
+    // Every write goes through here: the first one to succeed after a migration shows the
+    // new schema works, so the pre-migrate copy is no longer needed
+    fn execute<P: rusqlite::Params>(&self, sql: &str, params: P) -> SqlResult<usize> {
+        if self.read_only {
+            return Err(rusqlite::Error::SqliteFailure(
+                rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_READONLY),
+                Some(AppError::ReadOnly.to_string()),
+            ));
+        }
+        let changed = self.conn.execute(sql, params)?;
+        if let Some(copy) = self.pre_migrate_copy.borrow_mut().take() {
+            let _ = std::fs::remove_file(copy);
+        }
+        Ok(changed)
+    }
+
+    /// Runs `f` as one transaction: every write it makes is kept, or none are. Transactions
+    /// nest, so `f` can call other operations that use one.
+    pub fn in_transaction<T>(&self, f: impl FnOnce(&Self) -> Result<T, String>) -> Result<T, String> {
+        self.conn.execute_batch("SAVEPOINT careerflow_tx").map_err(|e| e.to_string())?;
+        match f(self) {
+            Ok(value) => {
+                self.conn.execute_batch("RELEASE careerflow_tx").map_err(|e| e.to_string())?;
+                Ok(value)
+            }
+            Err(e) => {
+                self.conn
+                    .execute_batch("ROLLBACK TO careerflow_tx; RELEASE careerflow_tx")
+                    .map_err(|rollback| format!("{} (and rolling back failed: {})", e, rollback))?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Marks every migrated value on the row as confirmed, used when the user saves the row.
+    fn clear_backfill_reviews(&self, table: &str, row_id: i64) -> SqlResult<()> {
+        self.execute(
+            "UPDATE backfill_reviews SET needs_review = FALSE WHERE table_name = ?1 AND row_id = ?2",
+            params![table, row_id],
+        )?;
+        Ok(())
+    }
+
+    // User Profile operations
+    pub fn get_user_profile(&self) -> Result<Option<UserProfile>, String> {
+        let mut stmt = self.conn
+            .prepare(
+                "SELECT id, first_name, last_name, date_of_birth, state, industry,
+                        highest_qualification, employment_type_preference, fifo_tolerance,
+                        travel_tolerance, overtime_appetite, privacy_acknowledged,
+                        disclaimer_acknowledged, standard_weekly_hours, created_at, updated_at,
+                        has_help_debt, total_super_balance, gender
+                 FROM user_profile
+                 LIMIT 1"
+            )
+            .map_err(|e| e.to_string())?;
+
+        let result = stmt.query_row([], |row| {
+            Ok(UserProfile {
+                id: Some(row.get(0)?),
+                first_name: row.get(1)?,
+                last_name: row.get(2)?,
+                date_of_birth: NaiveDate::parse_from_str(&row.get::<_, String>(3)?, "%Y-%m-%d")
+                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e)))?,
+                state: serde_json::from_str(&row.get::<_, String>(4)?)
+                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(4, rusqlite::types::Type::Text, Box::new(e)))?,
+                industry: row.get(5)?,
+                highest_qualification: serde_json::from_str(&row.get::<_, String>(6)?)
+                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(6, rusqlite::types::Type::Text, Box::new(e)))?,
+                career_preferences: CareerPreferences {
+                    employment_type_preference: serde_json::from_str(&row.get::<_, String>(7)?)
+                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(7, rusqlite::types::Type::Text, Box::new(e)))?,
+                    fifo_tolerance: serde_json::from_str(&row.get::<_, String>(8)?)
+                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(8, rusqlite::types::Type::Text, Box::new(e)))?,
+                    travel_tolerance: serde_json::from_str(&row.get::<_, String>(9)?)
+                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(9, rusqlite::types::Type::Text, Box::new(e)))?,
+                    overtime_appetite: serde_json::from_str(&row.get::<_, String>(10)?)
+                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(10, rusqlite::types::Type::Text, Box::new(e)))?,
+                    privacy_acknowledged: row.get(11)?,
+                    disclaimer_acknowledged: row.get(12)?,
+                },
+                standard_weekly_hours: row.get(13)?,
+                has_help_debt: row.get(16)?,
+                total_super_balance: row.get(17)?,
+                gender: serde_json::from_str(&row.get::<_, String>(18)?)
+                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(18, rusqlite::types::Type::Text, Box::new(e)))?,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(14)?)
+                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(14, rusqlite::types::Type::Text, Box::new(e)))?
+                    .with_timezone(&Utc),
+                updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(15)?)
+                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(15, rusqlite::types::Type::Text, Box::new(e)))?
+                    .with_timezone(&Utc),
+            })
+        });
+
+        match result {
+            Ok(profile) => Ok(Some(profile)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn save_user_profile(&self, profile: UserProfile) -> SqlResult<()> {
+        let now = Utc::now().to_rfc3339();
+        
+        if let Some(id) = profile.id {
+            // Update existing
+            self.execute(
+                "UPDATE user_profile SET
+                    first_name = ?1, last_name = ?2, date_of_birth = ?3, state = ?4,
+                    industry = ?5, highest_qualification = ?6, employment_type_preference = ?7,
+                    fifo_tolerance = ?8, travel_tolerance = ?9, overtime_appetite = ?10,
+                    privacy_acknowledged = ?11, disclaimer_acknowledged = ?12,
+                    standard_weekly_hours = ?13, updated_at = ?14, has_help_debt = ?16,
+                    total_super_balance = ?17, gender = ?18
+                 WHERE id = ?15",
+                params![
+                    profile.first_name,
+                    profile.last_name,
+                    profile.date_of_birth.to_string(),
+                    to_json(&profile.state)?,
+                    profile.industry,
+                    to_json(&profile.highest_qualification)?,
+                    to_json(&profile.career_preferences.employment_type_preference)?,
+                    to_json(&profile.career_preferences.fifo_tolerance)?,
+                    to_json(&profile.career_preferences.travel_tolerance)?,
+                    to_json(&profile.career_preferences.overtime_appetite)?,
+                    profile.career_preferences.privacy_acknowledged,
+                    profile.career_preferences.disclaimer_acknowledged,
+                    profile.standard_weekly_hours,
+                    now,
+                    id,
+                    profile.has_help_debt,
+                    profile.total_super_balance,
+                    to_json(&profile.gender)?
+                ],
+            )?;
+            self.clear_backfill_reviews("user_profile", id)?;
+        } else {
+            // Insert new
+            self.execute(
+                "INSERT INTO user_profile (
+                    first_name, last_name, date_of_birth, state, industry,
+                    highest_qualification, employment_type_preference, fifo_tolerance,
+                    travel_tolerance, overtime_appetite, privacy_acknowledged,
+                    disclaimer_acknowledged, standard_weekly_hours, created_at, updated_at,
+                    has_help_debt, total_super_balance, gender
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+                params![
+                    profile.first_name,
+                    profile.last_name,
+                    profile.date_of_birth.to_string(),
+                    to_json(&profile.state)?,
+                    profile.industry,
+                    to_json(&profile.highest_qualification)?,
+                    to_json(&profile.career_preferences.employment_type_preference)?,
+                    to_json(&profile.career_preferences.fifo_tolerance)?,
+                    to_json(&profile.career_preferences.travel_tolerance)?,
+                    to_json(&profile.career_preferences.overtime_appetite)?,
+                    profile.career_preferences.privacy_acknowledged,
+                    profile.career_preferences.disclaimer_acknowledged,
+                    profile.standard_weekly_hours,
+                    now,
+                    now,
+                    profile.has_help_debt,
+                    profile.total_super_balance,
+                    to_json(&profile.gender)?
+                ],
+            )?;
+        }
+        
+        Ok(())
+    }
+
+    // Position operations
+    /// Every position, or an error when any row no longer reads; see [`Database::list_positions`].
+    pub fn get_positions(&self) -> Result<Vec<Position>, String> {
+        every_row(self.list_positions()?)
+    }
+
+    /// Every position, with the rows that failed to read (a bad date or malformed JSON) listed
+    /// separately so they can be repaired.
+    pub fn list_positions(&self) -> Result<ListResult<Position>, String> {
+        let mut stmt = self.conn
+            .prepare(&format!(
+                "SELECT {}
+                 FROM positions
+                 ORDER BY start_date DESC",
+                POSITION_COLUMNS
+            ))
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, position_from_row(row))))
+            .map_err(|e| e.to_string())?;
+        collect_list("positions", rows)
+    }
+
+    /// Every position without its responsibilities, skills or achievements, which can run
+    /// to kilobytes each. Same order as [`Database::get_positions`].
+    pub fn get_position_summaries(&self) -> Result<Vec<PositionSummary>, String> {
+        let mut stmt = self.conn
+            .prepare(&format!(
+                "SELECT {}
+                 FROM positions
+                 ORDER BY start_date DESC",
+                POSITION_SUMMARY_COLUMNS
+            ))
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map([], position_summary_from_row).map_err(|e| e.to_string())?;
+
+        let mut summaries = Vec::new();
+        for row_result in rows {
+            summaries.push(row_result.map_err(|e| e.to_string())?);
+        }
+        Ok(summaries)
+    }
+
+    /// Positions overlapping the inclusive date range; positions without an end date are
+    /// still active.
+    pub fn positions_active_between(&self, start: NaiveDate, end: NaiveDate) -> Result<Vec<Position>, String> {
+        let mut stmt = self.conn
+            .prepare(&format!(
+                "SELECT {}
+                 FROM positions
+                 WHERE start_date <= ?2 AND (end_date IS NULL OR end_date >= ?1)
+                 ORDER BY start_date DESC",
+                POSITION_COLUMNS
+            ))
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map(params![start.to_string(), end.to_string()], position_from_row)
+            .map_err(|e| e.to_string())?;
+
+        let mut positions = Vec::new();
+        for row_result in rows {
+            positions.push(row_result.map_err(|e| e.to_string())?);
+        }
+        Ok(positions)
+    }
+
+    pub fn save_position(&self, position: Position) -> SqlResult<i64> {
+        let now = Utc::now().to_rfc3339();
+        
+        let tools_json = to_json(&position.tools_systems_skills)?;
+        let achievements_json = to_json(&position.achievements)?;
+        let industry = position.industry.as_deref().map(str::trim).filter(|i| !i.is_empty());
+        let week_ending_day = position.week_ending_day.map(|day| day.to_string());
+        
+        if let Some(id) = position.id {
+            // Update existing
+            self.execute(
+                "UPDATE positions SET
+                    employer_name = ?1, job_title = ?2, employment_type = ?3, location = ?4,
+                    start_date = ?5, end_date = ?6, seniority_level = ?7, core_responsibilities = ?8,
+                    tools_systems_skills = ?9, achievements = ?10, updated_at = ?11, industry = ?12,
+                    week_ending_day = ?13, notice_period_weeks = ?15, leave_loading_percent = ?16,
+                    probation_months = ?17, commute_minutes_per_day = ?18, archived = ?19
+                 WHERE id = ?14",
+                params![
+                    position.employer_name,
+                    position.job_title,
+                    to_json(&position.employment_type)?,
+                    position.location,
+                    position.start_date.to_string(),
+                    position.end_date.map(|d| d.to_string()),
+                    to_json(&position.seniority_level)?,
+                    position.core_responsibilities,
+                    tools_json,
+                    achievements_json,
+                    now,
+                    industry,
+                    week_ending_day,
+                    id,
+                    position.notice_period_weeks,
+                    position.leave_loading_percent,
+                    position.probation_months,
+                    position.commute_minutes_per_day,
+                    position.archived
+                ],
+            )?;
+            Ok(id)
+        } else {
+            // Insert new
+            self.execute(
+                "INSERT INTO positions (
+                    employer_name, job_title, employment_type, location, start_date,
+                    end_date, seniority_level, core_responsibilities, tools_systems_skills,
+                    achievements, created_at, updated_at, industry, week_ending_day,
+                    notice_period_weeks, leave_loading_percent, probation_months, commute_minutes_per_day,
+                    archived
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)",
+                params![
+                    position.employer_name,
+                    position.job_title,
+                    to_json(&position.employment_type)?,
+                    position.location,
+                    position.start_date.to_string(),
+                    position.end_date.map(|d| d.to_string()),
+                    to_json(&position.seniority_level)?,
+                    position.core_responsibilities,
+                    tools_json,
+                    achievements_json,
+                    now,
+                    now,
+                    industry,
+                    week_ending_day,
+                    position.notice_period_weeks,
+                    position.leave_loading_percent,
+                    position.probation_months,
+                    position.commute_minutes_per_day,
+                    position.archived
+                ],
+            )?;
+            Ok(self.conn.last_insert_rowid())
+        }
+    }
+
+    pub fn get_position(&self, id: i64) -> Result<Option<Position>, String> {
+        self.conn
+            .query_row(
+                &format!("SELECT {} FROM positions WHERE id = ?1", POSITION_COLUMNS),
+                [id],
+                position_from_row,
+            )
+            .optional()
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn set_position_archived(&self, id: i64, archived: bool) -> SqlResult<()> {
+        self.execute(
+            "UPDATE positions SET archived = ?1, updated_at = ?2 WHERE id = ?3",
+            params![archived, Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_position(&self, id: i64) -> SqlResult<()> {
+        self.execute("DELETE FROM positions WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    // Compensation Record operations
+    /// The position's records, skipping any row that no longer reads.
+    pub fn get_compensation_records(&self, position_id: i64) -> Result<Vec<CompensationRecord>, String> {
+        let mut stmt = self.conn
+            .prepare(&format!(
+                "SELECT {} FROM compensation_records
+                 WHERE position_id = ?1
+                 ORDER BY effective_date DESC",
+                COMPENSATION_RECORD_COLUMNS
+            ))
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map([position_id], |row| Ok((row.get::<_, i64>(0)?, compensation_record_from_row(row))))
+            .map_err(|e| e.to_string())?;
+        every_row(collect_list("compensation_records", rows)?)
+    }
+
+    pub fn get_compensation_record(&self, id: i64) -> Result<Option<CompensationRecord>, String> {
+        self.conn
+            .query_row(
+                &format!("SELECT {} FROM compensation_records WHERE id = ?1", COMPENSATION_RECORD_COLUMNS),
+                [id],
+                compensation_record_from_row,
+            )
+            .optional()
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn save_compensation_record(&self, record: CompensationRecord) -> SqlResult<i64> {
+        let now = Utc::now().to_rfc3339();
+        
+        let allowances_json = to_json(&record.allowances)?;
+        let bonuses_json = to_json(&record.bonuses)?;
+        let payslip_freq_json: Option<String> = match &record.payslip_frequency {
+            Some(freq) => Some(to_json(freq)?),
+            None => None,
+        };
+        let equity_json: Option<String> = match &record.equity {
+            Some(equity) => Some(to_json(equity)?),
+            None => None,
+        };
+        let packaging_json = to_json(&record.packaging)?;
+        let non_cash_benefits_json = to_json(&record.non_cash_benefits)?;
+        
+        if let Some(id) = record.id {
+            // Update existing
+            self.execute(
+                "UPDATE compensation_records SET
+                    entry_type = ?1, pay_type = ?2, base_rate = ?3, standard_weekly_hours = ?4,
+                    overtime_frequency = ?5, overtime_rate_multiplier = ?6,
+                    overtime_average_hours_per_week = ?7, overtime_annual_hours = ?8,
+                    allowances = ?9, bonuses = ?10, super_contribution_rate = ?11,
+                    super_additional_contributions = ?12, super_salary_sacrifice = ?13,
+                    payslip_frequency = ?14, tax_withheld = ?15, effective_date = ?16, confidence_score = ?17, notes = ?18,
+                    currency = ?19, jurisdiction = ?20, claims_tax_free_threshold = ?21, equity = ?23, packaging = ?24,
+                    award_classification_id = ?25, non_cash_benefits = ?26
+                 WHERE id = ?22",
+                params![
+                    to_json(&record.entry_type)?,
+                    to_json(&record.pay_type)?,
+                    record.base_rate,
+                    record.standard_weekly_hours,
+                    to_json(&record.overtime.frequency)?,
+                    record.overtime.rate_multiplier,
+                    record.overtime.average_hours_per_week,
+                    record.overtime.annual_hours,
+                    allowances_json,
+                    bonuses_json,
+                    record.super_contributions.contribution_rate,
+                    record.super_contributions.additional_contributions,
+                    record.super_contributions.salary_sacrifice,
+                    payslip_freq_json,
+                    record.tax_withheld,
+                    record.effective_date.to_string(),
+                    record.confidence_score,
+                    record.notes,
+                    record.currency,
+                    to_json(&record.jurisdiction)?,
+                    record.claims_tax_free_threshold,
+                    id,
+                    equity_json,
+                    packaging_json,
+                    record.award_classification_id,
+                    non_cash_benefits_json
+                ],
+            )?;
+            self.clear_backfill_reviews("compensation_records", id)?;
+            Ok(id)
+        } else {
+            // Insert new
+            self.execute(
+                "INSERT INTO compensation_records (
+                    position_id, entry_type, pay_type, base_rate, standard_weekly_hours,
+                    overtime_frequency, overtime_rate_multiplier, overtime_average_hours_per_week,
+                    overtime_annual_hours, allowances, bonuses, super_contribution_rate,
+                    super_additional_contributions, super_salary_sacrifice, payslip_frequency,
+                    tax_withheld, effective_date, confidence_score, notes, created_at, currency, jurisdiction,
+                    claims_tax_free_threshold, equity, packaging, award_classification_id, non_cash_benefits
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27)",
+                params![
+                    record.position_id,
+                    to_json(&record.entry_type)?,
+                    to_json(&record.pay_type)?,
+                    record.base_rate,
+                    record.standard_weekly_hours,
+                    to_json(&record.overtime.frequency)?,
+                    record.overtime.rate_multiplier,
+                    record.overtime.average_hours_per_week,
+                    record.overtime.annual_hours,
+                    allowances_json,
+                    bonuses_json,
+                    record.super_contributions.contribution_rate,
+                    record.super_contributions.additional_contributions,
+                    record.super_contributions.salary_sacrifice,
+                    payslip_freq_json,
+                    record.tax_withheld,
+                    record.effective_date.to_string(),
+                    record.confidence_score,
+                    record.notes,
+                    now,
+                    record.currency,
+                    to_json(&record.jurisdiction)?,
+                    record.claims_tax_free_threshold,
+                    equity_json,
+                    packaging_json,
+                    record.award_classification_id,
+                    non_cash_benefits_json
+                ],
+            )?;
+            Ok(self.conn.last_insert_rowid())
+        }
+    }
+
+    /// Moves a compensation record to another position, leaving the rest of it as it is.
+    pub fn move_compensation_record(&self, id: i64, position_id: i64) -> SqlResult<()> {
+        self.execute(
+            "UPDATE compensation_records SET position_id = ?1 WHERE id = ?2",
+            params![position_id, id],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_compensation_record(&self, id: i64) -> SqlResult<()> {
+        self.execute("DELETE FROM compensation_records WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    // Weekly Entry operations
+    /// Every pay entry, or an error when any row no longer reads; see [`Database::list_weekly_entries`].
+    pub fn get_weekly_entries(&self) -> Result<Vec<WeeklyCompensationEntry>, String> {
+        every_row(self.list_weekly_entries()?)
+    }
+
+    /// Every pay entry, with the rows that failed to read listed separately.
+    pub fn list_weekly_entries(&self) -> Result<ListResult<WeeklyCompensationEntry>, String> {
+        let mut stmt = self.conn
+            .prepare(
+                "SELECT id, position_id, financial_year, week_ending, gross_pay,
+                        tax_withheld, net_pay, hours_ordinary, hours_overtime,
+                        overtime_rate_multiplier, allowances, super_contributed,
+                        notes, created_at, period_start, period_type, currency
+                 FROM weekly_entries
+                 ORDER BY week_ending DESC"
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, weekly_entry_from_row(row))))
+            .map_err(|e| e.to_string())?;
+        collect_list("weekly_entries", rows)
+    }
+
+    /// Totals and date coverage of one position's pay entries, worked out in a single query.
+    /// Amounts are summed as entered, whatever their currency. A gap is any break of more than
+    /// a few days between one period's end and the next one's start; the slack absorbs
+    /// month-end rounding on monthly payslips.
+    pub fn get_position_weekly_stats(&self, position_id: i64) -> Result<PositionWeeklyStats, String> {
+        self.conn
+            .query_row(
+                "WITH periods AS (
+                     SELECT week_ending, gross_pay, tax_withheld, net_pay, super_contributed,
+                            hours_ordinary, hours_overtime,
+                            COALESCE(period_start, CASE period_type
+                                WHEN '\"Fortnightly\"' THEN date(week_ending, '-13 days')
+                                WHEN '\"Monthly\"' THEN date(week_ending, '-1 month', '+1 day')
+                                ELSE date(week_ending, '-6 days')
+                            END) AS starts,
+                            LAG(week_ending) OVER (ORDER BY week_ending) AS previous_end
+                     FROM weekly_entries
+                     WHERE position_id = ?1
+                 )
+                 SELECT COUNT(*), MIN(week_ending), MAX(week_ending),
+                        COALESCE(SUM(julianday(week_ending) - julianday(starts) + 1), 0) / 7.0,
+                        COALESCE(SUM(starts > date(previous_end, '+4 days')), 0),
+                        COALESCE(SUM(gross_pay), 0), COALESCE(SUM(tax_withheld), 0),
+                        COALESCE(SUM(net_pay), 0), COALESCE(SUM(super_contributed), 0),
+                        COALESCE(SUM(hours_ordinary), 0), COALESCE(SUM(hours_overtime), 0)
+                 FROM periods",
+                [position_id],
+                |row| {
+                    let date_at = |index: usize| -> SqlResult<Option<NaiveDate>> {
+                        row.get::<_, Option<String>>(index)?
+                            .map(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+                                .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e))))
+                            .transpose()
+                    };
+                    let weeks_logged: f64 = row.get(3)?;
+                    let hours_ordinary: f64 = row.get(9)?;
+                    let hours_overtime: f64 = row.get(10)?;
+                    let hours = hours_ordinary + hours_overtime;
+                    Ok(PositionWeeklyStats {
+                        position_id,
+                        entry_count: row.get(0)?,
+                        first_week_ending: date_at(1)?,
+                        last_week_ending: date_at(2)?,
+                        weeks_logged,
+                        gap_count: row.get(4)?,
+                        total_gross: row.get(5)?,
+                        total_tax_withheld: row.get(6)?,
+                        total_net: row.get(7)?,
+                        total_super: row.get(8)?,
+                        average_weekly_hours: if weeks_logged > 0.0 { hours / weeks_logged } else { 0.0 },
+                        overtime_share: if hours > 0.0 { hours_overtime / hours } else { 0.0 },
+                    })
+                },
+            )
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn save_weekly_entry(&self, entry: WeeklyCompensationEntry) -> SqlResult<i64> {
+        let now = Utc::now().to_rfc3339();
+        
+        let allowances_json = to_json(&entry.allowances)?;
+        let period_start = entry.period_start
+            .unwrap_or_else(|| dates::period_start_for(entry.week_ending, &entry.period_type));
+        let period_type_json = to_json(&entry.period_type)?;
+        // The label follows the period end, whatever the caller worked out
+        let financial_year = FinancialYear::containing(entry.week_ending).label();
+        
+        if let Some(id) = entry.id {
+            // Update existing
+            self.execute(
+                "UPDATE weekly_entries SET
+                    position_id = ?1, financial_year = ?2, week_ending = ?3,
+                    gross_pay = ?4, tax_withheld = ?5, net_pay = ?6,
+                    hours_ordinary = ?7, hours_overtime = ?8, overtime_rate_multiplier = ?9,
+                    allowances = ?10, super_contributed = ?11, notes = ?12,
+                    period_start = ?13, period_type = ?14, currency = ?15
+                 WHERE id = ?16",
+                params![
+                    entry.position_id,
+                    financial_year,
+                    entry.week_ending.to_string(),
+                    entry.gross_pay,
+                    entry.tax_withheld,
+                    entry.net_pay,
+                    entry.hours_ordinary,
+                    entry.hours_overtime,
+                    entry.overtime_rate_multiplier,
+                    allowances_json,
+                    entry.super_contributed,
+                    entry.notes,
+                    period_start.to_string(),
+                    period_type_json,
+                    entry.currency,
+                    id
+                ],
+            )?;
+            self.clear_backfill_reviews("weekly_entries", id)?;
+            Ok(id)
+        } else {
+            // Insert new
+            self.execute(
+                "INSERT INTO weekly_entries (
+                    position_id, financial_year, week_ending, gross_pay, tax_withheld,
+                    net_pay, hours_ordinary, hours_overtime, overtime_rate_multiplier,
+                    allowances, super_contributed, notes, created_at, period_start, period_type, currency
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+                params![
+                    entry.position_id,
+                    financial_year,
+                    entry.week_ending.to_string(),
+                    entry.gross_pay,
+                    entry.tax_withheld,
+                    entry.net_pay,
+                    entry.hours_ordinary,
+                    entry.hours_overtime,
+                    entry.overtime_rate_multiplier,
+                    allowances_json,
+                    entry.super_contributed,
+                    entry.notes,
+                    now,
+                    period_start.to_string(),
+                    period_type_json,
+                    entry.currency
+                ],
+            )?;
+            Ok(self.conn.last_insert_rowid())
+        }
+    }
+
+    pub fn delete_weekly_entry(&self, id: i64) -> SqlResult<()> {
+        self.execute("DELETE FROM weekly_entries WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    /// Moves pay entries from one position (or unlinked entries, when `from_position_id` is
+    /// None) to another, optionally only those ending within an inclusive date range.
+    /// Returns how many were moved.
+    pub fn reassign_weekly_entries(
+        &self,
+        from_position_id: Option<i64>,
+        to_position_id: i64,
+        date_range: Option<(NaiveDate, NaiveDate)>,
+    ) -> SqlResult<usize> {
+        let (from_date, to_date) = match date_range {
+            Some((from, to)) => (Some(from.to_string()), Some(to.to_string())),
+            None => (None, None),
+        };
+        self.execute(
+            "UPDATE weekly_entries SET position_id = ?1
+             WHERE position_id IS ?2
+               AND (?3 IS NULL OR week_ending >= ?3)
+               AND (?4 IS NULL OR week_ending <= ?4)",
+            params![to_position_id, from_position_id, from_date, to_date],
+        )
+    }
+
+    // Get ALL compensation records (across all positions)
+    /// Every record, or an error when any row no longer reads; see [`Database::list_compensation_records`].
+    pub fn get_all_compensation_records(&self) -> Result<Vec<CompensationRecord>, String> {
+        every_row(self.list_compensation_records()?)
+    }
+
+    /// Every record, with the rows that failed to read listed separately.
+    pub fn list_compensation_records(&self) -> Result<ListResult<CompensationRecord>, String> {
+        let mut stmt = self.conn
+            .prepare(&format!(
+                "SELECT {} FROM compensation_records
+                 ORDER BY effective_date DESC",
+                COMPENSATION_RECORD_COLUMNS
+            ))
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, compensation_record_from_row(row))))
+            .map_err(|e| e.to_string())?;
+        collect_list("compensation_records", rows)
+    }
+
+    // Yearly Income Entry operations
+    /// Every yearly entry, or an error when any row no longer reads; see [`Database::list_yearly_entries`].
+    pub fn get_yearly_entries(&self) -> Result<Vec<YearlyIncomeEntry>, String> {
+        every_row(self.list_yearly_entries()?)
+    }
+
+    /// Every yearly entry, with the rows that failed to read listed separately.
+    pub fn list_yearly_entries(&self) -> Result<ListResult<YearlyIncomeEntry>, String> {
+        let mut stmt = self.conn
+            .prepare(
+                "SELECT id, position_id, financial_year, gross_income, tax_withheld,
+                        reportable_super, reportable_fringe_benefits, allowances, source, notes, created_at,
+                        currency, jurisdiction
+                 FROM yearly_income_entries
+                 ORDER BY financial_year DESC"
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, yearly_entry_from_row(row))))
+            .map_err(|e| e.to_string())?;
+        collect_list("yearly_income_entries", rows)
+    }
+
+    pub fn save_yearly_entry(&self, entry: YearlyIncomeEntry) -> SqlResult<i64> {
+        let now = Utc::now().to_rfc3339();
+        
+        if let Some(id) = entry.id {
+            // Update existing
+            self.execute(
+                "UPDATE yearly_income_entries SET
+                    position_id = ?1, financial_year = ?2, gross_income = ?3,
+                    tax_withheld = ?4, reportable_super = ?5, reportable_fringe_benefits = ?6,
+                    allowances = ?7, source = ?8, notes = ?9, currency = ?10, jurisdiction = ?11
+                 WHERE id = ?12",
+                params![
+                    entry.position_id,
+                    entry.financial_year,
+                    entry.gross_income,
+                    entry.tax_withheld,
+                    entry.reportable_super,
+                    entry.reportable_fringe_benefits,
+                    to_json(&entry.allowances)?,
+                    to_json(&entry.source)?,
+                    entry.notes,
+                    entry.currency,
+                    to_json(&entry.jurisdiction)?,
+                    id
+                ],
+            )?;
+            self.clear_backfill_reviews("yearly_income_entries", id)?;
+            Ok(id)
+        } else {
+            // Insert new
+            self.execute(
+                "INSERT INTO yearly_income_entries (
+                    position_id, financial_year, gross_income, tax_withheld,
+                    reportable_super, reportable_fringe_benefits, allowances, source, notes, created_at,
+                    currency, jurisdiction
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    entry.position_id,
+                    entry.financial_year,
+                    entry.gross_income,
+                    entry.tax_withheld,
+                    entry.reportable_super,
+                    entry.reportable_fringe_benefits,
+                    to_json(&entry.allowances)?,
+                    to_json(&entry.source)?,
+                    entry.notes,
+                    now,
+                    entry.currency,
+                    to_json(&entry.jurisdiction)?
+                ],
+            )?;
+            Ok(self.conn.last_insert_rowid())
+        }
+    }
+
+    pub fn delete_yearly_entry(&self, id: i64) -> SqlResult<()> {
+        self.execute("DELETE FROM yearly_income_entries WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    // Exchange Rate operations
+    pub fn get_exchange_rates(&self) -> Result<Vec<ExchangeRate>, String> {
+        let mut stmt = self.conn
+            .prepare(
+                "SELECT id, currency, financial_year, rate_to_aud
+                 FROM exchange_rates
+                 ORDER BY currency, financial_year"
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(ExchangeRate {
+                id: Some(row.get(0)?),
+                currency: row.get(1)?,
+                financial_year: row.get(2)?,
+                rate_to_aud: row.get(3)?,
+            })
+        }).map_err(|e| e.to_string())?;
+
+        let mut rates = Vec::new();
+        for row_result in rows {
+            rates.push(row_result.map_err(|e| e.to_string())?);
+        }
+        Ok(rates)
+    }
+
+    /// Inserts or replaces the rate for the currency and financial year.
+    pub fn save_exchange_rate(&self, rate: ExchangeRate) -> SqlResult<i64> {
+        self.execute(
+            "INSERT INTO exchange_rates (currency, financial_year, rate_to_aud)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT (currency, financial_year) DO UPDATE SET rate_to_aud = excluded.rate_to_aud",
+            params![rate.currency, rate.financial_year, rate.rate_to_aud],
+        )?;
+
+        self.conn.query_row(
+            "SELECT id FROM exchange_rates WHERE currency = ?1 AND financial_year = ?2",
+            params![rate.currency, rate.financial_year],
+            |row| row.get(0),
+        )
+    }
+
+    pub fn delete_exchange_rate(&self, id: i64) -> SqlResult<()> {
+        self.execute("DELETE FROM exchange_rates WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    // Award rate operations
+    pub fn get_award_rates(&self) -> Result<Vec<AwardRate>, String> {
+        let mut stmt = self.conn
+            .prepare(
+                "SELECT id, award_name, classification, effective_date, hourly_minimum,
+                        overtime_multiplier, casual_loading
+                 FROM award_rates
+                 ORDER BY award_name, classification, effective_date"
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map([], row_to_award_rate).map_err(|e| e.to_string())?;
+
+        let mut rates = Vec::new();
+        for row_result in rows {
+            rates.push(row_result.map_err(|e| e.to_string())?);
+        }
+        Ok(rates)
+    }
+
+    pub fn save_award_rate(&self, rate: AwardRate) -> SqlResult<i64> {
+        if let Some(id) = rate.id {
+            self.execute(
+                "UPDATE award_rates SET
+                    award_name = ?1, classification = ?2, effective_date = ?3, hourly_minimum = ?4,
+                    overtime_multiplier = ?5, casual_loading = ?6
+                 WHERE id = ?7",
+                params![
+                    rate.award_name.trim(),
+                    rate.classification.trim(),
+                    rate.effective_date.to_string(),
+                    rate.hourly_minimum,
+                    rate.overtime_multiplier,
+                    rate.casual_loading,
+                    id
+                ],
+            )?;
+            Ok(id)
+        } else {
+            self.execute(
+                "INSERT INTO award_rates (award_name, classification, effective_date, hourly_minimum,
+                                          overtime_multiplier, casual_loading)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    rate.award_name.trim(),
+                    rate.classification.trim(),
+                    rate.effective_date.to_string(),
+                    rate.hourly_minimum,
+                    rate.overtime_multiplier,
+                    rate.casual_loading
+                ],
+            )?;
+            Ok(self.conn.last_insert_rowid())
+        }
+    }
+
+    /// Deletes the rate. Compensation records linked to it are moved to the latest other rate
+    /// for the same classification, or unlinked when there's none.
+    pub fn delete_award_rate(&self, id: i64) -> SqlResult<()> {
+        self.execute(
+            "UPDATE compensation_records SET award_classification_id = (
+                SELECT other.id FROM award_rates other
+                JOIN award_rates gone ON gone.id = ?1
+                WHERE other.id != ?1
+                  AND other.award_name = gone.award_name
+                  AND other.classification = gone.classification
+                ORDER BY other.effective_date DESC
+                LIMIT 1
+             )
+             WHERE award_classification_id = ?1",
+            [id],
+        )?;
+        self.execute("DELETE FROM award_rates WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    // Analysis snapshot operations
+    pub fn save_analysis_snapshot(&self, snapshot: &AnalysisSnapshot) -> SqlResult<i64> {
+        self.execute(
+            "INSERT INTO analysis_snapshots (taken_at, total_compensation, income_percentile,
+                                             loyalty_tax_annual, loyalty_tax_cumulative, projected_super)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                snapshot.taken_at.to_rfc3339(),
+                snapshot.total_compensation,
+                snapshot.income_percentile,
+                snapshot.loyalty_tax_annual,
+                snapshot.loyalty_tax_cumulative,
+                snapshot.projected_super,
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Every snapshot, oldest first.
+    pub fn get_analysis_snapshots(&self) -> Result<Vec<AnalysisSnapshot>, String> {
+        let mut stmt = self.conn
+            .prepare(
+                "SELECT id, taken_at, total_compensation, income_percentile,
+                        loyalty_tax_annual, loyalty_tax_cumulative, projected_super
+                 FROM analysis_snapshots
+                 ORDER BY taken_at, id"
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map([], row_to_analysis_snapshot).map_err(|e| e.to_string())?;
+
+        let mut snapshots = Vec::new();
+        for row_result in rows {
+            snapshots.push(row_result.map_err(|e| e.to_string())?);
+        }
+        Ok(snapshots)
+    }
+
+    pub fn get_analysis_snapshot(&self, id: i64) -> Result<Option<AnalysisSnapshot>, String> {
+        self.conn
+            .query_row(
+                "SELECT id, taken_at, total_compensation, income_percentile,
+                        loyalty_tax_annual, loyalty_tax_cumulative, projected_super
+                 FROM analysis_snapshots
+                 WHERE id = ?1",
+                [id],
+                row_to_analysis_snapshot,
+            )
+            .optional()
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn delete_analysis_snapshot(&self, id: i64) -> SqlResult<()> {
+        self.execute("DELETE FROM analysis_snapshots WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    /// Changes whenever this connection writes anything, so results computed from the data can be keyed on it.
+    pub fn data_version(&self) -> SqlResult<i64> {
+        self.conn.query_row("SELECT total_changes()", [], |row| row.get(0))
+    }
+
+    // Settings operations
+    /// Reads a setting, falling back to the type's default when it has never been saved.
+    pub fn get_setting<T: serde::de::DeserializeOwned + Default>(&self, key: &str) -> Result<T, String> {
+        let value: Option<String> = self.conn
+            .query_row("SELECT value FROM settings WHERE key = ?1", [key], |row| row.get(0))
+            .optional()
+            .map_err(|e| e.to_string())?;
+
+        match value {
+            Some(json) => serde_json::from_str(&json).map_err(|e| format!("Invalid {} setting: {}", key, e)),
+            None => Ok(T::default()),
+        }
+    }
+
+    pub fn save_setting<T: serde::Serialize>(&self, key: &str, value: &T) -> SqlResult<()> {
+        self.execute(
+            "INSERT INTO settings (key, value) VALUES (?1, ?2)
+             ON CONFLICT (key) DO UPDATE SET value = excluded.value",
+            params![key, to_json(value)?],
+        )?;
+        Ok(())
+    }
+
+    // Backfill review operations
+    /// Migrated values still flagged for review whose row still holds the migration default.
+    pub fn get_default_backfill_warnings(&self) -> Result<Vec<BackfillWarning>, String> {
+        let mut stmt = self.conn
+            .prepare(
+                "SELECT id, table_name, row_id, column_name, default_value
+                 FROM backfill_reviews
+                 WHERE needs_review
+                 ORDER BY table_name, row_id, column_name"
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(BackfillWarning {
+                id: row.get(0)?,
+                table_name: row.get(1)?,
+                row_id: row.get(2)?,
+                column_name: row.get(3)?,
+                default_value: row.get(4)?,
+                message: String::new(),
+            })
+        }).map_err(|e| e.to_string())?;
+
+        let mut warnings = Vec::new();
+        for row_result in rows {
+            let mut warning = row_result.map_err(|e| e.to_string())?;
+
+            // Table and column names come from the migration, never from user input
+            let current: Option<String> = self.conn
+                .query_row(
+                    &format!("SELECT CAST({} AS TEXT) FROM {} WHERE id = ?1", warning.column_name, warning.table_name),
+                    [warning.row_id],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(|e| e.to_string())?;
+
+            if current.as_deref() == Some(warning.default_value.as_str()) {
+                warning.message = backfill_message(&warning.column_name, &warning.default_value);
+                warnings.push(warning);
+            }
+        }
+        Ok(warnings)
+    }
+
+    // JSON column repair
+    /// Checks every JSON array column and rewrites rows that no longer parse, so one bad row can't
+    /// fail a whole list. A value that can still be read leniently (a lone item, or comma-separated
+    /// text in a list of strings) is kept; anything else is reset to `[]`. Each change is logged
+    /// with the original text.
+    pub fn repair_json_columns(&self) -> Result<Vec<RepairLog>, String> {
+        self.in_transaction(|db| {
+            let mut logs = Vec::new();
+            for column in JSON_ARRAY_COLUMNS {
+                logs.extend(db.repair_json_column(column)?);
+            }
+            Ok(logs)
+        })
+    }
+
+    fn repair_json_column(&self, column: &JsonArrayColumn) -> Result<Vec<RepairLog>, String> {
+        // Table and column names come from JSON_ARRAY_COLUMNS, never from user input
+        let mut stmt = self.conn
+            .prepare(&format!("SELECT id, {} FROM {} ORDER BY id", column.column, column.table))
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))
+            .map_err(|e| e.to_string())?;
+
+        let mut logs = Vec::new();
+        for row_result in rows {
+            let (row_id, original) = row_result.map_err(|e| e.to_string())?;
+            if (column.parses)(&original) {
+                continue;
+            }
+            let recovered = lenient_json_array(&original, column);
+            let repaired_to = recovered.clone().unwrap_or_else(|| "[]".to_string());
+            self.execute(
+                &format!("UPDATE {} SET {} = ?1 WHERE id = ?2", column.table, column.column),
+                params![repaired_to, row_id],
+            )
+            .map_err(|e| e.to_string())?;
+            logs.push(RepairLog {
+                table_name: column.table.to_string(),
+                row_id,
+                column_name: column.column.to_string(),
+                original,
+                repaired_to,
+                recovered: recovered.is_some(),
+            });
+        }
+        Ok(logs)
+    }
+
+    /// Confirms a migrated value so it's no longer reported.
+    pub fn resolve_backfill_warning(&self, id: i64) -> SqlResult<()> {
+        self.execute("UPDATE backfill_reviews SET needs_review = FALSE WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    pub fn get_dismissed_reminders(&self) -> Result<Vec<String>, String> {
+        let mut stmt = self.conn
+            .prepare("SELECT reminder_id FROM dismissed_reminders ORDER BY reminder_id")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt.query_map([], |row| row.get(0)).map_err(|e| e.to_string())?;
+
+        let mut ids = Vec::new();
+        for row_result in rows {
+            ids.push(row_result.map_err(|e| e.to_string())?);
+        }
+        Ok(ids)
+    }
+
+    pub fn dismiss_reminder(&self, reminder_id: &str) -> SqlResult<()> {
+        self.execute(
+            "INSERT OR IGNORE INTO dismissed_reminders (reminder_id, dismissed_at) VALUES (?1, ?2)",
+            params![reminder_id, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    // Change log operations
+    pub fn log_record_change(&self, record_id: i64, change: &str, before: &CompensationRecord) -> SqlResult<i64> {
+        self.execute(
+            "INSERT INTO record_changes (record_id, changed_at, change, before) VALUES (?1, ?2, ?3, ?4)",
+            params![record_id, Utc::now().to_rfc3339(), change, to_json(before)?],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// The change log for a record, newest first.
+    pub fn get_record_changes(&self, record_id: i64) -> Result<Vec<RecordChange>, String> {
+        let mut stmt = self.conn
+            .prepare(
+                "SELECT id, record_id, changed_at, change, before
+                 FROM record_changes
+                 WHERE record_id = ?1
+                 ORDER BY changed_at DESC, id DESC"
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map([record_id], row_to_record_change).map_err(|e| e.to_string())?;
+
+        let mut changes = Vec::new();
+        for row_result in rows {
+            changes.push(row_result.map_err(|e| e.to_string())?);
+        }
+        Ok(changes)
+    }
+
+    // Scenario operations
+    pub fn get_scenarios(&self) -> Result<Vec<Scenario>, String> {
+        let mut stmt = self.conn
+            .prepare(
+                "SELECT id, name, position_id, baseline, offer, result, created_at
+                 FROM scenarios
+                 ORDER BY created_at DESC, id DESC"
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map([], row_to_scenario).map_err(|e| e.to_string())?;
+
+        let mut scenarios = Vec::new();
+        for row_result in rows {
+            scenarios.push(row_result.map_err(|e| e.to_string())?);
+        }
+        Ok(scenarios)
+    }
+
+    pub fn get_scenario(&self, id: i64) -> Result<Option<Scenario>, String> {
+        self.conn
+            .query_row(
+                "SELECT id, name, position_id, baseline, offer, result, created_at
+                 FROM scenarios WHERE id = ?1",
+                [id],
+                row_to_scenario,
+            )
+            .optional()
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn save_scenario(&self, scenario: Scenario) -> SqlResult<i64> {
+        if let Some(id) = scenario.id {
+            self.execute(
+                "UPDATE scenarios SET
+                    name = ?1, position_id = ?2, baseline = ?3, offer = ?4, result = ?5, created_at = ?6
+                 WHERE id = ?7",
+                params![
+                    scenario.name.trim(),
+                    scenario.position_id,
+                    to_json(&scenario.baseline)?,
+                    to_json(&scenario.offer)?,
+                    to_json(&scenario.result)?,
+                    scenario.created_at.to_rfc3339(),
+                    id
+                ],
+            )?;
+            Ok(id)
+        } else {
+            self.execute(
+                "INSERT INTO scenarios (name, position_id, baseline, offer, result, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    scenario.name.trim(),
+                    scenario.position_id,
+                    to_json(&scenario.baseline)?,
+                    to_json(&scenario.offer)?,
+                    to_json(&scenario.result)?,
+                    scenario.created_at.to_rfc3339()
+                ],
+            )?;
+            Ok(self.conn.last_insert_rowid())
+        }
+    }
+
+    pub fn delete_scenario(&self, id: i64) -> SqlResult<()> {
+        self.execute("DELETE FROM scenarios WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    // Position template operations
+    pub fn get_templates(&self) -> Result<Vec<PositionTemplate>, String> {
+        let mut stmt = self.conn
+            .prepare("SELECT id, name, fields, created_at FROM position_templates ORDER BY name, id")
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map([], row_to_template).map_err(|e| e.to_string())?;
+
+        let mut templates = Vec::new();
+        for row_result in rows {
+            templates.push(row_result.map_err(|e| e.to_string())?);
+        }
+        Ok(templates)
+    }
+
+    pub fn get_template(&self, id: i64) -> Result<Option<PositionTemplate>, String> {
+        self.conn
+            .query_row(
+                "SELECT id, name, fields, created_at FROM position_templates WHERE id = ?1",
+                [id],
+                row_to_template,
+            )
+            .optional()
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn save_template(&self, template: PositionTemplate) -> SqlResult<i64> {
+        if let Some(id) = template.id {
+            self.execute(
+                "UPDATE position_templates SET name = ?1, fields = ?2, created_at = ?3 WHERE id = ?4",
+                params![template.name.trim(), to_json(&template.fields)?, template.created_at.to_rfc3339(), id],
+            )?;
+            Ok(id)
+        } else {
+            self.execute(
+                "INSERT INTO position_templates (name, fields, created_at) VALUES (?1, ?2, ?3)",
+                params![template.name.trim(), to_json(&template.fields)?, template.created_at.to_rfc3339()],
+            )?;
+            Ok(self.conn.last_insert_rowid())
+        }
+    }
+
+    pub fn delete_template(&self, id: i64) -> SqlResult<()> {
+        self.execute("DELETE FROM position_templates WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    // Insurance policy operations
+    pub fn get_insurance_policies(&self) -> Result<Vec<InsurancePolicy>, String> {
+        let mut stmt = self.conn
+            .prepare(
+                "SELECT id, policy_type, provider, annual_premium, through_super, start_date, end_date
+                 FROM insurance_policies
+                 ORDER BY start_date, id"
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map([], row_to_insurance_policy).map_err(|e| e.to_string())?;
+
+        let mut policies = Vec::new();
+        for row_result in rows {
+            policies.push(row_result.map_err(|e| e.to_string())?);
+        }
+        Ok(policies)
+    }
+
+    pub fn save_insurance_policy(&self, policy: InsurancePolicy) -> SqlResult<i64> {
+        if let Some(id) = policy.id {
+            self.execute(
+                "UPDATE insurance_policies SET
+                    policy_type = ?1, provider = ?2, annual_premium = ?3, through_super = ?4,
+                    start_date = ?5, end_date = ?6
+                 WHERE id = ?7",
+                params![
+                    to_json(&policy.policy_type)?,
+                    policy.provider.trim(),
+                    policy.annual_premium,
+                    policy.through_super,
+                    policy.start_date.to_string(),
+                    policy.end_date.map(|d| d.to_string()),
+                    id
+                ],
+            )?;
+            Ok(id)
+        } else {
+            self.execute(
+                "INSERT INTO insurance_policies (policy_type, provider, annual_premium, through_super,
+                                                 start_date, end_date)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    to_json(&policy.policy_type)?,
+                    policy.provider.trim(),
+                    policy.annual_premium,
+                    policy.through_super,
+                    policy.start_date.to_string(),
+                    policy.end_date.map(|d| d.to_string())
+                ],
+            )?;
+            Ok(self.conn.last_insert_rowid())
+        }
+    }
+
+    pub fn delete_insurance_policy(&self, id: i64) -> SqlResult<()> {
+        self.execute("DELETE FROM insurance_policies WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    // Partner income operations
+    pub fn get_partner_income_entries(&self) -> Result<Vec<PartnerIncomeEntry>, String> {
+        let mut stmt = self.conn
+            .prepare(
+                "SELECT id, financial_year, gross_income, is_estimate
+                 FROM partner_income_entries
+                 ORDER BY financial_year, id"
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map([], row_to_partner_income).map_err(|e| e.to_string())?;
+
+        let mut entries = Vec::new();
+        for row_result in rows {
+            entries.push(row_result.map_err(|e| e.to_string())?);
+        }
+        Ok(entries)
+    }
+
+    pub fn save_partner_income_entry(&self, entry: PartnerIncomeEntry) -> SqlResult<i64> {
+        if let Some(id) = entry.id {
+            self.execute(
+                "UPDATE partner_income_entries SET financial_year = ?1, gross_income = ?2, is_estimate = ?3
+                 WHERE id = ?4",
+                params![entry.financial_year, entry.gross_income, entry.is_estimate, id],
+            )?;
+            Ok(id)
+        } else {
+            self.execute(
+                "INSERT INTO partner_income_entries (financial_year, gross_income, is_estimate)
+                 VALUES (?1, ?2, ?3)",
+                params![entry.financial_year, entry.gross_income, entry.is_estimate],
+            )?;
+            Ok(self.conn.last_insert_rowid())
+        }
+    }
+
+    pub fn delete_partner_income_entry(&self, id: i64) -> SqlResult<()> {
+        self.execute("DELETE FROM partner_income_entries WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    // Tag operations
+    pub fn get_tags(&self) -> Result<Vec<Tag>, String> {
+        let mut stmt = self.conn
+            .prepare("SELECT id, name FROM tags ORDER BY name COLLATE NOCASE, id")
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map([], |row| Ok(Tag { id: Some(row.get(0)?), name: row.get(1)? }))
+            .map_err(|e| e.to_string())?;
+
+        let mut tags = Vec::new();
+        for row_result in rows {
+            tags.push(row_result.map_err(|e| e.to_string())?);
+        }
+        Ok(tags)
+    }
+
+    /// Creates the tag, or renames it when it has an id.
+    pub fn save_tag(&self, tag: Tag) -> SqlResult<i64> {
+        let name = tag.name.trim();
+        if let Some(id) = tag.id {
+            self.execute("UPDATE tags SET name = ?1 WHERE id = ?2", params![name, id])?;
+            Ok(id)
+        } else {
+            self.execute("INSERT INTO tags (name) VALUES (?1)", params![name])?;
+            Ok(self.conn.last_insert_rowid())
+        }
+    }
+
+    /// Deletes the tag and takes it off everything it was on, together or not at all.
+    pub fn delete_tag(&self, id: i64) -> Result<(), String> {
+        self.in_transaction(|db| {
+            db.execute("DELETE FROM entity_tags WHERE tag_id = ?1", [id]).map_err(|e| e.to_string())?;
+            db.execute("DELETE FROM tags WHERE id = ?1", [id]).map_err(|e| e.to_string())?;
+            Ok(())
+        })
+    }
+
+    pub fn get_entity_tags(&self) -> Result<Vec<EntityTag>, String> {
+        let mut stmt = self.conn
+            .prepare("SELECT tag_id, entity_type, entity_id FROM entity_tags ORDER BY tag_id, entity_type, entity_id")
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map([], row_to_entity_tag).map_err(|e| e.to_string())?;
+
+        let mut entity_tags = Vec::new();
+        for row_result in rows {
+            entity_tags.push(row_result.map_err(|e| e.to_string())?);
+        }
+        Ok(entity_tags)
+    }
+
+    /// Puts the tag on the record; putting it on twice changes nothing.
+    pub fn assign_tag(&self, entity_tag: &EntityTag) -> SqlResult<()> {
+        self.execute(
+            "INSERT OR IGNORE INTO entity_tags (tag_id, entity_type, entity_id) VALUES (?1, ?2, ?3)",
+            params![entity_tag.tag_id, to_json(&entity_tag.entity_type)?, entity_tag.entity_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn unassign_tag(&self, entity_tag: &EntityTag) -> SqlResult<()> {
+        self.execute(
+            "DELETE FROM entity_tags WHERE tag_id = ?1 AND entity_type = ?2 AND entity_id = ?3",
+            params![entity_tag.tag_id, to_json(&entity_tag.entity_type)?, entity_tag.entity_id],
+        )?;
+        Ok(())
+    }
+
+    /// Whether the table has a row with this id. `table` must be one of ours, never user input.
+    pub fn row_exists(&self, table: &str, id: i64) -> Result<bool, String> {
+        self.conn
+            .query_row(&format!("SELECT 1 FROM {} WHERE id = ?1", table), [id], |_| Ok(()))
+            .optional()
+            .map(|row| row.is_some())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Gives a row a different id, so a restored row keeps the one other rows refer to it by.
+    pub fn renumber_row(&self, table: &str, from: i64, to: i64) -> SqlResult<()> {
+        self.execute(&format!("UPDATE {} SET id = ?1 WHERE id = ?2", table), [to, from])?;
+        Ok(())
+    }
+
+    /// Writes a consistent copy of the whole database to a new file, safe while the app is open.
+    pub fn backup_to(&self, path: &Path) -> SqlResult<()> {
+        self.conn.execute("VACUUM INTO ?1", [path.to_string_lossy()])?;
+        Ok(())
+    }
+
+    // Clear all data - for data backup/reset functionality
+    pub fn clear_all_data(&mut self) -> SqlResult<()> {
+        self.execute("DELETE FROM analysis_snapshots", [])?;
+        self.execute("DELETE FROM scenarios", [])?;
+        self.execute("DELETE FROM position_templates", [])?;
+        self.execute("DELETE FROM insurance_policies", [])?;
+        self.execute("DELETE FROM partner_income_entries", [])?;
+        self.execute("DELETE FROM entity_tags", [])?;
+        self.execute("DELETE FROM tags", [])?;
+        self.execute("DELETE FROM record_changes", [])?;
+        self.execute("DELETE FROM dismissed_reminders", [])?;
+        self.execute("DELETE FROM backfill_reviews", [])?;
+        self.execute("DELETE FROM exchange_rates", [])?;
+        self.execute("DELETE FROM yearly_income_entries", [])?;
+        self.execute("DELETE FROM weekly_entries", [])?;
+        self.execute("DELETE FROM compensation_records", [])?;
+        self.execute("DELETE FROM award_rates", [])?;
+        self.execute("DELETE FROM positions", [])?;
+        self.execute("DELETE FROM user_profile", [])?;
+        Ok(())
+    }
+}
+
@@ -0,0 +1,161 @@
+use crate::models::{Jurisdiction, PayslipFrequency};
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// An Australian financial year, running 1 July to 30 June.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FinancialYear {
+    pub start_year: i32,
+}
+
+impl FinancialYear {
+    /// Parses labels like "FY2024-25", "2024-25" or "FY2024" (matching the frontend's getFinancialYearBounds).
+    pub fn parse(label: &str) -> Result<Self, String> {
+        let trimmed = label.trim();
+        let without_prefix = trimmed
+            .strip_prefix("FY")
+            .or_else(|| trimmed.strip_prefix("fy"))
+            .unwrap_or(trimmed);
+        let year_digits: String = without_prefix.chars().take(4).collect();
+
+        match year_digits.parse::<i32>() {
+            Ok(start_year) if year_digits.len() == 4 => Ok(Self { start_year }),
+            _ => Err(format!("Invalid financial year '{}', expected a label like FY2024-25", label)),
+        }
+    }
+
+    pub fn containing(date: NaiveDate) -> Self {
+        let start_year = if date.month() >= 7 { date.year() } else { date.year() - 1 };
+        Self { start_year }
+    }
+
+    pub fn start(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.start_year, 7, 1).unwrap_or(NaiveDate::MIN)
+    }
+
+    pub fn end(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.start_year + 1, 6, 30).unwrap_or(NaiveDate::MAX)
+    }
+
+    /// First day of the tax year in the jurisdiction: 1 July in Australia, 1 April in New Zealand.
+    pub fn tax_year_start(&self, jurisdiction: &Jurisdiction) -> NaiveDate {
+        match jurisdiction {
+            Jurisdiction::AU => self.start(),
+            Jurisdiction::NZ => NaiveDate::from_ymd_opt(self.start_year, 4, 1).unwrap_or(NaiveDate::MIN),
+        }
+    }
+
+    pub fn tax_year_end(&self, jurisdiction: &Jurisdiction) -> NaiveDate {
+        match jurisdiction {
+            Jurisdiction::AU => self.end(),
+            Jurisdiction::NZ => NaiveDate::from_ymd_opt(self.start_year + 1, 3, 31).unwrap_or(NaiveDate::MAX),
+        }
+    }
+
+    /// The jurisdiction's tax year containing the date.
+    pub fn containing_for(date: NaiveDate, jurisdiction: &Jurisdiction) -> Self {
+        match jurisdiction {
+            Jurisdiction::AU => Self::containing(date),
+            Jurisdiction::NZ => Self { start_year: if date.month() >= 4 { date.year() } else { date.year() - 1 } },
+        }
+    }
+
+    /// Formats as "FY2024-25", the label stored on weekly and yearly entries.
+    pub fn label(&self) -> String {
+        format!("FY{}-{:02}", self.start_year, (self.start_year + 1) % 100)
+    }
+}
+
+/// How per-year figures are bucketed. Financial years match the FY summaries and are the
+/// default; calendar years are for comparing against things reported by calendar year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum YearBasis {
+    Calendar,
+    #[default]
+    Financial,
+}
+
+impl YearBasis {
+    /// The year containing the date: "2024-25" for the financial year, "2024" for the calendar year.
+    /// Labels sort in date order.
+    pub fn label(&self, date: NaiveDate) -> String {
+        match self {
+            YearBasis::Calendar => date.year().to_string(),
+            YearBasis::Financial => {
+                let fy = FinancialYear::containing(date);
+                format!("{}-{:02}", fy.start_year, (fy.start_year + 1) % 100)
+            }
+        }
+    }
+}
+
+/// Number of weeks ending on `week_ending_day` that fall within the financial year.
+///
+/// A financial year has 365 or 366 days, so one or two weekdays occur 53 times
+/// (e.g. weekly payers with a Wednesday or Thursday week ending in FY2015-16).
+pub fn weeks_in_financial_year(fy: &FinancialYear, week_ending_day: Weekday) -> u32 {
+    let days_to_first = (7 + week_ending_day.num_days_from_monday() as i64
+        - fy.start().weekday().num_days_from_monday() as i64) % 7;
+    let first = fy.start() + Duration::days(days_to_first);
+    ((fy.end() - first).num_days() / 7 + 1) as u32
+}
+
+/// The date on `day` closest to `date`, at most three days either side.
+pub fn nearest_weekday(date: NaiveDate, day: Weekday) -> NaiveDate {
+    let forward = (day.num_days_from_monday() as i64 - date.weekday().num_days_from_monday() as i64).rem_euclid(7);
+    let shift = if forward > 3 { forward - 7 } else { forward };
+    date + Duration::days(shift)
+}
+
+/// Full English name of the day, e.g. "Tuesday".
+pub fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+/// Number of pay periods whose end date falls within the financial year, given any known
+/// period end on the same cadence. Weekly gives 52/53, fortnightly 26/27, monthly 12.
+pub fn pay_periods_in_fy(fy: &FinancialYear, frequency: &PayslipFrequency, anchor_period_end: NaiveDate) -> u32 {
+    let cycle_days = match frequency {
+        PayslipFrequency::Weekly => return weeks_in_financial_year(fy, anchor_period_end.weekday()),
+        PayslipFrequency::Fortnightly => 14,
+        PayslipFrequency::Monthly => return 12,
+    };
+
+    let offset = (anchor_period_end - fy.start()).num_days().rem_euclid(cycle_days);
+    let first = fy.start() + Duration::days(offset);
+    ((fy.end() - first).num_days() / cycle_days + 1) as u32
+}
+
+/// Age in whole years on `as_of`, accounting for whether the birthday has occurred yet that year.
+pub fn age_on(dob: NaiveDate, as_of: NaiveDate) -> i32 {
+    let mut age = as_of.year() - dob.year();
+    if (as_of.month(), as_of.day()) < (dob.month(), dob.day()) {
+        age -= 1;
+    }
+    age
+}
+
+/// First day of a pay period, derived from its last day and the pay frequency.
+pub fn period_start_for(period_end: NaiveDate, period_type: &PayslipFrequency) -> NaiveDate {
+    match period_type {
+        PayslipFrequency::Weekly => period_end - Duration::days(6),
+        PayslipFrequency::Fortnightly => period_end - Duration::days(13),
+        PayslipFrequency::Monthly => period_end
+            .checked_sub_months(Months::new(1))
+            .map(|d| d + Duration::days(1))
+            .unwrap_or(period_end - Duration::days(30)),
+    }
+}
+
+/// Inclusive length of a pay period in days.
+pub fn period_days(period_start: NaiveDate, period_end: NaiveDate) -> i64 {
+    (period_end - period_start).num_days() + 1
+}
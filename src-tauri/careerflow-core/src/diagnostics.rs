@@ -0,0 +1,148 @@
+use crate::anonymize;
+use crate::backup;
+use crate::calculations;
+use crate::database::Database;
+use crate::lsl;
+use crate::models::*;
+use chrono::{Datelike, NaiveDate};
+use std::collections::{BTreeMap, HashMap};
+
+// Settings that change analysis results; nothing else goes into a bundle
+const SETTINGS_KEYS: &[&str] = &[
+    lsl::SETTINGS_KEY,
+    calculations::PROJECTION_SETTINGS_KEY,
+    calculations::WAGE_INDEX_SETTINGS_KEY,
+    calculations::DEMOGRAPHIC_SETTINGS_KEY,
+];
+
+/// The analysis inputs with identity removed by [`anonymize::anonymize`], the settings in
+/// effect, and the loyalty tax and earnings analysis.
+///
+/// The outputs are computed from the anonymised inputs rather than the originals, so loading
+/// the bundle with [`import_bundle`] reproduces them exactly. Positions lose their industry,
+/// pay-period and contract details, and their location is kept only as the state.
+pub fn export_bundle(db: &Database, app_version: &str, today: NaiveDate) -> Result<DiagnosticBundle, String> {
+    let data = backup::export_all_data(db)?;
+    let anonymized = anonymize::anonymize(&data, today)?;
+
+    let mut settings = BTreeMap::new();
+    for key in SETTINGS_KEYS {
+        if let Some(value) = db.get_setting::<Option<serde_json::Value>>(key)? {
+            settings.insert(key.to_string(), value);
+        }
+    }
+
+    let profile = restore_profile(&anonymized);
+    let positions = restore_positions(&anonymized);
+    Ok(DiagnosticBundle {
+        app_version: app_version.to_string(),
+        loyalty_tax: calculations::calculate_loyalty_tax(&positions, today),
+        earnings_analysis: calculations::calculate_earnings_analysis(
+            &positions,
+            &anonymized.compensation_records,
+            &Some(profile),
+            &data.exchange_rates,
+            today,
+        ),
+        exchange_rates: data.exchange_rates,
+        settings,
+        data: anonymized,
+    })
+}
+
+/// Loads a bundle into an empty database, normally an in-memory one, to reproduce a report.
+///
+/// Everything is saved as new rows; records and entries follow their position to its new id.
+pub fn import_bundle(db: &Database, bundle: &DiagnosticBundle) -> Result<(), String> {
+    db.save_user_profile(restore_profile(&bundle.data)).map_err(|e| e.to_string())?;
+
+    let mut position_ids = HashMap::new();
+    for position in restore_positions(&bundle.data) {
+        let old_id = position.id;
+        let new_id = db.save_position(Position { id: None, ..position }).map_err(|e| e.to_string())?;
+        if let Some(old_id) = old_id {
+            position_ids.insert(old_id, new_id);
+        }
+    }
+
+    for record in &bundle.data.compensation_records {
+        let Some(&position_id) = position_ids.get(&record.position_id) else {
+            continue;
+        };
+        db.save_compensation_record(CompensationRecord { id: None, position_id, ..record.clone() })
+            .map_err(|e| e.to_string())?;
+    }
+    for entry in &bundle.data.weekly_entries {
+        let position_id = entry.position_id.and_then(|id| position_ids.get(&id).copied());
+        db.save_weekly_entry(WeeklyCompensationEntry { id: None, position_id, ..entry.clone() })
+            .map_err(|e| e.to_string())?;
+    }
+    for entry in &bundle.data.yearly_entries {
+        let position_id = entry.position_id.and_then(|id| position_ids.get(&id).copied());
+        db.save_yearly_entry(YearlyIncomeEntry { id: None, position_id, ..entry.clone() })
+            .map_err(|e| e.to_string())?;
+    }
+    for rate in &bundle.exchange_rates {
+        db.save_exchange_rate(ExchangeRate { id: None, ..rate.clone() }).map_err(|e| e.to_string())?;
+    }
+    for (key, value) in &bundle.settings {
+        db.save_setting(key, value).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// A profile of the same age band, state, industry and hours, with placeholder identity
+fn restore_profile(data: &AnonymizedExport) -> UserProfile {
+    let exported = data.export_date.date_naive();
+    let birth_year = exported.year() - anonymize::representative_age(&data.profile.age_band);
+    UserProfile {
+        id: None,
+        first_name: "Diagnostic".to_string(),
+        last_name: "Bundle".to_string(),
+        date_of_birth: exported.with_year(birth_year).unwrap_or(exported),
+        state: data.profile.state,
+        industry: data.profile.industry.clone(),
+        highest_qualification: data.profile.highest_qualification.clone(),
+        career_preferences: CareerPreferences {
+            employment_type_preference: EmploymentType::Permanent,
+            fifo_tolerance: FIFOTolerance::Limited,
+            travel_tolerance: TravelTolerance::Regional,
+            overtime_appetite: OvertimeAppetite::Moderate,
+            privacy_acknowledged: true,
+            disclaimer_acknowledged: true,
+        },
+        standard_weekly_hours: data.profile.standard_weekly_hours,
+        has_help_debt: false,
+        total_super_balance: None,
+        gender: Gender::PreferNotToSay,
+        created_at: data.export_date,
+        updated_at: data.export_date,
+    }
+}
+
+fn restore_positions(data: &AnonymizedExport) -> Vec<Position> {
+    data.positions.iter()
+        .map(|position| Position {
+            id: position.id,
+            employer_name: position.employer.clone(),
+            job_title: position.job_title.clone(),
+            employment_type: position.employment_type.clone(),
+            location: position.state.map(|state| format!("{:?}", state)).unwrap_or_default(),
+            start_date: position.start_date,
+            end_date: position.end_date,
+            seniority_level: position.seniority_level.clone(),
+            core_responsibilities: String::new(),
+            tools_systems_skills: position.tools_systems_skills.clone(),
+            achievements: Vec::new(),
+            industry: None,
+            week_ending_day: None,
+            notice_period_weeks: None,
+            leave_loading_percent: None,
+            probation_months: None,
+            commute_minutes_per_day: None,
+            archived: position.archived,
+            created_at: data.export_date,
+            updated_at: data.export_date,
+        })
+        .collect()
+}
@@ -0,0 +1,36 @@
+use std::fmt;
+
+/// Failures the user can act on, shown as they are rather than as a storage error.
+///
+/// Commands return `Result<_, String>`, so these convert into their message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppError {
+    /// Another CareerFlow window has the database open for writing.
+    ReadOnly,
+    /// An analysis needs more history than has been entered.
+    NotEnoughData { needed_months: u32, have_months: u32 },
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::ReadOnly => write!(
+                f,
+                "CareerFlow is open in another window, so this one is read-only. Close the other window to make changes here."
+            ),
+            AppError::NotEnoughData { needed_months, have_months } => write!(
+                f,
+                "This needs at least {} months of pay entries and there are {} so far.",
+                needed_months, have_months
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<AppError> for String {
+    fn from(error: AppError) -> Self {
+        error.to_string()
+    }
+}
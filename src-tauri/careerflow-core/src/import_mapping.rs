@@ -0,0 +1,451 @@
+use crate::coercion::{self, Coercion};
+use crate::database::Database;
+use crate::dates::FinancialYear;
+use crate::models::*;
+use crate::validation;
+use chrono::{NaiveDate, Utc};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+pub const SETTINGS_KEY: &str = "import_mappings";
+
+// On compensation records and weekly entries this isn't stored: it's resolved to a
+// position_id by matching existing positions
+const EMPLOYER_FIELD: &str = "employer_name";
+
+const EMPLOYMENT_TYPES: &[&str] = &["Permanent", "Contract", "Casual"];
+const SENIORITY_LEVELS: &[&str] = &["Entry", "Junior", "Mid", "Senior", "Lead", "Manager", "Director", "Executive"];
+const ENTRY_TYPES: &[&str] = &["Fuzzy", "Exact"];
+const PAY_TYPES: &[&str] = &["Salary", "Hourly"];
+const OVERTIME_FREQUENCIES: &[&str] = &["None", "Occasional", "Frequent", "Extreme"];
+const PAYSLIP_FREQUENCIES: &[&str] = &["Weekly", "Fortnightly", "Monthly"];
+const JURISDICTIONS: &[&str] = &["AU", "NZ"];
+
+/// A field that can be mapped, its usual coercion and, for enums, the allowed values.
+struct FieldSpec {
+    path: &'static str,
+    coercion: Coercion,
+    choices: Option<&'static [&'static str]>,
+}
+
+const fn field(path: &'static str, coercion: Coercion) -> FieldSpec {
+    FieldSpec { path, coercion, choices: None }
+}
+
+const fn choice(path: &'static str, choices: &'static [&'static str]) -> FieldSpec {
+    FieldSpec { path, coercion: Coercion::Text, choices: Some(choices) }
+}
+
+const POSITION_FIELDS: &[FieldSpec] = &[
+    field("employer_name", Coercion::Text),
+    field("job_title", Coercion::Text),
+    choice("employment_type", EMPLOYMENT_TYPES),
+    field("location", Coercion::Text),
+    field("start_date", Coercion::Date),
+    field("end_date", Coercion::Date),
+    choice("seniority_level", SENIORITY_LEVELS),
+    field("core_responsibilities", Coercion::Text),
+    field("tools_systems_skills", Coercion::List),
+    field("achievements", Coercion::List),
+];
+
+const COMPENSATION_FIELDS: &[FieldSpec] = &[
+    field("position_id", Coercion::Integer),
+    field(EMPLOYER_FIELD, Coercion::Text),
+    choice("entry_type", ENTRY_TYPES),
+    choice("pay_type", PAY_TYPES),
+    field("base_rate", Coercion::Money),
+    field("standard_weekly_hours", Coercion::Hours),
+    choice("overtime.frequency", OVERTIME_FREQUENCIES),
+    field("overtime.rate_multiplier", Coercion::Number),
+    field("overtime.average_hours_per_week", Coercion::Hours),
+    field("overtime.annual_hours", Coercion::Hours),
+    field("super_contributions.contribution_rate", Coercion::Percent),
+    field("super_contributions.additional_contributions", Coercion::Money),
+    field("super_contributions.salary_sacrifice", Coercion::Money),
+    field("tax_withheld", Coercion::Money),
+    choice("payslip_frequency", PAYSLIP_FREQUENCIES),
+    field("effective_date", Coercion::Date),
+    field("confidence_score", Coercion::Number),
+    field("currency", Coercion::Text),
+    choice("jurisdiction", JURISDICTIONS),
+    field("notes", Coercion::Text),
+];
+
+const WEEKLY_FIELDS: &[FieldSpec] = &[
+    field("position_id", Coercion::Integer),
+    field(EMPLOYER_FIELD, Coercion::Text),
+    field("week_ending", Coercion::Date),
+    field("period_start", Coercion::Date),
+    choice("period_type", PAYSLIP_FREQUENCIES),
+    field("gross_pay", Coercion::Money),
+    field("tax_withheld", Coercion::Money),
+    field("net_pay", Coercion::Money),
+    field("hours_ordinary", Coercion::Hours),
+    field("hours_overtime", Coercion::Hours),
+    field("overtime_rate_multiplier", Coercion::Number),
+    field("super_contributed", Coercion::Money),
+    field("currency", Coercion::Text),
+    field("notes", Coercion::Text),
+];
+
+fn fields_for(target: ImportTarget) -> &'static [FieldSpec] {
+    match target {
+        ImportTarget::Positions => POSITION_FIELDS,
+        ImportTarget::CompensationRecords => COMPENSATION_FIELDS,
+        ImportTarget::WeeklyEntries => WEEKLY_FIELDS,
+    }
+}
+
+/// Fields every row must end up with, from a column or a default. Compensation records and
+/// weekly entries also need a position, by id or employer name.
+pub fn required_fields(target: ImportTarget) -> &'static [&'static str] {
+    match target {
+        ImportTarget::Positions => &["employer_name", "job_title", "start_date"],
+        ImportTarget::CompensationRecords => &["base_rate", "effective_date"],
+        ImportTarget::WeeklyEntries => &["week_ending", "gross_pay"],
+    }
+}
+
+fn is_required(target: ImportTarget, field: &str) -> bool {
+    required_fields(target).contains(&field)
+}
+
+/// Names of the fields a mapping for the target may use.
+pub fn field_names(target: ImportTarget) -> Vec<&'static str> {
+    fields_for(target).iter().map(|f| f.path).collect()
+}
+
+/// Mappings shipped with the app for common spreadsheet layouts.
+pub fn presets() -> Vec<ImportMapping> {
+    let column = |source: &str, field: &str, default_value: Option<&str>| ColumnMapping {
+        source: source.to_string(),
+        field: field.to_string(),
+        coercion: None,
+        default_value: default_value.map(str::to_string),
+    };
+
+    vec![
+        ImportMapping {
+            name: "Spreadsheet job history".to_string(),
+            target: ImportTarget::Positions,
+            columns: vec![
+                column("Company", "employer_name", None),
+                column("Title", "job_title", None),
+                column("Type", "employment_type", Some("Permanent")),
+                column("Location", "location", None),
+                column("Start", "start_date", None),
+                column("End", "end_date", None),
+                column("Level", "seniority_level", Some("Mid")),
+                column("Description", "core_responsibilities", None),
+                column("Skills", "tools_systems_skills", None),
+            ],
+            builtin: true,
+        },
+        ImportMapping {
+            name: "Spreadsheet salary history".to_string(),
+            target: ImportTarget::CompensationRecords,
+            columns: vec![
+                column("Company", EMPLOYER_FIELD, None),
+                column("Date", "effective_date", None),
+                column("Salary", "base_rate", None),
+                column("Hours", "standard_weekly_hours", Some("38")),
+                column("Super", "super_contributions.contribution_rate", None),
+                column("Notes", "notes", None),
+            ],
+            builtin: true,
+        },
+        ImportMapping {
+            name: "Payroll export".to_string(),
+            target: ImportTarget::WeeklyEntries,
+            columns: vec![
+                column("Employer", EMPLOYER_FIELD, None),
+                column("Period Ending", "week_ending", None),
+                column("Pay Frequency", "period_type", Some("Weekly")),
+                column("Gross", "gross_pay", None),
+                column("Tax", "tax_withheld", Some("0")),
+                column("Net", "net_pay", None),
+                column("Ordinary Hours", "hours_ordinary", Some("0")),
+                column("Overtime Hours", "hours_overtime", Some("0")),
+                column("Super", "super_contributed", Some("0")),
+            ],
+            builtin: true,
+        },
+    ]
+}
+
+/// Maps every row of the file onto the target, collecting an error for each cell that
+/// can't be converted. Unless `dry_run`, the records are saved together when no row has errors.
+pub fn import_with_mapping(db: &Database, csv_text: &str, mapping: &ImportMapping, dry_run: bool) -> Result<MappedImportResult, String> {
+    validation::validate_import_mapping(mapping)?;
+    let positions = db.get_positions()?;
+
+    let mut result = map_rows(csv_text, mapping, &positions)?;
+    result.dry_run = dry_run;
+    if dry_run || !result.errors.is_empty() {
+        return Ok(result);
+    }
+
+    // All or nothing, so a failed save doesn't leave half the file imported
+    db.in_transaction(|db| {
+        for position in &result.positions {
+            db.save_position(position.clone()).map_err(|e| e.to_string())?;
+        }
+        for record in &result.compensation_records {
+            db.save_compensation_record(record.clone()).map_err(|e| e.to_string())?;
+        }
+        for entry in &result.weekly_entries {
+            db.save_weekly_entry(entry.clone()).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    })?;
+    result.imported_count = result.positions.len() + result.compensation_records.len() + result.weekly_entries.len();
+
+    Ok(result)
+}
+
+/// Converts the file without touching the database. `positions` are used to resolve
+/// employer names on compensation records and weekly entries.
+pub fn map_rows(csv_text: &str, mapping: &ImportMapping, positions: &[Position]) -> Result<MappedImportResult, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(csv_text.as_bytes());
+
+    let headers: Vec<String> = reader.headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?
+        .iter()
+        .map(|h| h.to_lowercase())
+        .collect();
+
+    // Optional columns may be absent, but a file without a required one can't be imported
+    let missing: Vec<&str> = mapping.columns.iter()
+        .filter(|c| is_required(mapping.target, &c.field) && c.default_value.is_none())
+        .filter(|c| !headers.contains(&c.source.trim().to_lowercase()))
+        .map(|c| c.source.as_str())
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("The file has no column named {}", missing.join(", ")));
+    }
+
+    let mut result = MappedImportResult {
+        target: mapping.target,
+        dry_run: true,
+        rows: 0,
+        positions: Vec::new(),
+        compensation_records: Vec::new(),
+        weekly_entries: Vec::new(),
+        errors: Vec::new(),
+        imported_count: 0,
+    };
+
+    for (index, record) in reader.records().enumerate() {
+        // Line 1 is the header
+        let line = index + 2;
+        result.rows += 1;
+
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                result.errors.push(row_error(line, e.to_string()));
+                continue;
+            }
+        };
+        let row: HashMap<&str, &str> = headers.iter().map(String::as_str).zip(record.iter()).collect();
+
+        let mut errors = Vec::new();
+        let values = map_cells(line, &row, mapping, &mut errors);
+        if errors.is_empty() {
+            if let Err(message) = build_record(values, mapping.target, positions, &mut result) {
+                errors.push(row_error(line, message));
+            }
+        }
+        result.errors.extend(errors);
+    }
+
+    Ok(result)
+}
+
+// Coerces each mapped cell, returning the values by field path
+fn map_cells(line: usize, row: &HashMap<&str, &str>, mapping: &ImportMapping, errors: &mut Vec<CellError>) -> HashMap<String, Value> {
+    let fields = fields_for(mapping.target);
+    let mut values = HashMap::new();
+
+    for column in &mapping.columns {
+        let cell = row.get(column.source.trim().to_lowercase().as_str()).copied().unwrap_or("");
+        let raw = match (cell.is_empty(), &column.default_value) {
+            (false, _) => cell,
+            (true, Some(default)) => default.as_str(),
+            (true, None) => continue,
+        };
+        let Some(spec) = fields.iter().find(|f| f.path == column.field) else {
+            continue;
+        };
+
+        let value = coercion::coerce(raw, column.coercion.unwrap_or(spec.coercion))
+            .and_then(|value| match (spec.choices, &value) {
+                (Some(choices), Value::String(text)) => coercion::coerce_choice(text, choices).map(Value::String),
+                _ => Ok(value),
+            });
+        match value {
+            Ok(value) => {
+                values.insert(column.field.clone(), value);
+            }
+            Err(message) => errors.push(CellError {
+                line,
+                column: Some(column.source.clone()),
+                value: Some(raw.to_string()),
+                message,
+            }),
+        }
+    }
+
+    // Fields whose cell failed to convert already have an error
+    for required in required_fields(mapping.target) {
+        let column = mapping.columns.iter().find(|c| c.field == *required).map(|c| c.source.clone());
+        let already_reported = errors.iter().any(|e| e.column.is_some() && e.column == column);
+        if !values.contains_key(*required) && !already_reported {
+            errors.push(CellError { line, column, value: None, message: format!("{} is required", required) });
+        }
+    }
+
+    values
+}
+
+fn build_record(
+    mut values: HashMap<String, Value>,
+    target: ImportTarget,
+    positions: &[Position],
+    result: &mut MappedImportResult,
+) -> Result<(), String> {
+    let now = Utc::now();
+
+    match target {
+        ImportTarget::Positions => {
+            let mut record = json!({
+                "id": null,
+                "employer_name": "",
+                "job_title": "",
+                "employment_type": "Permanent",
+                "location": "",
+                "end_date": null,
+                "seniority_level": "Mid",
+                "core_responsibilities": "",
+                "tools_systems_skills": [],
+                "achievements": [],
+                "created_at": now,
+                "updated_at": now,
+            });
+            apply(&mut record, values);
+            let position: Position = serde_json::from_value(record).map_err(|e| e.to_string())?;
+            if position.end_date.map(|end| end < position.start_date).unwrap_or(false) {
+                return Err("End date is before the start date".to_string());
+            }
+            result.positions.push(position);
+        }
+        ImportTarget::CompensationRecords => {
+            let on = date_value(&values, "effective_date");
+            let position_id = resolve_position(&mut values, positions, on)?;
+            let mut record = json!({
+                "id": null,
+                "position_id": position_id,
+                "entry_type": "Exact",
+                "pay_type": "Salary",
+                "standard_weekly_hours": 38.0,
+                "overtime": { "frequency": "None", "rate_multiplier": 1.5, "average_hours_per_week": 0.0, "annual_hours": null },
+                "allowances": [],
+                "bonuses": [],
+                "super_contributions": { "contribution_rate": 0.0, "additional_contributions": 0.0, "salary_sacrifice": 0.0 },
+                "tax_withheld": null,
+                "payslip_frequency": null,
+                "confidence_score": 100.0,
+                "notes": null,
+                "created_at": now,
+            });
+            apply(&mut record, values);
+            let record: CompensationRecord = serde_json::from_value(record).map_err(|e| e.to_string())?;
+            validation::validate_compensation_record(&record)?;
+            result.compensation_records.push(record);
+        }
+        ImportTarget::WeeklyEntries => {
+            let on = date_value(&values, "week_ending");
+            let position_id = resolve_position(&mut values, positions, on)?;
+            let financial_year = on.map(|d| FinancialYear::containing(d).label()).unwrap_or_default();
+            let net_pay_mapped = values.contains_key("net_pay");
+            let mut record = json!({
+                "id": null,
+                "position_id": position_id,
+                "financial_year": financial_year,
+                "tax_withheld": 0.0,
+                "net_pay": 0.0,
+                "hours_ordinary": 0.0,
+                "hours_overtime": 0.0,
+                "overtime_rate_multiplier": 1.5,
+                "allowances": [],
+                "super_contributed": 0.0,
+                "notes": null,
+                "created_at": now,
+            });
+            apply(&mut record, values);
+            let mut entry: WeeklyCompensationEntry = serde_json::from_value(record).map_err(|e| e.to_string())?;
+            if !net_pay_mapped {
+                entry.net_pay = entry.gross_pay - entry.tax_withheld;
+            }
+            validation::validate_weekly_entry(&entry)?;
+            result.weekly_entries.push(entry);
+        }
+    }
+
+    Ok(())
+}
+
+// Writes each value at its dotted path
+fn apply(record: &mut Value, values: HashMap<String, Value>) {
+    for (path, value) in values {
+        let mut target = &mut *record;
+        let mut parts = path.split('.').peekable();
+        while let Some(part) = parts.next() {
+            let Value::Object(object) = target else { break };
+            if parts.peek().is_none() {
+                object.insert(part.to_string(), value);
+                break;
+            }
+            target = object.entry(part.to_string()).or_insert_with(|| Value::Object(Map::new()));
+        }
+    }
+}
+
+fn date_value(values: &HashMap<String, Value>, field: &str) -> Option<NaiveDate> {
+    values.get(field).and_then(|v| v.as_str()).and_then(|d| d.parse().ok())
+}
+
+// The position for a row: a mapped id, else the position with the employer name that was
+// held on the row's date, else the most recent one with that name
+fn resolve_position(values: &mut HashMap<String, Value>, positions: &[Position], on: Option<NaiveDate>) -> Result<Value, String> {
+    let employer = values.remove(EMPLOYER_FIELD);
+    if let Some(id) = values.remove("position_id") {
+        return Ok(id);
+    }
+    let Some(employer) = employer.as_ref().and_then(|e| e.as_str()) else {
+        return Err("A position_id or employer_name column is required".to_string());
+    };
+
+    let matching: Vec<&Position> = positions.iter()
+        .filter(|p| p.employer_name.trim().eq_ignore_ascii_case(employer.trim()))
+        .collect();
+    let held_on = |p: &&&Position| match on {
+        Some(date) => p.start_date <= date && p.end_date.map(|end| date <= end).unwrap_or(true),
+        None => false,
+    };
+
+    matching.iter()
+        .find(held_on)
+        .or_else(|| matching.iter().max_by_key(|p| p.start_date))
+        .and_then(|p| p.id)
+        .map(Value::from)
+        .ok_or_else(|| format!("No position found for employer '{}'", employer))
+}
+
+fn row_error(line: usize, message: String) -> CellError {
+    CellError { line, column: None, value: None, message }
+}
@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+/// Shares the result of a computation that is already running with callers asking for the same key.
+///
+/// Nothing is kept once the computation finishes, so a caller arriving afterwards computes afresh.
+/// Keys should include whatever the result depends on, such as `Database::data_version`.
+pub struct InFlight<V> {
+    running: Mutex<HashMap<String, Arc<OnceCell<V>>>>,
+}
+
+impl<V: Clone> InFlight<V> {
+    pub fn new() -> Self {
+        InFlight { running: Mutex::new(HashMap::new()) }
+    }
+
+    /// Runs `compute` unless a call with the same key is running, in which case awaits its result.
+    ///
+    /// Waiting callers yield to the runtime rather than holding a worker thread.
+    pub async fn run<F, Fut>(&self, key: &str, compute: F) -> V
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V>,
+    {
+        let cell = self.running
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(key.to_string())
+            .or_default()
+            .clone();
+
+        let value = cell.get_or_init(compute).await.clone();
+
+        let mut running = self.running.lock().unwrap_or_else(|e| e.into_inner());
+        if running.get(key).is_some_and(|current| Arc::ptr_eq(current, &cell)) {
+            running.remove(key);
+        }
+        value
+    }
+}
+
+impl<V: Clone> Default for InFlight<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
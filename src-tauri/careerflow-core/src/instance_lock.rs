@@ -0,0 +1,101 @@
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Marks the database as held by this process. The lock file holds the process id and is
+/// removed when the lock is dropped; one left behind by a crash is recognised because its
+/// process is no longer running.
+#[derive(Debug)]
+pub struct InstanceLock {
+    path: PathBuf,
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// The lock file kept next to the database, e.g. `careerflow.db.lock`.
+pub fn lock_path(db_path: &Path) -> PathBuf {
+    let mut name = db_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".lock");
+    db_path.with_file_name(name)
+}
+
+/// Takes the lock for the database, or returns `None` when a running process already has it.
+pub fn acquire(db_path: &Path) -> Result<Option<InstanceLock>, String> {
+    let path = lock_path(db_path);
+
+    // A stale lock is removed once; if another instance takes it in between, it wins
+    for _ in 0..2 {
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                writeln!(file, "{}", std::process::id())
+                    .map_err(|e| format!("Failed to write lock file {}: {}", path.display(), e))?;
+                return Ok(Some(InstanceLock { path }));
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                let contents = match fs::read_to_string(&path) {
+                    Ok(contents) => contents,
+                    // Released between the two calls
+                    Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                    Err(e) => return Err(format!("Failed to read lock file {}: {}", path.display(), e)),
+                };
+                if let Some(pid) = holder(&contents) {
+                    if process_is_running(pid) {
+                        return Ok(None);
+                    }
+                }
+                // Only remove the lock that was judged stale, not one written since
+                if fs::read_to_string(&path).ok().as_deref() == Some(contents.as_str()) {
+                    fs::remove_file(&path)
+                        .map_err(|e| format!("Failed to remove stale lock file {}: {}", path.display(), e))?;
+                }
+            }
+            Err(e) => return Err(format!("Failed to create lock file {}: {}", path.display(), e)),
+        }
+    }
+    Ok(None)
+}
+
+/// The process id recorded in a lock file, `None` if it cannot be read (a partial write).
+fn holder(contents: &str) -> Option<u32> {
+    contents.lines().next()?.trim().parse().ok()
+}
+
+/// Whether a process with the id is running. When the check itself fails the process is
+/// assumed to be running, so the worst case is opening read-only.
+#[cfg(unix)]
+pub fn process_is_running(pid: u32) -> bool {
+    if Path::new("/proc/self").exists() {
+        return Path::new(&format!("/proc/{}", pid)).exists();
+    }
+    Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(true)
+}
+
+#[cfg(windows)]
+pub fn process_is_running(pid: u32) -> bool {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+        .stderr(Stdio::null())
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).contains(&format!("\"{}\"", pid)))
+        .unwrap_or(true)
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn process_is_running(_pid: u32) -> bool {
+    true
+}
@@ -0,0 +1,83 @@
+//! Core of CareerFlow: the data model, SQLite storage and the earnings calculations.
+//!
+//! The desktop app is a thin Tauri shell over this crate, and anything else (scripts, a CLI,
+//! tests) can use the same API against a database file:
+//!
+//! ```no_run
+//! use careerflow_core::{calculations, Database};
+//!
+//! let db = Database::new("careerflow.db".into())?;
+//! let positions = db.get_positions()?;
+//! let records = db.get_all_compensation_records()?;
+//! let profile = db.get_user_profile()?;
+//! let rates = db.get_exchange_rates()?;
+//! let today = chrono::Local::now().date_naive();
+//! let analysis = calculations::calculate_earnings_analysis(&positions, &records, &profile, &rates, today);
+//! println!("{:.0}", analysis.current_total_compensation);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+/// Exports with identifying details removed.
+pub mod anonymize;
+/// Scheduled JSON exports to a user-chosen folder.
+pub mod auto_export;
+/// Whole-database export and import.
+pub mod backup;
+/// Moving and editing many records at once.
+pub mod bulk_edit;
+/// Earnings, loyalty tax, income composition and financial year calculations.
+pub mod calculations;
+/// Turning text cells from imported files into typed values.
+pub mod coercion;
+/// Copying compensation records forward for a raise.
+pub mod compensation_history;
+/// Relative living costs by location, for comparing pay across places.
+pub mod cost_of_living;
+/// Currency codes and conversion to AUD.
+pub mod currency;
+/// Parsing payslip CSVs into weekly entries.
+pub mod csv_import;
+/// Anonymised bundles of analysis inputs and outputs for bug reports.
+pub mod diagnostics;
+/// SQLite storage and schema migrations.
+pub mod database;
+/// Financial years, pay periods and ages.
+pub mod dates;
+/// Errors shown to the user as they are.
+pub mod error;
+/// Importing other tools' CSV layouts through a column mapping.
+pub mod import_mapping;
+/// Sharing one run of an expensive calculation between callers that ask at the same time.
+pub mod in_flight;
+/// Keeping a second running instance of the app from writing to the same database.
+pub mod instance_lock;
+/// Long service leave rules by state.
+pub mod lsl;
+/// Dates coming up for positions, such as probation and contract ends.
+pub mod reminders;
+/// The start-of-year checklist: placeholders for the new financial year and checks on the last.
+pub mod rollover;
+/// Records stored by the app and results returned by the calculations.
+pub mod models;
+/// Locating the database on disk.
+pub mod paths;
+/// A minimal PDF writer using the standard fonts, for reports.
+pub mod pdf;
+/// The earnings analysis as a document to share.
+pub mod report;
+/// Chart builders that produce inline SVG for the HTML report.
+pub mod svg_chart;
+/// Flattening exported records into rows for CSV and spreadsheets.
+pub mod tables;
+/// Australian and New Zealand income tax tables.
+pub mod tax;
+/// Saved role setups to start new positions from.
+pub mod templates;
+/// Ending one position and starting the next at the same employer.
+pub mod transitions;
+/// Checks applied before records are saved or imported.
+pub mod validation;
+/// Picking up payslip CSVs dropped into a watched folder.
+pub mod watch_folder;
+
+pub use database::Database;
@@ -0,0 +1,44 @@
+use crate::models::*;
+
+pub const SETTINGS_KEY: &str = "lsl_rules";
+
+// Where the construction industry has a portable scheme; every state and territory does
+const CONSTRUCTION: &str = "Construction";
+
+// 8 2/3 weeks over 10 years, the same as Victoria's 1/60th of each week worked
+const WEEKS_PER_YEAR: f64 = 13.0 / 15.0;
+// 13 weeks after 10 years
+const GENEROUS_WEEKS_PER_YEAR: f64 = 1.3;
+
+// (state, weeks per year, years to full entitlement, years to pro rata)
+const STATE_RULES: &[(AustralianState, f64, f64, f64)] = &[
+    (AustralianState::NSW, WEEKS_PER_YEAR, 10.0, 5.0),
+    (AustralianState::VIC, WEEKS_PER_YEAR, 7.0, 7.0),
+    (AustralianState::QLD, WEEKS_PER_YEAR, 10.0, 7.0),
+    (AustralianState::WA, WEEKS_PER_YEAR, 10.0, 7.0),
+    (AustralianState::SA, GENEROUS_WEEKS_PER_YEAR, 10.0, 7.0),
+    (AustralianState::TAS, WEEKS_PER_YEAR, 10.0, 7.0),
+    (AustralianState::ACT, WEEKS_PER_YEAR, 7.0, 5.0),
+    (AustralianState::NT, GENEROUS_WEEKS_PER_YEAR, 10.0, 7.0),
+];
+
+/// The built-in rules for every state and territory.
+pub fn default_rules() -> Vec<LslRule> {
+    STATE_RULES.iter()
+        .map(|&(state, weeks_per_year, full_entitlement_years, pro_rata_years)| LslRule {
+            state,
+            weeks_per_year,
+            full_entitlement_years,
+            pro_rata_years,
+            portable_industries: vec![CONSTRUCTION.to_string()],
+        })
+        .collect()
+}
+
+/// The built-in rules with any saved in settings replacing those for the same state.
+pub fn rules_with_overrides(overrides: &[LslRule]) -> Vec<LslRule> {
+    default_rules()
+        .into_iter()
+        .map(|rule| overrides.iter().find(|o| o.state == rule.state).cloned().unwrap_or(rule))
+        .collect()
+}
@@ -0,0 +1,2436 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use chrono::{DateTime, Months, Utc, NaiveDate, Weekday};
+use crate::coercion::Coercion;
+
+pub fn default_currency() -> String {
+    "AUD".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserProfile {
+    pub id: Option<i64>,
+    pub first_name: String,
+    pub last_name: String,
+    pub date_of_birth: NaiveDate,
+    pub state: AustralianState,
+    pub industry: String,
+    pub highest_qualification: Qualification,
+    pub career_preferences: CareerPreferences,
+    pub standard_weekly_hours: f64, // User-configurable weekly hours
+    #[serde(default)]
+    pub has_help_debt: bool, // HELP/HECS study loan, repaid through tax
+    #[serde(default)]
+    pub total_super_balance: Option<f64>, // Across all funds at the last 30 June, for carry-forward
+    #[serde(default)]
+    pub gender: Gender, // Optional; only used for demographic comparisons, never exported anonymised
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Gender {
+    Woman,
+    Man,
+    NonBinary,
+    #[default]
+    PreferNotToSay,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CareerPreferences {
+    pub employment_type_preference: EmploymentType,
+    pub fifo_tolerance: FIFOTolerance,
+    pub travel_tolerance: TravelTolerance,
+    pub overtime_appetite: OvertimeAppetite,
+    pub privacy_acknowledged: bool,
+    pub disclaimer_acknowledged: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Position {
+    pub id: Option<i64>,
+    pub employer_name: String,
+    pub job_title: String,
+    pub employment_type: EmploymentType,
+    pub location: String,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub seniority_level: SeniorityLevel,
+    pub core_responsibilities: String,
+    pub tools_systems_skills: Vec<String>,
+    pub achievements: Vec<String>,
+    #[serde(default)]
+    pub industry: Option<String>, // None falls back to the profile's industry
+    #[serde(default)]
+    pub week_ending_day: Option<Weekday>, // The day pay periods end on, when known
+    #[serde(default)]
+    pub notice_period_weeks: Option<f64>, // Notice the contract requires on resigning
+    #[serde(default)]
+    pub leave_loading_percent: Option<f64>, // Paid on annual leave cashed out on leaving
+    #[serde(default)]
+    pub probation_months: Option<u32>,
+    #[serde(default)]
+    pub commute_minutes_per_day: Option<u32>, // Door to door, there and back
+    #[serde(default)]
+    pub archived: bool, // Kept and exported, but left out of analyses unless asked for
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Where a position sits relative to a given day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PositionStatus {
+    Past,
+    Current,
+    Future,
+}
+
+impl Position {
+    /// Future until the start date, past from the day after the end date, current between.
+    pub fn status_on(&self, date: NaiveDate) -> PositionStatus {
+        if self.start_date > date {
+            PositionStatus::Future
+        } else if self.end_date.is_some_and(|end| end < date) {
+            PositionStatus::Past
+        } else {
+            PositionStatus::Current
+        }
+    }
+}
+
+// A position without its free-text and list fields, for list views
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionSummary {
+    pub id: Option<i64>,
+    pub employer_name: String,
+    pub job_title: String,
+    pub employment_type: EmploymentType,
+    pub location: String,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub seniority_level: SeniorityLevel,
+}
+
+impl From<&Position> for PositionSummary {
+    fn from(position: &Position) -> Self {
+        PositionSummary {
+            id: position.id,
+            employer_name: position.employer_name.clone(),
+            job_title: position.job_title.clone(),
+            employment_type: position.employment_type.clone(),
+            location: position.location.clone(),
+            start_date: position.start_date,
+            end_date: position.end_date,
+            seniority_level: position.seniority_level.clone(),
+        }
+    }
+}
+
+// A position that hasn't started yet, shown apart from the current ones
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpcomingPosition {
+    pub position: PositionSummary,
+    pub starts_in_days: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompensationRecord {
+    pub id: Option<i64>,
+    pub position_id: i64,
+    pub entry_type: CompensationEntryType,
+    pub pay_type: PayType,
+    pub base_rate: f64, // Annual salary OR hourly rate
+    pub standard_weekly_hours: f64,
+    pub overtime: OvertimeDetails,
+    pub allowances: Vec<Allowance>,
+    pub bonuses: Vec<Bonus>,
+    pub super_contributions: SuperDetails,
+    pub tax_withheld: Option<f64>, // Annual tax withheld amount
+    pub payslip_frequency: Option<PayslipFrequency>,
+    pub effective_date: NaiveDate,
+    pub confidence_score: f64, // 0-100 for fuzzy entries
+    #[serde(default = "default_currency")]
+    pub currency: String, // ISO 4217 code, amounts above are in this currency
+    #[serde(default)]
+    pub jurisdiction: Jurisdiction,
+    #[serde(default = "default_true")]
+    pub claims_tax_free_threshold: bool, // Usually only the main job does
+    #[serde(default)]
+    pub equity: Option<EquityGrant>,
+    #[serde(default)]
+    pub packaging: Vec<PackagingItem>,
+    #[serde(default)]
+    pub non_cash_benefits: Vec<NonCashBenefit>,
+    #[serde(default)]
+    pub award_classification_id: Option<i64>, // An award rate row; its award and classification apply
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OvertimeDetails {
+    pub frequency: OvertimeFrequency,
+    pub rate_multiplier: f64, // 1.5x, 2.0x, or mixed
+    pub average_hours_per_week: f64,
+    pub annual_hours: Option<f64>, // For precise annual calculation
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Allowance {
+    pub name: String,
+    pub amount: f64,
+    pub frequency: AllowanceFrequency,
+    pub taxable: bool,
+    #[serde(default)]
+    pub ordinary_time: Option<bool>, // Counts towards super; when unset, taxable allowances do
+}
+
+impl Allowance {
+    /// Whether the allowance is part of ordinary time earnings, which super is paid on.
+    /// Unless it's been set, taxable allowances are, and non-taxable ones are taken to be
+    /// expense reimbursements, which aren't.
+    pub fn is_ordinary_time(&self) -> bool {
+        self.ordinary_time.unwrap_or(self.taxable)
+    }
+}
+
+// A salary packaging arrangement such as a novated lease, per pay period at the record's
+// payslip frequency. Pre-tax amounts reduce taxable income; post-tax ones come out of net pay.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackagingItem {
+    pub name: String,
+    pub pre_tax_amount_per_period: f64,
+    pub post_tax_amount_per_period: f64,
+    pub attracts_fbt: bool, // Shows up as reportable fringe benefits on the income statement
+}
+
+// Something the employer provides rather than pays for, such as a car, phone or gym membership
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NonCashBenefit {
+    pub name: String,
+    pub annual_value: f64, // Estimated, in the record's currency
+    pub fringe_benefit: bool, // Its taxable value is a reportable fringe benefit
+}
+
+// Shares or RSUs granted with the package, valued at grant in the record's currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EquityGrant {
+    pub grant_value: f64,
+    pub grant_date: NaiveDate,
+    pub vesting_months: u32, // Whole schedule, e.g. 48
+    pub cliff_months: u32, // Nothing vests before the cliff; what has accrued vests on it
+    pub vesting_frequency: VestingFrequency,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum VestingFrequency {
+    Monthly,
+    Quarterly,
+    Annually,
+}
+
+impl VestingFrequency {
+    pub fn months(&self) -> u32 {
+        match self {
+            VestingFrequency::Monthly => 1,
+            VestingFrequency::Quarterly => 3,
+            VestingFrequency::Annually => 12,
+        }
+    }
+}
+
+impl EquityGrant {
+    /// When each tranche vests and its value, in date order. Tranches are equal; those due
+    /// before the cliff vest together on it, and a last short period takes the remainder.
+    pub fn vesting_events(&self) -> Vec<(NaiveDate, f64)> {
+        let step = self.vesting_frequency.months();
+        if self.vesting_months == 0 {
+            return vec![(self.grant_date, self.grant_value)];
+        }
+
+        let cliff = self.cliff_months.min(self.vesting_months);
+        let mut events: Vec<(NaiveDate, f64)> = Vec::new();
+        let mut month = 0;
+        while month < self.vesting_months {
+            let next = (month + step).min(self.vesting_months);
+            let value = self.grant_value * (next - month) as f64 / self.vesting_months as f64;
+            let vest_month = next.max(cliff);
+            let date = self.grant_date.checked_add_months(Months::new(vest_month)).unwrap_or(NaiveDate::MAX);
+            match events.last_mut() {
+                Some((last, amount)) if *last == date => *amount += value,
+                _ => events.push((date, value)),
+            }
+            month = next;
+        }
+        events
+    }
+
+    /// Value vested on or before the date.
+    pub fn vested_by(&self, date: NaiveDate) -> f64 {
+        self.vesting_events().iter().filter(|(on, _)| *on <= date).map(|(_, value)| value).sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bonus {
+    pub name: String,
+    pub amount: f64,
+    pub date_awarded: NaiveDate,
+    pub taxable: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuperDetails {
+    pub contribution_rate: f64, // Percentage
+    pub additional_contributions: f64, // Dollar amount
+    pub salary_sacrifice: f64, // Dollar amount
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EarningsAnalysis {
+    pub current_total_compensation: f64,
+    pub current_effective_hourly_rate: f64,
+    pub income_percentile: f64,
+    pub loyalty_tax_annual: f64,
+    pub loyalty_tax_cumulative: f64,
+    pub earnings_over_time: Vec<EarningsSnapshot>,
+    pub percentile_over_time: Vec<PercentilePoint>,
+    pub gender_benchmark: Option<GenderBenchmark>, // Only with a gender given and comparisons on
+    pub hours_vs_earnings: Vec<HoursEarningsPoint>,
+    pub super_trajectory: Vec<SuperSnapshot>,
+    pub insights: Vec<EarningsInsight>,
+    pub current_positions: Vec<PositionSummary>, // Every open-ended position, the one analysed first
+    #[serde(default)]
+    pub upcoming_positions: Vec<UpcomingPosition>, // Accepted but not started; left out of the figures
+    #[serde(default)]
+    pub warnings: Vec<AnalysisWarning>,
+}
+
+// An assumption a calculation made in place of missing or unusable data
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalysisWarning {
+    pub code: AnalysisWarningCode,
+    pub message: String,
+    pub related_entity: Option<RelatedEntity>,
+}
+
+// Stable across releases; the frontend keys its own wording on these
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AnalysisWarningCode {
+    NoProfile, // Percentile compared against defaults
+    PayEstimatedFromLevel, // Position pay from its seniority level, not its records
+    MissingExchangeRate, // Foreign income counted as nothing
+    OpenEndedTenure, // Current role's tenure counted to a fixed end
+    ShortTenureSkipped, // Too short to measure progression
+    PartialYearProjected, // Gross projected from the pay periods recorded
+    TaxNotEstimated,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RelatedEntity {
+    Position(i64),
+    Employer(String),
+    FinancialYear(String),
+}
+
+// The headline figures of an analysis, kept to compare against later
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalysisSnapshot {
+    pub id: Option<i64>,
+    pub taken_at: DateTime<Utc>,
+    pub total_compensation: f64,
+    pub income_percentile: f64,
+    pub loyalty_tax_annual: f64,
+    pub loyalty_tax_cumulative: f64,
+    pub projected_super: f64, // Employer contributions for the financial year the snapshot was taken in
+}
+
+// Later snapshot minus the earlier one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotComparison {
+    pub earlier: AnalysisSnapshot,
+    pub later: AnalysisSnapshot,
+    pub days_between: i64,
+    pub total_compensation_change: f64,
+    pub income_percentile_change: f64,
+    pub loyalty_tax_annual_change: f64,
+    pub loyalty_tax_cumulative_change: f64,
+    pub projected_super_change: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EarningsSnapshot {
+    pub date: NaiveDate,
+    pub base_annual: f64,
+    pub actual_annual: f64,
+    pub total_with_super: f64,
+    pub effective_hourly_rate: f64,
+    pub jurisdiction: Jurisdiction,
+    #[serde(default)]
+    pub col_adjusted_annual: Option<f64>, // Actual earnings at national-average prices, when the location is known
+}
+
+// Current pay against the median for the profile's gender in its industry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenderBenchmark {
+    pub gender: Gender,
+    pub median: f64,
+    pub percentile: f64,
+}
+
+// Whether comparisons against demographic groups are made at all
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DemographicSettings {
+    pub comparisons_enabled: bool,
+}
+
+impl Default for DemographicSettings {
+    fn default() -> Self {
+        Self { comparisons_enabled: true }
+    }
+}
+
+// Where a year's earnings sat against the benchmark median carried back to that year
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PercentilePoint {
+    pub financial_year: String,
+    pub annual_earnings: f64,
+    pub benchmark_median: f64,
+    pub percentile: f64,
+}
+
+// Wage growth by year, to carry the benchmark medians back from the year they're for
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WageIndex {
+    pub benchmark_year: String, // e.g. "FY2023-24"
+    pub growth: Vec<WageGrowth>,
+    pub default_growth_percent: f64, // For years not listed
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WageGrowth {
+    pub financial_year: String,
+    pub growth_percent: f64, // Over the year to its June
+}
+
+// ABS Wage Price Index, all sectors, hourly rates excluding bonuses, through the year to June
+impl Default for WageIndex {
+    fn default() -> Self {
+        let growth = [
+            ("FY2012-13", 2.9), ("FY2013-14", 2.6), ("FY2014-15", 2.3), ("FY2015-16", 2.1),
+            ("FY2016-17", 1.9), ("FY2017-18", 2.1), ("FY2018-19", 2.3), ("FY2019-20", 1.8),
+            ("FY2020-21", 1.7), ("FY2021-22", 2.6), ("FY2022-23", 3.6), ("FY2023-24", 4.1),
+            ("FY2024-25", 3.4),
+        ];
+        Self {
+            benchmark_year: "FY2023-24".to_string(),
+            growth: growth.into_iter()
+                .map(|(financial_year, growth_percent)| WageGrowth { financial_year: financial_year.to_string(), growth_percent })
+                .collect(),
+            default_growth_percent: 3.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HoursEarningsPoint {
+    pub label: String, // "2023-24" or "2023", depending on the YearBasis
+    pub total_hours_worked: f64,
+    pub total_earnings: f64,
+    pub overtime_percentage: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuperSnapshot {
+    pub financial_year: String,
+    pub employer_contributions: f64,
+    pub personal_contributions: f64,
+    pub total_super_balance: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuperContributionSummary {
+    pub financial_year: String,
+    pub employer_contributions: f64, // AUD
+    pub method: SuperContributionMethod,
+    pub periods_recorded: u32, // Payslips summed, 0 when estimated
+    pub sg_rate: Option<f64>, // Percent used for the estimate
+}
+
+// Salary sacrifice that still fits under the concessional cap, and what it would save in tax
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SacrificeHeadroom {
+    pub financial_year: String,
+    pub concessional_cap: f64,
+    pub employer_contributions: f64, // AUD
+    pub employer_contributions_method: SuperContributionMethod,
+    pub existing_salary_sacrifice: f64, // AUD
+    pub taxable_income: f64, // Estimated, after existing sacrifice
+    pub headroom: f64, // Further sacrifice before the cap is reached
+    pub div293_on_headroom: f64, // Extra Division 293 tax from sacrificing the headroom
+    pub estimated_tax_saving: f64, // Income tax avoided less contributions tax and Division 293 on the headroom
+}
+
+// What sacrificing a fixed amount is worth at one income
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SacrificeBenefitPoint {
+    pub income: f64, // Before the sacrifice
+    pub income_tax_saving: f64,
+    pub contributions_tax: f64,
+    pub div293: f64, // Division 293 added by the sacrifice
+    pub net_benefit: f64,
+    pub benefit_per_dollar: f64,
+}
+
+// Before-tax super contributions for a financial year, in AUD
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConcessionalContributions {
+    pub financial_year: String,
+    pub employer_contributions: f64,
+    pub employer_contributions_method: SuperContributionMethod,
+    pub salary_sacrifice: f64, // Reportable super from income statements when entered, else the records
+    pub additional_contributions: f64,
+    pub total: f64,
+}
+
+// A year's concessional contributions against its cap, with unused cap carried forward
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuperCapStatus {
+    pub contributions: ConcessionalContributions,
+    pub concessional_cap: f64,
+    pub carry_forward_available: f64, // Unused cap from the previous five years, 0 when not eligible
+    pub carry_forward_used: f64,
+    pub headroom: f64, // Further contributions before the cap and carry-forward are used up
+    pub excess: f64, // Over the cap after carry-forward, taxed at marginal rates
+}
+
+// A bonus taken as cash against sacrificed into super, as far as the cap allows
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BonusSacrificeAnalysis {
+    pub financial_year: String,
+    pub bonus_amount: f64,
+    pub marginal_rate: f64, // Percent, including Medicare
+    pub cap_headroom: f64, // Room under the concessional cap for the year, with carry-forward
+    pub has_cap_room: bool,
+    pub amount_sacrificed: f64, // The part of the bonus that fits under the cap
+    pub cash_after_tax: f64, // Taking the whole bonus as cash
+    pub sacrifice_after_tax: f64, // Into super after contributions tax, plus the rest as cash
+    pub net_benefit: f64, // Sacrificing over taking cash; the super is preserved until retirement
+}
+
+// A state's long service leave rules. Service in a portable industry counts across employers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LslRule {
+    pub state: AustralianState,
+    pub weeks_per_year: f64, // Accrual, e.g. 0.8667 for 8 2/3 weeks over 10 years
+    pub full_entitlement_years: f64,
+    pub pro_rata_years: f64, // Paid out pro rata on leaving from here; conditions vary by state
+    #[serde(default)]
+    pub portable_industries: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LslEntitlement {
+    None,
+    ProRata,
+    Full,
+}
+
+// Long service leave accrued with one employer, or across a portable industry in a state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LslTenure {
+    pub position_ids: Vec<i64>,
+    pub employer_name: String, // The industry for a portable scheme
+    pub state: AustralianState,
+    pub portable: bool,
+    pub current: bool, // Still employed in the tenure
+    pub years_of_service: f64,
+    pub accrued_weeks: f64,
+    pub weekly_rate: f64, // Ordinary weekly pay on the latest record
+    pub accrued_value: f64,
+    pub entitlement: LslEntitlement,
+    pub payable_on_leaving: f64, // Accrued value once pro-rata or full entitlement is reached
+    pub next_threshold: Option<LslEntitlement>,
+    pub next_threshold_date: Option<NaiveDate>, // Only while still employed
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LslStatus {
+    pub tenures: Vec<LslTenure>,
+    pub total_accrued_weeks: f64,
+    pub total_accrued_value: f64,
+    pub total_payable_on_leaving: f64,
+}
+
+// Tax on a financial year's income from every job together against what each employer
+// withheld on its own, in AUD
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CombinedTaxPosition {
+    pub financial_year: String,
+    pub sources: Vec<TaxedIncomeSource>,
+    pub total_gross_income: f64,
+    pub total_taxable_income: f64, // After deductions
+    #[serde(default)]
+    pub deductions: f64, // Income protection premiums paid outside super
+    pub tax_payable: f64, // On the combined taxable income, with any HELP repayment and surcharge
+    #[serde(default)]
+    pub medicare_levy_surcharge: f64, // Only when the household settings say there's no hospital cover
+    #[serde(default)]
+    pub family_income: Option<f64>, // With a partner's income, which sets the surcharge tier
+    pub tax_payable_if_separate: f64, // Each source taxed as if it were the only income
+    pub total_withheld: f64,
+    pub net_position: f64, // Withheld minus payable; negative is a bill
+    #[serde(default)]
+    pub warnings: Vec<AnalysisWarning>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaxedIncomeSource {
+    pub position_id: Option<i64>, // None for income not linked to a position
+    pub employer_name: String,
+    pub method: TaxedIncomeMethod,
+    pub gross_income: f64,
+    pub taxable_income: f64,
+    pub tax_withheld: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TaxedIncomeMethod {
+    YearlyEntry, // The income statement for the year
+    Payslips,    // Summed from the payslips entered so far
+    Estimated,   // From the compensation record, with the withholding its employer would expect
+}
+
+// Everything a financial year's tax return needs from each job, in AUD
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaxTimeSummary {
+    pub financial_year: String,
+    pub sources: Vec<TaxTimeIncome>,
+    pub total_gross_income: f64,
+    pub total_tax_withheld: f64,
+    pub employer_super: f64,
+    pub salary_sacrifice: f64,
+    pub reportable_super: f64, // Employer contributions and salary sacrifice together
+    pub reportable_fringe_benefits: f64,
+    pub taxable_allowances: f64,
+    #[serde(default)]
+    pub warnings: Vec<AnalysisWarning>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaxTimeIncome {
+    pub position_id: Option<i64>, // None for income not linked to a position
+    pub employer_name: String,
+    pub method: TaxedIncomeMethod, // Never Estimated; jobs with nothing entered are left out
+    pub gross_income: f64,
+    pub tax_withheld: f64,
+    pub employer_super: f64, // From payslips when entered, else estimated from the record
+    pub salary_sacrifice: f64, // Reportable super on the income statement, else from the record
+    pub reportable_fringe_benefits: f64,
+    pub taxable_allowances: f64,
+}
+
+// The salary to ask for to take home a target amount a year, after the year's tax
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetNetEstimate {
+    pub financial_year: String,
+    pub target_net: f64,
+    pub gross_salary: f64,
+    pub employer_super: f64, // At the super guarantee rate, on top of the salary
+    pub total_package: f64,
+    pub tax_payable: f64, // Income tax less the low income tax offset, plus the Medicare levy
+    pub help_repayment: f64,
+    pub average_tax_rate: f64, // Percent of gross, HELP included
+    pub marginal_tax_rate: f64, // Percent on the next dollar, HELP included
+}
+
+// A car salary packaged through a novated lease against buying it from take-home pay, a year
+// over the lease
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NovatedLeaseAnalysis {
+    pub vehicle_cost: f64, // Drive-away, GST included
+    pub lease_term_months: u32,
+    pub annual_km: f64,
+    pub marginal_rate: f64, // Percent, including the Medicare levy
+    pub gst_credit: f64, // Claimed by the employer on the purchase, up to the car limit
+    pub amount_financed: f64,
+    pub residual_value: f64, // The ATO minimum for the term, due at the end
+    pub annual_lease_cost: f64, // Repayments, with the residual spread over the term
+    pub annual_running_costs: f64, // Without the GST the employer claims back
+    pub fbt_taxable_value: f64, // Statutory formula, averaged over the term
+    pub pre_tax_contribution: f64,
+    pub post_tax_contribution: f64, // Employee contribution method, cancelling the FBT
+    pub income_tax_saved: f64,
+    pub annual_cost_with_lease: f64, // Out of take-home pay once the tax saved is counted
+    pub annual_cost_without_lease: f64, // Same car on the same finance from take-home pay
+    pub annual_net_benefit: f64,
+    pub non_cash_benefit: NonCashBenefit, // The saving, to add to the compensation record
+}
+
+// The gross pay and salary a net weekly figure implies under the year's withholding, with a
+// band either side since the weekly tables don't match the annual tax exactly
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReverseBaseEstimate {
+    pub financial_year: String,
+    pub net_weekly: f64,
+    pub claims_tax_free_threshold: bool,
+    pub gross_weekly: f64,
+    pub tax_withheld_weekly: f64,
+    pub annual_base: f64,
+    pub employer_super: f64, // At the super guarantee rate
+    pub total_package: f64, // Base and super
+    pub annual_base_low: f64,
+    pub annual_base_high: f64,
+}
+
+// The assumptions behind projections, and how far either side of them the low and high
+// series go
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectionAssumptions {
+    pub investment_return: f64, // Percent a year, after fees
+    pub return_spread: f64, // Percentage points either side
+    pub salary_growth_spread: f64, // Percentage points either side of the seniority's market growth
+}
+
+impl Default for ProjectionAssumptions {
+    fn default() -> Self {
+        Self {
+            investment_return: 7.0,
+            return_spread: 2.0,
+            salary_growth_spread: 2.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectionPoint {
+    pub years_from_now: u32,
+    pub low: f64,
+    pub expected: f64,
+    pub high: f64,
+}
+
+// Super balance each year to age 67 from the current balance and contributions, in AUD
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuperProjection {
+    pub starting_age: i32,
+    pub starting_balance: f64,
+    pub annual_contributions: f64, // Concessional, before contributions tax, growing with salary
+    #[serde(default)]
+    pub insurance_premiums: f64, // Paid from the balance each year, not growing
+    pub salary_growth: f64, // Expected, percent a year
+    pub investment_return: f64, // Expected, percent a year
+    pub points: Vec<ProjectionPoint>,
+}
+
+// A position's pay grown at its seniority's market rate, in the record's currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SalaryProjection {
+    pub position_id: i64,
+    pub current_salary: f64, // Annual gross on the latest record
+    pub salary_growth: f64, // Expected, percent a year
+    pub points: Vec<ProjectionPoint>,
+}
+
+// A backdated pay rise recomputed over the pay periods it covers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackPayCalculation {
+    pub position_id: i64,
+    pub from_date: NaiveDate,
+    pub to_date: NaiveDate,
+    pub old_hourly_rate: f64,
+    pub new_hourly_rate: f64,
+    pub periods: Vec<BackPayPeriod>,
+    pub estimated_periods: u32,
+    pub total_back_pay: f64,
+    pub estimated_withholding: f64, // On the lump sum, spread over the year's pay periods
+    pub net_back_pay: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackPayPeriod {
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub hours_ordinary: f64,
+    pub hours_overtime: f64,
+    pub old_gross: f64,
+    pub new_gross: f64,
+    pub difference: f64,
+    pub estimated: bool, // No payslip, so the new record's standard hours were assumed
+}
+
+// When a role's next bonus is likely, from its bonus history, and whether to hold off resigning
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResignationTimingAdvice {
+    pub position_id: i64,
+    pub bonuses_on_record: u32, // Distinct award dates
+    pub typical_interval_months: Option<u32>,
+    pub predicted_bonus_date: Option<NaiveDate>,
+    pub days_until_bonus: Option<i64>,
+    pub bonus_at_risk: f64, // What was paid on the last award date, in the record's currency
+    pub window_days: u32,
+    pub wait_for_bonus: bool, // The predicted date is within the window
+    pub advice: String,
+}
+
+// Notice, leave paid out and the final pay for resigning from a position on a given day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResignationPlan {
+    pub position_id: i64,
+    pub notice_given: NaiveDate,
+    pub notice_period_weeks: f64,
+    pub notice_from_contract: bool, // Otherwise the National Employment Standards minimum
+    pub earliest_last_day: NaiveDate,
+    pub proposed_last_day: NaiveDate,
+    pub annual_leave_hours: f64,
+    pub annual_leave_estimated: bool, // Accrued since the last work anniversary, not a balance entered
+    pub annual_leave_value: f64,
+    pub leave_loading: f64,
+    pub long_service_leave: Option<LslTenure>,
+    pub long_service_leave_value: f64, // Nothing when it stays with a portable scheme
+    pub final_pay_gross: f64, // Leave paid out, on top of the last period's ordinary pay
+    pub estimated_withholding: f64,
+    pub final_pay_net: f64,
+    pub tax_treatment: String,
+}
+
+// How exposed current income is to losing a job, overtime drying up or a bad bonus year,
+// from 0 (resilient) to 100
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IncomeRiskAssessment {
+    pub score: f64,
+    pub level: IncomeRiskLevel,
+    pub annual_income: f64, // Gross across current positions, in the records' currencies
+    pub factors: Vec<IncomeRiskFactor>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IncomeRiskFactor {
+    pub factor: String,
+    pub points: f64,
+    pub max_points: f64,
+    pub detail: String,
+}
+
+// A current position that goes against a preference stated in the profile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PreferenceMismatch {
+    pub position_id: i64,
+    pub preference: MismatchedPreference,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MismatchedPreference {
+    FifoTolerance,
+    OvertimeAppetite,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum IncomeRiskLevel {
+    Low,
+    Moderate,
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SuperContributionMethod {
+    WeeklyEntries, // Summed from super_contributed on payslips
+    Estimated,     // SG rate on base salary
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EarningsInsight {
+    pub category: InsightCategory,
+    pub title: String,
+    pub description: String,
+    pub confidence_level: f64,
+    pub data_points: Vec<String>,
+    #[serde(default)]
+    pub position_ids: Vec<i64>, // Positions the insight is about, to link to them
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoyaltyTaxAnalysis {
+    pub tenure_blocks: Vec<TenureBlock>,
+    pub market_comparison: MarketComparison,
+    pub annual_loyalty_tax: Vec<YearlyLoyaltyTax>,
+    pub cumulative_loyalty_tax: f64,
+    pub confidence_level: f64,
+    #[serde(default)]
+    pub warnings: Vec<AnalysisWarning>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TenureBlock {
+    pub employer_name: String,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub years_of_service: f64,
+    pub actual_progression: f64, // Percentage increase
+    pub market_expected_progression: f64,
+    pub loyalty_tax_impact: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketComparison {
+    pub industry_average_growth: f64,
+    pub role_level_growth: f64,
+    pub cpi_adjusted_growth: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YearlyLoyaltyTax {
+    pub year: i32,
+    pub loyalty_tax_amount: f64,
+    pub missed_opportunities: Vec<String>,
+}
+
+// Career earnings had each employer's base pay grown at the market rate, beside the actual earnings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CounterfactualEarnings {
+    pub years: Vec<CounterfactualYear>,
+    pub actual_total: f64, // AUD
+    pub counterfactual_total: f64, // AUD
+    pub foregone_total: f64, // Negative when pay outgrew the market
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CounterfactualYear {
+    pub year: i32,
+    pub actual: f64,
+    pub counterfactual: f64,
+    pub cumulative_actual: f64,
+    pub cumulative_counterfactual: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResumeExport {
+    pub profile_summary: ProfileSummary,
+    pub career_timeline: Vec<ResumePosition>,
+    #[serde(default)]
+    pub upcoming_positions: Vec<ResumePosition>, // Accepted but not yet started
+    pub achievements: Vec<String>,
+    pub skills_and_tools: Vec<String>,
+    pub compensation_summary: CompensationSummary,
+    pub target_preferences: CareerPreferences,
+}
+
+// Further study weighed up before tax: what it costs against what it should add to pay
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualificationRoi {
+    pub current_qualification: Qualification,
+    pub cost: f64,
+    pub study_years: f64,
+    pub current_annual_pay: f64,
+    pub foregone_earnings: f64, // Pay given up while studying full time
+    pub total_investment: f64,
+    pub expected_salary_uplift: f64, // A year, in the first year qualified
+    pub uplift_is_estimate: bool, // From the base estimates rather than given
+    pub market_growth_percent: f64, // How the uplift grows each year
+    pub payback_years: Option<f64>, // From the start of study
+    pub ten_year_net_benefit: f64,
+}
+
+// Earnings over a whole working life, to date and projected to retirement. Real figures are
+// in today's dollars.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LifetimeEarningsProjection {
+    pub current_age: i32,
+    pub retirement_age: i32,
+    pub current_annual_earnings: f64,
+    pub salary_growth: f64, // Percent a year, before promotions
+    pub inflation: f64, // Percent a year assumed for the future
+    pub historical_nominal: f64,
+    pub historical_real: f64,
+    pub projected_nominal: f64,
+    pub projected_real: f64,
+    pub lifetime_nominal: f64,
+    pub lifetime_real: f64,
+    pub points: Vec<LifetimeEarningsPoint>, // A year each from next year to retirement
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LifetimeEarningsPoint {
+    pub age: i32,
+    pub nominal: f64,
+    pub real: f64,
+    pub seniority_level: SeniorityLevel,
+}
+
+// A skill ranked by how recently and how long it's been used
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillRecency {
+    pub skill: String,
+    pub last_used_year: i32,
+    pub current: bool, // Used in a position that hasn't ended
+    pub positions_used: u32,
+    pub years_used: f64,
+    pub score: f64, // Years of use, discounted by how long ago they were
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileSummary {
+    pub name: String,
+    pub age: i32,
+    pub location: String,
+    pub industry: String,
+    pub experience_years: f64,
+    pub seniority_level: SeniorityLevel,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResumePosition {
+    pub employer: String,
+    pub title: String,
+    pub duration: String,
+    pub responsibilities: Vec<String>,
+    pub achievements: Vec<String>,
+    pub skills_used: Vec<String>,
+}
+
+// One position's pay entries in aggregate, for its detail screen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionWeeklyStats {
+    pub position_id: i64,
+    pub entry_count: u32,
+    pub first_week_ending: Option<NaiveDate>,
+    pub last_week_ending: Option<NaiveDate>,
+    pub weeks_logged: f64, // A fortnightly payslip counts as two
+    pub gap_count: u32,
+    pub total_gross: f64,
+    pub total_tax_withheld: f64,
+    pub total_net: f64,
+    pub total_super: f64,
+    pub average_weekly_hours: f64,
+    pub overtime_share: f64, // Of hours worked, 0-1
+}
+
+// The figures from a compensation record worth showing at a glance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompensationRecordSummary {
+    pub id: Option<i64>,
+    pub effective_date: NaiveDate,
+    pub pay_type: PayType,
+    pub base_rate: f64,
+    pub annual_gross: f64, // Base, overtime, allowances and bonuses
+    pub currency: String,
+}
+
+// Everything the position detail screen summarises, in one call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionStatistics {
+    pub weekly: PositionWeeklyStats,
+    pub compensation_record_count: u32,
+    pub latest_record: Option<CompensationRecordSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompensationSummary {
+    pub current_base: f64,
+    pub current_total: f64,
+    pub career_earnings_total: f64,
+    pub average_annual_increase: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IncomeComposition {
+    pub position_id: i64,
+    pub effective_date: NaiveDate,
+    pub currency: String, // Currency the record was entered in
+    pub exchange_rate_to_aud: Option<f64>, // None when no rate has been entered
+    pub total_compensation: f64, // AUD
+    pub original_total_compensation: f64, // In the record's currency
+    pub components: Vec<IncomeComponent>,
+    pub non_cash_benefits: f64, // AUD, not part of total compensation
+    pub total_package_value: f64, // AUD, total compensation with non-cash benefits
+    pub original_total_package_value: f64,
+    #[serde(default)]
+    pub warnings: Vec<AnalysisWarning>, // Such as AUD amounts left at nothing for want of a rate
+}
+
+// An offer's package against the current one, differences as offer less current
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OfferComparison {
+    pub current: IncomeComposition,
+    pub offer: IncomeComposition,
+    pub current_take_home: f64, // A year, in the record's currency
+    pub offer_take_home: f64,
+    pub total_compensation_difference: f64, // AUD
+    pub total_package_value_difference: f64, // AUD
+    pub take_home_difference: f64,
+    #[serde(default)]
+    pub mls_tier_change: Option<MlsTierChange>,
+}
+
+// A named what-if: an offer against a position's pay, saved to rerun later
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Scenario {
+    pub id: Option<i64>,
+    pub name: String,
+    pub position_id: Option<i64>,
+    pub baseline: CompensationRecord, // The position's latest record when saved
+    pub offer: CompensationRecord,
+    pub result: OfferComparison, // As it came out when saved
+    pub created_at: DateTime<Utc>,
+}
+
+// A saved role setup that new positions can start from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionTemplate {
+    pub id: Option<i64>,
+    pub name: String,
+    pub fields: TemplateFields,
+    pub created_at: DateTime<Utc>,
+}
+
+// Everything a template carries. Left out on purpose, as they belong to one job: the employer,
+// dates, commute, achievements, notes, bonuses, tax withheld, equity, packaging, non-cash benefits and
+// award links.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateFields {
+    pub job_title: String,
+    pub employment_type: EmploymentType,
+    pub location: String,
+    pub seniority_level: SeniorityLevel,
+    pub core_responsibilities: String,
+    pub tools_systems_skills: Vec<String>,
+    pub industry: Option<String>,
+    pub week_ending_day: Option<Weekday>,
+    pub notice_period_weeks: Option<f64>,
+    pub leave_loading_percent: Option<f64>,
+    pub probation_months: Option<u32>,
+    pub compensation: Option<TemplateCompensation>, // From the latest record; None when there was none
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateCompensation {
+    pub pay_type: PayType,
+    pub base_rate: f64, // 0 unless rates were kept
+    pub standard_weekly_hours: f64,
+    pub overtime: OvertimeDetails,
+    pub allowances: Vec<Allowance>, // Amounts 0 unless rates were kept
+    pub super_contributions: SuperDetails,
+    pub payslip_frequency: Option<PayslipFrequency>,
+    pub currency: String,
+    pub jurisdiction: Jurisdiction,
+    pub claims_tax_free_threshold: bool,
+}
+
+// What a new position from a template needs that the template can't know
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateOverrides {
+    pub employer_name: String,
+    pub start_date: NaiveDate,
+    #[serde(default)]
+    pub job_title: Option<String>,
+    #[serde(default)]
+    pub location: Option<String>,
+    #[serde(default)]
+    pub base_rate: Option<f64>,
+}
+
+// A saved scenario's original result next to the same offer against today's data
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioRerun {
+    pub scenario: Scenario,
+    pub original: OfferComparison,
+    pub current: OfferComparison,
+    pub warnings: Vec<String>, // e.g. the position was deleted and the saved pay was used
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IncomeComponent {
+    pub kind: IncomeComponentKind,
+    pub annual_amount: f64, // AUD
+    pub original_annual_amount: f64,
+    pub percentage: f64, // Share of total compensation
+}
+
+// Overtime a week for a position's gross pay to reach a target, in the record's currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OvertimeTargetResult {
+    pub position_id: i64,
+    pub target_annual: f64,
+    pub income_without_overtime: f64, // Base, allowances and bonuses a year
+    pub overtime_hourly_rate: f64, // Base hourly rate times the multiplier
+    pub overtime_hours_per_week: f64, // 0 when the target is met without overtime
+    pub total_weekly_hours: f64, // Standard hours plus the overtime
+    pub weekly_hours_ceiling: f64,
+    pub unsustainable: bool, // More hours than the ceiling
+    pub marginal_tax_rate_without_overtime: f64, // Percent on the next dollar, HELP included
+    pub marginal_tax_rate_at_target: f64,
+    pub crosses_tax_bracket: bool,
+    pub net_per_overtime_hour_at_target: f64, // After tax at the marginal rate
+}
+
+// Effective hourly rate with and without overtime hours, in the record's currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OvertimeRateEffect {
+    pub position_id: i64,
+    pub effective_date: NaiveDate,
+    pub currency: String,
+    pub overtime_hours_per_week: f64,
+    pub rate_multiplier: f64,
+    pub hourly_rate_without_overtime: f64,
+    pub hourly_rate_with_overtime: f64,
+    pub difference: f64, // With minus without
+    pub direction: OvertimeEffectDirection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum OvertimeEffectDirection {
+    NetPositive, // Overtime pays more per hour than the rest of the package
+    NetNegative, // Overtime dilutes the hourly figure, e.g. unpaid or 1.0x with allowances
+    Neutral,     // No overtime, or no measurable change
+}
+
+// How complete a position's data is, out of 100, with what was taken off and why
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataQuality {
+    pub score: f64,
+    pub deductions: Vec<QualityDeduction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualityDeduction {
+    pub reason: String,
+    pub points: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionDataQuality {
+    pub position_id: i64,
+    pub employer_name: String,
+    pub job_title: String,
+    pub quality: DataQuality,
+}
+
+// Every position's data quality, and their average
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataQualityReport {
+    pub overall_score: f64,
+    pub positions: Vec<PositionDataQuality>, // Lowest score first
+}
+
+// Employer super on a compensation record, paid on ordinary time earnings only
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmployerSuperBreakdown {
+    pub position_id: i64,
+    pub effective_date: NaiveDate,
+    pub currency: String,
+    pub ordinary_time_earnings: f64, // Base pay and ordinary-time allowances
+    pub excluded_earnings: f64, // Overtime, other allowances and bonuses
+    pub contribution_rate: f64, // Percent
+    pub employer_super: f64,
+}
+
+// A compensation record's annual pay as it lands each pay period, in the record's currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayCycleBreakdown {
+    pub position_id: i64,
+    pub currency: String,
+    pub frequency: PayslipFrequency,
+    pub periods_per_year: f64, // Average over a 365.25-day year, so 26.09 fortnights
+    pub extra_period_every_years: Option<f64>, // How often a year has a 53rd week or 27th fortnight
+    pub annual: PayCycleAmounts,
+    pub per_period: PayCycleAmounts,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayCycleAmounts {
+    pub gross: f64,
+    pub tax: f64, // Income tax, Medicare levy and HELP repayments
+    pub salary_sacrifice: f64, // Super and other packaging taken before pay
+    pub net: f64,
+    pub employer_super: f64, // Paid on top, not out of gross
+}
+
+// A compensation record's year at 0.8 of its ordinary hours against the year as it is
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FourDayWeekImpact {
+    pub position_id: i64,
+    pub currency: String,
+    pub fte: f64,
+    pub weekly_hours_gained: f64,
+    pub time_gained_percent: f64,
+    pub gross_reduction_percent: f64,
+    pub net_reduction_percent: f64, // Less than the gross reduction once tax is taken out
+    pub current: PayCycleAmounts, // A year
+    pub four_day_week: PayCycleAmounts,
+}
+
+// One pay entry's period moved onto the position's week-ending day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeekEndingShift {
+    pub entry_id: i64,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub days: i64, // Negative when moved earlier
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeekEndingNormalization {
+    pub position_id: i64,
+    pub target_weekday: Weekday,
+    pub shifts: Vec<WeekEndingShift>, // Entries already on the day are left out
+    pub applied: bool, // False for a dry run
+}
+
+// A compensation record's tax withheld against what its employer is expected to withhold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WithholdingCheck {
+    pub position_id: i64,
+    pub effective_date: NaiveDate,
+    pub financial_year: String,
+    pub claims_tax_free_threshold: bool,
+    pub taxable_income: f64,
+    pub expected_withholding: f64,
+    pub tax_withheld: f64,
+    pub difference: f64, // Withheld minus expected
+    pub status: WithholdingStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WithholdingStatus {
+    AsExpected,
+    OverWithheld,
+    UnderWithheld,
+}
+
+// Super paid on a position's payslips against what its contracted rate on ordinary time
+// earnings comes to, in the payslips' currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuperComplianceReport {
+    pub position_id: i64,
+    pub financial_year: String,
+    pub periods_checked: u32,
+    pub ordinary_time_earnings: f64,
+    pub expected_super: f64,
+    pub super_paid: f64,
+    pub underpayment: f64, // Shortfalls in the quarters underpaid; overpaying one quarter doesn't cover another
+    pub quarters: Vec<SuperQuarterCompliance>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuperQuarterCompliance {
+    pub quarter: u32, // 1 is July to September
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub ordinary_time_earnings: f64,
+    pub expected_super: f64,
+    pub super_paid: f64,
+    pub shortfall: f64, // Expected minus paid; negative when overpaid
+    pub underpaid: bool,
+}
+
+// Year-on-year base pay change against inflation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RealPayCut {
+    pub financial_year: String,
+    pub previous_annual_pay: f64, // AUD base pay at the end of the previous year
+    pub current_annual_pay: f64,  // AUD base pay at the end of this year
+    pub nominal_change_percent: f64,
+    pub cpi_percent: f64,
+    pub real_change_percent: f64, // Nominal change less CPI
+    pub purchasing_power_lost: f64, // Raise needed to keep pace with CPI, less the raise received; negative when ahead
+}
+
+// How much of the change in average tax rate across the career came from fixed bracket
+// thresholds rather than real income growth
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BracketCreepReport {
+    pub years: Vec<BracketCreepYear>,
+    pub total_creep_points: f64, // Sum of the yearly creep, in percentage points of average rate
+    pub total_real_growth_points: f64,
+    pub total_creep_tax: f64, // Extra tax paid across the years because thresholds didn't move with CPI
+}
+
+// One financial year against the year before it, on each year's own brackets
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BracketCreepYear {
+    pub financial_year: String,
+    pub income: f64, // ATO gross where lodged, otherwise the projected gross
+    pub average_tax_rate: f64, // Percent, income tax only
+    pub previous_average_tax_rate: f64,
+    pub cpi_percent: f64,
+    pub creep_points: f64, // Rate change had income only kept pace with CPI
+    pub real_growth_points: f64, // The rest of the rate change, from income growth above or below CPI
+    pub creep_tax: f64, // Tax on the CPI-indexed previous income, above the previous year's average rate
+}
+
+// A role's annual base growth graded against inflation, super guarantee rises and the market
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProgressionScorecard {
+    pub position_id: i64,
+    pub from: NaiveDate, // The role's first record
+    pub to: NaiveDate, // The role's end, or today
+    pub years: f64,
+    pub annual_base_growth_percent: f64, // Compound, from the first record's base to the latest's
+    pub grades: Vec<BenchmarkGrade>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkGrade {
+    pub benchmark: ProgressionBenchmark,
+    pub benchmark_growth_percent: f64, // A year, compound
+    pub actual_growth_percent: f64, // Base growth, or package growth against the super guarantee
+    pub grade: ScorecardGrade,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ProgressionBenchmark {
+    Cpi,
+    SuperGuarantee, // The package kept up with guarantee rises rather than the base paying for them
+    Market, // Growth for the role's seniority level
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ScorecardGrade {
+    Green, // At or above the benchmark
+    Amber, // Slightly below
+    Red,
+}
+
+// A promotion or role change at the same employer: the current position ends on `end_date`
+// and the new one starts the next day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionTransition {
+    pub end_date: NaiveDate,
+    pub job_title: String,
+    pub seniority_level: SeniorityLevel,
+    pub employment_type: Option<EmploymentType>, // Kept from the old position when None
+    pub location: Option<String>,
+    pub core_responsibilities: String,
+    #[serde(default)]
+    pub copy_skills: bool,
+    #[serde(default)]
+    pub carry_over_compensation: bool, // Copy the latest record to the new position
+    pub raise_percent: Option<f64>, // Applied to the carried-over base rate
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionTransitionResult {
+    pub old_position_id: i64,
+    pub new_position_id: i64,
+    pub compensation_record_id: Option<i64>, // The carried-over record, if any
+}
+
+// Changes to a compensation record, copied for a raise or corrected; anything left out is kept
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompensationRecordOverrides {
+    pub entry_type: Option<CompensationEntryType>,
+    pub pay_type: Option<PayType>,
+    pub base_rate: Option<f64>,
+    pub standard_weekly_hours: Option<f64>,
+    pub overtime: Option<OvertimeDetails>,
+    pub allowances: Option<Vec<Allowance>>,
+    pub bonuses: Option<Vec<Bonus>>,
+    pub super_contribution_rate: Option<f64>,
+    pub super_additional_contributions: Option<f64>,
+    pub super_salary_sacrifice: Option<f64>,
+    pub tax_withheld: Option<f64>,
+    pub confidence_score: Option<f64>,
+    pub notes: Option<String>,
+}
+
+// Fields set on every entry in a bulk edit; anything left out is kept
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WeeklyEntryPatch {
+    pub overtime_rate_multiplier: Option<f64>,
+    pub super_rate: Option<f64>, // Percent of ordinary-hours pay, sets super_contributed
+}
+
+// What a bulk edit did to one row
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BulkEditResult {
+    pub id: i64,
+    pub updated: bool,
+    pub error: Option<String>, // Why the row was left as it was
+}
+
+// A compensation record as it was before a change, kept in the change log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordChange {
+    pub id: Option<i64>,
+    pub record_id: i64,
+    pub changed_at: DateTime<Utc>,
+    pub change: String, // e.g. "Upgraded to exact"
+    pub before: CompensationRecord,
+}
+
+// A fuzzy record and how much of the career's earnings rest on it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FuzzyRecordWeight {
+    pub record: CompensationRecord,
+    pub employer_name: String,
+    pub annual_total_compensation: f64, // AUD
+    pub years_in_effect: f64, // Until the position's next record, its end, or today
+    pub earnings_weight: f64, // AUD earned while the record was in effect
+    pub share_of_earnings: f64, // Percent of the earnings weight of every record
+}
+
+// A position's compensation records in effective-date order, with what changed each time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordLineageEntry {
+    pub record: CompensationRecord,
+    pub annual_base: f64,
+    pub annual_package: f64, // Base, overtime, allowances and bonuses
+    pub change_from_previous: Option<RecordDelta>, // None for the first record
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordDelta {
+    pub days_since_previous: i64,
+    pub base_rate_change: f64,
+    pub annual_base_change: f64,
+    pub annual_base_change_percent: f64,
+    pub annual_package_change: f64,
+    pub super_rate_change: f64, // Percentage points
+}
+
+// A position's allowances for a financial year, split by whether they're taxed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AllowanceSummary {
+    pub position_id: i64,
+    pub financial_year: String,
+    pub effective_date: NaiveDate, // Of the compensation record the allowances come from
+    pub currency: String,
+    pub taxable_total: f64,     // Annual
+    pub non_taxable_total: f64, // Annual
+    pub allowances: Vec<AllowanceBreakdown>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AllowanceBreakdown {
+    pub name: String,
+    pub amount: f64, // Per payment
+    pub frequency: AllowanceFrequency,
+    pub taxable: bool,
+    pub annual_amount: f64,
+}
+
+// Time spent and money earned in one industry across the career
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndustryExposure {
+    pub industry: String,
+    pub position_count: u32,
+    pub years: f64,
+    pub share_of_years: f64, // Percentage of all years worked
+    pub total_earnings: f64, // AUD
+    pub share_of_earnings: f64, // Percentage of career earnings
+}
+
+// How quickly seniority has risen across positions, against a typical pace for the industry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromotionVelocity {
+    pub industry: String,
+    pub typical_years_per_level: f64,
+    pub levels_gained: u32,
+    pub average_years_per_level: Option<f64>, // None until there has been a promotion
+    pub stints: Vec<LevelStint>,
+    pub insights: Vec<EarningsInsight>,
+}
+
+// Continuous time at one seniority level, across however many positions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelStint {
+    pub seniority_level: SeniorityLevel,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>, // None while still at the level
+    pub years: f64,
+    pub stalled: bool, // Well past the typical time at a level
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FinancialYearSummary {
+    pub financial_year: String,
+    pub jurisdiction: Jurisdiction,
+    pub year_start: NaiveDate, // 1 July for AU, 1 April for NZ
+    pub year_end: NaiveDate,
+    pub gross_income: f64,
+    pub tax_withheld: f64,
+    pub net_income: f64,
+    pub super_contributed: f64,
+    pub hours_ordinary: f64,
+    pub hours_overtime: f64,
+    pub average_weekly_gross: f64, // Normalised by the days each pay period covers
+    pub average_weekly_hours: f64,
+    pub periods_recorded: u32,
+    pub expected_periods: u32, // 52/53 weekly, 26/27 fortnightly, 12 monthly per position
+    pub projected_gross_income: f64,
+    pub ato_gross_income: Option<f64>,
+    pub reconciliation_difference: Option<f64>, // ATO gross minus projected gross
+    pub estimated_income_tax: Option<f64>, // AUD; None when income isn't in the jurisdiction's currency
+    pub foreign_income: Vec<ForeignIncome>, // Non-AUD amounts; Australian tax is not applied to these
+    #[serde(default)]
+    pub vested_equity: f64, // AUD, valued at grant; not part of gross income
+    #[serde(default)]
+    pub unvested_equity: f64, // Still to vest after the year, as potential compensation
+    #[serde(default)]
+    pub total_compensation: f64, // Gross income plus vested equity
+    #[serde(default)]
+    pub warnings: Vec<AnalysisWarning>,
+}
+
+// Equity vesting in a financial year across every grant, in AUD at grant value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EquityYear {
+    pub financial_year: String,
+    pub vested: f64,
+    pub unvested: f64, // Granted by the end of the year and still to vest after it
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForeignIncome {
+    pub currency: String,
+    pub original_gross: f64,
+    pub exchange_rate_to_aud: Option<f64>,
+    pub aud_gross: Option<f64>, // None (and excluded from AUD totals) when no rate is available
+}
+
+// Enums
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AustralianState {
+    NSW, VIC, QLD, WA, SA, TAS, ACT, NT,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Qualification {
+    HighSchool,
+    Certificate,
+    Diploma,
+    Bachelor,
+    GraduateCertificate,
+    GraduateDiploma,
+    Masters,
+    PhD,
+    Other(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EmploymentType {
+    Permanent,
+    Contract,
+    Casual,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FIFOTolerance {
+    None,
+    Limited,
+    Regular,
+    Extensive,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TravelTolerance {
+    None,
+    Local,
+    Regional,
+    National,
+    International,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum OvertimeAppetite {
+    None,
+    Minimal,
+    Moderate,
+    High,
+    Extreme,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SeniorityLevel {
+    Entry,
+    Junior,
+    Mid,
+    Senior,
+    Lead,
+    Manager,
+    Director,
+    Executive,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CompensationEntryType {
+    Fuzzy,
+    Exact,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PayType {
+    Salary,
+    Hourly,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum OvertimeFrequency {
+    None,
+    Occasional,
+    Frequent,
+    Extreme,
+}
+
+// Pay by calendar month across years, for seeing which months pay best
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EarningsSeasonality {
+    pub months_of_data: u32, // Whole months with pay entries
+    pub years: Vec<i32>, // Calendar years with at least one whole month
+    pub months: Vec<SeasonalMonth>, // Only months that have been observed
+    pub strongest_month: Option<u32>, // 1-12
+    pub weakest_month: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeasonalMonth {
+    pub month: u32,
+    pub years_observed: u32,
+    pub average_gross: f64,
+    pub average_hours: f64,
+    pub index: f64, // 100 is the average month
+    pub hours_index: f64,
+    pub above_average_percent: f64, // Years this month beat that year's monthly average
+}
+
+// How often overtime shows up in a position's pay entries against the declared record
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OvertimeProfile {
+    pub position_id: i64,
+    pub periods_observed: u32,
+    pub periods_with_overtime: u32,
+    pub observed_percent: f64, // Share of pay periods with any overtime
+    pub average_hours_per_week: f64, // Across every observed week, with or without overtime
+    pub implied_frequency: OvertimeFrequency,
+    pub declared_frequency: Option<OvertimeFrequency>, // None without a compensation record
+    pub declared_hours_per_week: Option<f64>,
+    pub frequency_steps: Option<i32>, // Implied minus declared, e.g. 2 for Occasional to Extreme
+    pub hours_delta: Option<f64>, // Observed minus declared hours a week
+    pub diverges: bool,
+    pub suggested_correction: Option<OvertimeCorrection>,
+}
+
+// Overtime details to save over a record's, through the usual record save
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OvertimeCorrection {
+    pub record_id: i64,
+    pub overtime: OvertimeDetails,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AllowanceFrequency {
+    Weekly,
+    Fortnightly,
+    Monthly,
+    Annually,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum PayslipFrequency {
+    #[default]
+    Weekly,
+    Fortnightly,
+    Monthly,
+}
+
+impl PayslipFrequency {
+    pub fn periods_per_year(&self) -> f64 {
+        match self {
+            PayslipFrequency::Weekly => 52.0,
+            PayslipFrequency::Fortnightly => 26.0,
+            PayslipFrequency::Monthly => 12.0,
+        }
+    }
+
+    /// Periods in an average year of 365.25 days: about 52.18 weeks or 26.09 fortnights.
+    pub fn average_periods_per_year(&self) -> f64 {
+        match self {
+            PayslipFrequency::Weekly => 365.25 / 7.0,
+            PayslipFrequency::Fortnightly => 365.25 / 14.0,
+            PayslipFrequency::Monthly => 12.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum IncomeComponentKind {
+    BaseSalary,
+    Overtime,
+    Allowances,
+    Bonuses,
+    EmployerSuper,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum InsightCategory {
+    Underpaid,
+    FairlyPaid,
+    Overpaid,
+    OvertimeHeavy,
+    LoyaltyTax,
+    MarketOpportunity,
+    SkillsGap,
+    SuperOpportunity,
+    ProgressionStall,
+    DataQuality,
+    LongServiceLeave,
+    IncomeRisk,
+    Insurance,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeeklyCompensationEntry {
+    pub id: Option<i64>,
+    pub position_id: Option<i64>,
+    pub financial_year: String,
+    pub week_ending: NaiveDate, // Last day of the pay period (kept as week_ending for compatibility)
+    #[serde(default)]
+    pub period_start: Option<NaiveDate>, // Derived from week_ending and period_type when omitted
+    #[serde(default)]
+    pub period_type: PayslipFrequency,
+    pub gross_pay: f64,
+    pub tax_withheld: f64,
+    pub net_pay: f64,
+    pub hours_ordinary: f64,
+    pub hours_overtime: f64,
+    pub overtime_rate_multiplier: f64,
+    pub allowances: Vec<Allowance>,
+    pub super_contributed: f64,
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+// Yearly ATO Summary Entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YearlyIncomeEntry {
+    pub id: Option<i64>,
+    pub position_id: Option<i64>,
+    pub financial_year: String,
+    pub gross_income: f64,
+    pub tax_withheld: f64,
+    pub reportable_super: f64,
+    pub reportable_fringe_benefits: Option<f64>,
+    pub allowances: Vec<Allowance>,
+    pub source: IncomeSource,
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    #[serde(default)]
+    pub jurisdiction: Jurisdiction, // NZ entries use the April-March tax year
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl YearlyIncomeEntry {
+    /// Nothing entered yet: a slot for the year's income statement, as the FY rollover creates.
+    pub fn is_placeholder(&self) -> bool {
+        self.gross_income == 0.0 && self.tax_withheld == 0.0 && self.reportable_super == 0.0
+    }
+}
+
+// User-maintained conversion rate, one per currency per financial year
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExchangeRate {
+    pub id: Option<i64>,
+    pub currency: String,
+    pub financial_year: String,
+    pub rate_to_aud: f64, // AUD value of one unit of the currency
+}
+
+// User-entered minimum pay under an award or enterprise agreement classification, from
+// its effective date until the next rate for the same classification
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AwardRate {
+    pub id: Option<i64>,
+    pub award_name: String,
+    pub classification: String,
+    pub effective_date: NaiveDate,
+    pub hourly_minimum: f64,
+    pub overtime_multiplier: f64, // Lowest overtime rate, e.g. 1.5 for the first hours
+    pub casual_loading: f64, // Percent on top of the hourly minimum for casuals
+}
+
+// An insurance policy the user pays for, in AUD a year. Premiums paid through super come out
+// of the balance; income protection held outside super is tax deductible.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InsurancePolicy {
+    pub id: Option<i64>,
+    pub policy_type: InsuranceType,
+    pub provider: String,
+    pub annual_premium: f64,
+    pub through_super: bool,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>, // None while the cover is held
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum InsuranceType {
+    IncomeProtection,
+    TPD, // Total and permanent disability
+    Life,
+}
+
+impl InsurancePolicy {
+    /// Whether the cover is held on the date.
+    pub fn is_held_on(&self, date: NaiveDate) -> bool {
+        self.start_date <= date && self.end_date.is_none_or(|end| end >= date)
+    }
+}
+
+// Whether to point out that no income protection is recorded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InsuranceSettings {
+    pub income_protection_insight: bool,
+    pub income_protection_threshold: f64, // Current annual pay above which the insight shows
+}
+
+impl Default for InsuranceSettings {
+    fn default() -> Self {
+        Self { income_protection_insight: true, income_protection_threshold: 80000.0 }
+    }
+}
+
+// A partner's income for a financial year, AUD. Only used for family thresholds, such as the
+// Medicare levy surcharge tiers, and never exported anonymised.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartnerIncomeEntry {
+    pub id: Option<i64>,
+    pub financial_year: String,
+    pub gross_income: f64, // Income for MLS purposes: taxable income plus reportable super and fringe benefits
+    pub is_estimate: bool,
+}
+
+// A user-defined label such as "FIFO" or "shift work", for grouping and filtering
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tag {
+    pub id: Option<i64>,
+    pub name: String,
+}
+
+// The kinds of record a tag can be put on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TagEntityType {
+    Position,
+    CompensationRecord,
+    WeeklyEntry,
+    YearlyEntry,
+}
+
+// One tag on one record
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityTag {
+    pub tag_id: i64,
+    pub entity_type: TagEntityType,
+    pub entity_id: i64,
+}
+
+// What the household thresholds need beyond the partner's income
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HouseholdSettings {
+    pub private_hospital_cover: Option<bool>, // None when not said, so the surcharge isn't assumed
+    pub oldest_age_on_policy: Option<i32>, // For the rebate; the profile's age when not set
+}
+
+// Medicare levy surcharge and private health insurance rebate income tiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum HealthIncomeTier {
+    Base,
+    Tier1,
+    Tier2,
+    Tier3,
+}
+
+// Where a financial year's income falls for the surcharge and the rebate, as a single or a family
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthCoverPosition {
+    pub financial_year: String,
+    pub income_for_mls: f64, // The user's own
+    pub partner_income: Option<f64>, // Some when a partner's income is recorded
+    pub family: bool,
+    pub tier: HealthIncomeTier,
+    pub next_tier_threshold: Option<f64>, // Income above which the next tier starts; None at tier 3
+    pub medicare_levy_surcharge: f64, // Without private hospital cover
+    pub rebate_percent: f64,
+}
+
+// A raise taking the household into a different surcharge tier
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MlsTierChange {
+    pub family: bool,
+    pub from: HealthIncomeTier,
+    pub to: HealthIncomeTier,
+    pub surcharge_difference: f64, // A year, without private hospital cover
+}
+
+// A compensation record checked against the award rate for its classification
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AwardFinding {
+    pub compensation_record_id: Option<i64>,
+    pub effective_date: NaiveDate,
+    pub award_name: String,
+    pub classification: String,
+    pub minimum_hourly_rate: f64, // Including casual loading for a casual position
+    pub base_hourly_rate: f64,
+    pub minimum_overtime_multiplier: f64,
+    pub overtime_multiplier: f64,
+    pub base_rate_below_minimum: bool,
+    pub overtime_below_minimum: bool,
+    pub periods_underpaid: u32, // Payslips while the record was in force
+    pub estimated_underpayment: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AwardComplianceReport {
+    pub position_id: i64,
+    pub records_checked: u32,
+    pub findings: Vec<AwardFinding>, // Only records with something below the award
+    pub estimated_back_pay: f64,
+}
+
+// Tax jurisdiction of an income record, Australia unless flagged otherwise
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Jurisdiction {
+    #[default]
+    AU,
+    NZ,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum IncomeSource {
+    ATO,
+    Manual,
+}
+
+// A value filled in by a schema migration that the user hasn't confirmed yet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackfillWarning {
+    pub id: i64,
+    pub table_name: String,
+    pub row_id: i64,
+    pub column_name: String,
+    pub default_value: String, // As stored, e.g. "38.0" or "AUD"
+    pub message: String,
+}
+
+// A list query's readable rows, and the ones it had to skip
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ListResult<T> {
+    pub rows: Vec<T>,
+    pub errors: Vec<RowError>,
+}
+
+// A row that failed to read, e.g. a bad date or malformed JSON
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RowError {
+    pub table_name: String,
+    pub row_id: i64,
+    pub message: String,
+}
+
+// A JSON column value that no longer parsed and what it was rewritten to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepairLog {
+    pub table_name: String,
+    pub row_id: i64,
+    pub column_name: String,
+    pub original: String,
+    pub repaired_to: String,
+    pub recovered: bool, // False when the value was reset to an empty list
+}
+
+// Scheduled JSON export to a folder of the user's choosing, e.g. a synced drive
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutoExportSettings {
+    pub enabled: bool,
+    pub path: Option<String>, // Destination folder
+    pub frequency: AutoExportFrequency,
+    pub retention_count: u32, // Exports to keep, 0 keeps all
+}
+
+// The last financial year rollover run
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RolloverSettings {
+    pub last_financial_year: Option<String>,
+    pub last_run_at: Option<DateTime<Utc>>,
+}
+
+// Whether the rollover has been run for the financial year we're in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RolloverStatus {
+    pub current_financial_year: String,
+    pub last_financial_year: Option<String>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub done_for_current: bool,
+}
+
+// The start-of-year checklist: what the rollover did and what the user should look at
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RolloverReport {
+    pub financial_year: String,
+    pub closed_financial_year: String,
+    pub items: Vec<RolloverItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RolloverItem {
+    pub step: RolloverStep,
+    pub outcome: RolloverOutcome,
+    pub message: String,
+    pub related_entity: Option<RelatedEntity>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RolloverStep {
+    YearlyPlaceholder, // An empty yearly entry for each position held into the new year
+    SuperRate, // Latest records against the new year's super guarantee rate
+    Reconciliation, // The closed year's payslips against its income statement
+    MissingWeeks, // Pay periods in the closed year with no entry
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RolloverOutcome {
+    Done,
+    AlreadyDone, // From an earlier run; nothing was changed
+    Ok, // Checked and nothing to do
+    NeedsAttention,
+}
+
+// How far ahead reminders worked out from positions are shown
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReminderSettings {
+    pub horizon_days: u32,
+}
+
+impl Default for ReminderSettings {
+    fn default() -> Self {
+        Self { horizon_days: 60 }
+    }
+}
+
+// A date coming up for a position. The id is the same every time it's worked out, so a
+// dismissal sticks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reminder {
+    pub id: String,
+    pub kind: ReminderKind,
+    pub position_id: i64,
+    pub due_date: NaiveDate,
+    pub days_until: i64,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ReminderKind {
+    ProbationEnding,
+    ContractEnding,
+    WorkAnniversary,
+}
+
+impl Default for AutoExportSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: None,
+            frequency: AutoExportFrequency::Daily,
+            retention_count: 14,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AutoExportFrequency {
+    Daily,
+    Weekly,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AutoExportStatus {
+    pub last_attempt: Option<DateTime<Utc>>,
+    pub last_success: Option<DateTime<Utc>>,
+    pub last_file: Option<String>,
+    pub last_error: Option<String>, // Cleared by the next successful export
+}
+
+// Folder watched for payslip CSVs dropped in by other tools
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchFolderSettings {
+    pub enabled: bool,
+    pub path: Option<String>,
+    pub pattern: String, // File name glob, e.g. "*.csv" or "payslips-*.csv"
+}
+
+impl Default for WatchFolderSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: None,
+            pattern: "*.csv".to_string(),
+        }
+    }
+}
+
+// Result of parsing a CSV without saving it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CsvImportPreview {
+    pub entries: Vec<WeeklyCompensationEntry>,
+    pub errors: Vec<CsvRowError>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CsvRowError {
+    pub line: usize, // 1-based line in the file, the header is line 1
+    pub message: String,
+}
+
+// A watched file parsed and waiting for the user to confirm
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingWatchedImport {
+    pub path: String,
+    pub detected_at: DateTime<Utc>,
+    pub preview: CsvImportPreview,
+}
+
+// What confirming a watched file saved, and the pay periods it left alone as already recorded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchedImportResult {
+    pub imported_count: usize,
+    pub skipped_period_endings: Vec<NaiveDate>,
+}
+
+// Column layout of another tool's CSV, mapped onto one kind of CareerFlow record
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportMapping {
+    pub name: String,
+    pub target: ImportTarget,
+    pub columns: Vec<ColumnMapping>,
+    #[serde(default)]
+    pub builtin: bool, // Presets shipped with the app, cannot be overwritten
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ImportTarget {
+    Positions,
+    CompensationRecords,
+    WeeklyEntries,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnMapping {
+    pub source: String, // Header in the file, matched ignoring case
+    pub field: String,  // Field on the target, dotted for nested ones, e.g. "overtime.rate_multiplier"
+    #[serde(default)]
+    pub coercion: Option<Coercion>, // Defaults to the field's usual type
+    #[serde(default)]
+    pub default_value: Option<String>, // Used when the cell is empty or the column is absent
+}
+
+// A cell or row that could not be imported
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CellError {
+    pub line: usize, // 1-based line in the file, the header is line 1
+    pub column: Option<String>, // None when the whole row is at fault
+    pub value: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MappedImportResult {
+    pub target: ImportTarget,
+    pub dry_run: bool,
+    pub rows: usize,
+    pub positions: Vec<Position>,
+    pub compensation_records: Vec<CompensationRecord>,
+    pub weekly_entries: Vec<WeeklyCompensationEntry>,
+    pub errors: Vec<CellError>,
+    pub imported_count: usize, // Nothing is imported while any row has errors
+}
+
+// Outcome of opening the database and bringing its schema up to date
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseStatus {
+    pub state: DatabaseState,
+    pub schema_version: u32,
+    pub error: Option<String>, // Why the last migration failed
+    pub possible_data_rollback: Option<DataRollback>, // Tables with fewer writes than at the last clean shutdown
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DatabaseState {
+    Ready,
+    MigrationFailed, // Rolled back to the copy taken before migrating, still usable at the old version
+}
+
+// Writes to one table, counted by triggers so every save path is included
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableModified {
+    pub table_name: String,
+    pub write_count: i64, // Rows inserted, updated or deleted since tracking began
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+// Write counts saved when the app last closed normally
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShutdownState {
+    pub recorded_at: DateTime<Utc>,
+    pub tables: Vec<TableModified>,
+}
+
+// The database holds fewer writes than it did at the last clean shutdown, most likely because
+// a sync tool put back an older copy. Detection only: nothing is changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataRollback {
+    pub recorded_at: DateTime<Utc>, // When the expected counts were saved
+    pub tables: Vec<TableRegression>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableRegression {
+    pub table_name: String,
+    pub expected_write_count: i64,
+    pub actual_write_count: i64,
+    pub writes_missing: i64,
+    pub expected_last_modified: Option<DateTime<Utc>>,
+    pub actual_last_modified: Option<DateTime<Utc>>,
+}
+
+// Whether this window can save, or another running instance holds the database
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AppMode {
+    Writable,
+    ReadOnly,
+}
+
+// A record that fails the checks applied when saving, or looks wrong alongside the others
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    pub record_type: String,
+    pub id: Option<i64>,
+    pub message: String,
+    #[serde(default)]
+    pub severity: IssueSeverity,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum IssueSeverity {
+    #[default]
+    Error,   // Fails the save-time checks
+    Warning, // Allowed, but probably a mistake
+}
+
+// Data Export/Import structure for backup and restore
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataExport {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ExportMetadata>, // Not covered by the checksum; absent from older exports
+    pub user_profile: Option<UserProfile>,
+    pub positions: Vec<Position>,
+    pub compensation_records: Vec<CompensationRecord>,
+    pub weekly_entries: Vec<WeeklyCompensationEntry>,
+    pub yearly_entries: Vec<YearlyIncomeEntry>,
+    #[serde(default)]
+    pub exchange_rates: Vec<ExchangeRate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub award_rates: Vec<AwardRate>, // Left out when empty so older exports keep their checksum
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scenarios: Vec<Scenario>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub templates: Vec<PositionTemplate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub insurance_policies: Vec<InsurancePolicy>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub partner_income_entries: Vec<PartnerIncomeEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entity_tags: Vec<EntityTag>,
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>, // SHA-256 hex of the export without metadata or checksum
+}
+
+// What differs between two exports of the same data
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportMetadata {
+    pub export_date: DateTime<Utc>,
+}
+
+// Export with identifying details removed, for contributing to benchmarks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnonymizedExport {
+    pub profile: AnonymizedProfile,
+    pub positions: Vec<AnonymizedPosition>,
+    pub compensation_records: Vec<CompensationRecord>, // Notes removed
+    pub weekly_entries: Vec<WeeklyCompensationEntry>,  // Notes removed
+    pub yearly_entries: Vec<YearlyIncomeEntry>,        // Notes removed
+    pub export_date: DateTime<Utc>,
+    pub version: String,
+}
+
+// Anonymised analysis inputs and the outputs computed from them, attached to calculation bug reports
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiagnosticBundle {
+    pub app_version: String,
+    pub data: AnonymizedExport,
+    pub exchange_rates: Vec<ExchangeRate>,
+    pub settings: BTreeMap<String, serde_json::Value>, // Saved settings the calculations read, by key
+    pub loyalty_tax: LoyaltyTaxAnalysis,
+    pub earnings_analysis: EarningsAnalysis,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnonymizedProfile {
+    pub age_band: String, // e.g. "25-34"
+    pub state: AustralianState,
+    pub industry: String,
+    pub highest_qualification: Qualification,
+    pub standard_weekly_hours: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnonymizedPosition {
+    pub id: Option<i64>,
+    pub employer: String, // Token such as "Mining employer 2", the same for every role at one employer
+    pub job_title: String,
+    pub employment_type: EmploymentType,
+    pub state: Option<AustralianState>, // Coarsened from the location, None when overseas or unknown
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub seniority_level: SeniorityLevel,
+    pub tools_systems_skills: Vec<String>,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportResult {
+    pub success: bool,
+    pub profile_imported: bool,
+    pub positions_count: usize,
+    pub compensation_count: usize,
+    pub weekly_count: usize,
+    pub yearly_count: usize,
+    pub exchange_rate_count: usize,
+    #[serde(default)]
+    pub award_rate_count: usize,
+    #[serde(default)]
+    pub scenario_count: usize,
+    #[serde(default)]
+    pub template_count: usize,
+    #[serde(default)]
+    pub insurance_policy_count: usize,
+    #[serde(default)]
+    pub partner_income_count: usize,
+    #[serde(default)]
+    pub tag_count: usize,
+    #[serde(default)]
+    pub warnings: Vec<String>, // Imported anyway, e.g. a checksum that doesn't match
+}
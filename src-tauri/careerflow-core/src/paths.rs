@@ -0,0 +1,25 @@
+use std::path::PathBuf;
+
+/// Bundle identifier the app data directory is named after; must match
+/// `tauri.bundle.identifier` in tauri.conf.json.
+pub const APP_IDENTIFIER: &str = "com.careerflow.app";
+
+/// File name of the database inside the app data directory.
+pub const DATABASE_FILE_NAME: &str = "careerflow.db";
+
+/// Where the platform keeps per-user app data. The desktop app answers this from Tauri's
+/// path resolver so every front end agrees on the database location.
+pub trait AppDataDir {
+    fn app_data_dir(&self) -> Option<PathBuf>;
+}
+
+/// Path to the database, creating the app data directory if it doesn't exist yet.
+pub fn database_path(dirs: &impl AppDataDir) -> Result<PathBuf, String> {
+    let app_data_dir = dirs.app_data_dir()
+        .ok_or_else(|| "Failed to resolve app data directory".to_string())?;
+
+    std::fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_data_dir.join(DATABASE_FILE_NAME))
+}
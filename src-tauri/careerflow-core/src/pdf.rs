@@ -0,0 +1,160 @@
+use std::fmt::Write;
+
+// A4 in points, origin at the bottom left
+pub const PAGE_WIDTH: f64 = 595.0;
+pub const PAGE_HEIGHT: f64 = 842.0;
+
+/// The two standard fonts every PDF reader has, so nothing needs embedding.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Font {
+    Regular, // Helvetica
+    Bold,    // Helvetica-Bold
+}
+
+/// The drawing operators for one page.
+#[derive(Debug, Default, Clone)]
+pub struct Page {
+    content: String,
+}
+
+impl Page {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Text with its baseline starting at (x, y).
+    pub fn text(&mut self, x: f64, y: f64, size: f64, font: Font, text: &str) {
+        let font = match font {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        };
+        let _ = writeln!(self.content, "BT /{} {:.1} Tf {:.2} {:.2} Td ({}) Tj ET", font, size, x, y, escape(text));
+    }
+
+    /// Text ending at x, for right-aligned columns of figures.
+    pub fn text_right(&mut self, x: f64, y: f64, size: f64, font: Font, text: &str) {
+        self.text(x - text_width(text, size, font), y, size, font, text);
+    }
+
+    pub fn line(&mut self, from: (f64, f64), to: (f64, f64), width: f64, gray: f64) {
+        let _ = writeln!(
+            self.content,
+            "q {:.2} w {:.2} G {:.2} {:.2} m {:.2} {:.2} l S Q",
+            width, gray, from.0, from.1, to.0, to.1
+        );
+    }
+
+    pub fn polyline(&mut self, points: &[(f64, f64)], width: f64, gray: f64) {
+        let Some(((x, y), rest)) = points.split_first().map(|(first, rest)| (*first, rest)) else {
+            return;
+        };
+        let mut path = format!("q {:.2} w {:.2} G {:.2} {:.2} m", width, gray, x, y);
+        for (x, y) in rest {
+            let _ = write!(path, " {:.2} {:.2} l", x, y);
+        }
+        let _ = writeln!(self.content, "{} S Q", path);
+    }
+
+    /// A filled rectangle; 0.0 is black and 1.0 white.
+    pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, gray: f64) {
+        let _ = writeln!(self.content, "q {:.2} g {:.2} {:.2} {:.2} {:.2} re f Q", gray, x, y, width, height);
+    }
+}
+
+/// Approximate width of text in the standard Helvetica metrics, for alignment and wrapping.
+pub fn text_width(text: &str, size: f64, font: Font) -> f64 {
+    let em: f64 = text.chars().map(|c| match c {
+        'i' | 'j' | 'l' | '.' | ',' | ':' | ';' | '\'' | '|' | '!' => 0.25,
+        'f' | 't' | 'r' | ' ' | '(' | ')' | '-' | '/' | 'I' => 0.33,
+        'm' | 'w' | 'M' | 'W' | '%' | '@' => 0.85,
+        'A'..='Z' => 0.68,
+        '0'..='9' | '$' => 0.56,
+        _ => 0.52,
+    }).sum();
+    let bold = if font == Font::Bold { 1.06 } else { 1.0 };
+    em * size * bold
+}
+
+/// Splits text into lines no wider than `width`, breaking at spaces.
+pub fn wrap(text: &str, width: f64, size: f64, font: Font) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+            if !line.is_empty() && text_width(&candidate, size, font) > width {
+                lines.push(std::mem::replace(&mut line, word.to_string()));
+            } else {
+                line = candidate;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Writes the pages as a PDF 1.4 document.
+pub fn render(pages: &[Page]) -> Vec<u8> {
+    let mut objects: Vec<String> = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        String::new(), // Page tree, once the page object numbers are known
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>".to_string(),
+    ];
+
+    let mut kids = Vec::new();
+    for page in pages {
+        let page_number = objects.len() + 1;
+        kids.push(format!("{} 0 R", page_number));
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH, PAGE_HEIGHT, page_number + 1
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            page.content.len(), page.content
+        ));
+    }
+    objects[1] = format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), pages.len());
+
+    let mut out = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        let _ = write!(out, "{} 0 obj\n{}\nendobj\n", i + 1, object);
+    }
+
+    let xref = out.len();
+    let _ = write!(out, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(out, "{:010} 00000 n ", offset);
+    }
+    let _ = write!(out, "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", objects.len() + 1, xref);
+
+    // Content is ASCII: escape() writes anything else as octal codes
+    out.into_bytes()
+}
+
+// Latin-1 characters keep their WinAnsi code; typographic punctuation becomes its ASCII
+// equivalent and anything else a question mark.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | '(' | ')' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            ' '..='~' => escaped.push(c),
+            '\u{2018}' | '\u{2019}' => escaped.push('\''),
+            '\u{201C}' | '\u{201D}' => escaped.push('"'),
+            '\u{2013}' | '\u{2014}' => escaped.push('-'),
+            '\u{2026}' => escaped.push_str("..."),
+            '\u{A0}'..='\u{FF}' => {
+                let _ = write!(escaped, "\\{:03o}", c as u32);
+            }
+            _ => escaped.push('?'),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,83 @@
+use crate::models::*;
+use chrono::{Months, NaiveDate};
+
+pub const SETTINGS_KEY: &str = "reminder_settings";
+
+/// Reminders due between `today` and `horizon_days` ahead, worked out from the positions:
+/// probation ending, a fixed-term contract's end date, and work anniversaries of positions
+/// still held. Dismissed ids are left out. Sorted by due date.
+pub fn upcoming_reminders(
+    positions: &[Position],
+    settings: &ReminderSettings,
+    dismissed: &[String],
+    today: NaiveDate,
+) -> Vec<Reminder> {
+    let horizon = today + chrono::Days::new(settings.horizon_days as u64);
+    let mut reminders = Vec::new();
+
+    for position in positions {
+        let Some(position_id) = position.id else {
+            continue;
+        };
+        let held_on = |date: NaiveDate| position.start_date <= date && position.end_date.is_none_or(|end| end >= date);
+        let mut add = |kind: ReminderKind, due_date: NaiveDate, title: String| {
+            if due_date < today || due_date > horizon {
+                return;
+            }
+            let id = format!("{}:{}:{}", reminder_key(kind), position_id, due_date);
+            if dismissed.contains(&id) {
+                return;
+            }
+            reminders.push(Reminder {
+                id,
+                kind,
+                position_id,
+                due_date,
+                days_until: (due_date - today).num_days(),
+                title,
+            });
+        };
+
+        if let Some(months) = position.probation_months {
+            if let Some(due_date) = position.start_date.checked_add_months(Months::new(months)).filter(|d| held_on(*d)) {
+                add(ReminderKind::ProbationEnding, due_date, format!(
+                    "Probation as {} at {} ends",
+                    position.job_title, position.employer_name
+                ));
+            }
+        }
+
+        if matches!(position.employment_type, EmploymentType::Contract) {
+            if let Some(end_date) = position.end_date {
+                add(ReminderKind::ContractEnding, end_date, format!(
+                    "Contract as {} at {} ends",
+                    position.job_title, position.employer_name
+                ));
+            }
+        }
+
+        // The next anniversary on or after today; a 29 February start falls on the 28th
+        let years = (1..)
+            .map_while(|years: u32| position.start_date.checked_add_months(Months::new(12 * years)).map(|d| (years, d)))
+            .find(|(_, date)| *date >= today);
+        if let Some((years, date)) = years.filter(|(_, date)| held_on(*date)) {
+            add(ReminderKind::WorkAnniversary, date, format!(
+                "{} {} at {}",
+                years,
+                if years == 1 { "year" } else { "years" },
+                position.employer_name
+            ));
+        }
+    }
+
+    reminders.sort_by(|a, b| a.due_date.cmp(&b.due_date).then_with(|| a.id.cmp(&b.id)));
+    reminders
+}
+
+fn reminder_key(kind: ReminderKind) -> &'static str {
+    match kind {
+        ReminderKind::ProbationEnding => "probation",
+        ReminderKind::ContractEnding => "contract-end",
+        ReminderKind::WorkAnniversary => "anniversary",
+    }
+}
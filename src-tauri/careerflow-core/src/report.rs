@@ -0,0 +1,609 @@
+use crate::models::*;
+use crate::pdf::{self, Font, Page, PAGE_HEIGHT, PAGE_WIDTH};
+use crate::dates::YearBasis;
+use crate::svg_chart::{self, escape};
+use chrono::NaiveDate;
+use std::fmt::Write;
+
+const MARGIN: f64 = 50.0;
+const FOOTER_HEIGHT: f64 = 20.0;
+const CONTENT_WIDTH: f64 = PAGE_WIDTH - 2.0 * MARGIN;
+const BODY_SIZE: f64 = 10.0;
+const LINE_HEIGHT: f64 = 14.0;
+const CHART_HEIGHT: f64 = 150.0;
+const CHART_AXIS_WIDTH: f64 = 50.0; // Room for the value labels
+const MIN_LABEL_SPACING: f64 = 36.0;
+
+/// The earnings analysis, loyalty tax and financial year results as a PDF for an adviser.
+///
+/// Tables carry on over as many pages as they need, repeating their header, and charts thin
+/// out their axis labels, so a long career doesn't overflow the page. `redact_personal`
+/// leaves out the name and date of birth; employer names are kept.
+pub fn analysis_report_pdf(
+    profile: Option<&UserProfile>,
+    analysis: &EarningsAnalysis,
+    loyalty_tax: &LoyaltyTaxAnalysis,
+    financial_years: &[FinancialYearSummary],
+    redact_personal: bool,
+    generated_on: NaiveDate,
+) -> Vec<u8> {
+    let mut layout = Layout::new();
+    for block in report_blocks(profile, analysis, loyalty_tax, financial_years, redact_personal, generated_on) {
+        layout.block(&block);
+    }
+    layout.finish()
+}
+
+/// The same report as [`analysis_report_pdf`], as a single HTML file.
+///
+/// Styles and charts are inline and nothing is linked, so the file opens offline and viewing
+/// it never makes a request.
+pub fn analysis_report_html(
+    profile: Option<&UserProfile>,
+    analysis: &EarningsAnalysis,
+    loyalty_tax: &LoyaltyTaxAnalysis,
+    financial_years: &[FinancialYearSummary],
+    redact_personal: bool,
+    generated_on: NaiveDate,
+) -> String {
+    let blocks = report_blocks(profile, analysis, loyalty_tax, financial_years, redact_personal, generated_on);
+    let mut body = String::new();
+    for block in &blocks {
+        html_block(&mut body, block);
+    }
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Career earnings report</title>\n<style>{}</style>\n</head>\n<body>\n{}<footer>CareerFlow - general information, not financial advice</footer>\n</body>\n</html>\n",
+        HTML_STYLE, body
+    )
+}
+
+// What the report says, independent of the format it's written in
+enum Block {
+    Title(String),
+    Heading(String),
+    Subheading(String),
+    Paragraph(String),
+    Bullet(String),
+    KeyValues(Vec<(String, String)>),
+    Table { columns: Vec<Column>, rows: Vec<Vec<String>> },
+    BarChart { title: String, data: Vec<(String, f64)> },
+    LineChart { title: String, data: Vec<(String, f64)> },
+    Gap, // Extra space on the page; the HTML styles handle spacing themselves
+}
+
+fn report_blocks(
+    profile: Option<&UserProfile>,
+    analysis: &EarningsAnalysis,
+    loyalty_tax: &LoyaltyTaxAnalysis,
+    financial_years: &[FinancialYearSummary],
+    redact_personal: bool,
+    generated_on: NaiveDate,
+) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    summary_section(&mut blocks, profile, analysis, loyalty_tax, redact_personal, generated_on);
+    earnings_section(&mut blocks, &analysis.earnings_over_time);
+    financial_years_section(&mut blocks, financial_years);
+    loyalty_tax_section(&mut blocks, loyalty_tax);
+    insights_section(&mut blocks, &analysis.insights);
+    blocks
+}
+
+fn summary_section(
+    blocks: &mut Vec<Block>,
+    profile: Option<&UserProfile>,
+    analysis: &EarningsAnalysis,
+    loyalty_tax: &LoyaltyTaxAnalysis,
+    redact_personal: bool,
+    generated_on: NaiveDate,
+) {
+    blocks.push(Block::Title("Career earnings report".to_string()));
+    let prepared = match profile.filter(|_| !redact_personal) {
+        Some(p) => format!(
+            "Prepared {} for {} {}, born {}",
+            date(generated_on), p.first_name, p.last_name, date(p.date_of_birth)
+        ),
+        None => format!("Prepared {}", date(generated_on)),
+    };
+    blocks.push(Block::Paragraph(prepared));
+    blocks.push(Block::Gap);
+
+    let mut figures = vec![
+        ("Current total compensation".to_string(), money(analysis.current_total_compensation)),
+        ("Effective hourly rate".to_string(), format!("{}/hr", money_cents(analysis.current_effective_hourly_rate))),
+        ("Income percentile".to_string(), format!("{:.0}th", analysis.income_percentile)),
+        ("Cumulative loyalty tax".to_string(), money(loyalty_tax.cumulative_loyalty_tax)),
+    ];
+    if !analysis.current_positions.is_empty() {
+        let roles: Vec<String> = analysis.current_positions.iter()
+            .map(|p| format!("{} at {}", p.job_title, p.employer_name))
+            .collect();
+        figures.push(("Current roles".to_string(), roles.join("; ")));
+    }
+    blocks.push(Block::KeyValues(figures));
+
+    let bars = analysis.earnings_over_time.iter()
+        .map(|s| (YearBasis::Financial.label(s.date), s.actual_annual))
+        .collect();
+    blocks.push(Block::BarChart { title: "Actual annual earnings".to_string(), data: bars });
+}
+
+fn earnings_section(blocks: &mut Vec<Block>, snapshots: &[EarningsSnapshot]) {
+    blocks.push(Block::Heading("Earnings over time".to_string()));
+    if snapshots.is_empty() {
+        blocks.push(Block::Paragraph("No compensation records yet.".to_string()));
+        return;
+    }
+
+    let points = snapshots.iter()
+        .map(|s| (YearBasis::Financial.label(s.date), s.effective_hourly_rate))
+        .collect();
+    blocks.push(Block::LineChart { title: "Effective hourly rate".to_string(), data: points });
+
+    let rows = snapshots.iter()
+        .map(|s| vec![
+            date(s.date),
+            money(s.base_annual),
+            money(s.actual_annual),
+            money(s.total_with_super),
+            money_cents(s.effective_hourly_rate),
+        ])
+        .collect();
+    blocks.push(Block::Table {
+        columns: vec![
+            Column::left("From", 95.0),
+            Column::right("Base", 100.0),
+            Column::right("Actual", 100.0),
+            Column::right("With super", 100.0),
+            Column::right("Per hour", 100.0),
+        ],
+        rows,
+    });
+}
+
+fn financial_years_section(blocks: &mut Vec<Block>, financial_years: &[FinancialYearSummary]) {
+    blocks.push(Block::Heading("Financial years".to_string()));
+    if financial_years.is_empty() {
+        blocks.push(Block::Paragraph("No payslips or ATO income statements recorded.".to_string()));
+        return;
+    }
+
+    let rows = financial_years.iter()
+        .map(|fy| vec![
+            fy.financial_year.clone(),
+            money(fy.gross_income),
+            money(fy.tax_withheld),
+            money(fy.net_income),
+            money(fy.super_contributed),
+            format!("{:.0}", fy.hours_ordinary + fy.hours_overtime),
+        ])
+        .collect();
+    blocks.push(Block::Table {
+        columns: vec![
+            Column::left("Year", 75.0),
+            Column::right("Gross", 85.0),
+            Column::right("Tax withheld", 85.0),
+            Column::right("Net", 85.0),
+            Column::right("Super", 85.0),
+            Column::right("Hours", 80.0),
+        ],
+        rows,
+    });
+}
+
+fn loyalty_tax_section(blocks: &mut Vec<Block>, loyalty_tax: &LoyaltyTaxAnalysis) {
+    blocks.push(Block::Heading("Loyalty tax".to_string()));
+    let market = &loyalty_tax.market_comparison;
+    blocks.push(Block::Paragraph(format!(
+        "Compared with industry growth of {:.1}% a year, {:.1}% for the role level and {:.1}% after inflation. Cumulative loyalty tax: {}.",
+        market.industry_average_growth, market.role_level_growth, market.cpi_adjusted_growth,
+        money(loyalty_tax.cumulative_loyalty_tax)
+    )));
+    blocks.push(Block::Gap);
+
+    if loyalty_tax.tenure_blocks.is_empty() {
+        blocks.push(Block::Paragraph("No positions recorded.".to_string()));
+    } else {
+        let rows = loyalty_tax.tenure_blocks.iter()
+            .map(|block| vec![
+                block.employer_name.clone(),
+                date(block.start_date),
+                block.end_date.map(date).unwrap_or_else(|| "Present".to_string()),
+                format!("{:.1}", block.years_of_service),
+                format!("{:.1}%", block.actual_progression),
+                format!("{:.1}%", block.market_expected_progression),
+                money(block.loyalty_tax_impact),
+            ])
+            .collect();
+        blocks.push(Block::Table {
+            columns: vec![
+                Column::left("Employer", 120.0),
+                Column::left("From", 62.0),
+                Column::left("To", 62.0),
+                Column::right("Years", 40.0),
+                Column::right("Actual", 55.0),
+                Column::right("Market", 55.0),
+                Column::right("Loyalty tax", 101.0),
+            ],
+            rows,
+        });
+    }
+
+    if loyalty_tax.annual_loyalty_tax.iter().any(|y| y.loyalty_tax_amount > 0.0) {
+        let bars = loyalty_tax.annual_loyalty_tax.iter()
+            .map(|y| (y.year.to_string(), y.loyalty_tax_amount))
+            .collect();
+        blocks.push(Block::BarChart { title: "Loyalty tax by year".to_string(), data: bars });
+    }
+}
+
+fn insights_section(blocks: &mut Vec<Block>, insights: &[EarningsInsight]) {
+    blocks.push(Block::Heading("Insights".to_string()));
+    if insights.is_empty() {
+        blocks.push(Block::Paragraph("Nothing to flag.".to_string()));
+        return;
+    }
+
+    for insight in insights {
+        blocks.push(Block::Subheading(insight.title.clone()));
+        blocks.push(Block::Paragraph(insight.description.clone()));
+        for point in &insight.data_points {
+            blocks.push(Block::Bullet(point.clone()));
+        }
+        blocks.push(Block::Paragraph(format!("Confidence: {:.0}%", insight.confidence_level * 100.0)));
+        blocks.push(Block::Gap);
+    }
+}
+
+struct Column {
+    title: &'static str,
+    width: f64,
+    right_aligned: bool,
+}
+
+impl Column {
+    fn left(title: &'static str, width: f64) -> Self {
+        Column { title, width, right_aligned: false }
+    }
+
+    fn right(title: &'static str, width: f64) -> Self {
+        Column { title, width, right_aligned: true }
+    }
+}
+
+// A cursor down the page that starts a new page whenever the next block won't fit
+struct Layout {
+    pages: Vec<Page>,
+    y: f64,
+}
+
+impl Layout {
+    fn new() -> Self {
+        Layout { pages: vec![Page::new()], y: PAGE_HEIGHT - MARGIN }
+    }
+
+    fn block(&mut self, block: &Block) {
+        match block {
+            Block::Title(text) => self.title(text),
+            Block::Heading(text) => self.heading(text),
+            Block::Subheading(text) => {
+                self.ensure(LINE_HEIGHT * 3.0);
+                self.line(text, Font::Bold, BODY_SIZE + 1.0);
+            }
+            Block::Paragraph(text) => self.paragraph(text, 0.0),
+            Block::Bullet(text) => self.paragraph(&format!("- {}", text), 12.0),
+            Block::KeyValues(rows) => self.key_values(rows),
+            Block::Table { columns, rows } => self.table(columns, rows),
+            Block::BarChart { title, data } => self.bar_chart(title, data),
+            Block::LineChart { title, data } => self.line_chart(title, data),
+            Block::Gap => self.gap(LINE_HEIGHT / 2.0),
+        }
+    }
+
+    fn page(&mut self) -> &mut Page {
+        if self.pages.is_empty() {
+            self.pages.push(Page::new());
+        }
+        let last = self.pages.len() - 1;
+        &mut self.pages[last]
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(Page::new());
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    fn ensure(&mut self, height: f64) {
+        if self.y - height < MARGIN + FOOTER_HEIGHT {
+            self.new_page();
+        }
+    }
+
+    fn gap(&mut self, height: f64) {
+        self.y -= height;
+    }
+
+    fn title(&mut self, text: &str) {
+        self.y -= 20.0;
+        let y = self.y;
+        self.page().text(MARGIN, y, 20.0, Font::Bold, text);
+        self.y -= 14.0;
+    }
+
+    fn heading(&mut self, text: &str) {
+        self.gap(LINE_HEIGHT);
+        // Keep a heading with at least the first few lines under it
+        self.ensure(24.0 + LINE_HEIGHT * 3.0);
+        self.y -= 14.0;
+        let y = self.y;
+        self.page().text(MARGIN, y, 14.0, Font::Bold, text);
+        self.y -= 10.0;
+    }
+
+    fn line(&mut self, text: &str, font: Font, size: f64) {
+        self.ensure(LINE_HEIGHT);
+        self.y -= LINE_HEIGHT;
+        let y = self.y;
+        self.page().text(MARGIN, y, size, font, text);
+    }
+
+    fn paragraph(&mut self, text: &str, indent: f64) {
+        for line in pdf::wrap(text, CONTENT_WIDTH - indent, BODY_SIZE, Font::Regular) {
+            self.ensure(LINE_HEIGHT);
+            self.y -= LINE_HEIGHT;
+            let y = self.y;
+            self.page().text(MARGIN + indent, y, BODY_SIZE, Font::Regular, &line);
+        }
+    }
+
+    fn key_values(&mut self, rows: &[(String, String)]) {
+        let label_width = 170.0;
+        for (label, value) in rows {
+            let lines = pdf::wrap(value, CONTENT_WIDTH - label_width, BODY_SIZE, Font::Regular);
+            for (i, line) in lines.iter().enumerate() {
+                self.ensure(LINE_HEIGHT);
+                self.y -= LINE_HEIGHT;
+                let y = self.y;
+                if i == 0 {
+                    self.page().text(MARGIN, y, BODY_SIZE, Font::Bold, label);
+                }
+                self.page().text(MARGIN + label_width, y, BODY_SIZE, Font::Regular, line);
+            }
+        }
+    }
+
+    fn table(&mut self, columns: &[Column], rows: &[Vec<String>]) {
+        self.ensure(LINE_HEIGHT * 3.0);
+        self.table_header(columns);
+        for (i, row) in rows.iter().enumerate() {
+            if self.y - LINE_HEIGHT < MARGIN + FOOTER_HEIGHT {
+                self.new_page();
+                self.table_header(columns);
+            }
+            self.y -= LINE_HEIGHT;
+            let y = self.y;
+            let page = self.page();
+            if i % 2 == 1 {
+                page.rect(MARGIN, y - 4.0, CONTENT_WIDTH, LINE_HEIGHT, 0.95);
+            }
+            let mut x = MARGIN;
+            for (column, cell) in columns.iter().zip(row) {
+                let cell = truncate(cell, column.width - 6.0);
+                if column.right_aligned {
+                    page.text_right(x + column.width - 4.0, y, BODY_SIZE - 1.0, Font::Regular, &cell);
+                } else {
+                    page.text(x + 2.0, y, BODY_SIZE - 1.0, Font::Regular, &cell);
+                }
+                x += column.width;
+            }
+        }
+    }
+
+    fn table_header(&mut self, columns: &[Column]) {
+        self.y -= LINE_HEIGHT + 2.0;
+        let y = self.y;
+        let page = self.page();
+        page.rect(MARGIN, y - 5.0, CONTENT_WIDTH, LINE_HEIGHT + 2.0, 0.85);
+        let mut x = MARGIN;
+        for column in columns {
+            if column.right_aligned {
+                page.text_right(x + column.width - 4.0, y, BODY_SIZE - 1.0, Font::Bold, column.title);
+            } else {
+                page.text(x + 2.0, y, BODY_SIZE - 1.0, Font::Bold, column.title);
+            }
+            x += column.width;
+        }
+    }
+
+    fn bar_chart(&mut self, title: &str, bars: &[(String, f64)]) {
+        let Some(frame) = self.chart_frame(title, bars) else { return };
+        let slot = frame.width / bars.len() as f64;
+        let page = self.page();
+        for (i, (_, value)) in bars.iter().enumerate() {
+            let height = value.max(0.0) / frame.max * frame.height;
+            page.rect(frame.x + slot * i as f64 + slot * 0.15, frame.y, slot * 0.7, height, 0.45);
+        }
+    }
+
+    fn line_chart(&mut self, title: &str, points: &[(String, f64)]) {
+        let Some(frame) = self.chart_frame(title, points) else { return };
+        let slot = frame.width / points.len() as f64;
+        let path: Vec<(f64, f64)> = points.iter().enumerate()
+            .map(|(i, (_, value))| (
+                frame.x + slot * (i as f64 + 0.5),
+                frame.y + value.max(0.0) / frame.max * frame.height,
+            ))
+            .collect();
+        let page = self.page();
+        page.polyline(&path, 1.5, 0.2);
+        for (x, y) in &path {
+            page.rect(x - 1.5, y - 1.5, 3.0, 3.0, 0.2);
+        }
+    }
+
+    // Draws the title, axes, gridlines and the category labels, leaving the plot to the caller
+    fn chart_frame(&mut self, title: &str, values: &[(String, f64)]) -> Option<ChartFrame> {
+        let max = values.iter().map(|(_, v)| *v).fold(0.0, f64::max);
+        if values.is_empty() || max <= 0.0 {
+            return None;
+        }
+
+        self.ensure(CHART_HEIGHT + LINE_HEIGHT * 3.0);
+        self.line(title, Font::Bold, BODY_SIZE);
+        self.y -= CHART_HEIGHT + 6.0;
+        let frame = ChartFrame {
+            x: MARGIN + CHART_AXIS_WIDTH,
+            y: self.y,
+            width: CONTENT_WIDTH - CHART_AXIS_WIDTH,
+            height: CHART_HEIGHT,
+            max,
+        };
+        let page = self.page();
+
+        for step in 0..=4 {
+            let value = max * step as f64 / 4.0;
+            let y = frame.y + frame.height * step as f64 / 4.0;
+            page.line((frame.x, y), (frame.x + frame.width, y), 0.5, if step == 0 { 0.0 } else { 0.8 });
+            page.text_right(frame.x - 4.0, y - 3.0, BODY_SIZE - 2.0, Font::Regular, &compact_money(value));
+        }
+        page.line((frame.x, frame.y), (frame.x, frame.y + frame.height), 0.5, 0.0);
+
+        // Every nth label, so twenty-odd years of snapshots don't run into each other
+        let slot = frame.width / values.len() as f64;
+        let every = (MIN_LABEL_SPACING / slot).ceil().max(1.0) as usize;
+        for (i, (label, _)) in values.iter().enumerate().step_by(every) {
+            let centre = frame.x + slot * (i as f64 + 0.5);
+            let width = pdf::text_width(label, BODY_SIZE - 2.0, Font::Regular);
+            page.text(centre - width / 2.0, frame.y - 12.0, BODY_SIZE - 2.0, Font::Regular, label);
+        }
+
+        self.y -= 18.0;
+        Some(frame)
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        let total = self.pages.len();
+        for (i, page) in self.pages.iter_mut().enumerate() {
+            page.text(MARGIN, MARGIN - 10.0, BODY_SIZE - 2.0, Font::Regular, "CareerFlow - general information, not financial advice");
+            page.text_right(PAGE_WIDTH - MARGIN, MARGIN - 10.0, BODY_SIZE - 2.0, Font::Regular, &format!("Page {} of {}", i + 1, total));
+        }
+        pdf::render(&self.pages)
+    }
+}
+
+struct ChartFrame {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    max: f64,
+}
+
+const HTML_STYLE: &str = "
+body { font-family: Helvetica, Arial, sans-serif; color: #111827; max-width: 50rem; margin: 2rem auto; padding: 0 1rem; font-size: 0.95rem; line-height: 1.45; }
+h1 { font-size: 1.8rem; margin-bottom: 0.25rem; }
+h2 { font-size: 1.25rem; margin-top: 2rem; border-bottom: 1px solid #d1d5db; padding-bottom: 0.25rem; }
+h3 { font-size: 1rem; margin: 1.25rem 0 0.25rem; }
+p { margin: 0.35rem 0; }
+p.bullet { margin-left: 1rem; }
+dl { display: grid; grid-template-columns: 14rem 1fr; gap: 0.25rem 1rem; }
+dt { font-weight: bold; }
+dd { margin: 0; }
+table { border-collapse: collapse; width: 100%; margin: 0.75rem 0; font-size: 0.85rem; }
+th { background: #e5e7eb; text-align: left; }
+th, td { padding: 0.25rem 0.5rem; }
+tr:nth-child(even) td { background: #f3f4f6; }
+.number { text-align: right; font-variant-numeric: tabular-nums; }
+figure { margin: 1rem 0; }
+svg { width: 100%; height: auto; }
+footer { margin-top: 2rem; font-size: 0.8rem; color: #6b7280; }
+@media print { h2 { break-after: avoid; } figure, tr { break-inside: avoid; } }
+";
+
+fn html_block(out: &mut String, block: &Block) {
+    let _ = match block {
+        Block::Title(text) => writeln!(out, "<h1>{}</h1>", escape(text)),
+        Block::Heading(text) => writeln!(out, "<h2>{}</h2>", escape(text)),
+        Block::Subheading(text) => writeln!(out, "<h3>{}</h3>", escape(text)),
+        Block::Paragraph(text) => writeln!(out, "<p>{}</p>", escape(text)),
+        Block::Bullet(text) => writeln!(out, "<p class=\"bullet\">&bull; {}</p>", escape(text)),
+        Block::KeyValues(rows) => {
+            out.push_str("<dl>\n");
+            for (label, value) in rows {
+                let _ = writeln!(out, "<dt>{}</dt><dd>{}</dd>", escape(label), escape(value));
+            }
+            writeln!(out, "</dl>")
+        }
+        Block::Table { columns, rows } => {
+            out.push_str("<table>\n<thead><tr>");
+            for column in columns {
+                let _ = write!(out, "<th{}>{}</th>", number_class(column), escape(column.title));
+            }
+            out.push_str("</tr></thead>\n<tbody>\n");
+            for row in rows {
+                out.push_str("<tr>");
+                for (column, cell) in columns.iter().zip(row) {
+                    let _ = write!(out, "<td{}>{}</td>", number_class(column), escape(cell));
+                }
+                out.push_str("</tr>\n");
+            }
+            writeln!(out, "</tbody>\n</table>")
+        }
+        Block::BarChart { title, data } => chart_figure(out, svg_chart::bar_chart(title, data, compact_money)),
+        Block::LineChart { title, data } => chart_figure(out, svg_chart::line_chart(title, data, compact_money)),
+        Block::Gap => Ok(()),
+    };
+}
+
+fn number_class(column: &Column) -> &'static str {
+    if column.right_aligned { " class=\"number\"" } else { "" }
+}
+
+fn chart_figure(out: &mut String, svg: Option<String>) -> std::fmt::Result {
+    match svg {
+        Some(svg) => writeln!(out, "<figure>\n{}\n</figure>", svg),
+        None => Ok(()),
+    }
+}
+
+fn truncate(text: &str, width: f64) -> String {
+    if pdf::text_width(text, BODY_SIZE - 1.0, Font::Regular) <= width {
+        return text.to_string();
+    }
+    let mut truncated: String = text.to_string();
+    while !truncated.is_empty() && pdf::text_width(&format!("{}...", truncated), BODY_SIZE - 1.0, Font::Regular) > width {
+        truncated.pop();
+    }
+    format!("{}...", truncated.trim_end())
+}
+
+fn date(date: NaiveDate) -> String {
+    date.format("%d/%m/%Y").to_string()
+}
+
+fn money(amount: f64) -> String {
+    let rounded = amount.round().abs() as u64;
+    let digits = rounded.to_string();
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    let sign = if amount.round() < 0.0 { "-" } else { "" };
+    format!("{}${}", sign, grouped)
+}
+
+fn money_cents(amount: f64) -> String {
+    format!("${:.2}", amount)
+}
+
+fn compact_money(amount: f64) -> String {
+    if amount >= 1_000_000.0 {
+        format!("${:.1}m", amount / 1_000_000.0)
+    } else if amount >= 1_000.0 {
+        format!("${:.0}k", amount / 1_000.0)
+    } else {
+        format!("${:.0}", amount)
+    }
+}
@@ -0,0 +1,180 @@
+use crate::calculations;
+use crate::database::Database;
+use crate::dates::FinancialYear;
+use crate::models::*;
+use chrono::{NaiveDate, Utc};
+
+pub const SETTINGS_KEY: &str = "fy_rollover";
+
+// Payslips and the income statement within this many dollars of each other agree
+const RECONCILIATION_TOLERANCE_DOLLARS: f64 = 100.0;
+
+const PLACEHOLDER_NOTE: &str = "Created by the financial year rollover; fill in from the income statement";
+
+/// Works through the start-of-year checklist for `new_fy`: yearly entry placeholders for every
+/// position held into the year, the new super guarantee rate against each latest record, and the
+/// closed year's reconciliation and missing weeks.
+///
+/// Only placeholders are written, and only where the position has no entry for the year yet, so
+/// running it again changes nothing. Everything else is reported for the user to act on.
+pub fn run_fy_rollover(db: &Database, new_fy: &FinancialYear, today: NaiveDate) -> Result<RolloverReport, String> {
+    let closed_fy = FinancialYear { start_year: new_fy.start_year - 1 };
+    let positions = db.get_positions()?;
+    let held_into_new_year: Vec<&Position> = positions.iter()
+        .filter(|p| p.start_date <= new_fy.end() && p.end_date.is_none_or(|end| end >= new_fy.start()))
+        .collect();
+
+    let mut items = db.in_transaction(|db| create_placeholders(db, &held_into_new_year, new_fy))?;
+    items.extend(super_rate_items(db, &held_into_new_year, new_fy)?);
+
+    let weekly_entries = db.get_weekly_entries()?;
+    let summaries = calculations::calculate_financial_year_summaries(
+        &weekly_entries,
+        &db.get_yearly_entries()?,
+        &db.get_exchange_rates()?,
+    );
+    let closed = summaries.iter()
+        .find(|s| s.financial_year == closed_fy.label() && s.jurisdiction == Jurisdiction::AU);
+    items.push(reconciliation_item(closed, &closed_fy));
+
+    for position in &positions {
+        let Some(id) = position.id else { continue };
+        let missing = calculations::detect_missing_weeks(position, &closed_fy, &weekly_entries, today);
+        if !missing.is_empty() {
+            items.push(RolloverItem {
+                step: RolloverStep::MissingWeeks,
+                outcome: RolloverOutcome::NeedsAttention,
+                message: format!(
+                    "{} week{} at {} in {} have no pay entry",
+                    missing.len(),
+                    if missing.len() == 1 { "" } else { "s" },
+                    position.employer_name,
+                    closed_fy.label()
+                ),
+                related_entity: Some(RelatedEntity::Position(id)),
+            });
+        }
+    }
+
+    db.save_setting(SETTINGS_KEY, &RolloverSettings {
+        last_financial_year: Some(new_fy.label()),
+        last_run_at: Some(Utc::now()),
+    })
+    .map_err(|e| e.to_string())?;
+
+    Ok(RolloverReport {
+        financial_year: new_fy.label(),
+        closed_financial_year: closed_fy.label(),
+        items,
+    })
+}
+
+/// Whether the rollover has been run for the financial year containing `today`.
+pub fn rollover_status(db: &Database, today: NaiveDate) -> Result<RolloverStatus, String> {
+    let settings: RolloverSettings = db.get_setting(SETTINGS_KEY)?;
+    let current = FinancialYear::containing(today).label();
+    Ok(RolloverStatus {
+        done_for_current: settings.last_financial_year.as_deref() == Some(current.as_str()),
+        current_financial_year: current,
+        last_financial_year: settings.last_financial_year,
+        last_run_at: settings.last_run_at,
+    })
+}
+
+fn create_placeholders(db: &Database, positions: &[&Position], fy: &FinancialYear) -> Result<Vec<RolloverItem>, String> {
+    let existing = db.get_yearly_entries()?;
+    let mut items = Vec::new();
+    for position in positions {
+        let Some(id) = position.id else { continue };
+        let has_entry = existing.iter()
+            .any(|e| e.position_id == Some(id) && FinancialYear::parse(&e.financial_year).ok() == Some(*fy));
+        if !has_entry {
+            db.save_yearly_entry(YearlyIncomeEntry {
+                id: None,
+                position_id: Some(id),
+                financial_year: fy.label(),
+                gross_income: 0.0,
+                tax_withheld: 0.0,
+                reportable_super: 0.0,
+                reportable_fringe_benefits: None,
+                allowances: Vec::new(),
+                source: IncomeSource::ATO,
+                currency: currency_for(db, id)?,
+                jurisdiction: Jurisdiction::AU,
+                notes: Some(PLACEHOLDER_NOTE.to_string()),
+                created_at: Utc::now(),
+            })
+            .map_err(|e| e.to_string())?;
+        }
+        items.push(RolloverItem {
+            step: RolloverStep::YearlyPlaceholder,
+            outcome: if has_entry { RolloverOutcome::AlreadyDone } else { RolloverOutcome::Done },
+            message: if has_entry {
+                format!("{} already has a {} yearly entry", position.employer_name, fy.label())
+            } else {
+                format!("Added an empty {} yearly entry for {}", fy.label(), position.employer_name)
+            },
+            related_entity: Some(RelatedEntity::Position(id)),
+        });
+    }
+    Ok(items)
+}
+
+// The currency of the position's latest record, so the placeholder matches what's paid
+fn currency_for(db: &Database, position_id: i64) -> Result<String, String> {
+    Ok(db.get_compensation_records(position_id)?
+        .into_iter()
+        .max_by_key(|r| r.effective_date)
+        .map(|r| r.currency)
+        .unwrap_or_else(|| "AUD".to_string()))
+}
+
+fn super_rate_items(db: &Database, positions: &[&Position], fy: &FinancialYear) -> Result<Vec<RolloverItem>, String> {
+    let guarantee = calculations::super_guarantee_rate(fy);
+    let mut items = Vec::new();
+    for position in positions {
+        let Some(id) = position.id else { continue };
+        let records = db.get_compensation_records(id)?;
+        let Some(latest) = records.iter().max_by_key(|r| r.effective_date) else { continue };
+        let rate = latest.super_contributions.contribution_rate;
+        let behind = rate < guarantee - 1e-9;
+        items.push(RolloverItem {
+            step: RolloverStep::SuperRate,
+            outcome: if behind { RolloverOutcome::NeedsAttention } else { RolloverOutcome::Ok },
+            message: if behind {
+                format!(
+                    "{} SG rate is {}%, your latest record at {} still says {}%",
+                    fy.label(), guarantee, position.employer_name, rate
+                )
+            } else {
+                format!("{}% at {} meets the {} SG rate of {}%", rate, position.employer_name, fy.label(), guarantee)
+            },
+            related_entity: Some(RelatedEntity::Position(id)),
+        });
+    }
+    Ok(items)
+}
+
+fn reconciliation_item(closed: Option<&FinancialYearSummary>, closed_fy: &FinancialYear) -> RolloverItem {
+    let related_entity = Some(RelatedEntity::FinancialYear(closed_fy.label()));
+    let (outcome, message) = match closed {
+        None => (RolloverOutcome::Ok, format!("No income recorded for {}", closed_fy.label())),
+        Some(summary) => match (summary.ato_gross_income, summary.reconciliation_difference) {
+            (None, _) => (
+                RolloverOutcome::NeedsAttention,
+                format!("Enter the {} income statement to reconcile it against your payslips", closed_fy.label()),
+            ),
+            (Some(_), Some(difference)) if difference.abs() > RECONCILIATION_TOLERANCE_DOLLARS => (
+                RolloverOutcome::NeedsAttention,
+                format!(
+                    "The {} income statement is ${:.0} {} than your payslips",
+                    closed_fy.label(),
+                    difference.abs(),
+                    if difference > 0.0 { "more" } else { "less" }
+                ),
+            ),
+            (Some(_), _) => (RolloverOutcome::Ok, format!("{} payslips match the income statement", closed_fy.label())),
+        },
+    };
+    RolloverItem { step: RolloverStep::Reconciliation, outcome, message, related_entity }
+}
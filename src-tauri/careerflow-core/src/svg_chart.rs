@@ -0,0 +1,172 @@
+use std::fmt::Write;
+
+// Chart size in SVG user units; the viewBox lets it scale to the page
+pub const WIDTH: f64 = 640.0;
+pub const HEIGHT: f64 = 240.0;
+const LEFT: f64 = 64.0; // Room for the value labels
+const RIGHT: f64 = 12.0;
+const TOP: f64 = 28.0; // Room for the title
+const BOTTOM: f64 = 28.0; // Room for the category labels
+const MIN_LABEL_SPACING: f64 = 40.0;
+const TICK_COUNT: f64 = 4.0;
+
+/// The part of the chart values are drawn in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlotArea {
+    pub x: f64,
+    pub y: f64, // Top edge
+    pub width: f64,
+    pub height: f64,
+}
+
+impl PlotArea {
+    pub fn standard() -> Self {
+        PlotArea { x: LEFT, y: TOP, width: WIDTH - LEFT - RIGHT, height: HEIGHT - TOP - BOTTOM }
+    }
+
+    pub fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+
+    /// Width given to each category, with the categories spread evenly across the plot.
+    pub fn slot_width(&self, count: usize) -> f64 {
+        self.width / count.max(1) as f64
+    }
+
+    /// Horizontal centre of the category at `index`.
+    pub fn slot_centre(&self, index: usize, count: usize) -> f64 {
+        self.x + self.slot_width(count) * (index as f64 + 0.5)
+    }
+}
+
+/// A value axis from zero to a round maximum, with evenly spaced ticks at round numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValueAxis {
+    pub max: f64,
+    pub ticks: Vec<f64>,
+}
+
+impl ValueAxis {
+    /// None when there is nothing above zero to plot.
+    pub fn for_values(values: &[f64]) -> Option<Self> {
+        let largest = values.iter().copied().filter(|v| v.is_finite()).fold(0.0, f64::max);
+        if largest <= 0.0 {
+            return None;
+        }
+
+        let step = nice_step(largest / TICK_COUNT);
+        let tick_count = (largest / step).ceil() as usize;
+        let ticks: Vec<f64> = (0..=tick_count).map(|i| step * i as f64).collect();
+        Some(ValueAxis { max: step * tick_count as f64, ticks })
+    }
+
+    /// Vertical coordinate of a value in the plot; values outside the axis are clamped.
+    pub fn y(&self, value: f64, plot: &PlotArea) -> f64 {
+        let share = (value / self.max).clamp(0.0, 1.0);
+        plot.bottom() - share * plot.height
+    }
+}
+
+// 1, 2, 2.5 or 5 times a power of ten, at least the raw step
+fn nice_step(raw: f64) -> f64 {
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let normalised = raw / magnitude;
+    let nice = [1.0, 2.0, 2.5, 5.0, 10.0].into_iter().find(|n| *n >= normalised - 1e-9).unwrap_or(10.0);
+    nice * magnitude
+}
+
+/// Which category labels to show, so that they don't run into each other: every nth.
+pub fn label_interval(count: usize, plot: &PlotArea) -> usize {
+    (MIN_LABEL_SPACING / plot.slot_width(count)).ceil().max(1.0) as usize
+}
+
+/// A bar per category, as an `<svg>` element to put inline in an HTML page. None when there's nothing above zero to show.
+pub fn bar_chart(title: &str, data: &[(String, f64)], format_value: impl Fn(f64) -> String) -> Option<String> {
+    let values: Vec<f64> = data.iter().map(|(_, v)| *v).collect();
+    let axis = ValueAxis::for_values(&values)?;
+    let plot = PlotArea::standard();
+    let slot = plot.slot_width(data.len());
+
+    let mut svg = frame(title, data, &axis, &plot, &format_value);
+    for (i, (label, value)) in data.iter().enumerate() {
+        let top = axis.y(*value, &plot);
+        let _ = writeln!(
+            svg,
+            r##"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" fill="#4b5563"><title>{}: {}</title></rect>"##,
+            plot.x + slot * i as f64 + slot * 0.15, top, slot * 0.7, plot.bottom() - top,
+            escape(label), escape(&format_value(*value))
+        );
+    }
+    svg.push_str("</svg>");
+    Some(svg)
+}
+
+/// A line through one point per category, as an inline `<svg>` element. None when there's nothing above zero to show.
+pub fn line_chart(title: &str, data: &[(String, f64)], format_value: impl Fn(f64) -> String) -> Option<String> {
+    let values: Vec<f64> = data.iter().map(|(_, v)| *v).collect();
+    let axis = ValueAxis::for_values(&values)?;
+    let plot = PlotArea::standard();
+
+    let points: Vec<(f64, f64)> = data.iter().enumerate()
+        .map(|(i, (_, value))| (plot.slot_centre(i, data.len()), axis.y(*value, &plot)))
+        .collect();
+    let path: Vec<String> = points.iter().map(|(x, y)| format!("{:.2},{:.2}", x, y)).collect();
+
+    let mut svg = frame(title, data, &axis, &plot, &format_value);
+    let _ = writeln!(svg, r##"<polyline points="{}" fill="none" stroke="#1f2937" stroke-width="2"/>"##, path.join(" "));
+    for ((x, y), (label, value)) in points.iter().zip(data) {
+        let _ = writeln!(
+            svg,
+            r##"<circle cx="{:.2}" cy="{:.2}" r="3" fill="#1f2937"><title>{}: {}</title></circle>"##,
+            x, y, escape(label), escape(&format_value(*value))
+        );
+    }
+    svg.push_str("</svg>");
+    Some(svg)
+}
+
+// Opening tag, title, gridlines with value labels and the category labels
+fn frame(
+    title: &str,
+    data: &[(String, f64)],
+    axis: &ValueAxis,
+    plot: &PlotArea,
+    format_value: &impl Fn(f64) -> String,
+) -> String {
+    let mut svg = format!(
+        r#"<svg viewBox="0 0 {} {}" role="img" aria-label="{}" font-family="Helvetica, Arial, sans-serif">"#,
+        WIDTH, HEIGHT, escape(title)
+    );
+    svg.push('\n');
+    let _ = writeln!(svg, r#"<text x="{}" y="16" font-size="13" font-weight="bold">{}</text>"#, plot.x, escape(title));
+
+    for tick in &axis.ticks {
+        let y = axis.y(*tick, plot);
+        let colour = if *tick == 0.0 { "#111827" } else { "#d1d5db" };
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" stroke="{}" stroke-width="1"/>"#,
+            plot.x, y, plot.x + plot.width, y, colour
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.2}" y="{:.2}" font-size="10" text-anchor="end">{}</text>"#,
+            plot.x - 6.0, y + 3.5, escape(&format_value(*tick))
+        );
+    }
+
+    let every = label_interval(data.len(), plot);
+    for (i, (label, _)) in data.iter().enumerate().step_by(every) {
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.2}" y="{:.2}" font-size="10" text-anchor="middle">{}</text>"#,
+            plot.slot_centre(i, data.len()), plot.bottom() + 16.0, escape(label)
+        );
+    }
+    svg
+}
+
+/// Escapes text for SVG or HTML content and attribute values.
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
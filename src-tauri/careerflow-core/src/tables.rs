@@ -0,0 +1,79 @@
+use crate::models::DataExport;
+use serde::Serialize;
+use serde_json::Value;
+
+/// One exported table flattened to text cells, for CSV and spreadsheet output.
+///
+/// Nested objects become dotted columns (`overtime.rate_multiplier`) and lists are kept as
+/// JSON text in a single cell.
+#[derive(Debug, Clone)]
+pub struct Table {
+    pub name: String,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+pub fn export_tables(data: &DataExport) -> Result<Vec<Table>, String> {
+    Ok(vec![
+        table("user_profile", data.user_profile.iter())?,
+        table("positions", data.positions.iter())?,
+        table("compensation_records", data.compensation_records.iter())?,
+        table("weekly_entries", data.weekly_entries.iter())?,
+        table("yearly_entries", data.yearly_entries.iter())?,
+        table("exchange_rates", data.exchange_rates.iter())?,
+        table("award_rates", data.award_rates.iter())?,
+        table("scenarios", data.scenarios.iter())?,
+        table("position_templates", data.templates.iter())?,
+        table("insurance_policies", data.insurance_policies.iter())?,
+        table("partner_income", data.partner_income_entries.iter())?,
+        table("tags", data.tags.iter())?,
+        table("entity_tags", data.entity_tags.iter())?,
+    ])
+}
+
+fn table<'a, T: Serialize + 'a>(name: &str, records: impl Iterator<Item = &'a T>) -> Result<Table, String> {
+    let mut headers: Vec<String> = Vec::new();
+    let mut flattened = Vec::new();
+
+    for record in records {
+        let value = serde_json::to_value(record).map_err(|e| e.to_string())?;
+        let mut cells = Vec::new();
+        flatten("", &value, &mut cells);
+        for (key, _) in &cells {
+            if !headers.contains(key) {
+                headers.push(key.clone());
+            }
+        }
+        flattened.push(cells);
+    }
+
+    let rows = flattened.into_iter()
+        .map(|cells| {
+            headers.iter()
+                .map(|header| {
+                    cells.iter()
+                        .find(|(key, _)| key == header)
+                        .map(|(_, cell)| cell.clone())
+                        .unwrap_or_default()
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(Table { name: name.to_string(), headers, rows })
+}
+
+fn flatten(prefix: &str, value: &Value, cells: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                let column = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten(&column, field, cells);
+            }
+        }
+        Value::Null => cells.push((prefix.to_string(), String::new())),
+        Value::String(text) => cells.push((prefix.to_string(), text.clone())),
+        Value::Array(_) => cells.push((prefix.to_string(), value.to_string())),
+        Value::Bool(_) | Value::Number(_) => cells.push((prefix.to_string(), value.to_string())),
+    }
+}
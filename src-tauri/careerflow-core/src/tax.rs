@@ -0,0 +1,377 @@
+use crate::dates::FinancialYear;
+use crate::models::{HealthIncomeTier, Jurisdiction};
+
+// Tax tables are keyed by the calendar year the tax year starts in and apply until the
+// next entry. Each bracket is (threshold, marginal rate on income above the threshold).
+type Brackets = &'static [(f64, f64)];
+
+// Australian resident rates, tax year starting 1 July
+const AU_TAX_BRACKETS: &[(i32, Brackets)] = &[
+    (2012, &[(0.0, 0.0), (18200.0, 0.19), (37000.0, 0.325), (80000.0, 0.37), (180000.0, 0.45)]),
+    (2016, &[(0.0, 0.0), (18200.0, 0.19), (37000.0, 0.325), (87000.0, 0.37), (180000.0, 0.45)]),
+    (2018, &[(0.0, 0.0), (18200.0, 0.19), (37000.0, 0.325), (90000.0, 0.37), (180000.0, 0.45)]),
+    (2020, &[(0.0, 0.0), (18200.0, 0.19), (45000.0, 0.325), (120000.0, 0.37), (180000.0, 0.45)]),
+    // Stage 3 tax cuts
+    (2024, &[(0.0, 0.0), (18200.0, 0.16), (45000.0, 0.30), (135000.0, 0.37), (190000.0, 0.45)]),
+];
+
+// New Zealand PAYE rates, tax year starting 1 April
+const NZ_TAX_BRACKETS: &[(i32, Brackets)] = &[
+    (2010, &[(0.0, 0.105), (14000.0, 0.175), (48000.0, 0.30), (70000.0, 0.33)]),
+    (2021, &[(0.0, 0.105), (14000.0, 0.175), (48000.0, 0.30), (70000.0, 0.33), (180000.0, 0.39)]),
+    // Composite rates for the year the thresholds changed on 31 July 2024
+    (2024, &[
+        (0.0, 0.105), (14000.0, 0.1282), (15600.0, 0.175), (48000.0, 0.2164),
+        (53500.0, 0.30), (70000.0, 0.3072), (78100.0, 0.33), (180000.0, 0.39),
+    ]),
+    (2025, &[(0.0, 0.105), (15600.0, 0.175), (53500.0, 0.30), (78100.0, 0.33), (180000.0, 0.39)]),
+];
+
+// Medicare levy low-income threshold for singles; the levy phases in at 10c per dollar above it
+const MEDICARE_LEVY_RATE: f64 = 0.02;
+const MEDICARE_SHADE_IN_RATE: f64 = 0.10;
+const MEDICARE_LOW_INCOME_THRESHOLDS: &[(i32, f64)] = &[
+    (2019, 22398.0),
+    (2020, 23226.0),
+    (2021, 23365.0),
+    (2022, 24276.0),
+    (2023, 26000.0),
+    (2024, 27222.0),
+];
+
+// Low income tax offset: the full offset, less a withdrawal rate on income above each threshold
+const LOW_INCOME_TAX_OFFSETS: &[(i32, (f64, Brackets))] = &[
+    (2012, (445.0, &[(0.0, 0.0), (37000.0, 0.015)])),
+    (2020, (700.0, &[(0.0, 0.0), (37500.0, 0.05), (45000.0, 0.015)])),
+];
+
+// Medicare levy surcharge and private health insurance rebate income thresholds for singles,
+// the tops of the base tier, tier 1 and tier 2. Family thresholds are double; the extra $1,500
+// for each dependent child after the first is not modelled.
+const HEALTH_TIER_THRESHOLDS: &[(i32, [f64; 3])] = &[
+    (2015, [90000.0, 105000.0, 140000.0]),
+    (2023, [93000.0, 108000.0, 144000.0]),
+    (2024, [97000.0, 113000.0, 151000.0]),
+    (2025, [101000.0, 118000.0, 158000.0]),
+];
+const FAMILY_THRESHOLD_MULTIPLE: f64 = 2.0;
+// Surcharge on income for MLS purposes at tiers 1, 2 and 3, without private hospital cover
+const MLS_RATES: [f64; 3] = [0.01, 0.0125, 0.015];
+// Private health insurance rebate (percent of premiums) from 1 April 2024, by age band of the
+// oldest person on the policy (under 65, 65-69, 70 and over), at the base tier and tiers 1 and 2
+const PRIVATE_HEALTH_REBATES: [[f64; 3]; 3] = [
+    [24.608, 16.405, 8.202],
+    [28.710, 20.507, 12.303],
+    [32.812, 24.608, 16.405],
+];
+
+// Most points a tax rate curve will sample, so a tiny step can't stall the app
+const MAX_CURVE_POINTS: usize = 10_000;
+
+// HELP compulsory repayment: a rate on the whole repayment income once it reaches each threshold
+const HELP_WHOLE_INCOME_RATES: &[(i32, Brackets)] = &[
+    (2023, &[
+        (51550.0, 0.01), (59519.0, 0.02), (63090.0, 0.025), (66876.0, 0.03), (70889.0, 0.035),
+        (75141.0, 0.04), (79650.0, 0.045), (84430.0, 0.05), (89495.0, 0.055), (94866.0, 0.06),
+        (100558.0, 0.065), (106591.0, 0.07), (112986.0, 0.075), (119765.0, 0.08), (126951.0, 0.085),
+        (134569.0, 0.09), (142643.0, 0.095), (151201.0, 0.10),
+    ]),
+    (2024, &[
+        (54435.0, 0.01), (62851.0, 0.02), (66621.0, 0.025), (70619.0, 0.03), (74856.0, 0.035),
+        (79347.0, 0.04), (84108.0, 0.045), (89155.0, 0.05), (94504.0, 0.055), (100175.0, 0.06),
+        (106186.0, 0.065), (112557.0, 0.07), (119310.0, 0.075), (126468.0, 0.08), (134057.0, 0.085),
+        (142101.0, 0.09), (150627.0, 0.095), (159663.0, 0.10),
+    ]),
+];
+// From 2025-26 repayments are marginal, on income above the threshold, capped at 10% of income
+const HELP_MARGINAL_FROM: i32 = 2025;
+const HELP_MARGINAL_RATES: Brackets = &[(0.0, 0.0), (67000.0, 0.15), (125000.0, 0.17)];
+const HELP_MAX_SHARE: f64 = 0.10;
+
+// Compulsory KiwiSaver employer contribution rates (percent of gross salary)
+const KIWISAVER_EMPLOYER_RATES: &[(i32, f64)] = &[
+    (2008, 1.0),
+    (2009, 2.0),
+    (2013, 3.0),
+    (2026, 3.5),
+    (2028, 4.0),
+];
+
+// Concessional (before-tax) super contributions cap, per person across all funds
+const CONCESSIONAL_CAPS: &[(i32, f64)] = &[
+    (2014, 30000.0),
+    (2017, 25000.0),
+    (2021, 27500.0),
+    (2024, 30000.0),
+];
+// Unused cap accrues from 2018-19 and can be used for five years, while the total super
+// balance at the previous 30 June is under the limit
+pub const CARRY_FORWARD_FROM: i32 = 2018;
+pub const CARRY_FORWARD_YEARS: i32 = 5;
+pub const CARRY_FORWARD_BALANCE_LIMIT: f64 = 500000.0;
+
+// Division 293: a further 15% on concessional contributions once income plus those
+// contributions is over the threshold. Introduced in 2012-13.
+const DIV293_THRESHOLDS: &[(i32, f64)] = &[
+    (2012, 300000.0),
+    (2017, 250000.0),
+];
+pub const DIV293_RATE: f64 = 0.15;
+
+// Fringe benefits go on the income statement grossed up at the lower (type 2) rate, and only
+// once their taxable value for the FBT year is over $2,000
+const FBT_TYPE2_GROSS_UP: f64 = 1.8868;
+const REPORTABLE_FRINGE_BENEFITS_THRESHOLD: f64 = 2000.0;
+
+// Car fringe benefits under the statutory formula: a flat 20% of the car's base value for
+// contracts entered into from 10 May 2011, with the base value reduced by a third once the car
+// has been held for four full FBT years
+pub const CAR_STATUTORY_FRACTION: f64 = 0.20;
+const CAR_BASE_VALUE_REDUCED_AFTER_YEARS: u32 = 4;
+
+// Car limit for 2024-25 and 2025-26; GST credits on a car's purchase are capped at a
+// eleventh of it
+pub const CAR_LIMIT: f64 = 69674.0;
+
+// Minimum residual values the ATO accepts for car leases, as a share of the cost, by term in years
+const MINIMUM_LEASE_RESIDUALS: &[(u32, f64)] = &[
+    (1, 0.65625),
+    (2, 0.5625),
+    (3, 0.46875),
+    (4, 0.375),
+    (5, 0.28125),
+];
+
+fn table_for<T: Copy>(table: &[(i32, T)], tax_year: &FinancialYear) -> Option<T> {
+    table.iter()
+        .rev()
+        .find(|(start_year, _)| *start_year <= tax_year.start_year)
+        .or_else(|| table.first())
+        .map(|(_, value)| *value)
+}
+
+fn progressive_tax(income: f64, brackets: &[(f64, f64)]) -> f64 {
+    let mut tax = 0.0;
+    for (i, (threshold, rate)) in brackets.iter().enumerate() {
+        if income <= *threshold {
+            break;
+        }
+        let upper = brackets.get(i + 1).map(|(next, _)| *next).unwrap_or(f64::INFINITY);
+        tax += (income.min(upper) - threshold) * rate;
+    }
+    tax
+}
+
+/// Income tax on taxable income for the jurisdiction's tax year, in that jurisdiction's currency.
+///
+/// Brackets only: offsets, levies and student loan repayments are not included.
+pub fn income_tax(jurisdiction: &Jurisdiction, taxable_income: f64, tax_year: &FinancialYear) -> f64 {
+    let brackets = match jurisdiction {
+        Jurisdiction::AU => table_for(AU_TAX_BRACKETS, tax_year),
+        Jurisdiction::NZ => table_for(NZ_TAX_BRACKETS, tax_year),
+    };
+    progressive_tax(taxable_income.max(0.0), brackets.unwrap_or(&[]))
+}
+
+/// Compulsory KiwiSaver employer contribution rate (percent) for the New Zealand tax year.
+pub fn kiwisaver_employer_rate(tax_year: &FinancialYear) -> f64 {
+    table_for(KIWISAVER_EMPLOYER_RATES, tax_year).unwrap_or(3.0)
+}
+
+/// Concessional contributions cap for the Australian financial year, before any carry-forward.
+pub fn concessional_cap(tax_year: &FinancialYear) -> f64 {
+    table_for(CONCESSIONAL_CAPS, tax_year).unwrap_or(30000.0)
+}
+
+/// Income plus concessional contributions above which Division 293 applies.
+pub fn div293_threshold(tax_year: &FinancialYear) -> f64 {
+    table_for(DIV293_THRESHOLDS, tax_year).unwrap_or(250000.0)
+}
+
+/// Division 293 tax, charged on the lesser of the concessional contributions and the amount
+/// income plus those contributions is over the threshold.
+///
+/// `income_for_surcharge` excludes the contributions: taxable income plus reportable fringe
+/// benefits and net investment losses.
+pub fn div293_liability(income_for_surcharge: f64, concessional_contributions: f64, tax_year: &FinancialYear) -> f64 {
+    let contributions = concessional_contributions.max(0.0);
+    let over_threshold = (income_for_surcharge + contributions - div293_threshold(tax_year)).max(0.0);
+    over_threshold.min(contributions) * DIV293_RATE
+}
+
+/// Reportable fringe benefits amount for a year's taxable value of fringe benefits. The gross-up
+/// rate is the one for recent years, so earlier years are approximate.
+pub fn reportable_fringe_benefits(taxable_value: f64) -> f64 {
+    if taxable_value <= REPORTABLE_FRINGE_BENEFITS_THRESHOLD {
+        return 0.0;
+    }
+    taxable_value * FBT_TYPE2_GROSS_UP
+}
+
+/// Taxable value of a car fringe benefit for a full FBT year under the statutory formula,
+/// `years_held` being the full FBT years the car was held before this one.
+pub fn car_statutory_taxable_value(base_value: f64, years_held: u32) -> f64 {
+    let base_value = if years_held >= CAR_BASE_VALUE_REDUCED_AFTER_YEARS {
+        base_value * 2.0 / 3.0
+    } else {
+        base_value
+    };
+    base_value * CAR_STATUTORY_FRACTION
+}
+
+/// Minimum residual value for a car lease as a share of its cost, interpolated between the
+/// whole-year terms. Terms outside one to five years are clamped to them.
+pub fn minimum_lease_residual(term_months: u32) -> f64 {
+    let years = (term_months as f64 / 12.0).clamp(1.0, 5.0);
+    let lower = years.floor() as usize;
+    let share_at = |year: usize| MINIMUM_LEASE_RESIDUALS[year - 1].1;
+    if lower >= MINIMUM_LEASE_RESIDUALS.len() {
+        return share_at(lower);
+    }
+    share_at(lower) + (share_at(lower + 1) - share_at(lower)) * (years - lower as f64)
+}
+
+/// Medicare levy for a single Australian resident with no dependants.
+///
+/// Exemptions and the family thresholds are not modelled; the surcharge is
+/// [`medicare_levy_surcharge`].
+pub fn medicare_levy(taxable_income: f64, tax_year: &FinancialYear) -> f64 {
+    let threshold = table_for(MEDICARE_LOW_INCOME_THRESHOLDS, tax_year).unwrap_or(0.0);
+    if taxable_income <= threshold {
+        return 0.0;
+    }
+    ((taxable_income - threshold) * MEDICARE_SHADE_IN_RATE).min(taxable_income * MEDICARE_LEVY_RATE)
+}
+
+/// The tops of the base tier, tier 1 and tier 2 for the Medicare levy surcharge and the private
+/// health insurance rebate, for a single or a family.
+pub fn health_tier_thresholds(family: bool, tax_year: &FinancialYear) -> [f64; 3] {
+    let single = table_for(HEALTH_TIER_THRESHOLDS, tax_year).unwrap_or([0.0; 3]);
+    if family {
+        single.map(|threshold| threshold * FAMILY_THRESHOLD_MULTIPLE)
+    } else {
+        single
+    }
+}
+
+/// The tier an income falls in: the person's own income for a single, the combined income for a
+/// family. Reaching a threshold exactly stays in the tier below.
+pub fn health_income_tier(income: f64, family: bool, tax_year: &FinancialYear) -> HealthIncomeTier {
+    let thresholds = health_tier_thresholds(family, tax_year);
+    match thresholds.iter().filter(|threshold| income > **threshold).count() {
+        0 => HealthIncomeTier::Base,
+        1 => HealthIncomeTier::Tier1,
+        2 => HealthIncomeTier::Tier2,
+        _ => HealthIncomeTier::Tier3,
+    }
+}
+
+/// Medicare levy surcharge for someone without private hospital cover. With a partner the tier
+/// comes from the family's combined income, but the rate still applies to the person's own income.
+pub fn medicare_levy_surcharge(income_for_mls: f64, family_income: Option<f64>, tax_year: &FinancialYear) -> f64 {
+    let tier = health_income_tier(family_income.unwrap_or(income_for_mls), family_income.is_some(), tax_year);
+    let rate = match tier {
+        HealthIncomeTier::Base => 0.0,
+        HealthIncomeTier::Tier1 => MLS_RATES[0],
+        HealthIncomeTier::Tier2 => MLS_RATES[1],
+        HealthIncomeTier::Tier3 => MLS_RATES[2],
+    };
+    income_for_mls.max(0.0) * rate
+}
+
+/// Private health insurance rebate, as a percent of premiums, for the tier and the age of the
+/// oldest person on the policy. Nothing at tier 3.
+pub fn private_health_rebate_percent(tier: HealthIncomeTier, oldest_age: i32) -> f64 {
+    let by_tier = match oldest_age {
+        ..=64 => PRIVATE_HEALTH_REBATES[0],
+        65..=69 => PRIVATE_HEALTH_REBATES[1],
+        _ => PRIVATE_HEALTH_REBATES[2],
+    };
+    match tier {
+        HealthIncomeTier::Base => by_tier[0],
+        HealthIncomeTier::Tier1 => by_tier[1],
+        HealthIncomeTier::Tier2 => by_tier[2],
+        HealthIncomeTier::Tier3 => 0.0,
+    }
+}
+
+/// Compulsory HELP repayment for the Australian tax year. Years before the earliest table use
+/// the earliest thresholds.
+pub fn help_repayment(repayment_income: f64, tax_year: &FinancialYear) -> f64 {
+    let income = repayment_income.max(0.0);
+    if tax_year.start_year >= HELP_MARGINAL_FROM {
+        return progressive_tax(income, HELP_MARGINAL_RATES).min(income * HELP_MAX_SHARE);
+    }
+    let rates = table_for(HELP_WHOLE_INCOME_RATES, tax_year).unwrap_or(&[]);
+    rates.iter()
+        .rev()
+        .find(|(threshold, _)| income >= *threshold)
+        .map(|(_, rate)| income * rate)
+        .unwrap_or(0.0)
+}
+
+/// Low income tax offset for an Australian resident. It can only reduce income tax, not the
+/// Medicare levy, and the low and middle income tax offset is not modelled.
+pub fn low_income_tax_offset(taxable_income: f64, tax_year: &FinancialYear) -> f64 {
+    let Some((full, withdrawal)) = table_for(LOW_INCOME_TAX_OFFSETS, tax_year) else {
+        return 0.0;
+    };
+    (full - progressive_tax(taxable_income.max(0.0), withdrawal)).max(0.0)
+}
+
+/// Income tax after the low income tax offset, plus the Medicare levy, for a single Australian
+/// resident.
+pub fn total_tax_payable(taxable_income: f64, tax_year: &FinancialYear) -> f64 {
+    let income_tax = income_tax(&Jurisdiction::AU, taxable_income, tax_year);
+    let offset = low_income_tax_offset(taxable_income, tax_year).min(income_tax);
+    income_tax - offset + medicare_levy(taxable_income, tax_year)
+}
+
+/// Tax an Australian employer is expected to withhold over a year from a job paying this
+/// taxable income. A job that claims the tax-free threshold withholds roughly the tax
+/// payable; one that doesn't withholds from the first dollar at the lowest rate, with no
+/// offset, since the threshold is used up by the main job.
+///
+/// An annual approximation of the ATO withholding schedules, not the weekly tables.
+pub fn expected_payg_withholding(taxable_income: f64, claims_tax_free_threshold: bool, tax_year: &FinancialYear) -> f64 {
+    if claims_tax_free_threshold {
+        return total_tax_payable(taxable_income, tax_year);
+    }
+    let brackets = table_for(AU_TAX_BRACKETS, tax_year).unwrap_or(&[]);
+    // The tax-free bracket taxed at the rate of the one above it
+    let no_threshold: Vec<(f64, f64)> = brackets.iter()
+        .skip(1)
+        .enumerate()
+        .map(|(i, (threshold, rate))| (if i == 0 { 0.0 } else { *threshold }, *rate))
+        .collect();
+    let income = taxable_income.max(0.0);
+    progressive_tax(income, &no_threshold) + income * MEDICARE_LEVY_RATE
+}
+
+/// Average tax rate (percent of income, from [`total_tax_payable`]) at incomes from `from` to
+/// `to` inclusive, every `step` dollars, as (income, rate) pairs for plotting.
+pub fn tax_rate_curve(from: f64, to: f64, step: f64, tax_year: &FinancialYear) -> Result<Vec<(f64, f64)>, String> {
+    Ok(curve_incomes(from, to, step)?
+        .into_iter()
+        .map(|income| {
+            let rate = if income > 0.0 { total_tax_payable(income, tax_year) / income * 100.0 } else { 0.0 };
+            (income, rate)
+        })
+        .collect())
+}
+
+/// Incomes from `from` to `to` inclusive, every `step` dollars and never below zero, for a
+/// curve of at most `MAX_CURVE_POINTS` points.
+pub(crate) fn curve_incomes(from: f64, to: f64, step: f64) -> Result<Vec<f64>, String> {
+    if step.is_nan() || step <= 0.0 || !from.is_finite() || !to.is_finite() {
+        return Err("The step must be more than zero and the range must be finite".to_string());
+    }
+    if to < from {
+        return Err(format!("The range ends at {} before it starts at {}", to, from));
+    }
+    let points = ((to - from) / step + 1e-9).floor() as usize + 1;
+    if points > MAX_CURVE_POINTS {
+        return Err(format!("That's {} points; use a larger step for at most {}", points, MAX_CURVE_POINTS));
+    }
+    Ok((0..points).map(|i| (from + step * i as f64).max(0.0)).collect())
+}
@@ -0,0 +1,135 @@
+use crate::database::Database;
+use crate::models::*;
+use crate::validation;
+use chrono::Utc;
+
+// A record from a template is an estimate until a payslip confirms it
+const TEMPLATE_CONFIDENCE: f64 = 50.0;
+
+/// Saves a position's setup and its latest record's pay structure as a named template. Without
+/// `keep_rates` the base rate and allowance amounts are zeroed, keeping only the structure.
+pub fn save_position_as_template(
+    db: &Database,
+    position_id: i64,
+    name: &str,
+    keep_rates: bool,
+) -> Result<PositionTemplate, String> {
+    if name.trim().is_empty() {
+        return Err("Give the template a name".to_string());
+    }
+    let position = db.get_position(position_id)?
+        .ok_or_else(|| format!("Position {} not found", position_id))?;
+    let records = db.get_compensation_records(position_id)?;
+    let latest = records.iter().max_by_key(|r| r.effective_date);
+
+    let mut template = PositionTemplate {
+        id: None,
+        name: name.trim().to_string(),
+        fields: template_fields(&position, latest, keep_rates),
+        created_at: Utc::now(),
+    };
+    template.id = Some(db.save_template(template.clone()).map_err(|e| e.to_string())?);
+    Ok(template)
+}
+
+/// The parts of a position and its latest record that a template carries; see [`TemplateFields`].
+pub fn template_fields(position: &Position, latest: Option<&CompensationRecord>, keep_rates: bool) -> TemplateFields {
+    TemplateFields {
+        job_title: position.job_title.clone(),
+        employment_type: position.employment_type.clone(),
+        location: position.location.clone(),
+        seniority_level: position.seniority_level.clone(),
+        core_responsibilities: position.core_responsibilities.clone(),
+        tools_systems_skills: position.tools_systems_skills.clone(),
+        industry: position.industry.clone(),
+        week_ending_day: position.week_ending_day,
+        notice_period_weeks: position.notice_period_weeks,
+        leave_loading_percent: position.leave_loading_percent,
+        probation_months: position.probation_months,
+        compensation: latest.map(|record| TemplateCompensation {
+            pay_type: record.pay_type.clone(),
+            base_rate: if keep_rates { record.base_rate } else { 0.0 },
+            standard_weekly_hours: record.standard_weekly_hours,
+            overtime: record.overtime.clone(),
+            allowances: record.allowances.iter()
+                .map(|a| Allowance { amount: if keep_rates { a.amount } else { 0.0 }, ..a.clone() })
+                .collect(),
+            super_contributions: record.super_contributions.clone(),
+            payslip_frequency: record.payslip_frequency.clone(),
+            currency: record.currency.clone(),
+            jurisdiction: record.jurisdiction,
+            claims_tax_free_threshold: record.claims_tax_free_threshold,
+        }),
+    }
+}
+
+/// Starts a position from a template, with a fuzzy compensation record effective from the
+/// start date when the template has a pay structure, in one transaction. Returns the new
+/// position's id.
+pub fn create_position_from_template(db: &Database, template_id: i64, overrides: TemplateOverrides) -> Result<i64, String> {
+    let template = db.get_template(template_id)?
+        .ok_or_else(|| format!("Template {} not found", template_id))?;
+    let fields = template.fields;
+    let now = Utc::now();
+
+    let position = Position {
+        id: None,
+        employer_name: overrides.employer_name,
+        job_title: overrides.job_title.unwrap_or(fields.job_title),
+        employment_type: fields.employment_type,
+        location: overrides.location.unwrap_or(fields.location),
+        start_date: overrides.start_date,
+        end_date: None,
+        seniority_level: fields.seniority_level,
+        core_responsibilities: fields.core_responsibilities,
+        tools_systems_skills: fields.tools_systems_skills,
+        achievements: Vec::new(),
+        industry: fields.industry,
+        week_ending_day: fields.week_ending_day,
+        notice_period_weeks: fields.notice_period_weeks,
+        leave_loading_percent: fields.leave_loading_percent,
+        probation_months: fields.probation_months,
+        commute_minutes_per_day: None,
+        archived: false,
+        created_at: now,
+        updated_at: now,
+    };
+    validation::validate_position(&position)?;
+
+    let record = fields.compensation.map(|pay| CompensationRecord {
+        id: None,
+        position_id: 0, // Set once the position is saved
+        entry_type: CompensationEntryType::Fuzzy,
+        pay_type: pay.pay_type,
+        base_rate: overrides.base_rate.unwrap_or(pay.base_rate),
+        standard_weekly_hours: pay.standard_weekly_hours,
+        overtime: pay.overtime,
+        allowances: pay.allowances,
+        bonuses: Vec::new(),
+        super_contributions: pay.super_contributions,
+        tax_withheld: None,
+        payslip_frequency: pay.payslip_frequency,
+        effective_date: overrides.start_date,
+        confidence_score: TEMPLATE_CONFIDENCE,
+        currency: pay.currency,
+        jurisdiction: pay.jurisdiction,
+        claims_tax_free_threshold: pay.claims_tax_free_threshold,
+        equity: None,
+        packaging: Vec::new(),
+        non_cash_benefits: Vec::new(),
+        award_classification_id: None,
+        notes: Some(format!("Started from the {} template", template.name)),
+        created_at: now,
+    });
+    if let Some(record) = &record {
+        validation::validate_compensation_record(record)?;
+    }
+
+    db.in_transaction(|db| {
+        let position_id = db.save_position(position).map_err(|e| e.to_string())?;
+        if let Some(record) = record {
+            db.save_compensation_record(CompensationRecord { position_id, ..record }).map_err(|e| e.to_string())?;
+        }
+        Ok(position_id)
+    })
+}
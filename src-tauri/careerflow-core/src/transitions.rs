@@ -0,0 +1,87 @@
+use crate::database::Database;
+use crate::models::*;
+use crate::validation;
+use chrono::{Duration, Utc};
+
+/// Ends a position and starts the next one at the same employer the following day, in one
+/// transaction: if any step fails, including validation of the new position, the old one is
+/// left as it was.
+///
+/// The old position must still be current and the end date can't be before it started.
+/// Skills and the latest compensation record (with an optional raise) can be carried over.
+pub fn transition_position(
+    db: &Database,
+    old_position_id: i64,
+    transition: PositionTransition,
+) -> Result<PositionTransitionResult, String> {
+    db.in_transaction(|db| {
+        let mut old = db.get_position(old_position_id)?
+            .ok_or_else(|| format!("Position {} not found", old_position_id))?;
+        if let Some(end_date) = old.end_date {
+            return Err(format!("{} at {} already ended on {}", old.job_title, old.employer_name, end_date));
+        }
+        if transition.end_date < old.start_date {
+            return Err(format!("End date {} is before the position started on {}", transition.end_date, old.start_date));
+        }
+
+        old.end_date = Some(transition.end_date);
+        db.save_position(old.clone()).map_err(|e| e.to_string())?;
+
+        let now = Utc::now();
+        let new_position = Position {
+            id: None,
+            employer_name: old.employer_name.clone(),
+            job_title: transition.job_title,
+            employment_type: transition.employment_type.unwrap_or_else(|| old.employment_type.clone()),
+            // The commute only still holds when the move keeps the same workplace
+            commute_minutes_per_day: if transition.location.is_none() { old.commute_minutes_per_day } else { None },
+            location: transition.location.unwrap_or_else(|| old.location.clone()),
+            start_date: transition.end_date + Duration::days(1),
+            end_date: None,
+            seniority_level: transition.seniority_level,
+            core_responsibilities: transition.core_responsibilities,
+            tools_systems_skills: if transition.copy_skills { old.tools_systems_skills.clone() } else { Vec::new() },
+            achievements: Vec::new(),
+            industry: old.industry.clone(),
+            week_ending_day: old.week_ending_day,
+            notice_period_weeks: old.notice_period_weeks,
+            leave_loading_percent: old.leave_loading_percent,
+            probation_months: None, // Already served at this employer
+            archived: false,
+            created_at: now,
+            updated_at: now,
+        };
+        validation::validate_position(&new_position)?;
+        let new_position_id = db.save_position(new_position.clone()).map_err(|e| e.to_string())?;
+
+        let compensation_record_id = if transition.carry_over_compensation {
+            let latest = db.get_compensation_records(old_position_id)?
+                .into_iter()
+                .filter(|r| r.effective_date <= transition.end_date)
+                .max_by_key(|r| r.effective_date)
+                .ok_or("The old position has no compensation record to carry over")?;
+
+            let raise = 1.0 + transition.raise_percent.unwrap_or(0.0) / 100.0;
+            let record = CompensationRecord {
+                id: None,
+                position_id: new_position_id,
+                base_rate: latest.base_rate * raise,
+                effective_date: new_position.start_date,
+                equity: None, // Grants stay with the employer that made them
+                packaging: Vec::new(), // So do packaging arrangements
+                created_at: now,
+                ..latest
+            };
+            validation::validate_compensation_record(&record)?;
+            Some(db.save_compensation_record(record).map_err(|e| e.to_string())?)
+        } else {
+            None
+        };
+
+        Ok(PositionTransitionResult {
+            old_position_id,
+            new_position_id,
+            compensation_record_id,
+        })
+    })
+}
@@ -0,0 +1,500 @@
+use crate::calculations;
+use crate::currency;
+use crate::dates::{self, FinancialYear};
+use crate::import_mapping;
+use crate::models::*;
+use chrono::{Datelike, NaiveDate};
+
+// Plausible working-age range for a profile's date of birth
+const MIN_PROFILE_AGE: i32 = 14;
+const MAX_PROFILE_AGE: i32 = 100;
+
+// Base rates outside these are more likely a typo than real pay
+const PLAUSIBLE_HOURLY_RATE: (f64, f64) = (15.0, 500.0);
+const PLAUSIBLE_SALARY: (f64, f64) = (20000.0, 2000000.0);
+
+const MAX_TAG_LENGTH: usize = 40;
+const MAX_COMMUTE_MINUTES_PER_DAY: u32 = 12 * 60;
+
+// How far reported fringe benefits can be from what the record implies before it's flagged
+const FRINGE_BENEFITS_TOLERANCE_SHARE: f64 = 0.2;
+const FRINGE_BENEFITS_TOLERANCE_DOLLARS: f64 = 1000.0;
+
+// User profile validation; `today` is the user's local date, so the age is right on their birthday
+pub fn validate_user_profile(profile: &UserProfile, today: NaiveDate) -> Result<(), String> {
+    if profile.date_of_birth >= today {
+        return Err("Date of birth must be in the past".to_string());
+    }
+
+    let age = dates::age_on(profile.date_of_birth, today);
+    if !(MIN_PROFILE_AGE..=MAX_PROFILE_AGE).contains(&age) {
+        return Err(format!(
+            "Date of birth gives an age of {}, expected between {} and {}",
+            age, MIN_PROFILE_AGE, MAX_PROFILE_AGE
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_currency(code: &str) -> Result<(), String> {
+    if currency::is_known_currency(code) {
+        Ok(())
+    } else {
+        Err(format!("Unknown currency code '{}', expected an ISO 4217 code such as AUD or NZD", code))
+    }
+}
+
+// Position validation
+pub fn validate_position(position: &Position) -> Result<(), String> {
+    if position.employer_name.trim().is_empty() {
+        return Err("Employer name is required".to_string());
+    }
+    if position.job_title.trim().is_empty() {
+        return Err("Job title is required".to_string());
+    }
+    if let Some(end_date) = position.end_date {
+        if end_date < position.start_date {
+            return Err(format!("End date {} is before start date {}", end_date, position.start_date));
+        }
+    }
+    if position.notice_period_weeks.is_some_and(|weeks| !(weeks.is_finite() && weeks >= 0.0)) {
+        return Err("Notice period must be zero or more weeks".to_string());
+    }
+    if position.leave_loading_percent.is_some_and(|percent| !(0.0..=100.0).contains(&percent)) {
+        return Err("Leave loading must be between 0 and 100 percent".to_string());
+    }
+    if position.commute_minutes_per_day.is_some_and(|minutes| minutes > MAX_COMMUTE_MINUTES_PER_DAY) {
+        return Err("A daily commute can't be longer than 12 hours".to_string());
+    }
+    Ok(())
+}
+
+// Compensation record validation
+pub fn validate_compensation_record(record: &CompensationRecord) -> Result<(), String> {
+    validate_currency(&record.currency)?;
+    if let Some(equity) = &record.equity {
+        if !(equity.grant_value.is_finite() && equity.grant_value >= 0.0) {
+            return Err("Equity grant value must be zero or more".to_string());
+        }
+        if equity.cliff_months > equity.vesting_months {
+            return Err("The vesting cliff can't be longer than the vesting period".to_string());
+        }
+    }
+    for benefit in &record.non_cash_benefits {
+        if benefit.name.trim().is_empty() {
+            return Err("Non-cash benefits need a name".to_string());
+        }
+        if !(benefit.annual_value.is_finite() && benefit.annual_value >= 0.0) {
+            return Err(format!("The value of {} must be zero or more", benefit.name));
+        }
+    }
+    Ok(())
+}
+
+/// Allowed but unlikely: a base rate outside the plausible band for its pay type, which is
+/// more often a typo ($450/hr for $45/hr) than real pay. For the user to confirm on saving.
+pub fn compensation_record_warnings(record: &CompensationRecord) -> Vec<ValidationIssue> {
+    let (low, high, unit) = match record.pay_type {
+        PayType::Hourly => (PLAUSIBLE_HOURLY_RATE.0, PLAUSIBLE_HOURLY_RATE.1, "an hour"),
+        PayType::Salary => (PLAUSIBLE_SALARY.0, PLAUSIBLE_SALARY.1, "a year"),
+    };
+    if (low..=high).contains(&record.base_rate) {
+        return Vec::new();
+    }
+    vec![ValidationIssue {
+        record_type: "compensation_record".to_string(),
+        id: record.id,
+        message: format!(
+            "A base rate of ${:.2} {} is outside the usual ${:.0}-${:.0}; check it isn't a typo",
+            record.base_rate, unit, low, high
+        ),
+        severity: IssueSeverity::Warning,
+    }]
+}
+
+// Yearly income entry validation
+pub fn validate_yearly_entry(entry: &YearlyIncomeEntry) -> Result<(), String> {
+    FinancialYear::parse(&entry.financial_year)?;
+    validate_currency(&entry.currency)
+}
+
+// Exchange rate validation
+pub fn validate_exchange_rate(rate: &ExchangeRate) -> Result<(), String> {
+    validate_currency(&rate.currency)?;
+    if currency::is_base_currency(&rate.currency) {
+        return Err(format!("{} is the base currency and doesn't need an exchange rate", rate.currency));
+    }
+    FinancialYear::parse(&rate.financial_year)?;
+    if !(rate.rate_to_aud.is_finite() && rate.rate_to_aud > 0.0) {
+        return Err("Exchange rate must be a positive number".to_string());
+    }
+    Ok(())
+}
+
+pub fn validate_insurance_policy(policy: &InsurancePolicy) -> Result<(), String> {
+    if policy.provider.trim().is_empty() {
+        return Err("Provider is required".to_string());
+    }
+    if !(policy.annual_premium.is_finite() && policy.annual_premium >= 0.0) {
+        return Err("Annual premium must be zero or more".to_string());
+    }
+    if policy.end_date.is_some_and(|end| end < policy.start_date) {
+        return Err("End date must be on or after the start date".to_string());
+    }
+    Ok(())
+}
+
+pub fn validate_partner_income_entry(entry: &PartnerIncomeEntry) -> Result<(), String> {
+    FinancialYear::parse(&entry.financial_year)?;
+    if !(entry.gross_income.is_finite() && entry.gross_income >= 0.0) {
+        return Err("Partner income must be zero or more".to_string());
+    }
+    Ok(())
+}
+
+/// A tag needs a name that no other tag has, ignoring case.
+pub fn validate_tag(tag: &Tag, existing: &[Tag]) -> Result<(), String> {
+    let name = tag.name.trim();
+    if name.is_empty() {
+        return Err("Tags need a name".to_string());
+    }
+    if name.chars().count() > MAX_TAG_LENGTH {
+        return Err(format!("Tag names can be at most {} characters", MAX_TAG_LENGTH));
+    }
+    if existing.iter().any(|t| t.id != tag.id && t.name.to_lowercase() == name.to_lowercase()) {
+        return Err(format!("There's already a tag called '{}'", name));
+    }
+    Ok(())
+}
+
+pub fn validate_award_rate(rate: &AwardRate) -> Result<(), String> {
+    if rate.award_name.trim().is_empty() {
+        return Err("Award name is required".to_string());
+    }
+    if rate.classification.trim().is_empty() {
+        return Err("Classification is required".to_string());
+    }
+    if !(rate.hourly_minimum.is_finite() && rate.hourly_minimum > 0.0) {
+        return Err("Hourly minimum must be a positive number".to_string());
+    }
+    if !(rate.overtime_multiplier.is_finite() && rate.overtime_multiplier >= 1.0) {
+        return Err("Overtime multiplier must be at least 1".to_string());
+    }
+    if !(0.0..=100.0).contains(&rate.casual_loading) {
+        return Err("Casual loading must be between 0 and 100 percent".to_string());
+    }
+    Ok(())
+}
+
+pub fn validate_lsl_rule(rule: &LslRule) -> Result<(), String> {
+    if !(rule.weeks_per_year.is_finite() && rule.weeks_per_year > 0.0) {
+        return Err("Long service leave weeks per year must be a positive number".to_string());
+    }
+    if !(rule.full_entitlement_years.is_finite() && rule.full_entitlement_years > 0.0) {
+        return Err("Years to full long service leave must be a positive number".to_string());
+    }
+    if !(rule.pro_rata_years.is_finite() && rule.pro_rata_years > 0.0 && rule.pro_rata_years <= rule.full_entitlement_years) {
+        return Err("Years to pro-rata long service leave must be positive and no more than the years to full entitlement".to_string());
+    }
+    Ok(())
+}
+
+// Weekly/pay-period entry validation
+pub fn validate_weekly_entry(entry: &WeeklyCompensationEntry) -> Result<(), String> {
+    validate_currency(&entry.currency)?;
+
+    let period_start = entry.period_start
+        .unwrap_or_else(|| dates::period_start_for(entry.week_ending, &entry.period_type));
+
+    if period_start > entry.week_ending {
+        return Err("Pay period start must be on or before the period end".to_string());
+    }
+
+    let days = dates::period_days(period_start, entry.week_ending);
+    let valid_length = match entry.period_type {
+        PayslipFrequency::Weekly => days == 7,
+        PayslipFrequency::Fortnightly => days == 14,
+        PayslipFrequency::Monthly => (28..=31).contains(&days),
+    };
+
+    if !valid_length {
+        return Err(format!(
+            "A {:?} pay period cannot span {} days ({} to {})",
+            entry.period_type, days, period_start, entry.week_ending
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn validate_projection_assumptions(assumptions: &ProjectionAssumptions) -> Result<(), String> {
+    if !(assumptions.investment_return.is_finite() && (-50.0..=50.0).contains(&assumptions.investment_return)) {
+        return Err("Investment return must be between -50 and 50 percent".to_string());
+    }
+    let valid_spread = |spread: f64| spread.is_finite() && spread >= 0.0;
+    if !(valid_spread(assumptions.return_spread) && valid_spread(assumptions.salary_growth_spread)) {
+        return Err("Projection spreads must be zero or more percentage points".to_string());
+    }
+    Ok(())
+}
+
+pub fn validate_wage_index(index: &WageIndex) -> Result<(), String> {
+    FinancialYear::parse(&index.benchmark_year)?;
+    let valid_growth = |percent: f64| percent.is_finite() && (-20.0..=50.0).contains(&percent);
+    if !valid_growth(index.default_growth_percent) {
+        return Err("Wage growth must be between -20 and 50 percent".to_string());
+    }
+    for year in &index.growth {
+        FinancialYear::parse(&year.financial_year)?;
+        if !valid_growth(year.growth_percent) {
+            return Err(format!("Wage growth for {} must be between -20 and 50 percent", year.financial_year));
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_reminder_settings(settings: &ReminderSettings) -> Result<(), String> {
+    if !(1..=366).contains(&settings.horizon_days) {
+        return Err("Reminders can look ahead between 1 and 366 days".to_string());
+    }
+    Ok(())
+}
+
+pub fn validate_insurance_settings(settings: &InsuranceSettings) -> Result<(), String> {
+    if !(settings.income_protection_threshold.is_finite() && settings.income_protection_threshold >= 0.0) {
+        return Err("The income threshold must be zero or more".to_string());
+    }
+    Ok(())
+}
+
+pub fn validate_auto_export_settings(settings: &AutoExportSettings) -> Result<(), String> {
+    if !settings.enabled {
+        return Ok(());
+    }
+
+    let path = settings.path.as_deref().unwrap_or("").trim();
+    if path.is_empty() {
+        return Err("Choose a folder for automatic exports".to_string());
+    }
+    if !std::path::Path::new(path).is_absolute() {
+        return Err("The automatic export folder must be a full path".to_string());
+    }
+
+    Ok(())
+}
+
+pub fn validate_watch_folder_settings(settings: &WatchFolderSettings) -> Result<(), String> {
+    if settings.pattern.trim().is_empty() {
+        return Err("The file pattern cannot be empty".to_string());
+    }
+    if settings.pattern.contains(['/', '\\']) {
+        return Err("The file pattern must be a file name, not a path".to_string());
+    }
+    if !settings.enabled {
+        return Ok(());
+    }
+
+    let path = settings.path.as_deref().unwrap_or("").trim();
+    if path.is_empty() {
+        return Err("Choose a folder to watch".to_string());
+    }
+    if !std::path::Path::new(path).is_absolute() {
+        return Err("The watched folder must be a full path".to_string());
+    }
+
+    Ok(())
+}
+
+pub fn validate_import_mapping(mapping: &ImportMapping) -> Result<(), String> {
+    if mapping.name.trim().is_empty() {
+        return Err("Give the mapping a name".to_string());
+    }
+    if mapping.columns.is_empty() {
+        return Err("Map at least one column".to_string());
+    }
+
+    let fields = import_mapping::field_names(mapping.target);
+    let mut mapped: Vec<&str> = Vec::new();
+    for column in &mapping.columns {
+        if column.source.trim().is_empty() {
+            return Err(format!("Choose the column to read {} from", column.field));
+        }
+        if !fields.contains(&column.field.as_str()) {
+            return Err(format!("'{}' is not a field that can be imported", column.field));
+        }
+        if mapped.contains(&column.field.as_str()) {
+            return Err(format!("{} is mapped more than once", column.field));
+        }
+        mapped.push(&column.field);
+    }
+
+    for required in import_mapping::required_fields(mapping.target) {
+        if !mapped.contains(required) {
+            return Err(format!("Map a column to {}", required));
+        }
+    }
+    if mapping.target != ImportTarget::Positions
+        && !mapped.contains(&"position_id")
+        && !mapped.contains(&"employer_name")
+    {
+        return Err("Map a column to position_id or employer_name".to_string());
+    }
+
+    Ok(())
+}
+
+/// Runs every save-time check over a whole export, collecting failures instead of stopping
+/// at the first one.
+pub fn validate_all(data: &DataExport, today: NaiveDate) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let mut check = |record_type: &str, id: Option<i64>, result: Result<(), String>| {
+        if let Err(message) = result {
+            issues.push(ValidationIssue {
+                record_type: record_type.to_string(),
+                id,
+                message,
+                severity: IssueSeverity::Error,
+            });
+        }
+    };
+
+    if let Some(profile) = &data.user_profile {
+        check("user_profile", profile.id, validate_user_profile(profile, today));
+    }
+    for position in &data.positions {
+        check("position", position.id, validate_position(position));
+    }
+    for record in &data.compensation_records {
+        check("compensation_record", record.id, validate_compensation_record(record));
+    }
+    for entry in &data.weekly_entries {
+        check("weekly_entry", entry.id, validate_weekly_entry(entry));
+    }
+    for entry in &data.yearly_entries {
+        check("yearly_entry", entry.id, validate_yearly_entry(entry));
+    }
+    for rate in &data.exchange_rates {
+        check("exchange_rate", rate.id, validate_exchange_rate(rate));
+    }
+    for rate in &data.award_rates {
+        check("award_rate", rate.id, validate_award_rate(rate));
+    }
+    for scenario in &data.scenarios {
+        check("scenario", scenario.id, validate_compensation_record(&scenario.offer));
+    }
+
+    issues.extend(data.compensation_records.iter().flat_map(compensation_record_warnings));
+    issues.extend(open_ended_position_warnings(&data.positions, today));
+    issues.extend(week_ending_warnings(&data.positions, &data.weekly_entries));
+    issues.extend(record_before_start_warnings(&data.positions, &data.compensation_records));
+    issues.extend(fringe_benefit_warnings(
+        &data.positions,
+        &data.compensation_records,
+        &data.yearly_entries,
+        &data.exchange_rates,
+    ));
+    issues
+}
+
+/// Warns about each yearly entry whose reportable fringe benefits are well off what the salary
+/// packaging and non-cash benefits on its position's compensation record imply, which usually
+/// means the amounts are out of date. Entries without a position or a reported amount aren't checked.
+pub fn fringe_benefit_warnings(
+    positions: &[Position],
+    records: &[CompensationRecord],
+    entries: &[YearlyIncomeEntry],
+    exchange_rates: &[ExchangeRate],
+) -> Vec<ValidationIssue> {
+    entries.iter()
+        .filter(|entry| entry.jurisdiction == Jurisdiction::AU)
+        .filter_map(|entry| {
+            let reported = entry.reportable_fringe_benefits?;
+            let position = positions.iter().find(|p| p.id.is_some() && p.id == entry.position_id)?;
+            let fy = FinancialYear::parse(&entry.financial_year).ok()?;
+            let expected = calculations::expected_reportable_fringe_benefits(position, records, exchange_rates, &fy)?;
+            let reported_aud = reported * currency::exchange_rate_for(exchange_rates, &entry.currency, &fy)?;
+
+            let tolerance = (expected * FRINGE_BENEFITS_TOLERANCE_SHARE).max(FRINGE_BENEFITS_TOLERANCE_DOLLARS);
+            ((reported_aud - expected).abs() > tolerance).then(|| ValidationIssue {
+                record_type: "yearly_entry".to_string(),
+                id: entry.id,
+                message: format!(
+                    "Reportable fringe benefits of ${:.0} for {} at {} don't match the ${:.0} the salary packaging and benefits on its compensation record imply",
+                    reported_aud, entry.financial_year, position.employer_name, expected
+                ),
+                severity: IssueSeverity::Warning,
+            })
+        })
+        .collect()
+}
+
+/// Warns about each weekly or fortnightly pay entry that doesn't end on its position's
+/// week-ending day, which throws out gap detection. Entries without a position, or whose
+/// position has no day set, aren't checked.
+pub fn week_ending_warnings(positions: &[Position], entries: &[WeeklyCompensationEntry]) -> Vec<ValidationIssue> {
+    entries.iter()
+        .filter(|entry| !matches!(entry.period_type, PayslipFrequency::Monthly))
+        .filter_map(|entry| {
+            let position = positions.iter().find(|p| p.id.is_some() && p.id == entry.position_id)?;
+            let expected = position.week_ending_day?;
+            (entry.week_ending.weekday() != expected).then(|| ValidationIssue {
+                record_type: "weekly_entry".to_string(),
+                id: entry.id,
+                message: format!(
+                    "Pay period ending {} ends on a {}, but pay at {} ends on a {}",
+                    entry.week_ending,
+                    dates::weekday_name(entry.week_ending.weekday()),
+                    position.employer_name,
+                    dates::weekday_name(expected)
+                ),
+                severity: IssueSeverity::Warning,
+            })
+        })
+        .collect()
+}
+
+/// Warns about each compensation record that takes effect before its position starts, which
+/// usually means the record's date or the position's start date is wrong. A position starting
+/// in the future is fine on its own.
+pub fn record_before_start_warnings(positions: &[Position], records: &[CompensationRecord]) -> Vec<ValidationIssue> {
+    records.iter()
+        .filter_map(|record| {
+            let position = positions.iter().find(|p| p.id == Some(record.position_id))?;
+            (record.effective_date < position.start_date).then(|| ValidationIssue {
+                record_type: "compensation_record".to_string(),
+                id: record.id,
+                message: format!(
+                    "Compensation effective {} is before {} at {} starts on {}",
+                    record.effective_date, position.job_title, position.employer_name, position.start_date
+                ),
+                severity: IssueSeverity::Warning,
+            })
+        })
+        .collect()
+}
+
+/// Warns about each open-ended permanent position when there's more than one, which usually
+/// means an old role was never given an end date. A casual or contract role alongside a
+/// permanent one is fine, and so is one that hasn't started yet.
+pub fn open_ended_position_warnings(positions: &[Position], today: NaiveDate) -> Vec<ValidationIssue> {
+    let open_permanent: Vec<&Position> = positions.iter()
+        .filter(|p| p.end_date.is_none() && matches!(p.employment_type, EmploymentType::Permanent))
+        .filter(|p| p.status_on(today) != PositionStatus::Future)
+        .collect();
+    if open_permanent.len() < 2 {
+        return Vec::new();
+    }
+
+    open_permanent.iter()
+        .map(|position| ValidationIssue {
+            record_type: "position".to_string(),
+            id: position.id,
+            message: format!(
+                "{} at {} is one of {} permanent positions with no end date; add an end date to any you've left",
+                position.job_title, position.employer_name, open_permanent.len()
+            ),
+            severity: IssueSeverity::Warning,
+        })
+        .collect()
+}
@@ -0,0 +1,155 @@
+use crate::csv_import;
+use crate::database::Database;
+use crate::models::*;
+use chrono::NaiveDate;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+pub const SETTINGS_KEY: &str = "watch_folder";
+
+/// Subfolders of the watched folder that files are moved into once handled.
+pub const PROCESSED_DIR: &str = "processed";
+pub const FAILED_DIR: &str = "failed";
+
+/// Case-insensitive file name match supporting `*` and `?`.
+pub fn matches_pattern(pattern: &str, file_name: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let name: Vec<char> = file_name.to_lowercase().chars().collect();
+
+    // Backtracking over the most recent `*` is enough for file name globs
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Files directly in the folder that match the pattern, oldest name first. Hidden files
+/// and the `processed`/`failed` subfolders are skipped.
+pub fn matching_files(folder: &Path, pattern: &str) -> Result<Vec<PathBuf>, String> {
+    let entries = std::fs::read_dir(folder)
+        .map_err(|e| format!("Failed to read {}: {}", folder.display(), e))?;
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && is_candidate(path, pattern))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Whether a path should be picked up: a visible file matching the pattern.
+pub fn is_candidate(path: &Path, pattern: &str) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| !name.starts_with('.') && matches_pattern(pattern, name))
+        .unwrap_or(false)
+}
+
+/// Parses a watched file as a dry run. A file that cannot be read, has the wrong columns
+/// or has no usable rows is an error; individual bad rows are left in the preview for the
+/// user to see before confirming.
+pub fn preview_file(path: &Path) -> Result<CsvImportPreview, String> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let preview = csv_import::parse_weekly_entries_csv(file)?;
+
+    if preview.entries.is_empty() {
+        let details: Vec<String> = preview.errors.iter()
+            .map(|e| format!("line {}: {}", e.line, e.message))
+            .collect();
+        return Err(if details.is_empty() {
+            "No rows to import".to_string()
+        } else {
+            format!("No rows could be imported\n{}", details.join("\n"))
+        });
+    }
+
+    Ok(preview)
+}
+
+/// Saves the previewed entries together, skipping any period the position already has an
+/// entry ending on, so a file dropped in twice or overlapping an earlier one adds nothing twice.
+pub fn commit_preview(db: &Database, preview: CsvImportPreview) -> Result<WatchedImportResult, String> {
+    db.in_transaction(|db| {
+        let mut recorded: HashSet<(Option<i64>, NaiveDate)> = db.get_weekly_entries()?
+            .into_iter()
+            .map(|e| (e.position_id, e.week_ending))
+            .collect();
+        let mut result = WatchedImportResult { imported_count: 0, skipped_period_endings: Vec::new() };
+        for entry in preview.entries {
+            if !recorded.insert((entry.position_id, entry.week_ending)) {
+                result.skipped_period_endings.push(entry.week_ending);
+                continue;
+            }
+            db.save_weekly_entry(entry).map_err(|e| e.to_string())?;
+            result.imported_count += 1;
+        }
+        Ok(result)
+    })
+}
+
+/// Moves an imported file into `processed/`.
+pub fn move_to_processed(path: &Path) -> Result<PathBuf, String> {
+    move_into(path, PROCESSED_DIR)
+}
+
+/// Moves a file that could not be imported into `failed/`, with the reason alongside it in
+/// `<file name>.error.txt`.
+pub fn move_to_failed(path: &Path, error: &str) -> Result<PathBuf, String> {
+    let moved = move_into(path, FAILED_DIR)?;
+
+    let mut sidecar = moved.clone().into_os_string();
+    sidecar.push(".error.txt");
+    std::fs::write(&sidecar, error)
+        .map_err(|e| format!("Failed to write {}: {}", PathBuf::from(&sidecar).display(), e))?;
+
+    Ok(moved)
+}
+
+fn move_into(path: &Path, subfolder: &str) -> Result<PathBuf, String> {
+    let parent = path.parent()
+        .ok_or_else(|| format!("{} has no parent folder", path.display()))?;
+    let file_name = path.file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| format!("{} has no file name", path.display()))?;
+
+    let dir = parent.join(subfolder);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+    // Never overwrite an earlier file of the same name
+    let mut destination = dir.join(file_name);
+    let (stem, extension) = match file_name.rsplit_once('.') {
+        Some((stem, extension)) => (stem, format!(".{}", extension)),
+        None => (file_name, String::new()),
+    };
+    let mut counter = 1;
+    while destination.exists() {
+        destination = dir.join(format!("{}-{}{}", stem, counter, extension));
+        counter += 1;
+    }
+
+    std::fs::rename(path, &destination)
+        .map_err(|e| format!("Failed to move {} to {}: {}", path.display(), destination.display(), e))?;
+    Ok(destination)
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use careerflow_core::{anonymize, diagnostics};
+use careerflow_core::models::*;
+use careerflow_core::Database;
+use chrono::Utc;
+use common::*;
+
+fn export(privacy_acknowledged: bool) -> DataExport {
+    let mut first = position("Pilbara Ports", date(2016, 2, 1), Some(date(2019, 6, 30)));
+    first.id = Some(1);
+    first.location = "Port Hedland WA".to_string();
+    let mut second = position("BHP", date(2019, 7, 1), Some(date(2021, 12, 31)));
+    second.id = Some(2);
+    second.location = "Perth, Western Australia".to_string();
+    let mut third = position("pilbara ports ", date(2022, 1, 10), None);
+    third.id = Some(3);
+    third.location = "Auckland".to_string();
+
+    let mut record = salary_record(3, 120000.0, date(2022, 1, 10));
+    record.notes = Some("Negotiated with Jo Bloggs".to_string());
+
+    DataExport {
+        metadata: Some(ExportMetadata { export_date: Utc::now() }),
+        user_profile: Some(UserProfile { gender: Gender::Woman, ..profile(date(1990, 5, 20), privacy_acknowledged) }),
+        // Out of career order on purpose
+        positions: vec![second, third, first],
+        compensation_records: vec![record],
+        weekly_entries: Vec::new(),
+        yearly_entries: Vec::new(),
+        exchange_rates: Vec::new(),
+        award_rates: Vec::new(),
+        scenarios: Vec::new(),
+        templates: Vec::new(),
+        insurance_policies: Vec::new(),
+        partner_income_entries: Vec::new(),
+        tags: Vec::new(),
+        entity_tags: Vec::new(),
+        version: "1.0.0".to_string(),
+        checksum: None,
+    }
+}
+
+#[test]
+fn identity_is_removed_and_pay_kept() {
+    let anonymized = anonymize::anonymize(&export(true), date(2025, 3, 1)).unwrap();
+
+    assert_eq!(anonymized.profile.age_band, "25-34");
+    let employers: Vec<(&str, Option<AustralianState>)> = anonymized.positions.iter()
+        .map(|p| (p.employer.as_str(), p.state))
+        .collect();
+    assert_eq!(format!("{:?}", employers), format!("{:?}", vec![
+        ("Mining employer 1", Some(AustralianState::WA)),
+        ("Mining employer 2", Some(AustralianState::WA)),
+        ("Mining employer 1", None),
+    ]));
+    assert_eq!(anonymized.compensation_records[0].base_rate, 120000.0);
+    assert!(anonymized.compensation_records[0].notes.is_none());
+
+    let json = serde_json::to_string(&anonymized).unwrap();
+    for identifying in ["Sam", "Citizen", "1990", "Pilbara Ports", "BHP", "Hedland", "Bloggs", "Plan shutdowns", "Woman"] {
+        assert!(!json.contains(identifying), "{} leaked", identifying);
+    }
+}
+
+#[test]
+fn refuses_without_privacy_acknowledgement() {
+    assert!(anonymize::anonymize(&export(false), date(2025, 3, 1)).unwrap_err().contains("privacy"));
+
+    let mut no_profile = export(true);
+    no_profile.user_profile = None;
+    assert!(anonymize::anonymize(&no_profile, date(2025, 3, 1)).is_err());
+}
+
+#[test]
+fn locations_and_ages_are_coarsened() {
+    assert!(matches!(anonymize::state_from_location("Victoria Park, Perth"), Some(AustralianState::WA)));
+    assert!(matches!(anonymize::state_from_location("Sydney"), Some(AustralianState::NSW)));
+    assert!(matches!(anonymize::state_from_location("Karratha, wa"), Some(AustralianState::WA)));
+    assert!(anonymize::state_from_location("Remote").is_none());
+
+    assert_eq!(anonymize::age_band(19), "Under 25");
+    assert_eq!(anonymize::age_band(45), "45-54");
+    assert_eq!(anonymize::age_band(70), "65+");
+}
+
+#[test]
+fn diagnostic_bundles_leave_identity_out_and_reproduce_their_outputs() {
+    let (_dir, db) = temp_db();
+    db.save_user_profile(profile(date(1990, 5, 20), true)).unwrap();
+    let mut first = position("Pilbara Ports", date(2015, 2, 1), Some(date(2021, 6, 30)));
+    first.location = "Port Hedland WA".to_string();
+    let first = db.save_position(first).unwrap();
+    let current = db.save_position(position("BHP", date(2021, 7, 1), None)).unwrap();
+    db.save_compensation_record(salary_record(first, 90000.0, date(2015, 2, 1))).unwrap();
+    db.save_compensation_record(salary_record(first, 94000.0, date(2019, 2, 1))).unwrap();
+    let mut record = salary_record(current, 130000.0, date(2021, 7, 1));
+    record.notes = Some("Negotiated with Jo Bloggs".to_string());
+    db.save_compensation_record(record).unwrap();
+    db.save_setting("wage_index", &WageIndex::default()).unwrap();
+    db.save_setting("reminder_settings", &serde_json::json!({ "note": "Sam's phone" })).unwrap();
+
+    let bundle = diagnostics::export_bundle(&db, "1.2.3", date(2025, 3, 1)).unwrap();
+    assert_eq!(bundle.app_version, "1.2.3");
+    assert_eq!(bundle.settings.keys().collect::<Vec<_>>(), vec!["wage_index"]);
+    assert_eq!(bundle.loyalty_tax.tenure_blocks.len(), 2);
+    let json = serde_json::to_string(&bundle).unwrap();
+    for identifying in ["Sam", "Citizen", "1990", "Pilbara Ports", "BHP", "Hedland", "Bloggs", "Plan shutdowns"] {
+        assert!(!json.contains(identifying), "{} leaked", identifying);
+    }
+
+    let scratch = Database::new(":memory:".into()).unwrap();
+    diagnostics::import_bundle(&scratch, &bundle).unwrap();
+    let reproduced = diagnostics::export_bundle(&scratch, "1.2.3", date(2025, 3, 1)).unwrap();
+    assert_eq!(reproduced.loyalty_tax.cumulative_loyalty_tax, bundle.loyalty_tax.cumulative_loyalty_tax);
+    assert_eq!(reproduced.earnings_analysis.current_total_compensation, bundle.earnings_analysis.current_total_compensation);
+    assert_eq!(reproduced.data.compensation_records.len(), 3);
+    assert_eq!(reproduced.settings, bundle.settings);
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use careerflow_core::auto_export;
+use careerflow_core::models::*;
+use chrono::{Duration, TimeZone, Utc};
+use common::*;
+
+fn enabled_settings(folder: &std::path::Path) -> AutoExportSettings {
+    AutoExportSettings {
+        enabled: true,
+        path: Some(folder.to_string_lossy().into_owned()),
+        frequency: AutoExportFrequency::Daily,
+        retention_count: 2,
+    }
+}
+
+#[test]
+fn export_is_due_once_per_period() {
+    let folder = tempfile::tempdir().unwrap();
+    let now = Utc.with_ymd_and_hms(2025, 3, 10, 12, 0, 0).unwrap();
+    let mut settings = enabled_settings(folder.path());
+    let mut status = AutoExportStatus::default();
+
+    assert!(auto_export::is_due(&settings, &status, now));
+
+    status.last_success = Some(now - Duration::hours(1));
+    assert!(!auto_export::is_due(&settings, &status, now));
+
+    status.last_success = Some(now - Duration::days(2));
+    assert!(auto_export::is_due(&settings, &status, now));
+
+    settings.frequency = AutoExportFrequency::Weekly;
+    assert!(!auto_export::is_due(&settings, &status, now));
+
+    settings.enabled = false;
+    status.last_success = None;
+    assert!(!auto_export::is_due(&settings, &status, now));
+}
+
+#[test]
+fn run_if_due_writes_export_and_records_status() {
+    let (_dir, db) = temp_db();
+    let folder = tempfile::tempdir().unwrap();
+    db.save_setting(auto_export::SETTINGS_KEY, &enabled_settings(folder.path())).unwrap();
+    let now = Utc::now();
+
+    let status = auto_export::run_if_due(&db, now).unwrap().unwrap();
+
+    assert_eq!(status.last_error, None);
+    let file = std::path::PathBuf::from(status.last_file.unwrap());
+    assert!(file.file_name().unwrap().to_str().unwrap().starts_with("careerflow-export-"));
+    let export: DataExport = serde_json::from_str(&std::fs::read_to_string(file).unwrap()).unwrap();
+    assert!(export.positions.is_empty());
+
+    // Already ran today
+    assert!(auto_export::run_if_due(&db, now).unwrap().is_none());
+}
+
+#[test]
+fn missing_folder_is_recorded_not_fatal() {
+    let (dir, db) = temp_db();
+    let missing = dir.path().join("OneDrive").join("Backups");
+    db.save_setting(auto_export::SETTINGS_KEY, &enabled_settings(&missing)).unwrap();
+
+    let status = auto_export::run_if_due(&db, Utc::now()).unwrap().unwrap();
+
+    assert!(status.last_error.unwrap().contains("does not exist"));
+    assert!(status.last_success.is_none());
+    let saved: AutoExportStatus = db.get_setting(auto_export::STATUS_KEY).unwrap();
+    assert!(saved.last_attempt.is_some());
+}
+
+#[test]
+fn prune_keeps_newest_exports_only() {
+    let folder = tempfile::tempdir().unwrap();
+    for name in [
+        "careerflow-export-20250101.json",
+        "careerflow-export-20250102.json",
+        "careerflow-export-20250103.json",
+        "notes.json",
+    ] {
+        std::fs::write(folder.path().join(name), "{}").unwrap();
+    }
+
+    auto_export::prune_exports(folder.path(), 2).unwrap();
+
+    let mut remaining: Vec<_> = std::fs::read_dir(folder.path()).unwrap()
+        .map(|e| e.unwrap().file_name().into_string().unwrap())
+        .collect();
+    remaining.sort();
+    assert_eq!(remaining, vec![
+        "careerflow-export-20250102.json",
+        "careerflow-export-20250103.json",
+        "notes.json",
+    ]);
+}
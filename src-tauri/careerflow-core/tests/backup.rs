@@ -0,0 +1,159 @@
+mod common;
+
+use careerflow_core::{backup, calculations};
+use careerflow_core::models::*;
+use careerflow_core::Database;
+use chrono::Utc;
+use common::*;
+
+// Saved out of career order, so id order and display order differ
+fn seeded_db() -> (tempfile::TempDir, Database) {
+    let (dir, db) = temp_db();
+    db.save_user_profile(profile(date(1990, 5, 20), true)).unwrap();
+    let current = db.save_position(position("Rio Tinto", date(2022, 7, 1), None)).unwrap();
+    let earlier = db.save_position(position("Pilbara Ports", date(2020, 2, 3), Some(date(2022, 6, 30)))).unwrap();
+    db.save_compensation_record(salary_record(current, 120000.0, date(2022, 7, 1))).unwrap();
+    db.save_compensation_record(salary_record(earlier, 95000.0, date(2020, 2, 3))).unwrap();
+    db.save_compensation_record(salary_record(current, 126000.0, date(2023, 7, 1))).unwrap();
+    db.save_weekly_entry(weekly_entry(Some(current), date(2024, 7, 14), 2300.0)).unwrap();
+    db.save_weekly_entry(weekly_entry(Some(current), date(2024, 7, 7), 2300.0)).unwrap();
+    db.save_yearly_entry(yearly_entry("FY2023-24", 118000.0)).unwrap();
+    db.save_yearly_entry(yearly_entry("FY2021-22", 97000.0)).unwrap();
+    for (currency, financial_year) in [("NZD", "FY2023-24"), ("NZD", "FY2022-23")] {
+        db.save_exchange_rate(ExchangeRate {
+            id: None,
+            currency: currency.to_string(),
+            financial_year: financial_year.to_string(),
+            rate_to_aud: 0.92,
+        }).unwrap();
+    }
+    db.save_insurance_policy(InsurancePolicy {
+        id: None,
+        policy_type: InsuranceType::IncomeProtection,
+        provider: "TAL".to_string(),
+        annual_premium: 1234.567,
+        through_super: false,
+        start_date: date(2022, 7, 1),
+        end_date: None,
+    }).unwrap();
+    db.save_partner_income_entry(PartnerIncomeEntry {
+        id: None,
+        financial_year: "FY2023-24".to_string(),
+        gross_income: 64000.0,
+        is_estimate: true,
+    }).unwrap();
+    let records = db.get_compensation_records(current).unwrap();
+    let offer = salary_record(current, 140000.0, date(2024, 8, 1));
+    db.save_scenario(calculations::offer_scenario("BHP offer", current, &records, offer, &[], false).unwrap()).unwrap();
+    (dir, db)
+}
+
+// The metadata differs between any two exports by design
+fn to_json(mut export: DataExport) -> String {
+    export.metadata = None;
+    serde_json::to_string_pretty(&export).unwrap()
+}
+
+#[test]
+fn exports_are_in_a_fixed_order_and_byte_identical() {
+    let (_dir, db) = seeded_db();
+
+    let first = backup::export_all_data(&db).unwrap();
+    let ids: Vec<Option<i64>> = first.positions.iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![Some(2), Some(1)]);
+    let ids: Vec<Option<i64>> = first.compensation_records.iter().map(|r| r.id).collect();
+    assert_eq!(ids, vec![Some(1), Some(3), Some(2)]);
+    let dates: Vec<chrono::NaiveDate> = first.weekly_entries.iter().map(|e| e.week_ending).collect();
+    assert_eq!(dates, vec![date(2024, 7, 7), date(2024, 7, 14)]);
+    let years: Vec<&str> = first.exchange_rates.iter().map(|r| r.financial_year.as_str()).collect();
+    assert_eq!(years, vec!["FY2022-23", "FY2023-24"]);
+
+    let second = backup::export_all_data(&db).unwrap();
+    assert!(first.metadata.is_some());
+    assert_eq!(first.checksum, second.checksum);
+    assert_eq!(to_json(first), to_json(second));
+}
+
+#[test]
+fn money_is_rounded_to_the_cent() {
+    let (_dir, db) = temp_db();
+    let id = db.save_position(position("Rio Tinto", date(2022, 7, 1), None)).unwrap();
+    db.save_weekly_entry(weekly_entry(Some(id), date(2024, 7, 7), 0.1 + 0.2)).unwrap();
+    db.save_weekly_entry(weekly_entry(Some(id), date(2024, 7, 14), -0.001)).unwrap();
+
+    let export = backup::export_all_data(&db).unwrap();
+    let json = serde_json::to_string(&export).unwrap();
+    assert_eq!(export.weekly_entries[0].gross_pay, 0.3);
+    assert!(json.contains(r#""gross_pay":0.3,"#));
+    assert!(json.contains(r#""gross_pay":0.0,"#) && !json.contains("-0.0"));
+}
+
+#[test]
+fn import_warns_when_the_checksum_does_not_match() {
+    let (_dir, db) = seeded_db();
+    let export = backup::export_all_data(&db).unwrap();
+    assert_eq!(export.checksum.as_deref().map(str::len), Some(64));
+
+    // The metadata isn't covered, so a later date doesn't matter
+    let mut untouched = export.clone();
+    untouched.metadata = Some(ExportMetadata { export_date: Utc::now() });
+    assert!(backup::import_all_data(&db, untouched, date(2025, 6, 2)).unwrap().warnings.is_empty());
+
+    let mut edited = export;
+    edited.positions[0].job_title = "Superintendent".to_string();
+    let result = backup::import_all_data(&db, edited, date(2025, 6, 2)).unwrap();
+    assert!(result.success);
+    assert_eq!(result.warnings.len(), 1);
+    assert!(result.warnings[0].contains("checksum"));
+}
+
+#[test]
+fn export_import_export_is_stable() {
+    let (_dir, db) = seeded_db();
+    let before = backup::export_all_data(&db).unwrap();
+
+    let document = serde_json::to_string(&before).unwrap();
+    let imported: DataExport = serde_json::from_str(&document).unwrap();
+    backup::import_all_data(&db, imported, date(2025, 6, 2)).unwrap();
+
+    // Saving stamps updated_at, which is the only thing an import may change
+    let mut after = backup::export_all_data(&db).unwrap();
+    if let (Some(profile), Some(previous)) = (after.user_profile.as_mut(), before.user_profile.as_ref()) {
+        profile.updated_at = previous.updated_at;
+    }
+    for (position, previous) in after.positions.iter_mut().zip(&before.positions) {
+        position.updated_at = previous.updated_at;
+    }
+    after.checksum = Some(backup::checksum(&after).unwrap());
+    assert_eq!(to_json(before), to_json(after));
+}
+
+#[test]
+fn import_rejects_a_profile_with_an_impossible_date_of_birth() {
+    let (_dir, db) = seeded_db();
+    let mut export = backup::export_all_data(&db).unwrap();
+    export.user_profile.as_mut().unwrap().date_of_birth = date(2030, 1, 1);
+
+    let (_dir, fresh) = temp_db();
+    assert!(backup::import_all_data(&fresh, export, date(2025, 6, 2)).unwrap_err().contains("Date of birth"));
+    assert_eq!(fresh.get_user_profile().unwrap(), None);
+}
+
+#[test]
+fn an_import_failing_partway_leaves_the_database_as_it_was() {
+    let (_dir, db) = seeded_db();
+    let mut export = backup::export_all_data(&db).unwrap();
+    // Exchange rates are imported after the positions, records and entries
+    export.exchange_rates[0].rate_to_aud = -1.0;
+
+    let (_dir, fresh) = temp_db();
+    assert!(backup::import_all_data(&fresh, export, date(2025, 6, 2)).is_err());
+    assert_eq!(fresh.get_user_profile().unwrap(), None);
+    assert!(fresh.get_positions().unwrap().is_empty());
+    assert!(fresh.get_all_compensation_records().unwrap().is_empty());
+    assert!(fresh.get_weekly_entries().unwrap().is_empty());
+    assert!(fresh.get_yearly_entries().unwrap().is_empty());
+
+    // Nothing was held back either, so the next save gets the first id
+    assert_eq!(fresh.save_position(position("Acme", date(2024, 1, 8), None)).unwrap(), 1);
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use careerflow_core::{bulk_edit, validation};
+use careerflow_core::models::*;
+use chrono::{Datelike, Weekday};
+use common::*;
+
+#[test]
+fn entries_outside_the_target_position_stay_put() {
+    let (_dir, db) = temp_db();
+    let wrong = db.save_position(position("Pilbara Ports", date(2020, 2, 3), None)).unwrap();
+    let right = db.save_position(position("Rio Tinto", date(2023, 7, 1), None)).unwrap();
+    for week_ending in [date(2023, 6, 25), date(2023, 7, 9), date(2023, 7, 16)] {
+        db.save_weekly_entry(weekly_entry(Some(wrong), week_ending, 2000.0)).unwrap();
+    }
+
+    let results = bulk_edit::reassign_weekly_entries(&db, Some(wrong), right, None).unwrap();
+    assert_eq!(results.len(), 3);
+    assert!(!results[0].updated && results[0].error.as_deref().unwrap().contains("outside"));
+    assert!(results[1].updated && results[2].updated);
+
+    let moved = db.get_weekly_entries().unwrap().iter().filter(|e| e.position_id == Some(right)).count();
+    assert_eq!(moved, 2);
+
+    let missing = bulk_edit::reassign_weekly_entries(&db, Some(wrong), 999, None);
+    assert!(missing.unwrap_err().contains("not found"));
+}
+
+#[test]
+fn records_are_not_moved_onto_a_date_the_target_already_has() {
+    let (_dir, db) = temp_db();
+    let from = db.save_position(position("Pilbara Ports", date(2021, 1, 1), None)).unwrap();
+    let to = db.save_position(position("Rio Tinto", date(2021, 1, 1), None)).unwrap();
+    db.save_compensation_record(salary_record(from, 90000.0, date(2021, 7, 1))).unwrap();
+    db.save_compensation_record(salary_record(from, 95000.0, date(2022, 7, 1))).unwrap();
+    db.save_compensation_record(salary_record(to, 99000.0, date(2022, 7, 1))).unwrap();
+
+    let results = bulk_edit::reassign_compensation_records(&db, from, to, None).unwrap();
+    let updated: Vec<bool> = results.iter().map(|r| r.updated).collect();
+    assert_eq!(updated, vec![true, false]);
+    assert_eq!(db.get_compensation_records(to).unwrap().len(), 2);
+    assert_eq!(db.get_compensation_records(from).unwrap().len(), 1);
+}
+
+#[test]
+fn a_patch_sets_the_multiplier_and_super_on_each_entry() {
+    let (_dir, db) = temp_db();
+    let mut entry = weekly_entry(None, date(2023, 7, 9), 2400.0);
+    entry.hours_ordinary = 38.0;
+    entry.hours_overtime = 8.0;
+    entry.overtime_rate_multiplier = 1.5;
+    let id = db.save_weekly_entry(entry).unwrap();
+
+    let patch = WeeklyEntryPatch { overtime_rate_multiplier: Some(2.0), super_rate: Some(10.0) };
+    let results = bulk_edit::update_weekly_entries(&db, &[id, 999], &patch).unwrap();
+    assert!(results[0].updated);
+    assert!(!results[1].updated && results[1].error.as_deref().unwrap().contains("not found"));
+
+    let saved = db.get_weekly_entries().unwrap().remove(0);
+    assert_eq!(saved.overtime_rate_multiplier, 2.0);
+    // 38 of 54 weighted hours are ordinary
+    assert!((saved.super_contributed - 2400.0 * 38.0 / 54.0 * 0.1).abs() < 1e-9);
+
+    let bad = WeeklyEntryPatch { overtime_rate_multiplier: Some(0.5), ..Default::default() };
+    assert!(bulk_edit::update_weekly_entries(&db, &[id], &bad).is_err());
+}
+
+#[test]
+fn week_endings_move_to_the_nearest_pay_day_after_a_dry_run() {
+    let (_dir, db) = temp_db();
+    let position_id = db.save_position(position("Pilbara Ports", date(2024, 1, 1), None)).unwrap();
+    // Keyed to Sundays, but pay weeks end on a Tuesday
+    let sunday = db.save_weekly_entry(weekly_entry(Some(position_id), date(2024, 7, 7), 2000.0)).unwrap();
+    let tuesday = db.save_weekly_entry(weekly_entry(Some(position_id), date(2024, 7, 16), 2000.0)).unwrap();
+    let friday = db.save_weekly_entry(weekly_entry(Some(position_id), date(2024, 7, 26), 2000.0)).unwrap();
+
+    let preview = bulk_edit::normalize_week_endings(&db, position_id, Weekday::Tue, true).unwrap();
+    assert!(!preview.applied);
+    let moves: Vec<(i64, i64)> = preview.shifts.iter().map(|s| (s.entry_id, s.days)).collect();
+    assert_eq!(moves, vec![(sunday, 2), (friday, 4 - 7)]);
+    assert!(db.get_weekly_entries().unwrap().iter().any(|e| e.week_ending == date(2024, 7, 7)));
+
+    let applied = bulk_edit::normalize_week_endings(&db, position_id, Weekday::Tue, false).unwrap();
+    assert!(applied.applied);
+    let entries = db.get_weekly_entries().unwrap();
+    assert!(entries.iter().all(|e| e.week_ending.weekday() == Weekday::Tue));
+    let moved = entries.iter().find(|e| e.id == Some(sunday)).unwrap();
+    assert_eq!(moved.week_ending, date(2024, 7, 9));
+    assert_eq!(moved.period_start, Some(date(2024, 7, 3)));
+    assert_eq!(moved.gross_pay, 2000.0);
+    assert!(entries.iter().any(|e| e.id == Some(tuesday) && e.week_ending == date(2024, 7, 16)));
+    assert_eq!(db.get_position(position_id).unwrap().unwrap().week_ending_day, Some(Weekday::Tue));
+
+    let mut off_day = weekly_entry(Some(position_id), date(2024, 8, 4), 2000.0);
+    off_day.id = Some(99);
+    let positions = db.get_positions().unwrap();
+    let warnings = validation::week_ending_warnings(&positions, &[off_day]);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, IssueSeverity::Warning);
+    assert!(warnings[0].message.contains("ends on a Sunday"));
+
+    // Two entries can't end up on the same day
+    db.save_weekly_entry(weekly_entry(Some(position_id), date(2024, 8, 4), 2000.0)).unwrap();
+    db.save_weekly_entry(weekly_entry(Some(position_id), date(2024, 8, 7), 2000.0)).unwrap();
+    assert!(bulk_edit::normalize_week_endings(&db, position_id, Weekday::Tue, false).is_err());
+}
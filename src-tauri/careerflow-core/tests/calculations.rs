@@ -0,0 +1,1927 @@
+mod common;
+
+use careerflow_core::{calculations, lsl};
+use careerflow_core::dates::{FinancialYear, YearBasis};
+use careerflow_core::models::*;
+use careerflow_core::tax;
+use common::*;
+
+#[test]
+fn financial_year_summary_totals_weekly_entries() {
+    let entries: Vec<_> = (0..4)
+        .map(|week| weekly_entry(Some(1), date(2024, 7, 7) + chrono::Duration::weeks(week), 2000.0))
+        .collect();
+
+    let summaries = calculations::calculate_financial_year_summaries(&entries, &[], &[]);
+
+    assert_eq!(summaries.len(), 1);
+    let summary = &summaries[0];
+    assert_eq!(summary.financial_year, "FY2024-25");
+    assert_eq!(summary.gross_income, 8000.0);
+    assert_eq!(summary.periods_recorded, 4);
+    assert_eq!(summary.average_weekly_gross, 2000.0);
+}
+
+#[test]
+fn nz_yearly_entries_use_april_to_march_year() {
+    let mut entry = yearly_entry("FY2023-24", 80000.0);
+    entry.currency = "NZD".to_string();
+    entry.jurisdiction = Jurisdiction::NZ;
+    let rates = vec![ExchangeRate {
+        id: None,
+        currency: "NZD".to_string(),
+        financial_year: "FY2023-24".to_string(),
+        rate_to_aud: 0.9,
+    }];
+
+    let summaries = calculations::calculate_financial_year_summaries(&[], &[entry], &rates);
+
+    assert_eq!(summaries[0].jurisdiction, Jurisdiction::NZ);
+    assert_eq!(summaries[0].year_start, date(2023, 4, 1));
+    assert_eq!(summaries[0].year_end, date(2024, 3, 31));
+    assert_eq!(summaries[0].ato_gross_income, Some(72000.0));
+}
+
+#[test]
+fn income_composition_converts_to_aud() {
+    let mut record = salary_record(1, 100000.0, date(2023, 7, 1));
+    record.currency = "NZD".to_string();
+    let rates = vec![ExchangeRate {
+        id: None,
+        currency: "NZD".to_string(),
+        financial_year: "FY2023-24".to_string(),
+        rate_to_aud: 0.9,
+    }];
+
+    let composition = calculations::calculate_income_composition(&record, &rates);
+
+    assert_eq!(composition.exchange_rate_to_aud, Some(0.9));
+    assert!((composition.original_total_compensation - 111500.0).abs() < 0.01);
+    assert!((composition.total_compensation - 100350.0).abs() < 0.01);
+}
+
+#[test]
+fn a_career_from_australia_to_new_zealand_and_back_is_counted_in_aud() {
+    let nzd = |financial_year: &str, rate_to_aud: f64| ExchangeRate {
+        id: None,
+        currency: "NZD".to_string(),
+        financial_year: financial_year.to_string(),
+        rate_to_aud,
+    };
+    let rates = vec![nzd("FY2020-21", 0.93), nzd("FY2021-22", 0.95)];
+
+    let perth = Position { id: Some(1), ..position("Rio Tinto", date(2019, 2, 4), Some(date(2020, 12, 18))) };
+    let auckland = Position { id: Some(2), location: "Auckland".to_string(), ..position("Fonterra", date(2021, 1, 11), Some(date(2022, 6, 24))) };
+    let brisbane = Position { id: Some(3), location: "Brisbane QLD".to_string(), ..position("Aurizon", date(2022, 7, 4), None) };
+    let nz_record = CompensationRecord {
+        currency: "NZD".to_string(),
+        jurisdiction: Jurisdiction::NZ,
+        ..salary_record(2, 110000.0, date(2021, 1, 11))
+    };
+    let records = vec![salary_record(1, 90000.0, date(2019, 2, 4)), nz_record.clone(), salary_record(3, 120000.0, date(2022, 7, 4))];
+
+    // Each package in AUD at its own year's rate
+    let compositions: Vec<_> = records.iter().map(|r| calculations::calculate_income_composition(r, &rates)).collect();
+    let rates_used: Vec<Option<f64>> = compositions.iter().map(|c| c.exchange_rate_to_aud).collect();
+    assert_eq!(rates_used, vec![Some(1.0), Some(0.93), Some(1.0)]);
+    assert!((compositions[1].total_compensation - compositions[1].original_total_compensation * 0.93).abs() < 0.01);
+    assert!((compositions[2].total_compensation - 133800.0).abs() < 0.01);
+    assert!(compositions.iter().all(|c| c.warnings.is_empty()));
+
+    let mut entries = vec![
+        weekly_entry(Some(1), date(2019, 9, 6), 2000.0),
+        weekly_entry(Some(2), date(2021, 3, 5), 2500.0),
+        weekly_entry(Some(2), date(2021, 9, 3), 2500.0),
+        weekly_entry(Some(3), date(2022, 9, 2), 2400.0),
+    ];
+    entries[1].currency = "NZD".to_string();
+    entries[2].currency = "NZD".to_string();
+    let summaries = calculations::calculate_financial_year_summaries(&entries, &[], &rates);
+    let gross: Vec<(String, f64)> = summaries.iter().map(|s| (s.financial_year.clone(), s.gross_income)).collect();
+    assert_eq!(gross.len(), 4);
+    assert_eq!(gross[0], ("FY2019-20".to_string(), 2000.0));
+    assert!((gross[1].1 - 2500.0 * 0.93).abs() < 1e-9);
+    assert!((gross[2].1 - 2500.0 * 0.95).abs() < 1e-9);
+    assert_eq!(gross[3], ("FY2022-23".to_string(), 2400.0));
+    assert_eq!(summaries[1].foreign_income[0].aud_gross, Some(2500.0 * 0.93));
+    assert!(summaries[0].foreign_income.is_empty() && summaries[3].foreign_income.is_empty());
+
+    let analysis = calculations::calculate_earnings_analysis(&[perth, auckland, brisbane], &records, &None, &rates, date(2024, 6, 1));
+    let mut timeline: Vec<&EarningsSnapshot> = analysis.earnings_over_time.iter().collect();
+    timeline.sort_by_key(|s| s.date);
+    let jurisdictions: Vec<Jurisdiction> = timeline.iter().map(|s| s.jurisdiction).collect();
+    assert_eq!(jurisdictions, vec![Jurisdiction::AU, Jurisdiction::NZ, Jurisdiction::AU]);
+    assert!((timeline[1].actual_annual - 110000.0 * 0.93).abs() < 0.01);
+    assert!(!analysis.warnings.iter().any(|w| w.code == AnalysisWarningCode::MissingExchangeRate));
+
+    // With no NZD rate at all the New Zealand amounts can't be counted, and each result says so
+    let unconverted = calculations::calculate_income_composition(&nz_record, &[]);
+    assert_eq!(unconverted.exchange_rate_to_aud, None);
+    assert_eq!(unconverted.total_compensation, 0.0);
+    assert!((unconverted.original_total_compensation - 122650.0).abs() < 0.01);
+    assert_eq!(unconverted.warnings.len(), 1);
+    assert_eq!(unconverted.warnings[0].code, AnalysisWarningCode::MissingExchangeRate);
+    assert!(matches!(unconverted.warnings[0].related_entity, Some(RelatedEntity::Position(2))));
+
+    let summaries = calculations::calculate_financial_year_summaries(&entries, &[], &[]);
+    assert_eq!(summaries[1].gross_income, 0.0);
+    assert_eq!(summaries[1].foreign_income[0].aud_gross, None);
+    assert!(summaries[1].warnings.iter().any(|w| w.code == AnalysisWarningCode::MissingExchangeRate));
+    assert_eq!(summaries[3].gross_income, 2400.0);
+}
+
+#[test]
+fn non_cash_benefits_count_towards_package_value_but_not_cash() {
+    let mut with_car = salary_record(1, 100000.0, date(2024, 7, 1));
+    with_car.non_cash_benefits = vec![NonCashBenefit {
+        name: "Car".to_string(),
+        annual_value: 15000.0,
+        fringe_benefit: true,
+    }];
+    let more_cash = salary_record(2, 112000.0, date(2024, 7, 1));
+
+    let car = calculations::calculate_income_composition(&with_car, &[]);
+    let cash = calculations::calculate_income_composition(&more_cash, &[]);
+    assert!(cash.total_compensation > car.total_compensation);
+    assert!((car.total_package_value - (111500.0 + 15000.0)).abs() < 0.01);
+    assert!(car.total_package_value > cash.total_package_value);
+    assert_eq!(cash.non_cash_benefits, 0.0);
+
+    // The car is grossed up as a reportable fringe benefit
+    let mut held = position("Rio Tinto", date(2024, 7, 1), None);
+    held.id = Some(1);
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+    let reportable = calculations::expected_reportable_fringe_benefits(&held, &[with_car], &[], &fy).unwrap();
+    assert!((reportable - tax::reportable_fringe_benefits(15000.0)).abs() < 0.01);
+    assert!(reportable > 28000.0);
+}
+
+#[test]
+fn co_contribution_phases_out_between_thresholds() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+
+    assert_eq!(calculations::calculate_co_contribution(40000.0, 1000.0, &fy), 500.0);
+    assert_eq!(calculations::calculate_co_contribution(40000.0, 200.0, &fy), 100.0);
+    assert_eq!(calculations::calculate_co_contribution(70000.0, 1000.0, &fy), 0.0);
+    let partial = calculations::calculate_co_contribution(55000.0, 1000.0, &fy);
+    assert!(partial > 0.0 && partial < 500.0);
+}
+
+#[test]
+fn personal_percentile_ranks_a_year_against_the_others() {
+    let statements: Vec<YearlyIncomeEntry> = [("FY2020-21", 70000.0), ("FY2021-22", 90000.0), ("FY2022-23", 80000.0), ("FY2023-24", 90000.0)]
+        .iter()
+        .map(|(fy, gross)| yearly_entry(fy, *gross))
+        .collect();
+    let summaries = calculations::calculate_financial_year_summaries(&[], &statements, &[]);
+    let percentile = |label: &str| calculations::personal_earnings_percentile(&summaries, &FinancialYear::parse(label).unwrap());
+
+    assert_eq!(percentile("FY2020-21").unwrap(), 0.0);
+    assert!((percentile("FY2022-23").unwrap() - 100.0 / 3.0).abs() < 1e-9);
+    // Beats two years and ties the third
+    assert!((percentile("FY2023-24").unwrap() - 250.0 / 3.0).abs() < 1e-9);
+    assert!(percentile("FY2019-20").is_err());
+    assert!(calculations::personal_earnings_percentile(&summaries[..1], &FinancialYear::parse("FY2020-21").unwrap()).is_err());
+}
+
+#[test]
+fn equity_vests_on_the_cliff_then_each_quarter() {
+    let grant = EquityGrant {
+        grant_value: 48000.0,
+        grant_date: date(2023, 3, 1),
+        vesting_months: 48,
+        cliff_months: 12,
+        vesting_frequency: VestingFrequency::Quarterly,
+    };
+    let events = grant.vesting_events();
+    assert_eq!(events.len(), 13);
+    assert_eq!(events[0], (date(2024, 3, 1), 12000.0));
+    assert_eq!(events[1], (date(2024, 6, 1), 3000.0));
+    assert_eq!(events.last().unwrap().0, date(2027, 3, 1));
+    assert_eq!(grant.vested_by(date(2024, 2, 29)), 0.0);
+    assert_eq!(grant.vested_by(date(2027, 3, 1)), 48000.0);
+
+    // The same grant copied onto a later record counts once
+    let mut record = salary_record(1, 150000.0, date(2023, 3, 1));
+    record.equity = Some(grant);
+    let mut raise = record.clone();
+    raise.effective_date = date(2024, 3, 1);
+
+    let equity = calculations::calculate_equity_by_fy(&[record, raise], &[]);
+    assert_eq!(equity[0].financial_year, "FY2022-23");
+    assert_eq!((equity[0].vested, equity[0].unvested), (0.0, 48000.0));
+    assert_eq!((equity[1].vested, equity[1].unvested), (15000.0, 33000.0));
+    assert_eq!((equity[2].vested, equity[2].unvested), (12000.0, 21000.0));
+    assert_eq!(equity.last().unwrap().financial_year, "FY2026-27");
+
+    let mut summaries = calculations::calculate_financial_year_summaries(
+        &[weekly_entry(Some(1), date(2024, 7, 7), 3000.0)], &[], &[],
+    );
+    calculations::add_equity_to_summaries(&mut summaries, &equity);
+    assert_eq!(summaries[0].vested_equity, 12000.0);
+    assert_eq!(summaries[0].unvested_equity, 21000.0);
+    assert_eq!(summaries[0].total_compensation, 15000.0);
+}
+
+#[test]
+fn div293_starts_just_past_the_threshold_and_flattens_the_sacrifice_benefit() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+    assert_eq!(tax::div293_threshold(&fy), 250000.0);
+    assert_eq!(tax::div293_threshold(&FinancialYear::parse("FY2016-17").unwrap()), 300000.0);
+
+    // Exactly at the threshold nothing is due; a dollar over is taxed at 15%
+    assert_eq!(tax::div293_liability(240000.0, 10000.0, &fy), 0.0);
+    assert!((tax::div293_liability(240001.0, 10000.0, &fy) - 0.15).abs() < 1e-9);
+
+    let curve = calculations::calculate_sacrifice_benefit_curve(10000.0, 0.0, 250000.0, 260000.0, 10000.0, &fy).unwrap();
+    // 45% saved against 15% contributions tax, then another 15% once the whole sacrifice is caught
+    assert_eq!(curve[0].div293, 0.0);
+    assert!((curve[0].benefit_per_dollar - 0.30).abs() < 1e-9);
+    assert!((curve[1].div293 - 1500.0).abs() < 1e-9);
+    assert!((curve[1].benefit_per_dollar - 0.15).abs() < 1e-9);
+}
+
+#[test]
+fn div293_taxes_the_lesser_of_contributions_and_the_excess() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+    assert_eq!(tax::div293_liability(200000.0, 30000.0, &fy), 0.0);
+    assert!((tax::div293_liability(240000.0, 30000.0, &fy) - 3000.0).abs() < 1e-9);
+    assert!((tax::div293_liability(300000.0, 30000.0, &fy) - 4500.0).abs() < 1e-9);
+
+    let mut held = position("Rio Tinto", date(2022, 7, 1), None);
+    held.id = Some(1);
+    let mut record = salary_record(1, 280000.0, date(2024, 7, 1));
+    record.super_contributions.salary_sacrifice = 10000.0;
+    let analysis = calculations::calculate_earnings_analysis(&[held], &[record], &None, &[], date(2025, 3, 1));
+    let insight = analysis.insights.iter().find(|i| i.title.contains("Division 293")).unwrap();
+    assert!(insight.data_points.iter().any(|p| p.contains("30%")));
+}
+
+#[test]
+fn income_tax_uses_the_year_brackets() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+
+    assert_eq!(tax::income_tax(&Jurisdiction::AU, 18200.0, &fy), 0.0);
+    assert!((tax::income_tax(&Jurisdiction::AU, 45000.0, &fy) - 4288.0).abs() < 0.01);
+    assert!((tax::income_tax(&Jurisdiction::NZ, 15600.0, &FinancialYear::parse("FY2025-26").unwrap()) - 1638.0).abs() < 0.01);
+}
+
+#[test]
+fn tax_rate_curve_includes_the_offset_and_medicare() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+
+    // $1,888 income tax less the full $700 offset, plus the Medicare levy shading in
+    assert!((tax::total_tax_payable(30000.0, &fy) - 1465.8).abs() < 0.01);
+    // Offset withdrawn to $325 at $45,000; full 2% levy
+    assert!((tax::total_tax_payable(45000.0, &fy) - 4863.0).abs() < 0.01);
+    assert_eq!(tax::low_income_tax_offset(70000.0, &fy), 0.0);
+
+    let curve = tax::tax_rate_curve(0.0, 100000.0, 25000.0, &fy).unwrap();
+    let incomes: Vec<f64> = curve.iter().map(|(income, _)| *income).collect();
+    assert_eq!(incomes, vec![0.0, 25000.0, 50000.0, 75000.0, 100000.0]);
+    assert_eq!(curve[0].1, 0.0);
+    assert!((curve[4].1 - 22.788).abs() < 1e-9);
+    assert!(curve.windows(2).all(|pair| pair[1].1 >= pair[0].1));
+
+    assert!(tax::tax_rate_curve(0.0, 100000.0, 0.0, &fy).is_err());
+    assert!(tax::tax_rate_curve(100000.0, 0.0, 1000.0, &fy).is_err());
+    assert!(tax::tax_rate_curve(0.0, 1e9, 1.0, &fy).is_err());
+}
+
+#[test]
+fn overtime_rate_effect_direction_follows_multiplier() {
+    let mut record = salary_record(1, 40.0, date(2024, 7, 1));
+    record.pay_type = PayType::Hourly;
+    record.overtime.average_hours_per_week = 10.0;
+    record.allowances = vec![Allowance {
+        name: "Site".to_string(),
+        amount: 100.0,
+        frequency: AllowanceFrequency::Weekly,
+        taxable: true,
+        ordinary_time: None,
+    }];
+
+    record.overtime.rate_multiplier = 1.0;
+    let straight_time = calculations::calculate_overtime_rate_effect(&record);
+    assert_eq!(straight_time.direction, OvertimeEffectDirection::NetNegative);
+    assert!(straight_time.hourly_rate_with_overtime < straight_time.hourly_rate_without_overtime);
+
+    record.overtime.rate_multiplier = 2.0;
+    let double_time = calculations::calculate_overtime_rate_effect(&record);
+    assert_eq!(double_time.direction, OvertimeEffectDirection::NetPositive);
+    assert!(double_time.difference > 0.0);
+
+    record.overtime.average_hours_per_week = 0.0;
+    let no_overtime = calculations::calculate_overtime_rate_effect(&record);
+    assert_eq!(no_overtime.direction, OvertimeEffectDirection::Neutral);
+}
+
+#[test]
+fn super_contributions_prefer_payslips_over_estimate() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+    let mut current = position("Acme", date(2024, 1, 1), None);
+    current.id = Some(1);
+    let records = vec![salary_record(1, 100000.0, date(2024, 1, 1))];
+
+    let estimated = calculations::calculate_super_contributions_for_fy(&fy, &[], &[current.clone()], &records, &[]);
+    assert_eq!(estimated.method, SuperContributionMethod::Estimated);
+    assert_eq!(estimated.sg_rate, Some(11.5));
+    assert!((estimated.employer_contributions - 11500.0).abs() < 0.01);
+
+    let payslips = vec![
+        weekly_entry(Some(1), date(2024, 7, 7), 2000.0),
+        weekly_entry(Some(1), date(2024, 7, 14), 2000.0),
+        weekly_entry(Some(1), date(2023, 7, 9), 2000.0), // Previous year
+    ];
+    let actual = calculations::calculate_super_contributions_for_fy(&fy, &payslips, &[current], &records, &[]);
+    assert_eq!(actual.method, SuperContributionMethod::WeeklyEntries);
+    assert_eq!(actual.periods_recorded, 2);
+    assert!((actual.employer_contributions - 460.0).abs() < 0.01);
+}
+
+#[test]
+fn missing_weeks_follow_the_pay_cadence_within_the_position() {
+    let mut held = position("Pilbara Ports", date(2024, 7, 10), Some(date(2024, 8, 20)));
+    held.id = Some(1);
+    let mut fortnight = weekly_entry(Some(1), date(2024, 8, 11), 4000.0);
+    fortnight.period_type = PayslipFrequency::Fortnightly;
+    let entries = vec![
+        weekly_entry(Some(1), date(2024, 7, 14), 2000.0),
+        weekly_entry(None, date(2024, 7, 21), 2000.0),
+        fortnight,
+        // Another job's pay doesn't fill the gap
+        weekly_entry(Some(2), date(2024, 7, 28), 2000.0),
+    ];
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+
+    let missing = calculations::detect_missing_weeks(&held, &fy, &entries, date(2025, 1, 1));
+    assert_eq!(missing, vec![date(2024, 7, 28), date(2024, 8, 18), date(2024, 8, 25)]);
+
+    // Weeks that haven't finished yet aren't missing
+    let missing = calculations::detect_missing_weeks(&held, &fy, &entries, date(2024, 8, 12));
+    assert_eq!(missing, vec![date(2024, 7, 28)]);
+}
+
+#[test]
+fn pay_freeze_is_a_real_cut_of_the_cpi_rate() {
+    let mut held = position("Pilbara Ports", date(2021, 7, 1), None);
+    held.id = Some(1);
+    let records = vec![
+        salary_record(1, 90000.0, date(2021, 7, 1)),
+        salary_record(1, 95000.0, date(2023, 9, 1)),
+    ];
+    let positions = vec![held];
+
+    let frozen = FinancialYear::parse("FY2022-23").unwrap();
+    let cut = calculations::calculate_real_pay_cut(&frozen, &positions, &records, &[]).unwrap();
+    assert_eq!(cut.nominal_change_percent, 0.0);
+    assert_eq!(cut.real_change_percent, -6.0);
+    assert!((cut.purchasing_power_lost - 5400.0).abs() < 1e-6);
+
+    let raised = FinancialYear::parse("FY2023-24").unwrap();
+    let cut = calculations::calculate_real_pay_cut(&raised, &positions, &records, &[]).unwrap();
+    assert!(cut.real_change_percent > 1.7);
+    assert!(cut.purchasing_power_lost < 0.0);
+
+    let before_career = FinancialYear::parse("FY2021-22").unwrap();
+    assert!(calculations::calculate_real_pay_cut(&before_career, &positions, &records, &[]).is_err());
+}
+
+#[test]
+fn seven_years_at_mid_is_a_progression_stall() {
+    let at = |employer: &str, level: SeniorityLevel, start: chrono::NaiveDate, end: Option<chrono::NaiveDate>| {
+        let mut held = position(employer, start, end);
+        held.seniority_level = level;
+        held
+    };
+    let positions = vec![
+        at("Rio Tinto", SeniorityLevel::Mid, date(2017, 1, 9), None),
+        at("Pilbara Ports", SeniorityLevel::Junior, date(2012, 2, 6), Some(date(2014, 1, 31))),
+        at("BHP", SeniorityLevel::Mid, date(2014, 2, 3), Some(date(2016, 12, 23))),
+    ];
+    let profile = Some(profile(date(1990, 5, 1), true));
+
+    let velocity = calculations::calculate_promotion_velocity(&positions, &profile, date(2024, 7, 1));
+
+    assert_eq!(velocity.typical_years_per_level, 3.0);
+    assert_eq!(velocity.levels_gained, 1);
+    assert!((velocity.average_years_per_level.unwrap() - 1.99).abs() < 0.01);
+
+    // Moving employer at the same level doesn't reset the stint
+    assert_eq!(velocity.stints.len(), 2);
+    assert!(!velocity.stints[0].stalled);
+    let mid = &velocity.stints[1];
+    assert_eq!(mid.start_date, date(2014, 2, 3));
+    assert!(mid.end_date.is_none());
+    assert!(mid.stalled);
+
+    assert_eq!(velocity.insights.len(), 1);
+    assert!(matches!(velocity.insights[0].category, InsightCategory::ProgressionStall));
+}
+
+#[test]
+fn allowances_are_annualised_and_split_by_taxable_status() {
+    let mut held = position("Pilbara Ports", date(2021, 7, 1), None);
+    held.id = Some(1);
+    let allowance = |name: &str, amount: f64, frequency: AllowanceFrequency, taxable: bool| Allowance {
+        name: name.to_string(),
+        amount,
+        frequency,
+        taxable,
+        ordinary_time: None,
+    };
+    let mut earlier = salary_record(1, 90000.0, date(2021, 7, 1));
+    earlier.allowances = vec![allowance("Site", 50.0, AllowanceFrequency::Weekly, true)];
+    let mut current = salary_record(1, 95000.0, date(2023, 9, 1));
+    current.allowances = vec![
+        allowance("Site", 60.0, AllowanceFrequency::Weekly, true),
+        allowance("Tool", 100.0, AllowanceFrequency::Monthly, true),
+        allowance("Travel reimbursement", 500.0, AllowanceFrequency::Fortnightly, false),
+    ];
+    let records = vec![earlier, current];
+
+    let fy = FinancialYear::parse("FY2023-24").unwrap();
+    let summary = calculations::calculate_allowance_summary(&held, &fy, &records).unwrap();
+    assert_eq!(summary.effective_date, date(2023, 9, 1));
+    assert_eq!(summary.allowances.len(), 3);
+    assert_eq!(summary.allowances[1].annual_amount, 1200.0);
+    assert_eq!(summary.taxable_total, 60.0 * 52.0 + 1200.0);
+    assert_eq!(summary.non_taxable_total, 13000.0);
+
+    let before = FinancialYear::parse("FY2020-21").unwrap();
+    assert!(calculations::calculate_allowance_summary(&held, &before, &records).is_err());
+}
+
+#[test]
+fn sacrifice_headroom_fills_the_cap_after_employer_super() {
+    let mut held = position("Pilbara Ports", date(2023, 7, 1), None);
+    held.id = Some(1);
+    let mut record = salary_record(1, 120000.0, date(2023, 7, 1));
+    record.super_contributions.salary_sacrifice = 5000.0;
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+
+    let headroom = calculations::calculate_sacrifice_headroom(&fy, &[], &[held], &[record], &[]);
+
+    assert_eq!(headroom.concessional_cap, 30000.0);
+    assert!((headroom.employer_contributions - 13800.0).abs() < 1e-6);
+    assert_eq!(headroom.taxable_income, 115000.0);
+    assert!((headroom.headroom - 11200.0).abs() < 1e-6);
+    // 30% marginal rate against 15% contributions tax
+    assert!((headroom.estimated_tax_saving - 1680.0).abs() < 1e-6);
+}
+
+#[test]
+fn unused_concessional_cap_carries_forward_for_five_years() {
+    let years = |totals: &[(i32, f64)]| -> Vec<ConcessionalContributions> {
+        totals.iter()
+            .map(|(start_year, total)| ConcessionalContributions {
+                financial_year: FinancialYear { start_year: *start_year }.label(),
+                employer_contributions: *total,
+                employer_contributions_method: SuperContributionMethod::Estimated,
+                salary_sacrifice: 0.0,
+                additional_contributions: 0.0,
+                total: *total,
+            })
+            .collect()
+    };
+    // Nothing accrues before 2018-19; 15,000 then 10,000 unused, then a 32,500 excess in 2023-24
+    let contributions = years(&[
+        (2017, 10000.0), (2018, 10000.0), (2019, 15000.0), (2020, 25000.0),
+        (2021, 27500.0), (2022, 27500.0), (2023, 60000.0), (2024, 20000.0),
+    ]);
+
+    let statuses = calculations::calculate_super_cap_status(&contributions, Some(400000.0));
+    assert_eq!(statuses[1].headroom, 15000.0);
+    assert_eq!(statuses[2].carry_forward_available, 15000.0);
+    assert_eq!(statuses[2].headroom, 25000.0);
+    assert_eq!(statuses[6].concessional_cap, 27500.0);
+    assert_eq!(statuses[6].carry_forward_available, 25000.0);
+    assert_eq!(statuses[6].carry_forward_used, 25000.0);
+    assert_eq!(statuses[6].excess, 7500.0);
+    assert_eq!(statuses[7].carry_forward_available, 0.0);
+    assert_eq!(statuses[7].headroom, 10000.0);
+
+    let insight = calculations::super_cap_insight(&statuses).unwrap();
+    assert_eq!(insight.data_points.len(), 1);
+    assert!(insight.data_points[0].starts_with("FY2023-24"));
+
+    // No recorded balance, or one over the limit, means no carry-forward
+    for balance in [None, Some(600000.0)] {
+        let statuses = calculations::calculate_super_cap_status(&contributions, balance);
+        assert_eq!(statuses[6].carry_forward_available, 0.0);
+        assert_eq!(statuses[6].excess, 32500.0);
+    }
+
+    // Unused cap from 2018-19 has lapsed by 2024-25
+    let contributions = years(&[
+        (2018, 5000.0), (2019, 25000.0), (2020, 25000.0), (2021, 27500.0),
+        (2022, 27500.0), (2023, 27500.0), (2024, 60000.0),
+    ]);
+    let statuses = calculations::calculate_super_cap_status(&contributions, Some(100000.0));
+    assert_eq!(statuses[5].carry_forward_available, 20000.0);
+    assert_eq!(statuses[6].carry_forward_available, 0.0);
+    assert_eq!(statuses[6].excess, 30000.0);
+    assert!(calculations::super_cap_insight(&statuses[..6]).is_none());
+}
+
+#[test]
+fn bonus_sacrifice_only_counts_what_fits_under_the_cap() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+
+    let analysis = calculations::calculate_bonus_sacrifice(10000.0, 34.5, 6000.0, &fy).unwrap();
+    assert!(analysis.has_cap_room);
+    assert_eq!(analysis.amount_sacrificed, 6000.0);
+    assert!((analysis.cash_after_tax - 6550.0).abs() < 1e-6);
+    // 6,000 at 15% and 4,000 at 34.5%
+    assert!((analysis.sacrifice_after_tax - 7720.0).abs() < 1e-6);
+    assert!((analysis.net_benefit - 1170.0).abs() < 1e-6);
+
+    let no_room = calculations::calculate_bonus_sacrifice(10000.0, 34.5, 0.0, &fy).unwrap();
+    assert!(!no_room.has_cap_room);
+    assert_eq!(no_room.net_benefit, 0.0);
+
+    assert!(calculations::calculate_bonus_sacrifice(10000.0, 345.0, 6000.0, &fy).is_err());
+}
+
+#[test]
+fn super_compliance_flags_the_quarter_paid_short_of_the_contract() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+    let mut job = position("Acme", date(2020, 7, 1), None);
+    job.id = Some(1);
+    let mut record = salary_record(1, 104000.0, date(2020, 7, 1));
+    record.super_contributions.contribution_rate = 12.0;
+
+    // Q1 paid at the 11.5% guarantee rather than the contracted 12%
+    let mut entries = vec![
+        weekly_entry(Some(1), date(2024, 7, 7), 2000.0),
+        weekly_entry(Some(1), date(2024, 7, 14), 2000.0),
+    ];
+    // Q2 paid in full, with overtime that attracts no super: 4 hours at time and a half are
+    // 6 of the 44 paid hours
+    let mut on_time = weekly_entry(Some(1), date(2024, 10, 6), 2000.0);
+    on_time.super_contributed = 240.0;
+    let mut with_overtime = weekly_entry(Some(1), date(2024, 10, 13), 2000.0);
+    with_overtime.hours_overtime = 4.0;
+    with_overtime.super_contributed = 2000.0 * 38.0 / 44.0 * 0.12;
+    entries.extend([on_time, with_overtime]);
+
+    let report = calculations::calculate_super_compliance(&job, &[record], &entries, &fy).unwrap();
+    assert_eq!(report.periods_checked, 4);
+    let underpaid: Vec<u32> = report.quarters.iter().filter(|q| q.underpaid).map(|q| q.quarter).collect();
+    assert_eq!(underpaid, vec![1]);
+    assert!((report.underpayment - 20.0).abs() < 1e-9);
+    assert_eq!(report.quarters[0].end, date(2024, 9, 30));
+
+    assert!(calculations::calculate_super_compliance(&job, &[], &entries, &FinancialYear::parse("FY2023-24").unwrap()).is_err());
+}
+
+fn award_rate(id: i64, effective: chrono::NaiveDate, hourly_minimum: f64) -> AwardRate {
+    AwardRate {
+        id: Some(id),
+        award_name: "Clerks Award".to_string(),
+        classification: "Level 2".to_string(),
+        effective_date: effective,
+        hourly_minimum,
+        overtime_multiplier: 1.5,
+        casual_loading: 25.0,
+    }
+}
+
+#[test]
+fn award_compliance_estimates_back_pay_across_a_rate_rise() {
+    let mut job = position("Acme", date(2023, 7, 3), None);
+    job.id = Some(1);
+    job.employment_type = EmploymentType::Casual;
+    let mut record = salary_record(1, 30.0, date(2023, 7, 1));
+    record.pay_type = PayType::Hourly;
+    record.overtime.frequency = OvertimeFrequency::Frequent;
+    record.award_classification_id = Some(1);
+    let rates = vec![award_rate(1, date(2023, 7, 1), 25.0), award_rate(2, date(2024, 7, 1), 26.0)];
+
+    // 38 hours at $30 against 31.25 with casual loading: 47.50 short
+    let before_rise = weekly_entry(Some(1), date(2024, 6, 30), 1140.0);
+    // After the rise, plus 2 hours overtime at time and a half: 41 paid hours at 32.50
+    // against 1,140 + 90, so 102.50 short
+    let mut after_rise = weekly_entry(Some(1), date(2024, 7, 7), 1230.0);
+    after_rise.hours_overtime = 2.0;
+
+    let report = calculations::calculate_award_compliance(&job, &[record], &rates, &[before_rise, after_rise]).unwrap();
+    assert_eq!(report.records_checked, 1);
+    let finding = &report.findings[0];
+    assert!(finding.base_rate_below_minimum);
+    assert!(!finding.overtime_below_minimum);
+    assert_eq!(finding.minimum_hourly_rate, 31.25);
+    assert_eq!(finding.periods_underpaid, 2);
+    assert!((report.estimated_back_pay - 150.0).abs() < 1e-9);
+}
+
+#[test]
+fn resignation_timing_waits_for_a_yearly_bonus_inside_the_window() {
+    let mut job = position("Acme", date(2022, 7, 1), None);
+    job.id = Some(1);
+    let bonus = |awarded, amount| Bonus { name: "Annual".to_string(), amount, date_awarded: awarded, taxable: true };
+    let mut first = salary_record(1, 100000.0, date(2022, 7, 1));
+    first.bonuses = vec![bonus(date(2022, 9, 15), 5000.0), bonus(date(2023, 9, 15), 6000.0)];
+    // A raise copied the bonus history onto the next record
+    let mut raise = first.clone();
+    raise.effective_date = date(2023, 10, 1);
+    let records = vec![first, raise];
+
+    let advice = calculations::calculate_resignation_timing(&job, &records, date(2024, 7, 15), None).unwrap();
+    assert_eq!(advice.bonuses_on_record, 2);
+    assert_eq!(advice.typical_interval_months, Some(12));
+    assert_eq!(advice.predicted_bonus_date, Some(date(2024, 9, 15)));
+    assert_eq!(advice.days_until_bonus, Some(62));
+    assert_eq!(advice.bonus_at_risk, 6000.0);
+    assert!(advice.wait_for_bonus);
+
+    let narrow = calculations::calculate_resignation_timing(&job, &records, date(2024, 7, 15), Some(30)).unwrap();
+    assert!(!narrow.wait_for_bonus);
+}
+
+#[test]
+fn concessional_contributions_prefer_reported_super_over_recorded_sacrifice() {
+    let mut held = position("Pilbara Ports", date(2023, 7, 1), None);
+    held.id = Some(1);
+    let mut record = salary_record(1, 120000.0, date(2023, 7, 1));
+    record.super_contributions.salary_sacrifice = 5000.0;
+    let mut statement = yearly_entry("FY2023-24", 115000.0);
+    statement.reportable_super = 6000.0;
+
+    let by_fy = calculations::concessional_contributions_by_fy(
+        &[held], &[record], &[], &[statement], &[], &FinancialYear::parse("FY2024-25").unwrap(),
+    );
+
+    assert_eq!(by_fy.len(), 2);
+    assert_eq!(by_fy[0].financial_year, "FY2023-24");
+    assert_eq!(by_fy[0].salary_sacrifice, 6000.0);
+    assert!((by_fy[0].total - 19200.0).abs() < 1e-6);
+    assert_eq!(by_fy[1].salary_sacrifice, 5000.0);
+    assert!((by_fy[1].total - 18800.0).abs() < 1e-6);
+}
+
+#[test]
+fn current_position_prefers_the_latest_open_ended_start() {
+    let with = |employer: &str, id: i64, start: chrono::NaiveDate, end: Option<chrono::NaiveDate>, employment_type: EmploymentType| {
+        let mut held = position(employer, start, end);
+        held.id = Some(id);
+        held.employment_type = employment_type;
+        held
+    };
+    let positions = vec![
+        // Started last but has ended
+        with("Ended", 1, date(2024, 1, 8), Some(date(2024, 3, 1)), EmploymentType::Permanent),
+        with("Main job", 2, date(2022, 7, 4), None, EmploymentType::Permanent),
+        with("Side job", 3, date(2023, 2, 1), None, EmploymentType::Casual),
+        with("Old job", 4, date(2019, 1, 7), None, EmploymentType::Permanent),
+    ];
+
+    let current: Vec<&str> = calculations::current_positions(&positions, date(2024, 6, 1)).iter().map(|p| p.employer_name.as_str()).collect();
+    assert_eq!(current, vec!["Side job", "Main job", "Old job"]);
+    assert_eq!(calculations::current_position(&positions, date(2024, 6, 1)).unwrap().employer_name, "Side job");
+
+    // Same start date: permanent first, then the most recently saved
+    let tied = vec![
+        with("Casual", 5, date(2023, 2, 1), None, EmploymentType::Casual),
+        with("Permanent A", 6, date(2023, 2, 1), None, EmploymentType::Permanent),
+        with("Permanent B", 7, date(2023, 2, 1), None, EmploymentType::Permanent),
+    ];
+    let current: Vec<&str> = calculations::current_positions(&tied, date(2024, 6, 1)).iter().map(|p| p.employer_name.as_str()).collect();
+    assert_eq!(current, vec!["Permanent B", "Permanent A", "Casual"]);
+
+    // Nothing open-ended: the most recently started
+    let ended = vec![positions[0].clone(), with("Earlier", 8, date(2020, 1, 6), Some(date(2023, 12, 22)), EmploymentType::Permanent)];
+    assert_eq!(calculations::current_position(&ended, date(2024, 6, 1)).unwrap().employer_name, "Ended");
+
+    let warnings = careerflow_core::validation::open_ended_position_warnings(&positions, date(2024, 6, 1));
+    let flagged: Vec<Option<i64>> = warnings.iter().map(|w| w.id).collect();
+    assert_eq!(flagged, vec![Some(2), Some(4)]);
+    assert!(warnings.iter().all(|w| w.severity == IssueSeverity::Warning));
+}
+
+#[test]
+fn a_future_position_is_upcoming_and_left_out_of_the_figures() {
+    let today = date(2025, 6, 2);
+    let mut current = position("Current Co", date(2021, 3, 1), None);
+    current.id = Some(1);
+    let mut future = position("Next Co", today + chrono::Duration::days(30), None);
+    future.id = Some(2);
+    let positions = vec![current.clone(), future.clone()];
+
+    assert_eq!(current.status_on(today), PositionStatus::Current);
+    assert_eq!(future.status_on(today), PositionStatus::Future);
+    assert_eq!(future.status_on(future.start_date), PositionStatus::Current);
+    assert_eq!(position("Old Co", date(2019, 1, 7), Some(date(2020, 12, 18))).status_on(today), PositionStatus::Past);
+
+    let open: Vec<Option<i64>> = calculations::current_positions(&positions, today).iter().map(|p| p.id).collect();
+    assert_eq!(open, vec![Some(1)]);
+    assert_eq!(calculations::current_position(&[future.clone()], today), None);
+
+    let records = vec![salary_record(1, 100000.0, date(2021, 3, 1)), salary_record(2, 150000.0, future.start_date)];
+    let analysis = calculations::calculate_earnings_analysis(&positions, &records, &None, &[], today);
+    assert_eq!(analysis.current_positions.len(), 1);
+    assert_eq!(analysis.current_positions[0].id, Some(1));
+    assert_eq!(analysis.upcoming_positions.len(), 1);
+    assert_eq!(analysis.upcoming_positions[0].position.id, Some(2));
+    assert_eq!(analysis.upcoming_positions[0].starts_in_days, 30);
+    assert_eq!(
+        analysis.current_total_compensation,
+        calculations::calculate_earnings_analysis(&[current.clone()], &records[..1], &None, &[], today).current_total_compensation
+    );
+
+    let loyalty = calculations::calculate_loyalty_tax(&positions, today);
+    assert_eq!(loyalty, calculations::calculate_loyalty_tax(&[current.clone()], today));
+
+    let resume = calculations::generate_resume_export(&positions, &None, today);
+    let timeline: Vec<&str> = resume.career_timeline.iter().map(|p| p.employer.as_str()).collect();
+    assert_eq!(timeline, vec!["Current Co"]);
+    assert_eq!(resume.upcoming_positions.len(), 1);
+    assert_eq!(resume.upcoming_positions[0].employer, "Next Co");
+    assert!(resume.upcoming_positions[0].duration.starts_with("Starting "));
+
+    // Two open-ended permanent roles is fine when one hasn't started, until it has
+    assert!(careerflow_core::validation::open_ended_position_warnings(&positions, today).is_empty());
+    assert_eq!(careerflow_core::validation::open_ended_position_warnings(&positions, future.start_date).len(), 2);
+
+    // A position starting on the day is current, whatever the time
+    let analysis_on_the_day = calculations::calculate_earnings_analysis(&positions, &records, &None, &[], future.start_date);
+    assert!(analysis_on_the_day.upcoming_positions.is_empty());
+    assert_eq!(analysis_on_the_day.current_positions[0].id, Some(2));
+
+    // A record dated before its position starts is flagged but still allowed
+    let early = salary_record(2, 150000.0, today);
+    assert!(careerflow_core::validation::validate_compensation_record(&early).is_ok());
+    let warnings = careerflow_core::validation::record_before_start_warnings(&positions, &[early, records[1].clone()]);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, IssueSeverity::Warning);
+}
+
+#[test]
+fn a_date_of_birth_must_give_a_working_age_on_the_day() {
+    let today = date(2025, 6, 2);
+    let check = |dob| careerflow_core::validation::validate_user_profile(&profile(dob, true), today);
+
+    assert!(check(date(1990, 5, 20)).is_ok());
+    assert!(check(today).unwrap_err().contains("in the past"));
+    assert!(check(date(2026, 1, 1)).unwrap_err().contains("in the past"));
+    // Fourteen on the day itself, but not the day before
+    assert!(check(date(2011, 6, 2)).is_ok());
+    assert!(check(date(2011, 6, 3)).unwrap_err().contains("age of 13"));
+    // A hundred until the day before turning 101
+    assert!(check(date(1924, 6, 3)).is_ok());
+    assert!(check(date(1924, 6, 2)).unwrap_err().contains("age of 101"));
+}
+
+#[test]
+fn cost_of_living_evens_out_pay_across_locations() {
+    use careerflow_core::cost_of_living;
+
+    assert_eq!(cost_of_living::index_for_location("Sydney NSW"), Some(116.0));
+    assert_eq!(cost_of_living::index_for_location("Dubbo, New South Wales"), Some(97.0));
+    assert_eq!(cost_of_living::index_for_location("Karratha WA"), Some(104.0));
+    assert_eq!(cost_of_living::index_for_location("Auckland"), None);
+
+    let mut sydney = position("City Co", date(2022, 1, 10), None);
+    sydney.id = Some(1);
+    sydney.location = "Sydney NSW".to_string();
+    let mut dubbo = position("Country Co", date(2019, 2, 4), Some(date(2021, 12, 24)));
+    dubbo.id = Some(2);
+    dubbo.location = "Dubbo NSW".to_string();
+
+    // The $120k Sydney role is barely ahead of the $100k regional one once prices are allowed for
+    let city = calculations::col_adjusted_earnings(&sydney, &salary_record(1, 120000.0, date(2022, 1, 10)), &[]).unwrap();
+    let country = calculations::col_adjusted_earnings(&dubbo, &salary_record(2, 100000.0, date(2019, 2, 4)), &[]).unwrap();
+    assert!((city - 120000.0 * 100.0 / 116.0).abs() < 0.01);
+    assert!((country - 100000.0 * 100.0 / 97.0).abs() < 0.01);
+    assert!(city - country < 1000.0);
+
+    let mut overseas = sydney.clone();
+    overseas.location = "Auckland".to_string();
+    assert!(calculations::col_adjusted_earnings(&overseas, &salary_record(1, 120000.0, date(2022, 1, 10)), &[]).is_err());
+
+    let analysis = calculations::calculate_earnings_analysis(&[sydney, overseas], &[], &None, &[], date(2024, 6, 1));
+    let adjusted: Vec<bool> = analysis.earnings_over_time.iter().map(|s| s.col_adjusted_annual.is_some()).collect();
+    assert_eq!(adjusted, vec![true, false]);
+    let snapshot = &analysis.earnings_over_time[0];
+    assert!((snapshot.col_adjusted_annual.unwrap() - snapshot.actual_annual * 100.0 / 116.0).abs() < 0.01);
+}
+
+#[test]
+fn net_hourly_rate_takes_out_tax_medicare_and_help() {
+    let record = salary_record(1, 95000.0, date(2024, 7, 1));
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+    let hours = 38.0 * 52.0;
+
+    // 19,288 tax and 1,900 Medicare
+    let without_help = calculations::calculate_net_hourly_rate(&record, &fy, false);
+    assert!((without_help - 73812.0 / hours).abs() < 1e-9);
+
+    // 5.5% of repayment income at 95,000
+    let with_help = calculations::calculate_net_hourly_rate(&record, &fy, true);
+    assert!((with_help - 68587.0 / hours).abs() < 1e-9);
+
+    // The levy phases in just above the low-income threshold
+    assert!((tax::medicare_levy(30000.0, &fy) - 277.8).abs() < 1e-9);
+    assert_eq!(tax::help_repayment(54000.0, &fy), 0.0);
+}
+
+#[test]
+fn commute_counts_against_the_hourly_rate_on_working_days() {
+    let mut far = position("Mine Co", date(2024, 1, 8), None);
+    far.commute_minutes_per_day = Some(120);
+    let mut local = position("Town Co", date(2024, 1, 8), None);
+    local.commute_minutes_per_day = Some(10);
+    let far_pay = salary_record(1, 110000.0, date(2024, 1, 8));
+    let local_pay = salary_record(2, 95000.0, date(2024, 1, 8));
+
+    // 38 hours is five days a week, 48 weeks of the year outside annual leave
+    let far_rate = calculations::commute_adjusted_hourly_rate(&far, &far_pay);
+    let local_rate = calculations::commute_adjusted_hourly_rate(&local, &local_pay);
+    assert!((far_rate - 110000.0 / (1976.0 + 2.0 * 5.0 * 48.0)).abs() < 1e-9);
+    assert!((local_rate - 95000.0 / (1976.0 + 40.0)).abs() < 1e-9);
+    assert!(far_rate < local_rate);
+
+    local.commute_minutes_per_day = None;
+    assert!((calculations::commute_adjusted_hourly_rate(&local, &local_pay) - 95000.0 / 1976.0).abs() < 1e-9);
+}
+
+fn novated_lease() -> PackagingItem {
+    PackagingItem {
+        name: "Novated lease".to_string(),
+        pre_tax_amount_per_period: 600.0,
+        post_tax_amount_per_period: 100.0,
+        attracts_fbt: true,
+    }
+}
+
+#[test]
+fn novated_lease_cuts_tax_but_comes_out_of_net_pay() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+    let hours = 38.0 * 52.0;
+    let mut record = salary_record(1, 95000.0, date(2024, 7, 1));
+    let before = calculations::calculate_net_hourly_rate(&record, &fy, false);
+
+    // 15,600 pre-tax and 2,600 post-tax a year on fortnightly pay, saving 4,680 tax and
+    // 312 Medicare on the pre-tax part
+    record.packaging = vec![novated_lease()];
+    let after = calculations::calculate_net_hourly_rate(&record, &fy, false);
+    assert!(((before - after) * hours - 13208.0).abs() < 1e-6);
+}
+
+#[test]
+fn reported_fringe_benefits_are_checked_against_packaging() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+    let mut job = position("Acme", date(2020, 7, 1), None);
+    job.id = Some(1);
+    let mut record = salary_record(1, 95000.0, date(2024, 7, 1));
+    record.packaging = vec![novated_lease()];
+    let records = vec![record];
+
+    // 15,600 taxable value grossed up at the type 2 rate
+    let expected = calculations::expected_reportable_fringe_benefits(&job, &records, &[], &fy).unwrap();
+    assert!((expected - 29434.08).abs() < 1e-6);
+
+    let with_rfba = |id: i64, amount: f64| {
+        let mut entry = yearly_entry("FY2024-25", 95000.0);
+        entry.id = Some(id);
+        entry.position_id = Some(1);
+        entry.reportable_fringe_benefits = Some(amount);
+        entry
+    };
+    let entries = vec![with_rfba(1, 29434.0), with_rfba(2, 12000.0)];
+    let warnings = careerflow_core::validation::fringe_benefit_warnings(&[job], &records, &entries, &[]);
+    let flagged: Vec<Option<i64>> = warnings.iter().map(|w| w.id).collect();
+    assert_eq!(flagged, vec![Some(2)]);
+}
+
+#[test]
+fn back_pay_recomputes_each_week_and_estimates_the_missing_one() {
+    let hourly = |rate, effective| {
+        let mut record = salary_record(1, rate, effective);
+        record.pay_type = PayType::Hourly;
+        record
+    };
+    let old = hourly(40.0, date(2023, 7, 1));
+    let new = hourly(42.0, date(2024, 7, 1));
+
+    let mut with_overtime = weekly_entry(Some(1), date(2024, 7, 14), 1760.0);
+    with_overtime.hours_overtime = 4.0;
+    let entries = vec![
+        weekly_entry(Some(1), date(2024, 7, 7), 1520.0),
+        with_overtime,
+        weekly_entry(Some(1), date(2024, 7, 28), 1520.0),
+        weekly_entry(Some(2), date(2024, 7, 21), 900.0),
+    ];
+
+    let result = calculations::back_pay(1, &old, &new, date(2024, 7, 1), date(2024, 7, 28), &entries).unwrap();
+    let differences: Vec<f64> = result.periods.iter().map(|p| p.difference).collect();
+    // Overtime is 4 hours at time and a half; the week ending 21 July has no payslip
+    assert_eq!(differences, vec![76.0, 88.0, 76.0, 76.0]);
+    assert_eq!(result.estimated_periods, 1);
+    assert!(result.periods[2].estimated);
+    assert_eq!(result.total_back_pay, 316.0);
+    // 30% plus Medicare at a year's pay of 82,992
+    assert!((result.estimated_withholding - 316.0 * 0.32).abs() < 1e-6);
+
+    assert!(calculations::back_pay(2, &old, &new, date(2024, 7, 1), date(2024, 7, 28), &entries).is_err());
+}
+
+#[test]
+fn a_second_job_leaves_a_bill_when_taxed_together() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+    let with_id = |employer, id| {
+        let mut p = position(employer, date(2022, 7, 1), None);
+        p.id = Some(id);
+        p
+    };
+    let positions = vec![with_id("Main", 1), with_id("Side", 2)];
+    let mut side = salary_record(2, 20000.0, date(2022, 7, 1));
+    side.claims_tax_free_threshold = false;
+    let records = vec![salary_record(1, 95000.0, date(2022, 7, 1)), side];
+
+    let combined = calculations::calculate_combined_tax_position(&fy, &positions, &records, &[], &[], &[], false);
+    assert!(combined.sources.iter().all(|s| s.method == TaxedIncomeMethod::Estimated));
+    assert_eq!(combined.total_taxable_income, 115000.0);
+    // 25,288 tax and 2,300 Medicare together; 21,188 and 3,600 withheld separately
+    assert!((combined.tax_payable - 27588.0).abs() < 1e-6);
+    assert!((combined.total_withheld - 24788.0).abs() < 1e-6);
+    assert!((combined.net_position + 2800.0).abs() < 1e-6);
+    assert!(combined.tax_payable > combined.tax_payable_if_separate);
+}
+
+#[test]
+fn tax_time_summary_prefers_income_statements_over_payslips() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+    let with_id = |employer, id| {
+        let mut p = position(employer, date(2022, 7, 1), None);
+        p.id = Some(id);
+        p
+    };
+    let positions = vec![with_id("Main", 1), with_id("Side", 2), with_id("Nothing Entered", 3)];
+    let records: Vec<CompensationRecord> = (1..=3).map(|id| salary_record(id, 95000.0, date(2022, 7, 1))).collect();
+    let taxable_allowance = |amount| Allowance {
+        name: "Site".to_string(),
+        amount,
+        frequency: AllowanceFrequency::Weekly,
+        taxable: true,
+        ordinary_time: None,
+    };
+
+    let mut statement = yearly_entry("FY2024-25", 95000.0);
+    statement.position_id = Some(1);
+    statement.reportable_super = 5000.0;
+    statement.allowances = vec![taxable_allowance(1200.0)];
+    // Payslips for a job with an income statement aren't counted twice
+    let mut payslips = vec![weekly_entry(Some(1), date(2024, 7, 7), 1800.0)];
+    for week_ending in [date(2024, 7, 7), date(2024, 7, 14)] {
+        let mut entry = weekly_entry(Some(2), week_ending, 1000.0);
+        entry.allowances = vec![taxable_allowance(50.0)];
+        payslips.push(entry);
+    }
+
+    let summary = calculations::tax_time_summary(&fy, &positions, &records, &payslips, &[statement], &[]);
+    assert_eq!(summary.sources.len(), 2);
+    let main = &summary.sources[0];
+    assert_eq!(main.method, TaxedIncomeMethod::YearlyEntry);
+    assert_eq!(main.salary_sacrifice, 5000.0);
+    // Income statements don't show employer super, so it comes from the payslip
+    assert!((main.employer_super - 1800.0 * 0.115).abs() < 1e-6);
+    assert_eq!(summary.sources[1].method, TaxedIncomeMethod::Payslips);
+    assert_eq!(summary.total_gross_income, 97000.0);
+    assert_eq!(summary.total_tax_withheld, 95000.0 * 0.25 + 500.0);
+    assert_eq!(summary.taxable_allowances, 1300.0);
+    assert!((summary.reportable_super - (5000.0 + 1800.0 * 0.115 + 230.0)).abs() < 1e-6);
+}
+
+#[test]
+fn tax_figures_name_the_income_they_could_not_convert() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+    let mut job = position("Auckland Port", date(2024, 7, 1), None);
+    job.id = Some(1);
+    let mut payslip = weekly_entry(Some(1), date(2024, 7, 7), 2000.0);
+    payslip.currency = "NZD".to_string();
+    let payslips = vec![payslip, weekly_entry(None, date(2024, 7, 7), 500.0)];
+
+    let summary = calculations::tax_time_summary(&fy, &[job.clone()], &[], &payslips, &[], &[]);
+    assert_eq!(summary.total_gross_income, 500.0);
+    assert_eq!(summary.warnings.len(), 1);
+    assert_eq!(summary.warnings[0].code, AnalysisWarningCode::MissingExchangeRate);
+    assert!(summary.warnings[0].message.contains("NZD"));
+
+    let combined = calculations::calculate_combined_tax_position(&fy, &[job.clone()], &[], &payslips, &[], &[], false);
+    assert_eq!(combined.total_gross_income, 500.0);
+    assert_eq!(combined.warnings, summary.warnings);
+
+    let rates = vec![ExchangeRate {
+        id: None,
+        currency: "NZD".to_string(),
+        financial_year: "FY2024-25".to_string(),
+        rate_to_aud: 0.9,
+    }];
+    let converted = calculations::tax_time_summary(&fy, &[job], &[], &payslips, &[], &rates);
+    assert_eq!(converted.total_gross_income, 2300.0);
+    assert!(converted.warnings.is_empty());
+}
+
+#[test]
+fn reverse_engineered_base_inverts_the_withholding() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+    // $90,000 pays 17,788 tax and 1,800 Medicare, leaving 70,412 a year
+    let estimate = calculations::reverse_engineer_base(70412.0 / 52.0, &fy, true).unwrap();
+    assert!((estimate.annual_base - 90000.0).abs() < 0.01);
+    assert!((estimate.tax_withheld_weekly - 19588.0 / 52.0).abs() < 0.01);
+    assert!((estimate.total_package - 90000.0 * 1.115).abs() < 0.01);
+    assert!(estimate.annual_base_low < estimate.annual_base && estimate.annual_base < estimate.annual_base_high);
+
+    // A second job withholds more, so the same take-home means a higher base
+    let second_job = calculations::reverse_engineer_base(70412.0 / 52.0, &fy, false).unwrap();
+    assert!(second_job.annual_base > estimate.annual_base);
+    assert!(calculations::reverse_engineer_base(0.0, &fy, true).is_err());
+}
+
+#[test]
+fn gross_for_target_net_inverts_the_assessed_tax() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+    let estimate = calculations::gross_for_target_net(70412.0, &fy, &None).unwrap();
+    assert!((estimate.gross_salary - 90000.0).abs() < 0.01);
+    assert!((estimate.tax_payable - 19588.0).abs() < 0.01);
+    assert!((estimate.average_tax_rate - 19588.0 / 900.0).abs() < 0.01);
+    assert!((estimate.marginal_tax_rate - 32.0).abs() < 0.01);
+    assert!((estimate.total_package - 90000.0 * 1.115).abs() < 0.01);
+
+    // HELP repayments come out of the same take-home, so the salary has to be higher
+    let with_help = UserProfile { has_help_debt: true, ..profile(date(1990, 5, 20), true) };
+    let estimate = calculations::gross_for_target_net(70412.0, &fy, &Some(with_help)).unwrap();
+    assert!(estimate.help_repayment > 0.0);
+    assert!((estimate.gross_salary - estimate.tax_payable - estimate.help_repayment - 70412.0).abs() < 0.01);
+    assert!(calculations::gross_for_target_net(-1.0, &fy, &None).is_err());
+}
+
+#[test]
+fn projection_low_series_never_exceeds_high() {
+    let mut held = position("BHP", date(2020, 7, 1), None);
+    held.id = Some(1);
+    let records = vec![salary_record(1, 95000.0, date(2020, 7, 1))];
+    let mut member = profile(date(1990, 1, 1), true);
+    member.total_super_balance = Some(100000.0);
+    let today = date(2025, 1, 1);
+
+    let assumptions = ProjectionAssumptions::default();
+    let super_projection = calculations::project_super(std::slice::from_ref(&held), &records, &member, &assumptions, &[], today);
+    assert_eq!(super_projection.starting_age, 35);
+    assert_eq!(super_projection.points.len(), 33);
+    // A year at 7% on the balance, then 10,925 contributed less contributions tax
+    assert!((super_projection.points[1].expected - (107000.0 + 10925.0 * 0.85)).abs() < 0.01);
+
+    let salary_projection = calculations::project_salary(&held, &records, &assumptions, None).unwrap();
+    assert!((salary_projection.points[10].expected - 95000.0 * 1.06f64.powi(10)).abs() < 0.01);
+
+    // Even with a negative return and wide spreads
+    let pessimistic = ProjectionAssumptions { investment_return: -10.0, return_spread: 20.0, salary_growth_spread: 8.0 };
+    for assumptions in [assumptions, pessimistic] {
+        let super_points = calculations::project_super(std::slice::from_ref(&held), &records, &member, &assumptions, &[], today).points;
+        let salary_points = calculations::project_salary(&held, &records, &assumptions, Some(20)).unwrap().points;
+        for point in super_points.iter().chain(&salary_points) {
+            assert!(point.low <= point.expected && point.expected <= point.high, "{:?}", point);
+        }
+    }
+}
+
+#[test]
+fn casual_overtime_heavy_income_scores_as_high_risk() {
+    let mut casual = position("Pilbara Ports", date(2023, 7, 1), None);
+    casual.id = Some(1);
+    casual.employment_type = EmploymentType::Casual;
+    // $26.32 an hour, and 19 hours a week at time and a half is another $39,000
+    let mut record = salary_record(1, 52000.0, date(2023, 7, 1));
+    record.overtime.average_hours_per_week = 19.0;
+    record.overtime.rate_multiplier = 1.5;
+
+    let today = date(2024, 6, 1);
+    let risky = calculations::income_risk_score(std::slice::from_ref(&casual), &[record], today);
+    assert_eq!(risky.level, IncomeRiskLevel::High);
+    assert!((risky.annual_income - 91000.0).abs() < 0.01);
+    assert!((risky.score - (35.0 + 25.0 * (39000.0 / 91000.0) / 0.5 + 20.0)).abs() < 0.01);
+    let insight = calculations::income_risk_insight(&risky, std::slice::from_ref(&casual), today).unwrap();
+    assert!(insight.data_points[0].starts_with("Employer concentration"));
+    assert_eq!(insight.position_ids, vec![1]);
+
+    // Two permanent salaries split the concentration and nothing else adds up
+    let diversified: Vec<Position> = [("Main", 2), ("Side", 3)].into_iter()
+        .map(|(employer, id)| {
+            let mut p = position(employer, date(2023, 7, 1), None);
+            p.id = Some(id);
+            p
+        })
+        .collect();
+    let records = vec![salary_record(2, 60000.0, date(2023, 7, 1)), salary_record(3, 60000.0, date(2023, 7, 1))];
+    let safe = calculations::income_risk_score(&diversified, &records, today);
+    assert_eq!(safe.level, IncomeRiskLevel::Low);
+    assert!((safe.score - 17.5).abs() < 0.01);
+    assert!(calculations::income_risk_insight(&safe, &diversified, today).is_none());
+}
+
+#[test]
+fn industry_exposure_splits_a_move_from_mining_to_tech() {
+    let mut mining = position("BHP", date(2014, 7, 1), Some(date(2020, 6, 30)));
+    mining.id = Some(1);
+    let mut tech = position("Atlassian", date(2020, 7, 1), None);
+    tech.id = Some(2);
+    tech.industry = Some("Technology".to_string());
+    let records = vec![
+        salary_record(1, 100000.0, date(2014, 7, 1)),
+        salary_record(2, 150000.0, date(2020, 7, 1)),
+        salary_record(2, 160000.0, date(2022, 7, 1)),
+    ];
+    let profile = Some(profile(date(1990, 5, 1), true));
+
+    let exposure = calculations::calculate_industry_exposure(&[tech, mining], &records, &profile, &[], date(2024, 7, 1));
+
+    // The profile's industry covers the position without its own
+    assert_eq!(exposure.len(), 2);
+    assert_eq!(exposure[0].industry, "Mining");
+    assert!((exposure[0].years - 2191.0 / 365.25).abs() < 1e-9);
+    assert!((exposure[0].total_earnings - 100000.0 * 2191.0 / 365.25).abs() < 0.01);
+    assert_eq!(exposure[1].industry, "Technology");
+    assert!((exposure[1].years - 1461.0 / 365.25).abs() < 1e-9);
+    assert!((exposure[1].total_earnings - (150000.0 * 730.0 + 160000.0 * 731.0) / 365.25).abs() < 0.01);
+
+    let share: f64 = exposure.iter().map(|e| e.share_of_years).sum();
+    assert!((share - 100.0).abs() < 1e-9);
+    assert!(exposure[1].share_of_earnings > exposure[1].share_of_years);
+}
+
+#[test]
+fn hours_vs_earnings_buckets_a_july_week_into_the_new_financial_year() {
+    let mut overtime = weekly_entry(None, date(2024, 7, 1), 2000.0);
+    overtime.hours_overtime = 12.0;
+    let entries = vec![
+        weekly_entry(None, date(2024, 6, 30), 1500.0),
+        overtime,
+        weekly_entry(None, date(2024, 12, 29), 1500.0),
+    ];
+
+    let financial = calculations::calculate_hours_vs_earnings(&entries, &[], YearBasis::Financial);
+    let labels: Vec<&str> = financial.iter().map(|p| p.label.as_str()).collect();
+    assert_eq!(labels, vec!["2023-24", "2024-25"]);
+    assert_eq!(financial[1].total_earnings, 3500.0);
+    assert_eq!(financial[1].total_hours_worked, 88.0);
+    assert!((financial[1].overtime_percentage - 12.0 / 88.0 * 100.0).abs() < 1e-9);
+
+    let calendar = calculations::calculate_hours_vs_earnings(&entries, &[], YearBasis::Calendar);
+    assert_eq!(calendar.len(), 1);
+    assert_eq!(calendar[0].label, "2024");
+    assert_eq!(calendar[0].total_earnings, 5000.0);
+
+    // Matches the financial year summaries
+    let summaries = calculations::calculate_financial_year_summaries(&entries, &[], &[]);
+    assert_eq!(summaries[1].financial_year, "FY2024-25");
+    assert_eq!(summaries[1].gross_income, financial[1].total_earnings);
+}
+
+#[test]
+fn a_second_job_without_the_threshold_isnt_over_withholding() {
+    let mut casual = salary_record(2, 30000.0, date(2024, 7, 1));
+    casual.tax_withheld = Some(5400.0);
+
+    let check = calculations::calculate_withholding_check(&casual).unwrap();
+    assert_eq!(check.financial_year, "FY2024-25");
+    assert_eq!(check.status, WithholdingStatus::OverWithheld);
+
+    casual.claims_tax_free_threshold = false;
+    let check = calculations::calculate_withholding_check(&casual).unwrap();
+    // 16% from the first dollar plus the 2% Medicare levy
+    assert!((check.expected_withholding - 5400.0).abs() < 1e-6);
+    assert_eq!(check.status, WithholdingStatus::AsExpected);
+
+    casual.tax_withheld = None;
+    assert!(calculations::calculate_withholding_check(&casual).is_err());
+}
+
+#[test]
+fn data_quality_itemises_what_it_takes_off() {
+    let today = date(2024, 7, 1);
+    let mut thin = position("Pilbara Ports", date(2023, 7, 1), None);
+    thin.id = Some(1);
+    let mut solid = position("Rio Tinto", date(2023, 7, 1), Some(date(2023, 7, 14)));
+    solid.id = Some(2);
+
+    let mut guessed = salary_record(1, 90000.0, date(2023, 7, 1));
+    guessed.confidence_score = 20.0;
+    let records = vec![guessed, salary_record(2, 95000.0, date(2023, 7, 1))];
+    // Half a year of weekly payslips for the first, both weeks for the second
+    let mut entries: Vec<WeeklyCompensationEntry> = (0..26)
+        .map(|week| weekly_entry(Some(1), date(2023, 7, 7) + chrono::Duration::weeks(week), 1800.0))
+        .collect();
+    entries.push(weekly_entry(Some(2), date(2023, 7, 7), 1800.0));
+    entries.push(weekly_entry(Some(2), date(2023, 7, 14), 1800.0));
+
+    let quality = calculations::data_quality(&thin, &records, &entries, today);
+    let points: Vec<f64> = quality.deductions.iter().map(|d| d.points).collect();
+    // One low-confidence record, and payslips for 182 of 366 days
+    assert_eq!(points, vec![10.0, 15.0]);
+    assert_eq!(quality.score, 75.0);
+
+    thin.core_responsibilities.clear();
+    let quality = calculations::data_quality(&thin, &[], &[], today);
+    assert_eq!(quality.score, 55.0);
+
+    let report = calculations::calculate_data_quality_report(&[thin.clone(), solid], &records, &entries, today);
+    let ids: Vec<i64> = report.positions.iter().map(|p| p.position_id).collect();
+    assert_eq!(ids, vec![1, 2]);
+    assert_eq!(report.overall_score, 85.0);
+    assert!(calculations::data_quality_insight(&report).is_none());
+
+    let poor = calculations::calculate_data_quality_report(&[thin], &[], &[], today);
+    let insight = calculations::data_quality_insight(&poor).unwrap();
+    assert_eq!(insight.position_ids, vec![1]);
+}
+
+#[test]
+fn employer_super_ignores_overtime() {
+    let mut record = salary_record(1, 40.0, date(2024, 7, 1));
+    record.pay_type = PayType::Hourly;
+    record.super_contributions.contribution_rate = 11.5;
+    record.allowances = vec![
+        Allowance {
+            name: "Shift".to_string(),
+            amount: 100.0,
+            frequency: AllowanceFrequency::Weekly,
+            taxable: true,
+            ordinary_time: None,
+        },
+        Allowance {
+            name: "Meal".to_string(),
+            amount: 20.0,
+            frequency: AllowanceFrequency::Weekly,
+            taxable: false,
+            ordinary_time: None,
+        },
+    ];
+    let without_overtime = calculations::calculate_employer_super(&record);
+
+    record.overtime.average_hours_per_week = 20.0;
+    record.overtime.rate_multiplier = 2.0;
+    let heavy_overtime = calculations::calculate_employer_super(&record);
+
+    // 38 hours at $40 plus the shift allowance, every week
+    let ordinary = (40.0 * 38.0 + 100.0) * 52.0;
+    assert_eq!(heavy_overtime.ordinary_time_earnings, ordinary);
+    assert!((heavy_overtime.employer_super - ordinary * 0.115).abs() < 1e-6);
+    assert_eq!(heavy_overtime.employer_super, without_overtime.employer_super);
+    assert_eq!(heavy_overtime.excluded_earnings, 40.0 * 2.0 * 20.0 * 52.0 + 20.0 * 52.0);
+}
+
+#[test]
+fn missing_weeks_follow_the_positions_week_ending_day() {
+    let mut held = position("Pilbara Ports", date(2024, 7, 1), None);
+    held.id = Some(1);
+    held.week_ending_day = Some(chrono::Weekday::Tue);
+    // A stray Sunday entry doesn't move the expected cadence
+    let entries = vec![weekly_entry(Some(1), date(2024, 7, 14), 2000.0)];
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+
+    let missing = calculations::detect_missing_weeks(&held, &fy, &entries, date(2024, 7, 31));
+    assert_eq!(missing, vec![date(2024, 7, 2), date(2024, 7, 16), date(2024, 7, 23), date(2024, 7, 30)]);
+}
+
+#[test]
+fn long_service_leave_thresholds_differ_by_state() {
+    let today = date(2025, 1, 1);
+    let mut positions: Vec<Position> = [("Ausgrid", "Newcastle NSW"), ("AusNet", "Geelong VIC"), ("Western Power", "Perth WA")]
+        .into_iter()
+        .enumerate()
+        .map(|(i, (employer, location))| {
+            let mut p = position(employer, date(2019, 1, 1), None);
+            p.id = Some(i as i64 + 1);
+            p.location = location.to_string();
+            p
+        })
+        .collect();
+    let records: Vec<CompensationRecord> = (1..=3).map(|id| salary_record(id, 104_000.0, date(2019, 1, 1))).collect();
+    let rules = lsl::default_rules();
+
+    let status = calculations::lsl_accrual(&positions, &records, &rules, &None, today);
+    let tenure = |state| status.tenures.iter().find(|t| t.state == state).unwrap();
+    let (nsw, vic, wa) = (tenure(AustralianState::NSW), tenure(AustralianState::VIC), tenure(AustralianState::WA));
+
+    // Six years in: NSW pays pro rata from five, Victoria nothing until seven, WA pro rata from seven
+    assert!((nsw.years_of_service - 6.0).abs() < 0.01);
+    assert!((nsw.accrued_weeks - 5.2).abs() < 0.01);
+    assert!((nsw.accrued_value - 5.2 * 2000.0).abs() < 20.0);
+    assert_eq!(nsw.entitlement, LslEntitlement::ProRata);
+    assert_eq!(nsw.next_threshold, Some(LslEntitlement::Full));
+    assert_eq!(vic.entitlement, LslEntitlement::None);
+    assert_eq!(vic.payable_on_leaving, 0.0);
+    assert_eq!(vic.next_threshold, Some(LslEntitlement::Full));
+    assert_eq!(wa.entitlement, LslEntitlement::None);
+    assert_eq!(wa.next_threshold, Some(LslEntitlement::ProRata));
+    assert_eq!(status.total_payable_on_leaving, nsw.payable_on_leaving);
+
+    // Victoria and WA are within a year of their next threshold; NSW's full entitlement is four away
+    let insight = calculations::lsl_insight(&status, &rules, today).unwrap();
+    assert!(matches!(insight.category, InsightCategory::LongServiceLeave));
+    let mut flagged = insight.position_ids.clone();
+    flagged.sort();
+    assert_eq!(flagged, vec![2, 3]);
+
+    // A construction worker keeps accruing across employers in the portable scheme
+    for (p, employer) in positions.iter_mut().zip(["Ausgrid", "Multiplex", "Laing O'Rourke"]) {
+        p.employer_name = employer.to_string();
+        p.location = "Perth WA".to_string();
+        p.industry = Some("construction".to_string());
+    }
+    positions[0].end_date = Some(date(2020, 12, 31));
+    positions[1].start_date = date(2021, 1, 1);
+    positions[1].end_date = Some(date(2022, 12, 31));
+    positions[2].start_date = date(2023, 1, 1);
+    let portable = calculations::lsl_accrual(&positions, &records, &rules, &None, today);
+    assert_eq!(portable.tenures.len(), 1);
+    assert!(portable.tenures[0].portable);
+    assert!((portable.tenures[0].years_of_service - 6.0).abs() < 0.01);
+}
+
+#[test]
+fn resignation_plan_pays_out_leave_and_enforces_notice() {
+    let today = date(2025, 1, 1);
+    let mut held = position("Western Power", date(2015, 3, 1), None);
+    held.id = Some(1);
+    held.notice_period_weeks = Some(4.0);
+    held.leave_loading_percent = Some(17.5);
+    // $104,000 over 38 hours a week is $52.63 an hour
+    let records = vec![salary_record(1, 104_000.0, date(2015, 3, 1))];
+    let last_day = date(2025, 9, 1);
+    let lsl = calculations::lsl_accrual(std::slice::from_ref(&held), &records, &lsl::default_rules(), &None, last_day);
+
+    let err = calculations::calculate_resignation_plan(&held, &records, &lsl, Some(76.0), today, date(2025, 1, 15)).unwrap_err();
+    assert!(err.contains("2025-01-29"), "{}", err);
+
+    let plan = calculations::calculate_resignation_plan(&held, &records, &lsl, Some(76.0), today, last_day).unwrap();
+    assert_eq!(plan.earliest_last_day, date(2025, 1, 29));
+    assert!(plan.notice_from_contract);
+    assert!((plan.annual_leave_value - 4000.0).abs() < 1e-6);
+    assert!((plan.leave_loading - 700.0).abs() < 1e-6);
+    // Past ten years in WA, the full 8.67 weeks plus the half year since
+    let tenure = plan.long_service_leave.as_ref().unwrap();
+    assert_eq!(tenure.entitlement, LslEntitlement::Full);
+    assert!((plan.long_service_leave_value - tenure.accrued_weeks * 2000.0).abs() < 1e-6);
+    assert_eq!(plan.final_pay_gross, 4700.0 + plan.long_service_leave_value);
+    assert!(plan.estimated_withholding > plan.final_pay_gross * 0.3);
+    assert!(plan.estimated_withholding < plan.final_pay_gross * 0.5);
+
+    // Without a contractual period, ten years' service needs the four-week minimum; a casual has no leave
+    held.notice_period_weeks = None;
+    held.employment_type = EmploymentType::Casual;
+    let plan = calculations::calculate_resignation_plan(&held, &records, &lsl, None, today, last_day).unwrap();
+    assert!(!plan.notice_from_contract);
+    assert_eq!(plan.notice_period_weeks, 4.0);
+    assert_eq!(plan.annual_leave_value, 0.0);
+}
+
+#[test]
+fn scenario_rerun_falls_back_to_its_snapshot_when_the_position_is_gone() {
+    let mut current = position("Rio Tinto", date(2022, 7, 1), None);
+    current.id = Some(1);
+    let records = vec![salary_record(1, 100000.0, date(2023, 7, 1))];
+    let offer = salary_record(1, 120000.0, date(2024, 7, 1));
+    let scenario = calculations::offer_scenario("BHP offer", 1, &records, offer, &[], false).unwrap();
+    assert!(scenario.result.take_home_difference > 0.0);
+    assert!(scenario.result.take_home_difference < 20000.0);
+
+    // A pay rise since saving narrows the gap
+    let raised = vec![records[0].clone(), salary_record(1, 110000.0, date(2024, 7, 1))];
+    let rerun = calculations::rerun_scenario(&scenario, std::slice::from_ref(&current), &raised, &[], false);
+    assert!(rerun.warnings.is_empty());
+    assert!(rerun.current.total_compensation_difference < rerun.original.total_compensation_difference);
+
+    let rerun = calculations::rerun_scenario(&scenario, &[], &[], &[], false);
+    assert_eq!(rerun.warnings.len(), 1);
+    assert_eq!(rerun.current.current.original_total_compensation, rerun.original.current.original_total_compensation);
+}
+
+#[test]
+fn novated_lease_cancels_fbt_and_saves_tax() {
+    let lease = calculations::novated_lease_analysis(50000.0, 36, 15000.0, 34.5).unwrap();
+    assert!((lease.gst_credit - 50000.0 / 11.0).abs() < 0.01);
+    assert!((lease.residual_value - lease.amount_financed * 0.46875).abs() < 0.01);
+    // The employee contribution method pays the statutory 20% post-tax
+    assert!((lease.post_tax_contribution - 10000.0).abs() < 0.01);
+    assert!(lease.pre_tax_contribution > 0.0);
+    assert!(lease.annual_net_benefit > lease.income_tax_saved * 0.5);
+    assert_eq!(lease.non_cash_benefit.annual_value, lease.annual_net_benefit);
+    assert!(!lease.non_cash_benefit.fringe_benefit);
+
+    // The base value drops by a third in the fifth year, and the GST credit stops at the car limit
+    let five_years = calculations::novated_lease_analysis(90000.0, 60, 15000.0, 34.5).unwrap();
+    assert!((five_years.fbt_taxable_value - (4.0 * 18000.0 + 12000.0) / 5.0).abs() < 0.01);
+    assert!((five_years.gst_credit - tax::CAR_LIMIT / 11.0).abs() < 0.01);
+
+    assert!(calculations::novated_lease_analysis(50000.0, 72, 15000.0, 34.5).is_err());
+}
+
+#[test]
+fn flat_pay_drifts_down_the_percentiles() {
+    let snapshot = EarningsSnapshot {
+        date: date(2019, 7, 1),
+        base_annual: 90000.0,
+        actual_annual: 90000.0,
+        total_with_super: 99000.0,
+        effective_hourly_rate: 45.55,
+        jurisdiction: Jurisdiction::AU,
+        col_adjusted_annual: None,
+    };
+    let points = calculations::percentile_over_time(std::slice::from_ref(&snapshot), "Unknown", &WageIndex::default(), date(2025, 3, 1)).unwrap();
+
+    let labels: Vec<&str> = points.iter().map(|p| p.financial_year.as_str()).collect();
+    assert_eq!(labels, ["FY2019-20", "FY2020-21", "FY2021-22", "FY2022-23", "FY2023-24", "FY2024-25"]);
+    assert!(points.iter().all(|p| p.annual_earnings == 90000.0));
+    assert!(points.windows(2).all(|pair| pair[1].benchmark_median > pair[0].benchmark_median));
+    assert!(points.windows(2).all(|pair| pair[1].percentile <= pair[0].percentile));
+    assert!(points[0].percentile > points[5].percentile);
+    assert_eq!(points[4].benchmark_median, 90000.0);
+
+    let insight = calculations::percentile_decline_insight(&points).unwrap();
+    assert!(matches!(insight.category, InsightCategory::Underpaid));
+
+    // Without wage growth there's nothing to fall behind
+    let flat_index = WageIndex { growth: Vec::new(), default_growth_percent: 0.0, ..WageIndex::default() };
+    let points = calculations::percentile_over_time(&[snapshot], "Unknown", &flat_index, date(2025, 3, 1)).unwrap();
+    assert!(calculations::percentile_decline_insight(&points).is_none());
+}
+
+#[test]
+fn preference_mismatches_flag_fifo_and_overtime() {
+    let mut profile = profile(date(1990, 5, 20), true);
+    profile.career_preferences.fifo_tolerance = FIFOTolerance::None;
+    profile.career_preferences.overtime_appetite = OvertimeAppetite::None;
+
+    let mut site = position("Rio Tinto", date(2022, 7, 1), None);
+    site.id = Some(1);
+    site.location = "Tom Price WA (FIFO 8/6)".to_string();
+    let mut office = position("Pilbara Ports", date(2023, 1, 9), None);
+    office.id = Some(2);
+    let mut past = position("BHP", date(2018, 1, 8), Some(date(2022, 6, 30)));
+    past.id = Some(3);
+    past.location = "FIFO ex Perth".to_string();
+
+    let mut overtime = salary_record(2, 90000.0, date(2023, 1, 9));
+    overtime.overtime.average_hours_per_week = 8.0;
+    let records = vec![salary_record(1, 120000.0, date(2022, 7, 1)), overtime];
+
+    let positions = vec![site, office, past];
+    let mismatches = calculations::preference_mismatch_check(&profile, &positions, &records, date(2024, 6, 1));
+    assert_eq!(mismatches.len(), 2);
+    assert!(mismatches.iter().any(|m| m.position_id == 1 && m.preference == MismatchedPreference::FifoTolerance));
+    assert!(mismatches.iter().any(|m| m.position_id == 2 && m.preference == MismatchedPreference::OvertimeAppetite));
+
+    profile.career_preferences.overtime_appetite = OvertimeAppetite::Moderate;
+    profile.career_preferences.fifo_tolerance = FIFOTolerance::Regular;
+    assert!(calculations::preference_mismatch_check(&profile, &positions, &records, date(2024, 6, 1)).is_empty());
+}
+
+#[test]
+fn gender_benchmark_is_left_out_when_comparisons_are_off() {
+    let profile = UserProfile { gender: Gender::Woman, industry: "Healthcare".to_string(), ..profile(date(1990, 5, 20), true) };
+    let mut analysis = calculations::calculate_earnings_analysis(&[], &[], &Some(profile.clone()), &[], date(2024, 6, 1));
+    analysis.current_total_compensation = 60000.0;
+    analysis.insights.push(EarningsInsight {
+        category: InsightCategory::Underpaid,
+        title: "Earnings Below Market Median".to_string(),
+        description: "You're in the 25th percentile for your industry and location.".to_string(),
+        confidence_level: 0.75,
+        position_ids: Vec::new(),
+        data_points: vec!["Current total: $60000".to_string()],
+    });
+    let untouched = serde_json::to_string(&analysis).unwrap();
+
+    let mut off = analysis.clone();
+    calculations::apply_gender_benchmark(&mut off, &profile, &DemographicSettings { comparisons_enabled: false });
+    assert_eq!(serde_json::to_string(&off).unwrap(), untouched);
+    assert!(!untouched.contains("women") && !untouched.contains("Woman"));
+
+    // Nor does a profile without a gender change anything
+    let mut unstated = analysis.clone();
+    let no_gender = UserProfile { gender: Gender::PreferNotToSay, ..profile.clone() };
+    calculations::apply_gender_benchmark(&mut unstated, &no_gender, &DemographicSettings::default());
+    assert_eq!(serde_json::to_string(&unstated).unwrap(), untouched);
+
+    let mut on = analysis;
+    calculations::apply_gender_benchmark(&mut on, &profile, &DemographicSettings::default());
+    let benchmark = on.gender_benchmark.unwrap();
+    assert!(benchmark.median < 85000.0);
+    assert!(on.insights[0].data_points.iter().any(|point| point.contains("for women")));
+}
+
+#[test]
+fn implausible_base_rates_warn_without_blocking() {
+    let salary = salary_record(1, 90000.0, date(2024, 7, 1));
+    assert!(careerflow_core::validation::compensation_record_warnings(&salary).is_empty());
+
+    let typo = CompensationRecord { pay_type: PayType::Hourly, base_rate: 450.0, ..salary.clone() };
+    assert!(careerflow_core::validation::compensation_record_warnings(&typo).is_empty());
+    let typo = CompensationRecord { pay_type: PayType::Hourly, base_rate: 4500.0, ..salary.clone() };
+    let warnings = careerflow_core::validation::compensation_record_warnings(&typo);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].severity == IssueSeverity::Warning);
+    assert!(careerflow_core::validation::validate_compensation_record(&typo).is_ok());
+
+    let missing_digit = CompensationRecord { base_rate: 9000.0, ..salary };
+    assert_eq!(careerflow_core::validation::compensation_record_warnings(&missing_digit).len(), 1);
+}
+
+#[test]
+fn counterfactual_earnings_grow_each_tenure_at_the_market_rate() {
+    let stayed = Position { id: Some(1), ..position("Rio Tinto", date(2018, 1, 1), Some(date(2022, 12, 31))) };
+    let moved = Position { id: Some(2), ..position("BHP", date(2023, 1, 1), None) };
+    let records = vec![
+        salary_record(1, 80000.0, date(2018, 1, 1)),
+        salary_record(2, 120000.0, date(2023, 1, 1)),
+    ];
+
+    let result = calculations::counterfactual_earnings(&[stayed, moved], &records, &[], date(2024, 12, 31));
+
+    assert_eq!(result.years.iter().map(|y| y.year).collect::<Vec<_>>(), (2018..=2024).collect::<Vec<_>>());
+    assert!((result.actual_total - 640000.0).abs() < 2000.0);
+    // Five flat years at Rio Tinto cost about 6% compounding at Mid level
+    let last_flat_year = &result.years[4];
+    assert!((last_flat_year.actual - 80000.0).abs() < 500.0);
+    assert!((last_flat_year.counterfactual - 80000.0 * 1.06f64.powf(4.5)).abs() < 500.0);
+    // The move restarts the comparison at the new pay
+    assert!((result.years[5].counterfactual - 120000.0 * 1.06f64.powf(0.5)).abs() < 500.0);
+    assert!(result.foregone_total > 50000.0);
+    assert!((result.foregone_total - (result.counterfactual_total - result.actual_total)).abs() < 0.01);
+    let last = result.years.last().unwrap();
+    assert!((last.cumulative_actual - result.actual_total).abs() < 0.01);
+}
+
+#[test]
+fn overtime_hours_for_target_flags_long_weeks_and_higher_brackets() {
+    let record = CompensationRecord { pay_type: PayType::Hourly, base_rate: 50.0, ..salary_record(1, 0.0, date(2024, 7, 1)) };
+    let fy = FinancialYear::containing(date(2024, 9, 1));
+
+    let modest = calculations::overtime_hours_for_target(&record, 130000.0, 60.0, &fy, false).unwrap();
+    assert!((modest.income_without_overtime - 98800.0).abs() < 0.01);
+    assert!((modest.overtime_hourly_rate - 75.0).abs() < 0.01);
+    assert!((modest.overtime_hours_per_week - 8.0).abs() < 0.01);
+    assert!((modest.total_weekly_hours - 46.0).abs() < 0.01);
+    assert!(!modest.unsustainable && !modest.crosses_tax_bracket);
+
+    let next_bracket = calculations::overtime_hours_for_target(&record, 150000.0, 60.0, &fy, false).unwrap();
+    assert!(next_bracket.crosses_tax_bracket);
+    assert!((next_bracket.net_per_overtime_hour_at_target - 75.0 * (1.0 - next_bracket.marginal_tax_rate_at_target / 100.0)).abs() < 0.01);
+
+    let gruelling = calculations::overtime_hours_for_target(&record, 200000.0, 60.0, &fy, false).unwrap();
+    assert!(gruelling.unsustainable);
+    assert!(!calculations::overtime_hours_for_target(&record, 200000.0, 70.0, &fy, false).unwrap().unsustainable);
+
+    assert_eq!(calculations::overtime_hours_for_target(&record, 90000.0, 60.0, &fy, false).unwrap().overtime_hours_per_week, 0.0);
+    assert!(calculations::overtime_hours_for_target(&record, -1.0, 60.0, &fy, false).is_err());
+}
+
+#[test]
+fn analysis_warnings_record_each_assumption() {
+    let codes = |warnings: &[AnalysisWarning]| warnings.iter().map(|w| w.code).collect::<Vec<_>>();
+
+    let au = Position { id: Some(1), ..position("Rio Tinto", date(2019, 1, 1), Some(date(2021, 12, 31))) };
+    let nz = Position { id: Some(2), ..position("Fonterra", date(2022, 1, 1), None) };
+    let nz_record = CompensationRecord {
+        jurisdiction: Jurisdiction::NZ,
+        currency: "NZD".to_string(),
+        ..salary_record(2, 110000.0, date(2022, 1, 1))
+    };
+    let analysis = calculations::calculate_earnings_analysis(&[au.clone(), nz.clone()], &[nz_record], &None, &[], date(2024, 6, 1));
+    let estimated = analysis.warnings.iter().find(|w| w.code == AnalysisWarningCode::PayEstimatedFromLevel).unwrap();
+    assert!(matches!(estimated.related_entity, Some(RelatedEntity::Position(1))));
+    let missing_rate = analysis.warnings.iter().find(|w| w.code == AnalysisWarningCode::MissingExchangeRate).unwrap();
+    assert!(matches!(missing_rate.related_entity, Some(RelatedEntity::Position(2))));
+    assert!(codes(&analysis.warnings).contains(&AnalysisWarningCode::NoProfile));
+
+    let short = position("Short Stint", date(2020, 1, 1), Some(date(2021, 1, 1)));
+    let open = position("Open Ended", date(2015, 1, 1), None);
+    let loyalty = calculations::calculate_loyalty_tax(&[short, open], date(2024, 6, 1));
+    let skipped = loyalty.warnings.iter().find(|w| w.code == AnalysisWarningCode::ShortTenureSkipped).unwrap();
+    assert!(matches!(&skipped.related_entity, Some(RelatedEntity::Employer(name)) if name == "Short Stint"));
+    assert!(codes(&loyalty.warnings).contains(&AnalysisWarningCode::OpenEndedTenure));
+    assert_eq!(loyalty.warnings.len(), 2);
+
+    let mut usd = weekly_entry(Some(1), date(2023, 8, 6), 2000.0);
+    usd.currency = "USD".to_string();
+    let weekly = vec![weekly_entry(Some(1), date(2023, 7, 30), 2000.0), usd];
+    let mut nz_summary = yearly_entry("FY2023-24", 90000.0);
+    nz_summary.jurisdiction = Jurisdiction::NZ;
+    let summaries = calculations::calculate_financial_year_summaries(&weekly, &[nz_summary], &[]);
+    let au_year = summaries.iter().find(|s| s.jurisdiction == Jurisdiction::AU).unwrap();
+    assert_eq!(codes(&au_year.warnings), vec![AnalysisWarningCode::PartialYearProjected, AnalysisWarningCode::MissingExchangeRate]);
+    assert!(matches!(&au_year.warnings[0].related_entity, Some(RelatedEntity::FinancialYear(fy)) if fy == "FY2023-24"));
+    let nz_year = summaries.iter().find(|s| s.jurisdiction == Jurisdiction::NZ).unwrap();
+    assert_eq!(codes(&nz_year.warnings), vec![AnalysisWarningCode::TaxNotEstimated]);
+}
+
+#[test]
+fn progression_scorecard_grades_growth_against_each_benchmark() {
+    let role = Position { id: Some(1), ..position("Rio Tinto", date(2020, 7, 1), Some(date(2024, 6, 30))) };
+    let grade_of = |card: &ProgressionScorecard, benchmark: ProgressionBenchmark| {
+        card.grades.iter().find(|g| g.benchmark == benchmark).unwrap().grade
+    };
+
+    // 4% over four years while CPI ran near 5% a year
+    let stalled = vec![salary_record(1, 100000.0, date(2020, 7, 1)), salary_record(1, 104000.0, date(2022, 7, 1))];
+    let card = calculations::progression_scorecard(&role, &stalled, date(2025, 3, 1)).unwrap();
+    assert!((card.years - 4.0).abs() < 0.01);
+    assert!((card.annual_base_growth_percent - 0.985).abs() < 0.01);
+    assert_eq!(grade_of(&card, ProgressionBenchmark::Cpi), ScorecardGrade::Red);
+    assert_eq!(grade_of(&card, ProgressionBenchmark::SuperGuarantee), ScorecardGrade::Green);
+    assert_eq!(grade_of(&card, ProgressionBenchmark::Market), ScorecardGrade::Red);
+
+    // 5.5% a year: ahead of inflation, just short of 6% for a Mid level role
+    let steady = vec![salary_record(1, 100000.0, date(2020, 7, 1)), salary_record(1, 123882.0, date(2024, 1, 1))];
+    let card = calculations::progression_scorecard(&role, &steady, date(2025, 3, 1)).unwrap();
+    assert_eq!(grade_of(&card, ProgressionBenchmark::Cpi), ScorecardGrade::Green);
+    assert_eq!(grade_of(&card, ProgressionBenchmark::Market), ScorecardGrade::Amber);
+
+    // A pay cut to fund the guarantee's rise
+    let absorbed = vec![salary_record(1, 100000.0, date(2020, 7, 1)), salary_record(1, 98000.0, date(2023, 7, 1))];
+    let card = calculations::progression_scorecard(&role, &absorbed, date(2025, 3, 1)).unwrap();
+    assert_ne!(grade_of(&card, ProgressionBenchmark::SuperGuarantee), ScorecardGrade::Green);
+
+    let too_new = Position { end_date: None, ..role };
+    assert!(calculations::progression_scorecard(&too_new, &[salary_record(1, 100000.0, date(2024, 10, 1))], date(2025, 3, 1)).is_err());
+}
+
+#[test]
+fn bracket_creep_separates_inflation_from_real_growth() {
+    // FY2022-23 CPI was 6%; both years share the same brackets
+    let summaries = calculations::calculate_financial_year_summaries(
+        &[],
+        &[yearly_entry("FY2021-22", 100000.0), yearly_entry("FY2022-23", 106000.0), yearly_entry("FY2024-25", 150000.0)],
+        &[],
+    );
+
+    let report = calculations::bracket_creep_analysis(&summaries);
+    assert_eq!(report.years.len(), 1);
+    let year = &report.years[0];
+    assert_eq!(year.financial_year, "FY2022-23");
+    assert!(year.creep_points > 0.0);
+    assert!(year.real_growth_points.abs() < 1e-9);
+    assert!((year.average_tax_rate - year.previous_average_tax_rate - year.creep_points).abs() < 1e-9);
+    assert!(report.total_creep_tax > 0.0);
+}
+
+#[test]
+fn pay_cycle_breakdown_divides_by_the_average_number_of_periods() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+    let record = salary_record(1, 95000.0, date(2024, 7, 1));
+
+    let fortnightly = calculations::pay_cycle_breakdown(&record, PayslipFrequency::Fortnightly, &fy, false);
+    assert!((fortnightly.periods_per_year - 26.089).abs() < 1e-3);
+    assert!((fortnightly.annual.net - 73812.0).abs() < 1e-9);
+    assert!((fortnightly.per_period.net * fortnightly.periods_per_year - 73812.0).abs() < 1e-6);
+    assert!((fortnightly.per_period.gross - fortnightly.per_period.tax - fortnightly.per_period.net).abs() < 1e-9);
+    assert!((fortnightly.annual.employer_super - 10925.0).abs() < 1e-9);
+    assert!((fortnightly.extra_period_every_years.unwrap() - 11.2).abs() < 0.05);
+
+    let monthly = calculations::pay_cycle_breakdown(&record, PayslipFrequency::Monthly, &fy, false);
+    assert!((monthly.per_period.gross - 95000.0 / 12.0).abs() < 1e-9);
+    assert!(monthly.extra_period_every_years.is_none());
+}
+
+fn policy(policy_type: InsuranceType, annual_premium: f64, through_super: bool) -> InsurancePolicy {
+    InsurancePolicy {
+        id: None,
+        policy_type,
+        provider: "TAL".to_string(),
+        annual_premium,
+        through_super,
+        start_date: date(2020, 1, 1),
+        end_date: None,
+    }
+}
+
+#[test]
+fn income_protection_outside_super_is_deducted_and_super_premiums_come_off_the_balance() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+    let mut held = position("BHP", date(2020, 7, 1), None);
+    held.id = Some(1);
+    let records = vec![salary_record(1, 95000.0, date(2020, 7, 1))];
+    let positions = vec![held];
+
+    // TPD outside super isn't deductible; cover held through super is paid from the balance
+    let policies = vec![
+        policy(InsuranceType::IncomeProtection, 2000.0, false),
+        policy(InsuranceType::TPD, 500.0, false),
+        policy(InsuranceType::Life, 600.0, true),
+    ];
+    let mut combined = calculations::calculate_combined_tax_position(&fy, &positions, &records, &[], &[], &[], false);
+    let before = combined.tax_payable;
+    calculations::apply_insurance_deductions(&mut combined, &policies, &fy, false);
+    assert_eq!(combined.deductions, 2000.0);
+    assert_eq!(combined.total_taxable_income, 93000.0);
+    // 30% and 2% Medicare on the 2,000
+    assert!((before - combined.tax_payable - 640.0).abs() < 1e-6);
+
+    let mut member = profile(date(1990, 1, 1), true);
+    member.total_super_balance = Some(100000.0);
+    let projection = calculations::project_super(&positions, &records, &member, &ProjectionAssumptions::default(), &policies, date(2025, 1, 1));
+    assert_eq!(projection.insurance_premiums, 600.0);
+    assert!((projection.points[1].expected - (107000.0 + 10925.0 * 0.85 - 600.0)).abs() < 0.01);
+
+    // Income protection held means nothing to point out; without it, above the threshold, there is
+    let settings = InsuranceSettings::default();
+    let today = date(2025, 1, 1);
+    assert!(calculations::income_protection_insight(&policies, &positions, &records, &settings, today).is_none());
+    let insight = calculations::income_protection_insight(&policies[1..], &positions, &records, &settings, today).unwrap();
+    assert!(matches!(insight.category, InsightCategory::Insurance));
+    let off = InsuranceSettings { income_protection_insight: false, ..settings };
+    assert!(calculations::income_protection_insight(&policies[1..], &positions, &records, &off, today).is_none());
+}
+
+#[test]
+fn a_four_day_week_costs_less_take_home_than_time() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+    let record = salary_record(1, 95000.0, date(2024, 7, 1));
+
+    let impact = calculations::model_four_day_week(&record, &fy, false);
+    assert!((impact.four_day_week.gross - 76000.0).abs() < 1e-9);
+    assert!((impact.weekly_hours_gained - 7.6).abs() < 1e-9);
+    assert!((impact.time_gained_percent - 20.0).abs() < 1e-9);
+    assert!((impact.gross_reduction_percent - 20.0).abs() < 1e-9);
+    assert!(impact.net_reduction_percent < 20.0 && impact.net_reduction_percent > 15.0);
+    assert!((impact.four_day_week.employer_super - 76000.0 * 0.115).abs() < 1e-6);
+}
+
+#[test]
+fn surcharge_tiers_use_family_thresholds_with_a_partner() {
+    let fy = FinancialYear::parse("FY2024-25").unwrap();
+
+    // Singles: 97,000 is the top of the base tier
+    assert_eq!(tax::health_income_tier(97000.0, false, &fy), HealthIncomeTier::Base);
+    assert_eq!(tax::health_income_tier(97001.0, false, &fy), HealthIncomeTier::Tier1);
+    assert_eq!(tax::health_income_tier(151001.0, false, &fy), HealthIncomeTier::Tier3);
+    // Couples: double, on the combined income
+    assert_eq!(tax::health_income_tier(194000.0, true, &fy), HealthIncomeTier::Base);
+    assert_eq!(tax::health_income_tier(194001.0, true, &fy), HealthIncomeTier::Tier1);
+
+    // 120,000 alone is tier 2, but with a partner on 70,000 the family is under 194,000
+    let single = calculations::health_cover_position(&fy, 120000.0, None, 40);
+    assert_eq!(single.tier, HealthIncomeTier::Tier2);
+    assert!((single.medicare_levy_surcharge - 1500.0).abs() < 1e-9);
+    assert_eq!(single.rebate_percent, 8.202);
+    let couple = calculations::health_cover_position(&fy, 120000.0, Some(70000.0), 40);
+    assert_eq!(couple.tier, HealthIncomeTier::Base);
+    assert_eq!(couple.medicare_levy_surcharge, 0.0);
+    assert_eq!(couple.next_tier_threshold, Some(194000.0));
+
+    // A raise from 120,000 to 130,000 takes the family over 194,000
+    let current = salary_record(1, 120000.0, date(2024, 7, 1));
+    let offer = salary_record(1, 130000.0, date(2024, 7, 1));
+    let change = calculations::mls_tier_change(&current, &offer, Some(70000.0)).unwrap();
+    assert!(change.family);
+    assert_eq!((change.from, change.to), (HealthIncomeTier::Base, HealthIncomeTier::Tier1));
+    assert!((change.surcharge_difference - 1300.0).abs() < 1e-9);
+    assert!(calculations::mls_tier_change(&current, &offer, None).is_none());
+
+    let entries = vec![PartnerIncomeEntry { id: None, financial_year: "FY2023-24".to_string(), gross_income: 70000.0, is_estimate: true }];
+    assert_eq!(calculations::partner_income_for(&entries, &fy), Some(70000.0));
+    assert_eq!(calculations::partner_income_for(&entries, &FinancialYear::parse("FY2022-23").unwrap()), None);
+}
+
+#[test]
+fn overtime_in_most_weeks_contradicts_an_occasional_record() {
+    let mut record = salary_record(1, 95000.0, date(2023, 7, 1));
+    record.id = Some(7);
+    record.overtime.frequency = OvertimeFrequency::Occasional;
+    record.overtime.average_hours_per_week = 2.0;
+    let entries: Vec<WeeklyCompensationEntry> = (0..52)
+        .map(|week| {
+            let mut entry = weekly_entry(Some(1), date(2023, 7, 7) + chrono::Duration::weeks(week), 1900.0);
+            entry.hours_overtime = if week % 13 == 0 { 0.0 } else { 6.0 };
+            entry
+        })
+        .collect();
+
+    let profile = calculations::infer_overtime_profile(1, &entries, Some(&record));
+    assert_eq!(profile.periods_with_overtime, 48);
+    assert!(matches!(profile.implied_frequency, OvertimeFrequency::Frequent));
+    assert_eq!(profile.frequency_steps, Some(1));
+    assert!(profile.diverges);
+    let correction = profile.suggested_correction.unwrap();
+    assert_eq!(correction.record_id, 7);
+    assert!((correction.overtime.average_hours_per_week - 48.0 * 6.0 / 52.0).abs() < 1e-9);
+
+    record.overtime.frequency = OvertimeFrequency::Frequent;
+    assert!(!calculations::infer_overtime_profile(1, &entries, Some(&record)).diverges);
+}
+
+fn seasonal_entries(weeks: i64) -> Vec<WeeklyCompensationEntry> {
+    (0..weeks)
+        .map(|week| {
+            let week_ending = date(2022, 1, 7) + chrono::Duration::weeks(week);
+            let gross = match chrono::Datelike::month(&week_ending) {
+                3 => 2400.0,
+                1 => 1000.0,
+                _ => 1600.0,
+            };
+            weekly_entry(Some(1), week_ending, gross)
+        })
+        .collect()
+}
+
+#[test]
+fn seasonality_peaks_in_the_month_that_pays_most_each_year() {
+    let seasonality = calculations::seasonality(&seasonal_entries(104), None).unwrap();
+    assert_eq!(seasonality.strongest_month, Some(3));
+    assert_eq!(seasonality.weakest_month, Some(1));
+    assert_eq!(seasonality.years, vec![2022, 2023]);
+
+    let march = seasonality.months.iter().find(|m| m.month == 3).unwrap();
+    let june = seasonality.months.iter().find(|m| m.month == 6).unwrap();
+    assert!(march.index > 120.0);
+    assert!(june.index > 90.0 && june.index < 110.0);
+    assert_eq!(march.above_average_percent, 100.0);
+    assert!(calculations::seasonality_insight(&seasonality).is_some());
+}
+
+#[test]
+fn seasonality_needs_eighteen_months() {
+    let error = calculations::seasonality(&seasonal_entries(52), None).unwrap_err();
+    assert!(matches!(error, careerflow_core::error::AppError::NotEnoughData { needed_months: 18, .. }));
+}
+
+#[test]
+fn skills_from_the_current_role_outrank_longer_use_a_decade_ago() {
+    let mut old = position("Acme", date(2008, 1, 1), Some(date(2015, 1, 1)));
+    old.id = Some(1);
+    old.tools_systems_skills = vec!["COBOL".to_string(), "sap".to_string()];
+    let mut current = position("Globex", date(2022, 1, 1), None);
+    current.id = Some(2);
+    current.tools_systems_skills = vec!["Python".to_string(), "SAP".to_string()];
+
+    let ranking = calculations::skill_recency_ranking(&[old, current], date(2025, 1, 1));
+    let names: Vec<&str> = ranking.iter().map(|s| s.skill.as_str()).collect();
+    assert_eq!(names, vec!["SAP", "Python", "COBOL"]);
+    assert_eq!(ranking[0].positions_used, 2);
+    assert!(ranking[0].current);
+    assert_eq!(ranking[2].last_used_year, 2015);
+    assert!(ranking[1].score > ranking[2].score);
+}
+
+#[test]
+fn a_qualification_pays_back_from_the_start_of_study() {
+    let mut current = position("Acme", date(2020, 1, 6), None);
+    current.id = Some(1);
+    let records = vec![salary_record(1, 95000.0, date(2023, 7, 1))];
+    let profile = profile(date(1990, 5, 20), true);
+
+    let roi = calculations::qualification_roi(20000.0, Some(25000.0), 1.0, &profile, &[current.clone()], &records, date(2024, 6, 1));
+    assert_eq!(roi.total_investment, 115000.0);
+    assert!(!roi.uplift_is_estimate);
+    // 25000 growing 6% a year: 109k back after four years qualified, the rest during the fifth
+    let payback = roi.payback_years.unwrap();
+    assert!(payback > 5.0 && payback < 6.0, "{}", payback);
+    let nine_years_of_uplift = 25000.0 * (1.06_f64.powi(9) - 1.0) / 0.06;
+    assert!((roi.ten_year_net_benefit - (nine_years_of_uplift - 115000.0)).abs() < 1.0);
+
+    // Mid to Senior in the base estimates
+    let estimated = calculations::qualification_roi(20000.0, None, 1.0, &profile, &[current], &records, date(2024, 6, 1));
+    assert!(estimated.uplift_is_estimate);
+    assert_eq!(estimated.expected_salary_uplift, 25000.0);
+}
+
+#[test]
+fn lifetime_earnings_add_pay_to_date_to_pay_projected_to_retirement() {
+    let today = date(2026, 7, 1);
+    let mut current = position("Acme", date(2024, 7, 1), None);
+    current.id = Some(1);
+    current.seniority_level = SeniorityLevel::Senior;
+    let records = vec![salary_record(1, 100000.0, date(2024, 7, 1))];
+    // Turns 61 today: six years to 67
+    let profile = profile(date(1965, 7, 1), true);
+
+    let projection = calculations::project_lifetime_earnings(&profile, &[current.clone()], &records, &[], Some(3.0), today);
+    assert_eq!(projection.current_age, 61);
+    assert_eq!(projection.current_annual_earnings, 100000.0);
+
+    // Two whole financial years and the first day of a third; no CPI figures yet, so 2.5% a year
+    let first_day = 100000.0 / 365.0;
+    assert!((projection.historical_nominal - (200000.0 + first_day)).abs() < 0.01);
+    let real = 100000.0 * 1.025 * 1.025 + 100000.0 * 1.025 + first_day;
+    assert!((projection.historical_real - real).abs() < 0.01);
+
+    // Mining moves up a level every three years: Senior to Lead, then no further
+    let expected: f64 = (1..=6)
+        .map(|year| 100000.0 * 1.03_f64.powi(year) * if year >= 3 { 140.0 / 120.0 } else { 1.0 })
+        .sum();
+    assert_eq!(projection.points.len(), 6);
+    assert_eq!(projection.points[5].age, 67);
+    assert_eq!(projection.points[1].seniority_level, SeniorityLevel::Senior);
+    assert_eq!(projection.points[5].seniority_level, SeniorityLevel::Lead);
+    assert!((projection.projected_nominal - expected).abs() < 0.01);
+    assert!(projection.projected_real < projection.projected_nominal);
+    assert!((projection.lifetime_nominal - (projection.historical_nominal + projection.projected_nominal)).abs() < 1e-6);
+    assert!((projection.lifetime_real - (projection.historical_real + projection.projected_real)).abs() < 1e-6);
+
+    // Without a growth rate, the market rate for the level
+    let market = calculations::project_lifetime_earnings(&profile, &[current.clone()], &records, &[], None, today);
+    let salary = calculations::project_salary(&current, &records, &ProjectionAssumptions::default(), None).unwrap();
+    assert_eq!(market.salary_growth, salary.salary_growth);
+}
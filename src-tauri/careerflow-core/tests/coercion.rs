@@ -0,0 +1,129 @@
+mod common;
+
+use careerflow_core::coercion::{self, Coercion};
+use common::*;
+use serde_json::json;
+
+#[test]
+fn money_accepts_symbols_separators_and_suffixes() {
+    let cases = [
+        ("95000", 95000.0),
+        ("$95,000", 95000.0),
+        (" $ 95,000.50 ", 95000.5),
+        ("AUD 95,000", 95000.0),
+        ("95,000 AUD", 95000.0),
+        ("A$1,234.5", 1234.5),
+        ("NZ$80k", 80000.0),
+        ("95K", 95000.0),
+        ("1.2m", 1_200_000.0),
+        ("(1,200.50)", -1200.5),
+        ("-$250", -250.0),
+        ("$-250", -250.0),
+    ];
+    for (raw, expected) in cases {
+        let parsed = coercion::parse_money(raw).unwrap_or_else(|e| panic!("{}: {}", raw, e));
+        assert!((parsed - expected).abs() < 1e-9, "{} gave {}", raw, parsed);
+    }
+
+    for raw in ["", "$", "ninety", "95,000 per year", "1.2.3", "k"] {
+        assert!(coercion::parse_money(raw).is_err(), "{} should not parse", raw);
+    }
+}
+
+#[test]
+fn hours_accept_units_and_periods() {
+    let cases = [
+        ("38", 38.0),
+        ("38 hrs", 38.0),
+        ("38hrs", 38.0),
+        ("37.5 hours", 37.5),
+        ("40h", 40.0),
+        ("1 hour", 1.0),
+        ("38 hrs/wk", 38.0),
+        ("38 hours per week", 38.0),
+        ("38 HRS PW", 38.0),
+    ];
+    for (raw, expected) in cases {
+        assert_eq!(coercion::parse_hours(raw).unwrap(), expected, "{}", raw);
+    }
+
+    assert!(coercion::parse_hours("-4 hrs").unwrap_err().contains("negative"));
+    assert!(coercion::parse_hours("full time").is_err());
+    assert!(coercion::parse_hours("38 days").is_err());
+}
+
+#[test]
+fn dates_are_read_day_first() {
+    let cases = [
+        ("2024-07-08", date(2024, 7, 8)),
+        ("08/07/2024", date(2024, 7, 8)),
+        ("8/7/2024", date(2024, 7, 8)),
+        ("08-07-2024", date(2024, 7, 8)),
+        ("08.07.2024", date(2024, 7, 8)),
+        ("2024/07/08", date(2024, 7, 8)),
+        ("8 Jul 2024", date(2024, 7, 8)),
+        ("8 July 2024", date(2024, 7, 8)),
+        ("08-Jul-2024", date(2024, 7, 8)),
+        ("Jul 8, 2024", date(2024, 7, 8)),
+        ("08/07/24", date(2024, 7, 8)),
+        ("8 Jul 24", date(2024, 7, 8)),
+        ("2024-07-08T09:30:00", date(2024, 7, 8)),
+        ("2024-07-08 09:30:00", date(2024, 7, 8)),
+        ("2024-07-08T09:30:00+10:00", date(2024, 7, 8)),
+        ("Jul 2024", date(2024, 7, 1)),
+        ("July 2024", date(2024, 7, 1)),
+        ("07/2024", date(2024, 7, 1)),
+        ("2024-07", date(2024, 7, 1)),
+        ("Jul 24", date(2024, 7, 1)),
+        ("July 24", date(2024, 7, 1)),
+        ("7/24", date(2024, 7, 1)),
+        ("07/24", date(2024, 7, 1)),
+    ];
+    for (raw, expected) in cases {
+        assert_eq!(coercion::parse_date(raw).unwrap_or_else(|e| panic!("{}: {}", raw, e)), expected, "{}", raw);
+    }
+
+    // A two-digit year is never read as the first century
+    for raw in ["31/02/2024", "07/31/2024", "yesterday", "2024", "", "13/24", "24-07", "Jul-24"] {
+        assert!(coercion::parse_date(raw).is_err(), "{} should not parse", raw);
+    }
+}
+
+#[test]
+fn percent_bool_list_and_integer() {
+    assert_eq!(coercion::parse_percent("11.5%").unwrap(), 11.5);
+    assert_eq!(coercion::parse_percent(" 12 ").unwrap(), 12.0);
+    assert!(coercion::parse_percent("twelve%").is_err());
+
+    assert!(coercion::parse_bool("Yes").unwrap());
+    assert!(coercion::parse_bool("x").unwrap());
+    assert!(!coercion::parse_bool("N").unwrap());
+    assert!(coercion::parse_bool("maybe").is_err());
+
+    assert_eq!(coercion::parse_list("SAP; Excel ;;Power BI"), vec!["SAP", "Excel", "Power BI"]);
+    assert_eq!(coercion::parse_list("SAP|Excel, VBA"), vec!["SAP", "Excel, VBA"]);
+    assert_eq!(coercion::parse_list("SAP, Excel"), vec!["SAP", "Excel"]);
+    assert!(coercion::parse_list("  ").is_empty());
+
+    assert_eq!(coercion::parse_integer("1,024").unwrap(), 1024);
+    assert!(coercion::parse_integer("3.5").unwrap_err().contains("whole number"));
+}
+
+#[test]
+fn choices_ignore_case_and_punctuation() {
+    let choices = ["Permanent", "Contract", "Casual"];
+    assert_eq!(coercion::coerce_choice("permanent", &choices).unwrap(), "Permanent");
+    assert_eq!(coercion::coerce_choice(" CONTRACT ", &choices).unwrap(), "Contract");
+    assert_eq!(coercion::coerce_choice("Full-Time", &["FullTime"]).unwrap(), "FullTime");
+    assert!(coercion::coerce_choice("temp", &choices).unwrap_err().contains("Permanent, Contract, Casual"));
+}
+
+#[test]
+fn coerce_produces_json_values() {
+    assert_eq!(coercion::coerce("$1,000", Coercion::Money).unwrap(), json!(1000.0));
+    assert_eq!(coercion::coerce("8/7/2024", Coercion::Date).unwrap(), json!("2024-07-08"));
+    assert_eq!(coercion::coerce("a;b", Coercion::List).unwrap(), json!(["a", "b"]));
+    assert_eq!(coercion::coerce(" text ", Coercion::Text).unwrap(), json!("text"));
+    assert_eq!(coercion::coerce("no", Coercion::Boolean).unwrap(), json!(false));
+    assert!(coercion::coerce("abc", Coercion::Number).is_err());
+}
@@ -0,0 +1,253 @@
+//! The largest valid instance of each saved model: every Option set, every list non-empty and
+//! values away from the defaults, so a field that goes missing on the way through can't hide.
+
+use super::date;
+use careerflow_core::calculations;
+use careerflow_core::models::*;
+use chrono::{DateTime, TimeZone, Utc, Weekday};
+
+pub fn timestamp() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 3, 4, 5, 6, 7).unwrap()
+}
+
+pub fn full_profile() -> UserProfile {
+    UserProfile {
+        id: None,
+        first_name: "Zoë".to_string(),
+        last_name: "O'Connor-Ngata".to_string(),
+        date_of_birth: date(1961, 2, 28),
+        state: AustralianState::TAS,
+        industry: "Construction".to_string(),
+        highest_qualification: Qualification::GraduateDiploma,
+        career_preferences: CareerPreferences {
+            employment_type_preference: EmploymentType::Contract,
+            fifo_tolerance: FIFOTolerance::Extensive,
+            travel_tolerance: TravelTolerance::International,
+            overtime_appetite: OvertimeAppetite::High,
+            privacy_acknowledged: true,
+            disclaimer_acknowledged: true,
+        },
+        standard_weekly_hours: 37.5,
+        has_help_debt: true,
+        total_super_balance: Some(512345.67),
+        gender: Gender::NonBinary,
+        created_at: timestamp(),
+        updated_at: timestamp(),
+    }
+}
+
+pub fn full_position() -> Position {
+    Position {
+        id: None,
+        employer_name: "Fortescue, \"FMG\"".to_string(),
+        job_title: "Senior Electrical Engineer".to_string(),
+        employment_type: EmploymentType::Contract,
+        location: "Port Hedland, WA".to_string(),
+        start_date: date(2019, 2, 28),
+        end_date: Some(date(2024, 2, 29)),
+        seniority_level: SeniorityLevel::Lead,
+        core_responsibilities: "Shutdown planning\nHV switching, 33kV".to_string(),
+        tools_systems_skills: vec!["SAP PM".to_string(), "ETAP".to_string(), "Power BI".to_string()],
+        achievements: vec!["Cut outage time by 40%".to_string(), "Commissioned a 20MW solar farm".to_string()],
+        industry: Some("Mining".to_string()),
+        week_ending_day: Some(Weekday::Wed),
+        notice_period_weeks: Some(4.5),
+        leave_loading_percent: Some(17.5),
+        probation_months: Some(6),
+        commute_minutes_per_day: Some(95),
+        archived: true,
+        created_at: timestamp(),
+        updated_at: timestamp(),
+    }
+}
+
+pub fn full_award_rate() -> AwardRate {
+    AwardRate {
+        id: None,
+        award_name: "Electrical, Electronic and Communications Contracting Award".to_string(),
+        classification: "Electrical Worker Grade 5".to_string(),
+        effective_date: date(2023, 7, 1),
+        hourly_minimum: 29.87,
+        overtime_multiplier: 1.5,
+        casual_loading: 25.0,
+    }
+}
+
+pub fn full_compensation_record(position_id: i64, award_classification_id: i64) -> CompensationRecord {
+    CompensationRecord {
+        id: None,
+        position_id,
+        entry_type: CompensationEntryType::Exact,
+        pay_type: PayType::Hourly,
+        base_rate: 87.35,
+        standard_weekly_hours: 42.5,
+        overtime: OvertimeDetails {
+            frequency: OvertimeFrequency::Extreme,
+            rate_multiplier: 2.0,
+            average_hours_per_week: 14.25,
+            annual_hours: Some(741.0),
+        },
+        allowances: vec![
+            Allowance {
+                name: "Site".to_string(),
+                amount: 123.45,
+                frequency: AllowanceFrequency::Fortnightly,
+                taxable: true,
+                ordinary_time: Some(false),
+            },
+            Allowance {
+                name: "Meal".to_string(),
+                amount: 15.5,
+                frequency: AllowanceFrequency::Weekly,
+                taxable: false,
+                ordinary_time: None,
+            },
+        ],
+        bonuses: vec![Bonus {
+            name: "Safety".to_string(),
+            amount: 7500.0,
+            date_awarded: date(2023, 12, 15),
+            taxable: true,
+        }],
+        super_contributions: SuperDetails {
+            contribution_rate: 12.5,
+            additional_contributions: 1200.0,
+            salary_sacrifice: 5000.0,
+        },
+        tax_withheld: Some(45678.9),
+        payslip_frequency: Some(PayslipFrequency::Fortnightly),
+        effective_date: date(2023, 7, 1),
+        confidence_score: 87.5,
+        currency: "NZD".to_string(),
+        jurisdiction: Jurisdiction::NZ,
+        claims_tax_free_threshold: false,
+        equity: Some(EquityGrant {
+            grant_value: 80000.0,
+            grant_date: date(2023, 8, 1),
+            vesting_months: 48,
+            cliff_months: 12,
+            vesting_frequency: VestingFrequency::Quarterly,
+        }),
+        packaging: vec![PackagingItem {
+            name: "Novated lease".to_string(),
+            pre_tax_amount_per_period: 410.25,
+            post_tax_amount_per_period: 95.0,
+            attracts_fbt: true,
+        }],
+        non_cash_benefits: vec![NonCashBenefit {
+            name: "Ute".to_string(),
+            annual_value: 9000.0,
+            fringe_benefit: true,
+        }],
+        award_classification_id: Some(award_classification_id),
+        notes: Some("Rate from the 2023 EBA, clause 12.3".to_string()),
+        created_at: timestamp(),
+    }
+}
+
+pub fn full_weekly_entry(position_id: i64) -> WeeklyCompensationEntry {
+    WeeklyCompensationEntry {
+        id: None,
+        position_id: Some(position_id),
+        financial_year: "FY2023-24".to_string(),
+        week_ending: date(2024, 2, 28),
+        period_start: Some(date(2024, 2, 15)),
+        period_type: PayslipFrequency::Fortnightly,
+        gross_pay: 9876.54,
+        tax_withheld: 2765.43,
+        net_pay: 7111.11,
+        hours_ordinary: 76.0,
+        hours_overtime: 12.5,
+        overtime_rate_multiplier: 1.75,
+        allowances: vec![Allowance {
+            name: "Tool".to_string(),
+            amount: 32.1,
+            frequency: AllowanceFrequency::Weekly,
+            taxable: true,
+            ordinary_time: Some(true),
+        }],
+        super_contributed: 987.65,
+        currency: "NZD".to_string(),
+        notes: Some("Includes back pay".to_string()),
+        created_at: timestamp(),
+    }
+}
+
+pub fn full_yearly_entry(position_id: i64) -> YearlyIncomeEntry {
+    YearlyIncomeEntry {
+        id: None,
+        position_id: Some(position_id),
+        financial_year: "FY2022-23".to_string(),
+        gross_income: 187654.32,
+        tax_withheld: 54321.09,
+        reportable_super: 15000.0,
+        reportable_fringe_benefits: Some(4321.0),
+        allowances: vec![Allowance {
+            name: "Travel".to_string(),
+            amount: 2500.0,
+            frequency: AllowanceFrequency::Annually,
+            taxable: true,
+            ordinary_time: Some(false),
+        }],
+        source: IncomeSource::Manual,
+        currency: "NZD".to_string(),
+        jurisdiction: Jurisdiction::NZ,
+        notes: Some("Amended statement".to_string()),
+        created_at: timestamp(),
+    }
+}
+
+pub fn full_exchange_rate() -> ExchangeRate {
+    ExchangeRate {
+        id: None,
+        currency: "NZD".to_string(),
+        financial_year: "FY2023-24".to_string(),
+        rate_to_aud: 0.9234,
+    }
+}
+
+pub fn full_scenario(position_id: i64, baseline: CompensationRecord) -> Scenario {
+    let mut offer = baseline.clone();
+    offer.base_rate = 95.0;
+    offer.effective_date = date(2024, 8, 1);
+    let mut scenario = calculations::offer_scenario("Roy Hill offer", position_id, &[baseline], offer, &[], true).unwrap();
+    scenario.created_at = timestamp();
+    scenario
+}
+
+pub fn full_template(record: &CompensationRecord) -> PositionTemplate {
+    PositionTemplate {
+        id: None,
+        name: "Shutdown contract".to_string(),
+        fields: careerflow_core::templates::template_fields(&full_position(), Some(record), true),
+        created_at: timestamp(),
+    }
+}
+
+pub fn full_insurance_policy() -> InsurancePolicy {
+    InsurancePolicy {
+        id: None,
+        policy_type: InsuranceType::IncomeProtection,
+        provider: "TAL".to_string(),
+        annual_premium: 2345.67,
+        through_super: false,
+        start_date: date(2020, 1, 31),
+        end_date: Some(date(2025, 1, 30)),
+    }
+}
+
+pub fn full_partner_income() -> PartnerIncomeEntry {
+    PartnerIncomeEntry {
+        id: None,
+        financial_year: "FY2023-24".to_string(),
+        gross_income: 71234.5,
+        is_estimate: true,
+    }
+}
+
+pub fn full_tag() -> Tag {
+    Tag {
+        id: None,
+        name: "FIFO 2:1, \"Pilbara\"".to_string(),
+    }
+}
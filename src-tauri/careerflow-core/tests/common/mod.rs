@@ -0,0 +1,122 @@
+#![allow(dead_code)]
+
+use careerflow_core::models::*;
+use careerflow_core::Database;
+use chrono::{NaiveDate, Utc};
+use serde_json::json;
+use tempfile::TempDir;
+
+pub mod fixtures;
+
+pub fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+/// A fresh database in its own temp directory; keep the directory alive for the test.
+pub fn temp_db() -> (TempDir, Database) {
+    let dir = tempfile::tempdir().unwrap();
+    let db = Database::new(dir.path().join("careerflow.db")).unwrap();
+    (dir, db)
+}
+
+pub fn profile(date_of_birth: NaiveDate, privacy_acknowledged: bool) -> UserProfile {
+    serde_json::from_value(json!({
+        "id": null,
+        "first_name": "Sam",
+        "last_name": "Citizen",
+        "date_of_birth": date_of_birth,
+        "state": "WA",
+        "industry": "Mining",
+        "highest_qualification": "Diploma",
+        "career_preferences": {
+            "employment_type_preference": "Permanent",
+            "fifo_tolerance": "Regular",
+            "travel_tolerance": "Regional",
+            "overtime_appetite": "Moderate",
+            "privacy_acknowledged": privacy_acknowledged,
+            "disclaimer_acknowledged": true,
+        },
+        "standard_weekly_hours": 38.0,
+        "created_at": Utc::now(),
+        "updated_at": Utc::now(),
+    }))
+    .unwrap()
+}
+
+pub fn position(employer: &str, start: NaiveDate, end: Option<NaiveDate>) -> Position {
+    serde_json::from_value(json!({
+        "id": null,
+        "employer_name": employer,
+        "job_title": "Maintenance Planner",
+        "employment_type": "Permanent",
+        "location": "Perth WA",
+        "start_date": start,
+        "end_date": end,
+        "seniority_level": "Mid",
+        "core_responsibilities": "Plan shutdowns",
+        "tools_systems_skills": ["SAP"],
+        "achievements": [],
+        "created_at": Utc::now(),
+        "updated_at": Utc::now(),
+    }))
+    .unwrap()
+}
+
+pub fn salary_record(position_id: i64, base: f64, effective: NaiveDate) -> CompensationRecord {
+    serde_json::from_value(json!({
+        "id": null,
+        "position_id": position_id,
+        "entry_type": "Exact",
+        "pay_type": "Salary",
+        "base_rate": base,
+        "standard_weekly_hours": 38.0,
+        "overtime": { "frequency": "None", "rate_multiplier": 1.5, "average_hours_per_week": 0.0, "annual_hours": null },
+        "allowances": [],
+        "bonuses": [],
+        "super_contributions": { "contribution_rate": 11.5, "additional_contributions": 0.0, "salary_sacrifice": 0.0 },
+        "tax_withheld": null,
+        "payslip_frequency": "Fortnightly",
+        "effective_date": effective,
+        "confidence_score": 100.0,
+        "notes": null,
+        "created_at": Utc::now(),
+    }))
+    .unwrap()
+}
+
+pub fn weekly_entry(position_id: Option<i64>, week_ending: NaiveDate, gross: f64) -> WeeklyCompensationEntry {
+    serde_json::from_value(json!({
+        "id": null,
+        "position_id": position_id,
+        "financial_year": "",
+        "week_ending": week_ending,
+        "gross_pay": gross,
+        "tax_withheld": gross * 0.25,
+        "net_pay": gross * 0.75,
+        "hours_ordinary": 38.0,
+        "hours_overtime": 0.0,
+        "overtime_rate_multiplier": 1.5,
+        "allowances": [],
+        "super_contributed": gross * 0.115,
+        "notes": null,
+        "created_at": Utc::now(),
+    }))
+    .unwrap()
+}
+
+pub fn yearly_entry(financial_year: &str, gross: f64) -> YearlyIncomeEntry {
+    serde_json::from_value(json!({
+        "id": null,
+        "position_id": null,
+        "financial_year": financial_year,
+        "gross_income": gross,
+        "tax_withheld": gross * 0.25,
+        "reportable_super": 0.0,
+        "reportable_fringe_benefits": null,
+        "allowances": [],
+        "source": "ATO",
+        "notes": null,
+        "created_at": Utc::now(),
+    }))
+    .unwrap()
+}
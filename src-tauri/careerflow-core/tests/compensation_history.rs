@@ -0,0 +1,135 @@
+mod common;
+
+use careerflow_core::{calculations, compensation_history};
+use careerflow_core::models::*;
+use common::*;
+
+#[test]
+fn a_raise_copies_the_record_and_shows_in_the_lineage() {
+    let (_dir, db) = temp_db();
+    let mut held = position("Pilbara Ports", date(2021, 7, 1), None);
+    held.id = Some(db.save_position(held.clone()).unwrap());
+    let position_id = held.id.unwrap();
+    let mut first = salary_record(position_id, 90000.0, date(2021, 7, 1));
+    first.super_contributions.contribution_rate = 10.0;
+    first.notes = Some("Offer letter".to_string());
+    let first_id = db.save_compensation_record(first).unwrap();
+
+    let overrides = CompensationRecordOverrides {
+        base_rate: Some(94500.0),
+        super_contribution_rate: Some(10.5),
+        ..Default::default()
+    };
+    let raised = compensation_history::clone_record(&db, first_id, date(2022, 7, 1), overrides).unwrap();
+
+    assert!(raised.id.is_some() && raised.id != Some(first_id));
+    assert_eq!(raised.effective_date, date(2022, 7, 1));
+    assert_eq!(raised.base_rate, 94500.0);
+    assert_eq!(raised.notes.as_deref(), Some("Offer letter"));
+
+    // One record per effective date
+    let again = compensation_history::clone_record(&db, first_id, date(2022, 7, 1), Default::default());
+    assert!(again.unwrap_err().contains("already has a record"));
+
+    let records = db.get_compensation_records(position_id).unwrap();
+    let lineage = calculations::calculate_record_lineage(position_id, &records);
+    assert_eq!(lineage.len(), 2);
+    assert!(lineage[0].change_from_previous.is_none());
+    let delta = lineage[1].change_from_previous.as_ref().unwrap();
+    assert_eq!(delta.days_since_previous, 365);
+    assert_eq!(delta.annual_base_change, 4500.0);
+    assert!((delta.annual_base_change_percent - 5.0).abs() < 1e-9);
+    assert!((delta.super_rate_change - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn merging_folds_an_overtime_only_record_into_the_base_record() {
+    let (_dir, db) = temp_db();
+    let mut held = position("Pilbara Ports", date(2021, 7, 1), None);
+    held.id = Some(db.save_position(held.clone()).unwrap());
+    let position_id = held.id.unwrap();
+
+    let mut base = salary_record(position_id, 90000.0, date(2021, 7, 1));
+    base.confidence_score = 60.0;
+    base.allowances.push(Allowance {
+        name: "Site".to_string(),
+        amount: 50.0,
+        frequency: AllowanceFrequency::Weekly,
+        taxable: true,
+        ordinary_time: None,
+    });
+    let base_id = db.save_compensation_record(base).unwrap();
+
+    let mut overtime = salary_record(position_id, 0.0, date(2021, 8, 1));
+    overtime.overtime = OvertimeDetails {
+        frequency: OvertimeFrequency::Frequent,
+        rate_multiplier: 1.5,
+        average_hours_per_week: 6.0,
+        annual_hours: None,
+    };
+    overtime.allowances.push(Allowance {
+        name: "Meal".to_string(),
+        amount: 20.0,
+        frequency: AllowanceFrequency::Weekly,
+        taxable: false,
+        ordinary_time: None,
+    });
+    let overtime_id = db.save_compensation_record(overtime).unwrap();
+
+    assert_eq!(compensation_history::merge_records(&db, base_id, overtime_id).unwrap(), base_id);
+
+    let records = db.get_compensation_records(position_id).unwrap();
+    assert_eq!(records.len(), 1);
+    let merged = &records[0];
+    assert_eq!(merged.effective_date, date(2021, 7, 1));
+    assert_eq!(merged.base_rate, 90000.0);
+    assert_eq!(merged.confidence_score, 100.0);
+    assert_eq!(merged.overtime.average_hours_per_week, 6.0);
+    let names: Vec<&str> = merged.allowances.iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(names, vec!["Site", "Meal"]);
+
+    let mut other = position("Karratha Gas", date(2022, 1, 1), None);
+    other.id = Some(db.save_position(other.clone()).unwrap());
+    let elsewhere = db.save_compensation_record(salary_record(other.id.unwrap(), 80000.0, date(2022, 1, 1))).unwrap();
+    let refused = compensation_history::merge_records(&db, base_id, elsewhere);
+    assert!(refused.unwrap_err().contains("different positions"));
+    assert!(db.get_compensation_record(elsewhere).unwrap().is_some());
+}
+
+#[test]
+fn upgrading_a_fuzzy_record_logs_it_and_drops_it_from_the_fuzzy_list() {
+    let (_dir, db) = temp_db();
+    let mut held = position("Rio Tinto", date(2020, 7, 1), Some(date(2024, 6, 30)));
+    held.id = Some(db.save_position(held.clone()).unwrap());
+    let position_id = held.id.unwrap();
+
+    let guessed = |base: f64, effective| CompensationRecord {
+        entry_type: CompensationEntryType::Fuzzy,
+        confidence_score: 60.0,
+        ..salary_record(position_id, base, effective)
+    };
+    let short = db.save_compensation_record(guessed(100000.0, date(2020, 7, 1))).unwrap();
+    let long = db.save_compensation_record(guessed(110000.0, date(2021, 7, 1))).unwrap();
+
+    // Three years on the later record outweigh one on the earlier
+    let records = db.get_all_compensation_records().unwrap();
+    let fuzzy = calculations::fuzzy_records_by_weight(&[held.clone()], &records, &[], date(2025, 1, 1));
+    let order: Vec<Option<i64>> = fuzzy.iter().map(|f| f.record.id).collect();
+    assert_eq!(order, [Some(long), Some(short)]);
+    assert!((fuzzy.iter().map(|f| f.share_of_earnings).sum::<f64>() - 100.0).abs() < 1e-6);
+
+    let corrections = CompensationRecordOverrides { base_rate: Some(112000.0), ..Default::default() };
+    let upgraded = compensation_history::upgrade_record_to_exact(&db, long, corrections).unwrap();
+    assert!(matches!(upgraded.entry_type, CompensationEntryType::Exact));
+    assert_eq!(upgraded.confidence_score, 100.0);
+
+    let changes = db.get_record_changes(long).unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].before.base_rate, 110000.0);
+    assert_eq!(db.get_compensation_record(long).unwrap().unwrap().base_rate, 112000.0);
+
+    let records = db.get_all_compensation_records().unwrap();
+    let fuzzy = calculations::fuzzy_records_by_weight(&[held], &records, &[], date(2025, 1, 1));
+    assert_eq!(fuzzy.len(), 1);
+    assert!(compensation_history::upgrade_record_to_exact(&db, long, CompensationRecordOverrides::default()).is_err());
+}
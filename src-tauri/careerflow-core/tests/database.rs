@@ -0,0 +1,480 @@
+mod common;
+
+use careerflow_core::{backup, calculations, validation};
+use careerflow_core::models::*;
+use common::*;
+
+#[test]
+fn compensation_records_round_trip() {
+    let (_dir, db) = temp_db();
+    let position_id = db.save_position(position("Acme", date(2022, 1, 10), None)).unwrap();
+
+    let mut record = salary_record(position_id, 95000.0, date(2023, 7, 1));
+    record.currency = "NZD".to_string();
+    record.jurisdiction = Jurisdiction::NZ;
+    record.non_cash_benefits = vec![NonCashBenefit { name: "Phone".to_string(), annual_value: 900.0, fringe_benefit: false }];
+    let record_id = db.save_compensation_record(record).unwrap();
+
+    let records = db.get_compensation_records(position_id).unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].id, Some(record_id));
+    assert_eq!(records[0].base_rate, 95000.0);
+    assert_eq!(records[0].currency, "NZD");
+    assert_eq!(records[0].jurisdiction, Jurisdiction::NZ);
+    assert_eq!(records[0].non_cash_benefits[0].annual_value, 900.0);
+}
+
+#[test]
+fn equity_grants_round_trip_and_stay_behind_on_records_without_one() {
+    let (_dir, db) = temp_db();
+    let position_id = db.save_position(position("Canva", date(2022, 1, 10), None)).unwrap();
+    let mut record = salary_record(position_id, 180000.0, date(2023, 7, 1));
+    record.equity = Some(EquityGrant {
+        grant_value: 80000.0,
+        grant_date: date(2023, 7, 1),
+        vesting_months: 48,
+        cliff_months: 12,
+        vesting_frequency: VestingFrequency::Monthly,
+    });
+    let id = db.save_compensation_record(record).unwrap();
+    db.save_compensation_record(salary_record(position_id, 120000.0, date(2022, 1, 10))).unwrap();
+
+    let saved = db.get_compensation_record(id).unwrap().unwrap();
+    let equity = saved.equity.unwrap();
+    assert_eq!(equity.grant_value, 80000.0);
+    assert_eq!(equity.vesting_frequency, VestingFrequency::Monthly);
+    let records = db.get_compensation_records(position_id).unwrap();
+    assert_eq!(records.iter().filter(|r| r.equity.is_none()).count(), 1);
+}
+
+#[test]
+fn deleting_an_award_rate_moves_linked_records_to_the_classifications_other_rate() {
+    let (_dir, db) = temp_db();
+    let rate = |effective, hourly_minimum| AwardRate {
+        id: None,
+        award_name: "Clerks Award".to_string(),
+        classification: "Level 2".to_string(),
+        effective_date: effective,
+        hourly_minimum,
+        overtime_multiplier: 1.5,
+        casual_loading: 25.0,
+    };
+    let earlier = db.save_award_rate(rate(date(2023, 7, 1), 25.0)).unwrap();
+    let later = db.save_award_rate(rate(date(2024, 7, 1), 26.0)).unwrap();
+    let position_id = db.save_position(position("Acme", date(2023, 7, 3), None)).unwrap();
+    let mut record = salary_record(position_id, 60000.0, date(2023, 7, 3));
+    record.award_classification_id = Some(later);
+    let id = db.save_compensation_record(record).unwrap();
+
+    db.delete_award_rate(later).unwrap();
+    assert_eq!(db.get_compensation_record(id).unwrap().unwrap().award_classification_id, Some(earlier));
+    db.delete_award_rate(earlier).unwrap();
+    assert_eq!(db.get_compensation_record(id).unwrap().unwrap().award_classification_id, None);
+}
+
+#[test]
+fn weekly_entry_period_start_is_derived_when_missing() {
+    let (_dir, db) = temp_db();
+    db.save_weekly_entry(weekly_entry(None, date(2024, 7, 7), 2000.0)).unwrap();
+
+    let entries = db.get_weekly_entries().unwrap();
+    assert_eq!(entries[0].period_start, Some(date(2024, 7, 1)));
+}
+
+#[test]
+fn exchange_rate_save_replaces_existing_year() {
+    let (_dir, db) = temp_db();
+    let rate = |rate_to_aud| ExchangeRate {
+        id: None,
+        currency: "NZD".to_string(),
+        financial_year: "FY2023-24".to_string(),
+        rate_to_aud,
+    };
+
+    let first = db.save_exchange_rate(rate(0.90)).unwrap();
+    let second = db.save_exchange_rate(rate(0.92)).unwrap();
+
+    assert_eq!(first, second);
+    let rates = db.get_exchange_rates().unwrap();
+    assert_eq!(rates.len(), 1);
+    assert_eq!(rates[0].rate_to_aud, 0.92);
+}
+
+#[test]
+fn new_database_has_no_backfill_warnings() {
+    let (_dir, db) = temp_db();
+    db.save_weekly_entry(weekly_entry(None, date(2024, 7, 7), 2000.0)).unwrap();
+
+    assert!(db.get_default_backfill_warnings().unwrap().is_empty());
+}
+
+#[test]
+fn export_includes_every_table() {
+    let (_dir, db) = temp_db();
+    let position_id = db.save_position(position("Acme", date(2022, 1, 10), None)).unwrap();
+    db.save_compensation_record(salary_record(position_id, 95000.0, date(2023, 7, 1))).unwrap();
+    db.save_weekly_entry(weekly_entry(Some(position_id), date(2024, 7, 7), 2000.0)).unwrap();
+    db.save_yearly_entry(yearly_entry("FY2023-24", 98000.0)).unwrap();
+
+    let export = backup::export_all_data(&db).unwrap();
+
+    assert_eq!(export.positions.len(), 1);
+    assert_eq!(export.compensation_records.len(), 1);
+    assert_eq!(export.weekly_entries.len(), 1);
+    assert_eq!(export.yearly_entries.len(), 1);
+    assert_eq!(export.yearly_entries[0].gross_income, 98000.0);
+}
+
+#[test]
+fn positions_active_between_treats_open_ended_roles_as_current() {
+    let (_dir, db) = temp_db();
+    db.save_position(position("Before", date(2015, 1, 1), Some(date(2018, 6, 30)))).unwrap();
+    db.save_position(position("Overlapping", date(2018, 3, 1), Some(date(2022, 7, 1)))).unwrap();
+    db.save_position(position("Current", date(2022, 7, 4), None)).unwrap();
+
+    let fy2022 = db.positions_active_between(date(2022, 7, 1), date(2023, 6, 30)).unwrap();
+    let employers: Vec<_> = fy2022.iter().map(|p| p.employer_name.as_str()).collect();
+    assert_eq!(employers, vec!["Current", "Overlapping"]);
+
+    let fy2019 = db.positions_active_between(date(2019, 7, 1), date(2020, 6, 30)).unwrap();
+    assert_eq!(fy2019.len(), 1);
+    assert_eq!(fy2019[0].employer_name, "Overlapping");
+}
+
+#[test]
+fn position_summaries_match_the_full_positions() {
+    let (_dir, db) = temp_db();
+    db.save_position(position("Pilbara Ports", date(2020, 2, 3), Some(date(2022, 6, 30)))).unwrap();
+    let mut current = position("Rio Tinto", date(2022, 7, 1), None);
+    current.core_responsibilities = "Plan shutdowns. ".repeat(500);
+    db.save_position(current).unwrap();
+
+    let summaries = db.get_position_summaries().unwrap();
+    let positions = db.get_positions().unwrap();
+    assert_eq!(summaries.len(), 2);
+    for (summary, position) in summaries.iter().zip(&positions) {
+        assert_eq!(summary.id, position.id);
+        assert_eq!(summary.employer_name, position.employer_name);
+        assert_eq!(summary.end_date, position.end_date);
+    }
+
+    let full = db.get_position(summaries[0].id.unwrap()).unwrap().unwrap();
+    assert_eq!(full.core_responsibilities.len(), 16 * 500);
+}
+
+#[test]
+fn weekly_entries_are_reassigned_by_position_and_date_range() {
+    let (_dir, db) = temp_db();
+    let wrong = db.save_position(position("Pilbara Ports", date(2020, 2, 3), Some(date(2023, 6, 30)))).unwrap();
+    let right = db.save_position(position("Rio Tinto", date(2023, 7, 1), None)).unwrap();
+    for (position_id, week_ending) in [
+        (Some(wrong), date(2023, 6, 25)),
+        (Some(wrong), date(2023, 7, 9)),
+        (Some(wrong), date(2023, 7, 16)),
+        (None, date(2023, 7, 23)),
+    ] {
+        db.save_weekly_entry(weekly_entry(position_id, week_ending, 2000.0)).unwrap();
+    }
+
+    let moved = db.reassign_weekly_entries(Some(wrong), right, Some((date(2023, 7, 1), date(2023, 7, 31)))).unwrap();
+    assert_eq!(moved, 2);
+    assert_eq!(db.reassign_weekly_entries(None, right, None).unwrap(), 1);
+
+    let linked: Vec<(chrono::NaiveDate, Option<i64>)> = db.get_weekly_entries().unwrap()
+        .into_iter()
+        .map(|e| (e.week_ending, e.position_id))
+        .collect();
+    assert!(linked.contains(&(date(2023, 6, 25), Some(wrong))));
+    assert_eq!(linked.iter().filter(|(_, id)| *id == Some(right)).count(), 3);
+}
+
+#[test]
+fn position_industry_is_saved_trimmed_and_blank_means_none() {
+    let (_dir, db) = temp_db();
+    let mut held = position("Atlassian", date(2020, 7, 1), None);
+    held.industry = Some(" Technology ".to_string());
+    let id = db.save_position(held).unwrap();
+    assert_eq!(db.get_position(id).unwrap().unwrap().industry.as_deref(), Some("Technology"));
+
+    let mut held = db.get_position(id).unwrap().unwrap();
+    held.industry = Some("  ".to_string());
+    db.save_position(held).unwrap();
+    assert_eq!(db.get_position(id).unwrap().unwrap().industry, None);
+}
+
+#[test]
+fn position_notice_and_leave_loading_are_saved_on_insert_and_update() {
+    let (_dir, db) = temp_db();
+    let mut held = position("Atlassian", date(2020, 7, 1), None);
+    held.notice_period_weeks = Some(4.0);
+    let id = db.save_position(held).unwrap();
+
+    let mut saved = db.get_position(id).unwrap().unwrap();
+    assert_eq!(saved.notice_period_weeks, Some(4.0));
+    assert_eq!(saved.leave_loading_percent, None);
+    saved.leave_loading_percent = Some(17.5);
+    db.save_position(saved).unwrap();
+    assert_eq!(db.get_position(id).unwrap().unwrap().leave_loading_percent, Some(17.5));
+}
+
+#[test]
+fn weekly_entries_are_labelled_with_the_financial_year_of_their_period_end() {
+    let (_dir, db) = temp_db();
+    let mut entry = weekly_entry(None, date(2024, 7, 1), 2000.0);
+    entry.financial_year = "FY2023-24".to_string();
+    db.save_weekly_entry(entry).unwrap();
+
+    assert_eq!(db.get_weekly_entries().unwrap()[0].financial_year, "FY2024-25");
+}
+
+#[test]
+fn analysis_snapshots_are_kept_in_order_and_compared_later_minus_earlier() {
+    let (_dir, db) = temp_db();
+    let snapshot = |day: u32, total: f64, percentile: f64| AnalysisSnapshot {
+        id: None,
+        taken_at: date(2025, 3, day).and_hms_opt(9, 0, 0).unwrap().and_utc(),
+        total_compensation: total,
+        income_percentile: percentile,
+        loyalty_tax_annual: 4000.0,
+        loyalty_tax_cumulative: 12000.0,
+        projected_super: total * 0.115,
+    };
+    let later = db.save_analysis_snapshot(&snapshot(20, 110000.0, 72.0)).unwrap();
+    let earlier = db.save_analysis_snapshot(&snapshot(1, 100000.0, 68.0)).unwrap();
+
+    let saved = db.get_analysis_snapshots().unwrap();
+    assert_eq!(saved.iter().map(|s| s.id).collect::<Vec<_>>(), vec![Some(earlier), Some(later)]);
+
+    let later = db.get_analysis_snapshot(later).unwrap().unwrap();
+    let comparison = calculations::compare_snapshots(&later, &saved[0]);
+    assert_eq!(comparison.earlier.id, Some(earlier));
+    assert_eq!(comparison.days_between, 19);
+    assert_eq!(comparison.total_compensation_change, 10000.0);
+    assert_eq!(comparison.income_percentile_change, 4.0);
+    assert_eq!(comparison.loyalty_tax_cumulative_change, 0.0);
+    assert!((comparison.projected_super_change - 1150.0).abs() < 1e-6);
+}
+
+#[test]
+fn repair_json_columns_recovers_what_it_can_and_resets_the_rest() {
+    let (dir, db) = temp_db();
+    let acme = db.save_position(position("Acme", date(2022, 1, 10), None)).unwrap();
+    let globex = db.save_position(position("Globex", date(2023, 1, 10), None)).unwrap();
+    let record_id = db.save_compensation_record(salary_record(acme, 95000.0, date(2023, 7, 1))).unwrap();
+
+    let conn = rusqlite::Connection::open(dir.path().join("careerflow.db")).unwrap();
+    conn.execute("UPDATE positions SET tools_systems_skills = 'Excel, SAP' WHERE id = ?1", [acme]).unwrap();
+    conn.execute("UPDATE positions SET achievements = '[\"Shipped' WHERE id = ?1", [globex]).unwrap();
+    conn.execute(
+        "UPDATE compensation_records SET allowances = '{\"name\": \"Tool\", \"amount\": 20.0, \"frequency\": \"Weekly\", \"taxable\": true}' WHERE id = ?1",
+        [record_id],
+    )
+    .unwrap();
+    assert_eq!(db.list_positions().unwrap().errors.len(), 2);
+
+    let logs = db.repair_json_columns().unwrap();
+    assert_eq!(logs.len(), 3);
+    let skills = logs.iter().find(|l| l.column_name == "tools_systems_skills").unwrap();
+    assert!(skills.recovered);
+    assert_eq!(skills.original, "Excel, SAP");
+    let achievements = logs.iter().find(|l| l.column_name == "achievements").unwrap();
+    assert!(!achievements.recovered);
+    assert_eq!(achievements.repaired_to, "[]");
+
+    let positions = db.get_positions().unwrap();
+    let acme_position = positions.iter().find(|p| p.id == Some(acme)).unwrap();
+    assert_eq!(acme_position.tools_systems_skills, vec!["Excel", "SAP"]);
+    assert_eq!(db.get_compensation_records(acme).unwrap()[0].allowances.len(), 1);
+    assert!(db.repair_json_columns().unwrap().is_empty());
+}
+
+#[test]
+fn a_corrupt_row_is_listed_separately_instead_of_failing_the_list() {
+    let (dir, db) = temp_db();
+    let acme = db.save_position(position("Acme", date(2022, 1, 10), None)).unwrap();
+    let globex = db.save_position(position("Globex", date(2023, 1, 10), None)).unwrap();
+    let conn = rusqlite::Connection::open(dir.path().join("careerflow.db")).unwrap();
+    conn.execute("UPDATE positions SET start_date = 'last March' WHERE id = ?1", [globex]).unwrap();
+
+    let list = db.list_positions().unwrap();
+    assert_eq!(list.rows.len(), 1);
+    assert_eq!(list.rows[0].id, Some(acme));
+    assert_eq!(list.errors.len(), 1);
+    assert_eq!(list.errors[0].table_name, "positions");
+    assert_eq!(list.errors[0].row_id, globex);
+    // Everything that works from the whole list refuses rather than quietly leave the row out
+    assert!(db.get_positions().is_err());
+
+    // Exporting would lose the row, so it refuses until it's repaired
+    assert!(backup::export_all_data(&db).is_err());
+}
+
+#[test]
+fn position_weekly_stats_total_one_positions_entries_and_count_gaps() {
+    let (_dir, db) = temp_db();
+    let position_id = db.save_position(position("Acme", date(2024, 7, 1), None)).unwrap();
+    let other_id = db.save_position(position("Other", date(2024, 7, 1), None)).unwrap();
+
+    let empty = db.get_position_weekly_stats(position_id).unwrap();
+    assert_eq!(empty.entry_count, 0);
+    assert_eq!(empty.first_week_ending, None);
+    assert_eq!(empty.gap_count, 0);
+    assert_eq!(empty.average_weekly_hours, 0.0);
+
+    // The week ending 21 July is missing; the fortnight after 28 July follows straight on
+    for week_ending in [date(2024, 7, 7), date(2024, 7, 14), date(2024, 7, 28)] {
+        db.save_weekly_entry(weekly_entry(Some(position_id), week_ending, 2000.0)).unwrap();
+    }
+    let mut fortnight = weekly_entry(Some(position_id), date(2024, 8, 11), 2000.0);
+    fortnight.period_type = PayslipFrequency::Fortnightly;
+    fortnight.hours_overtime = 10.0;
+    db.save_weekly_entry(fortnight).unwrap();
+    db.save_weekly_entry(weekly_entry(Some(other_id), date(2024, 7, 21), 5000.0)).unwrap();
+
+    let stats = db.get_position_weekly_stats(position_id).unwrap();
+    assert_eq!(stats.entry_count, 4);
+    assert_eq!(stats.first_week_ending, Some(date(2024, 7, 7)));
+    assert_eq!(stats.last_week_ending, Some(date(2024, 8, 11)));
+    assert!((stats.weeks_logged - 5.0).abs() < 1e-9);
+    assert_eq!(stats.gap_count, 1);
+    assert!((stats.total_gross - 8000.0).abs() < 1e-9);
+    assert!((stats.total_tax_withheld - 2000.0).abs() < 1e-9);
+    assert!((stats.total_net - 6000.0).abs() < 1e-9);
+    assert!((stats.total_super - 920.0).abs() < 1e-9);
+    assert!((stats.average_weekly_hours - 162.0 / 5.0).abs() < 1e-9);
+    assert!((stats.overtime_share - 10.0 / 162.0).abs() < 1e-9);
+
+    let record = salary_record(position_id, 95000.0, date(2024, 7, 1));
+    assert_eq!(calculations::compensation_record_summary(&record).annual_gross, 95000.0);
+}
+
+#[test]
+fn tags_filter_positions_and_go_when_the_tag_or_record_does() {
+    let (_dir, db) = temp_db();
+    let fifo = db.save_tag(Tag { id: None, name: "FIFO".to_string() }).unwrap();
+    let government = db.save_tag(Tag { id: None, name: "Government".to_string() }).unwrap();
+    let site = db.save_position(position("Site Co", date(2018, 2, 5), Some(date(2021, 6, 30)))).unwrap();
+    let office = db.save_position(position("Office Co", date(2021, 7, 5), None)).unwrap();
+    let tagged = |tag_id, entity_id| EntityTag { tag_id, entity_type: TagEntityType::Position, entity_id };
+
+    let duplicate = Tag { id: None, name: " fifo ".to_string() };
+    assert!(validation::validate_tag(&duplicate, &db.get_tags().unwrap()).is_err());
+    db.save_tag(Tag { id: Some(government), name: "Public sector".to_string() }).unwrap();
+    let names: Vec<String> = db.get_tags().unwrap().into_iter().map(|t| t.name).collect();
+    assert_eq!(names, vec!["FIFO", "Public sector"]);
+
+    db.assign_tag(&tagged(fifo, site)).unwrap();
+    db.assign_tag(&tagged(fifo, site)).unwrap();
+    db.assign_tag(&tagged(government, office)).unwrap();
+    db.assign_tag(&EntityTag { tag_id: fifo, entity_type: TagEntityType::WeeklyEntry, entity_id: site }).unwrap();
+    assert_eq!(db.get_entity_tags().unwrap().len(), 3);
+
+    let positions = db.get_positions().unwrap();
+    let entity_tags = db.get_entity_tags().unwrap();
+    let fifo_only = calculations::filter_positions_by_tags(&positions, &entity_tags, &[fifo]);
+    assert_eq!(fifo_only.iter().map(|p| p.id).collect::<Vec<_>>(), vec![Some(site)]);
+    assert_eq!(calculations::filter_positions_by_tags(&positions, &entity_tags, &[fifo, government]).len(), 2);
+    assert_eq!(calculations::filter_positions_by_tags(&positions, &entity_tags, &[]).len(), 2);
+    let site_only: Vec<Position> = positions.iter().filter(|p| p.id == Some(site)).cloned().collect();
+    let today = date(2024, 6, 1);
+    assert_eq!(calculations::calculate_loyalty_tax(&fifo_only, today), calculations::calculate_loyalty_tax(&site_only, today));
+
+    db.unassign_tag(&tagged(government, office)).unwrap();
+    assert_eq!(db.get_entity_tags().unwrap().len(), 2);
+
+    // Deleting a position takes its tags with it, but only those on the position
+    db.delete_position(site).unwrap();
+    let left = db.get_entity_tags().unwrap();
+    assert_eq!(left, vec![EntityTag { tag_id: fifo, entity_type: TagEntityType::WeeklyEntry, entity_id: site }]);
+
+    db.delete_tag(fifo).unwrap();
+    assert!(db.get_entity_tags().unwrap().is_empty());
+    assert_eq!(db.get_tags().unwrap().len(), 1);
+}
+
+#[test]
+fn archiving_a_position_takes_it_out_of_the_analyses() {
+    let (_dir, db) = temp_db();
+    let mut uni = position("Campus Bar", date(2008, 2, 4), Some(date(2010, 11, 26)));
+    uni.employment_type = EmploymentType::Casual;
+    uni.seniority_level = SeniorityLevel::Entry;
+    let uni = db.save_position(uni).unwrap();
+    db.save_position(position("Acme", date(2015, 1, 12), Some(date(2019, 12, 20)))).unwrap();
+    let mut lead = position("Bigger Co", date(2020, 1, 13), None);
+    lead.seniority_level = SeniorityLevel::Senior;
+    db.save_position(lead).unwrap();
+    let profile = Some(profile(date(1989, 5, 1), true));
+    let today = date(2024, 6, 1);
+
+    let before = calculations::generate_resume_export(&calculations::analysed_positions(&db.get_positions().unwrap(), false), &profile, today);
+    db.set_position_archived(uni, true).unwrap();
+    let positions = db.get_positions().unwrap();
+    let after = calculations::generate_resume_export(&calculations::analysed_positions(&positions, false), &profile, today);
+
+    // About three years of casual work drop out, and the growth is measured from the first career role
+    assert!((before.profile_summary.experience_years - after.profile_summary.experience_years - 2.8).abs() < 0.05);
+    assert_ne!(before.compensation_summary.average_annual_increase, after.compensation_summary.average_annual_increase);
+    assert_eq!(after.career_timeline.len(), 2);
+    assert_eq!(calculations::generate_resume_export(&calculations::analysed_positions(&positions, true), &profile, today), before);
+
+    // Still listed, flagged
+    assert_eq!(positions.len(), 3);
+    assert_eq!(positions.iter().filter(|p| p.archived).map(|p| p.id).collect::<Vec<_>>(), vec![Some(uni)]);
+
+    db.set_position_archived(uni, false).unwrap();
+    let restored = calculations::generate_resume_export(&calculations::analysed_positions(&db.get_positions().unwrap(), false), &profile, today);
+    assert_eq!(restored, before);
+}
+
+#[test]
+fn archived_positions_take_their_records_and_pay_entries_out_of_the_analyses() {
+    let (_dir, db) = temp_db();
+    let uni = db.save_position(position("Campus Bar", date(2008, 2, 4), Some(date(2010, 11, 26)))).unwrap();
+    let acme = db.save_position(position("Acme", date(2015, 1, 12), None)).unwrap();
+    db.save_compensation_record(salary_record(uni, 30000.0, date(2008, 2, 4))).unwrap();
+    db.save_compensation_record(salary_record(acme, 95000.0, date(2015, 1, 12))).unwrap();
+    db.save_weekly_entry(weekly_entry(Some(uni), date(2010, 11, 21), 600.0)).unwrap();
+    db.save_weekly_entry(weekly_entry(Some(acme), date(2024, 6, 2), 1800.0)).unwrap();
+    db.save_weekly_entry(weekly_entry(None, date(2024, 6, 2), 200.0)).unwrap();
+    db.set_position_archived(uni, true).unwrap();
+    let load = |include_archived| calculations::analysis_data(
+        &db.get_positions().unwrap(),
+        db.get_all_compensation_records().unwrap(),
+        db.get_weekly_entries().unwrap(),
+        &[],
+        &[],
+        include_archived,
+    );
+
+    let data = load(false);
+    assert_eq!(data.positions.iter().map(|p| p.id).collect::<Vec<_>>(), vec![Some(acme)]);
+    assert!(data.records.iter().all(|r| r.position_id == acme));
+    // The entry not tied to a position still counts
+    assert_eq!(data.weekly_entries.len(), 2);
+    assert!(data.weekly_entries.iter().all(|e| e.position_id != Some(uni)));
+
+    let everything = load(true);
+    assert_eq!(everything.positions.len(), 2);
+    assert_eq!(everything.records.len(), 2);
+    assert_eq!(everything.weekly_entries.len(), 3);
+}
+
+#[test]
+fn archived_positions_are_still_exported() {
+    let (_dir, db) = temp_db();
+    let archived = db.save_position(position("Campus Bar", date(2008, 2, 4), Some(date(2010, 11, 26)))).unwrap();
+    db.save_compensation_record(salary_record(archived, 30000.0, date(2008, 2, 4))).unwrap();
+    db.save_position(position("Acme", date(2015, 1, 12), None)).unwrap();
+    db.set_position_archived(archived, true).unwrap();
+
+    let data = backup::export_all_data(&db).unwrap();
+    let exported: Vec<(Option<i64>, bool)> = data.positions.iter().map(|p| (p.id, p.archived)).collect();
+    assert!(exported.contains(&(Some(archived), true)));
+    assert_eq!(data.positions.len(), 2);
+    assert_eq!(data.compensation_records.len(), 1);
+
+    let (_dir, copy) = temp_db();
+    backup::import_all_data(&copy, data, date(2025, 6, 2)).unwrap();
+    let flags: Vec<bool> = copy.get_positions().unwrap().iter().map(|p| p.archived).collect();
+    assert_eq!(flags.iter().filter(|&&a| a).count(), 1);
+}
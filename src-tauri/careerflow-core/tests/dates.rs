@@ -0,0 +1,67 @@
+mod common;
+
+use careerflow_core::dates::{self, FinancialYear};
+use careerflow_core::models::PayslipFrequency;
+use chrono::Weekday;
+use common::*;
+
+const WEEKDAYS: [Weekday; 7] = [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri, Weekday::Sat, Weekday::Sun];
+
+#[test]
+fn a_financial_year_has_53_weeks_on_the_days_it_starts_and_ends() {
+    // Each year's 53-week days: the weekday of 1 July, and of 30 June too in a leap year
+    let years = [
+        ("FY2015-16", vec![Weekday::Wed, Weekday::Thu]),
+        ("FY2016-17", vec![Weekday::Fri]),
+        ("FY2019-20", vec![Weekday::Mon, Weekday::Tue]),
+        ("FY2023-24", vec![Weekday::Sat, Weekday::Sun]),
+        ("FY2024-25", vec![Weekday::Mon]),
+    ];
+    for (label, long_days) in years {
+        let fy = FinancialYear::parse(label).unwrap();
+        for day in WEEKDAYS {
+            let expected = if long_days.contains(&day) { 53 } else { 52 };
+            assert_eq!(dates::weeks_in_financial_year(&fy, day), expected, "{} {:?}", label, day);
+        }
+    }
+}
+
+#[test]
+fn pay_periods_in_a_year_follow_the_cadence_of_any_known_period_end() {
+    let fy2015 = FinancialYear::parse("FY2015-16").unwrap();
+    let fy2024 = FinancialYear::parse("FY2024-25").unwrap();
+    let cases = [
+        // A 365-day year only fits 27 fortnights when one ends on 1 July
+        (&fy2024, PayslipFrequency::Fortnightly, date(2024, 7, 1), 27),
+        (&fy2024, PayslipFrequency::Fortnightly, date(2024, 7, 2), 26),
+        (&fy2024, PayslipFrequency::Fortnightly, date(2024, 7, 14), 26),
+        (&fy2024, PayslipFrequency::Fortnightly, date(2025, 6, 30), 27),
+        // A leap year also fits them when one ends on 2 July, and the anchor can be any year
+        (&fy2015, PayslipFrequency::Fortnightly, date(2015, 7, 2), 27),
+        (&fy2015, PayslipFrequency::Fortnightly, date(2015, 6, 18), 27),
+        (&fy2015, PayslipFrequency::Fortnightly, date(2015, 7, 3), 26),
+        (&fy2015, PayslipFrequency::Fortnightly, date(2019, 7, 4), 26),
+        (&fy2015, PayslipFrequency::Weekly, date(2016, 6, 29), 53),
+        (&fy2015, PayslipFrequency::Weekly, date(2016, 6, 24), 52),
+        (&fy2024, PayslipFrequency::Monthly, date(2024, 7, 31), 12),
+    ];
+    for (fy, frequency, anchor, expected) in cases {
+        assert_eq!(dates::pay_periods_in_fy(fy, &frequency, anchor), expected, "{} {:?} {}", fy.label(), frequency, anchor);
+    }
+}
+
+#[test]
+fn age_counts_a_year_only_once_the_birthday_is_reached() {
+    let cases = [
+        (date(1990, 5, 20), date(2025, 5, 19), 34),
+        (date(1990, 5, 20), date(2025, 5, 20), 35),
+        (date(1990, 5, 20), date(2025, 12, 31), 35),
+        // A 29 February birthday comes round on 1 March in other years
+        (date(2000, 2, 29), date(2025, 2, 28), 24),
+        (date(2000, 2, 29), date(2025, 3, 1), 25),
+        (date(2000, 2, 29), date(2024, 2, 29), 24),
+    ];
+    for (dob, as_of, expected) in cases {
+        assert_eq!(dates::age_on(dob, as_of), expected, "born {} on {}", dob, as_of);
+    }
+}
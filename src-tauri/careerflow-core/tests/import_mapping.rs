@@ -0,0 +1,124 @@
+mod common;
+
+use careerflow_core::import_mapping;
+use careerflow_core::models::*;
+use common::*;
+
+fn preset(target: ImportTarget) -> ImportMapping {
+    import_mapping::presets().into_iter().find(|m| m.target == target).unwrap()
+}
+
+#[test]
+fn presets_are_valid_mappings() {
+    for mapping in import_mapping::presets() {
+        careerflow_core::validation::validate_import_mapping(&mapping).unwrap();
+    }
+}
+
+#[test]
+fn job_history_imports_positions_after_a_clean_dry_run() {
+    let (_dir, db) = temp_db();
+    let csv = "\
+Company,Title,Type,Start,End,Skills
+Pilbara Ports,Maintenance Planner,contract,3 Feb 2020,30/06/2022,SAP; Excel
+Rio Tinto,Senior Planner,,Jul 2022,,
+";
+    let mapping = preset(ImportTarget::Positions);
+
+    let preview = import_mapping::import_with_mapping(&db, csv, &mapping, true).unwrap();
+    assert!(preview.errors.is_empty(), "{:?}", preview.errors);
+    assert_eq!(preview.imported_count, 0);
+    assert!(db.get_positions().unwrap().is_empty());
+
+    let first = &preview.positions[0];
+    assert!(matches!(first.employment_type, EmploymentType::Contract));
+    assert_eq!(first.start_date, date(2020, 2, 3));
+    assert_eq!(first.tools_systems_skills, vec!["SAP", "Excel"]);
+    assert_eq!(preview.positions[1].start_date, date(2022, 7, 1));
+    assert!(preview.positions[1].end_date.is_none());
+
+    let result = import_mapping::import_with_mapping(&db, csv, &mapping, false).unwrap();
+    assert_eq!(result.imported_count, 2);
+    assert_eq!(db.get_positions().unwrap().len(), 2);
+}
+
+#[test]
+fn a_save_failing_partway_leaves_nothing_imported() {
+    let (dir, db) = temp_db();
+    let conn = rusqlite::Connection::open(dir.path().join("careerflow.db")).unwrap();
+    conn.execute_batch(
+        "CREATE TRIGGER refuse_rio BEFORE INSERT ON positions WHEN NEW.employer_name = 'Rio Tinto'
+         BEGIN SELECT RAISE(ABORT, 'disk full'); END;",
+    )
+    .unwrap();
+    let csv = "\
+Company,Title,Type,Start,End,Skills
+Pilbara Ports,Maintenance Planner,contract,3 Feb 2020,30/06/2022,
+Rio Tinto,Senior Planner,,Jul 2022,,
+";
+
+    let error = import_mapping::import_with_mapping(&db, csv, &preset(ImportTarget::Positions), false).unwrap_err();
+    assert!(error.contains("disk full"));
+    assert!(db.get_positions().unwrap().is_empty());
+}
+
+#[test]
+fn cell_errors_name_the_line_and_column_and_block_the_import() {
+    let (_dir, db) = temp_db();
+    let mut held = position("Pilbara Ports", date(2020, 2, 3), None);
+    held.id = Some(db.save_position(held.clone()).unwrap());
+
+    let csv = "\
+Company,Date,Salary,Hours,Super
+Pilbara Ports,01/07/2023,\"$95,000\",38 hrs,11%
+Pilbara Ports,sometime,lots,38,
+Fortescue,01/07/2024,105k,,
+";
+    let result = import_mapping::import_with_mapping(&db, csv, &preset(ImportTarget::CompensationRecords), false).unwrap();
+
+    assert_eq!(result.rows, 3);
+    assert_eq!(result.imported_count, 0);
+    assert!(db.get_all_compensation_records().unwrap().is_empty());
+
+    let record = &result.compensation_records[0];
+    assert_eq!(record.position_id, held.id.unwrap());
+    assert_eq!(record.base_rate, 95000.0);
+    assert_eq!(record.super_contributions.contribution_rate, 11.0);
+
+    let cells: Vec<(usize, Option<&str>)> = result.errors.iter()
+        .map(|e| (e.line, e.column.as_deref()))
+        .collect();
+    assert_eq!(cells, vec![(3, Some("Date")), (3, Some("Salary")), (4, None)]);
+    assert_eq!(result.errors[0].value.as_deref(), Some("sometime"));
+    assert!(result.errors[2].message.contains("Fortescue"));
+}
+
+#[test]
+fn payroll_export_derives_net_pay_and_financial_year() {
+    let (_dir, db) = temp_db();
+    let mut held = position("Pilbara Ports", date(2020, 2, 3), None);
+    held.id = Some(db.save_position(held.clone()).unwrap());
+
+    let csv = "\
+Employer,Period Ending,Pay Frequency,Gross,Tax,Ordinary Hours
+pilbara ports,14/07/2024,fortnightly,\"$4,200.00\",\"$1,010.00\",76 hrs
+";
+    let result = import_mapping::import_with_mapping(&db, csv, &preset(ImportTarget::WeeklyEntries), false).unwrap();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+
+    let entries = db.get_weekly_entries().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].position_id, held.id);
+    assert_eq!(entries[0].financial_year, "FY2024-25");
+    assert!(matches!(entries[0].period_type, PayslipFrequency::Fortnightly));
+    assert!((entries[0].net_pay - 3190.0).abs() < 1e-9);
+    assert_eq!(entries[0].hours_ordinary, 76.0);
+}
+
+#[test]
+fn a_file_without_a_required_column_is_rejected() {
+    let (_dir, db) = temp_db();
+    let error = import_mapping::import_with_mapping(&db, "Company,Title\nA,B\n", &preset(ImportTarget::Positions), true)
+        .unwrap_err();
+    assert!(error.contains("Start"));
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use careerflow_core::in_flight::InFlight;
+
+// A single-threaded runtime only finishes if waiting callers yield instead of blocking the thread
+#[tokio::test]
+async fn concurrent_identical_calls_share_one_computation() {
+    let in_flight = Arc::new(InFlight::<Result<u64, String>>::new());
+    let executions = Arc::new(AtomicUsize::new(0));
+
+    let callers: Vec<_> = (0..10)
+        .map(|_| {
+            let in_flight = Arc::clone(&in_flight);
+            let executions = Arc::clone(&executions);
+            tokio::spawn(async move {
+                in_flight
+                    .run("7:2025-03-01", || async {
+                        executions.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(200)).await;
+                        Ok(42)
+                    })
+                    .await
+            })
+        })
+        .collect();
+
+    for caller in callers {
+        assert_eq!(caller.await.unwrap(), Ok(42));
+    }
+    assert_eq!(executions.load(Ordering::SeqCst), 1);
+
+    // Finished results aren't kept, and a different key never shares
+    assert_eq!(in_flight.run("7:2025-03-01", || async { Ok(43) }).await, Ok(43));
+    assert_eq!(in_flight.run("8:2025-03-01", || async { Ok(44) }).await, Ok(44));
+}
+
+#[test]
+fn data_version_moves_on_every_write() {
+    let (_dir, db) = common::temp_db();
+    let before = db.data_version().unwrap();
+    db.save_setting("reminder_settings", &serde_json::json!({})).unwrap();
+    assert!(db.data_version().unwrap() > before);
+}
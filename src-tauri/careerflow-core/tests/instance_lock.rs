@@ -0,0 +1,52 @@
+mod common;
+
+use careerflow_core::error::AppError;
+use careerflow_core::instance_lock;
+use careerflow_core::Database;
+use common::*;
+
+#[test]
+fn a_second_instance_waits_until_the_lock_is_released() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("careerflow.db");
+
+    let first = instance_lock::acquire(&path).unwrap();
+    assert!(first.is_some());
+    assert!(instance_lock::lock_path(&path).exists());
+    assert!(instance_lock::acquire(&path).unwrap().is_none());
+
+    drop(first);
+    assert!(!instance_lock::lock_path(&path).exists());
+    assert!(instance_lock::acquire(&path).unwrap().is_some());
+}
+
+#[test]
+fn a_lock_left_by_a_crashed_process_is_taken_over() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("careerflow.db");
+
+    let mut child = std::process::Command::new("true").spawn().unwrap();
+    let dead_pid = child.id();
+    child.wait().unwrap();
+    assert!(!instance_lock::process_is_running(dead_pid));
+    std::fs::write(instance_lock::lock_path(&path), format!("{}\n", dead_pid)).unwrap();
+
+    let lock = instance_lock::acquire(&path).unwrap();
+    assert!(lock.is_some());
+    let holder = std::fs::read_to_string(instance_lock::lock_path(&path)).unwrap();
+    assert_eq!(holder.trim(), std::process::id().to_string());
+}
+
+#[test]
+fn a_read_only_database_reads_but_refuses_writes() {
+    let (dir, db) = temp_db();
+    db.save_position(position("Pilbara Ports", date(2020, 2, 3), None)).unwrap();
+
+    let read_only = Database::open_read_only(dir.path().join("careerflow.db")).unwrap();
+    assert!(read_only.is_read_only());
+    assert_eq!(read_only.get_positions().unwrap().len(), 1);
+
+    let error = read_only.save_position(position("Rio Tinto", date(2022, 7, 1), None)).unwrap_err();
+    assert!(error.to_string().contains(&AppError::ReadOnly.to_string()));
+    assert_eq!(db.get_positions().unwrap().len(), 1);
+}
@@ -0,0 +1,212 @@
+mod common;
+
+use careerflow_core::database::{self, Migration};
+use careerflow_core::models::*;
+use careerflow_core::Database;
+use common::*;
+
+fn with_migration(migration: Migration) -> Vec<Migration> {
+    let mut migrations = database::MIGRATIONS.to_vec();
+    migrations.push(migration);
+    migrations
+}
+
+fn next_version() -> u32 {
+    database::MIGRATIONS.last().unwrap().version + 1
+}
+
+fn columns(path: &std::path::Path, table: &str) -> Vec<String> {
+    let conn = rusqlite::Connection::open(path).unwrap();
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table)).unwrap();
+    let names = stmt.query_map([], |row| row.get(1)).unwrap();
+    names.map(|name| name.unwrap()).collect()
+}
+
+#[test]
+fn failed_migration_restores_the_previous_database() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("careerflow.db");
+    {
+        let db = Database::new(path.clone()).unwrap();
+        db.save_position(position("Pilbara Ports", date(2020, 2, 3), None)).unwrap();
+    }
+
+    // Applies its first statement, then fails
+    let broken = with_migration(Migration {
+        version: next_version(),
+        name: "broken",
+        apply: |conn| conn.execute_batch("ALTER TABLE positions ADD COLUMN half_done TEXT; NOT VALID SQL;"),
+    });
+    let db = Database::open_with_migrations(path.clone(), &broken).unwrap();
+
+    let status = db.status();
+    assert_eq!(status.state, DatabaseState::MigrationFailed);
+    assert_eq!(status.schema_version, next_version() - 1);
+    assert!(status.error.unwrap().contains("broken"));
+    assert!(!columns(&path, "positions").contains(&"half_done".to_string()));
+
+    // Data is intact and the database still takes writes at the old version
+    assert_eq!(db.get_positions().unwrap().len(), 1);
+    db.save_position(position("Rio Tinto", date(2022, 7, 1), None)).unwrap();
+    assert_eq!(db.get_positions().unwrap().len(), 2);
+    drop(db);
+
+    let reopened = Database::new(path).unwrap();
+    assert_eq!(reopened.status().state, DatabaseState::Ready);
+    assert_eq!(reopened.get_positions().unwrap().len(), 2);
+}
+
+#[test]
+fn pre_migrate_copy_is_removed_after_the_first_write() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("careerflow.db");
+    drop(Database::new(path.clone()).unwrap());
+
+    let migrations = with_migration(Migration {
+        version: next_version(),
+        name: "add notes table",
+        apply: |conn| conn.execute_batch("CREATE TABLE notes (id INTEGER PRIMARY KEY, body TEXT NOT NULL)"),
+    });
+    let db = Database::open_with_migrations(path.clone(), &migrations).unwrap();
+    let copy = database::pre_migrate_path(&path);
+
+    assert_eq!(db.status().state, DatabaseState::Ready);
+    assert_eq!(db.status().schema_version, next_version());
+    assert!(copy.exists());
+
+    db.get_positions().unwrap();
+    assert!(copy.exists(), "reads keep the copy");
+
+    db.save_position(position("Pilbara Ports", date(2020, 2, 3), None)).unwrap();
+    assert!(!copy.exists());
+}
+
+#[test]
+fn a_new_database_is_not_copied() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("careerflow.db");
+
+    let db = Database::new(path.clone()).unwrap();
+    assert_eq!(db.status().schema_version, database::MIGRATIONS.last().unwrap().version);
+    assert!(!database::pre_migrate_path(&path).exists());
+}
+
+#[test]
+fn an_older_copy_than_the_last_clean_shutdown_is_flagged() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("careerflow.db");
+    let older = dir.path().join("older.db");
+    {
+        let db = Database::new(path.clone()).unwrap();
+        db.save_position(position("Pilbara Ports", date(2020, 2, 3), None)).unwrap();
+        db.backup_to(&older).unwrap();
+        db.save_position(position("Rio Tinto", date(2022, 7, 1), None)).unwrap();
+        db.save_setting("example", &1).unwrap();
+        db.record_clean_shutdown().unwrap();
+    }
+
+    // Reopening what was saved is not a rollback
+    let db = Database::new(path.clone()).unwrap();
+    assert!(db.status().possible_data_rollback.is_none());
+    let summary = db.get_last_modified_summary().unwrap();
+    let positions = summary.iter().find(|t| t.table_name == "positions").unwrap();
+    assert_eq!(positions.write_count, 2);
+    assert!(positions.last_modified.is_some());
+    drop(db);
+
+    // A sync tool puts back the copy from before the second position
+    std::fs::copy(&older, &path).unwrap();
+    let db = Database::new(path).unwrap();
+    let rollback = db.status().possible_data_rollback.unwrap();
+    let tables: Vec<&str> = rollback.tables.iter().map(|t| t.table_name.as_str()).collect();
+    assert_eq!(tables, vec!["positions", "settings"]);
+    assert_eq!(rollback.tables[0].writes_missing, 1);
+    assert_eq!(rollback.tables[1].actual_write_count, 0);
+}
+
+#[test]
+fn every_tracked_table_counts_its_writes() {
+    let (dir, db) = temp_db();
+    let conn = rusqlite::Connection::open(dir.path().join("careerflow.db")).unwrap();
+    for table in database::TRACKED_TABLES {
+        let triggers: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type = 'trigger' AND tbl_name = ?1 AND name LIKE 'track_%'",
+                [table],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(triggers, 3, "{}", table);
+    }
+    drop(conn);
+
+    // A table added after write tracking rolls back like the rest
+    let older = dir.path().join("older.db");
+    db.backup_to(&older).unwrap();
+    db.save_tag(Tag { id: None, name: "FIFO".to_string() }).unwrap();
+    db.record_clean_shutdown().unwrap();
+    let path = dir.path().join("careerflow.db");
+    drop(db);
+    std::fs::copy(&older, &path).unwrap();
+
+    let rollback = Database::new(path).unwrap().status().possible_data_rollback.unwrap();
+    let tables: Vec<&str> = rollback.tables.iter().map(|t| t.table_name.as_str()).collect();
+    assert_eq!(tables, vec!["tags"]);
+}
+
+#[test]
+fn standard_hours_from_before_reviews_were_tracked_are_flagged() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("careerflow.db");
+    {
+        // The profile table as the first release created it, with the column already there
+        let conn = rusqlite::Connection::open(&path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE user_profile (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                first_name TEXT NOT NULL,
+                last_name TEXT NOT NULL,
+                date_of_birth TEXT NOT NULL,
+                state TEXT NOT NULL,
+                industry TEXT NOT NULL,
+                highest_qualification TEXT NOT NULL,
+                employment_type_preference TEXT NOT NULL,
+                fifo_tolerance TEXT NOT NULL,
+                travel_tolerance TEXT NOT NULL,
+                overtime_appetite TEXT NOT NULL,
+                privacy_acknowledged BOOLEAN NOT NULL DEFAULT FALSE,
+                disclaimer_acknowledged BOOLEAN NOT NULL DEFAULT FALSE,
+                standard_weekly_hours REAL NOT NULL DEFAULT 38.0,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+            INSERT INTO user_profile (first_name, last_name, date_of_birth, state, industry, highest_qualification,
+                employment_type_preference, fifo_tolerance, travel_tolerance, overtime_appetite, created_at, updated_at)
+            VALUES ('Sam', 'Lee', '1990-05-01', '\"WA\"', 'Mining', '\"Bachelor\"', '\"FullTime\"', '\"None\"', '\"None\"', '\"None\"',
+                '2023-01-01T00:00:00Z', '2023-01-01T00:00:00Z');
+            INSERT INTO user_profile (first_name, last_name, date_of_birth, state, industry, highest_qualification,
+                employment_type_preference, fifo_tolerance, travel_tolerance, overtime_appetite, standard_weekly_hours,
+                created_at, updated_at)
+            VALUES ('Alex', 'Ng', '1985-02-11', '\"VIC\"', 'Health', '\"Bachelor\"', '\"FullTime\"', '\"None\"', '\"None\"', '\"None\"', 40.0,
+                '2023-01-01T00:00:00Z', '2023-01-01T00:00:00Z');",
+        )
+        .unwrap();
+    }
+
+    let db = Database::new(path.clone()).unwrap();
+    let hours: Vec<BackfillWarning> = db.get_default_backfill_warnings().unwrap()
+        .into_iter()
+        .filter(|w| w.column_name == "standard_weekly_hours")
+        .collect();
+    assert_eq!(hours.len(), 1);
+    assert_eq!(hours[0].row_id, 1);
+    assert!(hours[0].message.contains("38"));
+    drop(db);
+
+    // Only once: a profile saved with 38 hours later on was entered by the user
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    conn.execute("UPDATE user_profile SET standard_weekly_hours = 38.0 WHERE id = 2", []).unwrap();
+    drop(conn);
+    let reopened = Database::new(path).unwrap();
+    assert_eq!(reopened.get_default_backfill_warnings().unwrap().iter().filter(|w| w.column_name == "standard_weekly_hours").count(), 1);
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use careerflow_core::reminders;
+use careerflow_core::models::*;
+use common::*;
+
+#[test]
+fn reminders_come_from_probation_contract_ends_and_anniversaries() {
+    let (_dir, db) = temp_db();
+    let today = date(2025, 3, 1);
+
+    let mut contract = position("Fortescue", date(2024, 12, 1), Some(date(2025, 4, 15)));
+    contract.employment_type = EmploymentType::Contract;
+    contract.probation_months = Some(3);
+    let contract_id = db.save_position(contract).unwrap();
+    // Anniversary on 10 March; a finished role has none
+    let permanent_id = db.save_position(position("BHP", date(2019, 3, 10), None)).unwrap();
+    db.save_position(position("Rio Tinto", date(2015, 3, 20), Some(date(2019, 3, 1)))).unwrap();
+
+    let settings = ReminderSettings::default();
+    let positions = db.get_positions().unwrap();
+    let found = reminders::upcoming_reminders(&positions, &settings, &[], today);
+    let summary: Vec<(ReminderKind, i64, chrono::NaiveDate)> = found.iter().map(|r| (r.kind, r.position_id, r.due_date)).collect();
+    assert_eq!(summary, vec![
+        (ReminderKind::ProbationEnding, contract_id, date(2025, 3, 1)),
+        (ReminderKind::WorkAnniversary, permanent_id, date(2025, 3, 10)),
+        (ReminderKind::ContractEnding, contract_id, date(2025, 4, 15)),
+    ]);
+    assert_eq!(found[1].title, "6 years at BHP");
+
+    // A dismissal is kept against the same id the next time they're worked out
+    db.dismiss_reminder(&found[0].id).unwrap();
+    let dismissed = db.get_dismissed_reminders().unwrap();
+    let found = reminders::upcoming_reminders(&positions, &settings, &dismissed, today);
+    assert_eq!(found.len(), 2);
+
+    let narrow = ReminderSettings { horizon_days: 14 };
+    assert_eq!(reminders::upcoming_reminders(&positions, &narrow, &dismissed, today).len(), 1);
+}
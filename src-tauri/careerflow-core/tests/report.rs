@@ -0,0 +1,92 @@
+mod common;
+
+use careerflow_core::calculations;
+use careerflow_core::models::*;
+use careerflow_core::report;
+use common::*;
+
+// A 25-year career, a new role every year across five employers
+fn long_career() -> (Vec<Position>, Vec<CompensationRecord>) {
+    let employers = ["Pilbara Ports", "BHP", "Rio Tinto", "Fortescue", "Roy Hill"];
+    let mut positions = Vec::new();
+    let mut records = Vec::new();
+    for year in 0..25 {
+        let start = date(2000 + year, 7, 1);
+        let end = (year < 24).then(|| date(2001 + year, 6, 30));
+        let mut held = position(employers[year as usize / 5], start, end);
+        held.id = Some(year as i64 + 1);
+        positions.push(held);
+        records.push(salary_record(year as i64 + 1, 60000.0 + year as f64 * 3000.0, start));
+    }
+    (positions, records)
+}
+
+fn page_count(pdf: &str) -> usize {
+    pdf.matches("/Type /Page ").count()
+}
+
+#[test]
+fn a_long_career_runs_over_several_well_formed_pages() {
+    let (positions, records) = long_career();
+    let profile = profile(date(1980, 5, 20), true);
+    let analysis = calculations::calculate_earnings_analysis(&positions, &records, &Some(profile.clone()), &[], date(2025, 3, 1));
+    let loyalty_tax = calculations::calculate_loyalty_tax(&positions, date(2025, 3, 1));
+    assert_eq!(analysis.earnings_over_time.len(), 25);
+
+    let bytes = report::analysis_report_pdf(Some(&profile), &analysis, &loyalty_tax, &[], false, date(2025, 3, 1));
+    let pdf = String::from_utf8(bytes).unwrap();
+    assert!(pdf.starts_with("%PDF-1.4"));
+    assert!(pdf.ends_with("%%EOF\n"));
+    assert!(page_count(&pdf) >= 2);
+    assert!(pdf.contains(&format!("Page {} of {}", page_count(&pdf), page_count(&pdf))));
+    assert!(pdf.contains("Sam Citizen") && pdf.contains("20/05/1980"));
+
+    // Every cross-reference entry points at the object it names
+    let xref = pdf.rfind("xref\n").unwrap();
+    let entries: Vec<&str> = pdf[xref..].lines().skip(3).take_while(|l| l.ends_with(" n ")).collect();
+    for (i, entry) in entries.iter().enumerate() {
+        let offset: usize = entry[..10].parse().unwrap();
+        assert!(pdf[offset..].starts_with(&format!("{} 0 obj", i + 1)));
+    }
+
+    // Nothing is drawn below the footer or off the page
+    for operator in pdf.lines().filter(|l| l.starts_with("BT ")) {
+        let parts: Vec<&str> = operator.split_whitespace().collect();
+        let td = parts.iter().position(|p| *p == "Td").unwrap();
+        let (x, y): (f64, f64) = (parts[td - 2].parse().unwrap(), parts[td - 1].parse().unwrap());
+        assert!((0.0..595.0).contains(&x) && (30.0..842.0).contains(&y), "{}", operator);
+    }
+}
+
+#[test]
+fn redaction_leaves_out_the_name_and_date_of_birth() {
+    let (positions, records) = long_career();
+    let profile = profile(date(1980, 5, 20), true);
+    let analysis = calculations::calculate_earnings_analysis(&positions, &records, &Some(profile.clone()), &[], date(2025, 3, 1));
+    let loyalty_tax = calculations::calculate_loyalty_tax(&positions, date(2025, 3, 1));
+
+    let bytes = report::analysis_report_pdf(Some(&profile), &analysis, &loyalty_tax, &[], true, date(2025, 3, 1));
+    let pdf = String::from_utf8(bytes).unwrap();
+    assert!(!pdf.contains("Citizen"));
+    assert!(!pdf.contains("20/05/1980"));
+    assert!(pdf.contains("Roy Hill"));
+}
+
+#[test]
+fn the_html_report_is_self_contained() {
+    let (positions, records) = long_career();
+    let profile = profile(date(1980, 5, 20), true);
+    let analysis = calculations::calculate_earnings_analysis(&positions, &records, &Some(profile.clone()), &[], date(2025, 3, 1));
+    let loyalty_tax = calculations::calculate_loyalty_tax(&positions, date(2025, 3, 1));
+
+    let html = report::analysis_report_html(Some(&profile), &analysis, &loyalty_tax, &[], false, date(2025, 3, 1));
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<svg") && html.contains("<table>"));
+    assert!(html.contains("Sam Citizen") && html.contains("Roy Hill"));
+    for reference in ["http:", "https:", "src=", "href=", "url(", "@import", "<script", "<link"] {
+        assert!(!html.contains(reference), "{}", reference);
+    }
+
+    let redacted = report::analysis_report_html(Some(&profile), &analysis, &loyalty_tax, &[], true, date(2025, 3, 1));
+    assert!(!redacted.contains("Citizen") && !redacted.contains("20/05/1980"));
+}
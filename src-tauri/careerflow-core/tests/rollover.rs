@@ -0,0 +1,82 @@
+mod common;
+
+use careerflow_core::{calculations, rollover};
+use careerflow_core::dates::FinancialYear;
+use careerflow_core::models::*;
+use common::*;
+
+#[test]
+fn rollover_adds_placeholders_once_and_flags_an_old_super_rate() {
+    let (_dir, db) = temp_db();
+    let held = db.save_position(position("Pilbara Ports", date(2023, 7, 1), None)).unwrap();
+    db.save_position(position("Old Co", date(2020, 1, 6), Some(date(2023, 6, 30)))).unwrap();
+    db.save_compensation_record(salary_record(held, 90000.0, date(2023, 7, 1))).unwrap();
+    let new_fy = FinancialYear::parse("FY2025-26").unwrap();
+    let today = date(2025, 7, 3);
+
+    assert!(!rollover::rollover_status(&db, today).unwrap().done_for_current);
+    let report = rollover::run_fy_rollover(&db, &new_fy, today).unwrap();
+    let outcome = |report: &RolloverReport, step| report.items.iter()
+        .filter(|item| item.step == step)
+        .map(|item| item.outcome)
+        .collect::<Vec<_>>();
+
+    assert_eq!(outcome(&report, RolloverStep::YearlyPlaceholder), vec![RolloverOutcome::Done]);
+    assert_eq!(outcome(&report, RolloverStep::SuperRate), vec![RolloverOutcome::NeedsAttention]);
+    assert!(report.items.iter().any(|item| item.message.contains("FY2025-26 SG rate is 12%") && item.message.contains("11.5%")));
+    assert_eq!(outcome(&report, RolloverStep::MissingWeeks), vec![RolloverOutcome::NeedsAttention]);
+
+    let again = rollover::run_fy_rollover(&db, &new_fy, today).unwrap();
+    assert_eq!(outcome(&again, RolloverStep::YearlyPlaceholder), vec![RolloverOutcome::AlreadyDone]);
+    let entries = db.get_yearly_entries().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert!(entries[0].is_placeholder());
+    assert!(rollover::rollover_status(&db, today).unwrap().done_for_current);
+
+    // The empty placeholder isn't read as an income statement of nothing
+    db.save_weekly_entry(weekly_entry(Some(held), date(2025, 7, 6), 1800.0)).unwrap();
+    let summaries = calculations::calculate_financial_year_summaries(&db.get_weekly_entries().unwrap(), &entries, &[]);
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0].ato_gross_income, None);
+}
+
+#[test]
+fn tax_time_uses_the_payslips_once_the_rollover_adds_a_placeholder() {
+    let (_dir, db) = temp_db();
+    let held = db.save_position(position("Pilbara Ports", date(2023, 7, 1), None)).unwrap();
+    db.save_compensation_record(salary_record(held, 90000.0, date(2023, 7, 1))).unwrap();
+    let new_fy = FinancialYear::parse("FY2025-26").unwrap();
+    rollover::run_fy_rollover(&db, &new_fy, date(2025, 7, 3)).unwrap();
+    for week_ending in [date(2025, 7, 6), date(2025, 7, 13)] {
+        db.save_weekly_entry(weekly_entry(Some(held), week_ending, 1800.0)).unwrap();
+    }
+
+    let positions = db.get_positions().unwrap();
+    let records = db.get_all_compensation_records().unwrap();
+    let weekly = db.get_weekly_entries().unwrap();
+    let yearly = db.get_yearly_entries().unwrap();
+    assert!(yearly.iter().all(|e| e.is_placeholder()));
+
+    let summary = calculations::tax_time_summary(&new_fy, &positions, &records, &weekly, &yearly, &[]);
+    assert_eq!(summary.sources.len(), 1);
+    assert_eq!(summary.sources[0].method, TaxedIncomeMethod::Payslips);
+    assert_eq!(summary.total_gross_income, 3600.0);
+
+    let combined = calculations::calculate_combined_tax_position(&new_fy, &positions, &records, &weekly, &yearly, &[], false);
+    assert_eq!(combined.sources[0].method, TaxedIncomeMethod::Payslips);
+    assert_eq!(combined.total_gross_income, 3600.0);
+}
+
+#[test]
+fn rollover_stops_on_an_unreadable_yearly_entry_instead_of_adding_another() {
+    let (dir, db) = temp_db();
+    let held = db.save_position(position("Pilbara Ports", date(2023, 7, 1), None)).unwrap();
+    let new_fy = FinancialYear::parse("FY2025-26").unwrap();
+    rollover::run_fy_rollover(&db, &new_fy, date(2025, 7, 3)).unwrap();
+    let conn = rusqlite::Connection::open(dir.path().join("careerflow.db")).unwrap();
+    conn.execute("UPDATE yearly_income_entries SET allowances = '[\"Tool' WHERE position_id = ?1", [held]).unwrap();
+
+    assert!(rollover::run_fy_rollover(&db, &new_fy, date(2025, 7, 3)).is_err());
+    assert_eq!(db.list_yearly_entries().unwrap().errors.len(), 1);
+    assert!(db.list_yearly_entries().unwrap().rows.is_empty());
+}
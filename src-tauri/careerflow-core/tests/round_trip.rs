@@ -0,0 +1,208 @@
+mod common;
+
+use careerflow_core::{backup, tables, Database};
+use careerflow_core::models::*;
+use common::fixtures::*;
+use common::*;
+use serde_json::Value;
+
+// Saves one of every fixture and returns them as the database should give them back
+fn seed(db: &Database) -> DataExport {
+    db.save_user_profile(full_profile()).unwrap();
+    let mut position = full_position();
+    position.id = Some(db.save_position(position.clone()).unwrap());
+    let position_id = position.id.unwrap();
+    let mut award_rate = full_award_rate();
+    award_rate.id = Some(db.save_award_rate(award_rate.clone()).unwrap());
+    let mut record = full_compensation_record(position_id, award_rate.id.unwrap());
+    record.id = Some(db.save_compensation_record(record.clone()).unwrap());
+    let mut weekly = full_weekly_entry(position_id);
+    weekly.id = Some(db.save_weekly_entry(weekly.clone()).unwrap());
+    let mut yearly = full_yearly_entry(position_id);
+    yearly.id = Some(db.save_yearly_entry(yearly.clone()).unwrap());
+    let mut exchange_rate = full_exchange_rate();
+    exchange_rate.id = Some(db.save_exchange_rate(exchange_rate.clone()).unwrap());
+    let mut scenario = full_scenario(position_id, record.clone());
+    scenario.id = Some(db.save_scenario(scenario.clone()).unwrap());
+    let mut template = full_template(&record);
+    template.id = Some(db.save_template(template.clone()).unwrap());
+    let mut policy = full_insurance_policy();
+    policy.id = Some(db.save_insurance_policy(policy.clone()).unwrap());
+    let mut partner = full_partner_income();
+    partner.id = Some(db.save_partner_income_entry(partner.clone()).unwrap());
+    let mut tag = full_tag();
+    tag.id = Some(db.save_tag(tag.clone()).unwrap());
+    let tag_id = tag.id.unwrap();
+    let entity_tags = vec![
+        EntityTag { tag_id, entity_type: TagEntityType::Position, entity_id: position_id },
+        EntityTag { tag_id, entity_type: TagEntityType::YearlyEntry, entity_id: yearly.id.unwrap() },
+    ];
+    for entity_tag in &entity_tags {
+        db.assign_tag(entity_tag).unwrap();
+    }
+
+    DataExport {
+        metadata: None,
+        user_profile: Some(full_profile()),
+        positions: vec![position],
+        compensation_records: vec![record],
+        weekly_entries: vec![weekly],
+        yearly_entries: vec![yearly],
+        exchange_rates: vec![exchange_rate],
+        award_rates: vec![award_rate],
+        scenarios: vec![scenario],
+        templates: vec![template],
+        insurance_policies: vec![policy],
+        partner_income_entries: vec![partner],
+        tags: vec![tag],
+        entity_tags,
+        version: "1.0.0".to_string(),
+        checksum: None,
+    }
+}
+
+// The database stamps these when a row is first saved, so they can't survive a save; the
+// profile's id is whatever its table gives it
+fn without_saved_stamps(mut data: DataExport) -> DataExport {
+    data.metadata = None;
+    data.checksum = None;
+    if let Some(profile) = data.user_profile.as_mut() {
+        profile.id = None;
+        profile.created_at = timestamp();
+        profile.updated_at = timestamp();
+    }
+    for position in &mut data.positions {
+        position.created_at = timestamp();
+        position.updated_at = timestamp();
+    }
+    for record in &mut data.compensation_records {
+        record.created_at = timestamp();
+    }
+    for entry in &mut data.weekly_entries {
+        entry.created_at = timestamp();
+    }
+    for entry in &mut data.yearly_entries {
+        entry.created_at = timestamp();
+    }
+    data
+}
+
+#[test]
+fn every_field_survives_a_save_and_load() {
+    let (_dir, db) = temp_db();
+    let expected = seed(&db);
+
+    let profile = db.get_user_profile().unwrap().unwrap();
+    assert!(profile.id.is_some());
+    assert_eq!(without_saved_stamps(DataExport { user_profile: Some(profile), ..expected.clone() }), expected);
+
+    let position_id = expected.positions[0].id.unwrap();
+    let loaded = DataExport {
+        user_profile: expected.user_profile.clone(),
+        positions: db.get_positions().unwrap(),
+        compensation_records: db.get_compensation_records(position_id).unwrap(),
+        weekly_entries: db.get_weekly_entries().unwrap(),
+        yearly_entries: db.get_yearly_entries().unwrap(),
+        exchange_rates: db.get_exchange_rates().unwrap(),
+        award_rates: db.get_award_rates().unwrap(),
+        scenarios: db.get_scenarios().unwrap(),
+        templates: db.get_templates().unwrap(),
+        insurance_policies: db.get_insurance_policies().unwrap(),
+        partner_income_entries: db.get_partner_income_entries().unwrap(),
+        tags: db.get_tags().unwrap(),
+        entity_tags: db.get_entity_tags().unwrap(),
+        ..expected.clone()
+    };
+    let loaded = without_saved_stamps(loaded);
+    assert_eq!(loaded.positions, expected.positions);
+    assert_eq!(loaded.compensation_records, expected.compensation_records);
+    assert_eq!(loaded.weekly_entries, expected.weekly_entries);
+    assert_eq!(loaded.yearly_entries, expected.yearly_entries);
+    assert_eq!(loaded.exchange_rates, expected.exchange_rates);
+    assert_eq!(loaded.award_rates, expected.award_rates);
+    assert_eq!(loaded.scenarios, expected.scenarios);
+    assert_eq!(loaded.templates, expected.templates);
+    assert_eq!(loaded.insurance_policies, expected.insurance_policies);
+    assert_eq!(loaded.partner_income_entries, expected.partner_income_entries);
+    assert_eq!(loaded.tags, expected.tags);
+    assert_eq!(loaded.entity_tags, expected.entity_tags);
+}
+
+#[test]
+fn every_field_survives_the_export_document() {
+    let (_dir, db) = temp_db();
+    let expected = seed(&db);
+    let export = backup::export_all_data(&db).unwrap();
+
+    let document = serde_json::to_string(&export).unwrap();
+    let parsed: DataExport = serde_json::from_str(&document).unwrap();
+    assert_eq!(parsed, export);
+    assert_eq!(without_saved_stamps(parsed), expected);
+}
+
+#[test]
+fn an_export_restores_into_a_fresh_database_unchanged() {
+    let (_dir, db) = temp_db();
+    seed(&db);
+    // An unlinked entry and a second position, so restored ids don't simply line up with new ones
+    db.save_position(position("Roy Hill", date(2024, 3, 1), None)).unwrap();
+    db.delete_position(1).unwrap();
+    let export = backup::export_all_data(&db).unwrap();
+
+    let (_fresh_dir, fresh) = temp_db();
+    let result = backup::import_all_data(&fresh, export.clone(), date(2025, 6, 2)).unwrap();
+    assert!(result.warnings.is_empty());
+    assert_eq!(without_saved_stamps(backup::export_all_data(&fresh).unwrap()), without_saved_stamps(export));
+
+    // A new row after a restore doesn't reuse a restored id
+    let next = fresh.save_position(position("BHP", date(2025, 1, 6), None)).unwrap();
+    assert!(fresh.get_positions().unwrap().iter().filter(|p| p.id == Some(next)).count() == 1);
+}
+
+fn leaves(prefix: &str, value: &Value, out: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                let column = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                leaves(&column, field, out);
+            }
+        }
+        Value::Null => out.push((prefix.to_string(), String::new())),
+        Value::String(text) => out.push((prefix.to_string(), text.clone())),
+        other => out.push((prefix.to_string(), other.to_string())),
+    }
+}
+
+// CSV and spreadsheet exports write these cells as they are. They're lossy by design: text
+// cells carry no type, and a list is one cell of JSON.
+#[test]
+fn every_field_has_its_own_cell_in_the_flat_tables() {
+    let (_dir, db) = temp_db();
+    seed(&db);
+    let export = backup::export_all_data(&db).unwrap();
+    let tables = tables::export_tables(&export).unwrap();
+
+    let check = |name: &str, value: Value| {
+        let table = tables.iter().find(|t| t.name == name).unwrap();
+        assert_eq!(table.rows.len(), 1, "{}", name);
+        let mut expected = Vec::new();
+        leaves("", &value, &mut expected);
+        assert_eq!(table.headers.len(), expected.len(), "{}", name);
+        for (column, cell) in expected {
+            let index = table.headers.iter().position(|h| *h == column).unwrap_or_else(|| panic!("{}.{} has no column", name, column));
+            assert_eq!(table.rows[0][index], cell, "{}.{}", name, column);
+        }
+    };
+    check("user_profile", serde_json::to_value(&export.user_profile).unwrap());
+    check("positions", serde_json::to_value(&export.positions[0]).unwrap());
+    check("compensation_records", serde_json::to_value(&export.compensation_records[0]).unwrap());
+    check("weekly_entries", serde_json::to_value(&export.weekly_entries[0]).unwrap());
+    check("yearly_entries", serde_json::to_value(&export.yearly_entries[0]).unwrap());
+    check("exchange_rates", serde_json::to_value(&export.exchange_rates[0]).unwrap());
+    check("award_rates", serde_json::to_value(&export.award_rates[0]).unwrap());
+    check("scenarios", serde_json::to_value(&export.scenarios[0]).unwrap());
+    check("position_templates", serde_json::to_value(&export.templates[0]).unwrap());
+    check("insurance_policies", serde_json::to_value(&export.insurance_policies[0]).unwrap());
+    check("partner_income", serde_json::to_value(&export.partner_income_entries[0]).unwrap());
+    check("tags", serde_json::to_value(&export.tags[0]).unwrap());
+}
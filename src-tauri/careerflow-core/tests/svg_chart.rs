@@ -0,0 +1,76 @@
+use careerflow_core::svg_chart::{self, PlotArea, ValueAxis};
+
+fn data(values: &[f64]) -> Vec<(String, f64)> {
+    values.iter().enumerate().map(|(i, v)| ((2020 + i).to_string(), *v)).collect()
+}
+
+fn dollars(value: f64) -> String {
+    format!("${:.0}", value)
+}
+
+#[test]
+fn the_value_axis_rounds_up_to_a_tick() {
+    let axis = ValueAxis::for_values(&[95000.0, 123000.0, 40000.0]).unwrap();
+    assert_eq!(axis.ticks, vec![0.0, 50000.0, 100000.0, 150000.0]);
+    assert_eq!(axis.max, 150000.0);
+
+    let axis = ValueAxis::for_values(&[38.5]).unwrap();
+    assert_eq!(axis.ticks, vec![0.0, 10.0, 20.0, 30.0, 40.0]);
+
+    assert!(ValueAxis::for_values(&[]).is_none());
+    assert!(ValueAxis::for_values(&[0.0, -5.0]).is_none());
+}
+
+#[test]
+fn values_map_onto_the_plot_from_the_bottom_up() {
+    let plot = PlotArea { x: 50.0, y: 20.0, width: 400.0, height: 200.0 };
+    let axis = ValueAxis::for_values(&[100.0]).unwrap();
+    assert_eq!(axis.y(0.0, &plot), 220.0);
+    assert_eq!(axis.y(50.0, &plot), 120.0);
+    assert_eq!(axis.y(100.0, &plot), 20.0);
+    assert_eq!(axis.y(-10.0, &plot), 220.0);
+
+    assert_eq!(plot.slot_width(4), 100.0);
+    assert_eq!(plot.slot_centre(0, 4), 100.0);
+    assert_eq!(plot.slot_centre(3, 4), 400.0);
+}
+
+#[test]
+fn labels_thin_out_as_categories_crowd_together() {
+    let plot = PlotArea { x: 0.0, y: 0.0, width: 400.0, height: 100.0 };
+    assert_eq!(svg_chart::label_interval(5, &plot), 1);
+    assert_eq!(svg_chart::label_interval(20, &plot), 2);
+    assert_eq!(svg_chart::label_interval(40, &plot), 4);
+
+    let svg = svg_chart::bar_chart("Earnings", &data(&[1.0; 40]), dollars).unwrap();
+    assert!(svg.contains(">2020</text>") && svg.contains(">2023</text>"));
+    assert!(!svg.contains(">2021</text>"));
+}
+
+#[test]
+fn bars_and_points_sit_at_their_values() {
+    let plot = PlotArea::standard();
+    let svg = svg_chart::bar_chart("Earnings", &data(&[50.0, 100.0]), dollars).unwrap();
+    let slot = plot.slot_width(2);
+    let full_bar = format!(
+        r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}""#,
+        plot.x + slot * 1.15, plot.y, slot * 0.7, plot.height
+    );
+    assert!(svg.contains(&full_bar), "{}", svg);
+
+    let svg = svg_chart::line_chart("Rate", &data(&[0.0, 100.0]), dollars).unwrap();
+    let points = format!(
+        r#"points="{:.2},{:.2} {:.2},{:.2}""#,
+        plot.slot_centre(0, 2), plot.bottom(), plot.slot_centre(1, 2), plot.y
+    );
+    assert!(svg.contains(&points), "{}", svg);
+    assert!(svg.starts_with("<svg") && svg.ends_with("</svg>"));
+}
+
+#[test]
+fn labels_are_escaped() {
+    let svg = svg_chart::bar_chart("Pay <& conditions>", &[("\"Q1\"".to_string(), 5.0)], dollars).unwrap();
+    assert!(svg.contains("Pay &lt;&amp; conditions&gt;"));
+    assert!(svg.contains("&quot;Q1&quot;"));
+    assert!(!svg.contains("<&"));
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use careerflow_core::{backup, templates};
+use careerflow_core::models::*;
+use common::*;
+
+fn fifo_role() -> (Position, CompensationRecord) {
+    let mut role = position("Pilbara Ports", date(2021, 7, 1), Some(date(2024, 6, 30)));
+    role.job_title = "FIFO Plant Operator".to_string();
+    role.achievements = vec!["Zero LTIs for 2 years".to_string()];
+    role.notice_period_weeks = Some(4.0);
+    let mut record = salary_record(1, 0.0, date(2023, 7, 1));
+    record.pay_type = PayType::Hourly;
+    record.base_rate = 52.0;
+    record.allowances = vec![Allowance {
+        name: "Site allowance".to_string(),
+        amount: 120.0,
+        frequency: AllowanceFrequency::Weekly,
+        taxable: true,
+        ordinary_time: None,
+    }];
+    record.bonuses = vec![Bonus {
+        name: "Performance".to_string(),
+        amount: 5000.0,
+        date_awarded: date(2024, 6, 30),
+        taxable: true,
+    }];
+    record.tax_withheld = Some(30000.0);
+    record.notes = Some("Roster 2:1".to_string());
+    (role, record)
+}
+
+#[test]
+fn template_fields_leave_out_what_belongs_to_one_job() {
+    let (role, record) = fifo_role();
+
+    let fields = templates::template_fields(&role, Some(&record), false);
+    let json = serde_json::to_string(&fields).unwrap();
+    for job_specific in ["Pilbara Ports", "2021-07-01", "2024-06-30", "Zero LTIs", "Roster 2:1", "30000", "Performance"] {
+        assert!(!json.contains(job_specific), "{} carried into the template", job_specific);
+    }
+    assert_eq!(fields.job_title, "FIFO Plant Operator");
+    assert_eq!(fields.tools_systems_skills, vec!["SAP".to_string()]);
+    assert_eq!(fields.notice_period_weeks, Some(4.0));
+    let pay = fields.compensation.unwrap();
+    assert_eq!(pay.base_rate, 0.0);
+    assert_eq!(pay.allowances[0].name, "Site allowance");
+    assert_eq!(pay.allowances[0].amount, 0.0);
+
+    let kept = templates::template_fields(&role, Some(&record), true).compensation.unwrap();
+    assert_eq!(kept.base_rate, 52.0);
+    assert_eq!(kept.allowances[0].amount, 120.0);
+
+    assert!(templates::template_fields(&role, None, true).compensation.is_none());
+}
+
+#[test]
+fn a_template_scaffolds_a_new_position_and_record() {
+    let (_dir, db) = temp_db();
+    let (role, record) = fifo_role();
+    let role_id = db.save_position(role).unwrap();
+    db.save_compensation_record(salary_record(role_id, 90000.0, date(2021, 7, 1))).unwrap();
+    db.save_compensation_record(CompensationRecord { position_id: role_id, ..record }).unwrap();
+
+    assert!(templates::save_position_as_template(&db, role_id, "  ", false).is_err());
+    let template = templates::save_position_as_template(&db, role_id, "FIFO operator", false).unwrap();
+    assert_eq!(db.get_templates().unwrap().len(), 1);
+
+    let new_id = templates::create_position_from_template(&db, template.id.unwrap(), TemplateOverrides {
+        employer_name: "Roy Hill".to_string(),
+        start_date: date(2024, 8, 5),
+        job_title: None,
+        location: None,
+        base_rate: Some(55.0),
+    }).unwrap();
+
+    let created = db.get_position(new_id).unwrap().unwrap();
+    assert_eq!(created.employer_name, "Roy Hill");
+    assert_eq!(created.job_title, "FIFO Plant Operator");
+    assert_eq!(created.start_date, date(2024, 8, 5));
+    assert!(created.end_date.is_none() && created.achievements.is_empty());
+    let records = db.get_compensation_records(new_id).unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].base_rate, 55.0);
+    assert_eq!(records[0].effective_date, date(2024, 8, 5));
+    assert!(matches!(records[0].entry_type, CompensationEntryType::Fuzzy));
+    assert_eq!(records[0].allowances.len(), 1);
+
+    // An invalid position leaves nothing behind
+    let blank_employer = TemplateOverrides {
+        employer_name: " ".to_string(),
+        start_date: date(2024, 8, 5),
+        job_title: None,
+        location: None,
+        base_rate: None,
+    };
+    assert!(templates::create_position_from_template(&db, template.id.unwrap(), blank_employer).is_err());
+    assert_eq!(db.get_positions().unwrap().len(), 2);
+
+    let export = backup::export_all_data(&db).unwrap();
+    assert_eq!(export.templates.len(), 1);
+    db.delete_template(template.id.unwrap()).unwrap();
+    assert!(db.get_templates().unwrap().is_empty());
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use careerflow_core::models::*;
+use careerflow_core::{calculations, transitions};
+use common::*;
+
+fn promotion(job_title: &str) -> PositionTransition {
+    PositionTransition {
+        end_date: date(2023, 6, 30),
+        job_title: job_title.to_string(),
+        seniority_level: SeniorityLevel::Senior,
+        employment_type: None,
+        location: None,
+        core_responsibilities: "Lead shutdown planning".to_string(),
+        copy_skills: true,
+        carry_over_compensation: true,
+        raise_percent: Some(10.0),
+    }
+}
+
+#[test]
+fn promotion_ends_the_old_position_and_carries_the_package_over() {
+    let (_dir, db) = temp_db();
+    let old_id = db.save_position(position("Pilbara Ports", date(2020, 2, 3), None)).unwrap();
+    db.save_compensation_record(salary_record(old_id, 100000.0, date(2020, 2, 3))).unwrap();
+
+    let result = transitions::transition_position(&db, old_id, promotion("Senior Planner")).unwrap();
+
+    let old = db.get_position(old_id).unwrap().unwrap();
+    let new = db.get_position(result.new_position_id).unwrap().unwrap();
+    assert_eq!(old.end_date, Some(date(2023, 6, 30)));
+    assert_eq!(new.start_date, date(2023, 7, 1));
+    assert_eq!(new.employer_name, "Pilbara Ports");
+    assert_eq!(new.tools_systems_skills, vec!["SAP"]);
+
+    let carried = db.get_compensation_records(result.new_position_id).unwrap();
+    assert_eq!(carried.len(), 1);
+    assert_eq!(Some(carried[0].id.unwrap()), result.compensation_record_id);
+    assert!((carried[0].base_rate - 110000.0).abs() < 1e-6);
+    assert_eq!(carried[0].effective_date, date(2023, 7, 1));
+
+    // Continuous tenure at the employer across both positions
+    let loyalty = calculations::calculate_loyalty_tax(&db.get_positions().unwrap(), date(2024, 6, 1));
+    assert_eq!(loyalty.tenure_blocks.len(), 1);
+    assert_eq!(loyalty.tenure_blocks[0].start_date, date(2020, 2, 3));
+
+    // Already ended
+    assert!(transitions::transition_position(&db, old_id, promotion("Lead Planner")).unwrap_err().contains("already ended"));
+}
+
+#[test]
+fn an_invalid_new_position_rolls_back_the_end_date() {
+    let (_dir, db) = temp_db();
+    let old_id = db.save_position(position("Pilbara Ports", date(2020, 2, 3), None)).unwrap();
+
+    // The old position is end-dated inside the transaction before the blank title is rejected
+    let error = transitions::transition_position(&db, old_id, promotion("  ")).unwrap_err();
+    assert!(error.contains("Job title"));
+
+    let positions = db.get_positions().unwrap();
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions[0].end_date, None);
+
+    // Nothing to carry over also undoes both position writes
+    let error = transitions::transition_position(&db, old_id, promotion("Senior Planner")).unwrap_err();
+    assert!(error.contains("no compensation record"));
+    let positions = db.get_positions().unwrap();
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions[0].end_date, None);
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use careerflow_core::{csv_import, watch_folder};
+use common::*;
+
+const PAYSLIPS: &str = "\
+Week_Ending,Gross_Pay,Tax_Withheld,Hours_Ordinary,Hours_Overtime
+07/07/2024,\"$2,150.00\",512.40,38,4
+2024-07-14,2080,498,38,
+2024-07-21,not a number,0,38,0
+";
+
+#[test]
+fn csv_rows_parse_with_errors_reported_by_line() {
+    let preview = csv_import::parse_weekly_entries_csv(PAYSLIPS.as_bytes()).unwrap();
+
+    assert_eq!(preview.entries.len(), 2);
+    let first = &preview.entries[0];
+    assert_eq!(first.week_ending, date(2024, 7, 7));
+    assert_eq!(first.financial_year, "FY2024-25");
+    assert!((first.gross_pay - 2150.0).abs() < 1e-9);
+    assert!((first.net_pay - 1637.6).abs() < 1e-9);
+    assert_eq!(preview.entries[1].hours_overtime, 0.0);
+
+    assert_eq!(preview.errors.len(), 1);
+    assert_eq!(preview.errors[0].line, 4);
+    assert!(preview.errors[0].message.contains("gross_pay"));
+}
+
+#[test]
+fn csv_without_required_columns_is_rejected() {
+    let result = csv_import::parse_weekly_entries_csv("week_ending,net_pay\n2024-07-07,100\n".as_bytes());
+    assert!(result.unwrap_err().contains("gross_pay"));
+}
+
+#[test]
+fn patterns_match_file_names() {
+    assert!(watch_folder::matches_pattern("*.csv", "payslips.CSV"));
+    assert!(watch_folder::matches_pattern("pay-*-2024?.csv", "pay-july-20241.csv"));
+    assert!(!watch_folder::matches_pattern("*.csv", "payslips.csv.tmp"));
+    assert!(!watch_folder::matches_pattern("pay*.csv", "export.csv"));
+}
+
+#[test]
+fn handled_files_move_aside_and_failures_keep_the_reason() {
+    let folder = tempfile::tempdir().unwrap();
+    let good = folder.path().join("good.csv");
+    let bad = folder.path().join("bad.csv");
+    std::fs::write(&good, PAYSLIPS).unwrap();
+    std::fs::write(&bad, "date,amount\n").unwrap();
+    std::fs::write(folder.path().join("notes.txt"), "").unwrap();
+
+    let files = watch_folder::matching_files(folder.path(), "*.csv").unwrap();
+    assert_eq!(files, vec![bad.clone(), good.clone()]);
+
+    let (_db_dir, db) = temp_db();
+    let preview = watch_folder::preview_file(&good).unwrap();
+    assert_eq!(watch_folder::commit_preview(&db, preview).unwrap().imported_count, 2);
+    assert_eq!(db.get_weekly_entries().unwrap().len(), 2);
+    let processed = watch_folder::move_to_processed(&good).unwrap();
+    assert_eq!(processed, folder.path().join("processed").join("good.csv"));
+
+    let error = watch_folder::preview_file(&bad).unwrap_err();
+    let failed = watch_folder::move_to_failed(&bad, &error).unwrap();
+    let sidecar = std::fs::read_to_string(folder.path().join("failed").join("bad.csv.error.txt")).unwrap();
+    assert!(failed.exists());
+    assert!(sidecar.contains("missing required columns"));
+
+    // A second file with the same name does not overwrite the first
+    std::fs::write(&good, PAYSLIPS).unwrap();
+    let again = watch_folder::move_to_processed(&good).unwrap();
+    assert_eq!(again, folder.path().join("processed").join("good-1.csv"));
+    assert!(watch_folder::matching_files(folder.path(), "*.csv").unwrap().is_empty());
+}
+
+#[test]
+fn a_file_dropped_in_again_adds_no_pay_periods_twice() {
+    let (_db_dir, db) = temp_db();
+    let first = csv_import::parse_weekly_entries_csv(PAYSLIPS.as_bytes()).unwrap();
+    assert_eq!(watch_folder::commit_preview(&db, first).unwrap().imported_count, 2);
+
+    let overlapping = "week_ending,gross_pay,tax_withheld\n2024-07-14,2080,498\n2024-07-28,2100,505\n2024-07-28,2100,505\n";
+    let preview = csv_import::parse_weekly_entries_csv(overlapping.as_bytes()).unwrap();
+    let result = watch_folder::commit_preview(&db, preview).unwrap();
+    assert_eq!(result.imported_count, 1);
+    assert_eq!(result.skipped_period_endings, vec![date(2024, 7, 14), date(2024, 7, 28)]);
+
+    let again = csv_import::parse_weekly_entries_csv(PAYSLIPS.as_bytes()).unwrap();
+    assert_eq!(watch_folder::commit_preview(&db, again).unwrap().imported_count, 0);
+    assert_eq!(db.get_weekly_entries().unwrap().len(), 3);
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+use careerflow_core::models::*;
+use careerflow_core::watch_folder;
+use notify::{RecursiveMode, Watcher};
+use tauri::Manager;
+
+use crate::AppState;
+
+pub const IMPORT_DETECTED_EVENT: &str = "watch-folder-import-detected";
+pub const IMPORT_FAILED_EVENT: &str = "watch-folder-import-failed";
+
+// How often the thread wakes to check the stop flag and settle pending files
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+// How long to wait before looking again when the folder is missing, e.g. an unmounted drive
+const RETRY_INTERVAL: Duration = Duration::from_secs(30);
+// A file must keep the same size for this long before it is read, so half-copied files are skipped
+const SETTLE_TIME: Duration = Duration::from_secs(2);
+
+/// A running watcher thread. Dropping it stops the thread.
+pub struct FolderWatcher {
+    stop: Arc<AtomicBool>,
+}
+
+impl Drop for FolderWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+pub fn start(handle: tauri::AppHandle, settings: &WatchFolderSettings) -> Option<FolderWatcher> {
+    let folder = PathBuf::from(settings.path.as_deref()?.trim());
+    let pattern = settings.pattern.clone();
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let thread_stop = stop.clone();
+    std::thread::spawn(move || watch(handle, folder, pattern, thread_stop));
+
+    Some(FolderWatcher { stop })
+}
+
+// Outer loop: (re)attach to the folder whenever it is available, until stopped
+fn watch(handle: tauri::AppHandle, folder: PathBuf, pattern: String, stop: Arc<AtomicBool>) {
+    while !stop.load(Ordering::Relaxed) {
+        if !folder.is_dir() {
+            sleep_unless_stopped(RETRY_INTERVAL, &stop);
+            continue;
+        }
+
+        if let Err(e) = watch_while_available(&handle, &folder, &pattern, &stop) {
+            eprintln!("Folder watch on {}: {}", folder.display(), e);
+            sleep_unless_stopped(RETRY_INTERVAL, &stop);
+        }
+    }
+}
+
+fn watch_while_available(handle: &tauri::AppHandle, folder: &Path, pattern: &str, stop: &AtomicBool) -> Result<(), String> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(|e| e.to_string())?;
+    watcher.watch(folder, RecursiveMode::NonRecursive).map_err(|e| e.to_string())?;
+
+    // Files waiting to settle, with their last seen size
+    let mut settling: HashMap<PathBuf, (u64, Instant)> = HashMap::new();
+
+    // Pick up anything dropped while the app was closed or the folder was away
+    for path in watch_folder::matching_files(folder, pattern)? {
+        note_change(&mut settling, path);
+    }
+
+    while !stop.load(Ordering::Relaxed) {
+        match rx.recv_timeout(POLL_INTERVAL) {
+            Ok(Ok(event)) => {
+                for path in event.paths {
+                    if path.parent() == Some(folder) && watch_folder::is_candidate(&path, pattern) {
+                        note_change(&mut settling, path);
+                    }
+                }
+            }
+            Ok(Err(e)) => return Err(e.to_string()),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => return Err("watcher stopped".to_string()),
+        }
+
+        if !folder.is_dir() {
+            return Err("folder is no longer available".to_string());
+        }
+
+        for path in settled_files(&mut settling) {
+            handle_file(handle, &path);
+        }
+    }
+
+    Ok(())
+}
+
+fn note_change(settling: &mut HashMap<PathBuf, (u64, Instant)>, path: PathBuf) {
+    let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    settling.insert(path, (size, Instant::now()));
+}
+
+fn settled_files(settling: &mut HashMap<PathBuf, (u64, Instant)>) -> Vec<PathBuf> {
+    let mut settled = Vec::new();
+    settling.retain(|path, (size, since)| {
+        let current = match std::fs::metadata(path) {
+            Ok(metadata) => metadata.len(),
+            // Moved or deleted before it settled
+            Err(_) => return false,
+        };
+        if current != *size {
+            *size = current;
+            *since = Instant::now();
+            return true;
+        }
+        if since.elapsed() < SETTLE_TIME {
+            return true;
+        }
+        settled.push(path.clone());
+        false
+    });
+    settled.sort();
+    settled
+}
+
+// Dry-runs the file and either queues it for confirmation or moves it to failed/
+fn handle_file(handle: &tauri::AppHandle, path: &Path) {
+    let key = path.to_string_lossy().to_string();
+    let state = handle.state::<AppState>();
+
+    let mut pending = match state.pending_imports.lock() {
+        Ok(pending) => pending,
+        Err(e) => {
+            eprintln!("Folder watch skipped {}: {}", key, e);
+            return;
+        }
+    };
+    if pending.contains_key(&key) {
+        return;
+    }
+
+    match watch_folder::preview_file(path) {
+        Ok(preview) => {
+            let import = PendingWatchedImport {
+                path: key.clone(),
+                detected_at: chrono::Utc::now(),
+                preview,
+            };
+            pending.insert(key, import.clone());
+            let _ = handle.emit_all(IMPORT_DETECTED_EVENT, import);
+        }
+        Err(error) => {
+            eprintln!("Folder watch could not import {}: {}", key, error);
+            if let Err(e) = watch_folder::move_to_failed(path, &error) {
+                eprintln!("Folder watch: {}", e);
+            }
+            let _ = handle.emit_all(IMPORT_FAILED_EVENT, format!("{}: {}", key, error));
+        }
+    }
+}
+
+fn sleep_unless_stopped(duration: Duration, stop: &AtomicBool) {
+    let deadline = Instant::now() + duration;
+    while Instant::now() < deadline && !stop.load(Ordering::Relaxed) {
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
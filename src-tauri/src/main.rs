@@ -1,211 +1,1740 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Mutex;
 use tauri::{Manager, State};
 
-mod database;
-mod models;
-mod calculations;
+use careerflow_core::{anonymize, auto_export, backup, bulk_edit, calculations, compensation_history, dates, diagnostics, import_mapping, instance_lock, lsl, paths, reminders, report, rollover, tax, templates, transitions, validation, watch_folder};
+use careerflow_core::database::Database;
+use careerflow_core::error::AppError;
+use careerflow_core::in_flight::InFlight;
+use careerflow_core::instance_lock::InstanceLock;
+use careerflow_core::models::*;
 
-use database::Database;
-use models::*;
+mod folder_watch;
 
 struct AppState {
     db: Mutex<Database>,
+    db_path: PathBuf,
+    instance_lock: Mutex<Option<InstanceLock>>, // None while another instance holds the database
+    folder_watcher: Mutex<Option<folder_watch::FolderWatcher>>,
+    pending_imports: Mutex<HashMap<String, PendingWatchedImport>>, // Keyed by file path
+    analysis_in_flight: InFlight<Result<EarningsAnalysis, String>>, // Keyed by data version and date
+}
+
+const AUTO_EXPORT_FAILED_EVENT: &str = "auto-export-failed";
+const POSITION_WARNINGS_EVENT: &str = "position-warnings";
+
+#[tauri::command]
+async fn get_user_profile(state: State<'_, AppState>) -> Result<Option<UserProfile>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_user_profile()
+}
+
+#[tauri::command]
+async fn save_user_profile(profile: UserProfile, state: State<'_, AppState>) -> Result<(), String> {
+    validation::validate_user_profile(&profile, chrono::Local::now().date_naive())?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.save_user_profile(profile).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_positions(tag_filter: Option<Vec<i64>>, state: State<'_, AppState>) -> Result<ListResult<Position>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let mut list = db.list_positions()?;
+    list.rows = calculations::filter_positions_by_tags(&list.rows, &db.get_entity_tags()?, &tag_filter.unwrap_or_default());
+    Ok(list)
+}
+
+#[tauri::command]
+async fn archive_position(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    set_position_archived(id, true, &state)
+}
+
+#[tauri::command]
+async fn unarchive_position(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    set_position_archived(id, false, &state)
+}
+
+fn set_position_archived(id: i64, archived: bool, state: &AppState) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_position(id)?.ok_or_else(|| format!("Position {} not found", id))?;
+    db.set_position_archived(id, archived).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_position_summaries(state: State<'_, AppState>) -> Result<Vec<PositionSummary>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_position_summaries()
+}
+
+#[tauri::command]
+async fn get_position(id: i64, state: State<'_, AppState>) -> Result<Position, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_position(id)?
+        .ok_or_else(|| format!("Position {} not found", id))
+}
+
+#[tauri::command]
+async fn positions_active_between(start: chrono::NaiveDate, end: chrono::NaiveDate, state: State<'_, AppState>) -> Result<Vec<Position>, String> {
+    if start > end {
+        return Err("Range start must be on or before its end".to_string());
+    }
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.positions_active_between(start, end)
+}
+
+#[tauri::command]
+async fn save_position(position: Position, app: tauri::AppHandle, state: State<'_, AppState>) -> Result<i64, String> {
+    validation::validate_position(&position)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let id = db.save_position(position).map_err(|e| e.to_string())?;
+
+    // Saved regardless; the frontend shows these alongside the positions
+    let warnings = validation::open_ended_position_warnings(&db.get_positions()?, chrono::Local::now().date_naive());
+    if !warnings.is_empty() {
+        let _ = app.emit_all(POSITION_WARNINGS_EVENT, warnings);
+    }
+    Ok(id)
+}
+
+#[tauri::command]
+async fn transition_position(
+    old_position_id: i64,
+    transition: PositionTransition,
+    state: State<'_, AppState>,
+) -> Result<PositionTransitionResult, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    transitions::transition_position(&db, old_position_id, transition)
+}
+
+#[tauri::command]
+async fn delete_position(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.delete_position(id).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_compensation_records(position_id: i64, state: State<'_, AppState>) -> Result<Vec<CompensationRecord>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_compensation_records(position_id)
+}
+
+#[tauri::command]
+async fn check_compensation_record(record: CompensationRecord, state: State<'_, AppState>) -> Result<Vec<ValidationIssue>, String> {
+    validation::validate_compensation_record(&record)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let positions = db.get_positions()?;
+    let mut warnings = validation::compensation_record_warnings(&record);
+    warnings.extend(validation::record_before_start_warnings(&positions, std::slice::from_ref(&record)));
+    Ok(warnings)
+}
+
+// A record with warnings from check_compensation_record is only saved once confirmed
+#[tauri::command]
+async fn save_compensation_record(record: CompensationRecord, confirmed: Option<bool>, state: State<'_, AppState>) -> Result<i64, String> {
+    validation::validate_compensation_record(&record)?;
+    let warnings = validation::compensation_record_warnings(&record);
+    if !warnings.is_empty() && !confirmed.unwrap_or(false) {
+        let messages: Vec<String> = warnings.into_iter().map(|w| w.message).collect();
+        return Err(format!("{}. Confirm to save it anyway.", messages.join("; ")));
+    }
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.save_compensation_record(record).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn clone_compensation_record(
+    id: i64,
+    new_effective_date: chrono::NaiveDate,
+    overrides: CompensationRecordOverrides,
+    state: State<'_, AppState>,
+) -> Result<CompensationRecord, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    compensation_history::clone_record(&db, id, new_effective_date, overrides)
+}
+
+#[tauri::command]
+async fn upgrade_record_to_exact(
+    id: i64,
+    corrected_fields: CompensationRecordOverrides,
+    state: State<'_, AppState>,
+) -> Result<CompensationRecord, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    compensation_history::upgrade_record_to_exact(&db, id, corrected_fields)
+}
+
+#[tauri::command]
+async fn get_fuzzy_records(state: State<'_, AppState>) -> Result<Vec<FuzzyRecordWeight>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let positions = db.get_positions()?;
+    let records = db.get_all_compensation_records()?;
+    let exchange_rates = db.get_exchange_rates()?;
+    Ok(calculations::fuzzy_records_by_weight(&positions, &records, &exchange_rates, chrono::Local::now().date_naive()))
+}
+
+#[tauri::command]
+async fn get_record_changes(record_id: i64, state: State<'_, AppState>) -> Result<Vec<RecordChange>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_record_changes(record_id)
+}
+
+#[tauri::command]
+async fn merge_compensation_records(primary_id: i64, secondary_id: i64, state: State<'_, AppState>) -> Result<i64, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    compensation_history::merge_records(&db, primary_id, secondary_id)
+}
+
+#[tauri::command]
+async fn check_tax_withheld(record_id: i64, state: State<'_, AppState>) -> Result<WithholdingCheck, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let record = db.get_compensation_record(record_id)?
+        .ok_or_else(|| format!("Compensation record {} not found", record_id))?;
+    calculations::calculate_withholding_check(&record)
+}
+
+#[tauri::command]
+async fn calculate_back_pay(
+    position_id: i64,
+    old_record_id: i64,
+    new_record_id: i64,
+    from_date: chrono::NaiveDate,
+    to_date: chrono::NaiveDate,
+    state: State<'_, AppState>,
+) -> Result<BackPayCalculation, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let old_record = db.get_compensation_record(old_record_id)?
+        .ok_or_else(|| format!("Compensation record {} not found", old_record_id))?;
+    let new_record = db.get_compensation_record(new_record_id)?
+        .ok_or_else(|| format!("Compensation record {} not found", new_record_id))?;
+    let weekly_entries = db.get_weekly_entries()?;
+    calculations::back_pay(position_id, &old_record, &new_record, from_date, to_date, &weekly_entries)
+}
+
+#[tauri::command]
+async fn get_record_lineage(position_id: i64, state: State<'_, AppState>) -> Result<Vec<RecordLineageEntry>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let records = db.get_compensation_records(position_id)?;
+    Ok(calculations::calculate_record_lineage(position_id, &records))
+}
+
+#[tauri::command]
+async fn delete_compensation_record(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.delete_compensation_record(id).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_weekly_entries(state: State<'_, AppState>) -> Result<ListResult<WeeklyCompensationEntry>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.list_weekly_entries()
+}
+
+#[tauri::command]
+async fn save_weekly_entry(entry: WeeklyCompensationEntry, state: State<'_, AppState>) -> Result<i64, String> {
+    validation::validate_weekly_entry(&entry)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.save_weekly_entry(entry).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn check_weekly_entry(entry: WeeklyCompensationEntry, state: State<'_, AppState>) -> Result<Vec<ValidationIssue>, String> {
+    validation::validate_weekly_entry(&entry)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let positions = db.get_positions()?;
+    Ok(validation::week_ending_warnings(&positions, std::slice::from_ref(&entry)))
+}
+
+#[tauri::command]
+async fn normalize_week_endings(
+    position_id: i64,
+    target_weekday: chrono::Weekday,
+    dry_run: bool,
+    state: State<'_, AppState>,
+) -> Result<WeekEndingNormalization, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    bulk_edit::normalize_week_endings(&db, position_id, target_weekday, dry_run)
+}
+
+#[tauri::command]
+async fn delete_weekly_entry(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.delete_weekly_entry(id).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+async fn calculate_earnings_analysis(
+    tag_filter: Option<Vec<i64>>,
+    include_archived: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<EarningsAnalysis, String> {
+    // Tab switches fire this several times at once; later calls wait for the first instead of recomputing.
+    // The database lock is released before waiting, or the running call could never take it.
+    let today = chrono::Local::now().date_naive();
+    let tag_filter = tag_filter.unwrap_or_default();
+    let include_archived = include_archived.unwrap_or(false);
+    let key = {
+        let db = state.db.lock().map_err(|e| e.to_string())?;
+        format!("{}:{}:{:?}:{}", db.data_version().map_err(|e| e.to_string())?, today, tag_filter, include_archived)
+    };
+    state.analysis_in_flight
+        .run(&key, || async { earnings_analysis(&state, today, &tag_filter, include_archived) })
+        .await
+}
+
+fn earnings_analysis(
+    state: &AppState,
+    today: chrono::NaiveDate,
+    tag_filter: &[i64],
+    include_archived: bool,
+) -> Result<EarningsAnalysis, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let calculations::AnalysisData { positions, records, weekly_entries } = calculations::analysis_data(
+        &db.get_positions()?,
+        db.get_all_compensation_records()?,
+        db.get_weekly_entries()?,
+        &db.get_entity_tags()?,
+        tag_filter,
+        include_archived,
+    );
+    let profile = db.get_user_profile()?;
+    let exchange_rates = db.get_exchange_rates()?;
+
+    let mut analysis = calculations::calculate_earnings_analysis(&positions, &records, &profile, &exchange_rates, today);
+    let quality = calculations::calculate_data_quality_report(&positions, &records, &weekly_entries, today);
+    analysis.insights.extend(calculations::data_quality_insight(&quality));
+    let cap_statuses = super_cap_statuses(&db, &dates::FinancialYear::containing(today))?;
+    analysis.insights.extend(calculations::super_cap_insight(&cap_statuses));
+    let rules = lsl::rules_with_overrides(&db.get_setting::<Vec<LslRule>>(lsl::SETTINGS_KEY)?);
+    let lsl_status = calculations::lsl_accrual(&positions, &records, &rules, &profile, today);
+    analysis.insights.extend(calculations::lsl_insight(&lsl_status, &rules, today));
+    let income_risk = calculations::income_risk_score(&positions, &records, today);
+    analysis.insights.extend(calculations::income_risk_insight(&income_risk, &positions, today));
+    let insurance_settings = db.get_setting::<InsuranceSettings>(calculations::INSURANCE_SETTINGS_KEY)?;
+    analysis.insights.extend(calculations::income_protection_insight(
+        &db.get_insurance_policies()?, &positions, &records, &insurance_settings, today,
+    ));
+    analysis.insights.extend(calculations::overtime_profile_insight(&positions, &records, &weekly_entries, today));
+    if let Ok(seasonality) = calculations::seasonality(&weekly_entries, None) {
+        analysis.insights.extend(calculations::seasonality_insight(&seasonality));
+    }
+    let industry = profile.as_ref().map(|p| p.industry.as_str()).unwrap_or("Unknown");
+    let wage_index = db.get_setting::<WageIndex>(calculations::WAGE_INDEX_SETTINGS_KEY)?;
+    analysis.percentile_over_time = calculations::percentile_over_time(&analysis.earnings_over_time, industry, &wage_index, today)?;
+    analysis.insights.extend(calculations::percentile_decline_insight(&analysis.percentile_over_time));
+    if let Some(profile) = &profile {
+        let demographics = db.get_setting::<DemographicSettings>(calculations::DEMOGRAPHIC_SETTINGS_KEY)?;
+        calculations::apply_gender_benchmark(&mut analysis, profile, &demographics);
+    }
+    Ok(analysis)
+}
+
+#[tauri::command]
+async fn get_earnings_seasonality(years: Option<u32>, state: State<'_, AppState>) -> Result<EarningsSeasonality, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    Ok(calculations::seasonality(&db.get_weekly_entries()?, years)?)
+}
+
+#[tauri::command]
+async fn get_overtime_profile(position_id: i64, state: State<'_, AppState>) -> Result<OvertimeProfile, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let records = db.get_compensation_records(position_id)?;
+    let latest = records.iter().max_by_key(|r| r.effective_date);
+    Ok(calculations::infer_overtime_profile(position_id, &db.get_weekly_entries()?, latest))
+}
+
+#[tauri::command]
+async fn get_data_quality(state: State<'_, AppState>) -> Result<DataQualityReport, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let positions = db.get_positions()?;
+    let records = db.get_all_compensation_records()?;
+    let weekly_entries = db.get_weekly_entries()?;
+
+    Ok(calculations::calculate_data_quality_report(&positions, &records, &weekly_entries, chrono::Local::now().date_naive()))
+}
+
+#[tauri::command]
+async fn income_risk_score(state: State<'_, AppState>) -> Result<IncomeRiskAssessment, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let positions = db.get_positions()?;
+    let records = db.get_all_compensation_records()?;
+    Ok(calculations::income_risk_score(&positions, &records, chrono::Local::now().date_naive()))
+}
+
+#[tauri::command]
+async fn preference_mismatch_check(state: State<'_, AppState>) -> Result<Vec<PreferenceMismatch>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let profile = db.get_user_profile()?.ok_or("Set up your profile to check your preferences")?;
+    let positions = db.get_positions()?;
+    let records = db.get_all_compensation_records()?;
+    Ok(calculations::preference_mismatch_check(&profile, &positions, &records, chrono::Local::now().date_naive()))
+}
+
+#[tauri::command]
+async fn save_scenario(name: String, position_id: i64, offer: CompensationRecord, state: State<'_, AppState>) -> Result<Scenario, String> {
+    if name.trim().is_empty() {
+        return Err("Give the scenario a name".to_string());
+    }
+    validation::validate_compensation_record(&offer)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let records = db.get_compensation_records(position_id)?;
+    let exchange_rates = db.get_exchange_rates()?;
+    let has_help_debt = db.get_user_profile()?.map(|p| p.has_help_debt).unwrap_or(false);
+
+    let mut scenario = calculations::offer_scenario(&name, position_id, &records, offer, &exchange_rates, has_help_debt)?;
+    let partner_income = calculations::partner_income_for(
+        &db.get_partner_income_entries()?,
+        &dates::FinancialYear::containing(scenario.offer.effective_date),
+    );
+    scenario.result.mls_tier_change = calculations::mls_tier_change(&scenario.baseline, &scenario.offer, partner_income);
+    scenario.id = Some(db.save_scenario(scenario.clone()).map_err(|e| e.to_string())?);
+    Ok(scenario)
+}
+
+#[tauri::command]
+async fn list_scenarios(state: State<'_, AppState>) -> Result<Vec<Scenario>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_scenarios()
+}
+
+#[tauri::command]
+async fn delete_scenario(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.delete_scenario(id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn rerun_scenario(id: i64, state: State<'_, AppState>) -> Result<ScenarioRerun, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let scenario = db.get_scenario(id)?
+        .ok_or_else(|| format!("Scenario {} not found", id))?;
+    let positions = db.get_positions()?;
+    let records = db.get_all_compensation_records()?;
+    let exchange_rates = db.get_exchange_rates()?;
+    let has_help_debt = db.get_user_profile()?.map(|p| p.has_help_debt).unwrap_or(false);
+    Ok(calculations::rerun_scenario(&scenario, &positions, &records, &exchange_rates, has_help_debt))
+}
+
+#[tauri::command]
+async fn save_position_as_template(position_id: i64, name: String, keep_rates: bool, state: State<'_, AppState>) -> Result<PositionTemplate, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    templates::save_position_as_template(&db, position_id, &name, keep_rates)
+}
+
+#[tauri::command]
+async fn list_templates(state: State<'_, AppState>) -> Result<Vec<PositionTemplate>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_templates()
+}
+
+#[tauri::command]
+async fn delete_template(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.delete_template(id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn create_position_from_template(template_id: i64, overrides: TemplateOverrides, state: State<'_, AppState>) -> Result<i64, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    templates::create_position_from_template(&db, template_id, overrides)
+}
+
+#[tauri::command]
+async fn save_analysis_snapshot(state: State<'_, AppState>) -> Result<AnalysisSnapshot, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let calculations::AnalysisData { positions, records, weekly_entries } = calculations::analysis_data(
+        &db.get_positions()?,
+        db.get_all_compensation_records()?,
+        db.get_weekly_entries()?,
+        &[],
+        &[],
+        false,
+    );
+    let profile = db.get_user_profile()?;
+    let exchange_rates = db.get_exchange_rates()?;
+
+    let today = chrono::Local::now().date_naive();
+    let analysis = calculations::calculate_earnings_analysis(&positions, &records, &profile, &exchange_rates, today);
+    let fy = dates::FinancialYear::containing(today);
+    let fy_positions = db.positions_active_between(fy.start(), fy.end())?;
+    let super_summary = calculations::calculate_super_contributions_for_fy(&fy, &weekly_entries, &fy_positions, &records, &exchange_rates);
+
+    let mut snapshot = calculations::analysis_snapshot(&analysis, &super_summary, chrono::Utc::now());
+    snapshot.id = Some(db.save_analysis_snapshot(&snapshot).map_err(|e| e.to_string())?);
+    Ok(snapshot)
+}
+
+#[tauri::command]
+async fn get_analysis_snapshots(state: State<'_, AppState>) -> Result<Vec<AnalysisSnapshot>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_analysis_snapshots()
+}
+
+#[tauri::command]
+async fn compare_snapshots(a: i64, b: i64, state: State<'_, AppState>) -> Result<SnapshotComparison, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let first = db.get_analysis_snapshot(a)?.ok_or_else(|| format!("Snapshot {} not found", a))?;
+    let second = db.get_analysis_snapshot(b)?.ok_or_else(|| format!("Snapshot {} not found", b))?;
+
+    Ok(calculations::compare_snapshots(&first, &second))
+}
+
+#[tauri::command]
+async fn delete_analysis_snapshot(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.delete_analysis_snapshot(id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn calculate_loyalty_tax(
+    tag_filter: Option<Vec<i64>>,
+    include_archived: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<LoyaltyTaxAnalysis, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let positions = calculations::filter_positions_by_tags(
+        &calculations::analysed_positions(&db.get_positions()?, include_archived.unwrap_or(false)),
+        &db.get_entity_tags()?,
+        &tag_filter.unwrap_or_default(),
+    );
+    
+    Ok(calculations::calculate_loyalty_tax(&positions, chrono::Local::now().date_naive()))
+}
+
+#[tauri::command]
+async fn counterfactual_earnings(state: State<'_, AppState>) -> Result<CounterfactualEarnings, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let positions = db.get_positions()?;
+    let records = db.get_all_compensation_records()?;
+    let exchange_rates = db.get_exchange_rates()?;
+
+    Ok(calculations::counterfactual_earnings(&positions, &records, &exchange_rates, chrono::Local::now().date_naive()))
+}
+
+#[tauri::command]
+async fn promotion_velocity(state: State<'_, AppState>) -> Result<PromotionVelocity, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let positions = db.get_positions()?;
+    let profile = db.get_user_profile()?;
+
+    Ok(calculations::calculate_promotion_velocity(&positions, &profile, chrono::Local::now().date_naive()))
+}
+
+#[tauri::command]
+async fn industry_exposure(state: State<'_, AppState>) -> Result<Vec<IndustryExposure>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let positions = db.get_positions()?;
+    let records = db.get_all_compensation_records()?;
+    let profile = db.get_user_profile()?;
+    let exchange_rates = db.get_exchange_rates()?;
+
+    Ok(calculations::calculate_industry_exposure(
+        &positions,
+        &records,
+        &profile,
+        &exchange_rates,
+        chrono::Local::now().date_naive(),
+    ))
+}
+
+#[tauri::command]
+async fn qualification_roi(
+    cost: f64,
+    expected_salary_uplift: Option<f64>,
+    study_years: f64,
+    state: State<'_, AppState>,
+) -> Result<QualificationRoi, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let profile = db.get_user_profile()?.ok_or("Set up your profile to weigh up further study")?;
+    Ok(calculations::qualification_roi(
+        cost,
+        expected_salary_uplift,
+        study_years,
+        &profile,
+        &db.get_positions()?,
+        &db.get_all_compensation_records()?,
+        chrono::Local::now().date_naive(),
+    ))
+}
+
+#[tauri::command]
+async fn project_lifetime_earnings(assumed_growth: Option<f64>, state: State<'_, AppState>) -> Result<LifetimeEarningsProjection, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let profile = db.get_user_profile()?.ok_or("Set up your profile to project lifetime earnings")?;
+    Ok(calculations::project_lifetime_earnings(
+        &profile,
+        &db.get_positions()?,
+        &db.get_all_compensation_records()?,
+        &db.get_exchange_rates()?,
+        assumed_growth,
+        chrono::Local::now().date_naive(),
+    ))
+}
+
+#[tauri::command]
+async fn skill_recency_ranking(state: State<'_, AppState>) -> Result<Vec<SkillRecency>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    Ok(calculations::skill_recency_ranking(&db.get_positions()?, chrono::Local::now().date_naive()))
+}
+
+#[tauri::command]
+async fn generate_resume_export(include_archived: Option<bool>, state: State<'_, AppState>) -> Result<ResumeExport, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let positions = calculations::analysed_positions(&db.get_positions()?, include_archived.unwrap_or(false));
+    let profile = db.get_user_profile()?;
+    
+    Ok(calculations::generate_resume_export(&positions, &profile, chrono::Local::now().date_naive()))
+}
+
+#[tauri::command]
+async fn export_analysis_report_pdf(redact_personal: bool, state: State<'_, AppState>) -> Result<Vec<u8>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let today = chrono::Local::now().date_naive();
+    let report = ReportInputs::load(&db, today)?;
+    Ok(report::analysis_report_pdf(
+        report.profile.as_ref(),
+        &report.analysis,
+        &report.loyalty_tax,
+        &report.financial_years,
+        redact_personal,
+        today,
+    ))
+}
+
+#[tauri::command]
+async fn export_analysis_report_html(redact_personal: bool, state: State<'_, AppState>) -> Result<String, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let today = chrono::Local::now().date_naive();
+    let report = ReportInputs::load(&db, today)?;
+    Ok(report::analysis_report_html(
+        report.profile.as_ref(),
+        &report.analysis,
+        &report.loyalty_tax,
+        &report.financial_years,
+        redact_personal,
+        today,
+    ))
+}
+
+// Everything the adviser report covers, whichever format it's written in
+struct ReportInputs {
+    profile: Option<UserProfile>,
+    analysis: EarningsAnalysis,
+    loyalty_tax: LoyaltyTaxAnalysis,
+    financial_years: Vec<FinancialYearSummary>,
+}
+
+impl ReportInputs {
+    fn load(db: &Database, today: chrono::NaiveDate) -> Result<Self, String> {
+        let all_records = db.get_all_compensation_records()?;
+        let weekly_entries = db.get_weekly_entries()?;
+        let calculations::AnalysisData { positions, records, .. } = calculations::analysis_data(
+            &db.get_positions()?,
+            all_records.clone(),
+            weekly_entries.clone(),
+            &[],
+            &[],
+            false,
+        );
+        let profile = db.get_user_profile()?;
+        let exchange_rates = db.get_exchange_rates()?;
+        // The tax years stay whole, to reconcile against the income statements
+        let mut financial_years = calculations::calculate_financial_year_summaries(
+            &weekly_entries,
+            &db.get_yearly_entries()?,
+            &exchange_rates,
+        );
+        calculations::add_equity_to_summaries(&mut financial_years, &calculations::calculate_equity_by_fy(&all_records, &exchange_rates));
+
+        let analysis = calculations::calculate_earnings_analysis(&positions, &records, &profile, &exchange_rates, today);
+        let loyalty_tax = calculations::calculate_loyalty_tax(&positions, today);
+        Ok(ReportInputs { profile, analysis, loyalty_tax, financial_years })
+    }
+}
+
+#[tauri::command]
+async fn income_composition(position_id: i64, state: State<'_, AppState>) -> Result<IncomeComposition, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let records = db.get_compensation_records(position_id)?;
+    let current = records.first()
+        .ok_or_else(|| "No compensation records found for this position".to_string())?;
+    let exchange_rates = db.get_exchange_rates()?;
+
+    Ok(calculations::calculate_income_composition(current, &exchange_rates))
+}
+
+#[tauri::command]
+async fn get_position_statistics(position_id: i64, state: State<'_, AppState>) -> Result<PositionStatistics, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_position(position_id)?
+        .ok_or_else(|| format!("Position {} not found", position_id))?;
+    let weekly = db.get_position_weekly_stats(position_id)?;
+    let records = db.get_compensation_records(position_id)?;
+
+    Ok(PositionStatistics {
+        weekly,
+        compensation_record_count: records.len() as u32,
+        latest_record: records.first().map(calculations::compensation_record_summary),
+    })
+}
+
+#[tauri::command]
+async fn col_adjusted_earnings(position_id: i64, state: State<'_, AppState>) -> Result<f64, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let position = db.get_position(position_id)?
+        .ok_or_else(|| format!("Position {} not found", position_id))?;
+    let records = db.get_compensation_records(position_id)?;
+    let current = records.first()
+        .ok_or_else(|| "No compensation records found for this position".to_string())?;
+    let exchange_rates = db.get_exchange_rates()?;
+
+    calculations::col_adjusted_earnings(&position, current, &exchange_rates)
+}
+
+#[tauri::command]
+async fn overtime_rate_effect(position_id: i64, state: State<'_, AppState>) -> Result<OvertimeRateEffect, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let records = db.get_compensation_records(position_id)?;
+    let current = records.first()
+        .ok_or_else(|| "No compensation records found for this position".to_string())?;
+
+    Ok(calculations::calculate_overtime_rate_effect(current))
+}
+
+#[tauri::command]
+async fn overtime_hours_for_target(
+    position_id: i64,
+    target_annual: f64,
+    weekly_hours_ceiling: Option<f64>,
+    state: State<'_, AppState>,
+) -> Result<OvertimeTargetResult, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let records = db.get_compensation_records(position_id)?;
+    let current = records.first()
+        .ok_or_else(|| "No compensation records found for this position".to_string())?;
+    let has_help_debt = db.get_user_profile()?.map(|p| p.has_help_debt).unwrap_or(false);
+    let fy = dates::FinancialYear::containing(chrono::Local::now().date_naive());
+
+    calculations::overtime_hours_for_target(
+        current,
+        target_annual,
+        weekly_hours_ceiling.unwrap_or(calculations::DEFAULT_WEEKLY_HOURS_CEILING),
+        &fy,
+        has_help_debt,
+    )
+}
+
+#[tauri::command]
+async fn employer_super(position_id: i64, state: State<'_, AppState>) -> Result<EmployerSuperBreakdown, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let records = db.get_compensation_records(position_id)?;
+    let current = records.first()
+        .ok_or_else(|| "No compensation records found for this position".to_string())?;
+
+    Ok(calculations::calculate_employer_super(current))
+}
+
+#[tauri::command]
+async fn net_hourly_rate(position_id: i64, state: State<'_, AppState>) -> Result<f64, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let position = db.get_position(position_id)?
+        .ok_or_else(|| format!("Position {} not found", position_id))?;
+    let records = db.get_compensation_records(position_id)?;
+    let current = records.first()
+        .ok_or_else(|| "No compensation records found for this position".to_string())?;
+    let has_help_debt = db.get_user_profile()?.map(|p| p.has_help_debt).unwrap_or(false);
+
+    let on = position.end_date.unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let tax_year = dates::FinancialYear::containing_for(on, &current.jurisdiction);
+    Ok(calculations::calculate_net_hourly_rate(current, &tax_year, has_help_debt))
+}
+
+#[tauri::command]
+async fn commute_adjusted_hourly_rate(position_id: i64, state: State<'_, AppState>) -> Result<f64, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let position = db.get_position(position_id)?
+        .ok_or_else(|| format!("Position {} not found", position_id))?;
+    let records = db.get_compensation_records(position_id)?;
+    let current = records.first()
+        .ok_or_else(|| "No compensation records found for this position".to_string())?;
+
+    Ok(calculations::commute_adjusted_hourly_rate(&position, current))
+}
+
+#[tauri::command]
+async fn pay_cycle_breakdown(position_id: i64, frequency: PayslipFrequency, state: State<'_, AppState>) -> Result<PayCycleBreakdown, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let position = db.get_position(position_id)?
+        .ok_or_else(|| format!("Position {} not found", position_id))?;
+    let records = db.get_compensation_records(position_id)?;
+    let current = records.first()
+        .ok_or_else(|| "No compensation records found for this position".to_string())?;
+    let has_help_debt = db.get_user_profile()?.map(|p| p.has_help_debt).unwrap_or(false);
+
+    let on = position.end_date.unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let tax_year = dates::FinancialYear::containing_for(on, &current.jurisdiction);
+    Ok(calculations::pay_cycle_breakdown(current, frequency, &tax_year, has_help_debt))
+}
+
+#[tauri::command]
+async fn model_four_day_week(position_id: i64, state: State<'_, AppState>) -> Result<FourDayWeekImpact, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let position = db.get_position(position_id)?
+        .ok_or_else(|| format!("Position {} not found", position_id))?;
+    let records = db.get_compensation_records(position_id)?;
+    let current = records.first()
+        .ok_or_else(|| "No compensation records found for this position".to_string())?;
+    let has_help_debt = db.get_user_profile()?.map(|p| p.has_help_debt).unwrap_or(false);
+
+    let on = position.end_date.unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let tax_year = dates::FinancialYear::containing_for(on, &current.jurisdiction);
+    Ok(calculations::model_four_day_week(current, &tax_year, has_help_debt))
+}
+
+#[tauri::command]
+async fn calculate_co_contribution(income: f64, personal_after_tax_contribution: f64, financial_year: String) -> Result<f64, String> {
+    let fy = dates::FinancialYear::parse(&financial_year)?;
+    Ok(calculations::calculate_co_contribution(income, personal_after_tax_contribution, &fy))
+}
+
+#[tauri::command]
+async fn calculate_div293(income: f64, concessional_contributions: f64, financial_year: String) -> Result<f64, String> {
+    let fy = dates::FinancialYear::parse(&financial_year)?;
+    Ok(tax::div293_liability(income, concessional_contributions, &fy))
+}
+
+#[tauri::command]
+async fn sacrifice_benefit_curve(
+    sacrifice: f64,
+    other_concessional: f64,
+    from: f64,
+    to: f64,
+    step: f64,
+    financial_year: String,
+) -> Result<Vec<SacrificeBenefitPoint>, String> {
+    let fy = dates::FinancialYear::parse(&financial_year)?;
+    calculations::calculate_sacrifice_benefit_curve(sacrifice, other_concessional, from, to, step, &fy)
+}
+
+#[tauri::command]
+async fn tax_rate_curve(from: f64, to: f64, step: f64, financial_year: String) -> Result<Vec<(f64, f64)>, String> {
+    let fy = dates::FinancialYear::parse(&financial_year)?;
+    tax::tax_rate_curve(from, to, step, &fy)
+}
+
+#[tauri::command]
+async fn get_all_compensation_records(state: State<'_, AppState>) -> Result<ListResult<CompensationRecord>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.list_compensation_records()
+}
+
+#[tauri::command]
+async fn reassign_weekly_entries(
+    from_position_id: Option<i64>,
+    to_position_id: i64,
+    date_range: Option<(chrono::NaiveDate, chrono::NaiveDate)>,
+    state: State<'_, AppState>,
+) -> Result<Vec<BulkEditResult>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    bulk_edit::reassign_weekly_entries(&db, from_position_id, to_position_id, date_range)
+}
+
+#[tauri::command]
+async fn reassign_compensation_records(
+    from_position_id: i64,
+    to_position_id: i64,
+    date_range: Option<(chrono::NaiveDate, chrono::NaiveDate)>,
+    state: State<'_, AppState>,
+) -> Result<Vec<BulkEditResult>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    bulk_edit::reassign_compensation_records(&db, from_position_id, to_position_id, date_range)
+}
+
+#[tauri::command]
+async fn bulk_update_weekly_entries(
+    ids: Vec<i64>,
+    patch: WeeklyEntryPatch,
+    state: State<'_, AppState>,
+) -> Result<Vec<BulkEditResult>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    bulk_edit::update_weekly_entries(&db, &ids, &patch)
+}
+
+// Yearly Income Entry commands
+#[tauri::command]
+async fn get_yearly_entries(state: State<'_, AppState>) -> Result<ListResult<YearlyIncomeEntry>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.list_yearly_entries()
+}
+
+#[tauri::command]
+async fn save_yearly_entry(entry: YearlyIncomeEntry, state: State<'_, AppState>) -> Result<i64, String> {
+    validation::validate_yearly_entry(&entry)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.save_yearly_entry(entry).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn delete_yearly_entry(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.delete_yearly_entry(id).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_financial_year_summaries(state: State<'_, AppState>) -> Result<Vec<FinancialYearSummary>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let weekly_entries = db.get_weekly_entries()?;
+    let yearly_entries = db.get_yearly_entries()?;
+    let exchange_rates = db.get_exchange_rates()?;
+    let records = db.get_all_compensation_records()?;
+
+    let mut summaries = calculations::calculate_financial_year_summaries(&weekly_entries, &yearly_entries, &exchange_rates);
+    calculations::add_equity_to_summaries(&mut summaries, &calculations::calculate_equity_by_fy(&records, &exchange_rates));
+    Ok(summaries)
+}
+
+#[tauri::command]
+async fn personal_earnings_percentile(financial_year: String, state: State<'_, AppState>) -> Result<f64, String> {
+    let fy = dates::FinancialYear::parse(&financial_year)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let weekly_entries = db.get_weekly_entries()?;
+    let yearly_entries = db.get_yearly_entries()?;
+    let exchange_rates = db.get_exchange_rates()?;
+    let records = db.get_all_compensation_records()?;
+
+    let mut summaries = calculations::calculate_financial_year_summaries(&weekly_entries, &yearly_entries, &exchange_rates);
+    calculations::add_equity_to_summaries(&mut summaries, &calculations::calculate_equity_by_fy(&records, &exchange_rates));
+    calculations::personal_earnings_percentile(&summaries, &fy)
+}
+
+#[tauri::command]
+async fn get_equity_by_fy(state: State<'_, AppState>) -> Result<Vec<EquityYear>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let records = db.get_all_compensation_records()?;
+    let exchange_rates = db.get_exchange_rates()?;
+
+    Ok(calculations::calculate_equity_by_fy(&records, &exchange_rates))
+}
+
+#[tauri::command]
+async fn get_hours_vs_earnings(year_basis: Option<dates::YearBasis>, state: State<'_, AppState>) -> Result<Vec<HoursEarningsPoint>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let weekly_entries = db.get_weekly_entries()?;
+    let exchange_rates = db.get_exchange_rates()?;
+
+    Ok(calculations::calculate_hours_vs_earnings(&weekly_entries, &exchange_rates, year_basis.unwrap_or_default()))
+}
+
+#[tauri::command]
+async fn detect_missing_weeks(position_id: i64, financial_year: String, state: State<'_, AppState>) -> Result<Vec<chrono::NaiveDate>, String> {
+    let fy = dates::FinancialYear::parse(&financial_year)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let position = db.get_positions()?
+        .into_iter()
+        .find(|p| p.id == Some(position_id))
+        .ok_or_else(|| format!("Position {} not found", position_id))?;
+    let weekly_entries = db.get_weekly_entries()?;
+
+    Ok(calculations::detect_missing_weeks(&position, &fy, &weekly_entries, chrono::Local::now().date_naive()))
+}
+
+#[tauri::command]
+async fn allowance_summary(position_id: i64, financial_year: String, state: State<'_, AppState>) -> Result<AllowanceSummary, String> {
+    let fy = dates::FinancialYear::parse(&financial_year)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let position = db.get_positions()?
+        .into_iter()
+        .find(|p| p.id == Some(position_id))
+        .ok_or_else(|| format!("Position {} not found", position_id))?;
+    let records = db.get_compensation_records(position_id)?;
+
+    calculations::calculate_allowance_summary(&position, &fy, &records)
+}
+
+#[tauri::command]
+async fn progression_scorecard(position_id: i64, state: State<'_, AppState>) -> Result<ProgressionScorecard, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let position = db.get_position(position_id)?
+        .ok_or_else(|| format!("Position {} not found", position_id))?;
+    let records = db.get_compensation_records(position_id)?;
+
+    calculations::progression_scorecard(&position, &records, chrono::Local::now().date_naive())
+}
+
+#[tauri::command]
+async fn real_pay_cut(financial_year: String, state: State<'_, AppState>) -> Result<RealPayCut, String> {
+    let fy = dates::FinancialYear::parse(&financial_year)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let positions = db.get_positions()?;
+    let records = db.get_all_compensation_records()?;
+    let exchange_rates = db.get_exchange_rates()?;
+
+    calculations::calculate_real_pay_cut(&fy, &positions, &records, &exchange_rates)
+}
+
+#[tauri::command]
+async fn bracket_creep_analysis(state: State<'_, AppState>) -> Result<BracketCreepReport, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let summaries = calculations::calculate_financial_year_summaries(
+        &db.get_weekly_entries()?,
+        &db.get_yearly_entries()?,
+        &db.get_exchange_rates()?,
+    );
+
+    Ok(calculations::bracket_creep_analysis(&summaries))
+}
+
+#[tauri::command]
+async fn super_contributions_for_fy(financial_year: String, state: State<'_, AppState>) -> Result<SuperContributionSummary, String> {
+    let fy = dates::FinancialYear::parse(&financial_year)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let weekly_entries = db.get_weekly_entries()?;
+    let positions = db.positions_active_between(fy.start(), fy.end())?;
+    let records = db.get_all_compensation_records()?;
+    let exchange_rates = db.get_exchange_rates()?;
+
+    Ok(calculations::calculate_super_contributions_for_fy(&fy, &weekly_entries, &positions, &records, &exchange_rates))
+}
+
+#[tauri::command]
+async fn sacrifice_headroom(financial_year: String, state: State<'_, AppState>) -> Result<SacrificeHeadroom, String> {
+    let fy = dates::FinancialYear::parse(&financial_year)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let weekly_entries = db.get_weekly_entries()?;
+    let positions = db.positions_active_between(fy.start(), fy.end())?;
+    let records = db.get_all_compensation_records()?;
+    let exchange_rates = db.get_exchange_rates()?;
+
+    Ok(calculations::calculate_sacrifice_headroom(&fy, &weekly_entries, &positions, &records, &exchange_rates))
+}
+
+#[tauri::command]
+async fn combined_tax_position(financial_year: String, state: State<'_, AppState>) -> Result<CombinedTaxPosition, String> {
+    let fy = dates::FinancialYear::parse(&financial_year)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let positions = db.get_positions()?;
+    let records = db.get_all_compensation_records()?;
+    let weekly_entries = db.get_weekly_entries()?;
+    let yearly_entries = db.get_yearly_entries()?;
+    let exchange_rates = db.get_exchange_rates()?;
+    let insurance_policies = db.get_insurance_policies()?;
+    let has_help_debt = db.get_user_profile()?.map(|p| p.has_help_debt).unwrap_or(false);
+
+    let mut combined = calculations::calculate_combined_tax_position(
+        &fy, &positions, &records, &weekly_entries, &yearly_entries, &exchange_rates, has_help_debt,
+    );
+    calculations::apply_insurance_deductions(&mut combined, &insurance_policies, &fy, has_help_debt);
+    let partner_income = calculations::partner_income_for(&db.get_partner_income_entries()?, &fy);
+    let household: HouseholdSettings = db.get_setting(calculations::HOUSEHOLD_SETTINGS_KEY)?;
+    calculations::apply_medicare_levy_surcharge(&mut combined, partner_income, &household, &fy);
+    Ok(combined)
+}
+
+#[tauri::command]
+async fn tax_time_summary(financial_year: String, state: State<'_, AppState>) -> Result<TaxTimeSummary, String> {
+    let fy = dates::FinancialYear::parse(&financial_year)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let positions = db.get_positions()?;
+    let records = db.get_all_compensation_records()?;
+    let weekly_entries = db.get_weekly_entries()?;
+    let yearly_entries = db.get_yearly_entries()?;
+    let exchange_rates = db.get_exchange_rates()?;
+
+    Ok(calculations::tax_time_summary(&fy, &positions, &records, &weekly_entries, &yearly_entries, &exchange_rates))
+}
+
+#[tauri::command]
+async fn reverse_engineer_base(net_weekly: f64, financial_year: String, claims_threshold: bool) -> Result<ReverseBaseEstimate, String> {
+    let fy = dates::FinancialYear::parse(&financial_year)?;
+    calculations::reverse_engineer_base(net_weekly, &fy, claims_threshold)
+}
+
+#[tauri::command]
+async fn gross_for_target_net(target_net: f64, financial_year: String, state: State<'_, AppState>) -> Result<TargetNetEstimate, String> {
+    let fy = dates::FinancialYear::parse(&financial_year)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let profile = db.get_user_profile()?;
+    calculations::gross_for_target_net(target_net, &fy, &profile)
+}
+
+#[tauri::command]
+async fn novated_lease_analysis(vehicle_cost: f64, lease_term_months: u32, annual_km: f64, marginal_rate: f64) -> Result<NovatedLeaseAnalysis, String> {
+    calculations::novated_lease_analysis(vehicle_cost, lease_term_months, annual_km, marginal_rate)
+}
+
+#[tauri::command]
+async fn get_super_cap_status(financial_year: String, state: State<'_, AppState>) -> Result<SuperCapStatus, String> {
+    let fy = dates::FinancialYear::parse(&financial_year)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let through = fy.max(dates::FinancialYear::containing(chrono::Local::now().date_naive()));
+
+    super_cap_statuses(&db, &through)?
+        .into_iter()
+        .find(|s| s.contributions.financial_year == fy.label())
+        .ok_or_else(|| format!("No super contributions on record for {}", fy.label()))
+}
+
+#[tauri::command]
+async fn super_payment_compliance(position_id: i64, financial_year: String, state: State<'_, AppState>) -> Result<SuperComplianceReport, String> {
+    let fy = dates::FinancialYear::parse(&financial_year)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let position = db.get_position(position_id)?
+        .ok_or_else(|| format!("Position {} not found", position_id))?;
+    let records = db.get_compensation_records(position_id)?;
+    let weekly_entries = db.get_weekly_entries()?;
+    calculations::calculate_super_compliance(&position, &records, &weekly_entries, &fy)
+}
+
+#[tauri::command]
+async fn resignation_timing(position_id: i64, window_days: Option<u32>, state: State<'_, AppState>) -> Result<ResignationTimingAdvice, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let position = db.get_position(position_id)?
+        .ok_or_else(|| format!("Position {} not found", position_id))?;
+    let records = db.get_compensation_records(position_id)?;
+    calculations::calculate_resignation_timing(&position, &records, chrono::Local::now().date_naive(), window_days)
+}
+
+#[tauri::command]
+async fn plan_resignation(
+    position_id: i64,
+    proposed_last_day: chrono::NaiveDate,
+    annual_leave_hours: Option<f64>,
+    state: State<'_, AppState>,
+) -> Result<ResignationPlan, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let position = db.get_position(position_id)?
+        .ok_or_else(|| format!("Position {} not found", position_id))?;
+    let positions = db.get_positions()?;
+    let records = db.get_all_compensation_records()?;
+    let profile = db.get_user_profile()?;
+    let rules = lsl::rules_with_overrides(&db.get_setting::<Vec<LslRule>>(lsl::SETTINGS_KEY)?);
+    let lsl_status = calculations::lsl_accrual(&positions, &records, &rules, &profile, proposed_last_day);
+
+    calculations::calculate_resignation_plan(
+        &position, &records, &lsl_status, annual_leave_hours, chrono::Local::now().date_naive(), proposed_last_day,
+    )
+}
+
+#[tauri::command]
+async fn bonus_sacrifice_analysis(
+    bonus_amount: f64,
+    marginal_rate: f64,
+    financial_year: String,
+    state: State<'_, AppState>,
+) -> Result<BonusSacrificeAnalysis, String> {
+    let fy = dates::FinancialYear::parse(&financial_year)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let through = fy.max(dates::FinancialYear::containing(chrono::Local::now().date_naive()));
+
+    // A year with nothing on record has its whole cap free
+    let cap_headroom = super_cap_statuses(&db, &through)?
+        .into_iter()
+        .find(|s| s.contributions.financial_year == fy.label())
+        .map(|s| s.headroom)
+        .unwrap_or_else(|| tax::concessional_cap(&fy));
+    calculations::calculate_bonus_sacrifice(bonus_amount, marginal_rate, cap_headroom, &fy)
+}
+
+// Every year's concessional contributions against the cap, through the given year
+fn super_cap_statuses(db: &Database, through: &dates::FinancialYear) -> Result<Vec<SuperCapStatus>, String> {
+    let positions = db.get_positions()?;
+    let records = db.get_all_compensation_records()?;
+    let weekly_entries = db.get_weekly_entries()?;
+    let yearly_entries = db.get_yearly_entries()?;
+    let exchange_rates = db.get_exchange_rates()?;
+    let super_balance = db.get_user_profile()?.and_then(|p| p.total_super_balance);
+
+    let contributions = calculations::concessional_contributions_by_fy(
+        &positions, &records, &weekly_entries, &yearly_entries, &exchange_rates, through,
+    );
+    Ok(calculations::calculate_super_cap_status(&contributions, super_balance))
+}
+
+// Exchange rate commands
+#[tauri::command]
+async fn get_exchange_rates(state: State<'_, AppState>) -> Result<Vec<ExchangeRate>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_exchange_rates()
+}
+
+#[tauri::command]
+async fn save_exchange_rate(rate: ExchangeRate, state: State<'_, AppState>) -> Result<i64, String> {
+    validation::validate_exchange_rate(&rate)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.save_exchange_rate(rate).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn delete_exchange_rate(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.delete_exchange_rate(id).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Award rate commands
+#[tauri::command]
+async fn get_award_rates(state: State<'_, AppState>) -> Result<Vec<AwardRate>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_award_rates()
 }
 
 #[tauri::command]
-async fn get_user_profile(state: State<'_, AppState>) -> Result<Option<UserProfile>, String> {
+async fn save_award_rate(rate: AwardRate, state: State<'_, AppState>) -> Result<i64, String> {
+    validation::validate_award_rate(&rate)?;
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.get_user_profile()
+    db.save_award_rate(rate).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-async fn save_user_profile(profile: UserProfile, state: State<'_, AppState>) -> Result<(), String> {
+async fn delete_award_rate(id: i64, state: State<'_, AppState>) -> Result<(), String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.save_user_profile(profile).map_err(|e| e.to_string())
+    db.delete_award_rate(id).map_err(|e| e.to_string())?;
+    Ok(())
 }
 
+// Insurance policy commands
 #[tauri::command]
-async fn get_positions(state: State<'_, AppState>) -> Result<Vec<Position>, String> {
+async fn get_insurance_policies(state: State<'_, AppState>) -> Result<Vec<InsurancePolicy>, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.get_positions()
+    db.get_insurance_policies()
 }
 
 #[tauri::command]
-async fn save_position(position: Position, state: State<'_, AppState>) -> Result<i64, String> {
+async fn save_insurance_policy(policy: InsurancePolicy, state: State<'_, AppState>) -> Result<i64, String> {
+    validation::validate_insurance_policy(&policy)?;
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.save_position(position).map_err(|e| e.to_string())
+    db.save_insurance_policy(policy).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-async fn delete_position(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+async fn delete_insurance_policy(id: i64, state: State<'_, AppState>) -> Result<(), String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.delete_position(id).map_err(|e| e.to_string())?;
-    Ok(())
+    db.delete_insurance_policy(id).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-async fn get_compensation_records(position_id: i64, state: State<'_, AppState>) -> Result<Vec<CompensationRecord>, String> {
+async fn get_insurance_settings(state: State<'_, AppState>) -> Result<InsuranceSettings, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.get_compensation_records(position_id)
+    db.get_setting(calculations::INSURANCE_SETTINGS_KEY)
 }
 
 #[tauri::command]
-async fn save_compensation_record(record: CompensationRecord, state: State<'_, AppState>) -> Result<i64, String> {
+async fn save_insurance_settings(settings: InsuranceSettings, state: State<'_, AppState>) -> Result<(), String> {
+    validation::validate_insurance_settings(&settings)?;
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.save_compensation_record(record).map_err(|e| e.to_string())
+    db.save_setting(calculations::INSURANCE_SETTINGS_KEY, &settings).map_err(|e| e.to_string())
 }
 
+// Household commands
 #[tauri::command]
-async fn delete_compensation_record(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+async fn get_partner_income_entries(state: State<'_, AppState>) -> Result<Vec<PartnerIncomeEntry>, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.delete_compensation_record(id).map_err(|e| e.to_string())?;
-    Ok(())
+    db.get_partner_income_entries()
 }
 
 #[tauri::command]
-async fn get_weekly_entries(state: State<'_, AppState>) -> Result<Vec<WeeklyCompensationEntry>, String> {
+async fn save_partner_income_entry(entry: PartnerIncomeEntry, state: State<'_, AppState>) -> Result<i64, String> {
+    validation::validate_partner_income_entry(&entry)?;
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.get_weekly_entries()
+    db.save_partner_income_entry(entry).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-async fn save_weekly_entry(entry: WeeklyCompensationEntry, state: State<'_, AppState>) -> Result<i64, String> {
+async fn delete_partner_income_entry(id: i64, state: State<'_, AppState>) -> Result<(), String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.save_weekly_entry(entry).map_err(|e| e.to_string())
+    db.delete_partner_income_entry(id).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-async fn delete_weekly_entry(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+async fn get_tags(state: State<'_, AppState>) -> Result<Vec<Tag>, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.delete_weekly_entry(id).map_err(|e| e.to_string())?;
-    Ok(())
+    db.get_tags()
 }
 
 #[tauri::command]
-async fn calculate_earnings_analysis(state: State<'_, AppState>) -> Result<EarningsAnalysis, String> {
+async fn create_tag(name: String, state: State<'_, AppState>) -> Result<i64, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    let positions = db.get_positions()?;
-    let profile = db.get_user_profile()?;
-    
-    Ok(calculations::calculate_earnings_analysis(&positions, &profile))
+    let tag = Tag { id: None, name };
+    validation::validate_tag(&tag, &db.get_tags()?)?;
+    db.save_tag(tag).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-async fn calculate_loyalty_tax(state: State<'_, AppState>) -> Result<LoyaltyTaxAnalysis, String> {
+async fn rename_tag(id: i64, name: String, state: State<'_, AppState>) -> Result<(), String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    let positions = db.get_positions()?;
-    
-    Ok(calculations::calculate_loyalty_tax(&positions))
+    if !db.row_exists("tags", id)? {
+        return Err(format!("Tag {} not found", id));
+    }
+    let tag = Tag { id: Some(id), name };
+    validation::validate_tag(&tag, &db.get_tags()?)?;
+    db.save_tag(tag).map(|_| ()).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-async fn generate_resume_export(state: State<'_, AppState>) -> Result<ResumeExport, String> {
+async fn delete_tag(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.delete_tag(id)
+}
+
+#[tauri::command]
+async fn get_entity_tags(state: State<'_, AppState>) -> Result<Vec<EntityTag>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_entity_tags()
+}
+
+#[tauri::command]
+async fn assign_tag(entity_tag: EntityTag, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.assign_tag(&entity_tag).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn unassign_tag(entity_tag: EntityTag, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.unassign_tag(&entity_tag).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_household_settings(state: State<'_, AppState>) -> Result<HouseholdSettings, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_setting(calculations::HOUSEHOLD_SETTINGS_KEY)
+}
+
+#[tauri::command]
+async fn save_household_settings(settings: HouseholdSettings, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.save_setting(calculations::HOUSEHOLD_SETTINGS_KEY, &settings).map_err(|e| e.to_string())
+}
+
+// The year's taxable income from the combined tax position, against single or family thresholds
+#[tauri::command]
+async fn health_cover_position(financial_year: String, state: State<'_, AppState>) -> Result<HealthCoverPosition, String> {
+    let fy = dates::FinancialYear::parse(&financial_year)?;
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    let positions = db.get_positions()?;
     let profile = db.get_user_profile()?;
-    
-    Ok(calculations::generate_resume_export(&positions, &profile))
+    let combined = calculations::calculate_combined_tax_position(
+        &fy,
+        &db.get_positions()?,
+        &db.get_all_compensation_records()?,
+        &db.get_weekly_entries()?,
+        &db.get_yearly_entries()?,
+        &db.get_exchange_rates()?,
+        profile.as_ref().is_some_and(|p| p.has_help_debt),
+    );
+    let partner_income = calculations::partner_income_for(&db.get_partner_income_entries()?, &fy);
+    let household: HouseholdSettings = db.get_setting(calculations::HOUSEHOLD_SETTINGS_KEY)?;
+    let oldest_age = household.oldest_age_on_policy
+        .or_else(|| profile.as_ref().map(|p| dates::age_on(p.date_of_birth, fy.end())))
+        .unwrap_or(0);
+
+    Ok(calculations::health_cover_position(&fy, combined.total_taxable_income, partner_income, oldest_age))
 }
 
 #[tauri::command]
-async fn get_all_compensation_records(state: State<'_, AppState>) -> Result<Vec<CompensationRecord>, String> {
+async fn check_award_compliance(position_id: i64, state: State<'_, AppState>) -> Result<AwardComplianceReport, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.get_all_compensation_records()
+    let position = db.get_position(position_id)?
+        .ok_or_else(|| format!("Position {} not found", position_id))?;
+    let records = db.get_compensation_records(position_id)?;
+    let award_rates = db.get_award_rates()?;
+    let weekly_entries = db.get_weekly_entries()?;
+    calculations::calculate_award_compliance(&position, &records, &award_rates, &weekly_entries)
 }
 
-// Yearly Income Entry commands
+// Long service leave commands
 #[tauri::command]
-async fn get_yearly_entries(state: State<'_, AppState>) -> Result<Vec<YearlyIncomeEntry>, String> {
+async fn get_lsl_rules(state: State<'_, AppState>) -> Result<Vec<LslRule>, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.get_yearly_entries()
+    Ok(lsl::rules_with_overrides(&db.get_setting::<Vec<LslRule>>(lsl::SETTINGS_KEY)?))
 }
 
 #[tauri::command]
-async fn save_yearly_entry(entry: YearlyIncomeEntry, state: State<'_, AppState>) -> Result<i64, String> {
+async fn save_lsl_rules(rules: Vec<LslRule>, state: State<'_, AppState>) -> Result<(), String> {
+    rules.iter().try_for_each(validation::validate_lsl_rule)?;
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.save_yearly_entry(entry).map_err(|e| e.to_string())
+    db.save_setting(lsl::SETTINGS_KEY, &rules).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-async fn delete_yearly_entry(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+async fn get_lsl_status(state: State<'_, AppState>) -> Result<LslStatus, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.delete_yearly_entry(id).map_err(|e| e.to_string())?;
+    let positions = db.get_positions()?;
+    let records = db.get_all_compensation_records()?;
+    let profile = db.get_user_profile()?;
+    let rules = lsl::rules_with_overrides(&db.get_setting::<Vec<LslRule>>(lsl::SETTINGS_KEY)?);
+    Ok(calculations::lsl_accrual(&positions, &records, &rules, &profile, chrono::Local::now().date_naive()))
+}
+
+// Migration review commands
+#[tauri::command]
+async fn get_default_backfill_warnings(state: State<'_, AppState>) -> Result<Vec<BackfillWarning>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_default_backfill_warnings()
+}
+
+#[tauri::command]
+async fn resolve_backfill_warning(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.resolve_backfill_warning(id).map_err(|e| e.to_string())?;
     Ok(())
 }
 
+#[tauri::command]
+async fn repair_json_columns(state: State<'_, AppState>) -> Result<Vec<RepairLog>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.repair_json_columns()
+}
+
 // Data export/import commands
 #[tauri::command]
 async fn export_all_data(state: State<'_, AppState>) -> Result<DataExport, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    
-    let user_profile = db.get_user_profile()?;
-    let positions = db.get_positions()?;
-    let compensation_records = db.get_all_compensation_records()?;
-    let weekly_entries = db.get_weekly_entries()?;
-    let yearly_entries = db.get_yearly_entries()?;
-    
-    Ok(DataExport {
-        user_profile,
-        positions,
-        compensation_records,
-        weekly_entries,
-        yearly_entries,
-        export_date: chrono::Utc::now(),
-        version: "1.0.0".to_string(),
-    })
+    backup::export_all_data(&db)
 }
 
 #[tauri::command]
-async fn import_all_data(data: DataExport, state: State<'_, AppState>) -> Result<ImportResult, String> {
+async fn export_anonymized(state: State<'_, AppState>) -> Result<AnonymizedExport, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    
-    let mut profile_imported = false;
-    let mut positions_count = 0;
-    let mut compensation_count = 0;
-    let mut weekly_count = 0;
-    let mut yearly_count = 0;
-    
-    // Import profile
-    if let Some(profile) = data.user_profile {
-        db.save_user_profile(profile).map_err(|e| e.to_string())?;
-        profile_imported = true;
+    let data = backup::export_all_data(&db)?;
+    anonymize::anonymize(&data, chrono::Local::now().date_naive())
+}
+
+// Attached to calculation bug reports: inputs without identity, settings and outputs
+#[tauri::command]
+async fn export_diagnostic_bundle(state: State<'_, AppState>) -> Result<DiagnosticBundle, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    diagnostics::export_bundle(&db, env!("CARGO_PKG_VERSION"), chrono::Local::now().date_naive())
+}
+
+// Reproduces a user's bundle in a scratch in-memory database; the user's own data isn't touched
+#[tauri::command]
+async fn import_diagnostic_bundle(bundle: DiagnosticBundle) -> Result<DiagnosticBundle, String> {
+    if !cfg!(debug_assertions) {
+        return Err("Diagnostic bundles can only be loaded in development builds".to_string());
     }
-    
-    // Import positions
-    for position in data.positions {
-        db.save_position(position).map_err(|e| e.to_string())?;
-        positions_count += 1;
+    let scratch = Database::new(":memory:".into()).map_err(|e| e.to_string())?;
+    diagnostics::import_bundle(&scratch, &bundle)?;
+    diagnostics::export_bundle(&scratch, env!("CARGO_PKG_VERSION"), chrono::Local::now().date_naive())
+}
+
+#[tauri::command]
+async fn import_all_data(data: DataExport, state: State<'_, AppState>) -> Result<ImportResult, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    backup::import_all_data(&db, data, chrono::Local::now().date_naive())
+}
+
+// Mapped CSV import commands
+#[tauri::command]
+async fn get_import_mappings(state: State<'_, AppState>) -> Result<Vec<ImportMapping>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let saved: Vec<ImportMapping> = db.get_setting(import_mapping::SETTINGS_KEY)?;
+    Ok(import_mapping::presets().into_iter().chain(saved).collect())
+}
+
+#[tauri::command]
+async fn save_import_mapping(mapping: ImportMapping, state: State<'_, AppState>) -> Result<(), String> {
+    validation::validate_import_mapping(&mapping)?;
+    if import_mapping::presets().iter().any(|p| p.name.eq_ignore_ascii_case(mapping.name.trim())) {
+        return Err(format!("'{}' is a built-in mapping, save it under another name", mapping.name));
     }
-    
-    // Import compensation records
-    for record in data.compensation_records {
-        db.save_compensation_record(record).map_err(|e| e.to_string())?;
-        compensation_count += 1;
+
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let mut saved: Vec<ImportMapping> = db.get_setting(import_mapping::SETTINGS_KEY)?;
+    saved.retain(|m| !m.name.eq_ignore_ascii_case(mapping.name.trim()));
+    saved.push(ImportMapping { builtin: false, ..mapping });
+    db.save_setting(import_mapping::SETTINGS_KEY, &saved).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn delete_import_mapping(name: String, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let mut saved: Vec<ImportMapping> = db.get_setting(import_mapping::SETTINGS_KEY)?;
+    saved.retain(|m| !m.name.eq_ignore_ascii_case(name.trim()));
+    db.save_setting(import_mapping::SETTINGS_KEY, &saved).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn import_with_mapping(csv_text: String, mapping: ImportMapping, dry_run: bool, state: State<'_, AppState>) -> Result<MappedImportResult, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    import_mapping::import_with_mapping(&db, &csv_text, &mapping, dry_run)
+}
+
+#[tauri::command]
+async fn get_demographic_settings(state: State<'_, AppState>) -> Result<DemographicSettings, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_setting(calculations::DEMOGRAPHIC_SETTINGS_KEY)
+}
+
+#[tauri::command]
+async fn save_demographic_settings(settings: DemographicSettings, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.save_setting(calculations::DEMOGRAPHIC_SETTINGS_KEY, &settings).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_wage_index(state: State<'_, AppState>) -> Result<WageIndex, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_setting(calculations::WAGE_INDEX_SETTINGS_KEY)
+}
+
+#[tauri::command]
+async fn save_wage_index(index: WageIndex, state: State<'_, AppState>) -> Result<(), String> {
+    validation::validate_wage_index(&index)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.save_setting(calculations::WAGE_INDEX_SETTINGS_KEY, &index).map_err(|e| e.to_string())
+}
+
+// Projection commands
+#[tauri::command]
+async fn get_projection_assumptions(state: State<'_, AppState>) -> Result<ProjectionAssumptions, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_setting(calculations::PROJECTION_SETTINGS_KEY)
+}
+
+#[tauri::command]
+async fn save_projection_assumptions(assumptions: ProjectionAssumptions, state: State<'_, AppState>) -> Result<(), String> {
+    validation::validate_projection_assumptions(&assumptions)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.save_setting(calculations::PROJECTION_SETTINGS_KEY, &assumptions).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn project_super(state: State<'_, AppState>) -> Result<SuperProjection, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let profile = db.get_user_profile()?.ok_or("Set up your profile to project your super")?;
+    let positions = db.get_positions()?;
+    let records = db.get_all_compensation_records()?;
+    let assumptions: ProjectionAssumptions = db.get_setting(calculations::PROJECTION_SETTINGS_KEY)?;
+    let insurance_policies = db.get_insurance_policies()?;
+    Ok(calculations::project_super(&positions, &records, &profile, &assumptions, &insurance_policies, chrono::Local::now().date_naive()))
+}
+
+#[tauri::command]
+async fn project_salary(position_id: i64, years: Option<u32>, state: State<'_, AppState>) -> Result<SalaryProjection, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let position = db.get_position(position_id)?
+        .ok_or_else(|| format!("Position {} not found", position_id))?;
+    let records = db.get_compensation_records(position_id)?;
+    let assumptions: ProjectionAssumptions = db.get_setting(calculations::PROJECTION_SETTINGS_KEY)?;
+    calculations::project_salary(&position, &records, &assumptions, years)
+}
+
+// Reminder commands
+#[tauri::command]
+async fn get_upcoming_reminders(state: State<'_, AppState>) -> Result<Vec<Reminder>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let positions = db.get_positions()?;
+    let settings: ReminderSettings = db.get_setting(reminders::SETTINGS_KEY)?;
+    let dismissed = db.get_dismissed_reminders()?;
+    Ok(reminders::upcoming_reminders(&positions, &settings, &dismissed, chrono::Local::now().date_naive()))
+}
+
+#[tauri::command]
+async fn dismiss_reminder(reminder_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.dismiss_reminder(&reminder_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_reminder_settings(state: State<'_, AppState>) -> Result<ReminderSettings, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_setting(reminders::SETTINGS_KEY)
+}
+
+#[tauri::command]
+async fn save_reminder_settings(settings: ReminderSettings, state: State<'_, AppState>) -> Result<(), String> {
+    validation::validate_reminder_settings(&settings)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.save_setting(reminders::SETTINGS_KEY, &settings).map_err(|e| e.to_string())
+}
+
+// Financial year rollover commands
+#[tauri::command]
+async fn run_fy_rollover(new_fy: String, state: State<'_, AppState>) -> Result<RolloverReport, String> {
+    let fy = dates::FinancialYear::parse(&new_fy)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    rollover::run_fy_rollover(&db, &fy, chrono::Local::now().date_naive())
+}
+
+#[tauri::command]
+async fn get_rollover_status(state: State<'_, AppState>) -> Result<RolloverStatus, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    rollover::rollover_status(&db, chrono::Local::now().date_naive())
+}
+
+// Automatic export commands
+#[tauri::command]
+async fn get_auto_export_settings(state: State<'_, AppState>) -> Result<AutoExportSettings, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_setting(auto_export::SETTINGS_KEY)
+}
+
+#[tauri::command]
+async fn save_auto_export_settings(settings: AutoExportSettings, state: State<'_, AppState>) -> Result<(), String> {
+    validation::validate_auto_export_settings(&settings)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.save_setting(auto_export::SETTINGS_KEY, &settings).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_auto_export_status(state: State<'_, AppState>) -> Result<AutoExportStatus, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_setting(auto_export::STATUS_KEY)
+}
+
+// Checks on launch and then hourly; a failed export is reported to the UI and retried
+// on the next check, it never takes the app down.
+fn spawn_auto_export_scheduler(handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+        loop {
+            interval.tick().await;
+
+            let result = {
+                let state = handle.state::<AppState>();
+                let db = match state.db.lock() {
+                    Ok(db) => db,
+                    Err(e) => {
+                        eprintln!("Auto-export skipped: {}", e);
+                        continue;
+                    }
+                };
+                // The instance holding the database runs the exports
+                if db.is_read_only() {
+                    continue;
+                }
+                auto_export::run_if_due(&db, chrono::Utc::now())
+            };
+
+            let error = match result {
+                Ok(Some(status)) => status.last_error,
+                Ok(None) => None,
+                Err(e) => Some(e),
+            };
+            if let Some(message) = error {
+                eprintln!("Auto-export failed: {}", message);
+                let _ = handle.emit_all(AUTO_EXPORT_FAILED_EVENT, message);
+            }
+        }
+    });
+}
+
+// Watched folder commands
+#[tauri::command]
+async fn get_watch_folder_settings(state: State<'_, AppState>) -> Result<WatchFolderSettings, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_setting(watch_folder::SETTINGS_KEY)
+}
+
+#[tauri::command]
+async fn save_watch_folder_settings(settings: WatchFolderSettings, app: tauri::AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    validation::validate_watch_folder_settings(&settings)?;
+    {
+        let db = state.db.lock().map_err(|e| e.to_string())?;
+        db.save_setting(watch_folder::SETTINGS_KEY, &settings).map_err(|e| e.to_string())?;
     }
-    
-    // Import weekly entries
-    for entry in data.weekly_entries {
-        db.save_weekly_entry(entry).map_err(|e| e.to_string())?;
-        weekly_count += 1;
+    restart_folder_watcher(app, &settings, &state)
+}
+
+#[tauri::command]
+async fn set_folder_watch_enabled(enabled: bool, app: tauri::AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let settings = {
+        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let settings = WatchFolderSettings {
+            enabled,
+            ..db.get_setting(watch_folder::SETTINGS_KEY)?
+        };
+        validation::validate_watch_folder_settings(&settings)?;
+        db.save_setting(watch_folder::SETTINGS_KEY, &settings).map_err(|e| e.to_string())?;
+        settings
+    };
+    restart_folder_watcher(app, &settings, &state)
+}
+
+#[tauri::command]
+async fn get_pending_watched_imports(state: State<'_, AppState>) -> Result<Vec<PendingWatchedImport>, String> {
+    let pending = state.pending_imports.lock().map_err(|e| e.to_string())?;
+    let mut imports: Vec<PendingWatchedImport> = pending.values().cloned().collect();
+    imports.sort_by_key(|import| import.detected_at);
+    Ok(imports)
+}
+
+#[tauri::command]
+async fn confirm_watched_import(path: String, state: State<'_, AppState>) -> Result<WatchedImportResult, String> {
+    let import = state.pending_imports.lock().map_err(|e| e.to_string())?
+        .remove(&path)
+        .ok_or_else(|| format!("No pending import for {}", path))?;
+
+    let result = {
+        let db = state.db.lock().map_err(|e| e.to_string())?;
+        watch_folder::commit_preview(&db, import.preview)?
+    };
+    watch_folder::move_to_processed(std::path::Path::new(&path))?;
+    Ok(result)
+}
+
+#[tauri::command]
+async fn reject_watched_import(path: String, state: State<'_, AppState>) -> Result<(), String> {
+    state.pending_imports.lock().map_err(|e| e.to_string())?
+        .remove(&path)
+        .ok_or_else(|| format!("No pending import for {}", path))?;
+    watch_folder::move_to_failed(std::path::Path::new(&path), "Rejected when reviewing the import")?;
+    Ok(())
+}
+
+// Stops any running watcher and starts a new one when the settings enable it
+fn restart_folder_watcher(app: tauri::AppHandle, settings: &WatchFolderSettings, state: &AppState) -> Result<(), String> {
+    let mut watcher = state.folder_watcher.lock().map_err(|e| e.to_string())?;
+    *watcher = None;
+    if settings.enabled {
+        *watcher = folder_watch::start(app, settings);
     }
-    
-    // Import yearly entries
-    for entry in data.yearly_entries {
-        db.save_yearly_entry(entry).map_err(|e| e.to_string())?;
-        yearly_count += 1;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_database_status(state: State<'_, AppState>) -> Result<DatabaseStatus, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    Ok(db.status())
+}
+
+#[tauri::command]
+async fn get_last_modified_summary(state: State<'_, AppState>) -> Result<Vec<TableModified>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_last_modified_summary()
+}
+
+#[tauri::command]
+async fn get_app_mode(state: State<'_, AppState>) -> Result<AppMode, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    Ok(if db.is_read_only() { AppMode::ReadOnly } else { AppMode::Writable })
+}
+
+// Reopens the database for writing once the instance holding it has closed
+#[tauri::command]
+async fn promote_to_writable(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<AppMode, String> {
+    let mut held = state.instance_lock.lock().map_err(|e| e.to_string())?;
+    if held.is_some() {
+        return Ok(AppMode::Writable);
     }
-    
-    Ok(ImportResult {
-        success: true,
-        profile_imported,
-        positions_count,
-        compensation_count,
-        weekly_count,
-        yearly_count,
-    })
+
+    let lock = instance_lock::acquire(&state.db_path)?.ok_or(AppError::ReadOnly)?;
+    let db = Database::new(state.db_path.clone())
+        .map_err(|e| format!("Failed to open the database for writing: {}", e))?;
+    let watch_settings: WatchFolderSettings = db.get_setting(watch_folder::SETTINGS_KEY).unwrap_or_default();
+
+    *state.db.lock().map_err(|e| e.to_string())? = db;
+    *held = Some(lock);
+    drop(held);
+
+    restart_folder_watcher(app, &watch_settings, &state)?;
+    Ok(AppMode::Writable)
 }
 
 #[tauri::command]
@@ -215,35 +1744,70 @@ async fn clear_all_data(state: State<'_, AppState>) -> Result<(), String> {
     Ok(())
 }
 
+// Resolves the database location through Tauri so it matches the platform's app data dir
+struct TauriAppDataDir(tauri::PathResolver);
+
+impl paths::AppDataDir for TauriAppDataDir {
+    fn app_data_dir(&self) -> Option<PathBuf> {
+        self.0.app_data_dir()
+    }
+}
+
 fn main() {
     tauri::Builder::default()
         .setup(|app| {
             // Get the app data directory for reliable database storage
-            let app_data_dir = match app.path_resolver().app_data_dir() {
-                Some(dir) => dir,
-                None => {
-                    eprintln!("FATAL: Failed to resolve app data directory");
-                    return Err("Failed to resolve app data directory".into());
+            let db_path = match paths::database_path(&TauriAppDataDir(app.path_resolver())) {
+                Ok(path) => path,
+                Err(e) => {
+                    eprintln!("FATAL: {}", e);
+                    return Err(e.into());
                 }
             };
             
-            // Create the directory if it doesn't exist
-            if let Err(e) = std::fs::create_dir_all(&app_data_dir) {
-                eprintln!("FATAL: Failed to create app data directory: {}", e);
-                return Err(format!("Failed to create app data directory: {}", e).into());
-            }
-            
-            let db_path = app_data_dir.join("careerflow.db");
-            
-            let db = match Database::new(db_path) {
+            // A second instance opens the database read-only rather than writing alongside the first
+            let instance_lock = match instance_lock::acquire(&db_path) {
+                Ok(lock) => lock,
+                Err(e) => {
+                    eprintln!("FATAL: {}", e);
+                    return Err(e.into());
+                }
+            };
+            let opened = match instance_lock {
+                Some(_) => Database::new(db_path.clone()),
+                None => Database::open_read_only(db_path.clone()),
+            };
+            let db = match opened {
                 Ok(database) => database,
                 Err(e) => {
                     eprintln!("FATAL: Failed to initialize database: {}", e);
                     return Err(format!("Failed to initialize database: {}", e).into());
                 }
             };
+            if let Some(error) = db.status().error {
+                eprintln!("Database opened at its previous version: {}", error);
+            }
+            if let Some(rollback) = db.status().possible_data_rollback {
+                let tables: Vec<String> = rollback.tables.iter().map(|t| t.table_name.clone()).collect();
+                eprintln!("Database looks older than at the last clean shutdown: {}", tables.join(", "));
+            }
             
-            app.manage(AppState { db: Mutex::new(db) });
+            let watch_settings: WatchFolderSettings = db.get_setting(watch_folder::SETTINGS_KEY).unwrap_or_default();
+            let folder_watcher = if watch_settings.enabled && !db.is_read_only() {
+                folder_watch::start(app.handle(), &watch_settings)
+            } else {
+                None
+            };
+
+            app.manage(AppState {
+                db: Mutex::new(db),
+                db_path,
+                instance_lock: Mutex::new(instance_lock),
+                folder_watcher: Mutex::new(folder_watcher),
+                pending_imports: Mutex::new(HashMap::new()),
+                analysis_in_flight: InFlight::new(),
+            });
+            spawn_auto_export_scheduler(app.handle());
             
             Ok(())
         })
@@ -251,29 +1815,188 @@ fn main() {
             get_user_profile,
             save_user_profile,
             get_positions,
+            archive_position,
+            unarchive_position,
+            get_position_summaries,
+            get_position,
+            positions_active_between,
             save_position,
+            transition_position,
             delete_position,
             get_compensation_records,
             get_all_compensation_records,
+            check_compensation_record,
             save_compensation_record,
+            clone_compensation_record,
+            merge_compensation_records,
+            upgrade_record_to_exact,
+            get_fuzzy_records,
+            get_record_changes,
+            check_tax_withheld,
+            calculate_back_pay,
+            get_record_lineage,
             delete_compensation_record,
             calculate_earnings_analysis,
+            get_data_quality,
+            get_overtime_profile,
+            get_earnings_seasonality,
+            income_risk_score,
+            preference_mismatch_check,
+            save_scenario,
+            list_scenarios,
+            delete_scenario,
+            rerun_scenario,
+            save_position_as_template,
+            list_templates,
+            delete_template,
+            create_position_from_template,
+            save_analysis_snapshot,
+            get_analysis_snapshots,
+            compare_snapshots,
+            delete_analysis_snapshot,
             calculate_loyalty_tax,
+            counterfactual_earnings,
+            promotion_velocity,
+            industry_exposure,
             generate_resume_export,
+            skill_recency_ranking,
+            project_lifetime_earnings,
+            qualification_roi,
+            export_analysis_report_pdf,
+            export_analysis_report_html,
+            income_composition,
+            col_adjusted_earnings,
+            get_position_statistics,
+            overtime_rate_effect,
+            overtime_hours_for_target,
+            employer_super,
+            net_hourly_rate,
+            commute_adjusted_hourly_rate,
+            pay_cycle_breakdown,
+            model_four_day_week,
+            calculate_co_contribution,
+            calculate_div293,
+            sacrifice_benefit_curve,
+            tax_rate_curve,
             get_weekly_entries,
             save_weekly_entry,
+            check_weekly_entry,
+            normalize_week_endings,
             delete_weekly_entry,
+            reassign_weekly_entries,
+            reassign_compensation_records,
+            bulk_update_weekly_entries,
             get_yearly_entries,
             save_yearly_entry,
             delete_yearly_entry,
+            get_financial_year_summaries,
+            get_equity_by_fy,
+            personal_earnings_percentile,
+            get_hours_vs_earnings,
+            super_contributions_for_fy,
+            sacrifice_headroom,
+            combined_tax_position,
+            tax_time_summary,
+            reverse_engineer_base,
+            gross_for_target_net,
+            novated_lease_analysis,
+            get_super_cap_status,
+            super_payment_compliance,
+            bonus_sacrifice_analysis,
+            resignation_timing,
+            plan_resignation,
+            detect_missing_weeks,
+            real_pay_cut,
+            bracket_creep_analysis,
+            progression_scorecard,
+            allowance_summary,
+            get_exchange_rates,
+            save_exchange_rate,
+            delete_exchange_rate,
+            get_award_rates,
+            save_award_rate,
+            delete_award_rate,
+            get_insurance_policies,
+            save_insurance_policy,
+            delete_insurance_policy,
+            get_insurance_settings,
+            save_insurance_settings,
+            get_partner_income_entries,
+            get_tags,
+            create_tag,
+            rename_tag,
+            delete_tag,
+            get_entity_tags,
+            assign_tag,
+            unassign_tag,
+            save_partner_income_entry,
+            delete_partner_income_entry,
+            get_household_settings,
+            save_household_settings,
+            health_cover_position,
+            check_award_compliance,
+            get_lsl_rules,
+            save_lsl_rules,
+            get_lsl_status,
+            get_database_status,
+            get_last_modified_summary,
+            get_app_mode,
+            promote_to_writable,
+            get_default_backfill_warnings,
+            resolve_backfill_warning,
+            repair_json_columns,
             export_all_data,
+            export_anonymized,
+            export_diagnostic_bundle,
+            import_diagnostic_bundle,
             import_all_data,
+            get_import_mappings,
+            save_import_mapping,
+            delete_import_mapping,
+            import_with_mapping,
+            get_projection_assumptions,
+            save_projection_assumptions,
+            project_super,
+            project_salary,
+            get_wage_index,
+            save_wage_index,
+            get_demographic_settings,
+            save_demographic_settings,
+            get_upcoming_reminders,
+            dismiss_reminder,
+            get_reminder_settings,
+            save_reminder_settings,
+            run_fy_rollover,
+            get_rollover_status,
+            get_auto_export_settings,
+            save_auto_export_settings,
+            get_auto_export_status,
+            get_watch_folder_settings,
+            save_watch_folder_settings,
+            set_folder_watch_enabled,
+            get_pending_watched_imports,
+            confirm_watched_import,
+            reject_watched_import,
             clear_all_data
         ])
-        .run(tauri::generate_context!())
+        .build(tauri::generate_context!())
         .unwrap_or_else(|e| {
             eprintln!("FATAL: Error while running Tauri application: {}", e);
             std::process::exit(1);
+        })
+        .run(|handle, event| {
+            // Release the database for another instance; a crash leaves the lock to the liveness check
+            if let tauri::RunEvent::Exit = event {
+                let state = handle.state::<AppState>();
+                if let Ok(db) = state.db.lock() {
+                    if let Err(e) = db.record_clean_shutdown() {
+                        eprintln!("Failed to record shutdown state: {}", e);
+                    }
+                }
+                if let Ok(mut lock) = state.instance_lock.lock() {
+                    *lock = None;
+                };
+            }
         });
 }
 
@@ -1,6 +1,6 @@
 import { useState } from 'react';
 import { useMutation, useQueryClient, useQuery } from '@tanstack/react-query';
-import { invoke } from '../lib/tauri';
+import { invoke, saveCompensationRecord } from '../lib/tauri';
 import {
     Dialog,
     DialogContent,
@@ -26,6 +26,7 @@ import {
 } from 'lucide-react';
 import {
     Position,
+    PositionSummary,
     CompensationRecord,
     CompensationEntryType,
     PayType,
@@ -55,8 +56,8 @@ export function AddIncomeWizard({ open, onOpenChange, onComplete }: AddIncomeWiz
 
     // Data fetching
     const { data: positions = [] } = useQuery({
-        queryKey: ['positions'],
-        queryFn: () => invoke<Position[]>('get_positions'),
+        queryKey: ['positions', 'summaries'],
+        queryFn: () => invoke<PositionSummary[]>('get_position_summaries'),
     });
 
     // Form states
@@ -84,7 +85,7 @@ export function AddIncomeWizard({ open, onOpenChange, onComplete }: AddIncomeWiz
     });
 
     const saveCompensationMutation = useMutation({
-        mutationFn: (record: CompensationRecord) => invoke<number>('save_compensation_record', { record }),
+        mutationFn: (record: CompensationRecord) => saveCompensationRecord({ record }),
         onSuccess: () => {
             queryClient.invalidateQueries({ queryKey: ['compensationRecords'] });
             handleComplete();
@@ -163,7 +164,7 @@ export function AddIncomeWizard({ open, onOpenChange, onComplete }: AddIncomeWiz
                     salary_sacrifice: 0,
                 },
                 effective_date: new Date(),
-                confidence_score: 0.6,
+                confidence_score: 60,
                 created_at: new Date(),
             };
             saveCompensationMutation.mutate(record);
@@ -0,0 +1,25 @@
+import { AnalysisWarning } from '../types';
+
+interface AssumptionsDisclosureProps {
+    warnings?: AnalysisWarning[];
+}
+
+// "3 assumptions made", expanding to what each calculation assumed
+export function AssumptionsDisclosure({ warnings }: AssumptionsDisclosureProps) {
+    if (!warnings || warnings.length === 0) {
+        return null;
+    }
+
+    return (
+        <details className="text-xs text-muted-foreground">
+            <summary className="cursor-pointer">
+                {warnings.length} {warnings.length === 1 ? 'assumption' : 'assumptions'} made
+            </summary>
+            <ul className="mt-1 space-y-1 list-disc pl-4">
+                {warnings.map((warning, index) => (
+                    <li key={index}>{warning.message}</li>
+                ))}
+            </ul>
+        </details>
+    );
+}
@@ -4,6 +4,7 @@ import { Download, Upload, AlertCircle, CheckCircle } from 'lucide-react';
 import { Button } from './ui/button';
 import { Card, CardContent, CardHeader, CardTitle } from './ui/card';
 import { Alert, AlertDescription } from './ui/alert';
+import type { ImportResult } from '../types';
 
 export function DataBackup() {
   const [isExporting, setIsExporting] = useState(false);
@@ -56,11 +57,12 @@ export function DataBackup() {
       }
 
       // Import the data
-      const result = await invoke<any>('import_all_data', { data });
+      const result = await invoke<ImportResult>('import_all_data', { data });
 
+      const warnings = (result.warnings ?? []).map(w => ` Warning: ${w}.`).join('');
       setMessage({
         type: 'success',
-        text: `Data imported successfully! Imported: ${result.imported.positions} positions, ${result.imported.compensation} compensation records`
+        text: `Data imported successfully! Imported: ${result.positions_count} positions, ${result.compensation_count} compensation records.${warnings}`
       });
 
       // Refresh the page to show imported data
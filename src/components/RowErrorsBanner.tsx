@@ -0,0 +1,67 @@
+import { useMutation, useQueryClient } from '@tanstack/react-query';
+import { AlertTriangle, Wrench } from 'lucide-react';
+import { invoke } from '../lib/tauri';
+import { RepairLog, RowError } from '../types';
+import { Alert, AlertDescription, AlertTitle } from './ui/alert';
+import { Button } from './ui/button';
+
+interface RowErrorsBannerProps {
+    errors: RowError[];
+}
+
+/**
+ * Lists the rows a list command could not read, so they are not silently
+ * missing from the page, and offers to repair their JSON columns.
+ */
+export function RowErrorsBanner({ errors }: RowErrorsBannerProps) {
+    const queryClient = useQueryClient();
+
+    const repairMutation = useMutation({
+        mutationFn: () => invoke<RepairLog[]>('repair_json_columns'),
+        onSuccess: () => {
+            queryClient.invalidateQueries({ queryKey: ['positions'] });
+            queryClient.invalidateQueries({ queryKey: ['compensationRecords'] });
+            queryClient.invalidateQueries({ queryKey: ['weeklyEntries'] });
+            queryClient.invalidateQueries({ queryKey: ['yearlyEntries'] });
+        },
+    });
+
+    if (errors.length === 0) return null;
+
+    const unrecovered = repairMutation.data?.filter(log => !log.recovered).length ?? 0;
+
+    return (
+        <Alert variant="destructive" className="mb-6">
+            <AlertTriangle className="h-4 w-4" />
+            <AlertTitle>
+                {errors.length} {errors.length === 1 ? 'row' : 'rows'} could not be read
+            </AlertTitle>
+            <AlertDescription>
+                <ul className="list-disc pl-4 mb-3">
+                    {errors.map(error => (
+                        <li key={`${error.table_name}-${error.row_id}`}>
+                            {error.table_name} #{error.row_id}: {error.message}
+                        </li>
+                    ))}
+                </ul>
+                {unrecovered > 0 && (
+                    <p className="mb-3">
+                        {unrecovered} {unrecovered === 1 ? 'column was' : 'columns were'} reset to an empty list because the original could not be recovered.
+                    </p>
+                )}
+                {repairMutation.isError && (
+                    <p className="mb-3">Repair failed: {String(repairMutation.error)}</p>
+                )}
+                <Button
+                    variant="outline"
+                    size="sm"
+                    onClick={() => repairMutation.mutate()}
+                    disabled={repairMutation.isPending}
+                >
+                    <Wrench className="w-4 h-4 mr-2" />
+                    {repairMutation.isPending ? 'Repairing...' : 'Repair rows'}
+                </Button>
+            </AlertDescription>
+        </Alert>
+    );
+}
@@ -5,9 +5,10 @@ import { getChartColors } from '../../lib/chartTheme';
 
 interface EarningsChartProps {
   data: EarningsSnapshot[];
+  costOfLivingAdjusted?: boolean;
 }
 
-export function EarningsChart({ data }: EarningsChartProps) {
+export function EarningsChart({ data, costOfLivingAdjusted = false }: EarningsChartProps) {
   const colors = getChartColors();
 
   const chartData = data.map(snapshot => ({
@@ -15,6 +16,7 @@ export function EarningsChart({ data }: EarningsChartProps) {
     base: snapshot.base_annual,
     actual: snapshot.actual_annual,
     total: snapshot.total_with_super,
+    adjusted: snapshot.col_adjusted_annual,
   }));
 
   const CustomTooltip = ({ active, payload }: { active?: boolean; payload?: Array<{ payload: any; color: string; name: string; value: number }> }) => {
@@ -74,6 +76,18 @@ export function EarningsChart({ data }: EarningsChartProps) {
           dot={false}
           name="Total with Super"
         />
+        {costOfLivingAdjusted && (
+          <Line
+            type="monotone"
+            dataKey="adjusted"
+            stroke={colors.primary}
+            strokeWidth={2}
+            strokeDasharray="5 5"
+            dot={false}
+            connectNulls
+            name="Actual (cost of living adjusted)"
+          />
+        )}
       </LineChart>
     </ResponsiveContainer>
   );
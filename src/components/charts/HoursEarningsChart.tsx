@@ -14,7 +14,7 @@ export function HoursEarningsChart({ data }: HoursEarningsChartProps) {
     hours: point.total_hours_worked,
     earnings: point.total_earnings,
     overtimePercent: point.overtime_percentage,
-    year: point.year,
+    label: point.label,
   }));
 
   const CustomTooltip = ({ active, payload }: { active?: boolean; payload?: Array<{ payload: any }> }) => {
@@ -22,7 +22,7 @@ export function HoursEarningsChart({ data }: HoursEarningsChartProps) {
       const data = payload[0].payload;
       return (
         <div className="bg-card p-3 border rounded-lg shadow-lg">
-          <p className="text-sm font-medium mb-2">Year: {data.year}</p>
+          <p className="text-sm font-medium mb-2">Year: {data.label}</p>
           <p className="text-sm">Hours Worked: {data.hours.toLocaleString()}</p>
           <p className="text-sm">Earnings: {formatCurrency(data.earnings)}</p>
           <p className="text-sm">Overtime: {data.overtimePercent.toFixed(1)}%</p>
@@ -1,5 +1,5 @@
 import React, { useState } from 'react';
-import { CompensationRecord, PayType, AllowanceFrequency, PayslipFrequency, CompensationEntryType, OvertimeFrequency } from '../../types';
+import { CompensationRecord, PayType, AllowanceFrequency, PayslipFrequency, CompensationEntryType, OvertimeFrequency, VestingFrequency } from '../../types';
 import { Label } from '../ui/label';
 import { Input } from '../ui/input';
 import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from '../ui/select';
@@ -34,6 +34,29 @@ export function ExactCompensationForm({ initialData: record, onSave, onCancel, o
   const [allowances, setAllowances] = useState(record?.allowances || []);
   const [bonuses, setBonuses] = useState(record?.bonuses || []);
   const [notes, setNotes] = useState(record?.notes || '');
+  const [claimsTaxFreeThreshold, setClaimsTaxFreeThreshold] = useState(record?.claims_tax_free_threshold ?? true);
+  const [equityValue, setEquityValue] = useState(record?.equity?.grant_value || 0);
+  const [equityGrantDate, setEquityGrantDate] = useState(
+    record?.equity?.grant_date ? new Date(record.equity.grant_date).toISOString().split('T')[0] : ''
+  );
+  const [vestingMonths, setVestingMonths] = useState(record?.equity?.vesting_months ?? 48);
+  const [cliffMonths, setCliffMonths] = useState(record?.equity?.cliff_months ?? 12);
+  const [vestingFrequency, setVestingFrequency] = useState<VestingFrequency>(record?.equity?.vesting_frequency || VestingFrequency.Quarterly);
+  const [packaging, setPackaging] = useState(record?.packaging || []);
+
+  const [newPackagingItem, setNewPackagingItem] = useState({
+    name: '',
+    pre_tax_amount_per_period: 0,
+    post_tax_amount_per_period: 0,
+    attracts_fbt: true,
+  });
+  const [nonCashBenefits, setNonCashBenefits] = useState(record?.non_cash_benefits || []);
+
+  const [newBenefit, setNewBenefit] = useState({
+    name: '',
+    annual_value: 0,
+    fringe_benefit: false,
+  });
 
   const [newAllowance, setNewAllowance] = useState({
     name: '',
@@ -95,6 +118,37 @@ export function ExactCompensationForm({ initialData: record, onSave, onCancel, o
     setAllowances(allowances.filter((_, i) => i !== index));
   };
 
+  const handleAddPackagingItem = () => {
+    if (newPackagingItem.name && (newPackagingItem.pre_tax_amount_per_period > 0 || newPackagingItem.post_tax_amount_per_period > 0)) {
+      setPackaging([...packaging, { ...newPackagingItem }]);
+      setNewPackagingItem({
+        name: '',
+        pre_tax_amount_per_period: 0,
+        post_tax_amount_per_period: 0,
+        attracts_fbt: true,
+      });
+    }
+  };
+
+  const handleRemovePackagingItem = (index: number) => {
+    setPackaging(packaging.filter((_, i) => i !== index));
+  };
+
+  const handleAddBenefit = () => {
+    if (newBenefit.name && newBenefit.annual_value > 0) {
+      setNonCashBenefits([...nonCashBenefits, { ...newBenefit }]);
+      setNewBenefit({
+        name: '',
+        annual_value: 0,
+        fringe_benefit: false,
+      });
+    }
+  };
+
+  const handleRemoveBenefit = (index: number) => {
+    setNonCashBenefits(nonCashBenefits.filter((_, i) => i !== index));
+  };
+
   const handleAddBonus = () => {
     if (newBonus.name && newBonus.amount > 0) {
       setBonuses([...bonuses, {
@@ -145,6 +199,16 @@ export function ExactCompensationForm({ initialData: record, onSave, onCancel, o
       payslip_frequency: payslipFrequency,
       effective_date: new Date(effectiveDate),
       confidence_score: 100, // Exact entries have 100% confidence
+      claims_tax_free_threshold: claimsTaxFreeThreshold,
+      equity: equityValue > 0 && equityGrantDate ? {
+        grant_value: equityValue,
+        grant_date: new Date(equityGrantDate),
+        vesting_months: vestingMonths,
+        cliff_months: Math.min(cliffMonths, vestingMonths),
+        vesting_frequency: vestingFrequency,
+      } : null,
+      packaging: packaging,
+      non_cash_benefits: nonCashBenefits,
       notes: notes,
       created_at: new Date(),
     };
@@ -205,6 +269,15 @@ export function ExactCompensationForm({ initialData: record, onSave, onCancel, o
             </div>
           </div>
 
+          <label className="flex items-center space-x-2">
+            <input
+              type="checkbox"
+              checked={claimsTaxFreeThreshold}
+              onChange={(e) => setClaimsTaxFreeThreshold(e.target.checked)}
+            />
+            <span className="text-sm">Claims the tax-free threshold (usually only your main job)</span>
+          </label>
+
           <div>
             <Label>
               Base {payType === 'Salary' ? 'Salary' : 'Hourly Rate'} (exact)
@@ -478,6 +551,192 @@ export function ExactCompensationForm({ initialData: record, onSave, onCancel, o
         </CardContent>
       </Card>
 
+      {/* Salary packaging */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="text-lg">Salary Packaging</CardTitle>
+        </CardHeader>
+        <CardContent className="space-y-4">
+          {packaging.map((item, index) => (
+            <div key={index} className="flex items-center justify-between p-3 bg-muted rounded">
+              <div>
+                <span className="font-medium text-foreground">{item.name}</span>
+                <span className="ml-2 text-sm text-muted-foreground">
+                  {formatCurrency(item.pre_tax_amount_per_period)} pre-tax, {formatCurrency(item.post_tax_amount_per_period)} post-tax per pay
+                  {item.attracts_fbt && ' (FBT)'}
+                </span>
+              </div>
+              <Button
+                type="button"
+                variant="ghost"
+                size="sm"
+                onClick={() => handleRemovePackagingItem(index)}
+              >
+                <Trash2 className="w-4 h-4" />
+              </Button>
+            </div>
+          ))}
+
+          <div className="grid grid-cols-3 gap-2">
+            <Input
+              placeholder="e.g., Novated lease"
+              value={newPackagingItem.name}
+              onChange={(e) => setNewPackagingItem({ ...newPackagingItem, name: e.target.value })}
+            />
+            <Input
+              type="number"
+              placeholder="Pre-tax per pay"
+              value={newPackagingItem.pre_tax_amount_per_period || ''}
+              onChange={(e) => setNewPackagingItem({ ...newPackagingItem, pre_tax_amount_per_period: Number(e.target.value) })}
+            />
+            <Input
+              type="number"
+              placeholder="Post-tax per pay"
+              value={newPackagingItem.post_tax_amount_per_period || ''}
+              onChange={(e) => setNewPackagingItem({ ...newPackagingItem, post_tax_amount_per_period: Number(e.target.value) })}
+            />
+          </div>
+
+          <div className="flex items-center justify-between">
+            <label className="flex items-center space-x-2">
+              <input
+                type="checkbox"
+                checked={newPackagingItem.attracts_fbt}
+                onChange={(e) => setNewPackagingItem({ ...newPackagingItem, attracts_fbt: e.target.checked })}
+              />
+              <span className="text-sm">Attracts FBT</span>
+            </label>
+
+            <Button type="button" onClick={handleAddPackagingItem}>
+              <Plus className="w-4 h-4 mr-1" />
+              Add
+            </Button>
+          </div>
+        </CardContent>
+      </Card>
+
+      {/* Non-cash benefits */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="text-lg">Non-Cash Benefits</CardTitle>
+        </CardHeader>
+        <CardContent className="space-y-4">
+          {nonCashBenefits.map((benefit, index) => (
+            <div key={index} className="flex items-center justify-between p-3 bg-muted rounded">
+              <div>
+                <span className="font-medium text-foreground">{benefit.name}</span>
+                <span className="ml-2 text-sm text-muted-foreground">
+                  {formatCurrency(benefit.annual_value)} a year
+                  {benefit.fringe_benefit && ' (fringe benefit)'}
+                </span>
+              </div>
+              <Button
+                type="button"
+                variant="ghost"
+                size="sm"
+                onClick={() => handleRemoveBenefit(index)}
+              >
+                <Trash2 className="w-4 h-4" />
+              </Button>
+            </div>
+          ))}
+
+          <div className="grid grid-cols-2 gap-2">
+            <Input
+              placeholder="e.g., Company car, phone, gym"
+              value={newBenefit.name}
+              onChange={(e) => setNewBenefit({ ...newBenefit, name: e.target.value })}
+            />
+            <Input
+              type="number"
+              placeholder="Estimated value a year"
+              value={newBenefit.annual_value || ''}
+              onChange={(e) => setNewBenefit({ ...newBenefit, annual_value: Number(e.target.value) })}
+            />
+          </div>
+
+          <div className="flex items-center justify-between">
+            <label className="flex items-center space-x-2">
+              <input
+                type="checkbox"
+                checked={newBenefit.fringe_benefit}
+                onChange={(e) => setNewBenefit({ ...newBenefit, fringe_benefit: e.target.checked })}
+              />
+              <span className="text-sm">Fringe benefit</span>
+            </label>
+
+            <Button type="button" onClick={handleAddBenefit}>
+              <Plus className="w-4 h-4 mr-1" />
+              Add
+            </Button>
+          </div>
+        </CardContent>
+      </Card>
+
+      {/* Equity */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="text-lg">Equity / RSUs</CardTitle>
+        </CardHeader>
+        <CardContent className="space-y-4">
+          <div className="grid grid-cols-2 gap-4">
+            <div>
+              <Label>Grant Value (at grant)</Label>
+              <Input
+                type="number"
+                value={equityValue}
+                onChange={(e) => setEquityValue(Number(e.target.value))}
+                min={0}
+                step={1000}
+              />
+            </div>
+            <div>
+              <Label>Grant Date</Label>
+              <Input
+                type="date"
+                value={equityGrantDate}
+                onChange={(e) => setEquityGrantDate(e.target.value)}
+              />
+            </div>
+          </div>
+          <div className="grid grid-cols-3 gap-4">
+            <div>
+              <Label>Vesting Period (months)</Label>
+              <Input
+                type="number"
+                value={vestingMonths}
+                onChange={(e) => setVestingMonths(Number(e.target.value))}
+                min={0}
+                step={1}
+              />
+            </div>
+            <div>
+              <Label>Cliff (months)</Label>
+              <Input
+                type="number"
+                value={cliffMonths}
+                onChange={(e) => setCliffMonths(Number(e.target.value))}
+                min={0}
+                step={1}
+              />
+            </div>
+            <div>
+              <Label>Vests</Label>
+              <Select value={vestingFrequency} onValueChange={(value: VestingFrequency) => setVestingFrequency(value)}>
+                <SelectTrigger>
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="Monthly">Monthly</SelectItem>
+                  <SelectItem value="Quarterly">Quarterly</SelectItem>
+                  <SelectItem value="Annually">Annually</SelectItem>
+                </SelectContent>
+              </Select>
+            </div>
+          </div>
+        </CardContent>
+      </Card>
+
       {/* Effective Date */}
       <div>
         <Label>Effective Date</Label>
@@ -1,5 +1,5 @@
 import React, { useState, useEffect } from 'react';
-import { Position, EmploymentType, SeniorityLevel } from '../../types';
+import { Position, EmploymentType, SeniorityLevel, Weekday } from '../../types';
 import { Label } from '../ui/label';
 import { Input } from '../ui/input';
 import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from '../ui/select';
@@ -19,6 +19,12 @@ export function PositionForm({ initialData, onSave, onCancel, onDelete, isSaving
     const [employerName, setEmployerName] = useState(initialData?.employer_name || '');
     const [jobTitle, setJobTitle] = useState(initialData?.job_title || '');
     const [location, setLocation] = useState(initialData?.location || '');
+    const [industry, setIndustry] = useState(initialData?.industry || '');
+    const [noticePeriodWeeks, setNoticePeriodWeeks] = useState(initialData?.notice_period_weeks?.toString() || '');
+    const [leaveLoadingPercent, setLeaveLoadingPercent] = useState(initialData?.leave_loading_percent?.toString() || '');
+    const [probationMonths, setProbationMonths] = useState(initialData?.probation_months?.toString() || '');
+    const [commuteMinutes, setCommuteMinutes] = useState(initialData?.commute_minutes_per_day?.toString() || '');
+    const [weekEndingDay, setWeekEndingDay] = useState<Weekday | ''>(initialData?.week_ending_day || '');
     const [employmentType, setEmploymentType] = useState<EmploymentType>(initialData?.employment_type || EmploymentType.Permanent);
     const [seniorityLevel, setSeniorityLevel] = useState<SeniorityLevel>(initialData?.seniority_level || SeniorityLevel.Mid);
     const [startDate, setStartDate] = useState(initialData?.start_date ? formatDateForInput(initialData.start_date) : '');
@@ -40,6 +46,13 @@ export function PositionForm({ initialData, onSave, onCancel, onDelete, isSaving
             core_responsibilities: responsibilities,
             tools_systems_skills: initialData?.tools_systems_skills || [],
             achievements: initialData?.achievements || [],
+            industry: industry.trim() || null,
+            week_ending_day: weekEndingDay || null,
+            notice_period_weeks: noticePeriodWeeks ? parseFloat(noticePeriodWeeks) : null,
+            leave_loading_percent: leaveLoadingPercent ? parseFloat(leaveLoadingPercent) : null,
+            probation_months: probationMonths ? parseInt(probationMonths, 10) : null,
+            commute_minutes_per_day: commuteMinutes ? parseInt(commuteMinutes, 10) : null,
+            archived: initialData?.archived ?? false,
             created_at: initialData?.created_at || new Date(),
             updated_at: new Date(),
         };
@@ -127,6 +140,78 @@ export function PositionForm({ initialData, onSave, onCancel, onDelete, isSaving
                     </Select>
                 </div>
             </div>
+            <div className="grid grid-cols-2 gap-4">
+                <div>
+                    <Label>Industry</Label>
+                    <Input
+                        value={industry}
+                        onChange={(e) => setIndustry(e.target.value)}
+                        placeholder="Leave blank to use the industry in your profile"
+                    />
+                </div>
+                <div>
+                    <Label>Pay Week Ends On</Label>
+                    <Select value={weekEndingDay || 'unset'} onValueChange={(v) => setWeekEndingDay(v === 'unset' ? '' : v as Weekday)}>
+                        <SelectTrigger>
+                            <SelectValue />
+                        </SelectTrigger>
+                        <SelectContent>
+                            <SelectItem value="unset">Not set</SelectItem>
+                            {(['Mon', 'Tue', 'Wed', 'Thu', 'Fri', 'Sat', 'Sun'] as Weekday[]).map((day) => (
+                                <SelectItem key={day} value={day}>{day}</SelectItem>
+                            ))}
+                        </SelectContent>
+                    </Select>
+                </div>
+            </div>
+            <div className="grid grid-cols-4 gap-4">
+                <div>
+                    <Label>Probation (months)</Label>
+                    <Input
+                        type="number"
+                        min="0"
+                        step="1"
+                        value={probationMonths}
+                        onChange={(e) => setProbationMonths(e.target.value)}
+                        placeholder="e.g., 6"
+                    />
+                </div>
+                <div>
+                    <Label>Notice Period (weeks)</Label>
+                    <Input
+                        type="number"
+                        min="0"
+                        step="0.5"
+                        value={noticePeriodWeeks}
+                        onChange={(e) => setNoticePeriodWeeks(e.target.value)}
+                        placeholder="From your contract"
+                    />
+                </div>
+                <div>
+                    <Label>Leave Loading (%)</Label>
+                    <Input
+                        type="number"
+                        min="0"
+                        max="100"
+                        step="0.5"
+                        value={leaveLoadingPercent}
+                        onChange={(e) => setLeaveLoadingPercent(e.target.value)}
+                        placeholder="e.g., 17.5"
+                    />
+                </div>
+                <div>
+                    <Label>Commute (min/day)</Label>
+                    <Input
+                        type="number"
+                        min="0"
+                        max="720"
+                        step="5"
+                        value={commuteMinutes}
+                        onChange={(e) => setCommuteMinutes(e.target.value)}
+                        placeholder="There and back"
+                    />
+                </div>
+            </div>
             <div>
                 <Label>Core Responsibilities</Label>
                 <textarea
@@ -16,5 +16,17 @@ export async function invoke<T>(command: string, args?: any): Promise<T> {
   }
 }
 
+// Saves a compensation record, asking the user to confirm implausible base rates first
+export async function saveCompensationRecord(args: { record: any }): Promise<number | null> {
+  const warnings = await invoke<{ message: string }[]>('check_compensation_record', { record: args.record });
+  if (warnings.length > 0) {
+    const messages = warnings.map((w) => w.message).join('\n');
+    if (!window.confirm(`${messages}\n\nSave it anyway?`)) {
+      return null;
+    }
+  }
+  return invoke<number>('save_compensation_record', { ...args, confirmed: true });
+}
+
 // Export other Tauri APIs if needed
 export const tauri = window.__TAURI__;
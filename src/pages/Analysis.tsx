@@ -4,6 +4,7 @@ import { EarningsAnalysis, LoyaltyTaxAnalysis } from '../types';
 import { TrendingUp, AlertTriangle, DollarSign, Clock, Target, BarChart3 } from 'lucide-react';
 import { Card, CardContent, CardHeader, CardTitle } from '../components/ui/card';
 import { Badge } from '../components/ui/badge';
+import { AssumptionsDisclosure } from '../components/AssumptionsDisclosure';
 import { formatCurrency } from '../lib/utils';
 
 export function Analysis() {
@@ -88,6 +89,8 @@ export function Analysis() {
                   ))}
                 </div>
               </div>
+
+              <AssumptionsDisclosure warnings={earnings?.warnings} />
             </div>
           </CardContent>
         </Card>
@@ -142,6 +145,8 @@ export function Analysis() {
                   Based on available career history and market benchmarks
                 </p>
               </div>
+
+              <AssumptionsDisclosure warnings={loyaltyTax?.warnings} />
             </div>
           </CardContent>
         </Card>
@@ -1,7 +1,7 @@
 import { useState } from 'react';
 import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
-import { invoke } from '../lib/tauri';
-import { Position, CompensationRecord, WeeklyCompensationEntry, YearlyIncomeEntry } from '../types';
+import { invoke, saveCompensationRecord } from '../lib/tauri';
+import { Position, PositionSummary, CompensationRecord, WeeklyCompensationEntry, YearlyIncomeEntry, ListResult } from '../types';
 import {
     Plus,
     Briefcase,
@@ -31,6 +31,7 @@ import { YearlyWageForm } from '../components/forms/YearlyWageForm';
 import { ExactCompensationForm } from '../components/forms/ExactCompensationForm';
 import { PayslipEntryForm } from '../components/forms/PayslipEntryForm';
 import { FuzzyCompensationForm } from '../components/forms/FuzzyCompensationForm';
+import { RowErrorsBanner } from '../components/RowErrorsBanner';
 import {
     formatDateAU,
     formatCurrency,
@@ -42,11 +43,16 @@ import {
 
 // Union type for timeline entries
 type TimelineEntry =
-    | { type: 'position'; date: Date; data: Position }
+    | { type: 'position'; date: Date; data: PositionSummary }
     | { type: 'compensation'; date: Date; data: CompensationRecord }
     | { type: 'weekly'; date: Date; data: WeeklyCompensationEntry }
     | { type: 'yearly'; date: Date; data: YearlyIncomeEntry };
 
+// The list only carries position summaries; the full position is loaded to edit it
+type EditingEntry =
+    | Exclude<TimelineEntry, { type: 'position' }>
+    | { type: 'position'; date: Date; data: Position };
+
 export function CareerLedger() {
     const [selectedYear, setSelectedYear] = useState<string>('all');
     const [wizardOpen, setWizardOpen] = useState(false);
@@ -54,25 +60,35 @@ export function CareerLedger() {
 
     // Fetch all data
     const { data: positions = [] } = useQuery({
-        queryKey: ['positions'],
-        queryFn: () => invoke<Position[]>('get_positions'),
+        queryKey: ['positions', 'summaries'],
+        queryFn: () => invoke<PositionSummary[]>('get_position_summaries'),
     });
 
-    const { data: compensationRecords = [] } = useQuery({
+    const { data: compensationList } = useQuery({
         queryKey: ['compensationRecords'],
-        queryFn: () => invoke<CompensationRecord[]>('get_all_compensation_records'),
+        queryFn: () => invoke<ListResult<CompensationRecord>>('get_all_compensation_records'),
     });
 
-    const { data: weeklyEntries = [] } = useQuery({
+    const { data: weeklyList } = useQuery({
         queryKey: ['weeklyEntries'],
-        queryFn: () => invoke<WeeklyCompensationEntry[]>('get_weekly_entries'),
+        queryFn: () => invoke<ListResult<WeeklyCompensationEntry>>('get_weekly_entries'),
     });
 
-    const { data: yearlyEntries = [] } = useQuery({
+    const { data: yearlyList } = useQuery({
         queryKey: ['yearlyEntries'],
-        queryFn: () => invoke<YearlyIncomeEntry[]>('get_yearly_entries'),
+        queryFn: () => invoke<ListResult<YearlyIncomeEntry>>('get_yearly_entries'),
     });
 
+    const compensationRecords = compensationList?.rows ?? [];
+    const weeklyEntries = weeklyList?.rows ?? [];
+    const yearlyEntries = yearlyList?.rows ?? [];
+    // Rows the backend could not read, shown rather than silently left out
+    const rowErrors = [
+        ...(compensationList?.errors ?? []),
+        ...(weeklyList?.errors ?? []),
+        ...(yearlyList?.errors ?? []),
+    ];
+
     // Combine all entries into a single timeline, sorted by date
     const allTimelineEntries: TimelineEntry[] = [
         ...positions.map(p => ({
@@ -132,7 +148,7 @@ export function CareerLedger() {
     });
 
     const saveCompensationMutation = useMutation({
-        mutationFn: (record: CompensationRecord) => invoke<number>('save_compensation_record', { ...record }),
+        mutationFn: (record: CompensationRecord) => saveCompensationRecord({ record }),
         onSuccess: () => {
             queryClient.invalidateQueries({ queryKey: ['compensationRecords'] });
             setEditingEntry(null);
@@ -179,10 +195,16 @@ export function CareerLedger() {
         },
     });
 
-    const [editingEntry, setEditingEntry] = useState<TimelineEntry | null>(null);
+    const [editingEntry, setEditingEntry] = useState<EditingEntry | null>(null);
 
-    const handleEntryClick = (entry: TimelineEntry) => {
-        setEditingEntry(entry);
+    const handleEntryClick = async (entry: TimelineEntry) => {
+        if (entry.type === 'position') {
+            if (entry.data.id === undefined) return;
+            const position = await invoke<Position>('get_position', { id: entry.data.id });
+            setEditingEntry({ ...entry, data: position });
+        } else {
+            setEditingEntry(entry);
+        }
     };
 
     const handleWizardComplete = () => {
@@ -358,6 +380,8 @@ export function CareerLedger() {
                 </div>
             </div>
 
+            <RowErrorsBanner errors={rowErrors} />
+
             {/* Summary Cards */}
             <div className="grid grid-cols-1 md:grid-cols-3 gap-4 mb-6">
                 <Card>
@@ -1,8 +1,8 @@
 import React, { useState } from 'react';
 import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
 import { invoke } from '../lib/tauri';
-import { Position, SeniorityLevel, EmploymentType } from '../types';
-import { Plus, Edit, Trash2, Briefcase, MapPin, Calendar, Database } from 'lucide-react';
+import { Position, SeniorityLevel, EmploymentType, DataQualityReport, ListResult, PositionStatistics } from '../types';
+import { Plus, Edit, Trash2, Briefcase, MapPin, Calendar, Database, Archive, ArchiveRestore } from 'lucide-react';
 import { Button } from '../components/ui/button';
 import { Card, CardContent } from '../components/ui/card';
 import { Dialog, DialogContent, DialogHeader, DialogTitle } from '../components/ui/dialog';
@@ -11,7 +11,41 @@ import { Label } from '../components/ui/label';
 import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from '../components/ui/select';
 import { Badge } from '../components/ui/badge';
 import { ConfirmationDialog } from '../components/ConfirmationDialog';
-import { formatDateAU, formatDuration } from '../lib/utils';
+import { RowErrorsBanner } from '../components/RowErrorsBanner';
+import { formatCurrency, formatCurrencyPrecise, formatDateAU, formatDuration } from '../lib/utils';
+
+function PositionStats({ positionId }: { positionId: number }) {
+  const { data: stats } = useQuery({
+    queryKey: ['position-statistics', positionId],
+    queryFn: () => invoke<PositionStatistics>('get_position_statistics', { positionId }),
+  });
+  if (!stats || stats.weekly.entry_count === 0) return null;
+  const { weekly } = stats;
+
+  return (
+    <p className="text-xs text-muted-foreground mb-3">
+      {Math.round(weekly.weeks_logged)} weeks logged, {formatDateAU(weekly.first_week_ending)} to {formatDateAU(weekly.last_week_ending)}
+      {weekly.gap_count > 0 && ` (${weekly.gap_count} ${weekly.gap_count === 1 ? 'gap' : 'gaps'})`}
+      {' · '}{formatCurrency(weekly.total_gross)} gross, {formatCurrency(weekly.total_net)} net
+      {' · '}{weekly.average_weekly_hours.toFixed(1)} h/week, {Math.round(weekly.overtime_share * 100)}% overtime
+    </p>
+  );
+}
+
+// Gross pay per hour once the unpaid commute is counted as time given to the job
+function CommuteRate({ positionId, minutes }: { positionId: number; minutes: number }) {
+  const { data: rate } = useQuery({
+    queryKey: ['commute-adjusted-rate', positionId, minutes],
+    queryFn: () => invoke<number>('commute_adjusted_hourly_rate', { positionId }),
+  });
+  if (rate === undefined) return null;
+
+  return (
+    <p className="text-xs text-muted-foreground mb-3">
+      {formatCurrencyPrecise(rate)}/hr door to door, with a {minutes}-minute daily commute
+    </p>
+  );
+}
 
 export function CareerTimeline() {
   const [dialogOpen, setDialogOpen] = useState(false);
@@ -21,10 +55,17 @@ export function CareerTimeline() {
   const [positionToDelete, setPositionToDelete] = useState<number | null>(null);
   const queryClient = useQueryClient();
 
-  const { data: positions = [] } = useQuery({
+  const { data: positionList } = useQuery({
     queryKey: ['positions'],
-    queryFn: () => invoke<Position[]>('get_positions'),
+    queryFn: () => invoke<ListResult<Position>>('get_positions'),
+  });
+  const positions = positionList?.rows ?? [];
+
+  const { data: dataQuality } = useQuery({
+    queryKey: ['dataQuality'],
+    queryFn: () => invoke<DataQualityReport>('get_data_quality'),
   });
+  const qualityFor = (id?: number) => dataQuality?.positions.find(p => p.position_id === id)?.quality;
 
   const loadSampleDataMutation = useMutation({
     mutationFn: () => invoke('load_sample_data'),
@@ -51,6 +92,7 @@ export function CareerTimeline() {
     mutationFn: (position: Position) => invoke<number>('save_position', { position }),
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ['positions'] });
+      queryClient.invalidateQueries({ queryKey: ['dataQuality'] });
       setDialogOpen(false);
       setEditingPosition(null);
     },
@@ -65,6 +107,16 @@ export function CareerTimeline() {
     },
   });
 
+  // Archived positions stay listed and exported but drop out of the analyses
+  const archivePositionMutation = useMutation({
+    mutationFn: ({ id, archived }: { id: number; archived: boolean }) =>
+      invoke(archived ? 'archive_position' : 'unarchive_position', { id }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ['positions'] });
+      queryClient.invalidateQueries({ queryKey: ['earningsAnalysis'] });
+    },
+  });
+
   const handleDeletePosition = (id: number) => {
     setPositionToDelete(id);
     setDeleteConfirmOpen(true);
@@ -96,6 +148,9 @@ export function CareerTimeline() {
       core_responsibilities: formData.get('responsibilities') as string,
       tools_systems_skills: (formData.get('tools') as string).split(',').map(s => s.trim()).filter(s => s),
       achievements: (formData.get('achievements') as string).split('\n').map(s => s.trim()).filter(s => s),
+      industry: (formData.get('industry') as string).trim() || null,
+      commute_minutes_per_day: editingPosition?.commute_minutes_per_day ?? null,
+      archived: editingPosition?.archived ?? false,
       created_at: editingPosition?.created_at || new Date(),
       updated_at: new Date(),
     };
@@ -135,6 +190,8 @@ export function CareerTimeline() {
         </div>
       </div>
 
+      <RowErrorsBanner errors={positionList?.errors ?? []} />
+
       <div className="space-y-4">
         {positions.map((position) => (
           <Card key={position.id}>
@@ -146,6 +203,17 @@ export function CareerTimeline() {
                     <h3 className="font-semibold text-lg">{position.job_title}</h3>
                     <Badge variant="secondary">{position.seniority_level}</Badge>
                     <Badge variant="outline">{position.employment_type}</Badge>
+                    {position.archived && (
+                      <Badge variant="outline" title="Left out of analyses; still exported">Archived</Badge>
+                    )}
+                    {qualityFor(position.id) && qualityFor(position.id)!.score < 100 && (
+                      <Badge
+                        variant={qualityFor(position.id)!.score < 70 ? 'destructive' : 'outline'}
+                        title={qualityFor(position.id)!.deductions.map(d => `${d.reason} (-${d.points})`).join('\n')}
+                      >
+                        Data {Math.round(qualityFor(position.id)!.score)}/100
+                      </Badge>
+                    )}
                   </div>
 
                   <div className="flex items-center gap-4 text-sm text-muted-foreground mb-3">
@@ -160,6 +228,11 @@ export function CareerTimeline() {
                     </div>
                   </div>
 
+                  {position.id !== undefined && <PositionStats positionId={position.id} />}
+                  {position.id !== undefined && !!position.commute_minutes_per_day && (
+                    <CommuteRate positionId={position.id} minutes={position.commute_minutes_per_day} />
+                  )}
+
                   <div className="mb-3">
                     <p className="text-sm font-medium text-foreground mb-1">Responsibilities</p>
                     <p className="text-sm text-muted-foreground whitespace-pre-line">{position.core_responsibilities}</p>
@@ -198,6 +271,14 @@ export function CareerTimeline() {
                   >
                     <Edit className="w-4 h-4" />
                   </Button>
+                  <Button
+                    variant="ghost"
+                    size="sm"
+                    title={position.archived ? 'Unarchive' : 'Archive'}
+                    onClick={() => archivePositionMutation.mutate({ id: position.id!, archived: !position.archived })}
+                  >
+                    {position.archived ? <ArchiveRestore className="w-4 h-4" /> : <Archive className="w-4 h-4" />}
+                  </Button>
                   <Button
                     variant="ghost"
                     size="sm"
@@ -319,6 +400,15 @@ export function CareerTimeline() {
               </div>
             </div>
 
+            <div>
+              <Label>Industry</Label>
+              <Input
+                name="industry"
+                defaultValue={editingPosition?.industry ?? ''}
+                placeholder="Leave blank to use the industry in your profile"
+              />
+            </div>
+
             <div>
               <Label>Core Responsibilities</Label>
               <textarea
@@ -30,6 +30,7 @@ import {
 export function Dashboard() {
   const queryClient = useQueryClient();
   const [selectedYear, setSelectedYear] = useState<string>('all');
+  const [costOfLivingAdjusted, setCostOfLivingAdjusted] = useState(false);
 
   const { data: analysis, isLoading } = useQuery({
     queryKey: ['earningsAnalysis'],
@@ -188,6 +189,18 @@ export function Dashboard() {
         <div>
           <h1 className="text-3xl font-black text-foreground tracking-tight">Career Dashboard</h1>
           <p className="text-muted-foreground">Your earnings analysis at a glance</p>
+          {analysis.current_positions.length > 1 && (
+            <p className="text-sm text-muted-foreground mt-1">
+              Current roles: {analysis.current_positions
+                .map(p => `${p.job_title} at ${p.employer_name}`)
+                .join(', ')}
+            </p>
+          )}
+          {analysis.upcoming_positions.map(({ position, starts_in_days }) => (
+            <p key={position.id} className="text-sm text-muted-foreground mt-1">
+              {position.job_title} at {position.employer_name}: starting in {starts_in_days} {starts_in_days === 1 ? 'day' : 'days'}
+            </p>
+          ))}
         </div>
 
         {availableYears.length > 0 && (
@@ -275,7 +288,17 @@ export function Dashboard() {
             {selectedYear === 'all' ? 'Earnings Over Time' : `Compensation Breakdown (${selectedYear})`}
           </h2>
           {selectedYear === 'all' ? (
-            <EarningsChart data={analysis.earnings_over_time} />
+            <>
+              <EarningsChart data={analysis.earnings_over_time} costOfLivingAdjusted={costOfLivingAdjusted} />
+              <label className="flex items-center space-x-2 mt-3">
+                <input
+                  type="checkbox"
+                  checked={costOfLivingAdjusted}
+                  onChange={(e) => setCostOfLivingAdjusted(e.target.checked)}
+                />
+                <span className="text-sm text-muted-foreground">Show earnings adjusted for the cost of living where you worked</span>
+              </label>
+            </>
           ) : (
             selectedYearSnapshot ? (
               <CompensationBreakdownChart
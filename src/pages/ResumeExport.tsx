@@ -13,6 +13,8 @@ type ResumeType = 'ats' | 'technical' | 'executive' | 'project' | 'summary';
 export function ResumeExport() {
   const [selectedType, setSelectedType] = useState<ResumeType>('ats');
   const [copied, setCopied] = useState(false);
+  const [redactPersonal, setRedactPersonal] = useState(true);
+  const [reportError, setReportError] = useState<string | null>(null);
 
   const { data: resumeData, isLoading } = useQuery({
     queryKey: ['resumeExport'],
@@ -33,6 +35,36 @@ export function ResumeExport() {
     linkElement.click();
   };
 
+  const handleDownloadReport = async () => {
+    setReportError(null);
+    try {
+      const bytes = await invoke<number[]>('export_analysis_report_pdf', { redactPersonal });
+      const url = URL.createObjectURL(new Blob([new Uint8Array(bytes)], { type: 'application/pdf' }));
+      const linkElement = document.createElement('a');
+      linkElement.setAttribute('href', url);
+      linkElement.setAttribute('download', 'careerflow_report.pdf');
+      linkElement.click();
+      URL.revokeObjectURL(url);
+    } catch (error) {
+      setReportError(String(error));
+    }
+  };
+
+  const handleDownloadHtmlReport = async () => {
+    setReportError(null);
+    try {
+      const html = await invoke<string>('export_analysis_report_html', { redactPersonal });
+      const url = URL.createObjectURL(new Blob([html], { type: 'text/html' }));
+      const linkElement = document.createElement('a');
+      linkElement.setAttribute('href', url);
+      linkElement.setAttribute('download', 'careerflow_report.html');
+      linkElement.click();
+      URL.revokeObjectURL(url);
+    } catch (error) {
+      setReportError(String(error));
+    }
+  };
+
   const handleCopyPrompt = async () => {
     if (!resumeData) return;
 
@@ -159,6 +191,35 @@ Please generate the resume content now.`;
           </CardContent>
         </Card>
 
+        {/* Adviser Report */}
+        <Card>
+          <CardHeader>
+            <CardTitle>Adviser Report</CardTitle>
+          </CardHeader>
+          <CardContent className="space-y-4">
+            <p className="text-sm text-muted-foreground">
+              Your earnings analysis, loyalty tax, financial years and insights to share with a financial adviser, as a PDF or a web page that opens offline.
+            </p>
+            <label className="flex items-center space-x-2">
+              <input
+                type="checkbox"
+                checked={redactPersonal}
+                onChange={(e) => setRedactPersonal(e.target.checked)}
+              />
+              <span className="text-sm">Leave out my name and date of birth</span>
+            </label>
+            <Button onClick={handleDownloadReport} variant="outline" className="w-full">
+              <FileText className="w-4 h-4 mr-2" />
+              Download careerflow_report.pdf
+            </Button>
+            <Button onClick={handleDownloadHtmlReport} variant="outline" className="w-full">
+              <FileText className="w-4 h-4 mr-2" />
+              Download careerflow_report.html
+            </Button>
+            {reportError && <p className="text-sm text-destructive">{reportError}</p>}
+          </CardContent>
+        </Card>
+
         {/* Profile Summary */}
         <Card>
           <CardHeader>
@@ -223,6 +284,25 @@ Please generate the resume content now.`;
         </CardContent>
       </Card>
 
+      {resumeData.upcoming_positions.length > 0 && (
+        <Card className="mt-6">
+          <CardHeader>
+            <CardTitle>Upcoming</CardTitle>
+          </CardHeader>
+          <CardContent>
+            <div className="space-y-4">
+              {resumeData.upcoming_positions.map((position: ResumePosition, index: number) => (
+                <div key={index} className="border-l-2 border-dashed border-border pl-4">
+                  <h3 className="font-medium text-foreground">{position.title}</h3>
+                  <p className="text-sm text-muted-foreground">{position.employer}</p>
+                  <p className="text-xs text-muted-foreground/70">{position.duration}</p>
+                </div>
+              ))}
+            </div>
+          </CardContent>
+        </Card>
+      )}
+
       {/* Instructions */}
       <Card className="mt-6 bg-blue-500/10 dark:bg-blue-500/20 border-blue-500/20">
         <CardHeader>
@@ -1,11 +1,12 @@
 import { useState } from 'react';
-import { useMutation, useQueryClient } from '@tanstack/react-query';
+import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
 import { invoke } from '../lib/tauri';
 import { DataBackup } from '../components/DataBackup';
 import { ConfirmationDialog } from '../components/ConfirmationDialog';
-import { Database, Trash2 } from 'lucide-react';
+import { Database, Shield, Trash2 } from 'lucide-react';
 import { Button } from '../components/ui/button';
 import { Card, CardContent, CardHeader, CardTitle } from '../components/ui/card';
+import type { DemographicSettings } from '../types';
 
 export function Settings() {
   const queryClient = useQueryClient();
@@ -31,6 +32,23 @@ export function Settings() {
     },
   });
 
+  const { data: demographicSettings } = useQuery({
+    queryKey: ['demographicSettings'],
+    queryFn: () => invoke<DemographicSettings>('get_demographic_settings'),
+  });
+
+  const saveDemographicSettingsMutation = useMutation({
+    mutationFn: (settings: DemographicSettings) => invoke('save_demographic_settings', { settings }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ['demographicSettings'] });
+      queryClient.invalidateQueries({ queryKey: ['earningsAnalysis'] });
+    },
+    onError: (error) => {
+      console.error('Failed to save demographic settings:', error);
+      alert('Failed to save the setting. Please try again.');
+    },
+  });
+
   const clearDataMutation = useMutation({
     mutationFn: () => invoke('clear_all_data'),
     onSuccess: () => {
@@ -93,6 +111,30 @@ export function Settings() {
           </CardContent>
         </Card>
 
+        {/* Demographic Comparisons */}
+        <Card className="max-w-2xl">
+          <CardHeader>
+            <CardTitle className="flex items-center">
+              <Shield className="w-5 h-5 mr-2" />
+              Demographic Comparisons
+            </CardTitle>
+          </CardHeader>
+          <CardContent className="space-y-4">
+            <p className="text-sm text-muted-foreground">
+              When your profile includes a gender, market comparisons also show where your pay sits against the median for that gender. Turn this off to leave demographic comparisons out entirely.
+            </p>
+            <label className="flex items-center space-x-2">
+              <input
+                type="checkbox"
+                checked={demographicSettings?.comparisons_enabled ?? true}
+                onChange={(e) => saveDemographicSettingsMutation.mutate({ comparisons_enabled: e.target.checked })}
+                disabled={!demographicSettings || saveDemographicSettingsMutation.isPending}
+              />
+              <span className="text-sm">Include demographic comparisons</span>
+            </label>
+          </CardContent>
+        </Card>
+
         {/* Data Backup */}
         <DataBackup />
       </div>
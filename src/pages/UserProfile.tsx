@@ -2,7 +2,7 @@ import React, { useState, useEffect } from 'react';
 import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
 import { invoke } from '../lib/tauri';
 import type { UserProfile as UserProfileType } from '../types';
-import { AustralianState, Qualification, EmploymentType, DEFAULT_PROFILE } from '../types';
+import { AustralianState, Qualification, EmploymentType, Gender, DEFAULT_PROFILE } from '../types';
 import { Save, User } from 'lucide-react';
 import { Button } from '../components/ui/button';
 import { Card, CardContent, CardHeader, CardTitle } from '../components/ui/card';
@@ -233,6 +233,44 @@ export function UserProfile() {
                   </SelectContent>
                 </Select>
               </div>
+
+              <div>
+                <Label>Gender (optional)</Label>
+                <Select value={safeProfile.gender ?? Gender.PreferNotToSay} onValueChange={(value) => handleInputChange('gender', value)}>
+                  <SelectTrigger>
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value={Gender.PreferNotToSay}>Prefer not to say</SelectItem>
+                    <SelectItem value={Gender.Woman}>Woman</SelectItem>
+                    <SelectItem value={Gender.Man}>Man</SelectItem>
+                    <SelectItem value={Gender.NonBinary}>Non-binary</SelectItem>
+                  </SelectContent>
+                </Select>
+                <p className="text-xs text-muted-foreground mt-1">
+                  Only used to compare your pay with gender-specific medians, which you can turn off. Never included in anonymised exports.
+                </p>
+              </div>
+
+              <label className="flex items-center space-x-2">
+                <input
+                  type="checkbox"
+                  checked={safeProfile.has_help_debt ?? false}
+                  onChange={(e) => handleInputChange('has_help_debt', e.target.checked)}
+                />
+                <span className="text-sm">I have a HELP/HECS debt</span>
+              </label>
+
+              <div>
+                <Label>Total Super Balance</Label>
+                <Input
+                  type="number"
+                  min="0"
+                  value={safeProfile.total_super_balance ?? ''}
+                  onChange={(e) => handleInputChange('total_super_balance', e.target.value === '' ? null : parseFloat(e.target.value))}
+                  placeholder="Across all funds at the last 30 June, to work out carry-forward"
+                />
+              </div>
             </CardContent>
           </Card >
 
@@ -2,6 +2,13 @@
 // These mirror the Rust models but are defined independently
 
 // Enums
+export enum Gender {
+  Woman = 'Woman',
+  Man = 'Man',
+  NonBinary = 'NonBinary',
+  PreferNotToSay = 'PreferNotToSay',
+}
+
 export enum AustralianState {
   NSW = 'NSW',
   VIC = 'VIC',
@@ -97,6 +104,11 @@ export enum PayslipFrequency {
   Monthly = 'Monthly',
 }
 
+export enum Jurisdiction {
+  AU = 'AU',
+  NZ = 'NZ', // April-March tax year
+}
+
 export enum InsightCategory {
   Underpaid = 'Underpaid',
   FairlyPaid = 'FairlyPaid',
@@ -105,6 +117,12 @@ export enum InsightCategory {
   LoyaltyTax = 'LoyaltyTax',
   MarketOpportunity = 'MarketOpportunity',
   SkillsGap = 'SkillsGap',
+  SuperOpportunity = 'SuperOpportunity',
+  ProgressionStall = 'ProgressionStall',
+  DataQuality = 'DataQuality',
+  LongServiceLeave = 'LongServiceLeave',
+  IncomeRisk = 'IncomeRisk',
+  Insurance = 'Insurance',
 }
 
 // Core Types
@@ -118,6 +136,9 @@ export interface UserProfile {
   highest_qualification: Qualification;
   career_preferences: CareerPreferences;
   standard_weekly_hours: number; // User-configurable, not hardcoded
+  has_help_debt: boolean; // HELP/HECS study loan, repaid through tax
+  total_super_balance?: number | null; // Across all funds at the last 30 June, for carry-forward
+  gender?: Gender; // Optional; only used for demographic comparisons
   created_at: Date;
   updated_at: Date;
 }
@@ -149,8 +170,13 @@ export const DEFAULT_PROFILE: Omit<UserProfile, 'id' | 'created_at' | 'updated_a
   highest_qualification: Qualification.HighSchool,
   career_preferences: DEFAULT_CAREER_PREFERENCES,
   standard_weekly_hours: 38, // Australian full-time default
+  has_help_debt: false,
+  total_super_balance: null,
+  gender: Gender.PreferNotToSay,
 };
 
+export type Weekday = 'Mon' | 'Tue' | 'Wed' | 'Thu' | 'Fri' | 'Sat' | 'Sun';
+
 export interface Position {
   id?: number;
   employer_name: string;
@@ -163,10 +189,35 @@ export interface Position {
   core_responsibilities: string;
   tools_systems_skills: string[];
   achievements: string[];
+  industry?: string | null; // Overrides the profile's industry when set
+  week_ending_day?: Weekday | null; // The day pay periods end on, when known
+  notice_period_weeks?: number | null; // Notice the contract requires on resigning
+  leave_loading_percent?: number | null; // Paid on annual leave cashed out on leaving
+  probation_months?: number | null;
+  commute_minutes_per_day?: number | null; // Door to door, there and back
+  archived?: boolean; // Kept and exported, but left out of analyses unless asked for
   created_at: Date;
   updated_at: Date;
 }
 
+// A position without its free-text and list fields, for list views
+export interface PositionSummary {
+  id?: number;
+  employer_name: string;
+  job_title: string;
+  employment_type: EmploymentType;
+  location: string;
+  start_date: Date;
+  end_date?: Date;
+  seniority_level: SeniorityLevel;
+}
+
+// A position that hasn't started yet, shown apart from the current ones
+export interface UpcomingPosition {
+  position: PositionSummary;
+  starts_in_days: number;
+}
+
 export interface CompensationRecord {
   id?: number;
   position_id: number;
@@ -182,10 +233,55 @@ export interface CompensationRecord {
   payslip_frequency?: PayslipFrequency;
   effective_date: Date;
   confidence_score: number;
+  currency?: string; // ISO 4217 code, defaults to AUD
+  jurisdiction?: Jurisdiction; // Defaults to AU
+  claims_tax_free_threshold?: boolean; // Defaults to true; usually only the main job claims it
+  equity?: EquityGrant | null;
+  packaging?: PackagingItem[];
+  non_cash_benefits?: NonCashBenefit[];
+  award_classification_id?: number | null; // An award rate row; its award and classification apply
   notes?: string;
   created_at: Date;
 }
 
+// A salary packaging arrangement such as a novated lease, per pay period at the record's
+// payslip frequency. Pre-tax amounts reduce taxable income; post-tax ones come out of net pay.
+export interface PackagingItem {
+  name: string;
+  pre_tax_amount_per_period: number;
+  post_tax_amount_per_period: number;
+  attracts_fbt: boolean; // Shows up as reportable fringe benefits on the income statement
+}
+
+// Something the employer provides rather than pays for, such as a car, phone or gym membership
+export interface NonCashBenefit {
+  name: string;
+  annual_value: number; // Estimated, in the record's currency
+  fringe_benefit: boolean; // Its taxable value is a reportable fringe benefit
+}
+
+// Shares or RSUs granted with the package, valued at grant in the record's currency
+export interface EquityGrant {
+  grant_value: number;
+  grant_date: Date;
+  vesting_months: number; // Whole schedule, e.g. 48
+  cliff_months: number; // Nothing vests before the cliff; what has accrued vests on it
+  vesting_frequency: VestingFrequency;
+}
+
+export enum VestingFrequency {
+  Monthly = 'Monthly',
+  Quarterly = 'Quarterly',
+  Annually = 'Annually',
+}
+
+// Equity vesting in a financial year across every grant, in AUD at grant value
+export interface EquityYear {
+  financial_year: string;
+  vested: number;
+  unvested: number; // Granted by the end of the year and still to vest after it
+}
+
 export interface OvertimeDetails {
   frequency: OvertimeFrequency;
   rate_multiplier: number;
@@ -198,6 +294,7 @@ export interface Allowance {
   amount: number;
   frequency: AllowanceFrequency;
   taxable: boolean;
+  ordinary_time?: boolean | null; // Counts towards super; when unset, taxable allowances do
 }
 
 export interface Bonus {
@@ -218,7 +315,9 @@ export interface WeeklyCompensationEntry {
   id?: number;
   position_id?: number; // Optional - can be inferred from date overlap with positions
   financial_year: string; // e.g., "FY2024-25"
-  week_ending: Date;
+  week_ending: Date; // Last day of the pay period
+  period_start?: Date; // Derived from week_ending and period_type when omitted
+  period_type?: PayslipFrequency; // Defaults to Weekly
   gross_pay: number;
   tax_withheld: number;
   net_pay: number; // Calculated: gross - tax
@@ -227,6 +326,7 @@ export interface WeeklyCompensationEntry {
   overtime_rate_multiplier: number; // e.g., 1.5 or 2.0
   allowances: Allowance[];
   super_contributed: number;
+  currency?: string; // ISO 4217 code, defaults to AUD
   notes?: string;
   created_at: Date;
 }
@@ -242,10 +342,978 @@ export interface YearlyIncomeEntry {
   reportable_fringe_benefits?: number;
   allowances: Allowance[];
   source: 'ATO' | 'Manual'; // Where the data came from
+  currency?: string; // ISO 4217 code, defaults to AUD
+  jurisdiction?: Jurisdiction; // Defaults to AU
   notes?: string;
   created_at: Date;
 }
 
+// User-entered minimum pay under an award or enterprise agreement classification
+export interface AwardRate {
+  id?: number;
+  award_name: string;
+  classification: string;
+  effective_date: string;
+  hourly_minimum: number;
+  overtime_multiplier: number; // Lowest overtime rate, e.g. 1.5 for the first hours
+  casual_loading: number; // Percent on top of the hourly minimum for casuals
+}
+
+// An insurance policy the user pays for, in AUD a year
+export interface InsurancePolicy {
+  id?: number;
+  policy_type: 'IncomeProtection' | 'TPD' | 'Life';
+  provider: string;
+  annual_premium: number;
+  through_super: boolean; // Paid from the super balance rather than take-home pay
+  start_date: string;
+  end_date: string | null;
+}
+
+export interface InsuranceSettings {
+  income_protection_insight: boolean;
+  income_protection_threshold: number; // Annual pay above which a missing policy is pointed out
+}
+
+// A partner's income for a financial year, used only for family thresholds
+export interface PartnerIncomeEntry {
+  id?: number;
+  financial_year: string;
+  gross_income: number;
+  is_estimate: boolean;
+}
+
+// A user-defined label such as "FIFO" or "shift work", for grouping and filtering
+export interface Tag {
+  id?: number;
+  name: string;
+}
+
+// The kinds of record a tag can be put on
+export type TagEntityType = 'Position' | 'CompensationRecord' | 'WeeklyEntry' | 'YearlyEntry';
+
+// One tag on one record
+export interface EntityTag {
+  tag_id: number;
+  entity_type: TagEntityType;
+  entity_id: number;
+}
+
+export interface HouseholdSettings {
+  private_hospital_cover: boolean | null; // Null when not said, so no surcharge is assumed
+  oldest_age_on_policy: number | null;
+}
+
+export type HealthIncomeTier = 'Base' | 'Tier1' | 'Tier2' | 'Tier3';
+
+// Medicare levy surcharge and private health rebate tier for a financial year
+export interface HealthCoverPosition {
+  financial_year: string;
+  income_for_mls: number;
+  partner_income: number | null;
+  family: boolean;
+  tier: HealthIncomeTier;
+  next_tier_threshold: number | null;
+  medicare_levy_surcharge: number; // Without private hospital cover
+  rebate_percent: number;
+}
+
+export interface MlsTierChange {
+  family: boolean;
+  from: HealthIncomeTier;
+  to: HealthIncomeTier;
+  surcharge_difference: number;
+}
+
+export interface AwardFinding {
+  compensation_record_id?: number;
+  effective_date: string;
+  award_name: string;
+  classification: string;
+  minimum_hourly_rate: number; // Including casual loading for a casual position
+  base_hourly_rate: number;
+  minimum_overtime_multiplier: number;
+  overtime_multiplier: number;
+  base_rate_below_minimum: boolean;
+  overtime_below_minimum: boolean;
+  periods_underpaid: number;
+  estimated_underpayment: number;
+}
+
+export interface AwardComplianceReport {
+  position_id: number;
+  records_checked: number;
+  findings: AwardFinding[];
+  estimated_back_pay: number;
+}
+
+// User-maintained conversion rate for overseas income
+export interface ExchangeRate {
+  id?: number;
+  currency: string;
+  financial_year: string; // e.g., "FY2022-23"
+  rate_to_aud: number; // AUD value of one unit of the currency
+}
+
+// Export with identity removed, for contributing to benchmarks
+export interface AnonymizedProfile {
+  age_band: string; // e.g. "25-34"
+  state: AustralianState;
+  industry: string;
+  highest_qualification: Qualification;
+  standard_weekly_hours: number;
+}
+
+export interface AnonymizedPosition {
+  id?: number;
+  employer: string; // e.g. "Mining employer 2"
+  job_title: string;
+  employment_type: EmploymentType;
+  state?: AustralianState; // Absent when overseas or unknown
+  start_date: Date;
+  end_date?: Date;
+  seniority_level: SeniorityLevel;
+  tools_systems_skills: string[];
+  archived?: boolean;
+}
+
+export interface AnonymizedExport {
+  profile: AnonymizedProfile;
+  positions: AnonymizedPosition[];
+  compensation_records: CompensationRecord[]; // Notes removed
+  weekly_entries: WeeklyCompensationEntry[];
+  yearly_entries: YearlyIncomeEntry[];
+  export_date: Date;
+  version: string;
+}
+
+// Anonymised analysis inputs and the outputs computed from them, attached to calculation bug reports
+export interface DiagnosticBundle {
+  app_version: string;
+  data: AnonymizedExport;
+  exchange_rates: ExchangeRate[];
+  settings: Record<string, unknown>; // Saved settings the calculations read, by key
+  loyalty_tax: LoyaltyTaxAnalysis;
+  earnings_analysis: EarningsAnalysis;
+}
+
+// A value filled in by a schema migration, awaiting user confirmation
+export interface EarningsSeasonality {
+  months_of_data: number;
+  years: number[];
+  months: SeasonalMonth[];
+  strongest_month: number | null;
+  weakest_month: number | null;
+}
+
+export interface SeasonalMonth {
+  month: number;
+  years_observed: number;
+  average_gross: number;
+  average_hours: number;
+  index: number;
+  hours_index: number;
+  above_average_percent: number;
+}
+
+export interface OvertimeProfile {
+  position_id: number;
+  periods_observed: number;
+  periods_with_overtime: number;
+  observed_percent: number;
+  average_hours_per_week: number;
+  implied_frequency: OvertimeFrequency;
+  declared_frequency: OvertimeFrequency | null;
+  declared_hours_per_week: number | null;
+  frequency_steps: number | null;
+  hours_delta: number | null;
+  diverges: boolean;
+  suggested_correction: OvertimeCorrection | null;
+}
+
+export interface OvertimeCorrection {
+  record_id: number;
+  overtime: OvertimeDetails;
+}
+
+export interface ListResult<T> {
+  rows: T[];
+  errors: RowError[];
+}
+
+export interface RowError {
+  table_name: string;
+  row_id: number;
+  message: string;
+}
+
+export interface RepairLog {
+  table_name: string;
+  row_id: number;
+  column_name: string;
+  original: string;
+  repaired_to: string;
+  recovered: boolean;
+}
+
+export interface BackfillWarning {
+  id: number;
+  table_name: string;
+  row_id: number;
+  column_name: string;
+  default_value: string;
+  message: string;
+}
+
+// Result of bringing the database schema up to date on launch
+export enum DatabaseState {
+  Ready = 'Ready',
+  MigrationFailed = 'MigrationFailed', // Rolled back, usable at the previous version
+}
+
+export interface DatabaseStatus {
+  state: DatabaseState;
+  schema_version: number;
+  error?: string;
+  possible_data_rollback?: DataRollback; // Tables with fewer writes than at the last clean shutdown
+}
+
+// Writes to one table, for the settings screen
+export interface TableModified {
+  table_name: string;
+  write_count: number;
+  last_modified?: Date;
+}
+
+// The database looks older than when the app last closed, e.g. a sync tool restored an old copy
+export interface DataRollback {
+  recorded_at: Date;
+  tables: TableRegression[];
+}
+
+export interface TableRegression {
+  table_name: string;
+  expected_write_count: number;
+  actual_write_count: number;
+  writes_missing: number;
+  expected_last_modified?: Date;
+  actual_last_modified?: Date;
+}
+
+// ReadOnly while another running instance holds the database; saves fail until promoted
+export enum AppMode {
+  Writable = 'Writable',
+  ReadOnly = 'ReadOnly',
+}
+
+// The assumptions behind projections, and how far either side the low and high series go
+export interface ProjectionAssumptions {
+  investment_return: number; // Percent a year, after fees
+  return_spread: number; // Percentage points either side
+  salary_growth_spread: number;
+}
+
+export interface ProjectionPoint {
+  years_from_now: number;
+  low: number;
+  expected: number;
+  high: number;
+}
+
+// Super balance each year to age 67, in AUD
+export interface SuperProjection {
+  starting_age: number;
+  starting_balance: number;
+  annual_contributions: number;
+  insurance_premiums: number; // Cover paid from the balance each year
+  salary_growth: number;
+  investment_return: number;
+  points: ProjectionPoint[];
+}
+
+// A position's pay grown at its seniority's market rate
+export interface SalaryProjection {
+  position_id: number;
+  current_salary: number;
+  salary_growth: number;
+  points: ProjectionPoint[];
+}
+
+// How far ahead reminders worked out from positions are shown
+export interface ReminderSettings {
+  horizon_days: number;
+}
+
+// Whether the start-of-year checklist has been run for the current financial year
+export interface RolloverStatus {
+  current_financial_year: string;
+  last_financial_year: string | null;
+  last_run_at: string | null;
+  done_for_current: boolean;
+}
+
+export interface RolloverReport {
+  financial_year: string;
+  closed_financial_year: string;
+  items: RolloverItem[];
+}
+
+export interface RolloverItem {
+  step: 'YearlyPlaceholder' | 'SuperRate' | 'Reconciliation' | 'MissingWeeks';
+  outcome: 'Done' | 'AlreadyDone' | 'Ok' | 'NeedsAttention';
+  message: string;
+  related_entity: RelatedEntity | null;
+}
+
+// A date coming up for a position; the id is stable so a dismissal sticks
+export interface Reminder {
+  id: string;
+  kind: ReminderKind;
+  position_id: number;
+  due_date: string;
+  days_until: number;
+  title: string;
+}
+
+export type ReminderKind = 'ProbationEnding' | 'ContractEnding' | 'WorkAnniversary';
+
+// Scheduled JSON export to a chosen folder
+export enum AutoExportFrequency {
+  Daily = 'Daily',
+  Weekly = 'Weekly',
+}
+
+export interface AutoExportSettings {
+  enabled: boolean;
+  path?: string; // Destination folder
+  frequency: AutoExportFrequency;
+  retention_count: number; // Exports to keep, 0 keeps all
+}
+
+export interface AutoExportStatus {
+  last_attempt?: Date;
+  last_success?: Date;
+  last_file?: string;
+  last_error?: string;
+}
+
+// Event emitted with the error message when a scheduled export fails
+export const AUTO_EXPORT_FAILED_EVENT = 'auto-export-failed';
+
+// Folder watched for payslip CSVs; imports wait for confirmation
+export interface WatchFolderSettings {
+  enabled: boolean;
+  path?: string;
+  pattern: string; // File name glob, e.g. "*.csv"
+}
+
+export interface CsvRowError {
+  line: number; // Header is line 1
+  message: string;
+}
+
+export interface CsvImportPreview {
+  entries: WeeklyCompensationEntry[];
+  errors: CsvRowError[];
+}
+
+export interface PendingWatchedImport {
+  path: string;
+  detected_at: Date;
+  preview: CsvImportPreview;
+}
+
+// What confirming a watched file saved, and the pay periods it left alone as already recorded
+export interface WatchedImportResult {
+  imported_count: number;
+  skipped_period_endings: string[];
+}
+
+// A record that fails the save-time checks, or a warning about it alongside the others
+export enum IssueSeverity {
+  Error = 'Error',
+  Warning = 'Warning',
+}
+
+export interface ValidationIssue {
+  record_type: string;
+  id?: number;
+  message: string;
+  severity: IssueSeverity;
+}
+
+// Emitted with ValidationIssue[] after a position save leaves several permanent roles open-ended
+export const POSITION_WARNINGS_EVENT = 'position-warnings';
+
+// Emitted with a PendingWatchedImport when a new file has been parsed
+export const WATCH_FOLDER_IMPORT_DETECTED_EVENT = 'watch-folder-import-detected';
+// Emitted with the error message when a file was moved to failed/
+export const WATCH_FOLDER_IMPORT_FAILED_EVENT = 'watch-folder-import-failed';
+
+// Importing another tool's CSV through a column mapping
+export enum ImportTarget {
+  Positions = 'Positions',
+  CompensationRecords = 'CompensationRecords',
+  WeeklyEntries = 'WeeklyEntries',
+}
+
+export enum Coercion {
+  Text = 'Text',
+  Integer = 'Integer',
+  Number = 'Number',
+  Money = 'Money', // "$95,000", "95k"
+  Hours = 'Hours', // "38 hrs"
+  Percent = 'Percent',
+  Date = 'Date', // Day first
+  Boolean = 'Boolean',
+  List = 'List',
+}
+
+export interface ColumnMapping {
+  source: string; // Header in the file
+  field: string; // Dotted for nested fields, e.g. "overtime.rate_multiplier"
+  coercion?: Coercion; // Defaults to the field's usual type
+  default_value?: string;
+}
+
+export interface ImportMapping {
+  name: string;
+  target: ImportTarget;
+  columns: ColumnMapping[];
+  builtin?: boolean;
+}
+
+export interface CellError {
+  line: number; // Header is line 1
+  column?: string; // Absent when the whole row is at fault
+  value?: string;
+  message: string;
+}
+
+// A record's annual pay as it lands each pay period
+export interface PayCycleBreakdown {
+  position_id: number;
+  currency: string;
+  frequency: PayslipFrequency;
+  periods_per_year: number; // Average, so 26.09 fortnights
+  extra_period_every_years: number | null; // How often a 53rd week or 27th fortnight falls
+  annual: PayCycleAmounts;
+  per_period: PayCycleAmounts;
+}
+
+export interface PayCycleAmounts {
+  gross: number;
+  tax: number; // Income tax, Medicare levy and HELP
+  salary_sacrifice: number;
+  net: number;
+  employer_super: number; // On top of gross
+}
+
+// A record's year at 0.8 of its ordinary hours against the year as it is
+export interface FourDayWeekImpact {
+  position_id: number;
+  currency: string;
+  fte: number;
+  weekly_hours_gained: number;
+  time_gained_percent: number;
+  gross_reduction_percent: number;
+  net_reduction_percent: number; // Smaller than the gross reduction after tax
+  current: PayCycleAmounts; // A year
+  four_day_week: PayCycleAmounts;
+}
+
+export interface EmployerSuperBreakdown {
+  position_id: number;
+  effective_date: string;
+  currency: string;
+  ordinary_time_earnings: number; // Base pay and ordinary-time allowances
+  excluded_earnings: number; // Overtime, other allowances and bonuses
+  contribution_rate: number;
+  employer_super: number;
+}
+
+export interface WeekEndingShift {
+  entry_id: number;
+  from: string;
+  to: string;
+  days: number; // Negative when moved earlier
+}
+
+export interface WeekEndingNormalization {
+  position_id: number;
+  target_weekday: Weekday;
+  shifts: WeekEndingShift[];
+  applied: boolean; // False for a dry run
+}
+
+export interface QualityDeduction {
+  reason: string;
+  points: number;
+}
+
+export interface DataQuality {
+  score: number; // Out of 100
+  deductions: QualityDeduction[];
+}
+
+export interface PositionDataQuality {
+  position_id: number;
+  employer_name: string;
+  job_title: string;
+  quality: DataQuality;
+}
+
+// One position's pay entries in aggregate, for its detail screen
+export interface PositionWeeklyStats {
+  position_id: number;
+  entry_count: number;
+  first_week_ending?: Date;
+  last_week_ending?: Date;
+  weeks_logged: number; // A fortnightly payslip counts as two
+  gap_count: number;
+  total_gross: number;
+  total_tax_withheld: number;
+  total_net: number;
+  total_super: number;
+  average_weekly_hours: number;
+  overtime_share: number; // Of hours worked, 0-1
+}
+
+// The figures from a compensation record worth showing at a glance
+export interface CompensationRecordSummary {
+  id?: number;
+  effective_date: Date;
+  pay_type: PayType;
+  base_rate: number;
+  annual_gross: number; // Base, overtime, allowances and bonuses
+  currency: string;
+}
+
+// Everything the position detail screen summarises, in one call
+export interface PositionStatistics {
+  weekly: PositionWeeklyStats;
+  compensation_record_count: number;
+  latest_record?: CompensationRecordSummary;
+}
+
+export interface DataQualityReport {
+  overall_score: number;
+  positions: PositionDataQuality[]; // Lowest score first
+}
+
+export interface AnalysisSnapshot {
+  id?: number;
+  taken_at: string;
+  total_compensation: number;
+  income_percentile: number;
+  loyalty_tax_annual: number;
+  loyalty_tax_cumulative: number;
+  projected_super: number; // Employer contributions for the financial year it was taken in
+}
+
+// Later snapshot minus the earlier one
+export interface SnapshotComparison {
+  earlier: AnalysisSnapshot;
+  later: AnalysisSnapshot;
+  days_between: number;
+  total_compensation_change: number;
+  income_percentile_change: number;
+  loyalty_tax_annual_change: number;
+  loyalty_tax_cumulative_change: number;
+  projected_super_change: number;
+}
+
+export type WithholdingStatus = 'AsExpected' | 'OverWithheld' | 'UnderWithheld';
+
+export interface WithholdingCheck {
+  position_id: number;
+  effective_date: string;
+  financial_year: string;
+  claims_tax_free_threshold: boolean;
+  taxable_income: number;
+  expected_withholding: number;
+  tax_withheld: number;
+  difference: number; // Withheld minus expected
+  status: WithholdingStatus;
+}
+
+// How exposed current income is to losing a job or overtime and bonuses drying up, 0-100
+export interface IncomeRiskAssessment {
+  score: number;
+  level: IncomeRiskLevel;
+  annual_income: number;
+  factors: IncomeRiskFactor[];
+}
+
+export interface IncomeRiskFactor {
+  factor: string;
+  points: number;
+  max_points: number;
+  detail: string;
+}
+
+export type IncomeRiskLevel = 'Low' | 'Moderate' | 'High';
+
+// A current position that goes against a preference stated in the profile
+export interface PreferenceMismatch {
+  position_id: number;
+  preference: 'FifoTolerance' | 'OvertimeAppetite';
+  description: string;
+}
+
+// A compensation record broken into annual components, converted to AUD
+export interface IncomeComposition {
+  position_id: number;
+  effective_date: string;
+  currency: string;
+  exchange_rate_to_aud: number | null;
+  total_compensation: number;
+  original_total_compensation: number;
+  components: IncomeComponent[];
+  non_cash_benefits: number;
+  total_package_value: number;
+  original_total_package_value: number;
+  warnings?: AnalysisWarning[]; // Such as AUD amounts left at nothing for want of a rate
+}
+
+export interface IncomeComponent {
+  kind: 'BaseSalary' | 'Overtime' | 'Allowances' | 'Bonuses' | 'EmployerSuper';
+  annual_amount: number;
+  original_annual_amount: number;
+  percentage: number;
+}
+
+// An offer's package against the current one, differences as offer less current
+export interface OfferComparison {
+  current: IncomeComposition;
+  offer: IncomeComposition;
+  current_take_home: number;
+  offer_take_home: number;
+  total_compensation_difference: number;
+  total_package_value_difference: number;
+  take_home_difference: number;
+  mls_tier_change?: MlsTierChange | null; // When the offer moves the household to another surcharge tier
+}
+
+// A named what-if, saved with the pay it was compared against
+export interface Scenario {
+  id?: number;
+  name: string;
+  position_id: number | null;
+  baseline: CompensationRecord;
+  offer: CompensationRecord;
+  result: OfferComparison;
+  created_at: string;
+}
+
+export interface ScenarioRerun {
+  scenario: Scenario;
+  original: OfferComparison;
+  current: OfferComparison;
+  warnings: string[]; // e.g. the position was deleted and the saved pay was used
+}
+
+// A saved role setup that new positions can start from
+export interface PositionTemplate {
+  id?: number;
+  name: string;
+  fields: TemplateFields;
+  created_at: string;
+}
+
+// Employer, dates, achievements, notes, bonuses, tax withheld, equity, packaging,
+// non-cash benefits and award links are never carried
+export interface TemplateFields {
+  job_title: string;
+  employment_type: EmploymentType;
+  location: string;
+  seniority_level: SeniorityLevel;
+  core_responsibilities: string;
+  tools_systems_skills: string[];
+  industry: string | null;
+  week_ending_day: Weekday | null;
+  notice_period_weeks: number | null;
+  leave_loading_percent: number | null;
+  probation_months: number | null;
+  compensation: TemplateCompensation | null; // From the latest record
+}
+
+export interface TemplateCompensation {
+  pay_type: PayType;
+  base_rate: number; // 0 unless rates were kept
+  standard_weekly_hours: number;
+  overtime: OvertimeDetails;
+  allowances: Allowance[]; // Amounts 0 unless rates were kept
+  super_contributions: SuperDetails;
+  payslip_frequency: PayslipFrequency | null;
+  currency: string;
+  jurisdiction: Jurisdiction;
+  claims_tax_free_threshold: boolean;
+}
+
+export interface TemplateOverrides {
+  employer_name: string;
+  start_date: string;
+  job_title?: string | null;
+  location?: string | null;
+  base_rate?: number | null;
+}
+
+// A state's long service leave rules; service in a portable industry counts across employers
+export interface LslRule {
+  state: AustralianState;
+  weeks_per_year: number;
+  full_entitlement_years: number;
+  pro_rata_years: number;
+  portable_industries: string[];
+}
+
+export type LslEntitlement = 'None' | 'ProRata' | 'Full';
+
+export interface LslTenure {
+  position_ids: number[];
+  employer_name: string; // The industry for a portable scheme
+  state: AustralianState;
+  portable: boolean;
+  current: boolean;
+  years_of_service: number;
+  accrued_weeks: number;
+  weekly_rate: number;
+  accrued_value: number;
+  entitlement: LslEntitlement;
+  payable_on_leaving: number;
+  next_threshold?: LslEntitlement;
+  next_threshold_date?: string; // Only while still employed
+}
+
+export interface LslStatus {
+  tenures: LslTenure[];
+  total_accrued_weeks: number;
+  total_accrued_value: number;
+  total_payable_on_leaving: number;
+}
+
+// Tax on a financial year's income from every job together, in AUD
+export interface CombinedTaxPosition {
+  financial_year: string;
+  sources: TaxedIncomeSource[];
+  total_gross_income: number;
+  total_taxable_income: number; // After deductions
+  deductions: number; // Income protection premiums paid outside super
+  tax_payable: number; // On the combined taxable income, with any HELP repayment and surcharge
+  medicare_levy_surcharge: number; // Only when the household settings say there's no hospital cover
+  family_income: number | null;
+  tax_payable_if_separate: number; // Each source taxed as if it were the only income
+  total_withheld: number;
+  net_position: number; // Withheld minus payable; negative is a bill
+  warnings?: AnalysisWarning[];
+}
+
+export interface TaxedIncomeSource {
+  position_id?: number; // Absent for income not linked to a position
+  employer_name: string;
+  method: TaxedIncomeMethod;
+  gross_income: number;
+  taxable_income: number;
+  tax_withheld: number;
+}
+
+export type TaxedIncomeMethod = 'YearlyEntry' | 'Payslips' | 'Estimated';
+
+// Everything a financial year's tax return needs from each job, in AUD
+export interface TaxTimeSummary {
+  financial_year: string;
+  sources: TaxTimeIncome[];
+  total_gross_income: number;
+  total_tax_withheld: number;
+  employer_super: number;
+  salary_sacrifice: number;
+  reportable_super: number; // Employer contributions and salary sacrifice together
+  reportable_fringe_benefits: number;
+  taxable_allowances: number;
+  warnings?: AnalysisWarning[];
+}
+
+export interface TaxTimeIncome {
+  position_id?: number; // Absent for income not linked to a position
+  employer_name: string;
+  method: TaxedIncomeMethod; // Never Estimated; jobs with nothing entered are left out
+  gross_income: number;
+  tax_withheld: number;
+  employer_super: number;
+  salary_sacrifice: number;
+  reportable_fringe_benefits: number;
+  taxable_allowances: number;
+}
+
+// The salary to ask for to take home a target amount a year, after the year's tax
+export interface TargetNetEstimate {
+  financial_year: string;
+  target_net: number;
+  gross_salary: number;
+  employer_super: number; // At the super guarantee rate, on top of the salary
+  total_package: number;
+  tax_payable: number; // Income tax less LITO, plus the Medicare levy
+  help_repayment: number;
+  average_tax_rate: number; // Percent of gross, HELP included
+  marginal_tax_rate: number; // Percent on the next dollar, HELP included
+}
+
+// A car packaged through a novated lease against buying it from take-home pay, a year
+export interface NovatedLeaseAnalysis {
+  vehicle_cost: number;
+  lease_term_months: number;
+  annual_km: number;
+  marginal_rate: number; // Percent, including the Medicare levy
+  gst_credit: number;
+  amount_financed: number;
+  residual_value: number;
+  annual_lease_cost: number;
+  annual_running_costs: number;
+  fbt_taxable_value: number;
+  pre_tax_contribution: number;
+  post_tax_contribution: number; // Employee contribution method, cancelling the FBT
+  income_tax_saved: number;
+  annual_cost_with_lease: number;
+  annual_cost_without_lease: number;
+  annual_net_benefit: number;
+  non_cash_benefit: NonCashBenefit; // The saving, to add to the compensation record
+}
+
+// The gross pay and salary a net weekly figure implies, with a band either side
+export interface ReverseBaseEstimate {
+  financial_year: string;
+  net_weekly: number;
+  claims_tax_free_threshold: boolean;
+  gross_weekly: number;
+  tax_withheld_weekly: number;
+  annual_base: number;
+  employer_super: number; // At the super guarantee rate
+  total_package: number; // Base and super
+  annual_base_low: number;
+  annual_base_high: number;
+}
+
+// A backdated pay rise recomputed over the pay periods it covers
+export interface BackPayCalculation {
+  position_id: number;
+  from_date: string;
+  to_date: string;
+  old_hourly_rate: number;
+  new_hourly_rate: number;
+  periods: BackPayPeriod[];
+  estimated_periods: number;
+  total_back_pay: number;
+  estimated_withholding: number; // On the lump sum, spread over the year's pay periods
+  net_back_pay: number;
+}
+
+export interface BackPayPeriod {
+  period_start: string;
+  period_end: string;
+  hours_ordinary: number;
+  hours_overtime: number;
+  old_gross: number;
+  new_gross: number;
+  difference: number;
+  estimated: boolean; // No payslip, so standard hours were assumed
+}
+
+// When a role's next bonus is likely, from its bonus history, and whether to hold off resigning
+export interface ResignationTimingAdvice {
+  position_id: number;
+  bonuses_on_record: number; // Distinct award dates
+  typical_interval_months?: number;
+  predicted_bonus_date?: string;
+  days_until_bonus?: number;
+  bonus_at_risk: number; // What was paid on the last award date
+  window_days: number;
+  wait_for_bonus: boolean;
+  advice: string;
+}
+
+// Notice, leave paid out and the final pay for resigning on a given day
+export interface ResignationPlan {
+  position_id: number;
+  notice_given: string;
+  notice_period_weeks: number;
+  notice_from_contract: boolean; // Otherwise the National Employment Standards minimum
+  earliest_last_day: string;
+  proposed_last_day: string;
+  annual_leave_hours: number;
+  annual_leave_estimated: boolean; // Accrued since the last work anniversary
+  annual_leave_value: number;
+  leave_loading: number;
+  long_service_leave?: LslTenure;
+  long_service_leave_value: number;
+  final_pay_gross: number; // Leave paid out, on top of the last period's ordinary pay
+  estimated_withholding: number;
+  final_pay_net: number;
+  tax_treatment: string;
+}
+
+// Super paid on a position's payslips against its contracted rate, in the payslips' currency
+export interface SuperComplianceReport {
+  position_id: number;
+  financial_year: string;
+  periods_checked: number;
+  ordinary_time_earnings: number;
+  expected_super: number;
+  super_paid: number;
+  underpayment: number; // Shortfalls in the quarters underpaid
+  quarters: SuperQuarterCompliance[];
+}
+
+export interface SuperQuarterCompliance {
+  quarter: number; // 1 is July to September
+  start: string;
+  end: string;
+  ordinary_time_earnings: number;
+  expected_super: number;
+  super_paid: number;
+  shortfall: number; // Expected minus paid; negative when overpaid
+  underpaid: boolean;
+}
+
+export interface WeeklyEntryPatch {
+  overtime_rate_multiplier?: number;
+  super_rate?: number; // Percent of ordinary-hours pay
+}
+
+export interface BulkEditResult {
+  id: number;
+  updated: boolean;
+  error: string | null; // Why the row was left as it was
+}
+
+export interface ImportResult {
+  success: boolean;
+  profile_imported: boolean;
+  positions_count: number;
+  compensation_count: number;
+  weekly_count: number;
+  yearly_count: number;
+  exchange_rate_count: number;
+  award_rate_count: number;
+  scenario_count: number;
+  template_count: number;
+  insurance_policy_count: number;
+  partner_income_count: number;
+  tag_count: number;
+  warnings: string[]; // Imported anyway, e.g. a checksum that doesn't match
+}
+
+export interface MappedImportResult {
+  target: ImportTarget;
+  dry_run: boolean;
+  rows: number;
+  positions: Position[];
+  compensation_records: CompensationRecord[];
+  weekly_entries: WeeklyCompensationEntry[];
+  errors: CellError[];
+  imported_count: number; // 0 while any row has errors
+}
+
 // Union type for income entries in the Career Ledger
 export type IncomeEntry =
   | { type: 'weekly'; data: WeeklyCompensationEntry }
@@ -254,6 +1322,118 @@ export type IncomeEntry =
   | { type: 'position'; data: Position };
 
 // Analysis Types
+export enum OvertimeEffectDirection {
+  NetPositive = 'NetPositive',
+  NetNegative = 'NetNegative',
+  Neutral = 'Neutral',
+}
+
+// Overtime a week for a position's gross pay to reach a target, in the record's currency
+export interface OvertimeTargetResult {
+  position_id: number;
+  target_annual: number;
+  income_without_overtime: number; // Base, allowances and bonuses a year
+  overtime_hourly_rate: number;
+  overtime_hours_per_week: number; // 0 when the target is met without overtime
+  total_weekly_hours: number;
+  weekly_hours_ceiling: number;
+  unsustainable: boolean; // More hours than the ceiling
+  marginal_tax_rate_without_overtime: number; // Percent, HELP included
+  marginal_tax_rate_at_target: number;
+  crosses_tax_bracket: boolean;
+  net_per_overtime_hour_at_target: number;
+}
+
+export interface OvertimeRateEffect {
+  position_id: number;
+  effective_date: Date;
+  currency: string; // Rates are in the record's currency
+  overtime_hours_per_week: number;
+  rate_multiplier: number;
+  hourly_rate_without_overtime: number;
+  hourly_rate_with_overtime: number;
+  difference: number;
+  direction: OvertimeEffectDirection;
+}
+
+// A role's annual base growth graded against inflation, super guarantee rises and the market
+export interface ProgressionScorecard {
+  position_id: number;
+  from: string;
+  to: string; // The role's end, or today
+  years: number;
+  annual_base_growth_percent: number;
+  grades: BenchmarkGrade[];
+}
+
+export interface BenchmarkGrade {
+  benchmark: 'Cpi' | 'SuperGuarantee' | 'Market';
+  benchmark_growth_percent: number;
+  actual_growth_percent: number; // Package growth for the SuperGuarantee benchmark
+  grade: 'Green' | 'Amber' | 'Red';
+}
+
+export interface RealPayCut {
+  financial_year: string;
+  previous_annual_pay: number; // AUD base pay at the end of the previous year
+  current_annual_pay: number;
+  nominal_change_percent: number;
+  cpi_percent: number;
+  real_change_percent: number; // Nominal change less CPI
+  purchasing_power_lost: number; // Negative when pay outpaced CPI
+}
+
+// Average tax rate changes split into bracket creep and real income growth
+export interface BracketCreepReport {
+  years: BracketCreepYear[];
+  total_creep_points: number;
+  total_real_growth_points: number;
+  total_creep_tax: number;
+}
+
+export interface BracketCreepYear {
+  financial_year: string;
+  income: number;
+  average_tax_rate: number; // Percent, income tax only
+  previous_average_tax_rate: number;
+  cpi_percent: number;
+  creep_points: number; // Negative when thresholds rose faster than CPI
+  real_growth_points: number;
+  creep_tax: number;
+}
+
+// Current pay against the median for the profile's gender in its industry
+export interface GenderBenchmark {
+  gender: Gender;
+  median: number;
+  percentile: number;
+}
+
+// Whether comparisons against demographic groups are made at all
+export interface DemographicSettings {
+  comparisons_enabled: boolean;
+}
+
+// Where a year's earnings sat against the benchmark median carried back to that year
+export interface PercentilePoint {
+  financial_year: string;
+  annual_earnings: number;
+  benchmark_median: number;
+  percentile: number;
+}
+
+// Wage growth by year, to carry the benchmark medians back from the year they're for
+export interface WageIndex {
+  benchmark_year: string; // e.g. "FY2023-24"
+  growth: WageGrowth[];
+  default_growth_percent: number; // For years not listed
+}
+
+export interface WageGrowth {
+  financial_year: string;
+  growth_percent: number;
+}
+
 export interface EarningsAnalysis {
   current_total_compensation: number;
   current_effective_hourly_rate: number;
@@ -263,24 +1443,56 @@ export interface EarningsAnalysis {
   loyalty_tax_cumulative: number;
   years_since_last_change: number;
   earnings_over_time: EarningsSnapshot[];
+  percentile_over_time: PercentilePoint[];
+  gender_benchmark: GenderBenchmark | null; // Only with a gender given and comparisons on
   hours_vs_earnings: HoursEarningsPoint[];
   super_trajectory: SuperSnapshot[];
   super_summary: SuperSnapshot;
   insights: EarningsInsight[];
+  current_positions: PositionSummary[]; // Every open-ended position, the one analysed first
+  upcoming_positions: UpcomingPosition[]; // Accepted but not started; left out of the figures
+  warnings: AnalysisWarning[];
 }
 
+// An assumption a calculation made in place of missing or unusable data
+export interface AnalysisWarning {
+  code: AnalysisWarningCode;
+  message: string;
+  related_entity: RelatedEntity | null;
+}
+
+// Stable across releases, for wording of our own
+export type AnalysisWarningCode =
+  | 'NoProfile'
+  | 'PayEstimatedFromLevel'
+  | 'MissingExchangeRate'
+  | 'OpenEndedTenure'
+  | 'ShortTenureSkipped'
+  | 'PartialYearProjected'
+  | 'TaxNotEstimated';
+
+export type RelatedEntity =
+  | { Position: number }
+  | { Employer: string }
+  | { FinancialYear: string };
+
 export interface EarningsSnapshot {
   date: Date;
   base_annual: number;
   actual_annual: number;
   total_with_super: number;
   effective_hourly_rate: number;
+  jurisdiction: Jurisdiction;
   bonuses_annual: number;
   allowances_annual: number;
+  col_adjusted_annual?: number; // Actual earnings at national-average prices, when the location is known
 }
 
+// How per-year figures are bucketed; commands default to Financial
+export type YearBasis = 'Calendar' | 'Financial';
+
 export interface HoursEarningsPoint {
-  year: number;
+  label: string; // "2023-24" or "2023", depending on the YearBasis
   total_hours_worked: number;
   total_earnings: number;
   overtime_percentage: number;
@@ -293,12 +1505,81 @@ export interface SuperSnapshot {
   total_super_balance: number;
 }
 
+// Salary sacrifice that still fits under the concessional cap
+export interface SacrificeHeadroom {
+  financial_year: string;
+  concessional_cap: number;
+  employer_contributions: number;
+  employer_contributions_method: SuperContributionMethod;
+  existing_salary_sacrifice: number;
+  taxable_income: number; // Estimated, after existing sacrifice
+  headroom: number;
+  div293_on_headroom: number; // Extra Division 293 tax from sacrificing the headroom
+  estimated_tax_saving: number; // Income tax avoided less contributions tax and Division 293
+}
+
+// What sacrificing a fixed amount is worth at one income
+export interface SacrificeBenefitPoint {
+  income: number; // Before the sacrifice
+  income_tax_saving: number;
+  contributions_tax: number;
+  div293: number; // Division 293 added by the sacrifice
+  net_benefit: number;
+  benefit_per_dollar: number;
+}
+
+// Before-tax super contributions for a financial year, in AUD
+export interface ConcessionalContributions {
+  financial_year: string;
+  employer_contributions: number;
+  employer_contributions_method: SuperContributionMethod;
+  salary_sacrifice: number; // Reportable super from income statements when entered, else the records
+  additional_contributions: number;
+  total: number;
+}
+
+export interface SuperCapStatus {
+  contributions: ConcessionalContributions;
+  concessional_cap: number;
+  carry_forward_available: number; // Unused cap from the previous five years, 0 when not eligible
+  carry_forward_used: number;
+  headroom: number;
+  excess: number; // Over the cap after carry-forward
+}
+
+// A bonus taken as cash against sacrificed into super, as far as the cap allows
+export interface BonusSacrificeAnalysis {
+  financial_year: string;
+  bonus_amount: number;
+  marginal_rate: number; // Percent, including Medicare
+  cap_headroom: number;
+  has_cap_room: boolean;
+  amount_sacrificed: number;
+  cash_after_tax: number;
+  sacrifice_after_tax: number; // Into super after contributions tax, plus the rest as cash
+  net_benefit: number;
+}
+
+export enum SuperContributionMethod {
+  WeeklyEntries = 'WeeklyEntries', // Summed from payslips
+  Estimated = 'Estimated', // SG rate on base salary
+}
+
+export interface SuperContributionSummary {
+  financial_year: string;
+  employer_contributions: number; // AUD
+  method: SuperContributionMethod;
+  periods_recorded: number;
+  sg_rate?: number; // Percent used for the estimate
+}
+
 export interface EarningsInsight {
   category: InsightCategory;
   title: string;
   description: string;
   confidence_level: number;
   data_points: string[];
+  position_ids?: number[]; // Positions the insight is about, to link to them
 }
 
 export interface LoyaltyTaxAnalysis {
@@ -307,6 +1588,126 @@ export interface LoyaltyTaxAnalysis {
   annual_loyalty_tax: YearlyLoyaltyTax[];
   cumulative_loyalty_tax: number;
   confidence_level: number;
+  warnings: AnalysisWarning[];
+}
+
+// A promotion at the same employer: the position ends on end_date, the new one starts the next day
+export interface PositionTransition {
+  end_date: Date;
+  job_title: string;
+  seniority_level: SeniorityLevel;
+  employment_type?: EmploymentType; // Kept from the old position when omitted
+  location?: string;
+  core_responsibilities: string;
+  copy_skills: boolean;
+  carry_over_compensation: boolean;
+  raise_percent?: number;
+}
+
+export interface PositionTransitionResult {
+  old_position_id: number;
+  new_position_id: number;
+  compensation_record_id?: number;
+}
+
+// A compensation record as it was before a change, kept in the change log
+export interface RecordChange {
+  id?: number;
+  record_id: number;
+  changed_at: string;
+  change: string; // e.g. "Upgraded to exact"
+  before: CompensationRecord;
+}
+
+// A fuzzy record and how much of the career's earnings rest on it
+export interface FuzzyRecordWeight {
+  record: CompensationRecord;
+  employer_name: string;
+  annual_total_compensation: number; // AUD
+  years_in_effect: number;
+  earnings_weight: number; // AUD earned while the record was in effect
+  share_of_earnings: number; // Percent of the earnings weight of every record
+}
+
+// Changes to a compensation record, copied for a raise or corrected; omitted fields are kept
+export interface CompensationRecordOverrides {
+  entry_type?: CompensationEntryType;
+  pay_type?: PayType;
+  base_rate?: number;
+  standard_weekly_hours?: number;
+  overtime?: OvertimeDetails;
+  allowances?: Allowance[];
+  bonuses?: Bonus[];
+  super_contribution_rate?: number;
+  super_additional_contributions?: number;
+  super_salary_sacrifice?: number;
+  tax_withheld?: number;
+  confidence_score?: number;
+  notes?: string;
+}
+
+// A position's records oldest first, with what changed each time
+export interface RecordLineageEntry {
+  record: CompensationRecord;
+  annual_base: number;
+  annual_package: number; // Base, overtime, allowances and bonuses
+  change_from_previous?: RecordDelta;
+}
+
+export interface RecordDelta {
+  days_since_previous: number;
+  base_rate_change: number;
+  annual_base_change: number;
+  annual_base_change_percent: number;
+  annual_package_change: number;
+  super_rate_change: number; // Percentage points
+}
+
+// A position's allowances for a financial year, split by taxable status
+export interface AllowanceSummary {
+  position_id: number;
+  financial_year: string;
+  effective_date: Date; // Of the compensation record used
+  currency: string;
+  taxable_total: number;
+  non_taxable_total: number;
+  allowances: AllowanceBreakdown[];
+}
+
+export interface AllowanceBreakdown {
+  name: string;
+  amount: number;
+  frequency: AllowanceFrequency;
+  taxable: boolean;
+  annual_amount: number;
+}
+
+export interface PromotionVelocity {
+  industry: string;
+  typical_years_per_level: number;
+  levels_gained: number;
+  average_years_per_level?: number; // Absent until there has been a promotion
+  stints: LevelStint[];
+  insights: EarningsInsight[];
+}
+
+// Time and AUD earnings in one industry across the career
+export interface IndustryExposure {
+  industry: string;
+  position_count: number;
+  years: number;
+  share_of_years: number; // Percentage
+  total_earnings: number;
+  share_of_earnings: number; // Percentage
+}
+
+// Continuous time at one seniority level, across positions
+export interface LevelStint {
+  seniority_level: SeniorityLevel;
+  start_date: Date;
+  end_date?: Date;
+  years: number;
+  stalled: boolean;
 }
 
 export interface TenureBlock {
@@ -331,10 +1732,74 @@ export interface YearlyLoyaltyTax {
   missed_opportunities: string[];
 }
 
+// Career earnings had each employer's base pay grown at the market rate, beside the actual earnings
+export interface CounterfactualEarnings {
+  years: CounterfactualYear[];
+  actual_total: number;
+  counterfactual_total: number;
+  foregone_total: number; // Negative when pay outgrew the market
+}
+
+export interface CounterfactualYear {
+  year: number;
+  actual: number;
+  counterfactual: number;
+  cumulative_actual: number;
+  cumulative_counterfactual: number;
+}
+
 // Resume Export Types
+export interface QualificationRoi {
+  current_qualification: Qualification;
+  cost: number;
+  study_years: number;
+  current_annual_pay: number;
+  foregone_earnings: number;
+  total_investment: number;
+  expected_salary_uplift: number;
+  uplift_is_estimate: boolean;
+  market_growth_percent: number;
+  payback_years: number | null;
+  ten_year_net_benefit: number;
+}
+
+// Earnings over a whole working life, to date and projected to retirement. Real figures are
+// in today's dollars.
+export interface LifetimeEarningsProjection {
+  current_age: number;
+  retirement_age: number;
+  current_annual_earnings: number;
+  salary_growth: number; // Percent a year, before promotions
+  inflation: number; // Percent a year assumed for the future
+  historical_nominal: number;
+  historical_real: number;
+  projected_nominal: number;
+  projected_real: number;
+  lifetime_nominal: number;
+  lifetime_real: number;
+  points: LifetimeEarningsPoint[]; // A year each from next year to retirement
+}
+
+export interface LifetimeEarningsPoint {
+  age: number;
+  nominal: number;
+  real: number;
+  seniority_level: SeniorityLevel;
+}
+
+export interface SkillRecency {
+  skill: string;
+  last_used_year: number;
+  current: boolean;
+  positions_used: number;
+  years_used: number;
+  score: number;
+}
+
 export interface ResumeExport {
   profile_summary: ProfileSummary;
   career_timeline: ResumePosition[];
+  upcoming_positions: ResumePosition[]; // Accepted but not yet started
   achievements: string[];
   skills_and_tools: string[];
   compensation_summary: CompensationSummary;